@@ -106,6 +106,12 @@ enum Command {
         /// Flag that allows to roll back already executed blocks. It's ultra dangerous and required only for fixing external nodes.
         #[arg(long)]
         allow_executed_block_reversion: bool,
+        /// Only prints the data that would be removed by the rollback, without modifying anything.
+        #[arg(long)]
+        dry_run: bool,
+        /// Displays the rollback plan as a JSON object, so that it is machine-readable.
+        #[arg(long, requires = "dry_run")]
+        json: bool,
     },
 
     /// Clears failed L1 transactions.
@@ -350,8 +356,10 @@ async fn main() -> anyhow::Result<()> {
             rollback_vm_runners_cache,
             rollback_snapshots,
             allow_executed_block_reversion,
+            dry_run,
+            json,
         } => {
-            if !rollback_tree && rollback_postgres {
+            if !rollback_tree && rollback_postgres && !dry_run {
                 println!("You want to roll back Postgres DB without rolling back tree.");
                 println!(
                     "If the tree is not yet rolled back to this L1 batch, then the only way \
@@ -367,14 +375,16 @@ async fn main() -> anyhow::Result<()> {
             }
 
             if allow_executed_block_reversion {
-                println!("You want to roll back already executed blocks. It's impossible to restore them for the main node");
-                println!("Make sure you are doing it ONLY for external node");
-                println!("Are you sure? Print y/n");
+                if !dry_run {
+                    println!("You want to roll back already executed blocks. It's impossible to restore them for the main node");
+                    println!("Make sure you are doing it ONLY for external node");
+                    println!("Are you sure? Print y/n");
 
-                let mut input = [0u8];
-                io::stdin().read_exact(&mut input).await.unwrap();
-                if input[0] != b'y' && input[0] != b'Y' {
-                    std::process::exit(0);
+                    let mut input = [0u8];
+                    io::stdin().read_exact(&mut input).await.unwrap();
+                    if input[0] != b'y' && input[0] != b'Y' {
+                        std::process::exit(0);
+                    }
                 }
                 block_reverter.allow_rolling_back_executed_batches();
             }
@@ -428,9 +438,19 @@ async fn main() -> anyhow::Result<()> {
                 }
             }
 
-            block_reverter
-                .roll_back(L1BatchNumber(l1_batch_number))
-                .await?;
+            let l1_batch_number = L1BatchNumber(l1_batch_number);
+            if dry_run {
+                let plan = block_reverter.plan_rollback(l1_batch_number).await?;
+                if json {
+                    println!("{}", serde_json::to_string(&plan)?);
+                } else {
+                    println!("Rollback plan: {plan:#?}");
+                }
+                return Ok(());
+            }
+
+            block_reverter.roll_back(l1_batch_number).await?;
+            block_reverter.verify_rollback(l1_batch_number).await?;
         }
         Command::ClearFailedL1Transactions => {
             block_reverter.clear_failed_l1_transactions().await?;
@@ -6,9 +6,11 @@ use clap::Parser;
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_core_leftovers::temp_config_store::read_yaml_repr;
 use zksync_dal::{custom_genesis_export_dal::GenesisState, ConnectionPool, Core, CoreDal};
-use zksync_node_genesis::{make_genesis_batch_params, utils::get_deduped_log_queries};
+use zksync_node_genesis::{
+    make_genesis_batch_params, manifest::GenesisManifest, utils::get_deduped_log_queries,
+};
 use zksync_protobuf_config::encode_yaml_repr;
-use zksync_types::{url::SensitiveUrl, StorageLog};
+use zksync_types::{system_contracts::get_system_smart_contracts, url::SensitiveUrl, StorageLog};
 
 #[derive(Debug, Parser)]
 #[command(name = "Custom genesis export tool", author = "Matter Labs")]
@@ -17,6 +19,11 @@ struct Args {
     #[arg(short, long)]
     database_url: Option<String>,
 
+    /// Path to a JSON or CSV manifest with genesis allocations. If specified, the genesis state is built
+    /// from system contracts and the manifest instead of being exported from the database.
+    #[arg(short, long, conflicts_with = "database_url")]
+    manifest_path: Option<PathBuf>,
+
     /// Output file path.
     #[arg(short, long, default_value = "genesis_export.bin")]
     output_path: PathBuf,
@@ -33,10 +40,12 @@ struct Args {
 ///     * `database_url` - URL to the PostgreSQL database.
 ///     * `output` - Path to the output file.
 ///     * `genesis_config_path` - Path to the `genesis.yaml` configuration file, which will be used to set up a new chain (located in the `file_based` directory).
+///     * `manifest_path` - Path to a JSON / CSV manifest with genesis allocations; used instead of `database_url`.
 ///
 /// Given the inputs above, `custom_genesis_export` will perform the following:
 ///     * Read storage logs, and factory dependencies; filter out those related to the system context,
-///       and save the remaining data to the output file.
+///       and save the remaining data to the output file. If a manifest is provided, storage logs and factory dependencies
+///       are produced from the system contracts and the validated manifest allocations instead.
 ///     * Calculate the new `genesis_root_hash`, `rollup_last_leaf_index`, and `genesis_commitment`, then update these
 ///       in-place in the provided `genesis.yaml`. Additionally, the tool will add a `custom_genesis_state_path` property
 ///       pointing to the genesis export.
@@ -55,35 +64,26 @@ async fn main() -> anyhow::Result<()> {
         args.output_path.canonicalize()?.display(),
     );
 
-    println!("Connecting to source database...");
-
-    let db_url = args.database_url.or_else(|| std::env::var("DATABASE_URL").ok()).expect("Specify the database connection string in either a CLI argument or in the DATABASE_URL environment variable.");
-    // we need only 1 DB connection at most for data export
-    let connection_pool_builder =
-        ConnectionPool::<Core>::builder(SensitiveUrl::from_str(db_url.as_str())?, 1);
-    let connection_pool = connection_pool_builder.build().await?;
-
-    let mut storage = connection_pool.connection().await?;
-    let mut transaction = storage.start_transaction().await?;
-
-    println!("Connected to source database.");
-
-    let storage_logs = transaction
-        .custom_genesis_export_dal()
-        .get_storage_logs()
-        .await?;
-    let factory_deps = transaction
-        .custom_genesis_export_dal()
-        .get_factory_deps()
-        .await?;
-
-    transaction.commit().await?;
+    let mut genesis_config = read_yaml_repr::<zksync_protobuf_config::proto::genesis::Genesis>(
+        &args.genesis_config_path,
+    )?;
 
-    println!(
-        "Loaded {} storage logs {} factory deps from source database.",
-        storage_logs.len(),
-        factory_deps.len()
-    );
+    let GenesisState {
+        storage_logs,
+        factory_deps,
+    } = if let Some(manifest_path) = &args.manifest_path {
+        let manifest = GenesisManifest::read(manifest_path)?;
+        println!(
+            "Loaded {} allocations from manifest {}.",
+            manifest.allocations.len(),
+            manifest_path.display()
+        );
+        let system_contracts =
+            get_system_smart_contracts(genesis_config.evm_emulator_hash.is_some());
+        manifest.to_genesis_state(&system_contracts)?
+    } else {
+        export_from_database(args.database_url).await?
+    };
 
     let storage_logs_for_genesis: Vec<StorageLog> =
         storage_logs.iter().map(StorageLog::from).collect();
@@ -102,10 +102,6 @@ async fn main() -> anyhow::Result<()> {
     );
     println!("Calculating new genesis parameters");
 
-    let mut genesis_config = read_yaml_repr::<zksync_protobuf_config::proto::genesis::Genesis>(
-        &args.genesis_config_path,
-    )?;
-
     let base_system_contract_hashes = BaseSystemContractsHashes {
         bootloader: genesis_config
             .bootloader_hash
@@ -139,3 +135,39 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+async fn export_from_database(database_url: Option<String>) -> anyhow::Result<GenesisState> {
+    println!("Connecting to source database...");
+
+    let db_url = database_url.or_else(|| std::env::var("DATABASE_URL").ok()).expect("Specify the database connection string in either a CLI argument or in the DATABASE_URL environment variable.");
+    // we need only 1 DB connection at most for data export
+    let connection_pool_builder =
+        ConnectionPool::<Core>::builder(SensitiveUrl::from_str(db_url.as_str())?, 1);
+    let connection_pool = connection_pool_builder.build().await?;
+
+    let mut storage = connection_pool.connection().await?;
+    let mut transaction = storage.start_transaction().await?;
+
+    println!("Connected to source database.");
+
+    let storage_logs = transaction
+        .custom_genesis_export_dal()
+        .get_storage_logs()
+        .await?;
+    let factory_deps = transaction
+        .custom_genesis_export_dal()
+        .get_factory_deps()
+        .await?;
+
+    transaction.commit().await?;
+
+    println!(
+        "Loaded {} storage logs {} factory deps from source database.",
+        storage_logs.len(),
+        factory_deps.len()
+    );
+    Ok(GenesisState {
+        storage_logs,
+        factory_deps,
+    })
+}
@@ -31,8 +31,8 @@ use zksync_node_api_server::{
 use zksync_protobuf_config::proto;
 use zksync_snapshots_applier::SnapshotsApplierConfig;
 use zksync_types::{
-    api::BridgeAddresses, commitment::L1BatchCommitmentMode, url::SensitiveUrl, Address,
-    L1BatchNumber, L1ChainId, L2ChainId, SLChainId, ETHEREUM_ADDRESS,
+    api::BridgeAddresses, commitment::L1BatchCommitmentMode, pruning::PruningProfile,
+    url::SensitiveUrl, Address, L1BatchNumber, L1ChainId, L2ChainId, SLChainId, ETHEREUM_ADDRESS,
 };
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -100,7 +100,7 @@ impl ConfigurationSource for Environment {
 }
 
 /// This part of the external node config is fetched directly from the main node.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub(crate) struct RemoteENConfig {
     pub l1_bytecodes_supplier_addr: Option<Address>,
     #[serde(alias = "bridgehub_proxy_addr")]
@@ -274,7 +274,7 @@ where
 /// This part of the external node config is completely optional to provide.
 /// It can tweak limits of the API, delay intervals of certain components, etc.
 /// If any of the fields are not provided, the default values will be used.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub(crate) struct OptionalENConfig {
     // User-facing API limits
     /// Max possible limit of filters to be in the API state at once.
@@ -358,6 +358,17 @@ pub(crate) struct OptionalENConfig {
     /// (hundreds or thousands RPS).
     #[serde(default = "OptionalENConfig::default_extended_api_tracing")]
     pub extended_rpc_tracing: bool,
+    /// Enables the index of transactions by address used by the `zks_getTransactionsByAddress` method.
+    /// The index is maintained when persisting L2 blocks and is backfilled for blocks persisted before it was enabled.
+    #[serde(default)]
+    pub address_index_enabled: bool,
+    /// Enables the index of token transfers used by the `zks_getTokenTransfers` and `zks_getBalanceHistory` methods.
+    /// The index is maintained when persisting L2 blocks and is backfilled for blocks persisted before it was enabled.
+    #[serde(default)]
+    pub token_transfer_index_enabled: bool,
+    /// Max number of simulation sessions (see `zks_createSimulationSession`) to be kept in the API state at once.
+    /// If not specified, simulation sessions are disabled.
+    pub simulation_sessions_limit: Option<usize>,
 
     // Health checks
     /// Time limit in milliseconds to mark a health check as slow and log the corresponding warning.
@@ -455,6 +466,12 @@ pub(crate) struct OptionalENConfig {
     /// Number of requests per second allocated for the main node HTTP client. Default is 100 requests.
     #[serde(default = "OptionalENConfig::default_main_node_rate_limit_rps")]
     pub main_node_rate_limit_rps: NonZeroUsize,
+    /// Fallback upstreams (e.g., trusted external nodes) used for syncing if the main node is unavailable or rate-limits
+    /// requests. Upstreams are tried in the specified order; each upstream uses a separate rate limit
+    /// of `main_node_rate_limit_rps`. If set, hashes of L2 blocks fetched via JSON-RPC are cross-checked between
+    /// the main node and all fallback upstreams before blocks are applied.
+    #[serde(default)]
+    pub main_node_fallback_urls: Vec<SensitiveUrl>,
 
     #[serde(default)]
     pub l1_batch_commit_data_generator_mode: L1BatchCommitmentMode,
@@ -492,6 +509,14 @@ pub(crate) struct OptionalENConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 7 days.
     #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
     pruning_data_retention_sec: u64,
+    /// If set, data is archived to this object store before it is hard-pruned from Postgres. Loaded from env variables
+    /// with the `EN_PRUNING_ARCHIVE_OBJECT_STORE_` prefix.
+    #[serde(default)]
+    pub pruning_archive_object_store: Option<ObjectStoreConfig>,
+    /// Profile determining which data is removed by pruning: `full` (default), `keep_transactions` (retains blocks,
+    /// transactions, receipts and events) or `keep_state` (retains blocks and historical storage values).
+    #[serde(default)]
+    pub pruning_profile: PruningProfile,
     /// Gateway RPC URL, needed for operating during migration.
     pub gateway_url: Option<SensitiveUrl>,
     /// Interval for bridge addresses refreshing in seconds.
@@ -499,6 +524,17 @@ pub(crate) struct OptionalENConfig {
     /// Minimum time between current block.timestamp and the end of the asserted range for TimestampAsserter
     #[serde(default = "OptionalENConfig::default_timestamp_asserter_min_time_till_end_sec")]
     pub timestamp_asserter_min_time_till_end_sec: u32,
+
+    /// Enables automatic recovery from reorgs detected while the node is running. If enabled, the node rolls back
+    /// its state (Postgres, Merkle tree and state cache) to the last L1 batch matching the main node and resumes syncing
+    /// without exiting. If disabled, the node exits once a reorg is detected; the rollback is performed on the next start.
+    #[serde(default)]
+    pub reorg_auto_recovery_enabled: bool,
+    /// Maximum number of L1 batches that can be rolled back automatically if `reorg_auto_recovery_enabled` is set.
+    /// If a deeper rollback is required, the node refuses to start and raises the `external_node_reorg_rollback_limit_exceeded`
+    /// metric; the rollback must then be performed manually using the block reverter.
+    #[serde(default = "OptionalENConfig::default_reorg_auto_recovery_max_l1_batches")]
+    pub reorg_auto_recovery_max_l1_batches: NonZeroU32,
 }
 
 impl OptionalENConfig {
@@ -713,6 +749,15 @@ impl OptionalENConfig {
                 data_retention_sec,
                 default_pruning_data_retention_sec
             ),
+            pruning_archive_object_store: load_config!(
+                general_config.pruning,
+                archive_object_store
+            ),
+            pruning_profile: general_config
+                .pruning
+                .as_ref()
+                .map(|a| a.profile)
+                .unwrap_or_default(),
             protective_reads_persistence_enabled: general_config
                 .db_config
                 .as_ref()
@@ -733,9 +778,24 @@ impl OptionalENConfig {
                 web3_json_rpc.extended_api_tracing,
                 default_extended_api_tracing
             ),
+            address_index_enabled: general_config
+                .api_config
+                .as_ref()
+                .map(|a| a.web3_json_rpc.address_index_enabled)
+                .unwrap_or_default(),
+            token_transfer_index_enabled: general_config
+                .api_config
+                .as_ref()
+                .map(|a| a.web3_json_rpc.token_transfer_index_enabled)
+                .unwrap_or_default(),
+            simulation_sessions_limit: load_config!(
+                general_config.api_config,
+                web3_json_rpc.simulation_sessions_limit
+            ),
             main_node_rate_limit_rps: enconfig
                 .main_node_rate_limit_rps
                 .unwrap_or_else(Self::default_main_node_rate_limit_rps),
+            main_node_fallback_urls: enconfig.main_node_fallback_urls.clone(),
             api_namespaces,
             contracts_diamond_proxy_addr: None,
             gateway_url: secrets
@@ -748,6 +808,10 @@ impl OptionalENConfig {
                 .as_ref()
                 .map(|x| x.min_time_till_end_sec)
                 .unwrap_or_else(Self::default_timestamp_asserter_min_time_till_end_sec),
+            reorg_auto_recovery_enabled: enconfig.reorg_auto_recovery_enabled,
+            reorg_auto_recovery_max_l1_batches: enconfig
+                .reorg_auto_recovery_max_l1_batches
+                .unwrap_or_else(Self::default_reorg_auto_recovery_max_l1_batches),
         })
     }
 
@@ -886,11 +950,16 @@ impl OptionalENConfig {
         60
     }
 
+    fn default_reorg_auto_recovery_max_l1_batches() -> NonZeroU32 {
+        NonZeroU32::new(10).unwrap()
+    }
+
     fn from_env() -> anyhow::Result<Self> {
         let mut result: OptionalENConfig = envy::prefixed("EN_")
             .from_env()
             .context("could not load external node config")?;
         result.snapshots_recovery_object_store = snapshot_recovery_object_store_config().ok();
+        result.pruning_archive_object_store = pruning_archive_object_store_config().ok();
         Ok(result)
     }
 
@@ -991,7 +1060,7 @@ impl OptionalENConfig {
 }
 
 /// This part of the external node config is required for its operation.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub(crate) struct RequiredENConfig {
     /// The chain ID of the L1 network (e.g., 1 for Ethereum mainnet).
     pub l1_chain_id: L1ChainId,
@@ -1084,7 +1153,7 @@ impl RequiredENConfig {
 /// While also mandatory, it historically used different naming scheme for corresponding
 /// environment variables.
 /// Thus it is kept separately for backward compatibility and ease of deserialization.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub(crate) struct PostgresConfig {
     database_url: SensitiveUrl,
     pub max_connections: u32,
@@ -1119,7 +1188,7 @@ impl PostgresConfig {
 
 /// Experimental part of the external node config. All parameters in this group can change or disappear without notice.
 /// Eventually, parameters from this group generally end up in the optional group.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub(crate) struct ExperimentalENConfig {
     // State keeper cache config
     /// Block cache capacity of the state keeper RocksDB cache. The default value is 128 MB.
@@ -1258,7 +1327,14 @@ pub(crate) fn snapshot_recovery_object_store_config() -> anyhow::Result<ObjectSt
         .context("failed loading snapshot object store config from env variables")
 }
 
-#[derive(Debug, Deserialize)]
+/// Configuration of the object store for archiving pruned data. Archiving is disabled if the config is not present.
+pub(crate) fn pruning_archive_object_store_config() -> anyhow::Result<ObjectStoreConfig> {
+    envy::prefixed("EN_PRUNING_ARCHIVE_OBJECT_STORE_")
+        .from_env::<ObjectStoreConfig>()
+        .context("failed loading pruning archive object store config from env variables")
+}
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct ApiComponentConfig {
     /// Address of the tree API used by this EN in case it does not have a
     /// local tree component running and in this case needs to send requests
@@ -1277,7 +1353,7 @@ impl ApiComponentConfig {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct TreeComponentConfig {
     pub api_port: Option<u16>,
 }
@@ -1294,7 +1370,7 @@ impl TreeComponentConfig {
 
 /// External Node Config contains all the configuration required for the EN operation.
 /// It is split into three parts: required, optional and remote for easier navigation.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct ExternalNodeConfig<R = RemoteENConfig> {
     pub required: RequiredENConfig,
     pub postgres: PostgresConfig,
@@ -1495,6 +1571,11 @@ impl From<&ExternalNodeConfig> for InternalApiConfig {
             dummy_verifier: config.remote.dummy_verifier,
             l1_batch_commit_data_generator_mode: config.remote.l1_batch_commit_data_generator_mode,
             timestamp_asserter_address: config.remote.l2_timestamp_asserter_addr,
+            address_index_enabled: config.optional.address_index_enabled,
+            token_transfer_index_enabled: config.optional.token_transfer_index_enabled,
+            simulation_sessions_limit: config.optional.simulation_sessions_limit,
+            // External nodes don't send settlement transactions.
+            settlement_mode: None,
         }
     }
 }
@@ -8,7 +8,7 @@ use zksync_vlog::{logs::LogFormat, prometheus::PrometheusExporterConfig};
 use super::{ConfigurationSource, Environment};
 
 /// Observability part of the node configuration.
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub(crate) struct ObservabilityENConfig {
     /// Port to bind the Prometheus exporter server to. If not specified, the server will not be launched.
     /// If the push gateway URL is specified, it will prevail.
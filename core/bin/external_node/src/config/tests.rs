@@ -105,6 +105,9 @@ fn parsing_optional_config_from_empty_env() {
         config.l1_batch_commit_data_generator_mode,
         L1BatchCommitmentMode::Rollup
     );
+    assert!(!config.reorg_auto_recovery_enabled);
+    assert_eq!(config.reorg_auto_recovery_max_l1_batches.get(), 10);
+    assert!(config.main_node_fallback_urls.is_empty());
 }
 
 #[test]
@@ -131,6 +134,12 @@ fn parsing_optional_config_from_env() {
         ),
         ("EN_L1_BATCH_COMMIT_DATA_GENERATOR_MODE", "Validium"),
         ("EN_TIMESTAMP_ASSERTER_MIN_TIME_TILL_END_SEC", "2"),
+        ("EN_REORG_AUTO_RECOVERY_ENABLED", "true"),
+        ("EN_REORG_AUTO_RECOVERY_MAX_L1_BATCHES", "5"),
+        (
+            "EN_MAIN_NODE_FALLBACK_URLS",
+            "http://en-1.example.com/,http://en-2.example.com/",
+        ),
     ];
     let env_vars = env_vars
         .into_iter()
@@ -176,6 +185,17 @@ fn parsing_optional_config_from_env() {
         config.l1_batch_commit_data_generator_mode,
         L1BatchCommitmentMode::Validium
     );
+    assert!(config.reorg_auto_recovery_enabled);
+    assert_eq!(config.reorg_auto_recovery_max_l1_batches.get(), 5);
+    let fallback_urls: Vec<_> = config
+        .main_node_fallback_urls
+        .iter()
+        .map(SensitiveUrl::expose_str)
+        .collect();
+    assert_eq!(
+        fallback_urls,
+        ["http://en-1.example.com/", "http://en-2.example.com/"]
+    );
 }
 
 #[test]
@@ -3,6 +3,8 @@ use std::{collections::HashSet, str::FromStr};
 use anyhow::Context as _;
 use clap::Parser;
 use node_builder::ExternalNodeBuilder;
+use zksync_node_framework::service::{TaskError, ZkStackServiceError};
+use zksync_vlog::ObservabilityGuard;
 use zksync_web3_decl::client::{Client, DynClient, L2};
 
 use crate::config::{generate_consensus_secrets, ExternalNodeConfig};
@@ -171,8 +173,52 @@ fn main() -> anyhow::Result<()> {
         .block_on(config.fetch_remote(main_node_client.as_ref()))
         .context("failed fetching remote part of node config from main node")?;
 
-    let node = ExternalNodeBuilder::on_runtime(runtime, config)
-        .build(opt.components.0.into_iter().collect())?;
+    let components = opt.components.0.into_iter().collect();
+    if config.optional.reorg_auto_recovery_enabled {
+        return run_with_reorg_recovery(runtime, config, components, guard);
+    }
+    let node = ExternalNodeBuilder::on_runtime(runtime, config).build(components)?;
     node.run(guard)?;
     anyhow::Ok(())
 }
+
+/// Runs the node, restarting it each time it stops because of a detected reorg. On restart, storage initialization
+/// rolls back the node state to the last L1 batch matching the main node, after which syncing resumes.
+fn run_with_reorg_recovery(
+    runtime: tokio::runtime::Runtime,
+    config: ExternalNodeConfig,
+    components: Vec<Component>,
+    mut guard: ObservabilityGuard,
+) -> anyhow::Result<()> {
+    let result = loop {
+        // Each run gets a fresh runtime; the original one is kept alive for the observability stack.
+        let node = ExternalNodeBuilder::on_runtime(tokio_runtime()?, config.clone())
+            .build(components.clone())?;
+        match node.run(None) {
+            Err(err) if is_reorg_detected(&err) => {
+                tracing::warn!("{err}; restarting the node to recover from the reorg");
+            }
+            result => break result,
+        }
+    };
+
+    // Make sure that the shutdown happens in the `tokio` context.
+    let _rt_guard = runtime.enter();
+    guard.shutdown();
+    Ok(result?)
+}
+
+fn is_reorg_detected(err: &ZkStackServiceError) -> bool {
+    let ZkStackServiceError::Task(errors) = err else {
+        return false;
+    };
+    errors.0.iter().any(|err| {
+        let TaskError::TaskFailed(_, err) = err else {
+            return false;
+        };
+        matches!(
+            err.downcast_ref(),
+            Some(zksync_reorg_detector::Error::ReorgDetected(_))
+        )
+    })
+}
@@ -17,6 +17,7 @@ use zksync_metadata_calculator::{
 use zksync_node_api_server::web3::Namespace;
 use zksync_node_framework::{
     implementations::layers::{
+        address_index_backfill::{AddressIndex, AddressIndexBackfillLayer},
         batch_status_updater::BatchStatusUpdaterLayer,
         block_reverter::BlockReverterLayer,
         commitment_generator::CommitmentGeneratorLayer,
@@ -149,7 +150,8 @@ impl ExternalNodeBuilder {
             self.config.required.main_node_url.clone(),
             self.config.optional.main_node_rate_limit_rps,
             self.config.required.l2_chain_id,
-        );
+        )
+        .with_fallback_urls(self.config.optional.main_node_fallback_urls.clone());
         self.node.add_layer(layer);
         Ok(self)
     }
@@ -219,7 +221,9 @@ impl ExternalNodeBuilder {
         .with_pre_insert_txs(true) // EN requires txs to be pre-inserted.
         .with_protective_reads_persistence_enabled(
             self.config.optional.protective_reads_persistence_enabled,
-        );
+        )
+        .with_address_index_enabled(self.config.optional.address_index_enabled)
+        .with_token_transfer_index_enabled(self.config.optional.token_transfer_index_enabled);
 
         let io_layer = ExternalIOLayer::new(self.config.required.l2_chain_id);
 
@@ -267,11 +271,18 @@ impl ExternalNodeBuilder {
 
     fn add_pruning_layer(mut self) -> anyhow::Result<Self> {
         if self.config.optional.pruning_enabled {
-            let layer = PruningLayer::new(
+            let mut layer = PruningLayer::new(
                 self.config.optional.pruning_removal_delay(),
                 self.config.optional.pruning_chunk_size,
                 self.config.optional.pruning_data_retention(),
-            );
+            )
+            .with_profile(self.config.optional.pruning_profile);
+            if let Some(object_store_config) = &self.config.optional.pruning_archive_object_store {
+                layer = layer.with_archive(
+                    object_store_config.clone(),
+                    self.config.required.l2_chain_id,
+                );
+            }
             self.node.add_layer(layer);
         } else {
             tracing::info!("Pruning is disabled");
@@ -468,6 +479,18 @@ impl ExternalNodeBuilder {
         Ok(self)
     }
 
+    fn add_address_index_backfill_layer(mut self) -> anyhow::Result<Self> {
+        if self.config.optional.address_index_enabled {
+            self.node
+                .add_layer(AddressIndexBackfillLayer::new(AddressIndex::Transactions));
+        }
+        if self.config.optional.token_transfer_index_enabled {
+            self.node
+                .add_layer(AddressIndexBackfillLayer::new(AddressIndex::TokenTransfers));
+        }
+        Ok(self)
+    }
+
     fn web3_api_optional_config(&self) -> Web3ServerOptionalConfig {
         // The refresh interval should be several times lower than the pruning removal delay, so that
         // soft-pruning will timely propagate to the API server.
@@ -564,6 +587,11 @@ impl ExternalNodeBuilder {
                 .optional
                 .snapshots_recovery_postgres_max_concurrency,
             snapshot_recovery_config,
+            max_reorg_rollback_depth: self
+                .config
+                .optional
+                .reorg_auto_recovery_enabled
+                .then_some(self.config.optional.reorg_auto_recovery_max_l1_batches),
         });
         let mut layer = NodeStorageInitializerLayer::new();
         if matches!(kind, LayerKind::Precondition) {
@@ -663,7 +691,8 @@ impl ExternalNodeBuilder {
                         .add_consistency_checker_layer()?
                         .add_commitment_generator_layer()?
                         .add_batch_status_updater_layer()?
-                        .add_logs_bloom_backfill_layer()?;
+                        .add_logs_bloom_backfill_layer()?
+                        .add_address_index_backfill_layer()?;
                 }
             }
         }
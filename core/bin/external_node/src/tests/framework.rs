@@ -70,6 +70,10 @@ impl Task for TestSigintTask {
         TaskKind::UnconstrainedTask
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::UnconstrainedTask)
+    }
+
     fn id(&self) -> TaskId {
         "test_sigint_task".into()
     }
@@ -0,0 +1,143 @@
+use std::{path::PathBuf, str::FromStr, sync::Arc};
+
+use anyhow::Context as _;
+use clap::{Parser, Subcommand};
+use zksync_config::ObjectStoreConfig;
+use zksync_dal::{pruning_dal::PrunedDataArchiveInfo, ConnectionPool, Core, CoreDal};
+use zksync_env_config::envy_load;
+use zksync_object_store::{ObjectStore, ObjectStoreFactory};
+use zksync_types::{pruning::PrunedDataArchive, url::SensitiveUrl, L1BatchNumber};
+
+/// Utility to inspect archives of data removed by Postgres pruning and re-import them for investigation.
+///
+/// The object store with archives is configured using env variables with the `EN_PRUNING_ARCHIVE_OBJECT_STORE_` prefix,
+/// i.e., in the same way as for the external node.
+#[derive(Debug, Parser)]
+#[command(author = "Matter Labs", version, about = "Pruned data archive utility", long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// PostgreSQL connection string of the node that pruned the data. If not specified,
+    /// will be taken from the `DATABASE_URL` env variable.
+    #[arg(long, global = true)]
+    database_url: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Lists archives recorded in the pruning log.
+    #[command(name = "list")]
+    List,
+    /// Downloads the archive containing the specified L1 batch and writes it as uncompressed JSON.
+    #[command(name = "export")]
+    Export {
+        /// L1 batch contained in the archive.
+        #[arg(long)]
+        l1_batch_number: u32,
+        /// Output file path.
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Imports the archive containing the specified L1 batch into the `imported_pruned_l1_batches`
+    /// and `imported_pruned_l2_blocks` tables. Imported data is not used by the node.
+    #[command(name = "import")]
+    Import {
+        /// L1 batch contained in the archive.
+        #[arg(long)]
+        l1_batch_number: u32,
+    },
+}
+
+impl Cli {
+    async fn connection_pool(&self) -> anyhow::Result<ConnectionPool<Core>> {
+        let database_url = match &self.database_url {
+            Some(url) => url.clone(),
+            None => std::env::var("DATABASE_URL").context(
+                "database URL must be specified either as a CLI arg or in the `DATABASE_URL` env variable",
+            )?,
+        };
+        let database_url = SensitiveUrl::from_str(&database_url).context("invalid database URL")?;
+        ConnectionPool::<Core>::singleton(database_url)
+            .build()
+            .await
+            .context("failed building connection pool")
+    }
+}
+
+async fn create_object_store() -> anyhow::Result<Arc<dyn ObjectStore>> {
+    let config: ObjectStoreConfig = envy_load(
+        "pruning_archive_object_store",
+        "EN_PRUNING_ARCHIVE_OBJECT_STORE_",
+    )?;
+    Ok(ObjectStoreFactory::new(config).create_store().await?)
+}
+
+/// Finds the archive containing the specified L1 batch, and fetches it from the object store.
+async fn fetch_archive(
+    archives: &[PrunedDataArchiveInfo],
+    l1_batch_number: L1BatchNumber,
+) -> anyhow::Result<(PrunedDataArchive, String)> {
+    let info = archives
+        .iter()
+        .find(|info| info.last_l1_batch >= l1_batch_number)
+        .with_context(|| format!("no archive contains L1 batch #{l1_batch_number}"))?;
+    let object_store = create_object_store().await?;
+    let archive: PrunedDataArchive = object_store
+        .get_by_encoded_key(info.location.clone())
+        .await
+        .with_context(|| format!("failed fetching archive `{}`", info.location))?;
+    anyhow::ensure!(
+        archive.first_l1_batch <= l1_batch_number,
+        "archive `{}` starts with L1 batch #{}; L1 batch #{l1_batch_number} was pruned without archiving",
+        info.location,
+        archive.first_l1_batch
+    );
+    Ok((archive, info.location.clone()))
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let pool = cli.connection_pool().await?;
+    let mut storage = pool.connection().await?;
+    let archives = storage.pruning_dal().get_pruned_data_archives().await?;
+
+    match cli.command {
+        Command::List => {
+            for info in &archives {
+                println!(
+                    "{}\tlast L1 batch #{}\tlast L2 block #{}",
+                    info.location, info.last_l1_batch, info.last_l2_block
+                );
+            }
+        }
+        Command::Export {
+            l1_batch_number,
+            output,
+        } => {
+            let (archive, location) =
+                fetch_archive(&archives, L1BatchNumber(l1_batch_number)).await?;
+            let json = serde_json::to_vec_pretty(&archive)?;
+            tokio::fs::write(&output, json)
+                .await
+                .with_context(|| format!("failed writing archive to {}", output.display()))?;
+            println!("Exported archive `{location}` to {}", output.display());
+        }
+        Command::Import { l1_batch_number } => {
+            let (archive, location) =
+                fetch_archive(&archives, L1BatchNumber(l1_batch_number)).await?;
+            storage
+                .pruning_dal()
+                .import_pruned_data_archive(&archive, &location)
+                .await?;
+            println!(
+                "Imported archive `{location}` with L1 batches #{}..=#{} and L2 blocks #{}..=#{}",
+                archive.first_l1_batch,
+                archive.last_l1_batch,
+                archive.first_l2_block,
+                archive.last_l2_block
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,361 @@
+//! Bisecting divergences between the legacy and fast VMs down to an instruction range.
+//!
+//! Both VMs execute the same EraVM bytecode, so after each executed instruction, the address of the current frame
+//! and the gas remaining in it should match. Bisection runs in two passes on the diverging transaction:
+//!
+//! 1. Record a checkpoint each `stride` instructions and find the first mismatching checkpoint.
+//! 2. Record all instructions between the last matching and the first mismatching checkpoints, and find
+//!    the first mismatching instruction.
+
+use std::{
+    cell::RefCell,
+    fmt, mem,
+    ops::{ControlFlow, Range},
+    rc::Rc,
+};
+
+use serde::Serialize;
+use zksync_multivm::{
+    interface::{
+        storage::{ImmutableStorageView, StoragePtr, StorageSnapshot, StorageView, WriteStorage},
+        utils::VmDump,
+        VmFactory, VmInterface, VmInterfaceExt,
+    },
+    tracers::dynamic::vm_1_5_0::DynTracer,
+    vm_fast::{
+        self,
+        interface::{CallframeInterface, GlobalStateInterface, OpcodeType, ShouldStop, Tracer},
+    },
+    vm_latest::{self, HistoryEnabled, HistoryMode, SimpleMemory, ToTracerPointer, VmTracer},
+    zk_evm_latest::tracing::{AfterExecutionData, VmLocalStateData},
+};
+use zksync_types::Address;
+
+use crate::replay::{for_each_step, ReplayStep, TxLocation};
+
+type LegacyVm = vm_latest::Vm<StorageView<StorageSnapshot>, HistoryEnabled>;
+type FastVm = vm_fast::Vm<ImmutableStorageView<StorageSnapshot>, FastCheckpointTracer, ()>;
+
+/// VM state after executing an instruction.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Checkpoint {
+    /// 0-based index of the instruction, counted from the start of the transaction execution.
+    pub index: u64,
+    /// Address of the current frame.
+    pub address: Address,
+    /// Gas remaining in the current frame.
+    pub gas: u32,
+    /// Executed opcode. Not compared since opcode naming differs between VM implementations.
+    pub opcode: String,
+}
+
+impl Checkpoint {
+    fn matches(&self, other: &Self) -> bool {
+        self.index == other.index && self.address == other.address && self.gas == other.gas
+    }
+}
+
+impl fmt::Display for Checkpoint {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "#{:<10} {:?} gas: {:<10} {}",
+            self.index, self.address, self.gas, self.opcode
+        )
+    }
+}
+
+/// Records checkpoints for the instructions in the specified range.
+#[derive(Debug, Default)]
+struct CheckpointRecorder {
+    range: Range<u64>,
+    stride: u64,
+    instruction_count: u64,
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl CheckpointRecorder {
+    fn new(range: Range<u64>, stride: u64) -> Self {
+        Self {
+            range,
+            stride: stride.max(1),
+            instruction_count: 0,
+            checkpoints: vec![],
+        }
+    }
+
+    fn record(&mut self, address: Address, gas: u32, opcode: impl FnOnce() -> String) {
+        let index = self.instruction_count;
+        self.instruction_count += 1;
+        if self.range.contains(&index) && (index - self.range.start) % self.stride == 0 {
+            self.checkpoints.push(Checkpoint {
+                index,
+                address,
+                gas,
+                opcode: opcode(),
+            });
+        }
+    }
+}
+
+/// Checkpoint tracer for the legacy VM. The recorder is shared since legacy tracers are consumed by the VM.
+#[derive(Debug)]
+struct LegacyCheckpointTracer(Rc<RefCell<CheckpointRecorder>>);
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for LegacyCheckpointTracer {
+    fn after_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: AfterExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        let frame = &state.vm_local_state.callstack.current;
+        self.0
+            .borrow_mut()
+            .record(frame.this_address, frame.ergs_remaining, || {
+                format!("{:?}", data.opcode.variant.opcode)
+            });
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for LegacyCheckpointTracer {}
+
+/// Checkpoint tracer for the fast VM. The default tracer doesn't record anything.
+#[derive(Debug, Default)]
+struct FastCheckpointTracer(CheckpointRecorder);
+
+impl Tracer for FastCheckpointTracer {
+    fn after_instruction<OP: OpcodeType, S: GlobalStateInterface>(
+        &mut self,
+        state: &mut S,
+    ) -> ShouldStop {
+        let frame = state.current_frame();
+        self.0
+            .record(frame.address(), frame.gas(), || format!("{:?}", OP::VALUE));
+        ShouldStop::Continue
+    }
+}
+
+/// Traces of a single transaction in the legacy and fast VMs.
+#[derive(Debug)]
+struct TxTraces {
+    legacy: CheckpointRecorder,
+    fast: CheckpointRecorder,
+}
+
+impl TxTraces {
+    /// Returns the index of the first mismatching checkpoint, or the number of checkpoints if one trace
+    /// is a prefix of another one. Returns `None` if traces fully match.
+    fn first_mismatch(&self) -> Option<usize> {
+        let (legacy, fast) = (&self.legacy.checkpoints, &self.fast.checkpoints);
+        let mismatch = legacy
+            .iter()
+            .zip(fast)
+            .position(|(legacy, fast)| !legacy.matches(fast));
+        if mismatch.is_some() {
+            return mismatch;
+        }
+        let common_len = legacy.len().min(fast.len());
+        let counts_differ = self.legacy.instruction_count != self.fast.instruction_count;
+        (legacy.len() != fast.len() || counts_differ).then_some(common_len)
+    }
+}
+
+/// Executes the dump on the legacy and fast VMs up to and including the target transaction, recording checkpoints
+/// for the target transaction.
+fn trace_tx(dump: &VmDump, target: TxLocation, range: Range<u64>, stride: u64) -> TxTraces {
+    let legacy_storage = StorageView::new(dump.storage.clone()).to_rc_ptr();
+    let mut legacy_vm = LegacyVm::new(
+        dump.l1_batch_env.clone(),
+        dump.system_env.clone(),
+        legacy_storage,
+    );
+    let fast_storage = StorageView::new(dump.storage.clone()).to_rc_ptr();
+    let mut fast_vm = FastVm::new(
+        dump.l1_batch_env.clone(),
+        dump.system_env.clone(),
+        fast_storage,
+    );
+
+    let mut traces = None;
+    for_each_step(&dump.l2_blocks, |step| match step {
+        ReplayStep::StartL2Block(env) => {
+            legacy_vm.start_new_l2_block(env);
+            fast_vm.start_new_l2_block(env);
+            ControlFlow::Continue(())
+        }
+        ReplayStep::ExecuteTx(location, tx) if location.index < target.index => {
+            legacy_vm.execute_transaction_with_bytecode_compression(tx.clone(), true);
+            fast_vm.execute_transaction_with_bytecode_compression(tx.clone(), true);
+            ControlFlow::Continue(())
+        }
+        ReplayStep::ExecuteTx(_, tx) => {
+            let recorder = Rc::new(RefCell::new(CheckpointRecorder::new(range.clone(), stride)));
+            let tracer = LegacyCheckpointTracer(recorder.clone()).into_tracer_pointer();
+            let mut legacy_tracer = vm_latest::TracerDispatcher::from(tracer);
+            legacy_vm.inspect_transaction_with_bytecode_compression(
+                &mut legacy_tracer,
+                tx.clone(),
+                true,
+            );
+            let legacy = mem::take(&mut *recorder.borrow_mut());
+
+            let mut fast_tracer = (
+                FastCheckpointTracer(CheckpointRecorder::new(range.clone(), stride)),
+                (),
+            );
+            fast_vm.inspect_transaction_with_bytecode_compression(
+                &mut fast_tracer,
+                tx.clone(),
+                true,
+            );
+            traces = Some(TxTraces {
+                legacy,
+                fast: fast_tracer.0 .0,
+            });
+            ControlFlow::Break(())
+        }
+    });
+    traces.expect("target transaction is not in the dump")
+}
+
+/// First divergence between the legacy and fast VMs on the instruction level.
+#[derive(Debug, Serialize)]
+pub(crate) struct InstructionDivergence {
+    /// Range of instruction indices starting from the last matching checkpoint and ending with the first
+    /// mismatching instruction (inclusive).
+    pub instruction_range: Range<u64>,
+    pub legacy_instruction_count: u64,
+    pub fast_instruction_count: u64,
+    /// Legacy VM trace leading to the divergence.
+    pub legacy_trace: Vec<Checkpoint>,
+    /// Fast VM trace leading to the divergence.
+    pub fast_trace: Vec<Checkpoint>,
+}
+
+impl fmt::Display for InstructionDivergence {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Range { start, end } = &self.instruction_range;
+        writeln!(
+            formatter,
+            "First diverging instruction is in range #{start}..#{end}; total instructions executed: \
+             {} (legacy VM), {} (fast VM)",
+            self.legacy_instruction_count, self.fast_instruction_count
+        )?;
+        for (name, trace) in [("legacy", &self.legacy_trace), ("fast", &self.fast_trace)] {
+            writeln!(formatter, "{name} VM trace:")?;
+            for checkpoint in trace {
+                writeln!(formatter, "  {checkpoint}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Narrows down divergence between the legacy and fast VMs on the specified transaction to an instruction range.
+/// Returns `None` if instruction traces match, i.e. the divergence is in the post-processing of the VM outputs.
+pub(crate) fn bisect_instructions(
+    dump: &VmDump,
+    tx: TxLocation,
+    stride: u64,
+    context: usize,
+) -> Option<InstructionDivergence> {
+    let coarse = trace_tx(dump, tx, 0..u64::MAX, stride);
+    let mismatch = coarse.first_mismatch()?;
+    // Start from the last matching checkpoint. The end is exclusive, hence `+ 1`.
+    let start = mismatch
+        .checked_sub(1)
+        .map_or(0, |idx| coarse.legacy.checkpoints[idx].index);
+    let end = [&coarse.legacy, &coarse.fast]
+        .into_iter()
+        .map(|trace| {
+            trace
+                .checkpoints
+                .get(mismatch)
+                .map_or(trace.instruction_count, |checkpoint| checkpoint.index)
+        })
+        .min()
+        .unwrap()
+        + 1;
+
+    let fine = trace_tx(dump, tx, start..end, 1);
+    // Traces can fully match if the divergence is caused by the instruction count, in which case the divergence
+    // is at the end of the shorter trace.
+    let mismatch = fine.first_mismatch().unwrap_or_else(|| {
+        fine.legacy
+            .checkpoints
+            .len()
+            .min(fine.fast.checkpoints.len())
+    });
+    let trace_start = mismatch.saturating_sub(context);
+    let trace_end = mismatch + 1;
+    let select_trace = |recorder: &CheckpointRecorder| {
+        let checkpoints = &recorder.checkpoints;
+        checkpoints[trace_start.min(checkpoints.len())..trace_end.min(checkpoints.len())].to_vec()
+    };
+    let diverging_index = fine
+        .legacy
+        .checkpoints
+        .get(mismatch)
+        .or_else(|| fine.fast.checkpoints.get(mismatch))
+        .map_or(end - 1, |checkpoint| checkpoint.index);
+
+    Some(InstructionDivergence {
+        instruction_range: start..diverging_index + 1,
+        legacy_instruction_count: coarse.legacy.instruction_count,
+        fast_instruction_count: coarse.fast.instruction_count,
+        legacy_trace: select_trace(&fine.legacy),
+        fast_trace: select_trace(&fine.fast),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_gas_values(recorder: &mut CheckpointRecorder, gas_values: &[u32]) {
+        for &gas in gas_values {
+            recorder.record(Address::zero(), gas, || "Nop".to_owned());
+        }
+    }
+
+    #[test]
+    fn recording_checkpoints_with_stride() {
+        let mut recorder = CheckpointRecorder::new(2..9, 3);
+        record_gas_values(&mut recorder, &[100, 99, 98, 97, 96, 95, 94, 93, 92, 91]);
+        assert_eq!(recorder.instruction_count, 10);
+        let indices: Vec<_> = recorder.checkpoints.iter().map(|cp| cp.index).collect();
+        assert_eq!(indices, [2, 5, 8]);
+        let gas_values: Vec<_> = recorder.checkpoints.iter().map(|cp| cp.gas).collect();
+        assert_eq!(gas_values, [98, 95, 92]);
+    }
+
+    #[test]
+    fn finding_first_mismatch() {
+        let mut traces = TxTraces {
+            legacy: CheckpointRecorder::new(0..u64::MAX, 1),
+            fast: CheckpointRecorder::new(0..u64::MAX, 1),
+        };
+        record_gas_values(&mut traces.legacy, &[100, 99, 98]);
+        record_gas_values(&mut traces.fast, &[100, 99, 98]);
+        assert_eq!(traces.first_mismatch(), None);
+
+        record_gas_values(&mut traces.legacy, &[97]);
+        assert_eq!(traces.first_mismatch(), Some(3));
+        record_gas_values(&mut traces.fast, &[90]);
+        assert_eq!(traces.first_mismatch(), Some(3));
+    }
+
+    #[test]
+    fn instruction_count_mismatch_without_recorded_checkpoints() {
+        let mut traces = TxTraces {
+            legacy: CheckpointRecorder::new(0..u64::MAX, 10),
+            fast: CheckpointRecorder::new(0..u64::MAX, 10),
+        };
+        record_gas_values(&mut traces.legacy, &[100, 99, 98]);
+        record_gas_values(&mut traces.fast, &[100, 99]);
+        // Only the checkpoint for the first instruction is recorded for both VMs.
+        assert_eq!(traces.first_mismatch(), Some(1));
+    }
+}
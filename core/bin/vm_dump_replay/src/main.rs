@@ -0,0 +1,260 @@
+//! Tool to replay VM dumps produced on VM divergences and to bisect divergences between VM implementations.
+
+use std::{
+    fs::{self, File},
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use zksync_multivm::interface::{utils::VmDump, GasProfile};
+use zksync_types::Address;
+
+use crate::{
+    bisect::{bisect_instructions, InstructionDivergence},
+    replay::{TxDiff, TxLocation, VmSpec},
+};
+
+mod bisect;
+mod profile;
+mod replay;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Replays VM dumps and bisects divergences between VM implementations",
+    long_about = None
+)]
+struct Cli {
+    /// Path to the JSON VM dump, e.g. one downloaded from the `vm_dumps` object store bucket.
+    #[arg(long)]
+    dump: PathBuf,
+    /// Output results as JSON.
+    #[arg(long)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// VMs are specified either as a fast VM mode (`old`, `new` or `shadow`), or as a legacy VM version
+/// (e.g., `Vm1_5_0IncreasedBootloaderMemory`).
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Replays the dump on the specified VM and outputs per-transaction execution summaries.
+    Replay {
+        #[arg(long, default_value = "old")]
+        vm: VmSpec,
+    },
+    /// Replays the dump on two VMs and outputs transactions with diverging outputs.
+    Diff {
+        #[arg(long, default_value = "old")]
+        main: VmSpec,
+        #[arg(long, default_value = "new")]
+        other: VmSpec,
+    },
+    /// Finds the first transaction on which two VMs diverge. For the `old` and `new` VMs, additionally narrows
+    /// the divergence down to an instruction range.
+    Bisect {
+        #[arg(long, default_value = "old")]
+        main: VmSpec,
+        #[arg(long, default_value = "new")]
+        other: VmSpec,
+        /// Number of instructions between checkpoints recorded during the first bisection pass.
+        /// Smaller values increase memory usage, but don't affect the bisection precision.
+        #[arg(long, default_value_t = 10_000)]
+        stride: u64,
+        /// Number of instructions preceding the first diverging instruction to output.
+        #[arg(long, default_value_t = 16)]
+        context: usize,
+    },
+    /// Profiles gas usage of a transaction by contracts and functions. Only the `old` and `new` VMs are supported.
+    Profile {
+        /// 0-based index of the transaction in the dump.
+        #[arg(long)]
+        tx: usize,
+        #[arg(long, default_value = "old")]
+        vm: VmSpec,
+        /// Path to write the profile in the collapsed stack format, which can be rendered as a flamegraph,
+        /// e.g. using `inferno-flamegraph`.
+        #[arg(long)]
+        collapsed: Option<PathBuf>,
+        /// Number of the most expensive functions to output.
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct BisectReport {
+    first_diverging_tx: Option<TxDiff>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instruction_divergence: Option<InstructionDivergence>,
+}
+
+#[derive(Debug, Serialize)]
+struct FunctionGas {
+    address: Address,
+    selector: Option<String>,
+    gas: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct ProfileReport {
+    tx: TxLocation,
+    total_gas: u64,
+    top_functions: Vec<FunctionGas>,
+}
+
+impl ProfileReport {
+    fn new(tx: TxLocation, profile: &GasProfile, top: usize) -> Self {
+        let mut functions: Vec<_> = profile
+            .by_function()
+            .into_iter()
+            .map(|((address, selector), gas)| FunctionGas {
+                address,
+                selector: selector.map(|selector| format!("0x{}", hex::encode(selector))),
+                gas,
+            })
+            .collect();
+        functions.sort_by(|a, b| b.gas.cmp(&a.gas));
+        functions.truncate(top);
+        Self {
+            tx,
+            total_gas: profile.total_gas(),
+            top_functions: functions,
+        }
+    }
+}
+
+fn load_dump(path: &Path) -> anyhow::Result<VmDump> {
+    let file = File::open(path).with_context(|| format!("cannot open VM dump at {path:?}"))?;
+    serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("cannot deserialize VM dump at {path:?}"))
+}
+
+fn print_json(value: &impl Serialize) -> anyhow::Result<()> {
+    let json = serde_json::to_string_pretty(value).context("failed serializing output")?;
+    println!("{json}");
+    Ok(())
+}
+
+impl Cli {
+    fn run(self) -> anyhow::Result<()> {
+        let dump = load_dump(&self.dump)?;
+        let tx_count: usize = dump.l2_blocks.iter().map(|block| block.txs.len()).sum();
+        if !self.json {
+            println!(
+                "Loaded dump for L1 batch #{} (protocol version {:?}): {} L2 block(s), {tx_count} transaction(s)",
+                dump.l1_batch_number(),
+                dump.system_env.version,
+                dump.l2_blocks.len()
+            );
+        }
+
+        match self.command {
+            Command::Replay { vm } => {
+                let summaries = replay::replay(&dump, vm)?;
+                if self.json {
+                    return print_json(&summaries);
+                }
+                println!("Replayed on `{vm}` VM:");
+                for summary in &summaries {
+                    println!("{summary}");
+                }
+            }
+            Command::Diff { main, other } => {
+                let diffs = replay::diff(&dump, main, other, false)?;
+                if self.json {
+                    return print_json(&diffs);
+                }
+                println!(
+                    "{} of {tx_count} transaction(s) diverged between `{main}` and `{other}` VMs",
+                    diffs.len()
+                );
+                for diff in &diffs {
+                    print!("{diff}");
+                }
+            }
+            Command::Bisect {
+                main,
+                other,
+                stride,
+                context,
+            } => {
+                let first_diverging_tx = replay::diff(&dump, main, other, true)?.pop();
+                let is_old_vs_new = (main.is_default_legacy() && matches!(other, VmSpec::Fast))
+                    || (matches!(main, VmSpec::Fast) && other.is_default_legacy());
+                let instruction_divergence = match &first_diverging_tx {
+                    Some(diff) if is_old_vs_new => {
+                        bisect_instructions(&dump, diff.location, stride, context)
+                    }
+                    _ => None,
+                };
+
+                if self.json {
+                    return print_json(&BisectReport {
+                        first_diverging_tx,
+                        instruction_divergence,
+                    });
+                }
+                let Some(diff) = first_diverging_tx else {
+                    println!("`{main}` and `{other}` VMs don't diverge on the dump");
+                    return Ok(());
+                };
+                print!("{diff}");
+                if let Some(divergence) = instruction_divergence {
+                    print!("{divergence}");
+                } else if is_old_vs_new {
+                    println!(
+                        "Instruction traces match; divergence is caused by post-processing of VM outputs"
+                    );
+                } else {
+                    println!(
+                        "Instruction-level bisection is only supported for `old` and `new` VMs"
+                    );
+                }
+            }
+            Command::Profile {
+                tx,
+                vm,
+                collapsed,
+                top,
+            } => {
+                let use_fast_vm = match vm {
+                    VmSpec::Fast => true,
+                    _ if vm.is_default_legacy() => false,
+                    _ => anyhow::bail!("profiling is only supported for `old` and `new` VMs"),
+                };
+                let (location, profile) = profile::profile_tx(&dump, tx, use_fast_vm)?;
+                if let Some(path) = &collapsed {
+                    fs::write(path, profile.to_collapsed_stacks())
+                        .with_context(|| format!("failed writing collapsed stacks to {path:?}"))?;
+                }
+
+                let report = ProfileReport::new(location, &profile, top);
+                if self.json {
+                    return print_json(&report);
+                }
+                println!(
+                    "Profiled {} on `{vm}` VM: {} gas in total",
+                    report.tx, report.total_gas
+                );
+                for function in &report.top_functions {
+                    let selector = function.selector.as_deref().unwrap_or("(no selector)");
+                    println!("  {:?} {selector}: {} gas", function.address, function.gas);
+                }
+                if let Some(path) = &collapsed {
+                    println!("Collapsed stacks written to {path:?}");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    Cli::parse().run()
+}
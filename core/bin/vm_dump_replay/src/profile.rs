@@ -0,0 +1,97 @@
+//! Gas profiling of transactions in a VM dump.
+
+use std::{ops::ControlFlow, sync::Arc};
+
+use once_cell::sync::OnceCell;
+use zksync_multivm::{
+    interface::{
+        storage::{ImmutableStorageView, StorageSnapshot, StorageView},
+        utils::VmDump,
+        GasProfile, L2BlockEnv, VmFactory, VmInterface, VmInterfaceExt,
+    },
+    tracers::GasProfilerTracer,
+    vm_fast,
+    vm_latest::{self, HistoryEnabled, ToTracerPointer},
+};
+use zksync_types::Transaction;
+
+use crate::replay::{for_each_step, ReplayStep, TxLocation};
+
+type LegacyVm = vm_latest::Vm<StorageView<StorageSnapshot>, HistoryEnabled>;
+type FastVm = vm_fast::Vm<ImmutableStorageView<StorageSnapshot>, vm_fast::GasProfilerTracer, ()>;
+
+/// Executes the dump up to and including the transaction with the specified index, and profiles gas usage
+/// of this transaction.
+pub(crate) fn profile_tx(
+    dump: &VmDump,
+    tx_index: usize,
+    use_fast_vm: bool,
+) -> anyhow::Result<(TxLocation, GasProfile)> {
+    let storage = StorageView::new(dump.storage.clone()).to_rc_ptr();
+    let (l1_batch_env, system_env) = (dump.l1_batch_env.clone(), dump.system_env.clone());
+    let mut vm = if use_fast_vm {
+        ProfiledVm::Fast(FastVm::new(l1_batch_env, system_env, storage))
+    } else {
+        ProfiledVm::Legacy(LegacyVm::new(l1_batch_env, system_env, storage))
+    };
+
+    let mut output = None;
+    for_each_step(&dump.l2_blocks, |step| match step {
+        ReplayStep::StartL2Block(env) => {
+            vm.start_new_l2_block(env);
+            ControlFlow::Continue(())
+        }
+        ReplayStep::ExecuteTx(location, tx) if location.index < tx_index => {
+            vm.execute(tx.clone());
+            ControlFlow::Continue(())
+        }
+        ReplayStep::ExecuteTx(location, tx) => {
+            output = Some((location, vm.profile(tx.clone())));
+            ControlFlow::Break(())
+        }
+    });
+    output.ok_or_else(|| anyhow::anyhow!("transaction #{tx_index} is not in the dump"))
+}
+
+#[derive(Debug)]
+enum ProfiledVm {
+    Legacy(LegacyVm),
+    Fast(FastVm),
+}
+
+impl ProfiledVm {
+    fn start_new_l2_block(&mut self, env: L2BlockEnv) {
+        match self {
+            Self::Legacy(vm) => vm.start_new_l2_block(env),
+            Self::Fast(vm) => vm.start_new_l2_block(env),
+        }
+    }
+
+    fn execute(&mut self, tx: Transaction) {
+        match self {
+            Self::Legacy(vm) => {
+                vm.execute_transaction_with_bytecode_compression(tx, true);
+            }
+            Self::Fast(vm) => {
+                vm.execute_transaction_with_bytecode_compression(tx, true);
+            }
+        }
+    }
+
+    fn profile(&mut self, tx: Transaction) -> GasProfile {
+        match self {
+            Self::Legacy(vm) => {
+                let result = Arc::new(OnceCell::new());
+                let tracer = GasProfilerTracer::new(result.clone()).into_tracer_pointer();
+                let mut tracer = vm_latest::TracerDispatcher::from(tracer);
+                vm.inspect_transaction_with_bytecode_compression(&mut tracer, tx, true);
+                result.get().cloned().unwrap_or_default()
+            }
+            Self::Fast(vm) => {
+                let mut tracer = (vm_fast::GasProfilerTracer::default(), ());
+                vm.inspect_transaction_with_bytecode_compression(&mut tracer, tx, true);
+                tracer.0.into_result()
+            }
+        }
+    }
+}
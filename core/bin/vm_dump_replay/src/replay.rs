@@ -0,0 +1,442 @@
+//! Replaying VM dumps on different VM implementations.
+
+use std::{
+    collections::BTreeSet,
+    fmt,
+    ops::ControlFlow,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+use zksync_multivm::{
+    interface::{
+        storage::{StorageSnapshot, StorageView},
+        utils::{CheckDivergence, DivergenceHandler, VmDump},
+        ExecutionResult, L2BlockEnv, VmExecutionResultAndLogs, VmInterface, VmInterfaceExt,
+    },
+    is_supported_by_fast_vm,
+    vm_latest::HistoryEnabled,
+    FastVmInstance, LegacyVmInstance, VmVersion,
+};
+use zksync_types::{block::L2BlockExecutionData, L2BlockNumber, Transaction, H256};
+
+const ALL_VM_VERSIONS: [VmVersion; 13] = [
+    VmVersion::M5WithoutRefunds,
+    VmVersion::M5WithRefunds,
+    VmVersion::M6Initial,
+    VmVersion::M6BugWithCompressionFixed,
+    VmVersion::Vm1_3_2,
+    VmVersion::VmVirtualBlocks,
+    VmVersion::VmVirtualBlocksRefundsEnhancement,
+    VmVersion::VmBoojumIntegration,
+    VmVersion::Vm1_4_1,
+    VmVersion::Vm1_4_2,
+    VmVersion::Vm1_5_0SmallBootloaderMemory,
+    VmVersion::Vm1_5_0IncreasedBootloaderMemory,
+    VmVersion::VmGateway,
+];
+
+/// Specification of the VM to replay a dump on.
+///
+/// Parsed either from a [`FastVmMode`](zksync_types::vm::FastVmMode) name (`old`, `new` or `shadow`),
+/// or from a legacy [`VmVersion`] name (e.g., `Vm1_5_0IncreasedBootloaderMemory`; case-insensitive).
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum VmSpec {
+    /// Legacy VM. If the version is not specified, it's derived from the protocol version in the dump.
+    Legacy(Option<VmVersion>),
+    /// Fast VM running in isolation.
+    Fast,
+    /// Fast VM shadowed by the latest legacy VM.
+    Shadow,
+}
+
+impl FromStr for VmSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "old" => Self::Legacy(None),
+            "new" => Self::Fast,
+            "shadow" => Self::Shadow,
+            _ => {
+                let version = ALL_VM_VERSIONS
+                    .into_iter()
+                    .find(|version| format!("{version:?}").eq_ignore_ascii_case(s));
+                let Some(version) = version else {
+                    let versions: Vec<_> = ALL_VM_VERSIONS
+                        .iter()
+                        .map(|version| format!("{version:?}"))
+                        .collect();
+                    return Err(format!(
+                        "unknown VM `{s}`; expected `old`, `new`, `shadow` or one of VM versions: {}",
+                        versions.join(", ")
+                    ));
+                };
+                Self::Legacy(Some(version))
+            }
+        })
+    }
+}
+
+impl fmt::Display for VmSpec {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Legacy(None) => formatter.write_str("old"),
+            Self::Legacy(Some(version)) => write!(formatter, "{version:?}"),
+            Self::Fast => formatter.write_str("new"),
+            Self::Shadow => formatter.write_str("shadow"),
+        }
+    }
+}
+
+impl VmSpec {
+    /// Checks whether this spec corresponds to the latest legacy VM for the dump, i.e. the VM which the fast VM
+    /// is shadowed by.
+    pub fn is_default_legacy(&self) -> bool {
+        matches!(self, Self::Legacy(None))
+    }
+}
+
+/// Location of a transaction in the dump.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct TxLocation {
+    /// 0-based index of the transaction in the L1 batch.
+    pub index: usize,
+    pub l2_block: L2BlockNumber,
+    pub hash: H256,
+}
+
+impl fmt::Display for TxLocation {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "transaction #{} ({:?}) in L2 block #{}",
+            self.index, self.hash, self.l2_block
+        )
+    }
+}
+
+/// Single step of replaying a dump.
+pub(crate) enum ReplayStep<'a> {
+    StartL2Block(L2BlockEnv),
+    ExecuteTx(TxLocation, &'a Transaction),
+}
+
+/// Iterates over the replay steps for the provided L2 blocks (which should be taken from a [`VmDump`])
+/// until `action` returns [`ControlFlow::Break`].
+pub(crate) fn for_each_step(
+    l2_blocks: &[L2BlockExecutionData],
+    mut action: impl FnMut(ReplayStep<'_>) -> ControlFlow<()>,
+) {
+    let mut tx_index = 0;
+    for (i, l2_block) in l2_blocks.iter().enumerate() {
+        if i > 0 {
+            // The first block is set when the VM is created.
+            let env = L2BlockEnv {
+                number: l2_block.number.0,
+                timestamp: l2_block.timestamp,
+                prev_block_hash: l2_block.prev_block_hash,
+                max_virtual_blocks_to_create: l2_block.virtual_blocks,
+            };
+            if action(ReplayStep::StartL2Block(env)).is_break() {
+                return;
+            }
+        }
+
+        for tx in &l2_block.txs {
+            let location = TxLocation {
+                index: tx_index,
+                l2_block: l2_block.number,
+                hash: tx.hash(),
+            };
+            tx_index += 1;
+            if action(ReplayStep::ExecuteTx(location, tx)).is_break() {
+                return;
+            }
+        }
+    }
+}
+
+/// Outputs of a transaction executed by a [`ReplayVm`].
+#[derive(Debug)]
+pub(crate) struct TxOutput {
+    pub compression_failed: bool,
+    pub result: VmExecutionResultAndLogs,
+    /// Divergence reported by the shadow VM, if any.
+    pub shadow_divergence: Option<String>,
+}
+
+impl TxOutput {
+    /// Checks divergences between this and another output. Returns human-readable descriptions of divergences.
+    pub fn diff(&self, other: &Self) -> Vec<String> {
+        let mut divergences = vec![];
+        if self.compression_failed != other.compression_failed {
+            divergences.push(format!(
+                "`compression_failed` mismatch: {} vs {}",
+                self.compression_failed, other.compression_failed
+            ));
+        }
+        divergences.extend_from_slice(self.result.check_divergence(&other.result).divergences());
+        divergences
+    }
+}
+
+/// VM instance replaying a dump.
+#[derive(Debug)]
+pub(crate) enum ReplayVm {
+    Legacy(LegacyVmInstance<StorageSnapshot, HistoryEnabled>),
+    Fast {
+        vm: FastVmInstance<StorageSnapshot>,
+        shadow_divergence: Arc<Mutex<Option<String>>>,
+    },
+}
+
+impl ReplayVm {
+    pub fn new(spec: VmSpec, dump: &VmDump) -> anyhow::Result<Self> {
+        let l1_batch_env = dump.l1_batch_env.clone();
+        let system_env = dump.system_env.clone();
+        let storage = StorageView::new(dump.storage.clone()).to_rc_ptr();
+        let protocol_version = system_env.version;
+
+        Ok(match spec {
+            VmSpec::Legacy(version) => {
+                let version = version.unwrap_or_else(|| protocol_version.into());
+                Self::Legacy(LegacyVmInstance::new_with_specific_version(
+                    l1_batch_env,
+                    system_env,
+                    storage,
+                    version,
+                ))
+            }
+            VmSpec::Fast | VmSpec::Shadow => {
+                anyhow::ensure!(
+                    is_supported_by_fast_vm(protocol_version),
+                    "protocol version {protocol_version:?} of the dump is not supported by the fast VM"
+                );
+                let shadow_divergence = Arc::<Mutex<Option<String>>>::default();
+                let vm = if matches!(spec, VmSpec::Fast) {
+                    FastVmInstance::fast(l1_batch_env, system_env, storage)
+                } else {
+                    let mut vm = FastVmInstance::shadowed(l1_batch_env, system_env, storage);
+                    if let FastVmInstance::Shadowed(vm) = &mut vm {
+                        // Record the divergence instead of panicking.
+                        let slot = shadow_divergence.clone();
+                        vm.set_divergence_handler(DivergenceHandler::new(move |errors, _| {
+                            *slot.lock().unwrap() = Some(errors.to_string());
+                        }));
+                    }
+                    vm
+                };
+                Self::Fast {
+                    vm,
+                    shadow_divergence,
+                }
+            }
+        })
+    }
+
+    pub fn start_new_l2_block(&mut self, l2_block_env: L2BlockEnv) {
+        match self {
+            Self::Legacy(vm) => vm.start_new_l2_block(l2_block_env),
+            Self::Fast { vm, .. } => vm.start_new_l2_block(l2_block_env),
+        }
+    }
+
+    pub fn execute(&mut self, tx: Transaction) -> TxOutput {
+        match self {
+            Self::Legacy(vm) => {
+                let (compression_result, result) =
+                    vm.execute_transaction_with_bytecode_compression(tx, true);
+                TxOutput {
+                    compression_failed: compression_result.is_err(),
+                    result,
+                    shadow_divergence: None,
+                }
+            }
+            Self::Fast {
+                vm,
+                shadow_divergence,
+            } => {
+                let (compression_result, result) =
+                    vm.execute_transaction_with_bytecode_compression(tx, true);
+                TxOutput {
+                    compression_failed: compression_result.is_err(),
+                    result,
+                    shadow_divergence: shadow_divergence.lock().unwrap().take(),
+                }
+            }
+        }
+    }
+}
+
+/// Summary of a transaction execution output.
+#[derive(Debug, Serialize)]
+pub(crate) struct TxSummary {
+    #[serde(flatten)]
+    pub location: TxLocation,
+    pub status: String,
+    pub gas_used: u64,
+    pub gas_refunded: u64,
+    pub storage_writes: usize,
+    pub events: usize,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub compression_failed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_divergence: Option<String>,
+}
+
+impl TxSummary {
+    pub fn new(location: TxLocation, output: TxOutput) -> Self {
+        let result = &output.result;
+        let status = match &result.result {
+            ExecutionResult::Success { .. } => "success".to_owned(),
+            ExecutionResult::Revert { output } => format!("revert: {output}"),
+            ExecutionResult::Halt { reason } => format!("halt: {reason}"),
+        };
+        let storage_writes: BTreeSet<_> = result
+            .logs
+            .storage_logs
+            .iter()
+            .filter(|log| log.log.is_write())
+            .map(|log| log.log.key)
+            .collect();
+
+        Self {
+            location,
+            status,
+            gas_used: result.statistics.gas_used,
+            gas_refunded: result.refunds.gas_refunded,
+            storage_writes: storage_writes.len(),
+            events: result.logs.events.len(),
+            compression_failed: output.compression_failed,
+            shadow_divergence: output.shadow_divergence,
+        }
+    }
+}
+
+impl fmt::Display for TxSummary {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{}: {}; gas used: {}, refunded: {}; storage writes: {}, events: {}",
+            self.location,
+            self.status,
+            self.gas_used,
+            self.gas_refunded,
+            self.storage_writes,
+            self.events
+        )?;
+        if self.compression_failed {
+            formatter.write_str("; bytecode compression failed")?;
+        }
+        if let Some(divergence) = &self.shadow_divergence {
+            write!(formatter, "\n  shadow VM: {divergence}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Divergence between two VMs on a single transaction.
+#[derive(Debug, Serialize)]
+pub(crate) struct TxDiff {
+    #[serde(flatten)]
+    pub location: TxLocation,
+    pub divergences: Vec<String>,
+}
+
+impl fmt::Display for TxDiff {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "{} diverged:", self.location)?;
+        for divergence in &self.divergences {
+            writeln!(formatter, "  - {divergence}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Replays the dump on the specified VM.
+pub(crate) fn replay(dump: &VmDump, spec: VmSpec) -> anyhow::Result<Vec<TxSummary>> {
+    let mut vm = ReplayVm::new(spec, dump)?;
+    let mut summaries = vec![];
+    for_each_step(&dump.l2_blocks, |step| {
+        match step {
+            ReplayStep::StartL2Block(env) => vm.start_new_l2_block(env),
+            ReplayStep::ExecuteTx(location, tx) => {
+                let output = vm.execute(tx.clone());
+                summaries.push(TxSummary::new(location, output));
+            }
+        }
+        ControlFlow::Continue(())
+    });
+    Ok(summaries)
+}
+
+/// Replays the dump on two VMs in lockstep and returns per-transaction divergences. If `stop_at_first` is set,
+/// replay stops on the first diverging transaction.
+pub(crate) fn diff(
+    dump: &VmDump,
+    main: VmSpec,
+    other: VmSpec,
+    stop_at_first: bool,
+) -> anyhow::Result<Vec<TxDiff>> {
+    let mut main_vm = ReplayVm::new(main, dump)?;
+    let mut other_vm = ReplayVm::new(other, dump)?;
+    let mut diffs = vec![];
+    for_each_step(&dump.l2_blocks, |step| {
+        match step {
+            ReplayStep::StartL2Block(env) => {
+                main_vm.start_new_l2_block(env);
+                other_vm.start_new_l2_block(env);
+            }
+            ReplayStep::ExecuteTx(location, tx) => {
+                let main_output = main_vm.execute(tx.clone());
+                let other_output = other_vm.execute(tx.clone());
+                let divergences = main_output.diff(&other_output);
+                if !divergences.is_empty() {
+                    diffs.push(TxDiff {
+                        location,
+                        divergences,
+                    });
+                    if stop_at_first {
+                        return ControlFlow::Break(());
+                    }
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    });
+    Ok(diffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_vm_spec() {
+        assert!(matches!("old".parse::<VmSpec>(), Ok(VmSpec::Legacy(None))));
+        assert!(matches!("new".parse::<VmSpec>(), Ok(VmSpec::Fast)));
+        assert!(matches!("shadow".parse::<VmSpec>(), Ok(VmSpec::Shadow)));
+        assert!(matches!(
+            "vm1_4_2".parse::<VmSpec>(),
+            Ok(VmSpec::Legacy(Some(VmVersion::Vm1_4_2)))
+        ));
+        assert!(matches!(
+            "VmGateway".parse::<VmSpec>(),
+            Ok(VmSpec::Legacy(Some(VmVersion::VmGateway)))
+        ));
+
+        let err = "fastest".parse::<VmSpec>().unwrap_err();
+        assert!(err.contains("unknown VM"), "{err}");
+    }
+
+    #[test]
+    fn vm_spec_roundtrip() {
+        for version in ALL_VM_VERSIONS {
+            let spec = VmSpec::Legacy(Some(version));
+            let parsed: VmSpec = spec.to_string().parse().unwrap();
+            assert_eq!(parsed.to_string(), spec.to_string());
+        }
+    }
+}
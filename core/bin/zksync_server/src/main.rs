@@ -27,6 +27,7 @@ use zksync_core_leftovers::{
     Component, Components,
 };
 use zksync_env_config::FromEnv;
+use zksync_node_framework::service::WiringGraph;
 
 use crate::node_builder::MainNodeBuilder;
 
@@ -71,6 +72,17 @@ struct Cli {
     /// Now the node framework is used by default and this argument is left for backward compatibility.
     #[arg(long)]
     use_node_framework: bool,
+    /// Instead of running the node, prints the dependency graph of its wiring layers (layers, resources, tasks)
+    /// in the specified format and validates it. Does not require access to Postgres or L1.
+    #[arg(long, value_enum)]
+    wiring_graph: Option<WiringGraphFormat>,
+}
+
+/// Output format for the wiring graph.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum WiringGraphFormat {
+    Json,
+    Dot,
 }
 
 #[derive(Debug, Clone)]
@@ -164,6 +176,15 @@ fn main() -> anyhow::Result<()> {
         secrets,
     )?;
 
+    if let Some(format) = opt.wiring_graph {
+        let node = if opt.genesis {
+            node.only_genesis()?
+        } else {
+            node.build(opt.components.0)?
+        };
+        return print_wiring_graph(node.wiring_graph(), format);
+    }
+
     let observability_guard = {
         // Observability initialization should be performed within tokio context.
         let _context_guard = node.runtime_handle().enter();
@@ -180,6 +201,22 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+fn print_wiring_graph(graph: &WiringGraph, format: WiringGraphFormat) -> anyhow::Result<()> {
+    let output = match format {
+        WiringGraphFormat::Json => {
+            serde_json::to_string_pretty(graph).context("failed serializing wiring graph")?
+        }
+        WiringGraphFormat::Dot => graph.to_dot(),
+    };
+    println!("{output}");
+
+    if let Err(errors) = graph.validate() {
+        let errors: Vec<_> = errors.iter().map(|err| format!("- {err}")).collect();
+        anyhow::bail!("Wiring graph is invalid:\n{}", errors.join("\n"));
+    }
+    Ok(())
+}
+
 fn load_env_config() -> anyhow::Result<TempConfigStore> {
     Ok(TempConfigStore {
         postgres_config: PostgresConfig::from_env().ok(),
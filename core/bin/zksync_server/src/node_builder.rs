@@ -19,6 +19,7 @@ use zksync_node_api_server::{
 };
 use zksync_node_framework::{
     implementations::layers::{
+        address_index_backfill::{AddressIndex, AddressIndexBackfillLayer},
         base_token::{
             base_token_ratio_persister::BaseTokenRatioPersisterLayer,
             base_token_ratio_provider::BaseTokenRatioProviderLayer, ExternalPriceApiLayer,
@@ -52,6 +53,7 @@ use zksync_node_framework::{
         prometheus_exporter::PrometheusExporterLayer,
         proof_data_handler::ProofDataHandlerLayer,
         query_eth_client::QueryEthClientLayer,
+        settlement_auditor::SettlementAuditorLayer,
         sigint::SigintHandlerLayer,
         state_keeper::{
             main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer,
@@ -283,7 +285,9 @@ impl MainNodeBuilder {
             self.contracts_config.l2_legacy_shared_bridge_addr,
             sk_config.l2_block_seal_queue_capacity,
         )
-        .with_protective_reads_persistence_enabled(sk_config.protective_reads_persistence_enabled);
+        .with_protective_reads_persistence_enabled(sk_config.protective_reads_persistence_enabled)
+        .with_address_index_enabled(self.address_index_enabled())
+        .with_token_transfer_index_enabled(self.token_transfer_index_enabled());
         let mempool_io_layer = MempoolIOLayer::new(
             self.genesis_config.l2_chain_id,
             sk_config.clone(),
@@ -450,9 +454,18 @@ impl MainNodeBuilder {
             with_extended_tracing: rpc_config.extended_api_tracing,
             ..Default::default()
         };
+        let mut internal_api_config =
+            InternalApiConfig::new(&rpc_config, &self.contracts_config, &self.genesis_config);
+        internal_api_config.settlement_mode = Some(
+            self.configs
+                .eth
+                .as_ref()
+                .and_then(|x| Some(x.gas_adjuster?.settlement_mode))
+                .unwrap_or(SettlementMode::SettlesToL1),
+        );
         self.node.add_layer(Web3ServerLayer::http(
             rpc_config.http_port,
-            InternalApiConfig::new(&rpc_config, &self.contracts_config, &self.genesis_config),
+            internal_api_config,
             optional_config,
         ));
 
@@ -491,9 +504,18 @@ impl MainNodeBuilder {
             with_extended_tracing: rpc_config.extended_api_tracing,
             ..Default::default()
         };
+        let mut internal_api_config =
+            InternalApiConfig::new(&rpc_config, &self.contracts_config, &self.genesis_config);
+        internal_api_config.settlement_mode = Some(
+            self.configs
+                .eth
+                .as_ref()
+                .and_then(|x| Some(x.gas_adjuster?.settlement_mode))
+                .unwrap_or(SettlementMode::SettlesToL1),
+        );
         self.node.add_layer(Web3ServerLayer::ws(
             rpc_config.ws_port,
-            InternalApiConfig::new(&rpc_config, &self.contracts_config, &self.genesis_config),
+            internal_api_config,
             optional_config,
         ));
 
@@ -705,12 +727,51 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_settlement_auditor_layer(mut self) -> anyhow::Result<Self> {
+        /// Number of already settled L1 batches audited when the auditor starts for the first time.
+        const MAX_BATCHES_TO_RECHECK: u32 = 10;
+
+        self.node.add_layer(SettlementAuditorLayer::new(
+            self.contracts_config.diamond_proxy_addr,
+            MAX_BATCHES_TO_RECHECK,
+            self.genesis_config.l2_chain_id,
+        ));
+        Ok(self)
+    }
+
     fn add_logs_bloom_backfill_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(LogsBloomBackfillLayer);
 
         Ok(self)
     }
 
+    fn address_index_enabled(&self) -> bool {
+        self.configs
+            .api_config
+            .as_ref()
+            .is_some_and(|config| config.web3_json_rpc.address_index_enabled)
+    }
+
+    fn token_transfer_index_enabled(&self) -> bool {
+        self.configs
+            .api_config
+            .as_ref()
+            .is_some_and(|config| config.web3_json_rpc.token_transfer_index_enabled)
+    }
+
+    fn add_address_index_backfill_layer(mut self) -> anyhow::Result<Self> {
+        if self.address_index_enabled() {
+            self.node
+                .add_layer(AddressIndexBackfillLayer::new(AddressIndex::Transactions));
+        }
+        if self.token_transfer_index_enabled() {
+            self.node
+                .add_layer(AddressIndexBackfillLayer::new(AddressIndex::TokenTransfers));
+        }
+
+        Ok(self)
+    }
+
     /// This layer will make sure that the database is initialized correctly,
     /// e.g. genesis will be performed if it's required.
     ///
@@ -784,7 +845,8 @@ impl MainNodeBuilder {
                         .add_l1_gas_layer()?
                         .add_storage_initialization_layer(LayerKind::Task)?
                         .add_state_keeper_layer()?
-                        .add_logs_bloom_backfill_layer()?;
+                        .add_logs_bloom_backfill_layer()?
+                        .add_address_index_backfill_layer()?;
                 }
                 Component::HttpApi => {
                     self = self
@@ -864,6 +926,9 @@ impl MainNodeBuilder {
                     tracing::info!("L2 denylist enabled.");
                     deny_list_enabled = true;
                 }
+                Component::SettlementAuditor => {
+                    self = self.add_settlement_auditor_layer()?;
+                }
             }
         }
         Ok(self.node.build())
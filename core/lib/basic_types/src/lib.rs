@@ -35,6 +35,7 @@ mod conversions;
 pub mod network;
 pub mod protocol_version;
 pub mod prover_dal;
+pub mod pruning;
 pub mod pubdata_da;
 pub mod secrets;
 pub mod serde_wrappers;
@@ -0,0 +1,128 @@
+//! Basic types related to Postgres pruning.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Class of data that can be removed from Postgres by pruning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrunedDataClass {
+    /// L1 batch and L2 block headers.
+    Blocks,
+    /// Transaction data, receipts, events and L2-to-L1 logs.
+    Transactions,
+    /// VM call traces for transactions.
+    CallTraces,
+    /// Historical (i.e., overwritten) storage values.
+    StateHistory,
+}
+
+impl PrunedDataClass {
+    pub const ALL: [Self; 4] = [
+        Self::Blocks,
+        Self::Transactions,
+        Self::CallTraces,
+        Self::StateHistory,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Blocks => "blocks",
+            Self::Transactions => "transactions",
+            Self::CallTraces => "call traces",
+            Self::StateHistory => "state history",
+        }
+    }
+}
+
+impl fmt::Display for PrunedDataClass {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+/// Profile specifying which [data classes](PrunedDataClass) are removed by pruning.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PruningProfile {
+    /// Remove all data for pruned L1 batches.
+    #[default]
+    Full,
+    /// Retain blocks, transactions, receipts and events; remove call traces and historical storage values.
+    /// Useful for nodes backing explorers.
+    KeepTransactions,
+    /// Retain blocks and historical storage values; remove transactions, receipts, events and call traces.
+    /// Useful for nodes serving historical state queries (e.g., `eth_call` or `eth_getBalance` for old blocks).
+    KeepState,
+}
+
+impl PruningProfile {
+    pub const ALL: [Self; 3] = [Self::Full, Self::KeepTransactions, Self::KeepState];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::KeepTransactions => "keep_transactions",
+            Self::KeepState => "keep_state",
+        }
+    }
+
+    /// Checks whether this profile removes the specified data class.
+    pub fn prunes(self, class: PrunedDataClass) -> bool {
+        match self {
+            Self::Full => true,
+            Self::KeepTransactions => matches!(
+                class,
+                PrunedDataClass::CallTraces | PrunedDataClass::StateHistory
+            ),
+            Self::KeepState => matches!(
+                class,
+                PrunedDataClass::Transactions | PrunedDataClass::CallTraces
+            ),
+        }
+    }
+}
+
+impl fmt::Display for PruningProfile {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PruningProfile {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(Self::Full),
+            "keep_transactions" => Ok(Self::KeepTransactions),
+            "keep_state" => Ok(Self::KeepState),
+            _ => Err(
+                "Incorrect pruning profile; expected one of `full`, `keep_transactions`, `keep_state`",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pruning_profile_roundtrip() {
+        for profile in PruningProfile::ALL {
+            assert_eq!(profile.as_str().parse::<PruningProfile>(), Ok(profile));
+            let json = serde_json::to_value(profile).unwrap();
+            assert_eq!(json, profile.as_str());
+        }
+    }
+
+    #[test]
+    fn full_profile_prunes_all_data() {
+        for class in PrunedDataClass::ALL {
+            assert!(PruningProfile::Full.prunes(class));
+        }
+        assert!(!PruningProfile::KeepTransactions.prunes(PrunedDataClass::Blocks));
+        assert!(!PruningProfile::KeepState.prunes(PrunedDataClass::Blocks));
+    }
+}
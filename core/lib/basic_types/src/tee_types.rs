@@ -7,12 +7,14 @@ use serde::{Deserialize, Serialize};
 #[non_exhaustive]
 pub enum TeeType {
     Sgx,
+    Tdx,
 }
 
 impl fmt::Display for TeeType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TeeType::Sgx => write!(f, "sgx"),
+            TeeType::Tdx => write!(f, "tdx"),
         }
     }
 }
@@ -28,8 +30,10 @@ mod tests {
         let json_str = "\"sgx\"";
         let tee_type: TeeType = serde_json::from_str(json_str).unwrap();
         assert_eq!(tee_type, TeeType::Sgx);
+        let tee_type: TeeType = serde_json::from_str("\"tdx\"").unwrap();
+        assert_eq!(tee_type, TeeType::Tdx);
 
-        for json_str in &["\"Sgx\"", "\"SGX\""] {
+        for json_str in &["\"Sgx\"", "\"SGX\"", "\"TDX\""] {
             let result: Result<TeeType, _> = serde_json::from_str(json_str);
             assert!(result.is_err());
         }
@@ -45,5 +49,6 @@ mod tests {
     #[test]
     fn test_display_teetype() {
         assert_eq!(TeeType::Sgx.to_string(), "sgx");
+        assert_eq!(TeeType::Tdx.to_string(), "tdx");
     }
 }
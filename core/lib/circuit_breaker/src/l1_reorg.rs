@@ -0,0 +1,35 @@
+use anyhow::Context as _;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+
+use crate::{CircuitBreaker, CircuitBreakerError};
+
+/// Trips if the Ethereum watcher has detected an L1 / settlement layer reorg orphaning blocks with processed events.
+#[derive(Debug)]
+pub struct L1ReorgChecker {
+    pub pool: ConnectionPool<Core>,
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for L1ReorgChecker {
+    fn name(&self) -> &'static str {
+        "l1_reorg"
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let reorg = self
+            .pool
+            .connection_tagged("circuit_breaker")
+            .await?
+            .eth_watcher_dal()
+            .get_detected_reorg()
+            .await
+            .context("cannot get detected L1 reorgs")?;
+        if let Some(reorg) = reorg {
+            return Err(CircuitBreakerError::L1Reorg {
+                chain_id: reorg.chain_id.0,
+                block_number: reorg.block_number,
+            });
+        }
+        Ok(())
+    }
+}
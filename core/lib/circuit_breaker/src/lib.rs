@@ -3,6 +3,7 @@ use std::{fmt, sync::Arc, time::Duration};
 use thiserror::Error;
 use tokio::sync::{watch, Mutex};
 
+pub mod l1_reorg;
 pub mod l1_txs;
 mod metrics;
 pub mod replication_lag;
@@ -33,6 +34,8 @@ impl CircuitBreakers {
 pub enum CircuitBreakerError {
     #[error("System has failed L1 transaction")]
     FailedL1Transaction,
+    #[error("L1 reorg on chain {chain_id} orphaned block #{block_number} with processed events")]
+    L1Reorg { chain_id: u64, block_number: u64 },
     #[error("Replication lag ({lag:?}) is above the threshold ({threshold:?})")]
     ReplicationLag { lag: Duration, threshold: Duration },
     #[error("Internal error running circuit breaker checks")]
@@ -224,6 +224,19 @@ pub struct Web3JsonRpcConfig {
     /// (hundreds or thousands RPS).
     #[serde(default)]
     pub extended_api_tracing: bool,
+    /// Enables the index of transactions by address used by the `zks_getTransactionsByAddress` method.
+    /// The index is maintained by the state keeper and is backfilled for blocks sealed before it was enabled.
+    /// The index should not be disabled once enabled; otherwise, it will miss transactions sealed in the meantime.
+    #[serde(default)]
+    pub address_index_enabled: bool,
+    /// Enables the index of token transfers used by the `zks_getTokenTransfers` and `zks_getBalanceHistory` methods.
+    /// Like the address index, it is maintained by the state keeper, is backfilled for blocks sealed before
+    /// it was enabled, and should not be disabled once enabled.
+    #[serde(default)]
+    pub token_transfer_index_enabled: bool,
+    /// Max number of simulation sessions (see `zks_createSimulationSession`) to be in the state at once.
+    /// If not set, simulation sessions are disabled.
+    pub simulation_sessions_limit: Option<u32>,
 }
 
 impl Web3JsonRpcConfig {
@@ -264,6 +277,9 @@ impl Web3JsonRpcConfig {
             whitelisted_tokens_for_aa: vec![],
             api_namespaces: None,
             extended_api_tracing: false,
+            address_index_enabled: false,
+            token_transfer_index_enabled: false,
+            simulation_sessions_limit: None,
         }
     }
 
@@ -132,6 +132,31 @@ pub struct StateKeeperConfig {
     #[serde(default)]
     pub protective_reads_persistence_enabled: bool,
 
+    /// Maximum age (in ms) of a non-empty L1 batch when fee-aware sealing is used. If set, fee-aware sealing is enabled:
+    /// the state keeper estimates the L1 overhead of the batch based on `batch_overhead_l1_gas` and the L1 gas price,
+    /// and may seal the batch before `block_commit_deadline_ms` or hold it open past this deadline, but never longer
+    /// than this value.
+    #[serde(default)]
+    pub fee_aware_seal_max_latency_ms: Option<u64>,
+    /// Minimum age (in ms) of an L1 batch before it can be sealed early by the fee-aware sealer.
+    #[serde(default)]
+    pub fee_aware_seal_min_latency_ms: u64,
+    /// L1 gas price (in wei) at or below which the fee-aware sealer seals a batch once it's older than
+    /// `fee_aware_seal_min_latency_ms`.
+    #[serde(default)]
+    pub fee_aware_seal_cheap_l1_gas_price: Option<u64>,
+    /// Batch overhead per transaction (in wei) above which the fee-aware sealer holds the batch open
+    /// past `block_commit_deadline_ms`.
+    #[serde(default)]
+    pub fee_aware_seal_max_overhead_per_tx: Option<u64>,
+    /// Per-batch quotas for classes of transactions.
+    #[serde(default)]
+    pub transaction_quotas: Vec<TransactionQuotaConfig>,
+    /// Trusted Multicall3 contracts. Calls aggregated via these contracts are attributed to transaction quotas
+    /// of the call targets.
+    #[serde(default)]
+    pub transaction_quota_multicall_contracts: Vec<Address>,
+
     // Base system contract hashes, required only for generating genesis config.
     // #PLA-811
     #[deprecated(note = "Use GenesisConfig::bootloader_hash instead")]
@@ -178,6 +203,12 @@ impl StateKeeperConfig {
             save_call_traces: true,
             max_circuits_per_batch: 24100,
             protective_reads_persistence_enabled: true,
+            fee_aware_seal_max_latency_ms: None,
+            fee_aware_seal_min_latency_ms: 0,
+            fee_aware_seal_cheap_l1_gas_price: None,
+            fee_aware_seal_max_overhead_per_tx: None,
+            transaction_quotas: vec![],
+            transaction_quota_multicall_contracts: vec![],
             bootloader_hash: None,
             default_aa_hash: None,
             evm_emulator_hash: None,
@@ -186,6 +217,35 @@ impl StateKeeperConfig {
     }
 }
 
+/// Per-batch quota for a class of transactions. A transaction belongs to the class if it's an L1 -> L2 transaction
+/// and `l1_transactions` is set, or if it's an L2 transaction routed through one of `contracts` (as the recipient,
+/// the initiator account, the paymaster, or a call target aggregated by a trusted multicall contract).
+///
+/// Shares are specified relative to `max_gas_per_batch` (for gas) and `max_pubdata_per_batch` (for pubdata).
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+pub struct TransactionQuotaConfig {
+    /// Name of the quota used in logs and metrics.
+    pub name: String,
+    /// Whether L1 -> L2 transactions belong to the class.
+    #[serde(default)]
+    pub l1_transactions: bool,
+    /// Contracts and accounts whose transactions belong to the class.
+    #[serde(default)]
+    pub contracts: Vec<Address>,
+    /// Maximum share of batch gas that can be used by transactions in the class.
+    #[serde(default)]
+    pub max_gas_share: Option<f64>,
+    /// Maximum share of batch pubdata that can be used by transactions in the class.
+    #[serde(default)]
+    pub max_pubdata_share: Option<f64>,
+    /// Share of batch gas reserved for transactions in the class; other transactions cannot use it.
+    #[serde(default)]
+    pub reserved_gas_share: Option<f64>,
+    /// Share of batch pubdata reserved for transactions in the class; other transactions cannot use it.
+    #[serde(default)]
+    pub reserved_pubdata_share: Option<f64>,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct OperationsManagerConfig {
     /// Sleep time in ms when there is no new input data
@@ -1,4 +1,4 @@
-use std::num::{NonZeroU64, NonZeroUsize};
+use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
 
 use serde::Deserialize;
 use zksync_basic_types::{
@@ -16,8 +16,16 @@ pub struct ENConfig {
     // Main node configuration
     pub main_node_url: SensitiveUrl,
     pub main_node_rate_limit_rps: Option<NonZeroUsize>,
+    /// Fallback upstreams (e.g., trusted external nodes) used if the main node is unavailable or rate-limits requests.
+    #[serde(default)]
+    pub main_node_fallback_urls: Vec<SensitiveUrl>,
 
     pub bridge_addresses_refresh_interval_sec: Option<NonZeroU64>,
 
     pub gateway_chain_id: Option<SLChainId>,
+
+    // Reorg recovery
+    #[serde(default)]
+    pub reorg_auto_recovery_enabled: bool,
+    pub reorg_auto_recovery_max_l1_batches: Option<NonZeroU32>,
 }
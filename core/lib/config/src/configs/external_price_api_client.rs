@@ -1,11 +1,22 @@
 use std::time::Duration;
 
 use serde::Deserialize;
+use zksync_basic_types::Address;
 
 pub const DEFAULT_TIMEOUT_MS: u64 = 10_000;
 
 pub const DEFAULT_FORCED_NEXT_VALUE_FLUCTUATION: u32 = 3;
 
+pub const DEFAULT_ONCHAIN_ORACLE_MAX_ANSWER_AGE_SEC: u64 = 60 * 60;
+
+pub const DEFAULT_ONCHAIN_ORACLE_TWAP_WINDOW_SEC: u32 = 30 * 60;
+
+pub const DEFAULT_COMPOSITE_MIN_SOURCES: u32 = 1;
+
+pub const DEFAULT_COMPOSITE_MAX_DEVIATION_PERCENT: u32 = 10;
+
+pub const DEFAULT_COMPOSITE_MAX_QUOTE_AGE_MS: u64 = 5 * 60 * 1_000;
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ForcedPriceClientConfig {
     /// Forced conversion ratio
@@ -21,6 +32,73 @@ pub struct ForcedPriceClientConfig {
     pub next_value_fluctuation: u32,
 }
 
+/// Configuration of price clients reading the base token price from an L1 contract.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OnchainOraclePriceClientConfig {
+    /// Address of the oracle contract on L1. For the `chainlink` source, this is a Chainlink aggregator
+    /// reporting the base token price in ETH; for the `uniswap_v3_twap` source, this is a Uniswap v3 pool
+    /// pairing the base token with WETH.
+    pub contract_address: Address,
+    /// Maximum age of the latest Chainlink answer. Older answers are rejected. Only used by the `chainlink` source.
+    #[serde(default = "OnchainOraclePriceClientConfig::default_max_answer_age_sec")]
+    pub max_answer_age_sec: u64,
+    /// Time window over which the Uniswap v3 TWAP is computed. Only used by the `uniswap_v3_twap` source.
+    #[serde(default = "OnchainOraclePriceClientConfig::default_twap_window_sec")]
+    pub twap_window_sec: u32,
+}
+
+impl OnchainOraclePriceClientConfig {
+    fn default_max_answer_age_sec() -> u64 {
+        DEFAULT_ONCHAIN_ORACLE_MAX_ANSWER_AGE_SEC
+    }
+
+    fn default_twap_window_sec() -> u32 {
+        DEFAULT_ONCHAIN_ORACLE_TWAP_WINDOW_SEC
+    }
+
+    pub fn max_answer_age(&self) -> Duration {
+        Duration::from_secs(self.max_answer_age_sec)
+    }
+}
+
+/// Configuration of the composite price client, which queries several price sources
+/// and aggregates their quotes.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CompositePriceClientConfig {
+    /// Underlying price sources. Each source is configured in the same way as a standalone client;
+    /// composite sources cannot be nested.
+    pub sources: Vec<ExternalPriceApiClientConfig>,
+    /// Minimum number of quotes that must pass staleness and deviation checks for the aggregated
+    /// ratio to be returned.
+    #[serde(default = "CompositePriceClientConfig::default_min_sources")]
+    pub min_sources: u32,
+    /// Maximum deviation (in percent) of a quote from the median of all fresh quotes.
+    /// Quotes deviating more are rejected as outliers.
+    #[serde(default = "CompositePriceClientConfig::default_max_deviation_percent")]
+    pub max_deviation_percent: u32,
+    /// Quotes older than this are considered stale and are rejected.
+    #[serde(default = "CompositePriceClientConfig::default_max_quote_age_ms")]
+    pub max_quote_age_ms: u64,
+}
+
+impl CompositePriceClientConfig {
+    fn default_min_sources() -> u32 {
+        DEFAULT_COMPOSITE_MIN_SOURCES
+    }
+
+    fn default_max_deviation_percent() -> u32 {
+        DEFAULT_COMPOSITE_MAX_DEVIATION_PERCENT
+    }
+
+    fn default_max_quote_age_ms() -> u64 {
+        DEFAULT_COMPOSITE_MAX_QUOTE_AGE_MS
+    }
+
+    pub fn max_quote_age(&self) -> Duration {
+        Duration::from_millis(self.max_quote_age_ms)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ExternalPriceApiClientConfig {
     pub source: String,
@@ -29,6 +107,10 @@ pub struct ExternalPriceApiClientConfig {
     #[serde(default = "ExternalPriceApiClientConfig::default_timeout")]
     pub client_timeout_ms: u64,
     pub forced: Option<ForcedPriceClientConfig>,
+    /// Configuration for the `chainlink` and `uniswap_v3_twap` sources.
+    pub onchain_oracle: Option<OnchainOraclePriceClientConfig>,
+    /// Configuration for the `composite` source.
+    pub composite: Option<CompositePriceClientConfig>,
 }
 
 impl ExternalPriceApiClientConfig {
@@ -1,7 +1,63 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 
 /// Configuration for the house keeper.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct HouseKeeperConfig {
     pub l1_batch_metrics_reporting_interval_ms: u64,
+    /// Interval between settlement SLA checks. If not set, settlement SLA tracking is disabled.
+    #[serde(default)]
+    pub settlement_sla_check_interval_ms: Option<u64>,
+    /// Number of the latest sealed L1 batches used to compute rolling settlement latency percentiles.
+    #[serde(default = "HouseKeeperConfig::default_settlement_sla_window_size")]
+    pub settlement_sla_window_size: u32,
+    /// Percentile of settlement stage latencies compared against SLAs, in the `(0, 100]` range.
+    #[serde(default = "HouseKeeperConfig::default_settlement_sla_percentile")]
+    pub settlement_sla_percentile: f64,
+    /// SLA for the latency between sealing and committing an L1 batch.
+    #[serde(default)]
+    pub settlement_sla_commit_latency_ms: Option<u64>,
+    /// SLA for the latency between committing and proving an L1 batch.
+    #[serde(default)]
+    pub settlement_sla_prove_latency_ms: Option<u64>,
+    /// SLA for the latency between proving and executing an L1 batch.
+    #[serde(default)]
+    pub settlement_sla_execute_latency_ms: Option<u64>,
+    /// SLA for the latency between sealing an L1 batch and dispatching its pubdata to the DA layer.
+    #[serde(default)]
+    pub settlement_sla_da_dispatch_latency_ms: Option<u64>,
+    /// SLA for the latency between dispatching L1 batch pubdata to the DA layer and its inclusion.
+    #[serde(default)]
+    pub settlement_sla_da_inclusion_latency_ms: Option<u64>,
+}
+
+impl HouseKeeperConfig {
+    const fn default_settlement_sla_window_size() -> u32 {
+        100
+    }
+
+    const fn default_settlement_sla_percentile() -> f64 {
+        95.0
+    }
+
+    /// Creates a config with settlement SLA tracking disabled.
+    pub fn new(l1_batch_metrics_reporting_interval_ms: u64) -> Self {
+        Self {
+            l1_batch_metrics_reporting_interval_ms,
+            settlement_sla_check_interval_ms: None,
+            settlement_sla_window_size: Self::default_settlement_sla_window_size(),
+            settlement_sla_percentile: Self::default_settlement_sla_percentile(),
+            settlement_sla_commit_latency_ms: None,
+            settlement_sla_prove_latency_ms: None,
+            settlement_sla_execute_latency_ms: None,
+            settlement_sla_da_dispatch_latency_ms: None,
+            settlement_sla_da_inclusion_latency_ms: None,
+        }
+    }
+
+    pub fn settlement_sla_check_interval(&self) -> Option<Duration> {
+        self.settlement_sla_check_interval_ms
+            .map(Duration::from_millis)
+    }
 }
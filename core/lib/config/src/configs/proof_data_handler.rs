@@ -1,7 +1,7 @@
 use std::time::Duration;
 
 use serde::Deserialize;
-use zksync_basic_types::L1BatchNumber;
+use zksync_basic_types::{L1BatchNumber, H256};
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct TeeConfig {
@@ -15,6 +15,31 @@ pub struct TeeConfig {
     pub tee_proof_generation_timeout_in_secs: u16,
     /// Timeout in hours after which a batch will be permanently ignored if repeated retries failed.
     pub tee_batch_permanently_ignored_timeout_in_hours: u16,
+    /// If true, attestation quotes are verified when registered, and TEE proofs are only accepted
+    /// if they are signed by a key bound to a successfully verified attestation.
+    #[serde(default = "TeeConfig::default_tee_verify_attestations")]
+    pub tee_verify_attestations: bool,
+    /// Path to the PEM-encoded root CA certificate that PCK certificate chains in attestation quotes
+    /// must chain to (the Intel SGX Root CA in production). Required if attestation verification is enabled.
+    #[serde(default)]
+    pub tee_attestation_root_ca_path: Option<String>,
+    /// Path to the JSON bundle with Intel DCAP collateral (CRLs, TCB infos and QE identities) used to check
+    /// revocation and TCB status of attested platforms. Required if attestation verification is enabled.
+    #[serde(default)]
+    pub tee_attestation_collateral_path: Option<String>,
+    /// TCB statuses (e.g., `SWHardeningNeeded`) accepted in addition to `UpToDate`.
+    #[serde(default)]
+    pub tee_attestation_allowed_tcb_statuses: Vec<String>,
+    /// Allowed MRENCLAVE values of SGX enclaves. An SGX quote is accepted if either its MRENCLAVE
+    /// or its MRSIGNER is allowed.
+    #[serde(default)]
+    pub tee_sgx_mrenclave_allowlist: Vec<H256>,
+    /// Allowed MRSIGNER values of SGX enclaves.
+    #[serde(default)]
+    pub tee_sgx_mrsigner_allowlist: Vec<H256>,
+    /// Allowed hex-encoded 48-byte MRTD values of TDX trust domains.
+    #[serde(default)]
+    pub tee_tdx_mrtd_allowlist: Vec<String>,
 }
 
 impl Default for TeeConfig {
@@ -26,6 +51,13 @@ impl Default for TeeConfig {
                 Self::default_tee_proof_generation_timeout_in_secs(),
             tee_batch_permanently_ignored_timeout_in_hours:
                 Self::default_tee_batch_permanently_ignored_timeout_in_hours(),
+            tee_verify_attestations: Self::default_tee_verify_attestations(),
+            tee_attestation_root_ca_path: None,
+            tee_attestation_collateral_path: None,
+            tee_attestation_allowed_tcb_statuses: vec![],
+            tee_sgx_mrenclave_allowlist: vec![],
+            tee_sgx_mrsigner_allowlist: vec![],
+            tee_tdx_mrtd_allowlist: vec![],
         }
     }
 }
@@ -47,6 +79,10 @@ impl TeeConfig {
         10 * 24
     }
 
+    pub fn default_tee_verify_attestations() -> bool {
+        false
+    }
+
     pub fn tee_proof_generation_timeout(&self) -> Duration {
         Duration::from_secs(self.tee_proof_generation_timeout_in_secs.into())
     }
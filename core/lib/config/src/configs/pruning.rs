@@ -1,6 +1,9 @@
 use std::num::NonZeroU64;
 
 use serde::Deserialize;
+use zksync_basic_types::pruning::PruningProfile;
+
+use crate::ObjectStoreConfig;
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct PruningConfig {
@@ -16,4 +19,10 @@ pub struct PruningConfig {
     /// the retention period greater than that implicitly imposed by other criteria (e.g., 7 or 30 days).
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 1 hour.
     pub data_retention_sec: Option<u64>,
+    /// If set, data is archived to the specified object store before it is hard-pruned from Postgres.
+    #[serde(default)]
+    pub archive_object_store: Option<ObjectStoreConfig>,
+    /// Profile determining which data classes are removed by pruning. By default, all data is removed.
+    #[serde(default)]
+    pub profile: PruningProfile,
 }
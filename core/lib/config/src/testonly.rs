@@ -7,6 +7,7 @@ use zksync_basic_types::{
     commitment::L1BatchCommitmentMode,
     network::Network,
     protocol_version::{ProtocolSemanticVersion, ProtocolVersionId, VersionPatch},
+    pruning::PruningProfile,
     pubdata_da::PubdataSendingMode,
     secrets::{APIKey, SeedPhrase},
     vm::FastVmMode,
@@ -49,6 +50,16 @@ impl Sample for Network {
     }
 }
 
+impl Sample for PruningProfile {
+    fn sample(rng: &mut (impl Rng + ?Sized)) -> PruningProfile {
+        match rng.gen_range(0..3) {
+            0 => PruningProfile::Full,
+            1 => PruningProfile::KeepTransactions,
+            _ => PruningProfile::KeepState,
+        }
+    }
+}
+
 impl Distribution<configs::chain::FeeModelVersion> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::chain::FeeModelVersion {
         type T = configs::chain::FeeModelVersion;
@@ -117,6 +128,9 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             api_namespaces: self
                 .sample_opt(|| self.sample_range(rng).map(|_| self.sample(rng)).collect()),
             extended_api_tracing: self.sample(rng),
+            address_index_enabled: self.sample(rng),
+            token_transfer_index_enabled: self.sample(rng),
+            simulation_sessions_limit: self.sample(rng),
         }
     }
 }
@@ -168,6 +182,20 @@ impl Distribution<configs::chain::NetworkConfig> for EncodeDist {
     }
 }
 
+impl Distribution<configs::chain::TransactionQuotaConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::chain::TransactionQuotaConfig {
+        configs::chain::TransactionQuotaConfig {
+            name: self.sample(rng),
+            l1_transactions: self.sample(rng),
+            contracts: self.sample_range(rng).map(|_| rng.gen()).collect(),
+            max_gas_share: self.sample_opt(|| rng.gen()),
+            max_pubdata_share: self.sample_opt(|| rng.gen()),
+            reserved_gas_share: self.sample_opt(|| rng.gen()),
+            reserved_pubdata_share: self.sample_opt(|| rng.gen()),
+        }
+    }
+}
+
 impl Distribution<configs::chain::StateKeeperConfig> for EncodeDist {
     #[allow(deprecated)]
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::chain::StateKeeperConfig {
@@ -196,6 +224,15 @@ impl Distribution<configs::chain::StateKeeperConfig> for EncodeDist {
             save_call_traces: self.sample(rng),
             max_circuits_per_batch: self.sample(rng),
             protective_reads_persistence_enabled: self.sample(rng),
+            fee_aware_seal_max_latency_ms: self.sample(rng),
+            fee_aware_seal_min_latency_ms: self.sample(rng),
+            fee_aware_seal_cheap_l1_gas_price: self.sample(rng),
+            fee_aware_seal_max_overhead_per_tx: self.sample(rng),
+            transaction_quotas: self.sample_collect(rng),
+            transaction_quota_multicall_contracts: self
+                .sample_range(rng)
+                .map(|_| rng.gen())
+                .collect(),
             // These values are not involved into files serialization skip them
             fee_account_addr: None,
             bootloader_hash: None,
@@ -643,6 +680,14 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::house_keeper::HouseKeeperConfig {
         configs::house_keeper::HouseKeeperConfig {
             l1_batch_metrics_reporting_interval_ms: self.sample(rng),
+            settlement_sla_check_interval_ms: self.sample(rng),
+            settlement_sla_window_size: self.sample(rng),
+            settlement_sla_percentile: rng.gen_range(1.0..=100.0),
+            settlement_sla_commit_latency_ms: self.sample(rng),
+            settlement_sla_prove_latency_ms: self.sample(rng),
+            settlement_sla_execute_latency_ms: self.sample(rng),
+            settlement_sla_da_dispatch_latency_ms: self.sample(rng),
+            settlement_sla_da_inclusion_latency_ms: self.sample(rng),
         }
     }
 }
@@ -688,6 +733,16 @@ impl Distribution<configs::ProofDataHandlerConfig> for EncodeDist {
                 first_tee_processed_batch: L1BatchNumber(rng.gen()),
                 tee_proof_generation_timeout_in_secs: self.sample(rng),
                 tee_batch_permanently_ignored_timeout_in_hours: self.sample(rng),
+                tee_verify_attestations: self.sample(rng),
+                tee_attestation_root_ca_path: self.sample(rng),
+                tee_attestation_collateral_path: self.sample(rng),
+                tee_attestation_allowed_tcb_statuses: self
+                    .sample_range(rng)
+                    .map(|_| self.sample(rng))
+                    .collect(),
+                tee_sgx_mrenclave_allowlist: self.sample_range(rng).map(|_| rng.gen()).collect(),
+                tee_sgx_mrsigner_allowlist: self.sample_range(rng).map(|_| rng.gen()).collect(),
+                tee_tdx_mrtd_allowlist: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
             },
         }
     }
@@ -948,8 +1003,14 @@ impl Distribution<configs::en_config::ENConfig> for EncodeDist {
                 _ => L1BatchCommitmentMode::Validium,
             },
             main_node_rate_limit_rps: self.sample_opt(|| rng.gen()),
+            main_node_fallback_urls: self
+                .sample_range(rng)
+                .map(|_| format!("localhost:{}", rng.gen::<u16>()).parse().unwrap())
+                .collect(),
             bridge_addresses_refresh_interval_sec: self.sample_opt(|| rng.gen()),
             gateway_chain_id: self.sample_opt(|| SLChainId(rng.gen())),
+            reorg_auto_recovery_enabled: self.sample(rng),
+            reorg_auto_recovery_max_l1_batches: self.sample_opt(|| rng.gen()),
         }
     }
 }
@@ -1070,6 +1131,8 @@ impl Distribution<configs::pruning::PruningConfig> for EncodeDist {
             chunk_size: self.sample(rng),
             removal_delay_sec: self.sample_opt(|| rng.gen()),
             data_retention_sec: self.sample(rng),
+            archive_object_store: self.sample(rng),
+            profile: Sample::sample(rng),
         }
     }
 }
@@ -1126,6 +1189,46 @@ impl Distribution<configs::external_price_api_client::ExternalPriceApiClientConf
                 fluctuation: self.sample(rng),
                 next_value_fluctuation: self.sample(rng),
             }),
+            onchain_oracle: self.sample(rng),
+            composite: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::external_price_api_client::OnchainOraclePriceClientConfig>
+    for EncodeDist
+{
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::external_price_api_client::OnchainOraclePriceClientConfig {
+        configs::external_price_api_client::OnchainOraclePriceClientConfig {
+            contract_address: rng.gen(),
+            max_answer_age_sec: self.sample(rng),
+            twap_window_sec: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::external_price_api_client::CompositePriceClientConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::external_price_api_client::CompositePriceClientConfig {
+        configs::external_price_api_client::CompositePriceClientConfig {
+            // Composite sources cannot be nested.
+            sources: self
+                .sample_range(rng)
+                .map(
+                    |_| configs::external_price_api_client::ExternalPriceApiClientConfig {
+                        composite: None,
+                        ..self.sample(rng)
+                    },
+                )
+                .collect(),
+            min_sources: self.sample(rng),
+            max_deviation_percent: self.sample(rng),
+            max_quote_age_ms: self.sample(rng),
         }
     }
 }
@@ -0,0 +1,3 @@
+ALTER TABLE tee_attestations
+    DROP COLUMN IF EXISTS tee_type,
+    DROP COLUMN IF EXISTS verified_at;
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS imported_pruned_l2_blocks;
+DROP TABLE IF EXISTS imported_pruned_l1_batches;
+
+ALTER TABLE pruning_log
+    DROP COLUMN IF EXISTS archive_location;
@@ -0,0 +1,21 @@
+-- Key of the pruned data archive in the object store; `NULL` if archiving was disabled.
+ALTER TABLE pruning_log
+    ADD COLUMN IF NOT EXISTS archive_location TEXT DEFAULT NULL;
+
+-- Data re-imported from pruned data archives for investigation. Not used by the node itself.
+CREATE TABLE IF NOT EXISTS imported_pruned_l1_batches (
+    number BIGINT PRIMARY KEY,
+    details JSONB NOT NULL,
+    archive_location TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS imported_pruned_l2_blocks (
+    number BIGINT PRIMARY KEY,
+    l1_batch_number BIGINT,
+    block JSONB NOT NULL,
+    transactions JSONB NOT NULL,
+    receipts JSONB NOT NULL,
+    archive_location TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
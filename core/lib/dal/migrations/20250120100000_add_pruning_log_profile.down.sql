@@ -0,0 +1,2 @@
+ALTER TABLE pruning_log
+    DROP COLUMN IF EXISTS profile;
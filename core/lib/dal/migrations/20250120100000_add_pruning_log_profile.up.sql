@@ -0,0 +1,3 @@
+-- Pruning profile used for the pruning iteration; determines which data classes were removed.
+ALTER TABLE pruning_log
+    ADD COLUMN IF NOT EXISTS profile TEXT NOT NULL DEFAULT 'full';
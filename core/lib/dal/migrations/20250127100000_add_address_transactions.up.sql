@@ -0,0 +1,14 @@
+-- Optional index of transactions by the initiator and recipient address. Maintained by the state keeper
+-- if the address index is enabled in the node configuration.
+CREATE TABLE IF NOT EXISTS address_transactions (
+    address BYTEA NOT NULL,
+    miniblock_number BIGINT NOT NULL,
+    index_in_block INT NOT NULL,
+    tx_hash BYTEA NOT NULL,
+    is_sender BOOLEAN NOT NULL,
+    is_recipient BOOLEAN NOT NULL,
+    PRIMARY KEY (address, miniblock_number, index_in_block)
+);
+
+CREATE INDEX IF NOT EXISTS address_transactions_miniblock_number_idx
+    ON address_transactions (miniblock_number);
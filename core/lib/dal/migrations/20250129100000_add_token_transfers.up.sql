@@ -0,0 +1,18 @@
+-- Optional index of token transfers, including base token balance changes. Maintained by the state keeper
+-- if the token transfer index is enabled in the node configuration.
+CREATE TABLE IF NOT EXISTS token_transfers (
+    miniblock_number BIGINT NOT NULL,
+    event_index_in_block INT NOT NULL,
+    tx_hash BYTEA NOT NULL,
+    tx_index_in_block INT NOT NULL,
+    token_address BYTEA NOT NULL,
+    from_address BYTEA NOT NULL,
+    to_address BYTEA NOT NULL,
+    amount BYTEA NOT NULL,
+    PRIMARY KEY (miniblock_number, event_index_in_block)
+);
+
+CREATE INDEX IF NOT EXISTS token_transfers_from_address_idx
+    ON token_transfers (from_address, token_address, miniblock_number);
+CREATE INDEX IF NOT EXISTS token_transfers_to_address_idx
+    ON token_transfers (to_address, token_address, miniblock_number);
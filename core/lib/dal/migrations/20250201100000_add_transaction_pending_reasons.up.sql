@@ -0,0 +1,10 @@
+-- Reasons why pending transactions are not picked up for execution. Maintained by the state keeper mempool
+-- and exposed via `zks_getTransactionStatusDetails`.
+CREATE TABLE IF NOT EXISTS transaction_pending_reasons (
+    tx_hash BYTEA PRIMARY KEY,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    -- Transactions can be replaced by changing their hash, hence `ON UPDATE CASCADE`.
+    FOREIGN KEY (tx_hash) REFERENCES transactions (hash) ON DELETE CASCADE ON UPDATE CASCADE
+);
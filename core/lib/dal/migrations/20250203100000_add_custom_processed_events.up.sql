@@ -0,0 +1,7 @@
+-- Processing progress of custom L1 event processors registered in `eth_watch`, keyed by the processor name.
+CREATE TABLE IF NOT EXISTS custom_processed_events (
+    name TEXT NOT NULL,
+    chain_id BIGINT NOT NULL,
+    next_block_to_process BIGINT NOT NULL,
+    PRIMARY KEY (chain_id, name)
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS eth_watcher_detected_reorgs;
+DROP TABLE IF EXISTS eth_watcher_block_hashes;
@@ -0,0 +1,21 @@
+-- Hashes of L1 / settlement layer blocks processed by `eth_watch`. Used to detect reorgs if events are processed
+-- after a certain number of confirmations rather than after the block is finalized.
+CREATE TABLE IF NOT EXISTS eth_watcher_block_hashes (
+    chain_id BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    block_hash BYTEA NOT NULL,
+    -- Whether any events from the block were consumed by the watcher.
+    has_processed_events BOOLEAN NOT NULL,
+    PRIMARY KEY (chain_id, block_number)
+);
+
+-- Reorgs detected by `eth_watch` that orphaned blocks with already processed events, and thus cannot be recovered from automatically.
+CREATE TABLE IF NOT EXISTS eth_watcher_detected_reorgs (
+    chain_id BIGINT NOT NULL,
+    -- Earliest orphaned block with processed events.
+    block_number BIGINT NOT NULL,
+    expected_block_hash BYTEA NOT NULL,
+    actual_block_hash BYTEA,
+    detected_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (chain_id, block_number)
+);
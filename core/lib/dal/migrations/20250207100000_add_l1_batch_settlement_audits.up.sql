@@ -0,0 +1,10 @@
+-- Results of auditing commit, prove and execute transactions of L1 batches on the settlement layer against local data.
+CREATE TABLE IF NOT EXISTS l1_batch_settlement_audits (
+    l1_batch_number BIGINT PRIMARY KEY REFERENCES l1_batches (number) ON DELETE CASCADE,
+    commit_status TEXT NOT NULL,
+    prove_status TEXT NOT NULL,
+    execute_status TEXT NOT NULL,
+    error TEXT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS eth_txs_confirmed_by_layer_idx;
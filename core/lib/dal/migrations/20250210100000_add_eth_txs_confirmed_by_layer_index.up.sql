@@ -0,0 +1,3 @@
+-- Speeds up looking up the latest confirmed transaction of each type for each settlement layer.
+CREATE INDEX IF NOT EXISTS eth_txs_confirmed_by_layer_idx ON eth_txs (is_gateway, tx_type, id)
+    WHERE confirmed_eth_tx_history_id IS NOT NULL;
@@ -0,0 +1,478 @@
+//! Optional index of transactions by the initiator and recipient address.
+
+use std::ops;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{api::AddressTransactionDirection, Address, L2BlockNumber, H256};
+use zksync_vm_interface::TransactionExecutionResult;
+
+use crate::Core;
+
+/// Reference to an indexed transaction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AddressTransactionRef {
+    pub l2_block_number: L2BlockNumber,
+    pub index_in_block: u32,
+    pub tx_hash: H256,
+}
+
+#[derive(Debug)]
+pub struct AddressTransactionsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl AddressTransactionsDal<'_, '_> {
+    /// Indexes transactions executed in the specified L2 block. Each transaction is indexed for its initiator
+    /// and, if it's different, for its recipient (the called contract).
+    pub async fn insert_address_transactions(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+        transactions: &[TransactionExecutionResult],
+    ) -> DalResult<()> {
+        let mut addresses = Vec::with_capacity(transactions.len() * 2);
+        let mut indices_in_block = Vec::with_capacity(transactions.len() * 2);
+        let mut tx_hashes = Vec::with_capacity(transactions.len() * 2);
+        let mut is_sender = Vec::with_capacity(transactions.len() * 2);
+        let mut is_recipient = Vec::with_capacity(transactions.len() * 2);
+
+        for (index_in_block, tx) in transactions.iter().enumerate() {
+            let initiator = tx.transaction.initiator_account();
+            let recipient = tx.transaction.recipient_account();
+            addresses.push(initiator.as_bytes().to_vec());
+            indices_in_block.push(index_in_block as i32);
+            tx_hashes.push(tx.hash.as_bytes().to_vec());
+            is_sender.push(true);
+            is_recipient.push(recipient == Some(initiator));
+
+            if let Some(recipient) = recipient.filter(|&addr| addr != initiator) {
+                addresses.push(recipient.as_bytes().to_vec());
+                indices_in_block.push(index_in_block as i32);
+                tx_hashes.push(tx.hash.as_bytes().to_vec());
+                is_sender.push(false);
+                is_recipient.push(true);
+            }
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            address_transactions (
+                address, miniblock_number, index_in_block, tx_hash, is_sender, is_recipient
+            )
+            SELECT
+                u.address,
+                $1,
+                u.index_in_block,
+                u.tx_hash,
+                u.is_sender,
+                u.is_recipient
+            FROM
+                UNNEST($2::bytea [], $3::INT [], $4::bytea [], $5::BOOL [], $6::BOOL [])
+                AS u (address, index_in_block, tx_hash, is_sender, is_recipient)
+            "#,
+            i64::from(l2_block_number.0),
+            &addresses,
+            &indices_in_block,
+            &tx_hashes,
+            &is_sender,
+            &is_recipient
+        )
+        .instrument("insert_address_transactions")
+        .with_arg("l2_block_number", &l2_block_number)
+        .with_arg("transactions.len", &transactions.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Indexes all transactions in the specified L2 block range based on the data in the `transactions` table.
+    /// Already indexed transactions are skipped. Returns the number of inserted index entries.
+    pub async fn backfill_address_transactions(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            INSERT INTO
+            address_transactions (
+                address, miniblock_number, index_in_block, tx_hash, is_sender, is_recipient
+            )
+            SELECT
+                initiator_address,
+                miniblock_number,
+                index_in_block,
+                hash,
+                TRUE,
+                contract_address IS NOT DISTINCT FROM initiator_address
+            FROM
+                transactions
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            UNION ALL
+            SELECT
+                contract_address,
+                miniblock_number,
+                index_in_block,
+                hash,
+                FALSE,
+                TRUE
+            FROM
+                transactions
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND contract_address IS NOT NULL
+                AND contract_address != initiator_address
+            ON CONFLICT DO NOTHING
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0)
+        )
+        .instrument("backfill_address_transactions")
+        .with_arg("l2_blocks", &l2_blocks)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    /// Returns the earliest L2 block containing indexed transactions.
+    pub async fn get_earliest_indexed_l2_block(&mut self) -> DalResult<Option<L2BlockNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(miniblock_number) AS "number"
+            FROM
+                address_transactions
+            "#
+        )
+        .instrument("get_earliest_indexed_l2_block")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.number.map(|number| L2BlockNumber(number as u32)))
+    }
+
+    /// Removes index entries for L2 blocks with numbers strictly greater than the specified `block_number`.
+    pub async fn roll_back_address_transactions(
+        &mut self,
+        block_number: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM address_transactions
+            WHERE
+                miniblock_number > $1
+            "#,
+            i64::from(block_number.0)
+        )
+        .instrument("roll_back_address_transactions")
+        .with_arg("block_number", &block_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns transactions involving `address` in the specified L2 block range, newest first.
+    /// Only transactions strictly before `before` (if specified) are returned.
+    pub async fn get_address_transactions(
+        &mut self,
+        address: Address,
+        direction: AddressTransactionDirection,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        before: Option<(L2BlockNumber, u32)>,
+        limit: usize,
+    ) -> DalResult<Vec<AddressTransactionRef>> {
+        let (include_sent, include_received) = match direction {
+            AddressTransactionDirection::Sent => (true, false),
+            AddressTransactionDirection::Received => (false, true),
+            AddressTransactionDirection::All => (true, true),
+        };
+        let (before_block, before_index) = match before {
+            Some((block, index)) => (i64::from(block.0), index as i32),
+            None => (i64::from(l2_blocks.end().0) + 1, 0),
+        };
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                index_in_block,
+                tx_hash
+            FROM
+                address_transactions
+            WHERE
+                address = $1
+                AND ((is_sender AND $2) OR (is_recipient AND $3))
+                AND miniblock_number BETWEEN $4 AND $5
+                AND (miniblock_number, index_in_block) < ($6, $7)
+            ORDER BY
+                miniblock_number DESC,
+                index_in_block DESC
+            LIMIT
+                $8
+            "#,
+            address.as_bytes(),
+            include_sent,
+            include_received,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            before_block,
+            before_index,
+            limit as i64
+        )
+        .instrument("get_address_transactions")
+        .with_arg("address", &address)
+        .with_arg("direction", &direction)
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("before", &before)
+        .with_arg("limit", &limit)
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| AddressTransactionRef {
+                l2_block_number: L2BlockNumber(row.miniblock_number as u32),
+                index_in_block: row.index_in_block as u32,
+                tx_hash: H256::from_slice(&row.tx_hash),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{ProtocolVersion, ProtocolVersionId, U256};
+    use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
+
+    use super::*;
+    use crate::{
+        tests::{create_l2_block_header, mock_execution_result, mock_l2_transaction},
+        ConnectionPool, CoreDal,
+    };
+
+    fn mock_executed_tx(
+        initiator: Address,
+        recipient: Address,
+    ) -> (H256, TransactionExecutionResult) {
+        let mut tx = mock_l2_transaction();
+        tx.common_data.initiator_address = initiator;
+        tx.execute.contract_address = Some(recipient);
+        (tx.hash(), mock_execution_result(tx))
+    }
+
+    fn hashes(refs: &[AddressTransactionRef]) -> Vec<H256> {
+        refs.iter().map(|tx_ref| tx_ref.tx_hash).collect()
+    }
+
+    #[tokio::test]
+    async fn indexing_and_paginating_address_transactions() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let alice = Address::repeat_byte(1);
+        let bob = Address::repeat_byte(2);
+
+        let (alice_to_bob, alice_to_bob_result) = mock_executed_tx(alice, bob);
+        let (bob_to_alice, bob_to_alice_result) = mock_executed_tx(bob, alice);
+        let (alice_to_self, alice_to_self_result) = mock_executed_tx(alice, alice);
+        conn.address_transactions_dal()
+            .insert_address_transactions(
+                L2BlockNumber(1),
+                &[alice_to_bob_result, bob_to_alice_result],
+            )
+            .await
+            .unwrap();
+        conn.address_transactions_dal()
+            .insert_address_transactions(L2BlockNumber(2), &[alice_to_self_result])
+            .await
+            .unwrap();
+
+        let all_blocks = L2BlockNumber(0)..=L2BlockNumber(10);
+        let mut dal = conn.address_transactions_dal();
+        let all_txs = dal
+            .get_address_transactions(
+                alice,
+                AddressTransactionDirection::All,
+                all_blocks.clone(),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            hashes(&all_txs),
+            [alice_to_self, bob_to_alice, alice_to_bob]
+        );
+        assert_eq!(all_txs[1].l2_block_number, L2BlockNumber(1));
+        assert_eq!(all_txs[1].index_in_block, 1);
+
+        let sent_txs = dal
+            .get_address_transactions(
+                alice,
+                AddressTransactionDirection::Sent,
+                all_blocks.clone(),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(hashes(&sent_txs), [alice_to_self, alice_to_bob]);
+        let received_txs = dal
+            .get_address_transactions(
+                alice,
+                AddressTransactionDirection::Received,
+                all_blocks.clone(),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(hashes(&received_txs), [alice_to_self, bob_to_alice]);
+
+        let first_page = dal
+            .get_address_transactions(
+                alice,
+                AddressTransactionDirection::All,
+                all_blocks.clone(),
+                None,
+                2,
+            )
+            .await
+            .unwrap();
+        assert_eq!(hashes(&first_page), [alice_to_self, bob_to_alice]);
+        let last_ref = first_page.last().unwrap();
+        let second_page = dal
+            .get_address_transactions(
+                alice,
+                AddressTransactionDirection::All,
+                all_blocks,
+                Some((last_ref.l2_block_number, last_ref.index_in_block)),
+                2,
+            )
+            .await
+            .unwrap();
+        assert_eq!(hashes(&second_page), [alice_to_bob]);
+
+        let block_range_txs = dal
+            .get_address_transactions(
+                alice,
+                AddressTransactionDirection::All,
+                L2BlockNumber(2)..=L2BlockNumber(2),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(hashes(&block_range_txs), [alice_to_self]);
+
+        dal.roll_back_address_transactions(L2BlockNumber(1))
+            .await
+            .unwrap();
+        let earliest_block = dal.get_earliest_indexed_l2_block().await.unwrap();
+        assert_eq!(earliest_block, Some(L2BlockNumber(1)));
+        let bob_txs = dal
+            .get_address_transactions(
+                bob,
+                AddressTransactionDirection::All,
+                L2BlockNumber(0)..=L2BlockNumber(10),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(hashes(&bob_txs), [bob_to_alice, alice_to_bob]);
+    }
+
+    #[tokio::test]
+    async fn backfilling_address_transactions() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(0))
+            .await
+            .unwrap();
+
+        let txs = [mock_l2_transaction(), mock_l2_transaction()];
+        for tx in &txs {
+            conn.transactions_dal()
+                .insert_transaction_l2(
+                    tx,
+                    TransactionExecutionMetrics::default(),
+                    ValidationTraces::default(),
+                )
+                .await
+                .unwrap();
+        }
+        let mut l2_block_header = create_l2_block_header(1);
+        l2_block_header.l2_tx_count = txs.len() as u16;
+        conn.blocks_dal()
+            .insert_l2_block(&l2_block_header)
+            .await
+            .unwrap();
+        let tx_results: Vec<_> = txs.iter().cloned().map(mock_execution_result).collect();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(1),
+                &tx_results,
+                U256::from(1),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+
+        let l2_blocks = L2BlockNumber(0)..=L2BlockNumber(1);
+        let inserted_count = conn
+            .address_transactions_dal()
+            .backfill_address_transactions(l2_blocks.clone())
+            .await
+            .unwrap();
+        // Each transaction is indexed both for its initiator and its recipient.
+        assert_eq!(inserted_count, 4);
+        let inserted_count = conn
+            .address_transactions_dal()
+            .backfill_address_transactions(l2_blocks.clone())
+            .await
+            .unwrap();
+        assert_eq!(inserted_count, 0);
+
+        for (i, tx) in txs.iter().enumerate() {
+            let sent_txs = conn
+                .address_transactions_dal()
+                .get_address_transactions(
+                    tx.initiator_account(),
+                    AddressTransactionDirection::Sent,
+                    l2_blocks.clone(),
+                    None,
+                    10,
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                sent_txs,
+                [AddressTransactionRef {
+                    l2_block_number: L2BlockNumber(1),
+                    index_in_block: i as u32,
+                    tx_hash: tx.hash(),
+                }]
+            );
+
+            let received_txs = conn
+                .address_transactions_dal()
+                .get_address_transactions(
+                    tx.recipient_account().unwrap(),
+                    AddressTransactionDirection::Received,
+                    l2_blocks.clone(),
+                    None,
+                    10,
+                )
+                .await
+                .unwrap();
+            assert_eq!(hashes(&received_txs), [tx.hash()]);
+        }
+    }
+}
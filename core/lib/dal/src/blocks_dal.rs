@@ -40,6 +40,20 @@ use crate::{
     Core, CoreDal,
 };
 
+/// Timestamps of settlement stages for a sealed L1 batch. Timestamps of stages that are not reached yet are `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1BatchStageTimestamps {
+    pub number: L1BatchNumber,
+    pub sealed_at: Option<DateTime<Utc>>,
+    pub committed_at: Option<DateTime<Utc>>,
+    pub proven_at: Option<DateTime<Utc>>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub da_dispatched_at: Option<DateTime<Utc>>,
+    pub da_included_at: Option<DateTime<Utc>>,
+    /// Whether the batch pubdata must be dispatched to a DA layer (i.e., the batch isn't a rollup one).
+    pub da_required: bool,
+}
+
 #[derive(Debug)]
 pub struct BlocksDal<'a, 'c> {
     pub(crate) storage: &'a mut Connection<'c, Core>,
@@ -2616,6 +2630,85 @@ impl BlocksDal<'_, '_> {
         .and_then(|row| row.sealed_at.map(|d| d.and_utc())))
     }
 
+    /// Returns settlement stage timestamps for up to `limit` latest sealed L1 batches, starting from the newest one.
+    /// The genesis batch is excluded since it's never settled in the usual way.
+    pub async fn get_l1_batch_stage_timestamps(
+        &mut self,
+        limit: usize,
+    ) -> DalResult<Vec<L1BatchStageTimestamps>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batches.number,
+                l1_batches.sealed_at,
+                commit_tx.confirmed_at AS "committed_at?",
+                prove_tx.confirmed_at AS "proven_at?",
+                execute_tx.confirmed_at AS "executed_at?",
+                data_availability.sent_at AS "da_dispatched_at?",
+                CASE
+                    WHEN data_availability.inclusion_data IS NOT NULL THEN data_availability.updated_at
+                END AS "da_included_at?",
+                (
+                    data_availability.l1_batch_number IS NOT NULL
+                    OR EXISTS (
+                        SELECT
+                            1
+                        FROM
+                            miniblocks
+                        WHERE
+                            miniblocks.l1_batch_number = l1_batches.number
+                            AND miniblocks.pubdata_type <> 'Rollup'
+                    )
+                ) AS "da_required!"
+            FROM
+                l1_batches
+            LEFT JOIN eth_txs_history AS commit_tx
+                ON (
+                    l1_batches.eth_commit_tx_id = commit_tx.eth_tx_id
+                    AND commit_tx.confirmed_at IS NOT NULL
+                )
+            LEFT JOIN eth_txs_history AS prove_tx
+                ON (
+                    l1_batches.eth_prove_tx_id = prove_tx.eth_tx_id
+                    AND prove_tx.confirmed_at IS NOT NULL
+                )
+            LEFT JOIN eth_txs_history AS execute_tx
+                ON (
+                    l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                    AND execute_tx.confirmed_at IS NOT NULL
+                )
+            LEFT JOIN data_availability
+                ON data_availability.l1_batch_number = l1_batches.number
+            WHERE
+                l1_batches.is_sealed
+                AND l1_batches.number > 0
+            ORDER BY
+                l1_batches.number DESC
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .instrument("get_l1_batch_stage_timestamps")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchStageTimestamps {
+                number: L1BatchNumber(row.number as u32),
+                sealed_at: row.sealed_at.map(|d| d.and_utc()),
+                committed_at: row.committed_at.map(|d| d.and_utc()),
+                proven_at: row.proven_at.map(|d| d.and_utc()),
+                executed_at: row.executed_at.map(|d| d.and_utc()),
+                da_dispatched_at: row.da_dispatched_at.map(|d| d.and_utc()),
+                da_included_at: row.da_included_at.map(|d| d.and_utc()),
+                da_required: row.da_required,
+            })
+            .collect())
+    }
+
     pub async fn set_protocol_version_for_pending_l2_blocks(
         &mut self,
         id: ProtocolVersionId,
@@ -3265,4 +3358,71 @@ mod tests {
             .unwrap()
             .is_none());
     }
+
+    #[tokio::test]
+    async fn loading_l1_batch_stage_timestamps() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 0..=2 {
+            insert_mock_l1_batch_header(&mut conn, &create_l1_batch_header(number)).await;
+        }
+
+        let committed_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        conn.eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(1),
+                AggregatedActionType::Commit,
+                H256::repeat_byte(1),
+                committed_at,
+                None,
+            )
+            .await
+            .unwrap();
+        let da_dispatched_at = DateTime::from_timestamp(1_700_000_010, 0).unwrap();
+        conn.data_availability_dal()
+            .insert_l1_batch_da(L1BatchNumber(1), "blob", da_dispatched_at.naive_utc())
+            .await
+            .unwrap();
+        conn.data_availability_dal()
+            .save_l1_batch_inclusion_data(L1BatchNumber(1), &[1; 32])
+            .await
+            .unwrap();
+
+        let timestamps = conn
+            .blocks_dal()
+            .get_l1_batch_stage_timestamps(10)
+            .await
+            .unwrap();
+        assert_eq!(timestamps.len(), 2);
+        let [latest, earliest] = timestamps.as_slice() else {
+            unreachable!();
+        };
+
+        assert_eq!(latest.number, L1BatchNumber(2));
+        assert!(latest.sealed_at.is_some());
+        assert_eq!(latest.committed_at, None);
+        assert_eq!(latest.da_dispatched_at, None);
+        assert_eq!(latest.da_included_at, None);
+        assert!(!latest.da_required);
+
+        assert_eq!(earliest.number, L1BatchNumber(1));
+        assert_eq!(earliest.committed_at, Some(committed_at));
+        assert_eq!(earliest.proven_at, None);
+        assert_eq!(earliest.executed_at, None);
+        assert_eq!(earliest.da_dispatched_at, Some(da_dispatched_at));
+        assert!(earliest.da_included_at.is_some());
+        assert!(earliest.da_required);
+
+        let timestamps = conn
+            .blocks_dal()
+            .get_l1_batch_stage_timestamps(1)
+            .await
+            .unwrap();
+        assert_eq!(timestamps.len(), 1);
+        assert_eq!(timestamps[0].number, L1BatchNumber(2));
+    }
 }
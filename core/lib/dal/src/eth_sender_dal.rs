@@ -1,4 +1,4 @@
-use std::{convert::TryFrom, str::FromStr};
+use std::{collections::BTreeMap, convert::TryFrom, str::FromStr};
 
 use anyhow::Context as _;
 use sqlx::types::chrono::{DateTime, Utc};
@@ -8,7 +8,9 @@ use zksync_db_connection::{
 };
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
+    api,
     eth_sender::{EthTx, EthTxBlobSidecar, TxHistory, TxHistoryToSend},
+    settlement::SettlementMode,
     Address, L1BatchNumber, SLChainId, H256, U256,
 };
 
@@ -86,6 +88,163 @@ impl EthSenderDal<'_, '_> {
         Ok(count.try_into().unwrap())
     }
 
+    /// Returns settlement stats for each layer the node has created settlement transactions for,
+    /// with L1 going first.
+    pub async fn get_settlement_layer_stats(
+        &mut self,
+    ) -> DalResult<Vec<api::SettlementLayerStats>> {
+        // Batches are settled in order, so the last batch for each layer and action type is covered by
+        // the latest confirmed transaction of this type. The lookup is covered by `eth_txs_confirmed_by_layer_idx`,
+        // and batches are then looked up by the indexed `eth_*_tx_id` columns.
+        let batch_rows = sqlx::query!(
+            r#"
+            SELECT
+                layers.is_gateway AS "is_gateway!",
+                tx_types.tx_type AS "tx_type!",
+                CASE tx_types.tx_type
+                    WHEN $1 THEN (
+                        SELECT
+                            MAX(number)
+                        FROM
+                            l1_batches
+                        WHERE
+                            eth_commit_tx_id = latest_tx.id
+                    )
+                    WHEN $2 THEN (
+                        SELECT
+                            MAX(number)
+                        FROM
+                            l1_batches
+                        WHERE
+                            eth_prove_tx_id = latest_tx.id
+                    )
+                    ELSE (
+                        SELECT
+                            MAX(number)
+                        FROM
+                            l1_batches
+                        WHERE
+                            eth_execute_tx_id = latest_tx.id
+                    )
+                END AS last_l1_batch
+            FROM
+                UNNEST(ARRAY[FALSE, TRUE]) AS layers (is_gateway)
+            CROSS JOIN UNNEST(ARRAY[$1, $2, $3]::TEXT []) AS tx_types (tx_type)
+            JOIN LATERAL (
+                SELECT
+                    id
+                FROM
+                    eth_txs
+                WHERE
+                    eth_txs.is_gateway = layers.is_gateway
+                    AND eth_txs.tx_type = tx_types.tx_type
+                    AND eth_txs.confirmed_eth_tx_history_id IS NOT NULL
+                ORDER BY
+                    id DESC
+                LIMIT
+                    1
+            ) latest_tx ON TRUE
+            "#,
+            AggregatedActionType::Commit.as_str(),
+            AggregatedActionType::PublishProofOnchain.as_str(),
+            AggregatedActionType::Execute.as_str()
+        )
+        .instrument("get_settlement_layer_stats#batches")
+        .fetch_all(self.storage)
+        .await?;
+
+        let inflight_rows = sqlx::query!(
+            r#"
+            SELECT
+                is_gateway,
+                COUNT(*) AS "count!"
+            FROM
+                eth_txs
+            WHERE
+                confirmed_eth_tx_history_id IS NULL
+            GROUP BY
+                is_gateway
+            "#
+        )
+        .instrument("get_settlement_layer_stats#inflight")
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut stats = BTreeMap::new();
+        let mut stats_for_layer = |is_gateway: bool| {
+            stats
+                .entry(is_gateway)
+                .or_insert_with(|| api::SettlementLayerStats {
+                    layer: if is_gateway {
+                        SettlementMode::Gateway
+                    } else {
+                        SettlementMode::SettlesToL1
+                    },
+                    last_committed_batch: None,
+                    last_proven_batch: None,
+                    last_executed_batch: None,
+                    inflight_txs: 0,
+                })
+        };
+
+        for row in batch_rows {
+            let Some(last_l1_batch) = row.last_l1_batch else {
+                continue;
+            };
+            let layer_stats = stats_for_layer(row.is_gateway);
+            let last_l1_batch = Some(L1BatchNumber(last_l1_batch as u32));
+            match AggregatedActionType::from_str(&row.tx_type) {
+                Ok(AggregatedActionType::Commit) => {
+                    layer_stats.last_committed_batch = last_l1_batch;
+                }
+                Ok(AggregatedActionType::PublishProofOnchain) => {
+                    layer_stats.last_proven_batch = last_l1_batch;
+                }
+                Ok(AggregatedActionType::Execute) => {
+                    layer_stats.last_executed_batch = last_l1_batch;
+                }
+                Err(err) => {
+                    tracing::warn!("Unexpected eth_txs.tx_type {:?}: {err}", row.tx_type);
+                }
+            }
+        }
+        for row in inflight_rows {
+            stats_for_layer(row.is_gateway).inflight_txs = row.count as u64;
+        }
+        Ok(stats.into_values().collect())
+    }
+
+    /// Returns the settlement layer and its chain ID (if known) for the latest created settlement transaction.
+    pub async fn get_latest_settlement_layer(
+        &mut self,
+    ) -> DalResult<Option<(SettlementMode, Option<SLChainId>)>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                is_gateway,
+                chain_id
+            FROM
+                eth_txs
+            ORDER BY
+                id DESC
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_latest_settlement_layer")
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| {
+            let layer = if row.is_gateway {
+                SettlementMode::Gateway
+            } else {
+                SettlementMode::SettlesToL1
+            };
+            (layer, row.chain_id.map(|id| SLChainId(id as u64)))
+        }))
+    }
+
     pub async fn get_unconfirmed_txs_count(&mut self) -> DalResult<usize> {
         let count = sqlx::query!(
             r#"
@@ -818,6 +977,58 @@ impl EthSenderDal<'_, '_> {
 
         Ok(())
     }
+
+    /// Returns the number of `eth_txs` referenced by L1 batches after the specified one, i.e., the transactions
+    /// that would be removed by [`Self::delete_eth_txs()`].
+    pub async fn count_eth_txs_for_l1_batches_after(
+        &mut self,
+        last_batch_to_keep: L1BatchNumber,
+    ) -> DalResult<u64> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                eth_txs
+            WHERE
+                id IN (
+                    (
+                        SELECT
+                            eth_commit_tx_id
+                        FROM
+                            l1_batches
+                        WHERE
+                            number > $1
+                    )
+                    UNION
+                    (
+                        SELECT
+                            eth_prove_tx_id
+                        FROM
+                            l1_batches
+                        WHERE
+                            number > $1
+                    )
+                    UNION
+                    (
+                        SELECT
+                            eth_execute_tx_id
+                        FROM
+                            l1_batches
+                        WHERE
+                            number > $1
+                    )
+                )
+            "#,
+            i64::from(last_batch_to_keep.0)
+        )
+        .instrument("count_eth_txs_for_l1_batches_after")
+        .with_arg("last_batch_to_keep", &last_batch_to_keep)
+        .fetch_one(self.storage)
+        .await?
+        .count;
+        Ok(count as u64)
+    }
 }
 
 /// These methods should only be used for tests.
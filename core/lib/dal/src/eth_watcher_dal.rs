@@ -1,5 +1,6 @@
+use chrono::NaiveDateTime;
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::SLChainId;
+use zksync_types::{SLChainId, H256};
 
 use crate::Core;
 
@@ -15,6 +16,27 @@ pub enum EventType {
     ChainBatchRoot,
 }
 
+/// Hash of an L1 / settlement layer block recorded by the watcher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EthWatcherBlockHash {
+    pub number: u64,
+    pub hash: H256,
+    /// Whether any events from the block were consumed by the watcher.
+    pub has_processed_events: bool,
+}
+
+/// Reorg that orphaned a block with processed events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedL1Reorg {
+    pub chain_id: SLChainId,
+    /// Earliest orphaned block with processed events.
+    pub block_number: u64,
+    pub expected_block_hash: H256,
+    /// Hash of the block in the current canonical chain, or `None` if the chain is shorter than `block_number`.
+    pub actual_block_hash: Option<H256>,
+    pub detected_at: NaiveDateTime,
+}
+
 impl EthWatcherDal<'_, '_> {
     // Returns last set value of next_block_to_process for given event_type and chain_id.
     // If the value was missing, initializes it with provided next_block_to_process value
@@ -97,6 +119,326 @@ impl EthWatcherDal<'_, '_> {
         .await?;
         Ok(())
     }
+
+    /// Same as [`Self::get_or_set_next_block_to_process()`], but for custom event processors
+    /// identified by `processor_name`.
+    pub async fn get_or_set_next_block_to_process_for_custom_events(
+        &mut self,
+        processor_name: &str,
+        chain_id: SLChainId,
+        next_block_to_process: u64,
+    ) -> DalResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            SELECT
+                next_block_to_process
+            FROM
+                custom_processed_events
+            WHERE
+                name = $1
+                AND chain_id = $2
+            "#,
+            processor_name,
+            chain_id.0 as i64
+        )
+        .instrument("get_or_set_next_block_to_process_for_custom_events")
+        .with_arg("processor_name", &processor_name)
+        .with_arg("chain_id", &chain_id)
+        .fetch_optional(self.storage)
+        .await?;
+
+        if let Some(row) = result {
+            Ok(row.next_block_to_process as u64)
+        } else {
+            sqlx::query!(
+                r#"
+                INSERT INTO
+                custom_processed_events (
+                    name,
+                    chain_id,
+                    next_block_to_process
+                )
+                VALUES
+                ($1, $2, $3)
+                "#,
+                processor_name,
+                chain_id.0 as i64,
+                next_block_to_process as i64
+            )
+            .instrument("get_or_set_next_block_to_process_for_custom_events - insert")
+            .with_arg("processor_name", &processor_name)
+            .with_arg("chain_id", &chain_id)
+            .execute(self.storage)
+            .await?;
+
+            Ok(next_block_to_process)
+        }
+    }
+
+    /// Same as [`Self::update_next_block_to_process()`], but for custom event processors
+    /// identified by `processor_name`.
+    pub async fn update_next_block_to_process_for_custom_events(
+        &mut self,
+        processor_name: &str,
+        chain_id: SLChainId,
+        next_block_to_process: u64,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE custom_processed_events
+            SET
+                next_block_to_process = $3
+            WHERE
+                name = $1
+                AND chain_id = $2
+            "#,
+            processor_name,
+            chain_id.0 as i64,
+            next_block_to_process as i64
+        )
+        .instrument("update_next_block_to_process_for_custom_events")
+        .with_arg("processor_name", &processor_name)
+        .with_arg("chain_id", &chain_id)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Records hashes of blocks processed by the watcher. If a hash for a block is already recorded, it's overwritten;
+    /// `has_processed_events` flags are merged.
+    ///
+    /// `hashes` must not contain duplicate block numbers; otherwise, the query will fail.
+    pub async fn insert_block_hashes(
+        &mut self,
+        chain_id: SLChainId,
+        hashes: &[EthWatcherBlockHash],
+    ) -> DalResult<()> {
+        let mut block_numbers = Vec::with_capacity(hashes.len());
+        let mut block_hashes = Vec::with_capacity(hashes.len());
+        let mut flags = Vec::with_capacity(hashes.len());
+        for entry in hashes {
+            block_numbers.push(entry.number as i64);
+            block_hashes.push(entry.hash.as_bytes().to_vec());
+            flags.push(entry.has_processed_events);
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            eth_watcher_block_hashes (
+                chain_id, block_number, block_hash, has_processed_events
+            )
+            SELECT
+                $1,
+                u.block_number,
+                u.block_hash,
+                u.has_processed_events
+            FROM
+                UNNEST($2::BIGINT [], $3::BYTEA [], $4::BOOLEAN [])
+                AS u (block_number, block_hash, has_processed_events)
+            ON CONFLICT (chain_id, block_number) DO
+            UPDATE
+            SET
+            block_hash = excluded.block_hash,
+            has_processed_events = (
+                eth_watcher_block_hashes.has_processed_events
+                OR excluded.has_processed_events
+            )
+            "#,
+            chain_id.0 as i64,
+            &block_numbers,
+            &block_hashes,
+            &flags
+        )
+        .instrument("insert_block_hashes")
+        .with_arg("chain_id", &chain_id)
+        .with_arg("hashes.len", &hashes.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns all recorded block hashes for the specified chain, starting from the newest block.
+    pub async fn get_block_hashes(
+        &mut self,
+        chain_id: SLChainId,
+    ) -> DalResult<Vec<EthWatcherBlockHash>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                block_number,
+                block_hash,
+                has_processed_events
+            FROM
+                eth_watcher_block_hashes
+            WHERE
+                chain_id = $1
+            ORDER BY
+                block_number DESC
+            "#,
+            chain_id.0 as i64
+        )
+        .instrument("get_block_hashes")
+        .with_arg("chain_id", &chain_id)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| EthWatcherBlockHash {
+                number: row.block_number as u64,
+                hash: H256::from_slice(&row.block_hash),
+                has_processed_events: row.has_processed_events,
+            })
+            .collect())
+    }
+
+    /// Removes block hashes recorded for blocks before `block_number`.
+    pub async fn prune_block_hashes(
+        &mut self,
+        chain_id: SLChainId,
+        block_number: u64,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_watcher_block_hashes
+            WHERE
+                chain_id = $1
+                AND block_number < $2
+            "#,
+            chain_id.0 as i64,
+            block_number as i64
+        )
+        .instrument("prune_block_hashes")
+        .with_arg("chain_id", &chain_id)
+        .with_arg("block_number", &block_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Reverts the watcher state for the specified chain so that all blocks after `last_valid_block` are processed
+    /// again: removes recorded block hashes and rewinds processing progress of all (built-in and custom) event processors.
+    /// Should be called in a transaction.
+    pub async fn revert_to_block(
+        &mut self,
+        chain_id: SLChainId,
+        last_valid_block: u64,
+    ) -> DalResult<()> {
+        let next_block_to_process = last_valid_block + 1;
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_watcher_block_hashes
+            WHERE
+                chain_id = $1
+                AND block_number > $2
+            "#,
+            chain_id.0 as i64,
+            last_valid_block as i64
+        )
+        .instrument("revert_to_block - remove_block_hashes")
+        .with_arg("chain_id", &chain_id)
+        .with_arg("last_valid_block", &last_valid_block)
+        .execute(self.storage)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE processed_events
+            SET
+                next_block_to_process = LEAST(next_block_to_process, $2)
+            WHERE
+                chain_id = $1
+            "#,
+            chain_id.0 as i64,
+            next_block_to_process as i64
+        )
+        .instrument("revert_to_block - rewind_processed_events")
+        .with_arg("chain_id", &chain_id)
+        .with_arg("last_valid_block", &last_valid_block)
+        .execute(self.storage)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE custom_processed_events
+            SET
+                next_block_to_process = LEAST(next_block_to_process, $2)
+            WHERE
+                chain_id = $1
+            "#,
+            chain_id.0 as i64,
+            next_block_to_process as i64
+        )
+        .instrument("revert_to_block - rewind_custom_processed_events")
+        .with_arg("chain_id", &chain_id)
+        .with_arg("last_valid_block", &last_valid_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Persists a reorg that cannot be recovered from automatically. Does nothing if the reorg is already recorded.
+    pub async fn insert_detected_reorg(
+        &mut self,
+        chain_id: SLChainId,
+        block_number: u64,
+        expected_block_hash: H256,
+        actual_block_hash: Option<H256>,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            eth_watcher_detected_reorgs (
+                chain_id, block_number, expected_block_hash, actual_block_hash, detected_at
+            )
+            VALUES
+            ($1, $2, $3, $4, NOW())
+            ON CONFLICT (chain_id, block_number) DO NOTHING
+            "#,
+            chain_id.0 as i64,
+            block_number as i64,
+            expected_block_hash.as_bytes(),
+            actual_block_hash.as_ref().map(H256::as_bytes)
+        )
+        .instrument("insert_detected_reorg")
+        .with_arg("chain_id", &chain_id)
+        .with_arg("block_number", &block_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the earliest detected reorg that cannot be recovered from automatically, if any.
+    pub async fn get_detected_reorg(&mut self) -> DalResult<Option<DetectedL1Reorg>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                chain_id,
+                block_number,
+                expected_block_hash,
+                actual_block_hash,
+                detected_at
+            FROM
+                eth_watcher_detected_reorgs
+            ORDER BY
+                detected_at
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_detected_reorg")
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| DetectedL1Reorg {
+            chain_id: SLChainId(row.chain_id as u64),
+            block_number: row.block_number as u64,
+            expected_block_hash: H256::from_slice(&row.expected_block_hash),
+            actual_block_hash: row.actual_block_hash.as_deref().map(H256::from_slice),
+            detected_at: row.detected_at,
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -152,4 +494,109 @@ mod tests {
             .expect("Failed to get or set next block to process");
         assert_eq!(next_block, 300);
     }
+
+    #[tokio::test]
+    async fn next_block_to_process_for_custom_events() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.eth_watcher_dal();
+
+        let next_block = dal
+            .get_or_set_next_block_to_process_for_custom_events("allowlist", SLChainId(1), 100)
+            .await
+            .unwrap();
+        assert_eq!(next_block, 100);
+        let next_block = dal
+            .get_or_set_next_block_to_process_for_custom_events("governance", SLChainId(1), 200)
+            .await
+            .unwrap();
+        assert_eq!(next_block, 200);
+
+        dal.update_next_block_to_process_for_custom_events("allowlist", SLChainId(1), 120)
+            .await
+            .unwrap();
+        let next_block = dal
+            .get_or_set_next_block_to_process_for_custom_events("allowlist", SLChainId(1), 150)
+            .await
+            .unwrap();
+        assert_eq!(next_block, 120);
+        let next_block = dal
+            .get_or_set_next_block_to_process_for_custom_events("governance", SLChainId(1), 250)
+            .await
+            .unwrap();
+        assert_eq!(next_block, 200);
+
+        // Custom processors should not interfere with the built-in ones.
+        let next_block = dal
+            .get_or_set_next_block_to_process(EventType::ProtocolUpgrades, SLChainId(1), 300)
+            .await
+            .unwrap();
+        assert_eq!(next_block, 300);
+    }
+
+    #[tokio::test]
+    async fn block_hashes_and_reverts() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.eth_watcher_dal();
+        let chain_id = SLChainId(1);
+
+        dal.get_or_set_next_block_to_process(EventType::PriorityTransactions, chain_id, 21)
+            .await
+            .unwrap();
+        dal.get_or_set_next_block_to_process_for_custom_events("allowlist", chain_id, 15)
+            .await
+            .unwrap();
+        let hashes: Vec<_> = [10, 15, 20]
+            .into_iter()
+            .map(|number| EthWatcherBlockHash {
+                number,
+                hash: H256::repeat_byte(number as u8),
+                has_processed_events: number == 15,
+            })
+            .collect();
+        dal.insert_block_hashes(chain_id, &hashes).await.unwrap();
+        // Flags must be merged on conflict.
+        let updated_hash = EthWatcherBlockHash {
+            has_processed_events: false,
+            ..hashes[1]
+        };
+        dal.insert_block_hashes(chain_id, &[updated_hash])
+            .await
+            .unwrap();
+
+        let mut expected_hashes = hashes.clone();
+        expected_hashes.reverse();
+        assert_eq!(
+            dal.get_block_hashes(chain_id).await.unwrap(),
+            expected_hashes
+        );
+        assert!(dal.get_block_hashes(SLChainId(2)).await.unwrap().is_empty());
+
+        dal.revert_to_block(chain_id, 12).await.unwrap();
+        assert_eq!(dal.get_block_hashes(chain_id).await.unwrap(), [hashes[0]]);
+        let next_block = dal
+            .get_or_set_next_block_to_process(EventType::PriorityTransactions, chain_id, 0)
+            .await
+            .unwrap();
+        assert_eq!(next_block, 13);
+        let next_block = dal
+            .get_or_set_next_block_to_process_for_custom_events("allowlist", chain_id, 0)
+            .await
+            .unwrap();
+        assert_eq!(next_block, 13);
+
+        dal.prune_block_hashes(chain_id, 11).await.unwrap();
+        assert!(dal.get_block_hashes(chain_id).await.unwrap().is_empty());
+
+        assert_eq!(dal.get_detected_reorg().await.unwrap(), None);
+        dal.insert_detected_reorg(chain_id, 15, H256::repeat_byte(15), None)
+            .await
+            .unwrap();
+        let reorg = dal.get_detected_reorg().await.unwrap().unwrap();
+        assert_eq!(reorg.chain_id, chain_id);
+        assert_eq!(reorg.block_number, 15);
+        assert_eq!(reorg.expected_block_hash, H256::repeat_byte(15));
+        assert_eq!(reorg.actual_block_hash, None);
+    }
 }
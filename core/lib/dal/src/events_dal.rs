@@ -118,6 +118,30 @@ impl EventsDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns the number of events emitted in L2 blocks after the specified one.
+    pub async fn count_events_after_l2_block(
+        &mut self,
+        block_number: L2BlockNumber,
+    ) -> DalResult<u64> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                events
+            WHERE
+                miniblock_number > $1
+            "#,
+            i64::from(block_number.0)
+        )
+        .instrument("count_events_after_l2_block")
+        .with_arg("block_number", &block_number)
+        .fetch_one(self.storage)
+        .await?
+        .count;
+        Ok(count as u64)
+    }
+
     /// Saves user L2-to-L1 logs from an L2 block. Logs must be ordered by transaction location
     /// and within each transaction.
     pub async fn save_user_l2_to_l1_logs(
@@ -198,6 +222,30 @@ impl EventsDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns the number of user L2-to-L1 logs emitted in L2 blocks after the specified one.
+    pub async fn count_l2_to_l1_logs_after_l2_block(
+        &mut self,
+        block_number: L2BlockNumber,
+    ) -> DalResult<u64> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                l2_to_l1_logs
+            WHERE
+                miniblock_number > $1
+            "#,
+            i64::from(block_number.0)
+        )
+        .instrument("count_l2_to_l1_logs_after_l2_block")
+        .with_arg("block_number", &block_number)
+        .fetch_one(self.storage)
+        .await?
+        .count;
+        Ok(count as u64)
+    }
+
     pub(crate) async fn get_logs_by_tx_hashes(
         &mut self,
         hashes: &[H256],
@@ -211,6 +211,30 @@ impl FactoryDepsDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns the number of factory deps added in L2 blocks after the specified one.
+    pub async fn count_factory_deps_after_l2_block(
+        &mut self,
+        block_number: L2BlockNumber,
+    ) -> DalResult<u64> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                factory_deps
+            WHERE
+                miniblock_number > $1
+            "#,
+            i64::from(block_number.0)
+        )
+        .instrument("count_factory_deps_after_l2_block")
+        .with_arg("block_number", &block_number)
+        .fetch_one(self.storage)
+        .await?
+        .count;
+        Ok(count as u64)
+    }
+
     /// Retrieves all factory deps entries for testing purposes.
     pub async fn dump_all_factory_deps_for_tests(&mut self) -> HashMap<H256, Vec<u8>> {
         sqlx::query!(
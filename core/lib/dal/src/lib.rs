@@ -12,21 +12,24 @@ pub use zksync_db_connection::{
 };
 
 use crate::{
-    base_token_dal::BaseTokenDal, blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal,
-    consensus_dal::ConsensusDal, contract_verification_dal::ContractVerificationDal,
+    address_transactions_dal::AddressTransactionsDal, base_token_dal::BaseTokenDal,
+    blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal, consensus_dal::ConsensusDal,
+    contract_verification_dal::ContractVerificationDal,
     custom_genesis_export_dal::CustomGenesisExportDal, data_availability_dal::DataAvailabilityDal,
     eth_sender_dal::EthSenderDal, eth_watcher_dal::EthWatcherDal, events_dal::EventsDal,
     events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
     proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, pruning_dal::PruningDal,
-    snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
-    snapshots_dal::SnapshotsDal, storage_logs_dal::StorageLogsDal,
-    storage_logs_dedup_dal::StorageLogsDedupDal, storage_web3_dal::StorageWeb3Dal,
-    sync_dal::SyncDal, system_dal::SystemDal, tee_proof_generation_dal::TeeProofGenerationDal,
+    settlement_audits_dal::SettlementAuditsDal, snapshot_recovery_dal::SnapshotRecoveryDal,
+    snapshots_creator_dal::SnapshotsCreatorDal, snapshots_dal::SnapshotsDal,
+    storage_logs_dal::StorageLogsDal, storage_logs_dedup_dal::StorageLogsDedupDal,
+    storage_web3_dal::StorageWeb3Dal, sync_dal::SyncDal, system_dal::SystemDal,
+    tee_proof_generation_dal::TeeProofGenerationDal, token_transfers_dal::TokenTransfersDal,
     tokens_dal::TokensDal, tokens_web3_dal::TokensWeb3Dal, transactions_dal::TransactionsDal,
     transactions_web3_dal::TransactionsWeb3Dal, vm_runner_dal::VmRunnerDal,
 };
 
+pub mod address_transactions_dal;
 pub mod base_token_dal;
 pub mod blocks_dal;
 pub mod blocks_web3_dal;
@@ -47,6 +50,7 @@ pub mod proof_generation_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
 pub mod pruning_dal;
+pub mod settlement_audits_dal;
 pub mod snapshot_recovery_dal;
 pub mod snapshots_creator_dal;
 pub mod snapshots_dal;
@@ -56,6 +60,7 @@ pub mod storage_web3_dal;
 pub mod sync_dal;
 pub mod system_dal;
 pub mod tee_proof_generation_dal;
+pub mod token_transfers_dal;
 pub mod tokens_dal;
 pub mod tokens_web3_dal;
 pub mod transactions_dal;
@@ -135,6 +140,12 @@ where
     fn eth_watcher_dal(&mut self) -> EthWatcherDal<'_, 'a>;
 
     fn custom_genesis_export_dal(&mut self) -> CustomGenesisExportDal<'_, 'a>;
+
+    fn address_transactions_dal(&mut self) -> AddressTransactionsDal<'_, 'a>;
+
+    fn token_transfers_dal(&mut self) -> TokenTransfersDal<'_, 'a>;
+
+    fn settlement_audits_dal(&mut self) -> SettlementAuditsDal<'_, 'a>;
 }
 
 #[derive(Clone, Debug)]
@@ -265,4 +276,16 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
     fn custom_genesis_export_dal(&mut self) -> CustomGenesisExportDal<'_, 'a> {
         CustomGenesisExportDal { storage: self }
     }
+
+    fn address_transactions_dal(&mut self) -> AddressTransactionsDal<'_, 'a> {
+        AddressTransactionsDal { storage: self }
+    }
+
+    fn token_transfers_dal(&mut self) -> TokenTransfersDal<'_, 'a> {
+        TokenTransfersDal { storage: self }
+    }
+
+    fn settlement_audits_dal(&mut self) -> SettlementAuditsDal<'_, 'a> {
+        SettlementAuditsDal { storage: self }
+    }
 }
@@ -12,6 +12,7 @@ pub mod storage_event;
 pub mod storage_log;
 pub mod storage_oracle_info;
 pub mod storage_protocol_version;
+pub(crate) mod storage_settlement_audit;
 pub mod storage_sync;
 pub mod storage_tee_proof;
 pub mod storage_transaction;
@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use zksync_types::{
+    api::{L1BatchSettlementAudit, SettlementAuditStatus},
+    L1BatchNumber,
+};
+
+/// Represents a row in the `l1_batch_settlement_audits` table.
+#[derive(Debug, Clone)]
+pub(crate) struct StorageL1BatchSettlementAudit {
+    pub l1_batch_number: i64,
+    pub commit_status: String,
+    pub prove_status: String,
+    pub execute_status: String,
+    pub error: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+fn parse_status(raw: &str) -> SettlementAuditStatus {
+    raw.parse()
+        .unwrap_or_else(|_| panic!("unknown settlement audit status: {raw}"))
+}
+
+impl From<StorageL1BatchSettlementAudit> for L1BatchSettlementAudit {
+    fn from(row: StorageL1BatchSettlementAudit) -> Self {
+        Self {
+            number: L1BatchNumber(row.l1_batch_number as u32),
+            commit: parse_status(&row.commit_status),
+            prove: parse_status(&row.prove_status),
+            execute: parse_status(&row.execute_status),
+            error: row.error,
+            audited_at: row.updated_at.and_utc(),
+        }
+    }
+}
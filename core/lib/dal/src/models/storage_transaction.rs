@@ -4,7 +4,10 @@ use bigdecimal::Zero;
 use serde_json::Value;
 use sqlx::types::chrono::{DateTime, NaiveDateTime, Utc};
 use zksync_types::{
-    api::{self, TransactionDetails, TransactionReceipt, TransactionStatus},
+    api::{
+        self, PendingTransactionReason, TransactionDetails, TransactionReceipt, TransactionStatus,
+        TransactionStatusDetails,
+    },
     fee::Fee,
     h256_to_address,
     l1::{OpProcessingType, PriorityQueueType},
@@ -438,17 +441,29 @@ pub(crate) struct StorageTransactionDetails {
     pub eth_execute_tx_hash: Option<String>,
 }
 
+fn transaction_status(
+    error: Option<&str>,
+    eth_execute_tx_hash: Option<&str>,
+    miniblock_number: Option<i64>,
+) -> TransactionStatus {
+    if error.is_some() {
+        TransactionStatus::Failed
+    } else if eth_execute_tx_hash.is_some() {
+        TransactionStatus::Verified
+    } else if miniblock_number.is_some() {
+        TransactionStatus::Included
+    } else {
+        TransactionStatus::Pending
+    }
+}
+
 impl StorageTransactionDetails {
     fn get_transaction_status(&self) -> TransactionStatus {
-        if self.error.is_some() {
-            TransactionStatus::Failed
-        } else if self.eth_execute_tx_hash.is_some() {
-            TransactionStatus::Verified
-        } else if self.miniblock_number.is_some() {
-            TransactionStatus::Included
-        } else {
-            TransactionStatus::Pending
-        }
+        transaction_status(
+            self.error.as_deref(),
+            self.eth_execute_tx_hash.as_deref(),
+            self.miniblock_number,
+        )
     }
 }
 
@@ -497,6 +512,41 @@ impl From<StorageTransactionDetails> for TransactionDetails {
     }
 }
 
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub(crate) struct StorageTransactionStatusDetails {
+    pub initiator_address: Vec<u8>,
+    pub received_at: NaiveDateTime,
+    pub miniblock_number: Option<i64>,
+    pub error: Option<String>,
+    pub eth_execute_tx_hash: Option<String>,
+    pub pending_reason: Option<String>,
+}
+
+impl From<StorageTransactionStatusDetails> for TransactionStatusDetails {
+    fn from(details: StorageTransactionStatusDetails) -> Self {
+        let status = transaction_status(
+            details.error.as_deref(),
+            details.eth_execute_tx_hash.as_deref(),
+            details.miniblock_number,
+        );
+        let pending_reason = if matches!(status, TransactionStatus::Pending) {
+            details
+                .pending_reason
+                .and_then(|reason| reason.parse::<PendingTransactionReason>().ok())
+        } else {
+            None
+        };
+
+        TransactionStatusDetails {
+            status,
+            initiator_address: H160::from_slice(&details.initiator_address),
+            received_at: DateTime::<Utc>::from_naive_utc_and_offset(details.received_at, Utc),
+            pending_reason,
+            error: details.error,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct StorageApiTransaction {
     pub tx_hash: Vec<u8>,
@@ -1,5 +1,5 @@
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::api::ProtocolVersion;
+use zksync_types::{api::ProtocolVersion, ProtocolVersionId};
 
 use crate::{models::storage_protocol_version::StorageApiProtocolVersion, Core, CoreDal};
 
@@ -38,6 +38,41 @@ impl ProtocolVersionsWeb3Dal<'_, '_> {
         Ok(storage_protocol_version.map(ProtocolVersion::from))
     }
 
+    /// Returns all protocol versions with IDs greater than `version_id`, ordered by ID.
+    pub async fn get_protocol_versions_after(
+        &mut self,
+        version_id: ProtocolVersionId,
+    ) -> DalResult<Vec<ProtocolVersion>> {
+        let storage_protocol_versions = sqlx::query_as!(
+            StorageApiProtocolVersion,
+            r#"
+            SELECT
+                id AS "minor!",
+                timestamp,
+                bootloader_code_hash,
+                default_account_code_hash,
+                evm_emulator_code_hash,
+                upgrade_tx_hash
+            FROM
+                protocol_versions
+            WHERE
+                id > $1
+            ORDER BY
+                id
+            "#,
+            version_id as i32
+        )
+        .instrument("get_protocol_versions_after")
+        .with_arg("version_id", &version_id)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(storage_protocol_versions
+            .into_iter()
+            .map(ProtocolVersion::from)
+            .collect())
+    }
+
     pub async fn get_latest_protocol_version(&mut self) -> DalResult<ProtocolVersion> {
         let latest_version = self
             .storage
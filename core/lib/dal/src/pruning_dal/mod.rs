@@ -1,7 +1,14 @@
 use std::ops;
 
-use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
+use zksync_db_connection::{
+    connection::Connection,
+    error::DalResult,
+    instrument::{InstrumentExt, Instrumented},
+};
+use zksync_types::{
+    pruning::{PrunedDataArchive, PrunedDataClass, PruningProfile},
+    L1BatchNumber, L2BlockNumber, H256,
+};
 
 use crate::Core;
 
@@ -40,6 +47,25 @@ impl PruningInfo {
     }
 }
 
+/// Information about soft pruning for each [`PrunedDataClass`]. Unlike [`PruningInfo`], takes
+/// [pruning profiles](PruningProfile) into account.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrunedDataInfo {
+    last_soft_pruned_by_profile: Vec<(PruningProfile, SoftPruningInfo)>,
+}
+
+impl PrunedDataInfo {
+    /// Returns the last soft-pruned L1 batch / L2 block for the specified data class, or `None` if this class
+    /// was never pruned.
+    pub fn last_soft_pruned(&self, class: PrunedDataClass) -> Option<SoftPruningInfo> {
+        self.last_soft_pruned_by_profile
+            .iter()
+            .filter(|(profile, _)| profile.prunes(class))
+            .map(|(_, info)| *info)
+            .max_by_key(|info| info.l1_batch)
+    }
+}
+
 /// Statistics about a single hard pruning iteration.
 #[derive(Debug, Default)]
 pub struct HardPruningStats {
@@ -51,6 +77,17 @@ pub struct HardPruningStats {
     pub deleted_l2_to_l1_logs: u64,
 }
 
+/// Information about a pruned data archive recorded in the pruning log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrunedDataArchiveInfo {
+    /// Last L1 batch pruned in the corresponding hard pruning iteration.
+    pub last_l1_batch: L1BatchNumber,
+    /// Last L2 block pruned in the corresponding hard pruning iteration.
+    pub last_l2_block: L2BlockNumber,
+    /// Key of the archive in the object store bucket.
+    pub location: String,
+}
+
 #[derive(Debug)]
 struct StoragePruningInfo {
     last_soft_pruned_l1_batch: Option<i64>,
@@ -102,7 +139,31 @@ pub struct PruningDal<'a, 'c> {
 }
 
 impl PruningDal<'_, '_> {
+    /// Returns information about pruned L1 batches and L2 blocks. Pruning iterations with [profiles](PruningProfile)
+    /// retaining block data are not taken into account, so block data up to and including the returned L1 batch / L2 block
+    /// is guaranteed to be (soft- or hard-) pruned.
     pub async fn get_pruning_info(&mut self) -> DalResult<PruningInfo> {
+        let block_retaining_profiles: Vec<_> = PruningProfile::ALL
+            .into_iter()
+            .filter(|profile| !profile.prunes(PrunedDataClass::Blocks))
+            .map(|profile| profile.as_str().to_owned())
+            .collect();
+        self.get_pruning_info_inner(&block_retaining_profiles, "get_pruning_info")
+            .await
+    }
+
+    /// Returns progress of pruning iterations regardless of their [profiles](PruningProfile). This is used by the DB pruner
+    /// to track its iterations; to check whether block data is available, use [`Self::get_pruning_info()`] instead.
+    pub async fn get_pruning_progress(&mut self) -> DalResult<PruningInfo> {
+        self.get_pruning_info_inner(&[], "get_pruning_progress")
+            .await
+    }
+
+    async fn get_pruning_info_inner(
+        &mut self,
+        excluded_profiles: &[String],
+        method_name: &'static str,
+    ) -> DalResult<PruningInfo> {
         let row = sqlx::query_as!(
             StoragePruningInfo,
             r#"
@@ -115,6 +176,7 @@ impl PruningDal<'_, '_> {
                     pruning_log
                 WHERE
                     type = 'Soft'
+                    AND profile <> ALL($1)
                 ORDER BY
                     pruned_l1_batch DESC
                 LIMIT
@@ -130,6 +192,7 @@ impl PruningDal<'_, '_> {
                     pruning_log
                 WHERE
                     type = 'Hard'
+                    AND profile <> ALL($1)
                 ORDER BY
                     pruned_l1_batch DESC
                 LIMIT
@@ -145,9 +208,11 @@ impl PruningDal<'_, '_> {
             FROM
                 soft
             FULL JOIN hard ON TRUE
-            "#
+            "#,
+            excluded_profiles
         )
-        .instrument("get_last_soft_pruned_batch")
+        .instrument(method_name)
+        .with_arg("excluded_profiles", &excluded_profiles)
         .report_latency()
         .fetch_optional(self.storage)
         .await?;
@@ -155,10 +220,49 @@ impl PruningDal<'_, '_> {
         Ok(row.map(PruningInfo::from).unwrap_or_default())
     }
 
+    /// Returns information about soft-pruned data taking pruning profiles into account.
+    pub async fn get_pruned_data_info(&mut self) -> DalResult<PrunedDataInfo> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                profile,
+                MAX(pruned_l1_batch) AS "pruned_l1_batch!",
+                MAX(pruned_miniblock) AS "pruned_miniblock!"
+            FROM
+                pruning_log
+            WHERE
+                type = 'Soft'
+            GROUP BY
+                profile
+            "#
+        )
+        .instrument("get_pruned_data_info")
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        let last_soft_pruned_by_profile = rows
+            .into_iter()
+            .map(|row| {
+                // Unknown profiles are conservatively treated as full pruning.
+                let profile = row.profile.parse().unwrap_or_default();
+                let info = SoftPruningInfo {
+                    l1_batch: L1BatchNumber(row.pruned_l1_batch as u32),
+                    l2_block: L2BlockNumber(row.pruned_miniblock as u32),
+                };
+                (profile, info)
+            })
+            .collect();
+        Ok(PrunedDataInfo {
+            last_soft_pruned_by_profile,
+        })
+    }
+
     pub async fn insert_soft_pruning_log(
         &mut self,
         last_l1_batch_to_prune: L1BatchNumber,
         last_l2_block_to_prune: L2BlockNumber,
+        profile: PruningProfile,
     ) -> DalResult<()> {
         sqlx::query!(
             r#"
@@ -167,20 +271,23 @@ impl PruningDal<'_, '_> {
                 pruned_l1_batch,
                 pruned_miniblock,
                 type,
+                profile,
                 created_at,
                 updated_at
             )
             VALUES
-            ($1, $2, $3, NOW(), NOW())
+            ($1, $2, $3, $4, NOW(), NOW())
             "#,
             i64::from(last_l1_batch_to_prune.0),
             i64::from(last_l2_block_to_prune.0),
             PruneType::Soft as PruneType,
+            profile.as_str()
         )
         .instrument("soft_prune_batches_range#insert_pruning_log")
         .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
         .with_arg("last_l2_block_to_prune", &last_l2_block_to_prune)
         .with_arg("prune_type", &PruneType::Soft)
+        .with_arg("profile", &profile)
         .report_latency()
         .execute(self.storage)
         .await?;
@@ -188,12 +295,27 @@ impl PruningDal<'_, '_> {
         Ok(())
     }
 
+    /// Removes data classes covered by the specified `profile` for L1 batches up to and including
+    /// `last_l1_batch_to_prune`. If the profile retains block headers, only L2 blocks after the last hard-pruned one
+    /// are processed; otherwise, all L2 blocks up to `last_l2_block_to_prune` are processed, which removes
+    /// data retained by previous iterations with other profiles.
+    ///
     /// Does not insert pruning logs; the caller is responsible to do this!
     pub async fn hard_prune_batches_range(
         &mut self,
         last_l1_batch_to_prune: L1BatchNumber,
         last_l2_block_to_prune: L2BlockNumber,
+        profile: PruningProfile,
     ) -> DalResult<HardPruningStats> {
+        let min_l2_block_to_prune = if profile.prunes(PrunedDataClass::Blocks) {
+            0
+        } else {
+            self.get_pruning_progress()
+                .await?
+                .last_hard_pruned
+                .map_or(0, |info| i64::from(info.l2_block.0) + 1)
+        };
+
         let row = sqlx::query!(
             r#"
             SELECT
@@ -202,11 +324,14 @@ impl PruningDal<'_, '_> {
                 miniblocks
             WHERE
                 l1_batch_number <= $1
+                AND number >= $2
             "#,
             i64::from(last_l1_batch_to_prune.0),
+            min_l2_block_to_prune
         )
         .instrument("hard_prune_batches_range#get_miniblocks_range")
         .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .with_arg("min_l2_block_to_prune", &min_l2_block_to_prune)
         .report_latency()
         .fetch_one(self.storage)
         .await?;
@@ -216,33 +341,31 @@ impl PruningDal<'_, '_> {
         };
 
         let first_l2_block_to_prune = L2BlockNumber(first_l2_block_to_prune as u32);
-
-        let deleted_events = self
-            .delete_events(first_l2_block_to_prune..=last_l2_block_to_prune)
-            .await?;
-        let deleted_l2_to_l1_logs = self
-            .delete_l2_to_l1_logs(first_l2_block_to_prune..=last_l2_block_to_prune)
-            .await?;
-        let deleted_call_traces = self
-            .delete_call_traces(first_l2_block_to_prune..=last_l2_block_to_prune)
-            .await?;
-        self.clear_transaction_fields(first_l2_block_to_prune..=last_l2_block_to_prune)
-            .await?;
-
-        let deleted_storage_logs = self
-            .prune_storage_logs(first_l2_block_to_prune..=last_l2_block_to_prune)
-            .await?;
-        let deleted_l1_batches = self.delete_l1_batches(last_l1_batch_to_prune).await?;
-        let deleted_l2_blocks = self.delete_l2_blocks(last_l2_block_to_prune).await?;
-
-        let stats = HardPruningStats {
-            deleted_l1_batches,
-            deleted_l2_blocks,
-            deleted_events,
-            deleted_l2_to_l1_logs,
-            deleted_call_traces,
-            deleted_storage_logs,
-        };
+        let l2_blocks_to_prune = first_l2_block_to_prune..=last_l2_block_to_prune;
+        let mut stats = HardPruningStats::default();
+
+        if profile.prunes(PrunedDataClass::Transactions) {
+            stats.deleted_events = self.delete_events(l2_blocks_to_prune.clone()).await?;
+            stats.deleted_l2_to_l1_logs = self
+                .delete_l2_to_l1_logs(l2_blocks_to_prune.clone())
+                .await?;
+            self.clear_transaction_fields(l2_blocks_to_prune.clone())
+                .await?;
+            self.delete_address_transactions(l2_blocks_to_prune.clone())
+                .await?;
+            self.delete_token_transfers(l2_blocks_to_prune.clone())
+                .await?;
+        }
+        if profile.prunes(PrunedDataClass::CallTraces) {
+            stats.deleted_call_traces = self.delete_call_traces(l2_blocks_to_prune.clone()).await?;
+        }
+        if profile.prunes(PrunedDataClass::StateHistory) {
+            stats.deleted_storage_logs = self.prune_storage_logs(l2_blocks_to_prune).await?;
+        }
+        if profile.prunes(PrunedDataClass::Blocks) {
+            stats.deleted_l1_batches = self.delete_l1_batches(last_l1_batch_to_prune).await?;
+            stats.deleted_l2_blocks = self.delete_l2_blocks(last_l2_block_to_prune).await?;
+        }
         Ok(stats)
     }
 
@@ -288,6 +411,48 @@ impl PruningDal<'_, '_> {
         Ok(execution_result.rows_affected())
     }
 
+    async fn delete_address_transactions(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM address_transactions
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            "#,
+            i64::from(l2_blocks_to_prune.start().0),
+            i64::from(l2_blocks_to_prune.end().0)
+        )
+        .instrument("hard_prune_batches_range#delete_address_transactions")
+        .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    async fn delete_token_transfers(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM token_transfers
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            "#,
+            i64::from(l2_blocks_to_prune.start().0),
+            i64::from(l2_blocks_to_prune.end().0)
+        )
+        .instrument("hard_prune_batches_range#delete_token_transfers")
+        .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
     // Call traces are returned via `TransactionsDal::get_call_trace()`, which is used by the `debug_traceTransaction` RPC method.
     // It should be acceptable to return `None` for transactions in pruned L2 blocks; this would make them indistinguishable
     // from traces for non-existing transactions.
@@ -442,6 +607,7 @@ impl PruningDal<'_, '_> {
         last_l1_batch_to_prune: L1BatchNumber,
         last_l2_block_to_prune: L2BlockNumber,
         last_pruned_l1_batch_root_hash: H256,
+        profile: PruningProfile,
     ) -> DalResult<()> {
         sqlx::query!(
             r#"
@@ -451,23 +617,176 @@ impl PruningDal<'_, '_> {
                 pruned_miniblock,
                 pruned_l1_batch_root_hash,
                 type,
+                profile,
                 created_at,
                 updated_at
             )
             VALUES
-            ($1, $2, $3, $4, NOW(), NOW())
+            ($1, $2, $3, $4, $5, NOW(), NOW())
             "#,
             i64::from(last_l1_batch_to_prune.0),
             i64::from(last_l2_block_to_prune.0),
             last_pruned_l1_batch_root_hash.as_bytes(),
-            PruneType::Hard as PruneType
+            PruneType::Hard as PruneType,
+            profile.as_str()
         )
         .instrument("hard_prune_batches_range#insert_pruning_log")
         .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
         .with_arg("last_l2_block_to_prune", &last_l2_block_to_prune)
+        .with_arg("profile", &profile)
         .report_latency()
         .execute(self.storage)
         .await?;
         Ok(())
     }
+
+    /// Records the location of the archive with data removed by the hard pruning iteration ending
+    /// at the specified L1 batch.
+    pub async fn set_hard_pruning_archive_location(
+        &mut self,
+        last_pruned_l1_batch: L1BatchNumber,
+        location: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE pruning_log
+            SET
+                archive_location = $2,
+                updated_at = NOW()
+            WHERE
+                type = 'Hard'
+                AND pruned_l1_batch = $1
+            "#,
+            i64::from(last_pruned_l1_batch.0),
+            location
+        )
+        .instrument("set_hard_pruning_archive_location")
+        .with_arg("last_pruned_l1_batch", &last_pruned_l1_batch)
+        .with_arg("location", &location)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns all pruned data archives recorded in the pruning log, ordered by L1 batch number.
+    pub async fn get_pruned_data_archives(&mut self) -> DalResult<Vec<PrunedDataArchiveInfo>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                pruned_l1_batch,
+                pruned_miniblock,
+                archive_location AS "archive_location!"
+            FROM
+                pruning_log
+            WHERE
+                type = 'Hard'
+                AND archive_location IS NOT NULL
+            ORDER BY
+                pruned_l1_batch
+            "#
+        )
+        .instrument("get_pruned_data_archives")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PrunedDataArchiveInfo {
+                last_l1_batch: L1BatchNumber(row.pruned_l1_batch as u32),
+                last_l2_block: L2BlockNumber(row.pruned_miniblock as u32),
+                location: row.archive_location,
+            })
+            .collect())
+    }
+
+    /// Imports data from a pruned data archive into dedicated tables (`imported_pruned_l1_batches`
+    /// and `imported_pruned_l2_blocks`) so that it can be investigated using SQL. Previously imported data
+    /// for the same L1 batches / L2 blocks is overwritten. Imported data is not used by the node.
+    pub async fn import_pruned_data_archive(
+        &mut self,
+        archive: &PrunedDataArchive,
+        location: &str,
+    ) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        for l1_batch in &archive.l1_batches {
+            let instrumentation = Instrumented::new("import_pruned_data_archive#insert_l1_batch")
+                .with_arg("l1_batch", &l1_batch.number)
+                .with_arg("location", &location);
+            let details = serde_json::to_value(l1_batch)
+                .map_err(|err| instrumentation.arg_error("l1_batch", err))?;
+            let query = sqlx::query!(
+                r#"
+                INSERT INTO
+                imported_pruned_l1_batches (number, details, archive_location, created_at)
+                VALUES
+                ($1, $2, $3, NOW())
+                ON CONFLICT (number) DO
+                UPDATE
+                SET
+                details = excluded.details,
+                archive_location = excluded.archive_location,
+                created_at = excluded.created_at
+                "#,
+                i64::from(l1_batch.number.0),
+                &details,
+                location
+            );
+            instrumentation
+                .with(query)
+                .execute(&mut transaction)
+                .await?;
+        }
+
+        for l2_block in &archive.l2_blocks {
+            let number = l2_block.block.number.as_u64();
+            let instrumentation = Instrumented::new("import_pruned_data_archive#insert_l2_block")
+                .with_arg("l2_block", &number)
+                .with_arg("location", &location);
+            let block = serde_json::to_value(&l2_block.block)
+                .map_err(|err| instrumentation.arg_error("block", err))?;
+            let transactions = serde_json::to_value(&l2_block.transactions)
+                .map_err(|err| instrumentation.arg_error("transactions", err))?;
+            let receipts = serde_json::to_value(&l2_block.receipts)
+                .map_err(|err| instrumentation.arg_error("receipts", err))?;
+            let query = sqlx::query!(
+                r#"
+                INSERT INTO
+                imported_pruned_l2_blocks (
+                    number,
+                    l1_batch_number,
+                    block,
+                    transactions,
+                    receipts,
+                    archive_location,
+                    created_at
+                )
+                VALUES
+                ($1, $2, $3, $4, $5, $6, NOW())
+                ON CONFLICT (number) DO
+                UPDATE
+                SET
+                l1_batch_number = excluded.l1_batch_number,
+                block = excluded.block,
+                transactions = excluded.transactions,
+                receipts = excluded.receipts,
+                archive_location = excluded.archive_location,
+                created_at = excluded.created_at
+                "#,
+                number as i64,
+                l2_block
+                    .block
+                    .l1_batch_number
+                    .map(|number| number.as_u64() as i64),
+                &block,
+                &transactions,
+                &receipts,
+                location
+            );
+            instrumentation
+                .with(query)
+                .execute(&mut transaction)
+                .await?;
+        }
+        transaction.commit().await
+    }
 }
@@ -219,7 +219,7 @@ async fn soft_pruning_works() {
 
     transaction
         .pruning_dal()
-        .insert_soft_pruning_log(L1BatchNumber(5), L2BlockNumber(11))
+        .insert_soft_pruning_log(L1BatchNumber(5), L2BlockNumber(11), PruningProfile::Full)
         .await
         .unwrap();
     assert_eq!(
@@ -235,7 +235,7 @@ async fn soft_pruning_works() {
 
     transaction
         .pruning_dal()
-        .insert_soft_pruning_log(L1BatchNumber(10), L2BlockNumber(21))
+        .insert_soft_pruning_log(L1BatchNumber(10), L2BlockNumber(21), PruningProfile::Full)
         .await
         .unwrap();
     assert_eq!(
@@ -251,7 +251,12 @@ async fn soft_pruning_works() {
 
     transaction
         .pruning_dal()
-        .insert_hard_pruning_log(L1BatchNumber(10), L2BlockNumber(21), H256::repeat_byte(23))
+        .insert_hard_pruning_log(
+            L1BatchNumber(10),
+            L2BlockNumber(21),
+            H256::repeat_byte(23),
+            PruningProfile::Full,
+        )
         .await
         .unwrap();
     assert_eq!(
@@ -270,6 +275,41 @@ async fn soft_pruning_works() {
     );
 }
 
+#[tokio::test]
+async fn pruned_data_archive_locations_are_recorded() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+
+    for (l1_batch, l2_block) in [(5, 11), (10, 21)] {
+        let (l1_batch, l2_block) = (L1BatchNumber(l1_batch), L2BlockNumber(l2_block));
+        conn.pruning_dal()
+            .insert_soft_pruning_log(l1_batch, l2_block, PruningProfile::Full)
+            .await
+            .unwrap();
+        conn.pruning_dal()
+            .insert_hard_pruning_log(l1_batch, l2_block, H256::zero(), PruningProfile::Full)
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        conn.pruning_dal().get_pruned_data_archives().await.unwrap(),
+        []
+    );
+
+    conn.pruning_dal()
+        .set_hard_pruning_archive_location(L1BatchNumber(10), "pruned_l1_batches_6_10.json.gzip")
+        .await
+        .unwrap();
+    assert_eq!(
+        conn.pruning_dal().get_pruned_data_archives().await.unwrap(),
+        [PrunedDataArchiveInfo {
+            last_l1_batch: L1BatchNumber(10),
+            last_l2_block: L2BlockNumber(21),
+            location: "pruned_l1_batches_6_10.json.gzip".to_owned(),
+        }]
+    );
+}
+
 fn random_storage_log(hashed_key_seed: u8, value_seed: u8) -> StorageLog {
     let key = StorageKey::new(
         AccountTreeId::from_fixed_bytes([hashed_key_seed; 20]),
@@ -356,7 +396,7 @@ async fn storage_logs_pruning_works_correctly() {
 
     let stats = transaction
         .pruning_dal()
-        .hard_prune_batches_range(L1BatchNumber(4), L2BlockNumber(9))
+        .hard_prune_batches_range(L1BatchNumber(4), L2BlockNumber(9), PruningProfile::Full)
         .await
         .unwrap();
     let actual_logs = transaction
@@ -374,7 +414,7 @@ async fn storage_logs_pruning_works_correctly() {
 
     let stats = transaction
         .pruning_dal()
-        .hard_prune_batches_range(L1BatchNumber(9), L2BlockNumber(19))
+        .hard_prune_batches_range(L1BatchNumber(9), L2BlockNumber(19), PruningProfile::Full)
         .await
         .unwrap();
     let actual_logs = transaction
@@ -416,7 +456,7 @@ async fn l1_batches_can_be_hard_pruned() {
 
     transaction
         .pruning_dal()
-        .hard_prune_batches_range(L1BatchNumber(5), L2BlockNumber(11))
+        .hard_prune_batches_range(L1BatchNumber(5), L2BlockNumber(11), PruningProfile::Full)
         .await
         .unwrap();
 
@@ -425,7 +465,7 @@ async fn l1_batches_can_be_hard_pruned() {
 
     let stats = transaction
         .pruning_dal()
-        .hard_prune_batches_range(L1BatchNumber(9), L2BlockNumber(19))
+        .hard_prune_batches_range(L1BatchNumber(9), L2BlockNumber(19), PruningProfile::Full)
         .await
         .unwrap();
     assert_eq!(stats.deleted_l1_batches, 4);
@@ -436,6 +476,134 @@ async fn l1_batches_can_be_hard_pruned() {
     assert_l1_batches_not_exist(&mut transaction, L1BatchNumber(1)..=L1BatchNumber(9)).await;
 }
 
+#[tokio::test]
+async fn l1_batches_can_be_selectively_pruned() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+
+    let mut conn = pool.connection().await.unwrap();
+    let mut transaction = conn.start_transaction().await.unwrap();
+    insert_realistic_l1_batches(&mut transaction, 10).await;
+
+    let iterations = [
+        (
+            L1BatchNumber(5),
+            L2BlockNumber(11),
+            PruningProfile::KeepTransactions,
+        ),
+        (
+            L1BatchNumber(9),
+            L2BlockNumber(19),
+            PruningProfile::KeepState,
+        ),
+    ];
+    let mut all_stats = vec![];
+    for (l1_batch, l2_block, profile) in iterations {
+        transaction
+            .pruning_dal()
+            .insert_soft_pruning_log(l1_batch, l2_block, profile)
+            .await
+            .unwrap();
+        let stats = transaction
+            .pruning_dal()
+            .hard_prune_batches_range(l1_batch, l2_block, profile)
+            .await
+            .unwrap();
+        transaction
+            .pruning_dal()
+            .insert_hard_pruning_log(l1_batch, l2_block, H256::zero(), profile)
+            .await
+            .unwrap();
+        assert_eq!(stats.deleted_l1_batches, 0);
+        assert_eq!(stats.deleted_l2_blocks, 0);
+        all_stats.push(stats);
+    }
+    assert_eq!(all_stats[0].deleted_events, 0);
+    assert_eq!(all_stats[0].deleted_l2_to_l1_logs, 0);
+    // Only L2 blocks after the last hard-pruned one should be processed.
+    assert_eq!(all_stats[1].deleted_events, 40);
+    assert_eq!(all_stats[1].deleted_l2_to_l1_logs, 40);
+    assert_l1_batches_exist(&mut transaction, L1BatchNumber(1)..=L1BatchNumber(9)).await;
+
+    // Block pruning markers must not be advanced by profiles retaining blocks.
+    let pruning_info = transaction.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(pruning_info, PruningInfo::default());
+    let pruning_progress = transaction
+        .pruning_dal()
+        .get_pruning_progress()
+        .await
+        .unwrap();
+    assert_eq!(
+        pruning_progress.last_soft_pruned,
+        Some(SoftPruningInfo {
+            l1_batch: L1BatchNumber(9),
+            l2_block: L2BlockNumber(19),
+        })
+    );
+    assert_eq!(
+        pruning_progress.last_hard_pruned.map(|info| info.l1_batch),
+        Some(L1BatchNumber(9))
+    );
+
+    let info = transaction
+        .pruning_dal()
+        .get_pruned_data_info()
+        .await
+        .unwrap();
+    assert_eq!(info.last_soft_pruned(PrunedDataClass::Blocks), None);
+    let expected_info = SoftPruningInfo {
+        l1_batch: L1BatchNumber(9),
+        l2_block: L2BlockNumber(19),
+    };
+    assert_eq!(
+        info.last_soft_pruned(PrunedDataClass::Transactions),
+        Some(expected_info)
+    );
+    assert_eq!(
+        info.last_soft_pruned(PrunedDataClass::CallTraces),
+        Some(expected_info)
+    );
+    assert_eq!(
+        info.last_soft_pruned(PrunedDataClass::StateHistory),
+        Some(SoftPruningInfo {
+            l1_batch: L1BatchNumber(5),
+            l2_block: L2BlockNumber(11),
+        })
+    );
+
+    // Full pruning should remove data retained by previous iterations.
+    transaction
+        .pruning_dal()
+        .insert_soft_pruning_log(L1BatchNumber(9), L2BlockNumber(19), PruningProfile::Full)
+        .await
+        .unwrap();
+    let stats = transaction
+        .pruning_dal()
+        .hard_prune_batches_range(L1BatchNumber(9), L2BlockNumber(19), PruningProfile::Full)
+        .await
+        .unwrap();
+    transaction
+        .pruning_dal()
+        .insert_hard_pruning_log(
+            L1BatchNumber(9),
+            L2BlockNumber(19),
+            H256::zero(),
+            PruningProfile::Full,
+        )
+        .await
+        .unwrap();
+    assert_eq!(stats.deleted_l1_batches, 10);
+    assert_eq!(stats.deleted_l2_blocks, 20);
+    assert_eq!(stats.deleted_events, 60);
+    assert_l1_batches_not_exist(&mut transaction, L1BatchNumber(0)..=L1BatchNumber(9)).await;
+
+    let pruning_info = transaction.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(
+        pruning_info.last_soft_pruned.map(|info| info.l1_batch),
+        Some(L1BatchNumber(9))
+    );
+    assert!(pruning_info.is_caught_up());
+}
+
 #[tokio::test]
 async fn transactions_are_handled_correctly_after_pruning() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -0,0 +1,365 @@
+//! Results of the L1 batch settlement auditor.
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{
+    api::{L1BatchSettlementAudit, SettlementAuditStatus},
+    L1BatchNumber, SLChainId, H256,
+};
+
+use crate::{models::storage_settlement_audit::StorageL1BatchSettlementAudit, Core};
+
+/// Confirmed settlement layer transaction for one of the L1 batch settlement stages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettlementTx {
+    pub hash: H256,
+    /// ID of the settlement layer the transaction was sent to. `None` for transactions sent before
+    /// chain IDs were recorded; such transactions are always sent to L1.
+    pub chain_id: Option<SLChainId>,
+}
+
+/// Confirmed settlement layer transactions for an L1 batch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct L1BatchSettlementTxs {
+    pub commit: Option<SettlementTx>,
+    pub prove: Option<SettlementTx>,
+    pub execute: Option<SettlementTx>,
+}
+
+#[derive(Debug)]
+pub struct SettlementAuditsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl SettlementAuditsDal<'_, '_> {
+    /// Returns confirmed settlement layer transactions for the specified L1 batch, or `None` if the batch
+    /// is not present in the storage.
+    pub async fn get_settlement_txs(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<L1BatchSettlementTxs>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                commit_tx.tx_hash AS "commit_tx_hash?",
+                commit_tx_data.chain_id AS "commit_chain_id?",
+                prove_tx.tx_hash AS "prove_tx_hash?",
+                prove_tx_data.chain_id AS "prove_chain_id?",
+                execute_tx.tx_hash AS "execute_tx_hash?",
+                execute_tx_data.chain_id AS "execute_chain_id?"
+            FROM
+                l1_batches
+            LEFT JOIN eth_txs AS commit_tx_data
+                ON (
+                    l1_batches.eth_commit_tx_id = commit_tx_data.id
+                    AND commit_tx_data.confirmed_eth_tx_history_id IS NOT NULL
+                )
+            LEFT JOIN eth_txs_history AS commit_tx
+                ON commit_tx_data.confirmed_eth_tx_history_id = commit_tx.id
+            LEFT JOIN eth_txs AS prove_tx_data
+                ON (
+                    l1_batches.eth_prove_tx_id = prove_tx_data.id
+                    AND prove_tx_data.confirmed_eth_tx_history_id IS NOT NULL
+                )
+            LEFT JOIN eth_txs_history AS prove_tx
+                ON prove_tx_data.confirmed_eth_tx_history_id = prove_tx.id
+            LEFT JOIN eth_txs AS execute_tx_data
+                ON (
+                    l1_batches.eth_execute_tx_id = execute_tx_data.id
+                    AND execute_tx_data.confirmed_eth_tx_history_id IS NOT NULL
+                )
+            LEFT JOIN eth_txs_history AS execute_tx
+                ON execute_tx_data.confirmed_eth_tx_history_id = execute_tx.id
+            WHERE
+                l1_batches.number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_settlement_txs")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let parse_tx = |hash: Option<String>, chain_id: Option<i64>| {
+            let hash = hash?;
+            let hash = hash.trim_start_matches("0x").parse().ok()?;
+            Some(SettlementTx {
+                hash,
+                chain_id: chain_id.map(|id| SLChainId(id as u64)),
+            })
+        };
+        Ok(Some(L1BatchSettlementTxs {
+            commit: parse_tx(row.commit_tx_hash, row.commit_chain_id),
+            prove: parse_tx(row.prove_tx_hash, row.prove_chain_id),
+            execute: parse_tx(row.execute_tx_hash, row.execute_chain_id),
+        }))
+    }
+
+    /// Inserts or updates the audit of the specified L1 batch.
+    pub async fn save_audit(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        [commit, prove, execute]: [SettlementAuditStatus; 3],
+        error: Option<&str>,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            l1_batch_settlement_audits (
+                l1_batch_number,
+                commit_status,
+                prove_status,
+                execute_status,
+                error,
+                created_at,
+                updated_at
+            )
+            VALUES
+            ($1, $2, $3, $4, $5, NOW(), NOW())
+            ON CONFLICT (l1_batch_number) DO
+            UPDATE
+            SET
+            commit_status = excluded.commit_status,
+            prove_status = excluded.prove_status,
+            execute_status = excluded.execute_status,
+            error = excluded.error,
+            updated_at = NOW()
+            "#,
+            i64::from(l1_batch_number.0),
+            commit.to_string(),
+            prove.to_string(),
+            execute.to_string(),
+            error
+        )
+        .instrument("save_audit")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_audit(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<L1BatchSettlementAudit>> {
+        let row = sqlx::query_as!(
+            StorageL1BatchSettlementAudit,
+            r#"
+            SELECT
+                l1_batch_number,
+                commit_status,
+                prove_status,
+                execute_status,
+                error,
+                updated_at
+            FROM
+                l1_batch_settlement_audits
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_audit")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(Into::into))
+    }
+
+    /// Returns the number of the latest L1 batch for which all settlement stages are audited
+    /// (i.e., none of the stages is pending).
+    pub async fn get_last_completed_audit_batch(&mut self) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(l1_batch_number) AS "number"
+            FROM
+                l1_batch_settlement_audits
+            WHERE
+                commit_status != 'pending'
+                AND prove_status != 'pending'
+                AND execute_status != 'pending'
+            "#
+        )
+        .instrument("get_last_completed_audit_batch")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.number.map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Returns numbers of L1 batches with failed audits, starting from the latest one.
+    pub async fn get_failed_audit_batches(
+        &mut self,
+        limit: usize,
+    ) -> DalResult<Vec<L1BatchNumber>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number
+            FROM
+                l1_batch_settlement_audits
+            WHERE
+                commit_status = 'failed'
+                OR prove_status = 'failed'
+                OR execute_status = 'failed'
+            ORDER BY
+                l1_batch_number DESC
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .instrument("get_failed_audit_batches")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchNumber(row.l1_batch_number as u32))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{aggregated_operations::AggregatedActionType, ProtocolVersion};
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool, Core, CoreDal};
+
+    #[tokio::test]
+    async fn saving_and_loading_audits() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 1..=3 {
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number))
+                .await
+                .unwrap();
+        }
+
+        let mut dal = conn.settlement_audits_dal();
+        assert_eq!(dal.get_last_completed_audit_batch().await.unwrap(), None);
+        assert!(dal.get_audit(L1BatchNumber(1)).await.unwrap().is_none());
+
+        use SettlementAuditStatus::{Failed, Passed, Pending};
+        dal.save_audit(L1BatchNumber(1), [Passed; 3], None)
+            .await
+            .unwrap();
+        dal.save_audit(
+            L1BatchNumber(2),
+            [Passed, Failed, Passed],
+            Some("prove: mismatch"),
+        )
+        .await
+        .unwrap();
+        dal.save_audit(L1BatchNumber(3), [Passed, Pending, Pending], None)
+            .await
+            .unwrap();
+
+        let audit = dal.get_audit(L1BatchNumber(2)).await.unwrap().unwrap();
+        assert_eq!(audit.number, L1BatchNumber(2));
+        assert_eq!(audit.commit, Passed);
+        assert_eq!(audit.prove, Failed);
+        assert_eq!(audit.execute, Passed);
+        assert_eq!(audit.error.as_deref(), Some("prove: mismatch"));
+        assert_eq!(
+            dal.get_last_completed_audit_batch().await.unwrap(),
+            Some(L1BatchNumber(2))
+        );
+        assert_eq!(
+            dal.get_failed_audit_batches(10).await.unwrap(),
+            [L1BatchNumber(2)]
+        );
+
+        dal.save_audit(L1BatchNumber(3), [Passed; 3], None)
+            .await
+            .unwrap();
+        let audit = dal.get_audit(L1BatchNumber(3)).await.unwrap().unwrap();
+        assert_eq!(audit.prove, Passed);
+        assert_eq!(
+            dal.get_last_completed_audit_batch().await.unwrap(),
+            Some(L1BatchNumber(3))
+        );
+    }
+
+    #[tokio::test]
+    async fn loading_settlement_txs() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            conn.settlement_audits_dal()
+                .get_settlement_txs(L1BatchNumber(1))
+                .await
+                .unwrap(),
+            None
+        );
+
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(1))
+            .await
+            .unwrap();
+        let txs = conn
+            .settlement_audits_dal()
+            .get_settlement_txs(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(txs, L1BatchSettlementTxs::default());
+
+        let commit_tx_hash = H256::repeat_byte(1);
+        let prove_tx_hash = H256::repeat_byte(2);
+        conn.eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(1),
+                AggregatedActionType::Commit,
+                commit_tx_hash,
+                chrono::Utc::now(),
+                None,
+            )
+            .await
+            .unwrap();
+        conn.eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(1),
+                AggregatedActionType::PublishProofOnchain,
+                prove_tx_hash,
+                chrono::Utc::now(),
+                Some(SLChainId(505)),
+            )
+            .await
+            .unwrap();
+
+        let txs = conn
+            .settlement_audits_dal()
+            .get_settlement_txs(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            txs.commit,
+            Some(SettlementTx {
+                hash: commit_tx_hash,
+                chain_id: None,
+            })
+        );
+        assert_eq!(
+            txs.prove,
+            Some(SettlementTx {
+                hash: prove_tx_hash,
+                chain_id: Some(SLChainId(505)),
+            })
+        );
+        assert_eq!(txs.execute, None);
+    }
+}
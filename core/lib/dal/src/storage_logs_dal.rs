@@ -225,6 +225,30 @@ impl StorageLogsDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns the number of storage logs produced in L2 blocks after the specified one.
+    pub async fn count_storage_logs_after_l2_block(
+        &mut self,
+        block_number: L2BlockNumber,
+    ) -> DalResult<u64> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number > $1
+            "#,
+            i64::from(block_number.0)
+        )
+        .instrument("count_storage_logs_after_l2_block")
+        .with_arg("block_number", &block_number)
+        .fetch_one(self.storage)
+        .await?
+        .count;
+        Ok(count as u64)
+    }
+
     /// Returns addresses and the corresponding deployment L2 block numbers among the specified contract
     /// `addresses`. `at_l2_block` allows filtering deployment by L2 blocks.
     pub async fn filter_deployed_contracts(
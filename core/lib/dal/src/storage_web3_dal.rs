@@ -77,6 +77,62 @@ impl StorageWeb3Dal<'_, '_> {
         Ok(h256_to_u256(balance))
     }
 
+    /// Batched version of [`Self::standard_token_historical_balance()`] returning balances at the end of each of
+    /// the specified L2 blocks.
+    pub async fn standard_token_historical_balances(
+        &mut self,
+        token_id: AccountTreeId,
+        account_id: AccountTreeId,
+        block_numbers: &[L2BlockNumber],
+    ) -> DalResult<HashMap<L2BlockNumber, U256>> {
+        let hashed_key =
+            storage_key_for_standard_token_balance(token_id, account_id.address()).hashed_key();
+        let block_numbers_i64: Vec<_> = block_numbers
+            .iter()
+            .map(|number| i64::from(number.0))
+            .collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                blocks.number AS "number!",
+                (
+                    SELECT
+                        value
+                    FROM
+                        storage_logs
+                    WHERE
+                        storage_logs.hashed_key = $1
+                        AND storage_logs.miniblock_number <= blocks.number
+                    ORDER BY
+                        storage_logs.miniblock_number DESC,
+                        storage_logs.operation_number DESC
+                    LIMIT
+                        1
+                ) AS "value?"
+            FROM
+                UNNEST($2::BIGINT []) AS blocks (number)
+            "#,
+            hashed_key.as_bytes(),
+            &block_numbers_i64
+        )
+        .instrument("standard_token_historical_balances")
+        .report_latency()
+        .with_arg("key", &hashed_key)
+        .with_arg("block_numbers.len", &block_numbers.len())
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let balance = row
+                    .value
+                    .map_or_else(U256::zero, |value| h256_to_u256(H256::from_slice(&value)));
+                (L2BlockNumber(row.number as u32), balance)
+            })
+            .collect())
+    }
+
     /// Gets the current value for the specified `key`. Uses state of the latest sealed L2 block.
     /// Returns error if there is no sealed L2 blocks.
     // FIXME: propagate hashed_key?
@@ -310,7 +366,11 @@ impl StorageWeb3Dal<'_, '_> {
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::{block::L1BatchHeader, ProtocolVersion, ProtocolVersionId};
+    use zksync_system_constants::L2_BASE_TOKEN_ADDRESS;
+    use zksync_types::{
+        block::L1BatchHeader, u256_to_h256, utils::storage_key_for_eth_balance, ProtocolVersion,
+        ProtocolVersionId, StorageLog,
+    };
 
     use super::*;
     use crate::{
@@ -459,4 +519,55 @@ mod tests {
             .unwrap();
         assert_eq!(timestamp, Some(first_l2_block.timestamp));
     }
+
+    #[tokio::test]
+    async fn getting_historical_balances() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let account = Address::repeat_byte(1);
+        let balance_key = storage_key_for_eth_balance(&account);
+        for (number, balance) in [(1, Some(100_u64)), (2, None), (3, Some(50))] {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+            if let Some(balance) = balance {
+                let log = StorageLog::new_write_log(balance_key, u256_to_h256(balance.into()));
+                conn.storage_logs_dal()
+                    .insert_storage_logs(L2BlockNumber(number), &[log])
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let block_numbers: Vec<_> = (0..=4).map(L2BlockNumber).collect();
+        let balances = conn
+            .storage_web3_dal()
+            .standard_token_historical_balances(
+                AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
+                AccountTreeId::new(account),
+                &block_numbers,
+            )
+            .await
+            .unwrap();
+        assert_eq!(balances.len(), block_numbers.len());
+        for number in block_numbers {
+            let expected_balance = conn
+                .storage_web3_dal()
+                .standard_token_historical_balance(
+                    AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
+                    AccountTreeId::new(account),
+                    number,
+                )
+                .await
+                .unwrap();
+            assert_eq!(balances[&number], expected_balance, "{number}");
+        }
+        assert_eq!(balances[&L2BlockNumber(2)], 100.into());
+        assert_eq!(balances[&L2BlockNumber(4)], 50.into());
+    }
 }
@@ -268,6 +268,73 @@ impl TeeProofGenerationDal<'_, '_> {
         Ok(())
     }
 
+    /// Saves an attestation that was verified by the caller, binding `pubkey` to the specified TEE type.
+    /// Unlike [`Self::save_attestation()`], overwrites the attestation if one is already stored for `pubkey`.
+    pub async fn save_verified_attestation(
+        &mut self,
+        pubkey: &[u8],
+        attestation: &[u8],
+        tee_type: TeeType,
+    ) -> DalResult<()> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO
+            tee_attestations (pubkey, attestation, tee_type, verified_at)
+            VALUES
+            ($1, $2, $3, NOW())
+            ON CONFLICT (pubkey) DO
+            UPDATE
+            SET
+            attestation = excluded.attestation,
+            tee_type = excluded.tee_type,
+            verified_at = excluded.verified_at
+            "#,
+            pubkey,
+            attestation,
+            tee_type.to_string()
+        );
+        let instrumentation = Instrumented::new("save_verified_attestation")
+            .with_arg("pubkey", &pubkey)
+            .with_arg("tee_type", &tee_type);
+        instrumentation
+            .clone()
+            .with(query)
+            .execute(self.storage)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Checks whether `pubkey` is bound to a verified attestation for the specified TEE type.
+    pub async fn is_attestation_verified(
+        &mut self,
+        pubkey: &[u8],
+        tee_type: TeeType,
+    ) -> DalResult<bool> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT
+                COUNT(*)
+            FROM
+                tee_attestations
+            WHERE
+                pubkey = $1
+                AND tee_type = $2
+                AND verified_at IS NOT NULL
+            "#,
+            pubkey,
+            tee_type.to_string()
+        )
+        .instrument("is_attestation_verified")
+        .with_arg("pubkey", &pubkey)
+        .with_arg("tee_type", &tee_type)
+        .fetch_one(self.storage)
+        .await?
+        .unwrap_or(0);
+
+        Ok(count != 0)
+    }
+
     pub async fn get_tee_proofs(
         &mut self,
         batch_number: L1BatchNumber,
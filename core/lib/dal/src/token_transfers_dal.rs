@@ -0,0 +1,786 @@
+//! Optional index of token transfers by the sender and recipient address.
+
+use std::ops;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_system_constants::L2_BASE_TOKEN_ADDRESS;
+use zksync_types::{
+    api::{AddressTransactionDirection, TokenTransfer},
+    h256_to_address,
+    tx::IncludedTxLocation,
+    Address, L2BlockNumber, H256, U256,
+};
+use zksync_vm_interface::VmEvent;
+
+use crate::Core;
+
+/// Long signature of the ERC-20 `Transfer(address,address,uint256)` event.
+const TRANSFER_EVENT_SIGNATURE: H256 = H256([
+    221, 242, 82, 173, 27, 226, 200, 155, 105, 194, 176, 104, 252, 55, 141, 170, 149, 43, 167, 241,
+    99, 196, 161, 22, 40, 245, 90, 77, 245, 35, 179, 239,
+]);
+/// Long signature of the `Mint(address,uint256)` event emitted by the base token contract on deposits.
+const MINT_EVENT_SIGNATURE: H256 = H256([
+    15, 103, 152, 165, 96, 121, 58, 84, 195, 188, 254, 134, 169, 60, 222, 30, 115, 8, 125, 148, 76,
+    14, 162, 5, 68, 19, 125, 65, 33, 57, 104, 133,
+]);
+/// Long signature of the `Withdrawal(address,address,uint256)` event emitted by the base token contract on withdrawals.
+const WITHDRAWAL_EVENT_SIGNATURE: H256 = H256([
+    39, 23, 234, 214, 185, 32, 13, 210, 53, 170, 212, 104, 201, 128, 158, 164, 0, 254, 51, 172,
+    105, 181, 191, 170, 109, 62, 144, 252, 146, 43, 99, 152,
+]);
+/// Long signature of the `WithdrawalWithMessage(address,address,uint256,bytes)` event emitted by the base token contract
+/// on withdrawals with an additional message.
+const WITHDRAWAL_WITH_MESSAGE_EVENT_SIGNATURE: H256 = H256([
+    196, 5, 254, 137, 88, 65, 11, 186, 240, 199, 59, 122, 12, 62, 32, 133, 158, 134, 202, 22, 138,
+    76, 155, 13, 239, 156, 84, 210, 85, 90, 48, 107,
+]);
+
+/// Parses a token transfer from an event. Recognizes ERC-20 `Transfer` events (ERC-721 transfers, which have
+/// an additional indexed topic, are skipped), base token mints, which are represented as transfers
+/// from the zero address, and base token withdrawals, which are represented as transfers to the zero address
+/// (the L1 receiver of a withdrawal is not an L2 account, so it's not indexed).
+fn parse_transfer(event: &VmEvent) -> Option<(Address, Address, U256)> {
+    // The transferred amount is the first word of data for all recognized events.
+    let amount = U256::from_big_endian(event.value.get(..32)?);
+    let has_single_word = event.value.len() == 32;
+    let is_base_token = event.address == L2_BASE_TOKEN_ADDRESS;
+    match event.indexed_topics.as_slice() {
+        [signature, from, to] if *signature == TRANSFER_EVENT_SIGNATURE && has_single_word => {
+            Some((h256_to_address(from), h256_to_address(to), amount))
+        }
+        [signature, account]
+            if *signature == MINT_EVENT_SIGNATURE && is_base_token && has_single_word =>
+        {
+            Some((Address::zero(), h256_to_address(account), amount))
+        }
+        [signature, sender, _]
+            if *signature == WITHDRAWAL_EVENT_SIGNATURE && is_base_token && has_single_word =>
+        {
+            Some((h256_to_address(sender), Address::zero(), amount))
+        }
+        [signature, sender, _]
+            if *signature == WITHDRAWAL_WITH_MESSAGE_EVENT_SIGNATURE && is_base_token =>
+        {
+            Some((h256_to_address(sender), Address::zero(), amount))
+        }
+        _ => None,
+    }
+}
+
+/// Aggregated transfers of a token involving a certain address in a single L2 block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenBalanceChange {
+    pub l2_block_number: L2BlockNumber,
+    pub received: U256,
+    pub sent: U256,
+}
+
+#[derive(Debug)]
+pub struct TokenTransfersDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl TokenTransfersDal<'_, '_> {
+    /// Indexes token transfers among events emitted in the specified L2 block. Events must be grouped
+    /// in the same way as for [`EventsDal::save_events()`](crate::events_dal::EventsDal::save_events()),
+    /// so that transfers are indexed with the same event indices.
+    pub async fn insert_token_transfers(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+        all_block_events: &[(IncludedTxLocation, Vec<&VmEvent>)],
+    ) -> DalResult<()> {
+        let mut event_indices = vec![];
+        let mut tx_hashes = vec![];
+        let mut tx_indices = vec![];
+        let mut tokens = vec![];
+        let mut senders = vec![];
+        let mut recipients = vec![];
+        let mut amounts = vec![];
+
+        let mut event_index_in_block = 0_i32;
+        for (tx_location, events) in all_block_events {
+            for event in events {
+                if let Some((from, to, amount)) = parse_transfer(event) {
+                    event_indices.push(event_index_in_block);
+                    tx_hashes.push(tx_location.tx_hash.as_bytes().to_vec());
+                    tx_indices.push(tx_location.tx_index_in_l2_block as i32);
+                    tokens.push(event.address.as_bytes().to_vec());
+                    senders.push(from.as_bytes().to_vec());
+                    recipients.push(to.as_bytes().to_vec());
+                    let mut amount_bytes = [0_u8; 32];
+                    amount.to_big_endian(&mut amount_bytes);
+                    amounts.push(amount_bytes.to_vec());
+                }
+                event_index_in_block += 1;
+            }
+        }
+        if event_indices.is_empty() {
+            return Ok(());
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            token_transfers (
+                miniblock_number,
+                event_index_in_block,
+                tx_hash,
+                tx_index_in_block,
+                token_address,
+                from_address,
+                to_address,
+                amount
+            )
+            SELECT
+                $1,
+                u.event_index_in_block,
+                u.tx_hash,
+                u.tx_index_in_block,
+                u.token_address,
+                u.from_address,
+                u.to_address,
+                u.amount
+            FROM
+                UNNEST(
+                    $2::INT [],
+                    $3::bytea [],
+                    $4::INT [],
+                    $5::bytea [],
+                    $6::bytea [],
+                    $7::bytea [],
+                    $8::bytea []
+                )
+                AS u (
+                    event_index_in_block,
+                    tx_hash,
+                    tx_index_in_block,
+                    token_address,
+                    from_address,
+                    to_address,
+                    amount
+                )
+            "#,
+            i64::from(l2_block_number.0),
+            &event_indices,
+            &tx_hashes,
+            &tx_indices,
+            &tokens,
+            &senders,
+            &recipients,
+            &amounts
+        )
+        .instrument("insert_token_transfers")
+        .with_arg("l2_block_number", &l2_block_number)
+        .with_arg("transfers.len", &event_indices.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Indexes token transfers in the specified L2 block range based on the data in the `events` table.
+    /// Already indexed transfers are skipped. Returns the number of inserted index entries.
+    pub async fn backfill_token_transfers(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            INSERT INTO
+            token_transfers (
+                miniblock_number,
+                event_index_in_block,
+                tx_hash,
+                tx_index_in_block,
+                token_address,
+                from_address,
+                to_address,
+                amount
+            )
+            SELECT
+                miniblock_number,
+                event_index_in_block,
+                tx_hash,
+                tx_index_in_block,
+                address,
+                SUBSTRING(topic2 FROM 13),
+                SUBSTRING(topic3 FROM 13),
+                value
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND topic1 = $3
+                AND LENGTH(topic3) = 32
+                AND topic4 = ''
+                AND LENGTH(value) = 32
+            UNION ALL
+            SELECT
+                miniblock_number,
+                event_index_in_block,
+                tx_hash,
+                tx_index_in_block,
+                address,
+                $5,
+                SUBSTRING(topic2 FROM 13),
+                value
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND address = $4
+                AND topic1 = $6
+                AND topic3 = ''
+                AND LENGTH(value) = 32
+            UNION ALL
+            SELECT
+                miniblock_number,
+                event_index_in_block,
+                tx_hash,
+                tx_index_in_block,
+                address,
+                SUBSTRING(topic2 FROM 13),
+                $5,
+                SUBSTRING(value FROM 1 FOR 32)
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND address = $4
+                AND LENGTH(topic3) = 32
+                AND topic4 = ''
+                AND (
+                    (topic1 = $7 AND LENGTH(value) = 32)
+                    OR (topic1 = $8 AND LENGTH(value) >= 32)
+                )
+            ON CONFLICT DO NOTHING
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            TRANSFER_EVENT_SIGNATURE.as_bytes(),
+            L2_BASE_TOKEN_ADDRESS.as_bytes(),
+            Address::zero().as_bytes(),
+            MINT_EVENT_SIGNATURE.as_bytes(),
+            WITHDRAWAL_EVENT_SIGNATURE.as_bytes(),
+            WITHDRAWAL_WITH_MESSAGE_EVENT_SIGNATURE.as_bytes()
+        )
+        .instrument("backfill_token_transfers")
+        .with_arg("l2_blocks", &l2_blocks)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    /// Returns the earliest L2 block containing indexed transfers.
+    pub async fn get_earliest_indexed_l2_block(&mut self) -> DalResult<Option<L2BlockNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(miniblock_number) AS "number"
+            FROM
+                token_transfers
+            "#
+        )
+        .instrument("get_earliest_indexed_l2_block")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.number.map(|number| L2BlockNumber(number as u32)))
+    }
+
+    /// Removes index entries for L2 blocks with numbers strictly greater than the specified `block_number`.
+    pub async fn roll_back_token_transfers(
+        &mut self,
+        block_number: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM token_transfers
+            WHERE
+                miniblock_number > $1
+            "#,
+            i64::from(block_number.0)
+        )
+        .instrument("roll_back_token_transfers")
+        .with_arg("block_number", &block_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns transfers involving `address` in the specified L2 block range, newest first.
+    /// Only transfers strictly before `before` (if specified) are returned.
+    pub async fn get_token_transfers(
+        &mut self,
+        address: Address,
+        token: Option<Address>,
+        direction: AddressTransactionDirection,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        before: Option<(L2BlockNumber, u32)>,
+        limit: usize,
+    ) -> DalResult<Vec<TokenTransfer>> {
+        let (include_sent, include_received) = match direction {
+            AddressTransactionDirection::Sent => (true, false),
+            AddressTransactionDirection::Received => (false, true),
+            AddressTransactionDirection::All => (true, true),
+        };
+        let (before_block, before_index) = match before {
+            Some((block, index)) => (i64::from(block.0), index as i32),
+            None => (i64::from(l2_blocks.end().0) + 1, 0),
+        };
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                event_index_in_block,
+                tx_hash,
+                tx_index_in_block,
+                token_address,
+                from_address,
+                to_address,
+                amount
+            FROM
+                token_transfers
+            WHERE
+                ((from_address = $1 AND $2) OR (to_address = $1 AND $3))
+                AND ($4::bytea IS NULL OR token_address = $4)
+                AND miniblock_number BETWEEN $5 AND $6
+                AND (miniblock_number, event_index_in_block) < ($7, $8)
+            ORDER BY
+                miniblock_number DESC,
+                event_index_in_block DESC
+            LIMIT
+                $9
+            "#,
+            address.as_bytes(),
+            include_sent,
+            include_received,
+            token.as_ref().map(Address::as_bytes),
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            before_block,
+            before_index,
+            limit as i64
+        )
+        .instrument("get_token_transfers")
+        .with_arg("address", &address)
+        .with_arg("token", &token)
+        .with_arg("direction", &direction)
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("before", &before)
+        .with_arg("limit", &limit)
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TokenTransfer {
+                block_number: L2BlockNumber(row.miniblock_number as u32),
+                transaction_hash: H256::from_slice(&row.tx_hash),
+                transaction_index: row.tx_index_in_block as u32,
+                log_index: row.event_index_in_block as u32,
+                token: Address::from_slice(&row.token_address),
+                from: Address::from_slice(&row.from_address),
+                to: Address::from_slice(&row.to_address),
+                amount: U256::from_big_endian(&row.amount),
+            })
+            .collect())
+    }
+
+    /// Returns per-block aggregated transfers of `token` involving `address` for up to `limit` latest L2 blocks
+    /// in the specified range, newest first.
+    pub async fn get_balance_changes(
+        &mut self,
+        address: Address,
+        token: Address,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        limit: usize,
+    ) -> DalResult<Vec<TokenBalanceChange>> {
+        let rows = sqlx::query!(
+            r#"
+            WITH
+            changed_blocks AS (
+                SELECT
+                    miniblock_number
+                FROM
+                    (
+                        SELECT
+                            miniblock_number
+                        FROM
+                            token_transfers
+                        WHERE
+                            from_address = $1
+                            AND token_address = $2
+                            AND miniblock_number BETWEEN $3 AND $4
+                        UNION
+                        SELECT
+                            miniblock_number
+                        FROM
+                            token_transfers
+                        WHERE
+                            to_address = $1
+                            AND token_address = $2
+                            AND miniblock_number BETWEEN $3 AND $4
+                    ) AS blocks
+                ORDER BY
+                    miniblock_number DESC
+                LIMIT
+                    $5
+            )
+
+            SELECT
+                token_transfers.miniblock_number,
+                from_address,
+                to_address,
+                amount
+            FROM
+                token_transfers
+            INNER JOIN changed_blocks
+                ON token_transfers.miniblock_number = changed_blocks.miniblock_number
+            WHERE
+                token_address = $2
+                AND (from_address = $1 OR to_address = $1)
+            ORDER BY
+                token_transfers.miniblock_number DESC
+            "#,
+            address.as_bytes(),
+            token.as_bytes(),
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            limit as i64
+        )
+        .instrument("get_balance_changes")
+        .with_arg("address", &address)
+        .with_arg("token", &token)
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("limit", &limit)
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut changes = Vec::<TokenBalanceChange>::new();
+        for row in rows {
+            let l2_block_number = L2BlockNumber(row.miniblock_number as u32);
+            let change = match changes.last_mut() {
+                Some(change) if change.l2_block_number == l2_block_number => change,
+                _ => {
+                    changes.push(TokenBalanceChange {
+                        l2_block_number,
+                        received: U256::zero(),
+                        sent: U256::zero(),
+                    });
+                    changes.last_mut().unwrap()
+                }
+            };
+            // Amounts are saturated to not fail on malicious tokens emitting bogus events.
+            let amount = U256::from_big_endian(&row.amount);
+            if row.from_address == address.as_bytes() {
+                change.sent = change.sent.saturating_add(amount);
+            }
+            if row.to_address == address.as_bytes() {
+                change.received = change.received.saturating_add(amount);
+            }
+        }
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{ethabi, L1BatchNumber, ProtocolVersion};
+
+    use super::*;
+    use crate::{tests::create_l2_block_header, ConnectionPool, CoreDal};
+
+    fn transfer_event(token: Address, from: Address, to: Address, amount: u64) -> VmEvent {
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: token,
+            indexed_topics: vec![TRANSFER_EVENT_SIGNATURE, H256::from(from), H256::from(to)],
+            value: H256::from_low_u64_be(amount).0.to_vec(),
+        }
+    }
+
+    fn mint_event(to: Address, amount: u64) -> VmEvent {
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: L2_BASE_TOKEN_ADDRESS,
+            indexed_topics: vec![MINT_EVENT_SIGNATURE, H256::from(to)],
+            value: H256::from_low_u64_be(amount).0.to_vec(),
+        }
+    }
+
+    fn withdrawal_event(from: Address, l1_receiver: Address, amount: u64) -> VmEvent {
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: L2_BASE_TOKEN_ADDRESS,
+            indexed_topics: vec![
+                WITHDRAWAL_EVENT_SIGNATURE,
+                H256::from(from),
+                H256::from(l1_receiver),
+            ],
+            value: H256::from_low_u64_be(amount).0.to_vec(),
+        }
+    }
+
+    fn tx_location(index: u32) -> IncludedTxLocation {
+        IncludedTxLocation {
+            tx_hash: H256::repeat_byte(index as u8 + 1),
+            tx_index_in_l2_block: index,
+            tx_initiator_address: Address::default(),
+        }
+    }
+
+    #[test]
+    fn event_signatures_match() {
+        let transfer_signature = ethabi::long_signature(
+            "Transfer",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+            ],
+        );
+        assert_eq!(TRANSFER_EVENT_SIGNATURE, transfer_signature);
+        let mint_signature = ethabi::long_signature(
+            "Mint",
+            &[ethabi::ParamType::Address, ethabi::ParamType::Uint(256)],
+        );
+        assert_eq!(MINT_EVENT_SIGNATURE, mint_signature);
+        let withdrawal_signature = ethabi::long_signature(
+            "Withdrawal",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+            ],
+        );
+        assert_eq!(WITHDRAWAL_EVENT_SIGNATURE, withdrawal_signature);
+        let withdrawal_with_message_signature = ethabi::long_signature(
+            "WithdrawalWithMessage",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+                ethabi::ParamType::Bytes,
+            ],
+        );
+        assert_eq!(
+            WITHDRAWAL_WITH_MESSAGE_EVENT_SIGNATURE,
+            withdrawal_with_message_signature
+        );
+    }
+
+    #[tokio::test]
+    async fn indexing_and_querying_token_transfers() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let alice = Address::repeat_byte(1);
+        let bob = Address::repeat_byte(2);
+        let token = Address::repeat_byte(0x10);
+
+        let deposit = mint_event(alice, 1_000);
+        let base_token_transfer = transfer_event(L2_BASE_TOKEN_ADDRESS, alice, bob, 100);
+        let token_transfer = transfer_event(token, bob, alice, 5);
+        let mut erc721_transfer = transfer_event(token, bob, alice, 0);
+        erc721_transfer.indexed_topics.push(H256::repeat_byte(1));
+        erc721_transfer.value = vec![];
+        let unrelated_event = VmEvent {
+            indexed_topics: vec![H256::repeat_byte(0xff)],
+            ..mint_event(alice, 1)
+        };
+
+        conn.token_transfers_dal()
+            .insert_token_transfers(
+                L2BlockNumber(1),
+                &[
+                    (tx_location(0), vec![&deposit, &unrelated_event]),
+                    (
+                        tx_location(1),
+                        vec![&base_token_transfer, &erc721_transfer, &token_transfer],
+                    ),
+                ],
+            )
+            .await
+            .unwrap();
+        let self_transfer = transfer_event(L2_BASE_TOKEN_ADDRESS, alice, alice, 10);
+        conn.token_transfers_dal()
+            .insert_token_transfers(L2BlockNumber(3), &[(tx_location(0), vec![&self_transfer])])
+            .await
+            .unwrap();
+
+        let all_blocks = L2BlockNumber(0)..=L2BlockNumber(10);
+        let mut dal = conn.token_transfers_dal();
+        let transfers = dal
+            .get_token_transfers(
+                alice,
+                None,
+                AddressTransactionDirection::All,
+                all_blocks.clone(),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        let log_indices: Vec<_> = transfers
+            .iter()
+            .map(|transfer| (transfer.block_number.0, transfer.log_index))
+            .collect();
+        assert_eq!(log_indices, [(3, 0), (1, 4), (1, 2), (1, 0)]);
+        assert_eq!(transfers[1].token, token);
+        assert_eq!(transfers[1].transaction_hash, tx_location(1).tx_hash);
+        assert_eq!(transfers[1].transaction_index, 1);
+        assert_eq!(transfers[3].from, Address::zero());
+        assert_eq!(transfers[3].to, alice);
+        assert_eq!(transfers[3].amount, 1_000.into());
+
+        let sent_base_token_transfers = dal
+            .get_token_transfers(
+                alice,
+                Some(L2_BASE_TOKEN_ADDRESS),
+                AddressTransactionDirection::Sent,
+                all_blocks.clone(),
+                Some((L2BlockNumber(3), 0)),
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(sent_base_token_transfers.len(), 1);
+        assert_eq!(sent_base_token_transfers[0].to, bob);
+
+        let changes = dal
+            .get_balance_changes(alice, L2_BASE_TOKEN_ADDRESS, all_blocks.clone(), 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            changes,
+            [
+                TokenBalanceChange {
+                    l2_block_number: L2BlockNumber(3),
+                    received: 10.into(),
+                    sent: 10.into(),
+                },
+                TokenBalanceChange {
+                    l2_block_number: L2BlockNumber(1),
+                    received: 1_000.into(),
+                    sent: 100.into(),
+                },
+            ]
+        );
+        let changes = dal
+            .get_balance_changes(alice, L2_BASE_TOKEN_ADDRESS, all_blocks, 1)
+            .await
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].l2_block_number, L2BlockNumber(3));
+
+        dal.roll_back_token_transfers(L2BlockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_earliest_indexed_l2_block().await.unwrap(),
+            Some(L2BlockNumber(1))
+        );
+        let changes = dal
+            .get_balance_changes(
+                alice,
+                L2_BASE_TOKEN_ADDRESS,
+                L2BlockNumber(0)..=L2BlockNumber(10),
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn backfilling_token_transfers() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(1))
+            .await
+            .unwrap();
+
+        let alice = Address::repeat_byte(1);
+        let bob = Address::repeat_byte(2);
+        let deposit = mint_event(alice, 1_000);
+        let transfer = transfer_event(L2_BASE_TOKEN_ADDRESS, alice, bob, 100);
+        let withdrawal = withdrawal_event(alice, Address::repeat_byte(0x11), 200);
+        let mut withdrawal_with_message = withdrawal_event(alice, Address::repeat_byte(0x11), 300);
+        withdrawal_with_message.indexed_topics[0] = WITHDRAWAL_WITH_MESSAGE_EVENT_SIGNATURE;
+        withdrawal_with_message.value = ethabi::encode(&[
+            ethabi::Token::Uint(300.into()),
+            ethabi::Token::Bytes(b"message".to_vec()),
+        ]);
+        let all_events = [
+            (tx_location(0), vec![&deposit, &transfer]),
+            (tx_location(1), vec![&withdrawal, &withdrawal_with_message]),
+        ];
+        conn.events_dal()
+            .save_events(L2BlockNumber(1), &all_events)
+            .await
+            .unwrap();
+
+        let inserted_count = conn
+            .token_transfers_dal()
+            .backfill_token_transfers(L2BlockNumber(0)..=L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(inserted_count, 4);
+        let backfilled_transfers = conn
+            .token_transfers_dal()
+            .get_token_transfers(
+                alice,
+                None,
+                AddressTransactionDirection::All,
+                L2BlockNumber(0)..=L2BlockNumber(1),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        let withdrawn_amounts: Vec<_> = backfilled_transfers
+            .iter()
+            .filter(|transfer| transfer.to == Address::zero())
+            .map(|transfer| transfer.amount)
+            .collect();
+        assert_eq!(withdrawn_amounts, [U256::from(300), U256::from(200)]);
+
+        // Backfilled transfers must be identical to ones indexed at seal time.
+        conn.token_transfers_dal()
+            .roll_back_token_transfers(L2BlockNumber(0))
+            .await
+            .unwrap();
+        conn.token_transfers_dal()
+            .insert_token_transfers(L2BlockNumber(1), &all_events)
+            .await
+            .unwrap();
+        let indexed_transfers = conn
+            .token_transfers_dal()
+            .get_token_transfers(
+                alice,
+                None,
+                AddressTransactionDirection::All,
+                L2BlockNumber(0)..=L2BlockNumber(1),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(backfilled_transfers, indexed_transfers);
+
+        let inserted_count = conn
+            .token_transfers_dal()
+            .backfill_token_transfers(L2BlockNumber(0)..=L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(inserted_count, 0);
+    }
+}
@@ -10,9 +10,9 @@ use zksync_db_connection::{
     utils::pg_interval_from_duration,
 };
 use zksync_types::{
-    block::L2BlockExecutionData, debug_flat_call::CallTraceMeta, l1::L1Tx, l2::L2Tx,
-    protocol_upgrade::ProtocolUpgradeTx, Address, ExecuteTransactionCommon, L1BatchNumber,
-    L1BlockNumber, L2BlockNumber, PriorityOpId, ProtocolVersionId, Transaction,
+    api::PendingTransactionReason, block::L2BlockExecutionData, debug_flat_call::CallTraceMeta,
+    l1::L1Tx, l2::L2Tx, protocol_upgrade::ProtocolUpgradeTx, Address, ExecuteTransactionCommon,
+    L1BatchNumber, L1BlockNumber, L2BlockNumber, PriorityOpId, ProtocolVersionId, Transaction,
     TransactionTimeRangeConstraint, H256, PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
 use zksync_vm_interface::{
@@ -1742,6 +1742,30 @@ impl TransactionsDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns the number of transactions included into L2 blocks after the specified one.
+    pub async fn count_transactions_after_l2_block(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<u64> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                transactions
+            WHERE
+                miniblock_number > $1
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("count_transactions_after_l2_block")
+        .with_arg("l2_block_number", &l2_block_number)
+        .fetch_one(self.storage)
+        .await?
+        .count;
+        Ok(count as u64)
+    }
+
     pub async fn remove_stuck_txs(&mut self, stuck_tx_timeout: Duration) -> DalResult<usize> {
         let stuck_tx_timeout = pg_interval_from_duration(stuck_tx_timeout);
         let rows = sqlx::query!(
@@ -1904,6 +1928,139 @@ impl TransactionsDal<'_, '_> {
         Ok(())
     }
 
+    /// Sets the pending `reason` for all pending transactions of the specified accounts.
+    pub async fn set_pending_reason_for_accounts(
+        &mut self,
+        accounts: &[Address],
+        reason: PendingTransactionReason,
+    ) -> DalResult<()> {
+        let addresses: Vec<_> = accounts.iter().map(Address::as_bytes).collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            transaction_pending_reasons (tx_hash, reason, created_at, updated_at)
+            SELECT
+                hash,
+                $2,
+                NOW(),
+                NOW()
+            FROM
+                transactions
+            WHERE
+                initiator_address = ANY($1)
+                AND miniblock_number IS NULL
+                AND error IS NULL
+            ON CONFLICT (tx_hash) DO
+            UPDATE
+            SET
+            reason = excluded.reason,
+            updated_at = NOW()
+            WHERE
+                transaction_pending_reasons.reason != excluded.reason
+            "#,
+            &addresses as &[&[u8]],
+            reason.to_string()
+        )
+        .instrument("set_pending_reason_for_accounts")
+        .with_arg("accounts.len", &accounts.len())
+        .with_arg("reason", &reason)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Clears the pending `reason` for transactions of the specified accounts. Other reasons are retained.
+    pub async fn clear_pending_reason_for_accounts(
+        &mut self,
+        accounts: &[Address],
+        reason: PendingTransactionReason,
+    ) -> DalResult<()> {
+        let addresses: Vec<_> = accounts.iter().map(Address::as_bytes).collect();
+        sqlx::query!(
+            r#"
+            DELETE FROM transaction_pending_reasons
+            USING transactions
+            WHERE
+                transaction_pending_reasons.tx_hash = transactions.hash
+                AND transactions.initiator_address = ANY($1)
+                AND transaction_pending_reasons.reason = $2
+            "#,
+            &addresses as &[&[u8]],
+            reason.to_string()
+        )
+        .instrument("clear_pending_reason_for_accounts")
+        .with_arg("accounts.len", &accounts.len())
+        .with_arg("reason", &reason)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Marks pending L2 transactions not in the mempool that don't cover the specified fees
+    /// with [`PendingTransactionReason::FeeTooLow`].
+    pub async fn mark_underpriced_pending_transactions(
+        &mut self,
+        fee_per_gas: u64,
+        gas_per_pubdata: u32,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            transaction_pending_reasons (tx_hash, reason, created_at, updated_at)
+            SELECT
+                hash,
+                $3,
+                NOW(),
+                NOW()
+            FROM
+                transactions
+            WHERE
+                miniblock_number IS NULL
+                AND in_mempool = FALSE
+                AND error IS NULL
+                AND is_priority = FALSE
+                AND (
+                    max_fee_per_gas < $1
+                    OR gas_per_pubdata_limit < $2
+                )
+            ON CONFLICT (tx_hash) DO
+            UPDATE
+            SET
+            reason = excluded.reason,
+            updated_at = NOW()
+            WHERE
+                transaction_pending_reasons.reason != excluded.reason
+            "#,
+            BigDecimal::from(fee_per_gas),
+            BigDecimal::from(gas_per_pubdata),
+            PendingTransactionReason::FeeTooLow.to_string()
+        )
+        .instrument("mark_underpriced_pending_transactions")
+        .with_arg("fee_per_gas", &fee_per_gas)
+        .with_arg("gas_per_pubdata", &gas_per_pubdata)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Clears pending reasons for the specified transactions, e.g. once they are picked up by the mempool.
+    pub async fn clear_pending_reasons(&mut self, tx_hashes: &[H256]) -> DalResult<()> {
+        let hashes: Vec<_> = tx_hashes.iter().map(H256::as_bytes).collect();
+        sqlx::query!(
+            r#"
+            DELETE FROM transaction_pending_reasons
+            WHERE
+                tx_hash = ANY($1)
+            "#,
+            &hashes as &[&[u8]]
+        )
+        .instrument("clear_pending_reasons")
+        .with_arg("tx_hashes.len", &tx_hashes.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn get_last_processed_l1_block(&mut self) -> DalResult<Option<L1BlockNumber>> {
         let maybe_row = sqlx::query!(
             r#"
@@ -18,6 +18,7 @@ use crate::{
     models::storage_transaction::{
         StorageApiTransaction, StorageTransaction, StorageTransactionDetails,
         StorageTransactionExecutionInfo, StorageTransactionReceipt,
+        StorageTransactionStatusDetails,
     },
     Core, CoreDal,
 };
@@ -336,6 +337,46 @@ impl TransactionsWeb3Dal<'_, '_> {
         Ok(row.map(Into::into))
     }
 
+    /// Returns the status of a transaction together with the reason it's not executed yet or was rejected.
+    pub async fn get_transaction_status_details(
+        &mut self,
+        hash: H256,
+    ) -> DalResult<Option<api::TransactionStatusDetails>> {
+        let row = sqlx::query_as!(
+            StorageTransactionStatusDetails,
+            r#"
+            SELECT
+                transactions.initiator_address,
+                transactions.received_at,
+                transactions.miniblock_number,
+                transactions.error,
+                execute_tx.tx_hash AS "eth_execute_tx_hash?",
+                transaction_pending_reasons.reason AS "pending_reason?"
+            FROM
+                transactions
+            LEFT JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+            LEFT JOIN l1_batches ON l1_batches.number = miniblocks.l1_batch_number
+            LEFT JOIN eth_txs_history AS execute_tx
+                ON (
+                    l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                    AND execute_tx.confirmed_at IS NOT NULL
+                )
+            LEFT JOIN transaction_pending_reasons
+                ON transaction_pending_reasons.tx_hash = transactions.hash
+            WHERE
+                transactions.hash = $1
+                AND transactions.data != '{}'::jsonb
+            "#,
+            hash.as_bytes()
+        )
+        .instrument("get_transaction_status_details")
+        .with_arg("hash", &hash)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
     /// Returns hashes of txs which were received after `from_timestamp` and the time of receiving the last tx.
     pub async fn get_pending_txs_hashes_after(
         &mut self,
@@ -839,4 +880,131 @@ mod tests {
             .unwrap();
         assert_eq!(next_nonce, 2.into());
     }
+
+    #[tokio::test]
+    async fn getting_transaction_status_details() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        let tx = mock_l2_transaction();
+        let tx_hash = tx.hash();
+        let initiator = tx.initiator_account();
+        conn.transactions_dal()
+            .insert_transaction_l2(
+                &tx,
+                TransactionExecutionMetrics::default(),
+                ValidationTraces::default(),
+            )
+            .await
+            .unwrap();
+
+        let details = conn
+            .transactions_web3_dal()
+            .get_transaction_status_details(tx_hash)
+            .await
+            .unwrap()
+            .expect("no transaction");
+        assert!(
+            matches!(details.status, api::TransactionStatus::Pending),
+            "{details:?}"
+        );
+        assert_eq!(details.initiator_address, initiator);
+        assert_eq!(details.pending_reason, None);
+
+        // Fees are sufficient; the transaction shouldn't be marked.
+        conn.transactions_dal()
+            .mark_underpriced_pending_transactions(1, 1)
+            .await
+            .unwrap();
+        let details = conn
+            .transactions_web3_dal()
+            .get_transaction_status_details(tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(details.pending_reason, None);
+
+        conn.transactions_dal()
+            .mark_underpriced_pending_transactions(u64::MAX, 1)
+            .await
+            .unwrap();
+        let details = conn
+            .transactions_web3_dal()
+            .get_transaction_status_details(tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            details.pending_reason,
+            Some(api::PendingTransactionReason::FeeTooLow)
+        );
+
+        conn.transactions_dal()
+            .set_pending_reason_for_accounts(&[initiator], api::PendingTransactionReason::NonceGap)
+            .await
+            .unwrap();
+        let details = conn
+            .transactions_web3_dal()
+            .get_transaction_status_details(tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            details.pending_reason,
+            Some(api::PendingTransactionReason::NonceGap)
+        );
+
+        // Clearing a different reason should be a no-op.
+        conn.transactions_dal()
+            .clear_pending_reason_for_accounts(
+                &[initiator],
+                api::PendingTransactionReason::FeeTooLow,
+            )
+            .await
+            .unwrap();
+        let details = conn
+            .transactions_web3_dal()
+            .get_transaction_status_details(tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            details.pending_reason,
+            Some(api::PendingTransactionReason::NonceGap)
+        );
+
+        conn.transactions_dal()
+            .clear_pending_reasons(&[tx_hash])
+            .await
+            .unwrap();
+        let details = conn
+            .transactions_web3_dal()
+            .get_transaction_status_details(tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(details.pending_reason, None);
+
+        conn.transactions_dal()
+            .mark_tx_as_rejected(tx_hash, "rejected: nonce too high")
+            .await
+            .unwrap();
+        let details = conn
+            .transactions_web3_dal()
+            .get_transaction_status_details(tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            matches!(details.status, api::TransactionStatus::Failed),
+            "{details:?}"
+        );
+        assert_eq!(details.error.as_deref(), Some("rejected: nonce too high"));
+
+        let details = conn
+            .transactions_web3_dal()
+            .get_transaction_status_details(H256::zero())
+            .await
+            .unwrap();
+        assert!(details.is_none());
+    }
 }
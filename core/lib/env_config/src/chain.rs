@@ -106,6 +106,12 @@ mod tests {
             l1_batch_commit_data_generator_mode,
             max_circuits_per_batch: 24100,
             protective_reads_persistence_enabled: true,
+            fee_aware_seal_max_latency_ms: Some(10_000),
+            fee_aware_seal_min_latency_ms: 500,
+            fee_aware_seal_cheap_l1_gas_price: Some(1_000_000_000),
+            fee_aware_seal_max_overhead_per_tx: None,
+            transaction_quotas: vec![],
+            transaction_quota_multicall_contracts: vec![],
         }
     }
 
@@ -139,6 +145,9 @@ mod tests {
             CHAIN_STATE_KEEPER_BOOTLOADER_HASH=0x010007ede999d096c84553fb514d3d6ca76fbf39789dda76bfeda9f3ae06236e
             CHAIN_STATE_KEEPER_DEFAULT_AA_HASH=0x0100055b041eb28aff6e3a6e0f37c31fd053fc9ef142683b05e5f0aee6934066
             CHAIN_STATE_KEEPER_PROTECTIVE_READS_PERSISTENCE_ENABLED=true
+            CHAIN_STATE_KEEPER_FEE_AWARE_SEAL_MAX_LATENCY_MS=10000
+            CHAIN_STATE_KEEPER_FEE_AWARE_SEAL_MIN_LATENCY_MS=500
+            CHAIN_STATE_KEEPER_FEE_AWARE_SEAL_CHEAP_L1_GAS_PRICE=1000000000
             CHAIN_STATE_KEEPER_L1_BATCH_COMMIT_DATA_GENERATOR_MODE="{l1_batch_commit_data_generator_mode}"
         "#
         )
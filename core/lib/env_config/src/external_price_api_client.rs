@@ -1,5 +1,6 @@
 use zksync_config::configs::{
-    external_price_api_client::ForcedPriceClientConfig, ExternalPriceApiClientConfig,
+    external_price_api_client::{ForcedPriceClientConfig, OnchainOraclePriceClientConfig},
+    ExternalPriceApiClientConfig,
 };
 
 use crate::{envy_load, FromEnv};
@@ -9,10 +10,20 @@ impl FromEnv for ExternalPriceApiClientConfig {
         let mut config: ExternalPriceApiClientConfig =
             envy_load("external_price_api_client", "EXTERNAL_PRICE_API_CLIENT_")?;
         config.forced = ForcedPriceClientConfig::from_env().ok();
+        config.onchain_oracle = OnchainOraclePriceClientConfig::from_env().ok();
         Ok(config)
     }
 }
 
+impl FromEnv for OnchainOraclePriceClientConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load(
+            "external_price_api_client_onchain_oracle",
+            "EXTERNAL_PRICE_API_CLIENT_ONCHAIN_ORACLE_",
+        )
+    }
+}
+
 impl FromEnv for ForcedPriceClientConfig {
     fn from_env() -> anyhow::Result<Self> {
         envy_load(
@@ -25,11 +36,12 @@ impl FromEnv for ForcedPriceClientConfig {
 #[cfg(test)]
 mod tests {
     use zksync_config::configs::external_price_api_client::{
-        ExternalPriceApiClientConfig, ForcedPriceClientConfig, DEFAULT_TIMEOUT_MS,
+        ExternalPriceApiClientConfig, ForcedPriceClientConfig, OnchainOraclePriceClientConfig,
+        DEFAULT_TIMEOUT_MS,
     };
 
     use super::*;
-    use crate::test_utils::EnvMutex;
+    use crate::test_utils::{addr, EnvMutex};
 
     static MUTEX: EnvMutex = EnvMutex::new();
 
@@ -45,6 +57,12 @@ mod tests {
                 fluctuation: Some(10),
                 next_value_fluctuation: 1,
             }),
+            onchain_oracle: Some(OnchainOraclePriceClientConfig {
+                contract_address: addr("0x986b5e1e1755e3c2440e960477f25201b0a8bbd4"),
+                max_answer_age_sec: 3600,
+                twap_window_sec: 600,
+            }),
+            composite: None,
         }
     }
 
@@ -59,6 +77,8 @@ mod tests {
             EXTERNAL_PRICE_API_CLIENT_FORCED_DENOMINATOR=1
             EXTERNAL_PRICE_API_CLIENT_FORCED_FLUCTUATION=10
             EXTERNAL_PRICE_API_CLIENT_FORCED_NEXT_VALUE_FLUCTUATION=1
+            EXTERNAL_PRICE_API_CLIENT_ONCHAIN_ORACLE_CONTRACT_ADDRESS=0x986b5e1e1755e3c2440e960477f25201b0a8bbd4
+            EXTERNAL_PRICE_API_CLIENT_ONCHAIN_ORACLE_TWAP_WINDOW_SEC=600
         "#;
         lock.set_env(config);
 
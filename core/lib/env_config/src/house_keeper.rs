@@ -18,6 +18,14 @@ mod tests {
     fn expected_config() -> HouseKeeperConfig {
         HouseKeeperConfig {
             l1_batch_metrics_reporting_interval_ms: 10_000,
+            settlement_sla_check_interval_ms: Some(30_000),
+            settlement_sla_window_size: 50,
+            settlement_sla_percentile: 99.0,
+            settlement_sla_commit_latency_ms: Some(600_000),
+            settlement_sla_prove_latency_ms: Some(3_600_000),
+            settlement_sla_execute_latency_ms: None,
+            settlement_sla_da_dispatch_latency_ms: None,
+            settlement_sla_da_inclusion_latency_ms: None,
         }
     }
 
@@ -26,6 +34,11 @@ mod tests {
         let mut lock = MUTEX.lock();
         let config = r#"
             HOUSE_KEEPER_L1_BATCH_METRICS_REPORTING_INTERVAL_MS="10000"
+            HOUSE_KEEPER_SETTLEMENT_SLA_CHECK_INTERVAL_MS="30000"
+            HOUSE_KEEPER_SETTLEMENT_SLA_WINDOW_SIZE="50"
+            HOUSE_KEEPER_SETTLEMENT_SLA_PERCENTILE="99"
+            HOUSE_KEEPER_SETTLEMENT_SLA_COMMIT_LATENCY_MS="600000"
+            HOUSE_KEEPER_SETTLEMENT_SLA_PROVE_LATENCY_MS="3600000"
         "#;
         lock.set_env(config);
 
@@ -13,7 +13,7 @@ impl FromEnv for ProofDataHandlerConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_basic_types::L1BatchNumber;
+    use zksync_basic_types::{L1BatchNumber, H256};
     use zksync_config::configs::TeeConfig;
 
     use super::*;
@@ -30,6 +30,13 @@ mod tests {
                 first_tee_processed_batch: L1BatchNumber(1337),
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 240,
+                tee_verify_attestations: true,
+                tee_attestation_root_ca_path: Some("/etc/tee/sgx_root_ca.pem".to_owned()),
+                tee_attestation_collateral_path: Some("/etc/tee/collateral.json".to_owned()),
+                tee_attestation_allowed_tcb_statuses: vec!["SWHardeningNeeded".to_owned()],
+                tee_sgx_mrenclave_allowlist: vec![H256::repeat_byte(0x11), H256::repeat_byte(0x22)],
+                tee_sgx_mrsigner_allowlist: vec![],
+                tee_tdx_mrtd_allowlist: vec!["33".repeat(48)],
             },
         }
     }
@@ -43,6 +50,12 @@ mod tests {
             PROOF_DATA_HANDLER_FIRST_TEE_PROCESSED_BATCH="1337"
             PROOF_DATA_HANDLER_TEE_PROOF_GENERATION_TIMEOUT_IN_SECS="600"
             PROOF_DATA_HANDLER_TEE_BATCH_PERMANENTLY_IGNORED_TIMEOUT_IN_HOURS="240"
+            PROOF_DATA_HANDLER_TEE_VERIFY_ATTESTATIONS="true"
+            PROOF_DATA_HANDLER_TEE_ATTESTATION_ROOT_CA_PATH="/etc/tee/sgx_root_ca.pem"
+            PROOF_DATA_HANDLER_TEE_ATTESTATION_COLLATERAL_PATH="/etc/tee/collateral.json"
+            PROOF_DATA_HANDLER_TEE_ATTESTATION_ALLOWED_TCB_STATUSES="SWHardeningNeeded"
+            PROOF_DATA_HANDLER_TEE_SGX_MRENCLAVE_ALLOWLIST="0x1111111111111111111111111111111111111111111111111111111111111111,0x2222222222222222222222222222222222222222222222222222222222222222"
+            PROOF_DATA_HANDLER_TEE_TDX_MRTD_ALLOWLIST="333333333333333333333333333333333333333333333333333333333333333333333333333333333333333333333333"
         "#;
         let mut lock = MUTEX.lock();
         lock.set_env(config);
@@ -193,6 +193,8 @@ mod tests {
             api_key,
             client_timeout_ms: 5000,
             forced: None,
+            onchain_oracle: None,
+            composite: None,
         }))
     }
 
@@ -344,6 +346,8 @@ mod tests {
             client_timeout_ms: 5000,
             source: "coinmarketcap".to_string(),
             forced: None,
+            onchain_oracle: None,
+            composite: None,
         });
 
         let tether: Address = "0xdac17f958d2ee523a2206206994597c13d831ec7"
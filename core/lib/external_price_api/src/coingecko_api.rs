@@ -171,6 +171,8 @@ mod test {
             source: "coingecko".to_string(),
             client_timeout_ms: DEFAULT_TIMEOUT_MS,
             forced: None,
+            onchain_oracle: None,
+            composite: None,
         }
     }
 
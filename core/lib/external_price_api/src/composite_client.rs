@@ -0,0 +1,265 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::future;
+use zksync_config::configs::external_price_api_client::CompositePriceClientConfig;
+use zksync_types::{base_token_ratio::BaseTokenAPIRatio, Address};
+
+use crate::{
+    metrics::{QuoteOutcome, METRICS},
+    PriceAPIClient,
+};
+
+/// Named price source used by [`CompositePriceClient`].
+#[derive(Debug, Clone)]
+pub struct PriceSource {
+    /// Name of the source used in logs and metrics.
+    pub name: String,
+    pub client: Arc<dyn PriceAPIClient>,
+}
+
+impl PriceSource {
+    pub fn new(name: impl Into<String>, client: Arc<dyn PriceAPIClient>) -> Self {
+        Self {
+            name: name.into(),
+            client,
+        }
+    }
+}
+
+/// Price client aggregating quotes from several price sources.
+///
+/// All sources are queried concurrently. Failed and stale quotes are discarded, and the remaining quotes
+/// are compared against their median; quotes deviating from the median by more than the configured percentage
+/// are rejected as outliers. The client returns the median of the remaining quotes, provided that at least
+/// `min_sources` quotes are left. Otherwise, an error is returned, so that the caller can retry or keep using
+/// the previously fetched ratio.
+#[derive(Debug)]
+pub struct CompositePriceClient {
+    sources: Vec<PriceSource>,
+    min_sources: usize,
+    max_deviation_percent: f64,
+    max_quote_age: Duration,
+}
+
+impl CompositePriceClient {
+    pub fn new(sources: Vec<PriceSource>, config: &CompositePriceClientConfig) -> Self {
+        assert!(
+            !sources.is_empty(),
+            "composite price client started with no sources"
+        );
+
+        Self {
+            sources,
+            min_sources: (config.min_sources as usize).max(1),
+            max_deviation_percent: config.max_deviation_percent as f64,
+            max_quote_age: config.max_quote_age(),
+        }
+    }
+
+    async fn fetch_quote(
+        source: &PriceSource,
+        token_address: Address,
+    ) -> anyhow::Result<BaseTokenAPIRatio> {
+        let latency = METRICS.source_latency[&source.name].start();
+        let result = source.client.fetch_ratio(token_address).await;
+        latency.observe();
+        result
+    }
+
+    fn report_outcome(source_name: &str, outcome: QuoteOutcome) {
+        METRICS.source_quotes[&(source_name.to_owned(), outcome)].inc();
+    }
+
+    /// Returns quotes that were successfully fetched and are not stale.
+    fn filter_fresh_quotes<'a>(
+        &self,
+        results: Vec<(&'a PriceSource, anyhow::Result<BaseTokenAPIRatio>)>,
+    ) -> Vec<(&'a str, BaseTokenAPIRatio)> {
+        let now = Utc::now();
+        let mut quotes = Vec::with_capacity(results.len());
+        for (source, result) in results {
+            let ratio = match result {
+                Ok(ratio) => ratio,
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed fetching ratio from price source `{}`: {err:#}",
+                        source.name
+                    );
+                    Self::report_outcome(&source.name, QuoteOutcome::Error);
+                    continue;
+                }
+            };
+
+            let age = (now - ratio.ratio_timestamp).to_std().unwrap_or_default();
+            if age > self.max_quote_age {
+                tracing::warn!(
+                    "Ratio from price source `{}` is stale: it was produced {age:?} ago, max allowed age is {:?}",
+                    source.name,
+                    self.max_quote_age
+                );
+                Self::report_outcome(&source.name, QuoteOutcome::Stale);
+                continue;
+            }
+
+            METRICS.source_ratio[&source.name].set(ratio_value(&ratio));
+            quotes.push((source.name.as_str(), ratio));
+        }
+        quotes
+    }
+
+    /// Removes quotes deviating from the median by more than the configured percentage.
+    fn reject_outliers(&self, quotes: &mut Vec<(&str, BaseTokenAPIRatio)>) {
+        let Some(median) = median_quote(quotes).map(ratio_value) else {
+            return;
+        };
+
+        quotes.retain(|(source_name, ratio)| {
+            let deviation_percent = (ratio_value(ratio) - median).abs() / median * 100.0;
+            if deviation_percent > self.max_deviation_percent {
+                tracing::warn!(
+                    "Ratio from price source `{source_name}` deviates from the median by {deviation_percent:.2}%, \
+                     max allowed deviation is {}%; ignoring it",
+                    self.max_deviation_percent
+                );
+                Self::report_outcome(source_name, QuoteOutcome::Outlier);
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl PriceAPIClient for CompositePriceClient {
+    async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let results =
+            future::join_all(self.sources.iter().map(|source| async move {
+                (source, Self::fetch_quote(source, token_address).await)
+            }))
+            .await;
+
+        let mut quotes = self.filter_fresh_quotes(results);
+        self.reject_outliers(&mut quotes);
+        METRICS.accepted_quotes.set(quotes.len());
+
+        anyhow::ensure!(
+            quotes.len() >= self.min_sources,
+            "only {} out of {} price sources returned valid ratios, while at least {} are required",
+            quotes.len(),
+            self.sources.len(),
+            self.min_sources
+        );
+        for (source_name, _) in &quotes {
+            Self::report_outcome(source_name, QuoteOutcome::Accepted);
+        }
+        // `unwrap()` is safe: `min_sources` is positive, so `quotes` is not empty.
+        Ok(*median_quote(&quotes).unwrap())
+    }
+}
+
+fn ratio_value(ratio: &BaseTokenAPIRatio) -> f64 {
+    ratio.numerator.get() as f64 / ratio.denominator.get() as f64
+}
+
+/// Returns the median quote. For an even number of quotes, the lower of two middle quotes is returned,
+/// so that the result is always one of the reported ratios rather than a float approximation.
+fn median_quote<'a>(quotes: &'a [(&str, BaseTokenAPIRatio)]) -> Option<&'a BaseTokenAPIRatio> {
+    let mut sorted: Vec<_> = quotes.iter().map(|(_, ratio)| ratio).collect();
+    sorted.sort_by(|&a, &b| ratio_value(a).total_cmp(&ratio_value(b)));
+    sorted.get(sorted.len().checked_sub(1)? / 2).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::*;
+
+    #[derive(Debug)]
+    enum MockSource {
+        Ratio(u64, chrono::DateTime<Utc>),
+        Error,
+    }
+
+    #[async_trait]
+    impl PriceAPIClient for MockSource {
+        async fn fetch_ratio(&self, _token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+            match self {
+                Self::Ratio(numerator, ratio_timestamp) => Ok(BaseTokenAPIRatio {
+                    numerator: NonZeroU64::new(*numerator).unwrap(),
+                    denominator: NonZeroU64::new(1).unwrap(),
+                    ratio_timestamp: *ratio_timestamp,
+                }),
+                Self::Error => Err(anyhow::anyhow!("source is down")),
+            }
+        }
+    }
+
+    fn fresh(numerator: u64) -> MockSource {
+        MockSource::Ratio(numerator, Utc::now())
+    }
+
+    fn config(min_sources: u32) -> CompositePriceClientConfig {
+        CompositePriceClientConfig {
+            sources: vec![],
+            min_sources,
+            max_deviation_percent: 10,
+            max_quote_age_ms: 60_000,
+        }
+    }
+
+    fn composite_client(sources: Vec<MockSource>, min_sources: u32) -> CompositePriceClient {
+        let sources = sources
+            .into_iter()
+            .enumerate()
+            .map(|(i, source)| PriceSource::new(format!("mock_{i}"), Arc::new(source)))
+            .collect();
+        CompositePriceClient::new(sources, &config(min_sources))
+    }
+
+    #[tokio::test]
+    async fn median_of_all_sources() {
+        let client = composite_client(vec![fresh(103), fresh(100), fresh(98)], 3);
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!(ratio.numerator.get(), 100);
+    }
+
+    #[tokio::test]
+    async fn outliers_are_rejected() {
+        let client = composite_client(vec![fresh(100), fresh(101), fresh(500), fresh(1)], 2);
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!(ratio.numerator.get(), 100);
+
+        let client = composite_client(vec![fresh(100), fresh(101), fresh(500), fresh(1)], 3);
+        client.fetch_ratio(Address::zero()).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn failed_and_stale_sources_are_ignored() {
+        let stale_timestamp = Utc::now() - chrono::Duration::minutes(5);
+        let client = composite_client(
+            vec![
+                MockSource::Error,
+                MockSource::Ratio(1_000, stale_timestamp),
+                fresh(200),
+            ],
+            1,
+        );
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!(ratio.numerator.get(), 200);
+
+        let client = composite_client(
+            vec![
+                MockSource::Error,
+                MockSource::Ratio(1_000, stale_timestamp),
+                fresh(200),
+            ],
+            2,
+        );
+        let err = client.fetch_ratio(Address::zero()).await.unwrap_err();
+        assert!(err.to_string().contains("only 1 out of 3"), "{err}");
+    }
+}
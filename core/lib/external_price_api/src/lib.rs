@@ -1,6 +1,9 @@
 pub mod cmc_api;
 pub mod coingecko_api;
+pub mod composite_client;
 pub mod forced_price_client;
+mod metrics;
+pub mod onchain_oracle;
 #[cfg(test)]
 mod tests;
 mod utils;
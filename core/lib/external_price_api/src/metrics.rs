@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use vise::{Buckets, Counter, EncodeLabelValue, Gauge, Histogram, LabeledFamily, Metrics};
+
+/// Outcome of a quote requested from a single price source by the composite client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(crate) enum QuoteOutcome {
+    /// Quote was used to compute the aggregated ratio.
+    Accepted,
+    /// Source returned an error.
+    Error,
+    /// Quote was older than the configured maximum age.
+    Stale,
+    /// Quote deviated too much from the median of the other quotes.
+    Outlier,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "external_price_api")]
+pub(crate) struct ExternalPriceApiMetrics {
+    /// Latency of fetching a quote from a specific price source.
+    #[metrics(buckets = Buckets::LATENCIES, labels = ["source"])]
+    pub source_latency: LabeledFamily<String, Histogram<Duration>>,
+    /// Number of quotes fetched from a specific price source, split by the outcome.
+    #[metrics(labels = ["source", "outcome"])]
+    pub source_quotes: LabeledFamily<(String, QuoteOutcome), Counter, 2>,
+    /// Last ratio reported by a specific price source.
+    #[metrics(labels = ["source"])]
+    pub source_ratio: LabeledFamily<String, Gauge<f64>>,
+    /// Number of quotes used to compute the last aggregated ratio.
+    pub accepted_quotes: Gauge<usize>,
+}
+
+#[vise::register]
+pub(crate) static METRICS: vise::Global<ExternalPriceApiMetrics> = vise::Global::new();
@@ -0,0 +1,491 @@
+//! Price clients reading the base token price from L1 contracts.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use tokio::sync::OnceCell;
+use zksync_config::configs::external_price_api_client::OnchainOraclePriceClientConfig;
+use zksync_eth_client::{
+    clients::{DynClient, L1},
+    CallFunctionArgs, EthInterface,
+};
+use zksync_types::{
+    base_token_ratio::BaseTokenAPIRatio,
+    ethabi::{Contract, Token},
+    web3, Address, U256,
+};
+
+use crate::{utils::get_fraction, PriceAPIClient};
+
+static CHAINLINK_AGGREGATOR_ABI: Lazy<Contract> = Lazy::new(|| {
+    let abi = r#"[
+      {
+        "inputs": [],
+        "name": "decimals",
+        "outputs": [{ "internalType": "uint8", "name": "", "type": "uint8" }],
+        "stateMutability": "view",
+        "type": "function"
+      },
+      {
+        "inputs": [],
+        "name": "latestRoundData",
+        "outputs": [
+          { "internalType": "uint80", "name": "roundId", "type": "uint80" },
+          { "internalType": "int256", "name": "answer", "type": "int256" },
+          { "internalType": "uint256", "name": "startedAt", "type": "uint256" },
+          { "internalType": "uint256", "name": "updatedAt", "type": "uint256" },
+          { "internalType": "uint80", "name": "answeredInRound", "type": "uint80" }
+        ],
+        "stateMutability": "view",
+        "type": "function"
+      }
+    ]"#;
+    serde_json::from_str(abi).unwrap()
+});
+
+static UNISWAP_V3_POOL_ABI: Lazy<Contract> = Lazy::new(|| {
+    let abi = r#"[
+      {
+        "inputs": [],
+        "name": "token0",
+        "outputs": [{ "internalType": "address", "name": "", "type": "address" }],
+        "stateMutability": "view",
+        "type": "function"
+      },
+      {
+        "inputs": [],
+        "name": "token1",
+        "outputs": [{ "internalType": "address", "name": "", "type": "address" }],
+        "stateMutability": "view",
+        "type": "function"
+      },
+      {
+        "inputs": [{ "internalType": "uint32[]", "name": "secondsAgos", "type": "uint32[]" }],
+        "name": "observe",
+        "outputs": [
+          { "internalType": "int56[]", "name": "tickCumulatives", "type": "int56[]" },
+          {
+            "internalType": "uint160[]",
+            "name": "secondsPerLiquidityCumulativeX128s",
+            "type": "uint160[]"
+          }
+        ],
+        "stateMutability": "view",
+        "type": "function"
+      }
+    ]"#;
+    serde_json::from_str(abi).unwrap()
+});
+
+static ERC20_DECIMALS_ABI: Lazy<Contract> = Lazy::new(|| {
+    let abi = r#"[
+      {
+        "inputs": [],
+        "name": "decimals",
+        "outputs": [{ "internalType": "uint8", "name": "", "type": "uint8" }],
+        "stateMutability": "view",
+        "type": "function"
+      }
+    ]"#;
+    serde_json::from_str(abi).unwrap()
+});
+
+/// Calls a view function returning multiple values. [`CallFunctionArgs`] only supports functions
+/// with a single output.
+async fn call_view_function(
+    client: &dyn EthInterface,
+    contract_address: Address,
+    contract_abi: &Contract,
+    name: &str,
+    params: &[Token],
+) -> anyhow::Result<Vec<Token>> {
+    let function = contract_abi.function(name)?;
+    let request = web3::CallRequest {
+        to: Some(contract_address),
+        data: Some(web3::Bytes(function.encode_input(params)?)),
+        ..web3::CallRequest::default()
+    };
+    let output = client
+        .call_contract_function(request, None)
+        .await
+        .with_context(|| format!("failed calling `{name}` on contract {contract_address:?}"))?;
+    function
+        .decode_output(&output.0)
+        .with_context(|| format!("failed decoding output of `{name}`"))
+}
+
+/// Converts the price of 1 base token in ETH to [`BaseTokenAPIRatio`].
+fn ratio_from_price_in_eth(
+    base_token_in_eth: f64,
+    ratio_timestamp: DateTime<Utc>,
+) -> anyhow::Result<BaseTokenAPIRatio> {
+    let (num_in_eth, denom_in_eth) = get_fraction(base_token_in_eth)?;
+    // take reciprocal of price as the oracle price is ETH/BaseToken and BaseToken/ETH is needed
+    Ok(BaseTokenAPIRatio {
+        numerator: denom_in_eth,
+        denominator: num_in_eth,
+        ratio_timestamp,
+    })
+}
+
+/// Price client reading the latest answer of a Chainlink aggregator reporting the base token price in ETH.
+///
+/// The answer is rejected if it's non-positive, if it was computed in an earlier round than the latest one,
+/// or if it's older than the configured maximum age.
+#[derive(Debug)]
+pub struct ChainlinkPriceClient {
+    client: Box<DynClient<L1>>,
+    aggregator_address: Address,
+    max_answer_age: Duration,
+    decimals: OnceCell<u32>,
+}
+
+impl ChainlinkPriceClient {
+    pub fn new(config: OnchainOraclePriceClientConfig, client: Box<DynClient<L1>>) -> Self {
+        Self {
+            client: client.for_component("chainlink_price_client"),
+            aggregator_address: config.contract_address,
+            max_answer_age: config.max_answer_age(),
+            decimals: OnceCell::new(),
+        }
+    }
+
+    async fn decimals(&self) -> anyhow::Result<u32> {
+        let decimals = self
+            .decimals
+            .get_or_try_init(|| async {
+                let decimals: U256 = CallFunctionArgs::new("decimals", ())
+                    .for_contract(self.aggregator_address, &CHAINLINK_AGGREGATOR_ABI)
+                    .call(&self.client)
+                    .await?;
+                anyhow::Ok(decimals.as_u32())
+            })
+            .await?;
+        Ok(*decimals)
+    }
+}
+
+#[async_trait]
+impl PriceAPIClient for ChainlinkPriceClient {
+    async fn fetch_ratio(&self, _token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let decimals = self.decimals().await?;
+        let output = call_view_function(
+            &self.client,
+            self.aggregator_address,
+            &CHAINLINK_AGGREGATOR_ABI,
+            "latestRoundData",
+            &[],
+        )
+        .await?;
+        let (round_id, answer, updated_at, answered_in_round) = match output.as_slice() {
+            [Token::Uint(round_id), Token::Int(answer), _, Token::Uint(updated_at), Token::Uint(answered_in_round)] => {
+                (*round_id, *answer, *updated_at, *answered_in_round)
+            }
+            _ => anyhow::bail!("unexpected `latestRoundData` output: {output:?}"),
+        };
+
+        // `answer` is a two's complement `int256`, so a set highest bit means that it's negative.
+        anyhow::ensure!(
+            !answer.is_zero() && !answer.bit(255),
+            "Chainlink aggregator returned non-positive answer: {answer}"
+        );
+        anyhow::ensure!(
+            answered_in_round >= round_id,
+            "Chainlink answer is carried over from round {answered_in_round}, latest round is {round_id}"
+        );
+        anyhow::ensure!(
+            answer <= U256::from(u128::MAX),
+            "Chainlink answer is too large: {answer}"
+        );
+
+        let updated_at = DateTime::from_timestamp(updated_at.as_u64() as i64, 0)
+            .context("invalid `updatedAt` timestamp")?;
+        let answer_age = (Utc::now() - updated_at).to_std().unwrap_or_default();
+        anyhow::ensure!(
+            answer_age <= self.max_answer_age,
+            "Chainlink answer is stale: it was updated {answer_age:?} ago, max allowed age is {:?}",
+            self.max_answer_age
+        );
+
+        let base_token_in_eth = answer.as_u128() as f64 / 10_f64.powi(decimals as i32);
+        ratio_from_price_in_eth(base_token_in_eth, updated_at)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PoolTokens {
+    token0: Address,
+    token1: Address,
+    decimals0: u32,
+    decimals1: u32,
+}
+
+/// Price client computing a time-weighted average price of the base token in ETH using a Uniswap v3 pool
+/// pairing the base token with WETH.
+#[derive(Debug)]
+pub struct UniswapV3TwapPriceClient {
+    client: Box<DynClient<L1>>,
+    pool_address: Address,
+    twap_window_sec: u32,
+    pool_tokens: OnceCell<PoolTokens>,
+}
+
+impl UniswapV3TwapPriceClient {
+    pub fn new(config: OnchainOraclePriceClientConfig, client: Box<DynClient<L1>>) -> Self {
+        Self {
+            client: client.for_component("uniswap_v3_twap_price_client"),
+            pool_address: config.contract_address,
+            twap_window_sec: config.twap_window_sec,
+            pool_tokens: OnceCell::new(),
+        }
+    }
+
+    async fn call_address(&self, name: &str) -> anyhow::Result<Address> {
+        Ok(CallFunctionArgs::new(name, ())
+            .for_contract(self.pool_address, &UNISWAP_V3_POOL_ABI)
+            .call(&self.client)
+            .await?)
+    }
+
+    async fn token_decimals(&self, token: Address) -> anyhow::Result<u32> {
+        let decimals: U256 = CallFunctionArgs::new("decimals", ())
+            .for_contract(token, &ERC20_DECIMALS_ABI)
+            .call(&self.client)
+            .await?;
+        Ok(decimals.as_u32())
+    }
+
+    async fn pool_tokens(&self) -> anyhow::Result<PoolTokens> {
+        let tokens = self
+            .pool_tokens
+            .get_or_try_init(|| async {
+                let token0 = self.call_address("token0").await?;
+                let token1 = self.call_address("token1").await?;
+                anyhow::Ok(PoolTokens {
+                    token0,
+                    token1,
+                    decimals0: self.token_decimals(token0).await?,
+                    decimals1: self.token_decimals(token1).await?,
+                })
+            })
+            .await?;
+        Ok(*tokens)
+    }
+
+    /// Returns the arithmetic mean tick over the TWAP window, rounded towards negative infinity
+    /// (the same way as Uniswap's `OracleLibrary.consult()`).
+    async fn mean_tick(&self) -> anyhow::Result<i64> {
+        anyhow::ensure!(
+            self.twap_window_sec > 0,
+            "Uniswap v3 TWAP window must be positive"
+        );
+        let seconds_agos = Token::Array(vec![
+            Token::Uint(self.twap_window_sec.into()),
+            Token::Uint(0.into()),
+        ]);
+        let output = call_view_function(
+            &self.client,
+            self.pool_address,
+            &UNISWAP_V3_POOL_ABI,
+            "observe",
+            &[seconds_agos],
+        )
+        .await?;
+        let Some(Token::Array(tick_cumulatives)) = output.first() else {
+            anyhow::bail!("unexpected `observe` output: {output:?}");
+        };
+        let [Token::Int(start), Token::Int(end)] = tick_cumulatives.as_slice() else {
+            anyhow::bail!("unexpected tick cumulatives: {tick_cumulatives:?}");
+        };
+
+        // `int56` values are sign-extended to 256 bits, so the lowest 64 bits are a valid `i64` representation.
+        let delta = (end.low_u64() as i64)
+            .checked_sub(start.low_u64() as i64)
+            .context("tick cumulative delta overflow")?;
+        let window = i64::from(self.twap_window_sec);
+        let mut mean_tick = delta / window;
+        if delta < 0 && delta % window != 0 {
+            mean_tick -= 1;
+        }
+        Ok(mean_tick)
+    }
+}
+
+#[async_trait]
+impl PriceAPIClient for UniswapV3TwapPriceClient {
+    async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let tokens = self.pool_tokens().await?;
+        anyhow::ensure!(
+            tokens.token0 == token_address || tokens.token1 == token_address,
+            "Uniswap v3 pool {:?} does not contain base token {token_address:?}",
+            self.pool_address
+        );
+
+        let mean_tick = self.mean_tick().await?;
+        // Price of 1 `token0` in `token1`, adjusted for token decimals.
+        let token0_in_token1 = 1.0001_f64.powf(mean_tick as f64)
+            * 10_f64.powi(tokens.decimals0 as i32 - tokens.decimals1 as i32);
+        let base_token_in_eth = if tokens.token0 == token_address {
+            token0_in_token1
+        } else {
+            1.0 / token0_in_token1
+        };
+        ratio_from_price_in_eth(base_token_in_eth, Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use zksync_types::web3::BlockId;
+    use zksync_web3_decl::client::MockClient;
+
+    use super::*;
+    use crate::tests::{approximate_value, TEST_TOKEN_ADDRESS};
+
+    const ORACLE_ADDRESS: Address = Address::repeat_byte(0x0a);
+    const WETH_ADDRESS: Address = Address::repeat_byte(0xee);
+
+    fn oracle_config() -> OnchainOraclePriceClientConfig {
+        OnchainOraclePriceClientConfig {
+            contract_address: ORACLE_ADDRESS,
+            max_answer_age_sec: 3_600,
+            twap_window_sec: 600,
+        }
+    }
+
+    /// Creates a mock L1 client dispatching `eth_call`s by the function name.
+    fn mock_l1_client(
+        contract_abi: &'static Contract,
+        handler: impl Fn(Address, &str, Vec<Token>) -> Vec<Token> + Send + Sync + 'static,
+    ) -> Box<DynClient<L1>> {
+        let client = MockClient::builder(L1::default())
+            .method(
+                "eth_call",
+                move |req: web3::CallRequest, _block: BlockId| {
+                    let contract = req.to.unwrap();
+                    let data = req.data.unwrap().0;
+                    let (selector, input) = data.split_at(4);
+                    let function = contract_abi
+                        .functions()
+                        .chain(ERC20_DECIMALS_ABI.functions())
+                        .find(|function| function.short_signature() == selector)
+                        .expect("unexpected function called");
+                    let input = function.decode_input(input).unwrap();
+                    let output = handler(contract, &function.name, input);
+                    Ok(web3::Bytes(zksync_types::ethabi::encode(&output)))
+                },
+            )
+            .build();
+        Box::new(client)
+    }
+
+    fn chainlink_client(answer: U256, updated_at: DateTime<Utc>) -> ChainlinkPriceClient {
+        let client = mock_l1_client(&CHAINLINK_AGGREGATOR_ABI, move |contract, name, _| {
+            assert_eq!(contract, ORACLE_ADDRESS);
+            match name {
+                "decimals" => vec![Token::Uint(18.into())],
+                "latestRoundData" => vec![
+                    Token::Uint(10.into()),
+                    Token::Int(answer),
+                    Token::Uint(updated_at.timestamp().into()),
+                    Token::Uint(updated_at.timestamp().into()),
+                    Token::Uint(10.into()),
+                ],
+                _ => unreachable!(),
+            }
+        });
+        ChainlinkPriceClient::new(oracle_config(), client)
+    }
+
+    #[tokio::test]
+    async fn chainlink_happy_path() {
+        // 1 base token = 0.002 ETH
+        let answer = U256::from(2_000_000_000_000_000_u64);
+        let client = chainlink_client(answer, Utc::now());
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!(ratio.numerator.get(), 500);
+        assert_eq!(ratio.denominator.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn chainlink_rejects_stale_answer() {
+        let answer = U256::from(2_000_000_000_000_000_u64);
+        let updated_at = Utc::now() - chrono::Duration::hours(2);
+        let client = chainlink_client(answer, updated_at);
+        let err = client.fetch_ratio(Address::zero()).await.unwrap_err();
+        assert!(err.to_string().contains("stale"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn chainlink_rejects_negative_answer() {
+        let minus_one = U256::MAX;
+        let client = chainlink_client(minus_one, Utc::now());
+        let err = client.fetch_ratio(Address::zero()).await.unwrap_err();
+        assert!(err.to_string().contains("non-positive"), "{err}");
+    }
+
+    fn int_token(value: i64) -> Token {
+        // Sign-extend the value to 256 bits.
+        let value = if value < 0 {
+            U256::MAX - U256::from(value.unsigned_abs()) + 1
+        } else {
+            U256::from(value as u64)
+        };
+        Token::Int(value)
+    }
+
+    fn uniswap_client(base_token_address: Address, mean_tick: i64) -> UniswapV3TwapPriceClient {
+        let client = mock_l1_client(
+            &UNISWAP_V3_POOL_ABI,
+            move |contract, name, input| match name {
+                "token0" => vec![Token::Address(base_token_address)],
+                "token1" => vec![Token::Address(WETH_ADDRESS)],
+                "decimals" => {
+                    assert!(contract == base_token_address || contract == WETH_ADDRESS);
+                    vec![Token::Uint(18.into())]
+                }
+                "observe" => {
+                    assert_eq!(
+                        input,
+                        [Token::Array(vec![
+                            Token::Uint(600.into()),
+                            Token::Uint(0.into())
+                        ])]
+                    );
+                    let start = -1_000_000;
+                    vec![
+                        Token::Array(vec![int_token(start), int_token(start + mean_tick * 600)]),
+                        Token::Array(vec![Token::Uint(0.into()), Token::Uint(0.into())]),
+                    ]
+                }
+                _ => unreachable!(),
+            },
+        );
+        UniswapV3TwapPriceClient::new(oracle_config(), client)
+    }
+
+    #[tokio::test]
+    async fn uniswap_twap_happy_path() {
+        let base_token_address = Address::from_str(TEST_TOKEN_ADDRESS).unwrap();
+        // 1.0001^(-59_180) ≈ 0.00269, i.e. 1 base token ≈ 0.00269 ETH
+        let client = uniswap_client(base_token_address, -59_180);
+        let ratio = client.fetch_ratio(base_token_address).await.unwrap();
+        let value = approximate_value(&ratio);
+        assert!((value - 371.7).abs() < 1.0, "{value}");
+    }
+
+    #[tokio::test]
+    async fn uniswap_twap_rejects_unrelated_pool() {
+        let client = uniswap_client(Address::repeat_byte(1), 0);
+        let err = client
+            .fetch_ratio(Address::from_str(TEST_TOKEN_ADDRESS).unwrap())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("does not contain"), "{err}");
+    }
+}
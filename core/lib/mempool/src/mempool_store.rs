@@ -1,11 +1,14 @@
-use std::collections::{hash_map, BTreeSet, HashMap};
+use std::{
+    collections::{hash_map, BTreeSet, HashMap},
+    ops::Bound,
+};
 
 use zksync_types::{
     l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction,
     TransactionTimeRangeConstraint,
 };
 
-use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
+use crate::types::{AccountTransactions, L2TxFilter, MempoolScore, TransactionPriority};
 
 #[derive(Debug)]
 pub struct MempoolInfo {
@@ -154,6 +157,23 @@ impl MempoolStore {
     pub fn next_transaction(
         &mut self,
         filter: &L2TxFilter,
+    ) -> Option<(Transaction, TransactionTimeRangeConstraint)> {
+        self.next_transaction_with_priority(
+            filter,
+            |_| TransactionPriority::Normal,
+            TransactionPriority::Normal,
+        )
+    }
+
+    /// Returns next transaction for execution from mempool taking into account priorities of L2 transactions
+    /// returned by `priority`. Deferred transactions are skipped, but are kept in the mempool; among other transactions,
+    /// the oldest one with the highest priority is returned. The search stops once a transaction with `max_priority`
+    /// is found. L1 transactions are always returned first.
+    pub fn next_transaction_with_priority(
+        &mut self,
+        filter: &L2TxFilter,
+        priority: impl Fn(&L2Tx) -> TransactionPriority,
+        max_priority: TransactionPriority,
     ) -> Option<(Transaction, TransactionTimeRangeConstraint)> {
         if let Some(transaction) = self.l1_transactions.remove(&self.next_priority_id) {
             self.next_priority_id += 1;
@@ -164,35 +184,53 @@ impl MempoolStore {
             ));
         }
 
-        let mut removed = 0;
         // We want to fetch the next transaction that would match the fee requirements.
-        let tx_pointer = self
-            .l2_priority_queue
-            .iter()
-            .rfind(|el| el.matches_filter(filter))?
-            .clone();
-
-        let initial_length = self.stashed_accounts.len();
+        let mut selected: Option<(&MempoolScore, TransactionPriority)> = None;
+        for pointer in self.l2_priority_queue.iter().rev() {
+            if !pointer.matches_filter(filter) {
+                continue;
+            }
+            let account_txs = self
+                .l2_transactions_per_account
+                .get(&pointer.account)
+                .expect("mempool: dangling pointer in priority queue");
+            let tx_priority = priority(account_txs.peek());
+            if tx_priority == TransactionPriority::Deferred {
+                continue;
+            }
+            if selected.map_or(true, |(_, selected_priority)| {
+                tx_priority > selected_priority
+            }) {
+                selected = Some((pointer, tx_priority));
+            }
+            if tx_priority >= max_priority {
+                break;
+            }
+        }
+        let tx_pointer = selected?.0.clone();
 
-        // Stash all observed transactions that don't meet criteria
-        for stashed_pointer in self
+        // Stash all observed transactions that don't meet fee criteria
+        let stashed_pointers: Vec<_> = self
             .l2_priority_queue
-            .split_off(&tx_pointer)
-            .into_iter()
-            .skip(1)
-        {
+            .range((Bound::Excluded(&tx_pointer), Bound::Unbounded))
+            .filter(|pointer| !pointer.matches_filter(filter))
+            .cloned()
+            .collect();
+        let mut removed = 0;
+        for stashed_pointer in &stashed_pointers {
+            self.l2_priority_queue.remove(stashed_pointer);
             removed += self
                 .l2_transactions_per_account
                 .remove(&stashed_pointer.account)
                 .expect("mempool: dangling pointer in priority queue")
                 .len();
-
             self.stashed_accounts.push(stashed_pointer.account);
         }
+        self.l2_priority_queue.remove(&tx_pointer);
 
         tracing::debug!(
             "Stashed {} accounts by filter: {:?}",
-            self.stashed_accounts.len() - initial_length,
+            stashed_pointers.len(),
             filter
         );
 
@@ -249,6 +287,15 @@ impl MempoolStore {
         }
     }
 
+    /// Returns accounts whose transactions cannot be executed because a transaction with the next expected nonce
+    /// is missing. This iterates over all accounts in the mempool, so it shouldn't be called on hot paths.
+    pub fn nonce_gap_accounts(&self) -> Vec<Address> {
+        self.l2_transactions_per_account
+            .iter()
+            .filter_map(|(&account, txs)| txs.has_nonce_gap().then_some(account))
+            .collect()
+    }
+
     pub fn stats(&self) -> MempoolStats {
         MempoolStats {
             l1_transaction_count: self.l1_transactions.len(),
@@ -12,7 +12,10 @@ use zksync_types::{
     TransactionTimeRangeConstraint, H256, U256,
 };
 
-use crate::{mempool_store::MempoolStore, types::L2TxFilter};
+use crate::{
+    mempool_store::MempoolStore,
+    types::{L2TxFilter, TransactionPriority},
+};
 
 #[test]
 fn basic_flow() {
@@ -101,6 +104,27 @@ fn missing_txns() {
     );
 }
 
+#[test]
+fn nonce_gap_accounts() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![gen_l2_tx(account0, Nonce(0)), gen_l2_tx(account1, Nonce(6))];
+    let nonces = HashMap::from([(account1, Nonce(5))]);
+    mempool.insert_without_constraints(transactions, nonces);
+    assert_eq!(mempool.nonce_gap_accounts(), vec![account1]);
+
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+    // Executed transactions should not be treated as a gap.
+    assert_eq!(mempool.nonce_gap_accounts(), vec![account1]);
+
+    mempool.insert_without_constraints(vec![gen_l2_tx(account1, Nonce(5))], HashMap::new());
+    assert!(mempool.nonce_gap_accounts().is_empty());
+}
+
 #[test]
 fn prioritize_l1_txns() {
     let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
@@ -321,6 +345,85 @@ fn stashed_accounts() {
     assert!(mempool.next_transaction(&filter_zero).is_none());
 }
 
+#[test]
+fn prioritizing_transactions() {
+    let filter_non_zero = L2TxFilter {
+        fee_input: Default::default(),
+        fee_per_gas: 0u64,
+        gas_per_pubdata: 1u32,
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let deferred_account = Address::random();
+    let normal_account = Address::random();
+    let high_priority_account = Address::random();
+    let stashed_account = Address::random();
+    let now = unix_timestamp_ms();
+    mempool.insert_without_constraints(
+        gen_transactions_for_filtering(vec![
+            (deferred_account, Nonce(0), now - 30, 1),
+            (stashed_account, Nonce(0), now - 20, 0),
+            (normal_account, Nonce(0), now - 10, 1),
+            (high_priority_account, Nonce(0), now, 1),
+            (high_priority_account, Nonce(1), now, 1),
+        ]),
+        HashMap::new(),
+    );
+    let priority = |tx: &L2Tx| match tx.initiator_account() {
+        account if account == deferred_account => TransactionPriority::Deferred,
+        account if account == high_priority_account => TransactionPriority::High,
+        _ => TransactionPriority::Normal,
+    };
+
+    // Without prioritization, the search stops at the oldest non-deferred transaction.
+    let mut other_mempool = MempoolStore::new(PriorityOpId(0), 100);
+    other_mempool.insert_without_constraints(
+        gen_transactions_for_filtering(vec![
+            (deferred_account, Nonce(0), now - 30, 1),
+            (normal_account, Nonce(0), now - 10, 1),
+            (high_priority_account, Nonce(0), now, 1),
+        ]),
+        HashMap::new(),
+    );
+    let tx = other_mempool.next_transaction_with_priority(
+        &filter_non_zero,
+        priority,
+        TransactionPriority::Normal,
+    );
+    assert_eq!(view(tx), (normal_account, 0));
+
+    for nonce in 0..2 {
+        let tx = mempool.next_transaction_with_priority(
+            &filter_non_zero,
+            priority,
+            TransactionPriority::High,
+        );
+        assert_eq!(view(tx), (high_priority_account, nonce));
+    }
+    let tx = mempool.next_transaction_with_priority(
+        &filter_non_zero,
+        priority,
+        TransactionPriority::High,
+    );
+    assert_eq!(view(tx), (normal_account, 0));
+    let tx = mempool.next_transaction_with_priority(
+        &filter_non_zero,
+        priority,
+        TransactionPriority::High,
+    );
+    assert_eq!(tx, None);
+
+    // The deferred transaction is kept in the mempool, while the transaction not matching the filter is stashed.
+    assert_eq!(
+        mempool.get_mempool_info().stashed_accounts,
+        vec![stashed_account]
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&filter_non_zero)),
+        (deferred_account, 0)
+    );
+    assert_eq!(mempool.stats().l2_transaction_count, 0);
+}
+
 #[test]
 fn mempool_capacity() {
     let mut mempool = MempoolStore::new(PriorityOpId(0), 4);
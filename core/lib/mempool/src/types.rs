@@ -79,10 +79,24 @@ impl AccountTransactions {
             .map(|(tx, c)| (Self::score_for_transaction(tx), c.clone()))
     }
 
+    /// Returns the transaction with the next expected nonce. Panics if no such transaction exists
+    pub fn peek(&self) -> &L2Tx {
+        &self
+            .transactions
+            .get(&self.nonce)
+            .expect("missing transaction in mempool")
+            .0
+    }
+
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
 
+    /// Checks whether the account has pending transactions, but none of them has the next expected nonce.
+    pub fn has_nonce_gap(&self) -> bool {
+        !self.transactions.is_empty() && !self.transactions.contains_key(&self.nonce)
+    }
+
     fn score_for_transaction(transaction: &L2Tx) -> MempoolScore {
         MempoolScore {
             account: transaction.initiator_account(),
@@ -147,6 +161,18 @@ pub struct L2TxFilter {
     pub gas_per_pubdata: u32,
 }
 
+/// Priority of an L2 transaction used by [`MempoolStore::next_transaction_with_priority()`].
+///
+/// [`MempoolStore::next_transaction_with_priority()`]: crate::MempoolStore::next_transaction_with_priority()
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TransactionPriority {
+    /// Transaction should not be returned yet, but must be kept in the mempool.
+    Deferred,
+    Normal,
+    /// Transaction should be returned before normal-priority transactions.
+    High,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
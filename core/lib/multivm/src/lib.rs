@@ -16,7 +16,9 @@ pub use crate::{
         vm_1_3_2, vm_1_4_1, vm_1_4_2, vm_boojum_integration, vm_fast, vm_latest, vm_m5, vm_m6,
         vm_refunds_enhancement, vm_virtual_blocks,
     },
-    vm_instance::{is_supported_by_fast_vm, FastVmInstance, LegacyVmInstance},
+    vm_instance::{
+        is_supported_by_fast_vm, is_supported_by_gas_profiler, FastVmInstance, LegacyVmInstance,
+    },
 };
 
 mod glue;
@@ -0,0 +1,212 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use once_cell::sync::OnceCell;
+use zksync_types::Address;
+
+use crate::{
+    glue::tracers::IntoOldVmTracer,
+    interface::{GasProfile, GasProfileFrame},
+};
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+#[derive(Debug, Clone)]
+struct ProfileNode {
+    frame: GasProfileFrame,
+    parent: Option<usize>,
+    self_gas: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PendingCall {
+    pc: u16,
+    gas_before: u64,
+}
+
+#[derive(Debug, Clone)]
+struct ActiveFrame {
+    node: usize,
+    code_address: Address,
+    /// Gas spent in this frame, including nested calls.
+    inclusive_gas: u64,
+    /// Call instruction executed in this frame that hasn't returned yet.
+    pending_call: Option<PendingCall>,
+}
+
+/// VM-agnostic gas profiler shared by tracers for different VM implementations.
+///
+/// The gas cost of an instruction is determined as the difference between gas in the current frame
+/// before and after the instruction. Call instructions are special: their cost is determined when the callee returns,
+/// as the difference between gas before the call and after return, minus the gas spent by the callee.
+/// Call stacks are stored as a trie so that the per-instruction processing doesn't allocate.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GasProfiler {
+    nodes: Vec<ProfileNode>,
+    children: HashMap<(usize, GasProfileFrame), usize>,
+    stack: Vec<ActiveFrame>,
+    pcs: HashMap<(Address, u16), u64>,
+    /// Program counter and gas before the currently executed instruction.
+    current_instruction: Option<(u16, u64)>,
+}
+
+impl GasProfiler {
+    fn push_frame(&mut self, frame: GasProfileFrame, code_address: Address) {
+        let parent = self.stack.last().map(|active| active.node);
+        let node = match parent {
+            Some(parent) => *self.children.entry((parent, frame)).or_insert_with(|| {
+                self.nodes.push(ProfileNode {
+                    frame,
+                    parent: Some(parent),
+                    self_gas: 0,
+                });
+                self.nodes.len() - 1
+            }),
+            None => {
+                self.nodes.push(ProfileNode {
+                    frame,
+                    parent: None,
+                    self_gas: 0,
+                });
+                self.nodes.len() - 1
+            }
+        };
+        self.stack.push(ActiveFrame {
+            node,
+            code_address,
+            inclusive_gas: 0,
+            pending_call: None,
+        });
+    }
+
+    fn charge(&mut self, pc: u16, gas: u64) {
+        let Some(frame) = self.stack.last_mut() else {
+            return;
+        };
+        frame.inclusive_gas += gas;
+        self.nodes[frame.node].self_gas += gas;
+        *self.pcs.entry((frame.code_address, pc)).or_default() += gas;
+    }
+
+    /// Must be called before each executed instruction.
+    pub fn before_instruction(&mut self, code_address: Address, pc: u16, gas: u64) {
+        if self.stack.is_empty() {
+            // The root frame is not necessarily a far call (e.g., the bootloader may be inside a near call
+            // when tracing starts), but this doesn't matter for profiling.
+            let root = GasProfileFrame::FarCall {
+                address: code_address,
+                selector: None,
+            };
+            self.push_frame(root, code_address);
+        }
+        self.current_instruction = Some((pc, gas));
+    }
+
+    /// Must be called after each executed instruction other than calls and returns.
+    pub fn after_instruction(&mut self, gas: u64) {
+        if let Some((pc, gas_before)) = self.current_instruction.take() {
+            self.charge(pc, gas_before.saturating_sub(gas));
+        }
+    }
+
+    fn after_call(&mut self, frame: GasProfileFrame, code_address: Address) {
+        let (Some((pc, gas_before)), Some(caller)) =
+            (self.current_instruction.take(), self.stack.last_mut())
+        else {
+            return;
+        };
+        caller.pending_call = Some(PendingCall { pc, gas_before });
+        self.push_frame(frame, code_address);
+    }
+
+    /// Must be called after a far call instruction, with the callee frame being current.
+    pub fn after_far_call(&mut self, code_address: Address, selector: Option<[u8; 4]>) {
+        let frame = GasProfileFrame::FarCall {
+            address: code_address,
+            selector,
+        };
+        self.after_call(frame, code_address);
+    }
+
+    /// Must be called after a near call instruction, with `entry_pc` being the program counter of the called function.
+    pub fn after_near_call(&mut self, entry_pc: u16) {
+        let Some(code_address) = self.stack.last().map(|frame| frame.code_address) else {
+            return;
+        };
+        self.after_call(GasProfileFrame::NearCall { pc: entry_pc }, code_address);
+    }
+
+    /// Must be called after a return instruction (including ones caused by panics), with `gas` being
+    /// the gas in the caller frame after return.
+    pub fn after_return(&mut self, gas: u64) {
+        self.current_instruction = None;
+        if self.stack.len() <= 1 {
+            // Return from a frame entered before profiling has started; we cannot attribute its cost.
+            return;
+        }
+        let callee = self.stack.pop().unwrap();
+        let Some(call) = self
+            .stack
+            .last_mut()
+            .and_then(|caller| caller.pending_call.take())
+        else {
+            return;
+        };
+        let call_gas = call.gas_before.saturating_sub(gas);
+        let overhead = call_gas.saturating_sub(callee.inclusive_gas);
+        self.stack.last_mut().unwrap().inclusive_gas += callee.inclusive_gas;
+        self.charge(call.pc, overhead);
+    }
+
+    pub fn into_profile(self) -> GasProfile {
+        let mut paths: Vec<Vec<GasProfileFrame>> = Vec::with_capacity(self.nodes.len());
+        let mut stacks = BTreeMap::new();
+        for node in self.nodes {
+            // Parents are always created before their children, so their paths are already computed.
+            let mut path = node
+                .parent
+                .map(|parent| paths[parent].clone())
+                .unwrap_or_default();
+            path.push(node.frame);
+            if node.self_gas > 0 {
+                *stacks.entry(path.clone()).or_default() += node.self_gas;
+            }
+            paths.push(path);
+        }
+        GasProfile {
+            stacks,
+            pcs: self.pcs.into_iter().collect(),
+        }
+    }
+}
+
+/// Tracer attributing gas spent during VM execution to contracts, functions and program counters.
+/// Profiling is only supported for the latest VM version; for older versions, the produced profile is empty.
+#[derive(Debug, Clone)]
+pub struct GasProfilerTracer {
+    profiler: GasProfiler,
+    result: Arc<OnceCell<GasProfile>>,
+}
+
+impl GasProfilerTracer {
+    pub fn new(result: Arc<OnceCell<GasProfile>>) -> Self {
+        Self {
+            profiler: GasProfiler::default(),
+            result,
+        }
+    }
+
+    fn store_result(&mut self) {
+        let profile = std::mem::take(&mut self.profiler).into_profile();
+        self.result.set(profile).ok();
+    }
+}
+
+impl IntoOldVmTracer for GasProfilerTracer {}
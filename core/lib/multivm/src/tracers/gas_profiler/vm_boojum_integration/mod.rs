@@ -0,0 +1,18 @@
+use crate::{
+    interface::{storage::WriteStorage, tracer::VmExecutionStopReason},
+    tracers::{dynamic::vm_1_4_0::DynTracer, GasProfilerTracer},
+    vm_boojum_integration::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for GasProfilerTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for GasProfilerTracer {
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.store_result();
+    }
+}
@@ -0,0 +1,82 @@
+use zk_evm_1_5_0::{
+    tracing::{AfterExecutionData, BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{FarCallABI, Opcode, CALL_IMPLICIT_CALLDATA_FAT_PTR_REGISTER},
+};
+
+use crate::{
+    interface::{
+        storage::{StoragePtr, WriteStorage},
+        tracer::VmExecutionStopReason,
+    },
+    tracers::{dynamic::vm_1_5_0::DynTracer, GasProfilerTracer},
+    vm_latest::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for GasProfilerTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        _data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        let current = &state.vm_local_state.callstack.current;
+        self.profiler.before_instruction(
+            current.code_address,
+            current.pc,
+            current.ergs_remaining.into(),
+        );
+    }
+
+    fn after_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: AfterExecutionData,
+        memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        let current = &state.vm_local_state.callstack.current;
+        match data.opcode.variant.opcode {
+            Opcode::FarCall(_) => {
+                let selector = if current.code_page.0 == 0 || current.ergs_remaining == 0 {
+                    None
+                } else {
+                    let packed_abi = state.vm_local_state.registers
+                        [CALL_IMPLICIT_CALLDATA_FAT_PTR_REGISTER as usize];
+                    let calldata_ptr =
+                        FarCallABI::from_u256(packed_abi.value).memory_quasi_fat_pointer;
+                    (calldata_ptr.length >= 4).then(|| {
+                        let selector = memory.read_unaligned_bytes(
+                            calldata_ptr.memory_page as usize,
+                            calldata_ptr.start as usize,
+                            4,
+                        );
+                        selector.try_into().unwrap()
+                    })
+                };
+                self.profiler.after_far_call(current.code_address, selector);
+            }
+            Opcode::NearCall(_) => {
+                self.profiler.after_near_call(current.pc);
+            }
+            Opcode::Ret(_) => {
+                self.profiler.after_return(current.ergs_remaining.into());
+            }
+            _ => {
+                self.profiler
+                    .after_instruction(current.ergs_remaining.into());
+            }
+        }
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for GasProfilerTracer {
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.store_result();
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{
+    interface::{storage::WriteStorage, VmExecutionResultAndLogs},
+    tracers::{dynamic::vm_1_3_3::DynTracer, GasProfilerTracer},
+    vm_virtual_blocks::{
+        ExecutionEndTracer, ExecutionProcessing, HistoryMode, SimpleMemory, VmTracer,
+    },
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for GasProfilerTracer {}
+
+impl<H: HistoryMode> ExecutionEndTracer<H> for GasProfilerTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> ExecutionProcessing<S, H> for GasProfilerTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for GasProfilerTracer {
+    fn save_results(&mut self, _result: &mut VmExecutionResultAndLogs) {
+        self.store_result();
+    }
+}
@@ -1,5 +1,7 @@
+pub(crate) use self::gas_profiler::GasProfiler;
 pub use self::{
     call_tracer::CallTracer,
+    gas_profiler::GasProfilerTracer,
     multivm_dispatcher::TracerDispatcher,
     prestate_tracer::PrestateTracer,
     storage_invocation::StorageInvocations,
@@ -8,6 +10,7 @@ pub use self::{
 
 mod call_tracer;
 pub mod dynamic;
+mod gas_profiler;
 mod multivm_dispatcher;
 pub mod old;
 mod prestate_tracer;
@@ -0,0 +1,88 @@
+//! Gas profiler tests. Similarly to call tracer tests, these tests don't use fixtures since profiles
+//! are invalidated by any changes in system contracts.
+
+use zksync_test_contracts::TestContract;
+use zksync_types::{Address, Execute};
+
+use super::{ContractToDeploy, TestedVmWithGasProfiler, VmTester, VmTesterBuilder};
+use crate::{
+    interface::{GasProfileFrame, TxExecutionMode},
+    vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+};
+
+pub(crate) fn test_basic_behavior<VM: TestedVmWithGasProfiler>() {
+    let bytecode = TestContract::counter().bytecode.to_vec();
+    let address = Address::repeat_byte(0xA5);
+    let mut vm: VmTester<VM> = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::new(bytecode, address)])
+        .build();
+
+    let calldata = TestContract::counter()
+        .function("increment")
+        .encode_input(&[ethabi::Token::Uint(6.into())])
+        .unwrap();
+    let selector: [u8; 4] = calldata[..4].try_into().unwrap();
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(address),
+            calldata,
+            value: 0.into(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+
+    vm.vm.push_transaction(tx);
+    let (res, profile) = vm.vm.inspect_with_gas_profiler();
+    assert!(!res.result.is_failed(), "{:#?}", res.result);
+
+    let total_gas = profile.total_gas();
+    assert!(total_gas > 0);
+    assert_eq!(profile.pcs.values().sum::<u64>(), total_gas);
+
+    let contract_gas = profile.by_function()[&(address, Some(selector))];
+    assert!(contract_gas > 0);
+    assert!(contract_gas < total_gas);
+    assert_eq!(profile.by_contract()[&address], contract_gas);
+    assert!(profile
+        .pcs
+        .iter()
+        .any(|(&(pc_address, _), &gas)| pc_address == address && gas > 0));
+
+    // The contract must be called from the bootloader via the default account.
+    let contract_frame = GasProfileFrame::FarCall {
+        address,
+        selector: Some(selector),
+    };
+    let contract_stacks: Vec<_> = profile
+        .stacks
+        .keys()
+        .filter(|stack| stack.contains(&contract_frame))
+        .collect();
+    assert!(!contract_stacks.is_empty());
+    for stack in contract_stacks {
+        let far_calls_before_contract = stack
+            .iter()
+            .take_while(|&frame| *frame != contract_frame)
+            .filter(|frame| matches!(frame, GasProfileFrame::FarCall { .. }))
+            .count();
+        assert!(far_calls_before_contract >= 2, "{stack:?}");
+    }
+
+    let collapsed = profile.to_collapsed_stacks();
+    let contract_frame = format!("{address:?}::0x{}", hex::encode(selector));
+    assert!(
+        collapsed.lines().any(|line| line.contains(&contract_frame)),
+        "{collapsed}"
+    );
+    for line in collapsed.lines() {
+        let (stack, gas) = line.rsplit_once(' ').unwrap();
+        assert!(!stack.is_empty());
+        assert!(gas.parse::<u64>().unwrap() > 0);
+    }
+}
@@ -22,8 +22,8 @@ use zksync_types::{
 };
 
 pub(super) use self::tester::{
-    validation_params, TestedVm, TestedVmForValidation, TestedVmWithCallTracer, VmTester,
-    VmTesterBuilder,
+    validation_params, TestedVm, TestedVmForValidation, TestedVmWithCallTracer,
+    TestedVmWithGasProfiler, VmTester, VmTesterBuilder,
 };
 use crate::{
     interface::{
@@ -44,6 +44,7 @@ pub(super) mod code_oracle;
 pub(super) mod default_aa;
 pub(super) mod evm_emulator;
 pub(super) mod gas_limit;
+pub(super) mod gas_profiler;
 pub(super) mod get_used_contracts;
 pub(super) mod is_write_initial;
 pub(super) mod l1_messenger;
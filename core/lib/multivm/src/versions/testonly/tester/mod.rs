@@ -8,7 +8,7 @@ use zksync_types::{
     writes::StateDiffRecord,
     Address, L1BatchNumber, StorageKey, Transaction, H256, U256,
 };
-use zksync_vm_interface::Call;
+use zksync_vm_interface::{Call, GasProfile};
 
 pub(crate) use self::transaction_test_info::{ExpectedError, TransactionTestInfo, TxModifier};
 use super::get_empty_storage;
@@ -257,3 +257,7 @@ pub(crate) fn validation_params(tx: &L2Tx, system: &SystemEnv) -> ValidationPara
 pub(crate) trait TestedVmWithCallTracer: TestedVm {
     fn inspect_with_call_tracer(&mut self) -> (VmExecutionResultAndLogs, Vec<Call>);
 }
+
+pub(crate) trait TestedVmWithGasProfiler: TestedVm {
+    fn inspect_with_gas_profiler(&mut self) -> (VmExecutionResultAndLogs, GasProfile);
+}
@@ -0,0 +1,6 @@
+use crate::versions::{testonly::gas_profiler, vm_fast::Vm};
+
+#[test]
+fn basic_behavior() {
+    gas_profiler::test_basic_behavior::<Vm<_, _, _>>();
+}
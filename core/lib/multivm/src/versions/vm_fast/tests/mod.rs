@@ -8,7 +8,7 @@ use zksync_vm_interface::{
     pubdata::{PubdataBuilder, PubdataInput},
     storage::ReadStorage,
     tracer::ViolatedValidationRule,
-    Call, CurrentExecutionState, InspectExecutionMode, L2BlockEnv, VmExecutionMode,
+    Call, CurrentExecutionState, GasProfile, InspectExecutionMode, L2BlockEnv, VmExecutionMode,
     VmExecutionResultAndLogs, VmInterface,
 };
 
@@ -17,8 +17,9 @@ use crate::{
     interface::storage::{ImmutableStorageView, InMemoryStorage},
     versions::testonly::{
         validation_params, TestedVm, TestedVmForValidation, TestedVmWithCallTracer,
+        TestedVmWithGasProfiler,
     },
-    vm_fast::{tracers::WithBuiltinTracers, CallTracer},
+    vm_fast::{tracers::WithBuiltinTracers, CallTracer, GasProfilerTracer},
 };
 
 mod account_validation_rules;
@@ -31,6 +32,7 @@ mod code_oracle;
 mod default_aa;
 mod evm_emulator;
 mod gas_limit;
+mod gas_profiler;
 mod get_used_contracts;
 mod is_write_initial;
 mod l1_messenger;
@@ -200,3 +202,11 @@ impl TestedVmWithCallTracer for TestedFastVm<CallTracer, ()> {
         (result, tracer.0.into_result())
     }
 }
+
+impl TestedVmWithGasProfiler for TestedFastVm<GasProfilerTracer, ()> {
+    fn inspect_with_gas_profiler(&mut self) -> (VmExecutionResultAndLogs, GasProfile) {
+        let mut tracer = (GasProfilerTracer::default(), ());
+        let result = self.inspect(&mut tracer, InspectExecutionMode::OneTx);
+        (result, tracer.0.into_result())
+    }
+}
@@ -0,0 +1,74 @@
+use zksync_vm2::{
+    interface::{
+        CallframeInterface, GlobalStateInterface, Opcode, OpcodeType, ShouldStop, StateInterface,
+        Tracer,
+    },
+    FatPointer,
+};
+
+use crate::{interface::GasProfile, tracers::GasProfiler};
+
+/// Gas profiling tracer for the fast VM. Attributes gas spent during execution to contracts, functions
+/// and program counters.
+#[derive(Debug, Clone, Default)]
+pub struct GasProfilerTracer {
+    profiler: GasProfiler,
+}
+
+impl GasProfilerTracer {
+    /// Converts this tracer into the collected gas profile.
+    pub fn into_result(self) -> GasProfile {
+        self.profiler.into_profile()
+    }
+}
+
+fn read_selector<S: StateInterface>(state: &S) -> Option<[u8; 4]> {
+    let pointer = FatPointer::from(state.read_register(1).0);
+    if pointer.length.saturating_sub(pointer.offset) < 4 {
+        return None;
+    }
+    let start = pointer.start + pointer.offset;
+    Some(std::array::from_fn(|i| {
+        state.read_heap_byte(pointer.memory_page, start + i as u32)
+    }))
+}
+
+impl Tracer for GasProfilerTracer {
+    fn before_instruction<OP: OpcodeType, S: GlobalStateInterface>(&mut self, state: &mut S) {
+        let frame = state.current_frame();
+        let code_address = frame.code_address();
+        let pc = frame.program_counter().unwrap_or(u16::MAX);
+        let gas = frame.gas();
+        self.profiler
+            .before_instruction(code_address, pc, gas.into());
+    }
+
+    fn after_instruction<OP: OpcodeType, S: GlobalStateInterface>(
+        &mut self,
+        state: &mut S,
+    ) -> ShouldStop {
+        match OP::VALUE {
+            Opcode::FarCall(_) => {
+                let callee = state.current_frame();
+                let code_address = callee.code_address();
+                // If the callee has no gas, it cannot read calldata, and the calldata may be inaccessible.
+                let has_gas = callee.gas() > 0;
+                let selector = if has_gas { read_selector(state) } else { None };
+                self.profiler.after_far_call(code_address, selector);
+            }
+            Opcode::NearCall => {
+                let pc = state.current_frame().program_counter().unwrap_or(u16::MAX);
+                self.profiler.after_near_call(pc);
+            }
+            Opcode::Ret(_) => {
+                let gas = state.current_frame().gas();
+                self.profiler.after_return(gas.into());
+            }
+            _ => {
+                let gas = state.current_frame().gas();
+                self.profiler.after_instruction(gas.into());
+            }
+        }
+        ShouldStop::Continue
+    }
+}
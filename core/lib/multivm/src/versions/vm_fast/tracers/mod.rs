@@ -5,6 +5,7 @@ use zksync_vm2::interface::{CycleStats, GlobalStateInterface, OpcodeType, Should
 pub(super) use self::evm_deploy::DynamicBytecodes;
 pub use self::{
     calls::CallTracer,
+    gas_profiler::GasProfilerTracer,
     validation::{FullValidationTracer, ValidationTracer},
 };
 use self::{circuits::CircuitsTracer, evm_deploy::EvmDeployTracer};
@@ -13,6 +14,7 @@ use crate::interface::CircuitStatistic;
 mod calls;
 mod circuits;
 mod evm_deploy;
+mod gas_profiler;
 mod validation;
 
 #[derive(Debug)]
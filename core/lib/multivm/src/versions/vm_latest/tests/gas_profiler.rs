@@ -0,0 +1,9 @@
+use crate::{
+    versions::testonly::gas_profiler,
+    vm_latest::{HistoryEnabled, Vm},
+};
+
+#[test]
+fn basic_behavior() {
+    gas_profiler::test_basic_behavior::<Vm<_, HistoryEnabled>>();
+}
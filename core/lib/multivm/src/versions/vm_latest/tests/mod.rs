@@ -14,7 +14,7 @@ use zksync_types::{
     bytecode::BytecodeHash, l2::L2Tx, vm::VmVersion, writes::StateDiffRecord, StorageKey,
     StorageValue, Transaction, H256, U256,
 };
-use zksync_vm_interface::{Call, InspectExecutionMode, VmInterface};
+use zksync_vm_interface::{Call, GasProfile, InspectExecutionMode, VmInterface};
 
 use super::{HistoryEnabled, ToTracerPointer, Vm};
 use crate::{
@@ -24,11 +24,11 @@ use crate::{
         tracer::ViolatedValidationRule,
         CurrentExecutionState, L2BlockEnv, VmExecutionMode, VmExecutionResultAndLogs,
     },
-    tracers::{CallTracer, ValidationTracer},
+    tracers::{CallTracer, GasProfilerTracer, ValidationTracer},
     utils::bytecode::bytes_to_be_words,
     versions::testonly::{
         filter_out_base_system_contracts, validation_params, TestedVm, TestedVmForValidation,
-        TestedVmWithCallTracer,
+        TestedVmWithCallTracer, TestedVmWithGasProfiler,
     },
     vm_latest::{
         constants::BOOTLOADER_HEAP_PAGE,
@@ -53,6 +53,7 @@ mod code_oracle;
 mod constants;
 mod evm_emulator;
 mod gas_limit;
+mod gas_profiler;
 mod get_used_contracts;
 mod is_write_initial;
 mod l1_messenger;
@@ -350,3 +351,13 @@ impl TestedVmWithCallTracer for TestedLatestVm {
         (res, traces)
     }
 }
+
+impl TestedVmWithGasProfiler for TestedLatestVm {
+    fn inspect_with_gas_profiler(&mut self) -> (VmExecutionResultAndLogs, GasProfile) {
+        let result = Arc::new(OnceCell::new());
+        let profiler = GasProfilerTracer::new(result.clone()).into_tracer_pointer();
+        let res = self.inspect(&mut profiler.into(), InspectExecutionMode::OneTx);
+        let profile = result.get().unwrap().clone();
+        (res, profile)
+    }
+}
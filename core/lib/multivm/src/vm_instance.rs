@@ -351,3 +351,14 @@ where
 pub fn is_supported_by_fast_vm(protocol_version: ProtocolVersionId) -> bool {
     FastVmVersion::try_from(VmVersion::from(protocol_version)).is_ok()
 }
+
+/// Checks whether gas profiling is supported for the protocol version. For unsupported versions,
+/// [`GasProfilerTracer`](crate::tracers::GasProfilerTracer) produces an empty profile.
+pub fn is_supported_by_gas_profiler(protocol_version: ProtocolVersionId) -> bool {
+    matches!(
+        VmVersion::from(protocol_version),
+        VmVersion::Vm1_5_0SmallBootloaderMemory
+            | VmVersion::Vm1_5_0IncreasedBootloaderMemory
+            | VmVersion::VmGateway
+    )
+}
@@ -93,6 +93,7 @@ impl MacroImpl {
         let crate_path = self.crate_path();
         let ident = self.ident;
         let mut fields = Vec::new();
+        let mut descriptions = Vec::new();
         for field in self.fields {
             let ty = field.ty;
             let ident = field.ident;
@@ -112,17 +113,28 @@ impl MacroImpl {
                 ));
             }
 
-            let field = if default {
-                quote! {
+            let (field, description) = if default {
+                let field = quote! {
                     #ident: ctx.get_resource_or_default::<#ty>()
-                }
+                };
+                let description = quote! {
+                    requests.push(#crate_path::service::ResourceRequest::new::<#ty>(
+                        #crate_path::service::RequestMode::Default,
+                    ));
+                };
+                (field, description)
             } else {
-                quote! {
+                let field = quote! {
                     #ident: <#ty as #crate_path::service::FromContext>::from_context(ctx)?
-                }
+                };
+                let description = quote! {
+                    <#ty as #crate_path::service::FromContext>::describe_inputs(requests);
+                };
+                (field, description)
             };
 
-            fields.push(field)
+            fields.push(field);
+            descriptions.push(description);
         }
 
         Ok(quote! {
@@ -132,6 +144,11 @@ impl MacroImpl {
                         #(#fields),*
                     })
                 }
+
+                #[allow(unused_variables)] // Unused if there are no fields
+                fn describe_inputs(requests: &mut ::std::vec::Vec<#crate_path::service::ResourceRequest>) {
+                    #(#descriptions)*
+                }
             }
         })
     }
@@ -140,6 +157,7 @@ impl MacroImpl {
         let crate_path = self.crate_path();
         let ident = self.ident;
         let mut actions = Vec::new();
+        let mut descriptions = Vec::new();
         for field in self.fields {
             let ty = field.ty;
             let ident = field.ident;
@@ -157,25 +175,41 @@ impl MacroImpl {
                 ));
             }
 
-            let action = if field.label.task {
+            let (action, description) = if field.label.task {
                 // Check whether the task is an `Option`.
-                if let Some(_inner_ty) = crate::helpers::extract_option_inner_type(&ty) {
-                    quote! {
+                if let Some(inner_ty) = crate::helpers::extract_option_inner_type(&ty) {
+                    let action = quote! {
                         if let Some(task) = self.#ident {
                             ctx.add_task(task);
                         }
-                    }
+                    };
+                    let description = quote! {
+                        items.push(
+                            #crate_path::service::ProvidedItem::task::<#inner_ty>(stringify!(#ident))
+                                .conditional(),
+                        );
+                    };
+                    (action, description)
                 } else {
-                    quote! {
+                    let action = quote! {
                         ctx.add_task(self.#ident);
-                    }
+                    };
+                    let description = quote! {
+                        items.push(#crate_path::service::ProvidedItem::task::<#ty>(stringify!(#ident)));
+                    };
+                    (action, description)
                 }
             } else {
-                quote! {
+                let action = quote! {
                     <#ty as #crate_path::service::IntoContext>::into_context(self.#ident, ctx)?;
-                }
+                };
+                let description = quote! {
+                    <#ty as #crate_path::service::IntoContext>::describe_outputs(items);
+                };
+                (action, description)
             };
             actions.push(action);
+            descriptions.push(description);
         }
 
         Ok(quote! {
@@ -184,6 +218,11 @@ impl MacroImpl {
                     #(#actions)*
                     Ok(())
                 }
+
+                #[allow(unused_variables)] // Unused if there are no fields
+                fn describe_outputs(items: &mut ::std::vec::Vec<#crate_path::service::ProvidedItem>) {
+                    #(#descriptions)*
+                }
             }
         })
     }
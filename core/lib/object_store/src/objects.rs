@@ -7,6 +7,7 @@ use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use prost::Message;
 use zksync_protobuf::{decode, ProtoFmt};
 use zksync_types::{
+    pruning::{PrunedDataArchive, PrunedDataArchiveKey},
     snapshots::{
         SnapshotFactoryDependencies, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
     },
@@ -87,6 +88,39 @@ impl StoredObject for SnapshotFactoryDependencies {
     }
 }
 
+impl StoredObject for PrunedDataArchive {
+    const BUCKET: Bucket = Bucket::PrunedDataArchive;
+    type Key<'a> = PrunedDataArchiveKey;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!(
+            "pruned_l1_batches_{}_{}.json.gzip",
+            key.first_l1_batch, key.last_l1_batch
+        )
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        serde_json::to_writer(&mut encoder, self)?;
+        encoder.finish().map_err(From::from)
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        let decoder = GzDecoder::new(&bytes[..]);
+        let archive: Self = serde_json::from_reader(decoder)
+            .context("deserialization of PrunedDataArchive")?;
+        if archive.version != Self::CURRENT_VERSION {
+            let err = anyhow::anyhow!(
+                "unsupported pruned data archive version {}, expected {}",
+                archive.version,
+                Self::CURRENT_VERSION
+            );
+            return Err(err.into());
+        }
+        Ok(archive)
+    }
+}
+
 impl<K> StoredObject for SnapshotStorageLogsChunk<K>
 where
     Self: ProtoFmt,
@@ -210,7 +244,7 @@ mod tests {
     use zksync_types::{
         snapshots::{SnapshotFactoryDependency, SnapshotStorageLog},
         web3::Bytes,
-        H256,
+        L2BlockNumber, H256,
     };
 
     use super::*;
@@ -290,4 +324,40 @@ mod tests {
         let reconstructed_factory_deps = store.get(key).await.unwrap();
         assert_eq!(factory_deps, reconstructed_factory_deps);
     }
+
+    #[tokio::test]
+    async fn pruned_data_archive_can_be_serialized_and_deserialized() {
+        let store = MockObjectStore::arc();
+        let key = PrunedDataArchiveKey {
+            first_l1_batch: L1BatchNumber(1),
+            last_l1_batch: L1BatchNumber(10),
+        };
+        assert_eq!(
+            PrunedDataArchive::encode_key(key),
+            "pruned_l1_batches_1_10.json.gzip"
+        );
+
+        let mut archive = PrunedDataArchive {
+            version: PrunedDataArchive::CURRENT_VERSION,
+            first_l1_batch: L1BatchNumber(1),
+            last_l1_batch: L1BatchNumber(10),
+            first_l2_block: L2BlockNumber(1),
+            last_l2_block: L2BlockNumber(20),
+            last_l1_batch_root_hash: H256::repeat_byte(1),
+            l1_batches: vec![],
+            l2_blocks: vec![],
+        };
+        store.put(key, &archive).await.unwrap();
+        let restored: PrunedDataArchive = store.get(key).await.unwrap();
+        assert_eq!(restored.last_l2_block, archive.last_l2_block);
+        assert_eq!(restored.last_l1_batch_root_hash, archive.last_l1_batch_root_hash);
+
+        archive.version = PrunedDataArchive::CURRENT_VERSION + 1;
+        store.put(key, &archive).await.unwrap();
+        let err = store.get::<PrunedDataArchive>(key).await.unwrap_err();
+        assert!(
+            err.to_string().contains("unsupported pruned data archive version"),
+            "{err}"
+        );
+    }
 }
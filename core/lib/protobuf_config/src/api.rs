@@ -150,6 +150,9 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .collect::<Result<Vec<_>, _>>()
                 .context("whitelisted_tokens_for_aa")?,
             extended_api_tracing: self.extended_api_tracing.unwrap_or_default(),
+            address_index_enabled: self.address_index_enabled.unwrap_or_default(),
+            token_transfer_index_enabled: self.token_transfer_index_enabled.unwrap_or_default(),
+            simulation_sessions_limit: self.simulation_sessions_limit,
             api_namespaces,
         })
     }
@@ -216,6 +219,9 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|k| format!("{:?}", k))
                 .collect(),
             extended_api_tracing: Some(this.extended_api_tracing),
+            address_index_enabled: Some(this.address_index_enabled),
+            token_transfer_index_enabled: Some(this.token_transfer_index_enabled),
+            simulation_sessions_limit: this.simulation_sessions_limit,
             api_namespaces: this.api_namespaces.clone().unwrap_or_default(),
         }
     }
@@ -2,7 +2,7 @@ use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::{repr::ProtoRepr, required};
 
-use crate::proto::chain as proto;
+use crate::{parse_h160, proto::chain as proto};
 
 impl proto::FeeModelVersion {
     fn new(n: &configs::chain::FeeModelVersion) -> Self {
@@ -81,6 +81,24 @@ impl ProtoRepr for proto::StateKeeper {
             protective_reads_persistence_enabled: self
                 .protective_reads_persistence_enabled
                 .unwrap_or_default(),
+            fee_aware_seal_max_latency_ms: self.fee_aware_seal_max_latency_ms,
+            fee_aware_seal_min_latency_ms: self.fee_aware_seal_min_latency_ms.unwrap_or_default(),
+            fee_aware_seal_cheap_l1_gas_price: self.fee_aware_seal_cheap_l1_gas_price,
+            fee_aware_seal_max_overhead_per_tx: self.fee_aware_seal_max_overhead_per_tx,
+            transaction_quotas: self
+                .transaction_quotas
+                .iter()
+                .enumerate()
+                .map(|(i, quota)| quota.read().context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("transaction_quotas")?,
+            transaction_quota_multicall_contracts: self
+                .transaction_quota_multicall_contracts
+                .iter()
+                .enumerate()
+                .map(|(i, k)| parse_h160(k).context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("transaction_quota_multicall_contracts")?,
 
             // We need these values only for instantiating configs from environmental variables, so it's not
             // needed during the initialization from files
@@ -120,6 +138,66 @@ impl ProtoRepr for proto::StateKeeper {
             save_call_traces: Some(this.save_call_traces),
             max_circuits_per_batch: Some(this.max_circuits_per_batch.try_into().unwrap()),
             protective_reads_persistence_enabled: Some(this.protective_reads_persistence_enabled),
+            fee_aware_seal_max_latency_ms: this.fee_aware_seal_max_latency_ms,
+            fee_aware_seal_min_latency_ms: Some(this.fee_aware_seal_min_latency_ms),
+            fee_aware_seal_cheap_l1_gas_price: this.fee_aware_seal_cheap_l1_gas_price,
+            fee_aware_seal_max_overhead_per_tx: this.fee_aware_seal_max_overhead_per_tx,
+            transaction_quotas: this
+                .transaction_quotas
+                .iter()
+                .map(ProtoRepr::build)
+                .collect(),
+            transaction_quota_multicall_contracts: this
+                .transaction_quota_multicall_contracts
+                .iter()
+                .map(|k| format!("{:?}", k))
+                .collect(),
+        }
+    }
+}
+
+/// Checks that a transaction quota share (if specified) is in `[0, 1]`.
+fn read_share(share: Option<f64>) -> anyhow::Result<Option<f64>> {
+    if let Some(share) = share {
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&share),
+            "share must be in [0, 1], got {share}"
+        );
+    }
+    Ok(share)
+}
+
+impl ProtoRepr for proto::TransactionQuota {
+    type Type = configs::chain::TransactionQuotaConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            name: required(&self.name).context("name")?.clone(),
+            l1_transactions: self.l1_transactions.unwrap_or_default(),
+            contracts: self
+                .contracts
+                .iter()
+                .enumerate()
+                .map(|(i, k)| parse_h160(k).context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("contracts")?,
+            max_gas_share: read_share(self.max_gas_share).context("max_gas_share")?,
+            max_pubdata_share: read_share(self.max_pubdata_share).context("max_pubdata_share")?,
+            reserved_gas_share: read_share(self.reserved_gas_share)
+                .context("reserved_gas_share")?,
+            reserved_pubdata_share: read_share(self.reserved_pubdata_share)
+                .context("reserved_pubdata_share")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            name: Some(this.name.clone()),
+            l1_transactions: Some(this.l1_transactions),
+            contracts: this.contracts.iter().map(|k| format!("{:?}", k)).collect(),
+            max_gas_share: this.max_gas_share,
+            max_pubdata_share: this.max_pubdata_share,
+            reserved_gas_share: this.reserved_gas_share,
+            reserved_pubdata_share: this.reserved_pubdata_share,
         }
     }
 }
@@ -1,5 +1,5 @@
 use std::{
-    num::{NonZeroU64, NonZeroUsize},
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
     str::FromStr,
 };
 
@@ -33,10 +33,21 @@ impl ProtoRepr for proto::ExternalNode {
             main_node_rate_limit_rps: self
                 .main_node_rate_limit_rps
                 .and_then(|a| NonZeroUsize::new(a as usize)),
+            main_node_fallback_urls: self
+                .main_node_fallback_urls
+                .iter()
+                .enumerate()
+                .map(|(i, url)| SensitiveUrl::from_str(url).context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("main_node_fallback_urls")?,
             bridge_addresses_refresh_interval_sec: self
                 .bridge_addresses_refresh_interval_sec
                 .and_then(NonZeroU64::new),
             gateway_chain_id: self.gateway_chain_id.map(SLChainId),
+            reorg_auto_recovery_enabled: self.reorg_auto_recovery_enabled.unwrap_or(false),
+            reorg_auto_recovery_max_l1_batches: self
+                .reorg_auto_recovery_max_l1_batches
+                .and_then(NonZeroU32::new),
         })
     }
 
@@ -52,10 +63,19 @@ impl ProtoRepr for proto::ExternalNode {
                 .into(),
             ),
             main_node_rate_limit_rps: this.main_node_rate_limit_rps.map(|a| a.get() as u64),
+            main_node_fallback_urls: this
+                .main_node_fallback_urls
+                .iter()
+                .map(|url| url.expose_str().to_string())
+                .collect(),
             bridge_addresses_refresh_interval_sec: this
                 .bridge_addresses_refresh_interval_sec
                 .map(|a| a.get()),
             gateway_chain_id: this.gateway_chain_id.map(|c| c.0),
+            reorg_auto_recovery_enabled: Some(this.reorg_auto_recovery_enabled),
+            reorg_auto_recovery_max_l1_batches: this
+                .reorg_auto_recovery_max_l1_batches
+                .map(|a| a.get()),
         }
     }
 }
@@ -1,7 +1,14 @@
-use zksync_config::configs::{self, external_price_api_client::ForcedPriceClientConfig};
+use anyhow::Context as _;
+use zksync_config::configs::{
+    self,
+    external_price_api_client::{
+        CompositePriceClientConfig, ForcedPriceClientConfig, OnchainOraclePriceClientConfig,
+        DEFAULT_ONCHAIN_ORACLE_MAX_ANSWER_AGE_SEC, DEFAULT_ONCHAIN_ORACLE_TWAP_WINDOW_SEC,
+    },
+};
 use zksync_protobuf::ProtoRepr;
 
-use crate::proto::external_price_api_client as proto;
+use crate::{parse_h160, proto::external_price_api_client as proto};
 
 impl ProtoRepr for proto::ExternalPriceApiClient {
     type Type = configs::external_price_api_client::ExternalPriceApiClientConfig;
@@ -21,6 +28,28 @@ impl ProtoRepr for proto::ExternalPriceApiClient {
                         configs::external_price_api_client::DEFAULT_FORCED_NEXT_VALUE_FLUCTUATION,
                     ),
                 }),
+                onchain_oracle: self
+                    .onchain_oracle_contract_address
+                    .as_ref()
+                    .map(|address| {
+                        anyhow::Ok(OnchainOraclePriceClientConfig {
+                            contract_address: parse_h160(address)?,
+                            max_answer_age_sec: self
+                                .onchain_oracle_max_answer_age_sec
+                                .unwrap_or(DEFAULT_ONCHAIN_ORACLE_MAX_ANSWER_AGE_SEC),
+                            twap_window_sec: self
+                                .onchain_oracle_twap_window_sec
+                                .unwrap_or(DEFAULT_ONCHAIN_ORACLE_TWAP_WINDOW_SEC),
+                        })
+                    })
+                    .transpose()
+                    .context("onchain_oracle_contract_address")?,
+                composite: self
+                    .composite
+                    .as_ref()
+                    .map(ProtoRepr::read)
+                    .transpose()
+                    .context("composite")?,
             },
         )
     }
@@ -30,6 +59,7 @@ impl ProtoRepr for proto::ExternalPriceApiClient {
         let denominator = this.forced.as_ref().and_then(|x| x.denominator);
         let fluctuation = this.forced.as_ref().and_then(|x| x.fluctuation);
         let next_value_fluctuation = this.forced.as_ref().map(|x| x.next_value_fluctuation);
+        let onchain_oracle = this.onchain_oracle.as_ref();
 
         Self {
             source: Some(this.source.clone()),
@@ -40,6 +70,45 @@ impl ProtoRepr for proto::ExternalPriceApiClient {
             forced_denominator: denominator,
             forced_fluctuation: fluctuation,
             forced_next_value_fluctuation: next_value_fluctuation,
+            composite: this.composite.as_ref().map(ProtoRepr::build),
+            onchain_oracle_contract_address: onchain_oracle
+                .map(|x| format!("{:?}", x.contract_address)),
+            onchain_oracle_max_answer_age_sec: onchain_oracle.map(|x| x.max_answer_age_sec),
+            onchain_oracle_twap_window_sec: onchain_oracle.map(|x| x.twap_window_sec),
+        }
+    }
+}
+
+impl ProtoRepr for proto::CompositePriceClient {
+    type Type = CompositePriceClientConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(CompositePriceClientConfig {
+            sources: self
+                .sources
+                .iter()
+                .enumerate()
+                .map(|(i, x)| x.read().context(i))
+                .collect::<Result<_, _>>()
+                .context("sources")?,
+            min_sources: self
+                .min_sources
+                .unwrap_or(configs::external_price_api_client::DEFAULT_COMPOSITE_MIN_SOURCES),
+            max_deviation_percent: self.max_deviation_percent.unwrap_or(
+                configs::external_price_api_client::DEFAULT_COMPOSITE_MAX_DEVIATION_PERCENT,
+            ),
+            max_quote_age_ms: self
+                .max_quote_age_ms
+                .unwrap_or(configs::external_price_api_client::DEFAULT_COMPOSITE_MAX_QUOTE_AGE_MS),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            sources: this.sources.iter().map(ProtoRepr::build).collect(),
+            min_sources: Some(this.min_sources),
+            max_deviation_percent: Some(this.max_deviation_percent),
+            max_quote_age_ms: Some(this.max_quote_age_ms),
         }
     }
 }
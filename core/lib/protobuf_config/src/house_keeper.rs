@@ -7,11 +7,24 @@ use crate::proto::house_keeper as proto;
 impl ProtoRepr for proto::HouseKeeper {
     type Type = configs::house_keeper::HouseKeeperConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
+        let l1_batch_metrics_reporting_interval_ms =
+            *required(&self.l1_batch_metrics_reporting_interval_ms)
+                .context("l1_batch_metrics_reporting_interval_ms")?;
+        let default_config = Self::Type::new(l1_batch_metrics_reporting_interval_ms);
         Ok(Self::Type {
-            l1_batch_metrics_reporting_interval_ms: *required(
-                &self.l1_batch_metrics_reporting_interval_ms,
-            )
-            .context("l1_batch_metrics_reporting_interval_ms")?,
+            l1_batch_metrics_reporting_interval_ms,
+            settlement_sla_check_interval_ms: self.settlement_sla_check_interval_ms,
+            settlement_sla_window_size: self
+                .settlement_sla_window_size
+                .unwrap_or(default_config.settlement_sla_window_size),
+            settlement_sla_percentile: self
+                .settlement_sla_percentile
+                .unwrap_or(default_config.settlement_sla_percentile),
+            settlement_sla_commit_latency_ms: self.settlement_sla_commit_latency_ms,
+            settlement_sla_prove_latency_ms: self.settlement_sla_prove_latency_ms,
+            settlement_sla_execute_latency_ms: self.settlement_sla_execute_latency_ms,
+            settlement_sla_da_dispatch_latency_ms: self.settlement_sla_da_dispatch_latency_ms,
+            settlement_sla_da_inclusion_latency_ms: self.settlement_sla_da_inclusion_latency_ms,
         })
     }
 
@@ -20,6 +33,14 @@ impl ProtoRepr for proto::HouseKeeper {
             l1_batch_metrics_reporting_interval_ms: Some(
                 this.l1_batch_metrics_reporting_interval_ms,
             ),
+            settlement_sla_check_interval_ms: this.settlement_sla_check_interval_ms,
+            settlement_sla_window_size: Some(this.settlement_sla_window_size),
+            settlement_sla_percentile: Some(this.settlement_sla_percentile),
+            settlement_sla_commit_latency_ms: this.settlement_sla_commit_latency_ms,
+            settlement_sla_prove_latency_ms: this.settlement_sla_prove_latency_ms,
+            settlement_sla_execute_latency_ms: this.settlement_sla_execute_latency_ms,
+            settlement_sla_da_dispatch_latency_ms: this.settlement_sla_da_dispatch_latency_ms,
+            settlement_sla_da_inclusion_latency_ms: this.settlement_sla_da_inclusion_latency_ms,
         }
     }
 }
@@ -3,7 +3,7 @@ use zksync_config::configs;
 use zksync_protobuf::{repr::ProtoRepr, required};
 use zksync_types::L1BatchNumber;
 
-use crate::proto::prover as proto;
+use crate::{parse_h256, proto::prover as proto};
 
 impl ProtoRepr for proto::ProofDataHandler {
     type Type = configs::ProofDataHandlerConfig;
@@ -35,6 +35,29 @@ impl ProtoRepr for proto::ProofDataHandler {
                     .unwrap_or_else(
                         configs::TeeConfig::default_tee_batch_permanently_ignored_timeout_in_hours,
                     ),
+                tee_verify_attestations: self
+                    .tee_verify_attestations
+                    .unwrap_or_else(configs::TeeConfig::default_tee_verify_attestations),
+                tee_attestation_root_ca_path: self.tee_attestation_root_ca_path.clone(),
+                tee_attestation_collateral_path: self.tee_attestation_collateral_path.clone(),
+                tee_attestation_allowed_tcb_statuses: self
+                    .tee_attestation_allowed_tcb_statuses
+                    .clone(),
+                tee_sgx_mrenclave_allowlist: self
+                    .tee_sgx_mrenclave_allowlist
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| parse_h256(x).context(i))
+                    .collect::<Result<_, _>>()
+                    .context("tee_sgx_mrenclave_allowlist")?,
+                tee_sgx_mrsigner_allowlist: self
+                    .tee_sgx_mrsigner_allowlist
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| parse_h256(x).context(i))
+                    .collect::<Result<_, _>>()
+                    .context("tee_sgx_mrsigner_allowlist")?,
+                tee_tdx_mrtd_allowlist: self.tee_tdx_mrtd_allowlist.clone(),
             },
         })
     }
@@ -53,6 +76,29 @@ impl ProtoRepr for proto::ProofDataHandler {
                     .tee_batch_permanently_ignored_timeout_in_hours
                     .into(),
             ),
+            tee_verify_attestations: Some(this.tee_config.tee_verify_attestations),
+            tee_attestation_root_ca_path: this.tee_config.tee_attestation_root_ca_path.clone(),
+            tee_attestation_collateral_path: this
+                .tee_config
+                .tee_attestation_collateral_path
+                .clone(),
+            tee_attestation_allowed_tcb_statuses: this
+                .tee_config
+                .tee_attestation_allowed_tcb_statuses
+                .clone(),
+            tee_sgx_mrenclave_allowlist: this
+                .tee_config
+                .tee_sgx_mrenclave_allowlist
+                .iter()
+                .map(|x| format!("{:?}", x))
+                .collect(),
+            tee_sgx_mrsigner_allowlist: this
+                .tee_config
+                .tee_sgx_mrsigner_allowlist
+                .iter()
+                .map(|x| format!("{:?}", x))
+                .collect(),
+            tee_tdx_mrtd_allowlist: this.tee_config.tee_tdx_mrtd_allowlist.clone(),
         }
     }
 }
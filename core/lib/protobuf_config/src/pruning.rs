@@ -1,9 +1,29 @@
 use std::num::NonZeroU64;
 
+use anyhow::Context as _;
+use zksync_basic_types::pruning::PruningProfile;
 use zksync_config::configs::PruningConfig;
 use zksync_protobuf::ProtoRepr;
 
-use crate::proto::pruning as proto;
+use crate::{proto::pruning as proto, read_optional_repr};
+
+impl proto::PruningProfile {
+    fn new(source: PruningProfile) -> Self {
+        match source {
+            PruningProfile::Full => Self::Full,
+            PruningProfile::KeepTransactions => Self::KeepTransactions,
+            PruningProfile::KeepState => Self::KeepState,
+        }
+    }
+
+    fn parse(&self) -> PruningProfile {
+        match self {
+            Self::Full => PruningProfile::Full,
+            Self::KeepTransactions => PruningProfile::KeepTransactions,
+            Self::KeepState => PruningProfile::KeepState,
+        }
+    }
+}
 
 impl ProtoRepr for proto::Pruning {
     type Type = PruningConfig;
@@ -14,6 +34,13 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: self.chunk_size,
             removal_delay_sec: self.removal_delay_sec.and_then(NonZeroU64::new),
             data_retention_sec: self.data_retention_sec,
+            archive_object_store: read_optional_repr(&self.archive_object_store),
+            profile: self
+                .profile
+                .map(proto::PruningProfile::try_from)
+                .transpose()
+                .context("profile")?
+                .map_or_else(PruningProfile::default, |profile| profile.parse()),
         })
     }
 
@@ -23,6 +50,8 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: this.chunk_size,
             removal_delay_sec: this.removal_delay_sec.map(|a| a.get()),
             data_retention_sec: this.data_retention_sec,
+            archive_object_store: this.archive_object_store.as_ref().map(ProtoRepr::build),
+            profile: Some(proto::PruningProfile::new(this.profile).into()),
         }
     }
 }
@@ -1,6 +1,9 @@
 use std::{path::PathBuf, str::FromStr};
 
-use zksync_protobuf::testonly::{test_encode_all_formats, ReprConv};
+use zksync_protobuf::{
+    repr::ProtoRepr,
+    testonly::{test_encode_all_formats, ReprConv},
+};
 
 use crate::{proto, read_yaml_repr};
 
@@ -69,3 +72,14 @@ fn verify_file_parsing() {
     read_yaml_repr::<proto::secrets::Secrets>(&base_path.join("secrets.yaml"), true).unwrap();
     read_yaml_repr::<proto::en::ExternalNode>(&base_path.join("external_node.yaml"), true).unwrap();
 }
+
+#[test]
+fn transaction_quota_with_invalid_share() {
+    let quota = proto::chain::TransactionQuota {
+        name: Some("test".to_owned()),
+        max_gas_share: Some(1.5),
+        ..proto::chain::TransactionQuota::default()
+    };
+    let err = quota.read().unwrap_err();
+    assert!(format!("{err:#}").contains("max_gas_share"), "{err:#}");
+}
@@ -14,6 +14,7 @@ use zksync_object_store::{ObjectStore, ObjectStoreError};
 use zksync_types::{
     api,
     bytecode::BytecodeHash,
+    pruning::PruningProfile,
     snapshots::{
         SnapshotFactoryDependencies, SnapshotHeader, SnapshotRecoveryStatus, SnapshotStorageLog,
         SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
@@ -728,6 +729,7 @@ impl<'a> SnapshotsApplier<'a> {
                 .insert_soft_pruning_log(
                     this.applied_snapshot_status.l1_batch_number,
                     this.applied_snapshot_status.l2_block_number,
+                    PruningProfile::Full,
                 )
                 .await?;
             storage_transaction
@@ -736,6 +738,7 @@ impl<'a> SnapshotsApplier<'a> {
                     this.applied_snapshot_status.l1_batch_number,
                     this.applied_snapshot_status.l2_block_number,
                     this.applied_snapshot_status.l1_batch_root_hash,
+                    PruningProfile::Full,
                 )
                 .await?;
         }
@@ -340,8 +340,23 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
     }
 
     pub fn with_options(path: &Path, options: RocksDBOptions) -> Result<Self, rocksdb::Error> {
+        Self::open(path, options, false)
+    }
+
+    /// Opens an existing DB in the read-only mode. All writes to the returned instance will fail. Unlike
+    /// [`Self::new()`], this doesn't create the DB or missing column families, and doesn't alter the DB in any way
+    /// (e.g., by flushing the write-ahead log), so it's safe to use for inspecting a DB.
+    pub fn open_read_only(path: &Path) -> Result<Self, rocksdb::Error> {
+        Self::open(path, RocksDBOptions::default(), true)
+    }
+
+    fn open(path: &Path, options: RocksDBOptions, read_only: bool) -> Result<Self, rocksdb::Error> {
         let caches = RocksDBCaches::new(options.block_cache_capacity);
         let mut db_options = Self::rocksdb_options(None, None);
+        if read_only {
+            db_options.create_if_missing(false);
+            db_options.create_missing_column_families(false);
+        }
         let max_open_files = if let Some(non_zero) = options.max_open_files {
             i32::try_from(non_zero.get()).unwrap_or(i32::MAX)
         } else {
@@ -402,7 +417,11 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             ColumnFamilyDescriptor::new(cf_name, cf_options)
         });
 
-        let db = DB::open_cf_descriptors(&db_options, path, cfs)?;
+        let db = if read_only {
+            DB::open_cf_descriptors_read_only(&db_options, path, cfs, false)?
+        } else {
+            DB::open_cf_descriptors(&db_options, path, cfs)?
+        };
         let inner = Arc::new(RocksDBInner {
             db,
             db_name: CF::DB_NAME,
@@ -413,12 +432,14 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         RocksdbSizeMetrics::register(CF::DB_NAME, Arc::downgrade(&inner));
 
         tracing::info!(
-            "Initialized RocksDB `{}` at `{}` with {options:?}",
+            "Initialized RocksDB `{}` at `{}` with {options:?} (read-only: {read_only})",
             CF::DB_NAME,
             path.display()
         );
 
-        inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+        if !read_only {
+            inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+        }
         Ok(Self {
             inner,
             sync_writes: false,
@@ -979,6 +1000,27 @@ mod tests {
         assert_eq!(value.unwrap(), b"value");
     }
 
+    #[test]
+    fn read_only_db() {
+        let temp_dir = TempDir::new().unwrap();
+        RocksDB::<NewColumnFamilies>::open_read_only(temp_dir.path()).unwrap_err();
+
+        let db = RocksDB::<NewColumnFamilies>::new(temp_dir.path())
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Default, b"test", b"value");
+        db.write(batch).unwrap();
+        drop(db);
+
+        let db = RocksDB::<NewColumnFamilies>::open_read_only(temp_dir.path()).unwrap();
+        let value = db.get_cf(NewColumnFamilies::Default, b"test").unwrap();
+        assert_eq!(value.unwrap(), b"value");
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Default, b"test", b"other_value");
+        db.write(batch).unwrap_err();
+    }
+
     #[derive(Debug, Clone, Copy)]
     struct JunkColumnFamily;
 
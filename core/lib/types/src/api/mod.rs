@@ -2,8 +2,9 @@ use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use serde_with::{hex::Hex, serde_as};
-use strum::Display;
+use strum::{Display, EnumString};
 use zksync_basic_types::{
+    settlement::SettlementMode,
     web3::{AccessList, Bytes, Index},
     Bloom, L1BatchNumber, SLChainId, H160, H256, H64, U256, U64,
 };
@@ -637,6 +638,209 @@ pub struct TransactionDetails {
     pub eth_execute_tx_hash: Option<H256>,
 }
 
+/// Reason why a pending transaction is not picked up for execution by the sequencer.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "snake_case")]
+pub enum PendingTransactionReason {
+    /// Max fee per gas or gas per pubdata limit of the transaction is below the values currently required
+    /// by the sequencer. The transaction will be reconsidered once the fees go down.
+    FeeTooLow,
+    /// A transaction with a lower nonce from the same initiator is missing.
+    NonceGap,
+    /// Transactions of the initiator were removed from the mempool because the next transaction of the initiator
+    /// didn't satisfy sequencer requirements (e.g., fees) for the current L1 batch. The transactions will be loaded
+    /// into the mempool again on its next sync.
+    AccountStashed,
+    /// Transactions of the initiator were evicted from the mempool because it has reached its capacity.
+    AccountPurged,
+}
+
+/// Status of a transaction returned by `zks_getTransactionStatusDetails`, together with an explanation
+/// why the transaction is not included into a block yet or why it was rejected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStatusDetails {
+    pub status: TransactionStatus,
+    pub initiator_address: Address,
+    pub received_at: DateTime<Utc>,
+    /// Reason why the transaction is not executed yet. Only set for pending transactions.
+    pub pending_reason: Option<PendingTransactionReason>,
+    /// Reason why the transaction was rejected by the sequencer or reverted during execution.
+    /// Only set for failed transactions.
+    pub error: Option<String>,
+}
+
+/// Direction of transactions returned by `zks_getTransactionsByAddress` or token transfers returned by
+/// `zks_getTokenTransfers` relative to the queried address.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AddressTransactionDirection {
+    /// Transactions initiated by the address / transfers from the address.
+    Sent,
+    /// Transactions calling the address / transfers to the address.
+    Received,
+    /// Both sent and received transactions / transfers.
+    #[default]
+    All,
+}
+
+/// Position of a transaction used to paginate `zks_getTransactionsByAddress` results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransactionsCursor {
+    pub block_number: L2BlockNumber,
+    pub transaction_index: u32,
+}
+
+/// Filter for `zks_getTransactionsByAddress`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransactionsFilter {
+    #[serde(default)]
+    pub direction: AddressTransactionDirection,
+    /// First block to return transactions from (inclusive). If not specified, transactions are returned
+    /// starting from the first block with retained transactions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockNumber>,
+    /// Last block to return transactions from (inclusive). Defaults to the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockNumber>,
+    /// Maximum number of transactions to return. Capped by the node-wide entity limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Cursor returned with the previous page. Only transactions preceding the cursor are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<AddressTransactionsCursor>,
+}
+
+/// Page of transactions returned by `zks_getTransactionsByAddress`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransactionsPage {
+    /// Transactions ordered from newest to oldest.
+    pub transactions: Vec<Transaction>,
+    /// Cursor to request the next page with. `None` if there are no more transactions matching the filter.
+    pub next_cursor: Option<AddressTransactionsCursor>,
+}
+
+/// Token transfer indexed by the node. Base token balance changes (including mints on deposits and burns on withdrawals)
+/// are represented as transfers of the base token; mints have the zero `from` address, and withdrawals have the zero `to` address.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfer {
+    pub block_number: L2BlockNumber,
+    /// Hash of the transaction emitting the transfer. Zero for transfers performed by the bootloader
+    /// outside of transactions (e.g., fee payments to the operator at the end of an L1 batch).
+    pub transaction_hash: H256,
+    pub transaction_index: u32,
+    /// Index of the transfer event in the block.
+    pub log_index: u32,
+    /// L2 address of the transferred token.
+    pub token: Address,
+    pub from: Address,
+    pub to: Address,
+    pub amount: U256,
+}
+
+/// Position of a transfer used to paginate `zks_getTokenTransfers` results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfersCursor {
+    pub block_number: L2BlockNumber,
+    pub log_index: u32,
+}
+
+/// Filter for `zks_getTokenTransfers`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfersFilter {
+    /// L2 address of the token. If not specified, transfers of all tokens are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<Address>,
+    #[serde(default)]
+    pub direction: AddressTransactionDirection,
+    /// First block to return transfers from (inclusive). If not specified, transfers are returned
+    /// starting from the first block with retained transactions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockNumber>,
+    /// Last block to return transfers from (inclusive). Defaults to the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockNumber>,
+    /// Maximum number of transfers to return. Capped by the node-wide entity limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Cursor returned with the previous page. Only transfers preceding the cursor are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<TokenTransfersCursor>,
+}
+
+/// Page of token transfers returned by `zks_getTokenTransfers`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfersPage {
+    /// Transfers ordered from newest to oldest.
+    pub transfers: Vec<TokenTransfer>,
+    /// Cursor to request the next page with. `None` if there are no more transfers matching the filter.
+    pub next_cursor: Option<TokenTransfersCursor>,
+}
+
+/// Filter for `zks_getBalanceHistory`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceHistoryFilter {
+    /// L2 address of the token. Defaults to the base token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<Address>,
+    /// First block to return snapshots for (inclusive). If not specified, snapshots are returned
+    /// starting from the first block with retained transactions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockNumber>,
+    /// Last block to return snapshots for (inclusive). Defaults to the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockNumber>,
+    /// Maximum number of snapshots to return. Capped by the node-wide entity limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Cursor returned with the previous page. Only snapshots for blocks preceding the cursor are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<L2BlockNumber>,
+}
+
+/// Balance snapshot of an address after an L2 block in which its balance has changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceSnapshot {
+    pub block_number: L2BlockNumber,
+    /// Total amount transferred to the address in the block.
+    pub received: U256,
+    /// Total amount transferred from the address in the block.
+    pub sent: U256,
+    /// Balance of the address at the end of the block. Only available for the base token,
+    /// and only if the node retains state history for the block.
+    pub balance: Option<U256>,
+}
+
+/// Page of balance snapshots returned by `zks_getBalanceHistory`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceHistoryPage {
+    /// Snapshots ordered from newest to oldest.
+    pub snapshots: Vec<BalanceSnapshot>,
+    /// Cursor to request the next page with. `None` if there are no more snapshots matching the filter.
+    pub next_cursor: Option<L2BlockNumber>,
+}
+
 #[derive(Debug, Clone)]
 pub struct GetLogsFilter {
     pub from_block: L2BlockNumber,
@@ -772,6 +976,10 @@ impl ProtocolVersion {
 pub enum SupportedTracers {
     CallTracer,
     FlatCallTracer,
+    /// Attributes gas spent by a transaction or call to contracts, functions and program counters.
+    /// Not supported for tracing blocks; stored transactions can only be profiled if they are the first transaction
+    /// in their L2 block. Only supported for protocol versions using the latest VM.
+    GasProfiler,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, Copy)]
@@ -836,22 +1044,65 @@ impl CallTracerBlockResult {
 pub enum CallTracerResult {
     CallTrace(DebugCall),
     FlatCallTrace(Vec<DebugCallFlat>),
+    GasProfile(DebugGasProfile),
 }
 
 impl CallTracerResult {
     pub fn unwrap_flat(self) -> Vec<DebugCallFlat> {
         match self {
-            Self::CallTrace(_) => panic!("Result is a FlatCallTrace"),
             Self::FlatCallTrace(trace) => trace,
+            _ => panic!("Result is not a FlatCallTrace"),
         }
     }
 
     pub fn unwrap_default(self) -> DebugCall {
         match self {
             Self::CallTrace(trace) => trace,
-            Self::FlatCallTrace(_) => panic!("Result is a CallTrace"),
+            _ => panic!("Result is not a CallTrace"),
         }
     }
+
+    pub fn unwrap_gas_profile(self) -> DebugGasProfile {
+        match self {
+            Self::GasProfile(profile) => profile,
+            _ => panic!("Result is not a GasProfile"),
+        }
+    }
+}
+
+/// Gas profile of a transaction or call produced by the `gasProfiler` tracer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugGasProfile {
+    /// Total gas attributed by the profiler. May slightly differ from the gas used by the transaction.
+    pub total_gas: u64,
+    /// Gas spent directly (i.e., excluding nested far calls) in contract functions, ordered by descending gas.
+    pub functions: Vec<DebugGasProfileFunction>,
+    /// Gas spent by contract instructions, ordered by descending gas.
+    pub instructions: Vec<DebugGasProfileInstruction>,
+    /// Profile in the collapsed stack format, one `frame;frame;... gas` line per call stack.
+    /// Can be rendered as a flamegraph, e.g. using `inferno-flamegraph`.
+    pub collapsed_stacks: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugGasProfileFunction {
+    /// Address of the executed code.
+    pub address: Address,
+    /// Function selector. `None` if the calldata is shorter than 4 bytes.
+    pub selector: Option<Bytes>,
+    pub gas: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugGasProfileInstruction {
+    /// Address of the executed code.
+    pub address: Address,
+    /// Program counter of the instruction.
+    pub pc: u16,
+    pub gas: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -898,6 +1149,151 @@ pub struct L1BatchDetails {
     pub base: BlockDetailsBase,
 }
 
+/// Outcome of auditing a single settlement stage (commit, prove or execute) of an L1 batch.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "snake_case")]
+pub enum SettlementAuditStatus {
+    /// The stage transaction is not confirmed on the settlement layer yet.
+    Pending,
+    /// The stage transaction and its effects on the settlement layer match local data.
+    Passed,
+    /// The stage transaction or its effects on the settlement layer diverge from local data.
+    Failed,
+}
+
+/// Settlement audit of an L1 batch returned by `zks_getL1BatchSettlementAudit`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchSettlementAudit {
+    pub number: L1BatchNumber,
+    pub commit: SettlementAuditStatus,
+    pub prove: SettlementAuditStatus,
+    pub execute: SettlementAuditStatus,
+    /// Description of divergences for failed stages.
+    pub error: Option<String>,
+    pub audited_at: DateTime<Utc>,
+}
+
+/// Settlement activity of the node on a single settlement layer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementLayerStats {
+    pub layer: SettlementMode,
+    /// Last L1 batch with a confirmed commit transaction on this layer.
+    pub last_committed_batch: Option<L1BatchNumber>,
+    /// Last L1 batch with a confirmed prove transaction on this layer.
+    pub last_proven_batch: Option<L1BatchNumber>,
+    /// Last L1 batch with a confirmed execute transaction on this layer.
+    pub last_executed_batch: Option<L1BatchNumber>,
+    /// Number of settlement transactions created for this layer that are not confirmed yet.
+    pub inflight_txs: u64,
+}
+
+/// Stage of a settlement layer migration as observed by the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SettlementLayerMigrationStage {
+    /// Settlement transactions are created for the configured layer, and there are no unconfirmed transactions
+    /// on other layers.
+    Completed,
+    /// The node is configured to settle on a layer different from the one used by the latest settlement transaction;
+    /// no transactions were created for the configured layer yet.
+    AwaitingNewLayer,
+    /// Settlement transactions are created for the configured layer, but some transactions on other layers
+    /// are not confirmed yet.
+    DrainingOldLayer,
+}
+
+/// Settlement layer status returned by `unstable_getSettlementLayerStatus`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementLayerStatus {
+    /// Settlement layer the node is configured to use, or `None` if the node doesn't send settlement transactions
+    /// (e.g., it's an external node).
+    pub configured_layer: Option<SettlementMode>,
+    /// Layer of the latest settlement transaction created by the node, or `None` if no transactions were created yet.
+    pub current_layer: Option<SettlementMode>,
+    /// Chain ID of the current layer; may be `None` if the latest transaction isn't sent yet.
+    pub current_layer_chain_id: Option<SLChainId>,
+    /// Migration stage towards the configured layer; `None` if the configured layer is unknown.
+    pub migration_stage: Option<SettlementLayerMigrationStage>,
+    /// Stats for each layer the node has created settlement transactions for.
+    pub layers: Vec<SettlementLayerStats>,
+}
+
+/// Precondition checked before switching the node to another settlement layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SettlementLayerMigrationCheck {
+    /// The node doesn't already settle on the target layer.
+    NotOnTargetLayer,
+    /// There are no unconfirmed settlement transactions on the layers other than the target one.
+    NoInflightTxs,
+    /// All committed L1 batches are executed.
+    AllBatchesExecuted,
+    /// The latest sealed L1 batch uses the latest known protocol version, which supports the target layer.
+    ProtocolVersion,
+}
+
+/// Outcome of a single [`SettlementLayerMigrationCheck`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementLayerMigrationCheckResult {
+    pub check: SettlementLayerMigrationCheck,
+    pub passed: bool,
+    pub details: String,
+}
+
+/// Settlement layer migration preflight returned by `unstable_settlementLayerMigrationPreflight`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementLayerMigrationPreflight {
+    pub target_layer: SettlementMode,
+    /// Whether all checks have passed, i.e. whether the node can be switched to the target layer.
+    pub ready: bool,
+    /// Results of all checks; failed checks correspond to pending migration steps.
+    pub checks: Vec<SettlementLayerMigrationCheckResult>,
+    pub status: SettlementLayerStatus,
+}
+
+/// Outcome of executing a protocol upgrade transaction in the API sandbox.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolUpgradeTxExecution {
+    pub success: bool,
+    /// Revert or halt reason if the transaction has failed.
+    pub error: Option<String>,
+    pub gas_used: u64,
+}
+
+/// State keeper readiness for a protocol upgrade returned by `unstable_protocolUpgradePreflight`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolUpgradePreflight {
+    pub minor_version: u16,
+    /// Timestamp at which the upgrade should be performed.
+    pub timestamp: u64,
+    pub upgrade_tx_hash: Option<H256>,
+    /// Result of executing the upgrade transaction on top of the latest sealed state with upgraded base system contracts.
+    /// `None` if the upgrade has no upgrade transaction, or if it cannot be executed.
+    pub upgrade_tx_execution: Option<ProtocolUpgradeTxExecution>,
+    /// Issues that would prevent the state keeper from applying the upgrade.
+    pub issues: Vec<String>,
+    /// Whether the state keeper is expected to apply the upgrade successfully.
+    pub ready: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StorageProof {
@@ -948,6 +1344,36 @@ pub struct ApiStorageLog {
     pub written_value: U256,
 }
 
+/// Transaction executed in a simulation session (see `zks_simulateTransaction` and `zks_simulateRawTransaction`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedTransaction {
+    /// Zero-based index of the transaction in the session.
+    pub index: U64,
+    /// Transaction hash; only set for raw transactions.
+    pub transaction_hash: Option<H256>,
+    /// Whether the transaction has succeeded.
+    pub success: bool,
+    /// Data returned by the transaction; for reverted transactions, the revert data.
+    pub output: Bytes,
+    /// Human-readable revert or halt reason if the transaction has failed.
+    pub revert_reason: Option<String>,
+    pub gas_used: U256,
+    /// Storage writes produced by the transaction. These writes are visible to subsequent transactions in the session.
+    pub storage_logs: Vec<ApiStorageLog>,
+    pub events: Vec<Log>,
+}
+
+/// Information about a simulation session returned by `zks_getSimulationSession`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationSessionDetails {
+    /// L2 block the session was forked from. Transactions in the session are executed on top of the state
+    /// after this block, with the environment of the following block.
+    pub fork_block_number: L2BlockNumber,
+    pub transactions: Vec<SimulatedTransaction>,
+}
+
 /// Raw transaction execution data.
 /// Data is taken from `TransactionExecutionMetrics`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
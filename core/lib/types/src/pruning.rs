@@ -0,0 +1,59 @@
+//! Types related to Postgres pruning.
+
+use serde::{Deserialize, Serialize};
+pub use zksync_basic_types::pruning::{PrunedDataClass, PruningProfile};
+use zksync_basic_types::{L1BatchNumber, L2BlockNumber, H256};
+
+use crate::api;
+
+/// Archive of data removed from Postgres during a single hard pruning iteration.
+///
+/// Archives are stored as gzip-compressed JSON. All nested data uses the same representation as the corresponding
+/// Web3 / `zks` API responses, so that archived data can be inspected with the same tooling as live data.
+/// Events and L2-to-L1 logs are included into transaction receipts.
+///
+/// The format is versioned via [`Self::version`]; readers should reject archives with an unknown version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrunedDataArchive {
+    /// Version of the archive format. See [`Self::CURRENT_VERSION`].
+    pub version: u32,
+    /// First L1 batch in the archive (inclusive).
+    pub first_l1_batch: L1BatchNumber,
+    /// Last L1 batch in the archive (inclusive).
+    pub last_l1_batch: L1BatchNumber,
+    /// First L2 block in the archive (inclusive).
+    pub first_l2_block: L2BlockNumber,
+    /// Last L2 block in the archive (inclusive).
+    pub last_l2_block: L2BlockNumber,
+    /// State root hash of the last archived L1 batch.
+    pub last_l1_batch_root_hash: H256,
+    /// L1 batches ordered by number.
+    pub l1_batches: Vec<api::L1BatchDetails>,
+    /// L2 blocks ordered by number.
+    pub l2_blocks: Vec<PrunedL2Block>,
+}
+
+impl PrunedDataArchive {
+    /// Current version of the archive format.
+    pub const CURRENT_VERSION: u32 = 1;
+}
+
+/// Archived L2 block together with its transactions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrunedL2Block {
+    /// Block header. Transactions are referenced by hashes.
+    pub block: api::Block<H256>,
+    /// Transactions in the block in the execution order.
+    pub transactions: Vec<api::Transaction>,
+    /// Transaction receipts in the execution order, including events and L2-to-L1 logs.
+    pub receipts: Vec<api::TransactionReceipt>,
+}
+
+/// Key of a [`PrunedDataArchive`] in an object store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrunedDataArchiveKey {
+    pub first_l1_batch: L1BatchNumber,
+    pub last_l1_batch: L1BatchNumber,
+}
@@ -181,6 +181,11 @@ impl ResolvedBlockInfo {
     pub fn use_evm_emulator(&self) -> bool {
         self.use_evm_emulator
     }
+
+    /// Checks whether this info corresponds to the pending block, i.e., the first L2 block in a new L1 batch.
+    pub fn is_pending(&self) -> bool {
+        self.is_pending
+    }
 }
 
 impl<C: ContractsKind> OneshotEnvParameters<C> {
@@ -1,8 +1,11 @@
 use std::sync::Arc;
 
+use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{Connection, Core};
 use zksync_multivm::interface::{OneshotEnv, TxExecutionMode};
-use zksync_types::{fee_model::BatchFeeInput, l2::L2Tx, AccountTreeId, L2ChainId};
+use zksync_types::{
+    fee_model::BatchFeeInput, l2::L2Tx, AccountTreeId, L2ChainId, ProtocolVersionId,
+};
 
 use super::{
     BaseSystemContractsProvider, CallOrExecute, ContractsKind, EstimateGas, ResolvedBlockInfo,
@@ -99,4 +102,33 @@ impl OneshotEnvParameters<CallOrExecute> {
         )
         .await
     }
+
+    /// Prepares environment for executing a protocol upgrade transaction. Similarly to the state keeper, the transaction
+    /// is executed first in a new L1 batch using base system contracts of the upgraded protocol version. Hence,
+    /// `resolved_block_info` must correspond to the pending block.
+    pub async fn to_protocol_upgrade_env(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        resolved_block_info: &ResolvedBlockInfo,
+        fee_input: BatchFeeInput,
+        protocol_version: ProtocolVersionId,
+        base_system_contracts: BaseSystemContracts,
+    ) -> anyhow::Result<OneshotEnv> {
+        anyhow::ensure!(
+            resolved_block_info.is_pending(),
+            "protocol upgrade transactions can only be executed on top of the pending block"
+        );
+        let mut env = self
+            .to_env_inner(
+                connection,
+                TxExecutionMode::VerifyExecute,
+                resolved_block_info,
+                fee_input,
+                None,
+            )
+            .await?;
+        env.system.version = protocol_version;
+        env.system.base_system_smart_contracts = base_system_contracts;
+        Ok(env)
+    }
 }
@@ -5,8 +5,8 @@ use zksync_multivm::interface::{
     executor::{OneshotExecutor, TransactionValidator},
     storage::ReadStorage,
     tracer::{ValidationError, ValidationParams, ValidationTraces},
-    ExecutionResult, OneshotEnv, OneshotTracingParams, OneshotTransactionExecutionResult,
-    TxExecutionArgs, TxExecutionMode, VmExecutionResultAndLogs,
+    ExecutionResult, GasProfile, OneshotEnv, OneshotTracingParams,
+    OneshotTransactionExecutionResult, TxExecutionArgs, TxExecutionMode, VmExecutionResultAndLogs,
 };
 use zksync_types::{l2::L2Tx, Transaction};
 
@@ -79,6 +79,14 @@ impl MockOneshotExecutor {
         )
     }
 
+    /// Same as [`Self::set_call_responses()`], but allows to customize returned VM logs etc.
+    pub fn set_full_call_responses<F>(&mut self, responses: F)
+    where
+        F: Fn(&Transaction, &OneshotEnv) -> VmExecutionResultAndLogs + 'static + Send + Sync,
+    {
+        self.call_responses = Box::new(responses);
+    }
+
     /// Same as [`Self::set_tx_responses()`], but allows to customize returned VM logs etc.
     pub fn set_full_tx_responses<F>(&mut self, responses: F)
     where
@@ -113,6 +121,7 @@ where
             tx_result: Box::new(self.mock_inspect(&env, args)),
             compression_result: Ok(()),
             call_traces: vec![],
+            gas_profile: GasProfile::default(),
         })
     }
 }
@@ -31,7 +31,19 @@ fn selecting_vm_for_execution() {
         assert_matches!(mode, FastVmMode::New);
 
         // Tracing calls is not supported by the new VM.
-        let mode = executor.select_fast_vm_mode(&env, &OneshotTracingParams { trace_calls: true });
+        let tracing_params = OneshotTracingParams {
+            trace_calls: true,
+            ..OneshotTracingParams::default()
+        };
+        let mode = executor.select_fast_vm_mode(&env, &tracing_params);
+        assert_matches!(mode, FastVmMode::Old);
+
+        // Neither is gas profiling.
+        let tracing_params = OneshotTracingParams {
+            profile_gas: true,
+            ..OneshotTracingParams::default()
+        };
+        let mode = executor.select_fast_vm_mode(&env, &tracing_params);
         assert_matches!(mode, FastVmMode::Old);
 
         // Old protocol versions are not supported either.
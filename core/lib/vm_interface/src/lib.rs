@@ -30,10 +30,11 @@ pub use crate::{
         outputs::{
             BatchTransactionExecutionResult, BootloaderMemory, Call, CallType, CircuitStatistic,
             CompressedBytecodeInfo, CurrentExecutionState, DeduplicatedWritesMetrics,
-            ExecutionResult, FinishedL1Batch, L2Block, OneshotTransactionExecutionResult,
-            PushTransactionResult, Refunds, TransactionExecutionMetrics,
-            TransactionExecutionResult, TxExecutionStatus, VmEvent, VmExecutionLogs,
-            VmExecutionMetrics, VmExecutionResultAndLogs, VmExecutionStatistics, VmMemoryMetrics,
+            ExecutionResult, FinishedL1Batch, GasProfile, GasProfileFrame, L2Block,
+            OneshotTransactionExecutionResult, PushTransactionResult, Refunds,
+            TransactionExecutionMetrics, TransactionExecutionResult, TxExecutionStatus, VmEvent,
+            VmExecutionLogs, VmExecutionMetrics, VmExecutionResultAndLogs, VmExecutionStatistics,
+            VmMemoryMetrics,
         },
         tracer,
     },
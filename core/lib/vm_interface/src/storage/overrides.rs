@@ -5,9 +5,69 @@ use std::{
     fmt,
 };
 
-use zksync_types::{AccountTreeId, StorageKey, StorageValue, H256};
+use zksync_types::{bytecode::BytecodeHash, AccountTreeId, StorageKey, StorageValue, H256};
 
 use super::ReadStorage;
+use crate::VmExecutionResultAndLogs;
+
+/// Storage changes accumulated by executing a sequence of transactions on top of a base storage,
+/// e.g. in a simulation session in the API server. Can be applied to [`StorageWithOverrides`]
+/// to execute further transactions on top of the accumulated state.
+#[derive(Debug, Clone, Default)]
+pub struct StorageDiff {
+    slots: HashMap<StorageKey, H256>,
+    factory_deps: HashMap<H256, Vec<u8>>,
+}
+
+impl StorageDiff {
+    /// Returns the value of the specified slot if it was written to.
+    pub fn read_value(&self, key: &StorageKey) -> Option<StorageValue> {
+        self.slots.get(key).copied()
+    }
+
+    /// Returns the bytecode with the specified hash if it was published.
+    pub fn load_factory_dep(&self, hash: H256) -> Option<&[u8]> {
+        self.factory_deps.get(&hash).map(Vec::as_slice)
+    }
+
+    /// Returns the number of written slots.
+    pub fn slot_count(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of published bytecodes.
+    pub fn factory_dep_count(&self) -> usize {
+        self.factory_deps.len()
+    }
+
+    pub fn set_value(&mut self, key: StorageKey, value: StorageValue) {
+        self.slots.insert(key, value);
+    }
+
+    pub fn store_factory_dep(&mut self, hash: H256, code: Vec<u8>) {
+        self.factory_deps.insert(hash, code);
+    }
+
+    /// Merges the effects of a transaction execution into this diff. `factory_deps` are the dependencies
+    /// supplied with the transaction. Changes reverted by the VM (e.g., if the transaction has failed)
+    /// are not present in storage logs, so all logs are merged regardless of the execution result.
+    /// Similarly, factory deps are stored regardless of the result since they may be marked as known
+    /// before the transaction is executed.
+    pub fn merge_execution(&mut self, result: &VmExecutionResultAndLogs, factory_deps: &[Vec<u8>]) {
+        for log in &result.logs.storage_logs {
+            if log.log.is_write() {
+                self.slots.insert(log.log.key, log.log.value);
+            }
+        }
+        for dep in factory_deps {
+            let hash = BytecodeHash::for_bytecode(dep).value();
+            self.factory_deps.insert(hash, dep.clone());
+        }
+        for (hash, dep) in &result.dynamic_factory_deps {
+            self.factory_deps.insert(*hash, dep.clone());
+        }
+    }
+}
 
 /// A storage view that allows to override some of the storage values.
 #[derive(Debug)]
@@ -16,6 +76,8 @@ pub struct StorageWithOverrides<S> {
     overridden_slots: HashMap<StorageKey, H256>,
     overridden_factory_deps: HashMap<H256, Vec<u8>>,
     empty_accounts: HashSet<AccountTreeId>,
+    /// Keys written by previously executed transactions; writes to these keys are not initial.
+    written_keys: HashSet<StorageKey>,
 }
 
 impl<S: ReadStorage> StorageWithOverrides<S> {
@@ -26,6 +88,19 @@ impl<S: ReadStorage> StorageWithOverrides<S> {
             overridden_slots: HashMap::new(),
             overridden_factory_deps: HashMap::new(),
             empty_accounts: HashSet::new(),
+            written_keys: HashSet::new(),
+        }
+    }
+
+    /// Applies changes accumulated by previously executed transactions. Unlike [`Self::set_value()`],
+    /// writes to the changed slots will be treated as repeated writes.
+    pub fn apply_diff(&mut self, diff: &StorageDiff) {
+        for (&key, &value) in &diff.slots {
+            self.overridden_slots.insert(key, value);
+            self.written_keys.insert(key);
+        }
+        for (&hash, code) in &diff.factory_deps {
+            self.overridden_factory_deps.insert(hash, code.clone());
         }
     }
 
@@ -54,6 +129,9 @@ impl<S: ReadStorage + fmt::Debug> ReadStorage for StorageWithOverrides<S> {
     }
 
     fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        if self.written_keys.contains(key) {
+            return false;
+        }
         self.storage_handle.is_write_initial(key)
     }
 
@@ -68,3 +146,62 @@ impl<S: ReadStorage + fmt::Debug> ReadStorage for StorageWithOverrides<S> {
         self.storage_handle.get_enumeration_index(key)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{Address, StorageLog, StorageLogWithPreviousValue};
+
+    use super::*;
+    use crate::storage::InMemoryStorage;
+
+    #[test]
+    fn applying_storage_diff() {
+        let mut base = InMemoryStorage::default();
+        let existing_key =
+            StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+        base.set_value(existing_key, H256::repeat_byte(1));
+        let new_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(2)), H256::zero());
+        let read_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(3)), H256::zero());
+
+        let mut result = VmExecutionResultAndLogs::mock_success();
+        result.logs.storage_logs = vec![
+            StorageLogWithPreviousValue {
+                log: StorageLog::new_write_log(new_key, H256::repeat_byte(2)),
+                previous_value: H256::zero(),
+            },
+            StorageLogWithPreviousValue {
+                log: StorageLog::new_read_log(read_key, H256::zero()),
+                previous_value: H256::zero(),
+            },
+            StorageLogWithPreviousValue {
+                log: StorageLog::new_write_log(existing_key, H256::repeat_byte(0xff)),
+                previous_value: H256::repeat_byte(1),
+            },
+        ];
+        let bytecode = vec![0; 32];
+        let bytecode_hash = BytecodeHash::for_bytecode(&bytecode).value();
+        let mut diff = StorageDiff::default();
+        diff.merge_execution(&result, &[bytecode.clone()]);
+
+        assert_eq!(diff.slot_count(), 2);
+        assert_eq!(diff.read_value(&new_key), Some(H256::repeat_byte(2)));
+        assert_eq!(
+            diff.read_value(&existing_key),
+            Some(H256::repeat_byte(0xff))
+        );
+        assert_eq!(diff.read_value(&read_key), None);
+        assert_eq!(
+            diff.load_factory_dep(bytecode_hash),
+            Some(bytecode.as_slice())
+        );
+
+        let mut storage = StorageWithOverrides::new(base);
+        storage.apply_diff(&diff);
+        assert_eq!(storage.read_value(&new_key), H256::repeat_byte(2));
+        assert_eq!(storage.read_value(&existing_key), H256::repeat_byte(0xff));
+        assert!(!storage.is_write_initial(&new_key));
+        assert!(!storage.is_write_initial(&existing_key));
+        assert!(storage.is_write_initial(&read_key));
+        assert_eq!(storage.load_factory_dep(bytecode_hash), Some(bytecode));
+    }
+}
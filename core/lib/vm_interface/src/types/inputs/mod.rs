@@ -1,5 +1,6 @@
 use zksync_types::{
-    l2::L2Tx, ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, U256,
+    l2::L2Tx, protocol_upgrade::ProtocolUpgradeTx, ExecuteTransactionCommon, Nonce,
+    PackedEthSignature, Transaction, U256,
 };
 
 pub use self::{
@@ -85,6 +86,17 @@ impl TxExecutionArgs {
             transaction,
         }
     }
+
+    /// Creates arguments for a protocol upgrade transaction. The transaction is executed as is, the same way
+    /// as in the state keeper.
+    pub fn for_protocol_upgrade(tx: ProtocolUpgradeTx) -> Self {
+        Self {
+            enforced_nonce: None,
+            added_balance: U256::zero(),
+            adjust_pubdata_price: false,
+            transaction: tx.into(),
+        }
+    }
 }
 
 /// Inputs and outputs for all tracers supported for oneshot transaction / call execution.
@@ -92,4 +104,6 @@ impl TxExecutionArgs {
 pub struct OneshotTracingParams {
     /// Whether to trace contract calls.
     pub trace_calls: bool,
+    /// Whether to profile gas usage by contracts, functions and program counters.
+    pub profile_gas: bool,
 }
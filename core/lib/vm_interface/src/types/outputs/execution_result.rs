@@ -13,7 +13,7 @@ use zksync_types::{
 };
 
 use crate::{
-    BytecodeCompressionError, CompressedBytecodeInfo, Halt, VmExecutionMetrics,
+    BytecodeCompressionError, CompressedBytecodeInfo, GasProfile, Halt, VmExecutionMetrics,
     VmExecutionStatistics, VmRevertReason,
 };
 
@@ -361,6 +361,8 @@ pub struct OneshotTransactionExecutionResult {
     pub compression_result: Result<(), BytecodeCompressionError>,
     /// Call traces (if requested; otherwise, empty).
     pub call_traces: Vec<Call>,
+    /// Gas profile (if requested; otherwise, empty).
+    pub gas_profile: GasProfile,
 }
 
 /// High-level transaction execution result used by the API server sandbox etc.
@@ -0,0 +1,158 @@
+use std::{collections::BTreeMap, fmt};
+
+use zksync_types::Address;
+
+/// Frame of a call stack used in a [`GasProfile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GasProfileFrame {
+    /// Far call to a contract.
+    FarCall {
+        /// Address of the executed code. For delegate calls, this is the address of the delegate,
+        /// rather than the address in whose context the code is executed.
+        address: Address,
+        /// Function selector, i.e. the first 4 bytes of the calldata. `None` if the calldata is shorter
+        /// than 4 bytes or cannot be read (e.g., the callee received no gas).
+        selector: Option<[u8; 4]>,
+    },
+    /// Near call within the contract code, i.e., a call of an internal function.
+    NearCall {
+        /// Program counter at the entry point of the called function.
+        pc: u16,
+    },
+}
+
+impl fmt::Display for GasProfileFrame {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FarCall { address, selector } => {
+                write!(formatter, "{address:?}")?;
+                if let Some(selector) = selector {
+                    write!(formatter, "::0x{}", hex::encode(selector))?;
+                }
+                Ok(())
+            }
+            Self::NearCall { pc } => write!(formatter, "@{pc:#x}"),
+        }
+    }
+}
+
+/// Gas (aka ergs) spent during VM execution, attributed to code locations. Produced by the gas profiling tracers.
+///
+/// Gas spent on far call and return instructions is attributed to the caller, minus the gas spent
+/// by the callee; as such, the total gas in the profile may slightly differ from the gas used by a transaction
+/// (e.g., because of call stipends).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GasProfile {
+    /// Gas spent directly (i.e., excluding nested calls) in each encountered call stack.
+    /// Stacks are ordered from the outermost frame.
+    pub stacks: BTreeMap<Vec<GasProfileFrame>, u64>,
+    /// Gas spent by instructions keyed by the code address and the program counter.
+    pub pcs: BTreeMap<(Address, u16), u64>,
+}
+
+impl GasProfile {
+    /// Returns the total gas recorded in this profile.
+    pub fn total_gas(&self) -> u64 {
+        self.stacks.values().sum()
+    }
+
+    /// Returns gas spent directly in each contract function, keyed by the code address and function selector.
+    /// Gas spent in near calls is attributed to the enclosing far call.
+    pub fn by_function(&self) -> BTreeMap<(Address, Option<[u8; 4]>), u64> {
+        let mut by_function = BTreeMap::new();
+        for (stack, &gas) in &self.stacks {
+            let far_call = stack.iter().rev().find_map(|frame| match frame {
+                GasProfileFrame::FarCall { address, selector } => Some((*address, *selector)),
+                GasProfileFrame::NearCall { .. } => None,
+            });
+            if let Some(key) = far_call {
+                *by_function.entry(key).or_default() += gas;
+            }
+        }
+        by_function
+    }
+
+    /// Returns gas spent directly in each contract, keyed by the code address.
+    pub fn by_contract(&self) -> BTreeMap<Address, u64> {
+        let mut by_contract = BTreeMap::new();
+        for ((address, _), gas) in self.by_function() {
+            *by_contract.entry(address).or_default() += gas;
+        }
+        by_contract
+    }
+
+    /// Outputs this profile in the collapsed stack format (one `frame;frame;... gas` line per stack)
+    /// accepted by flamegraph renderers, such as `inferno-flamegraph` or `flamegraph.pl`.
+    pub fn to_collapsed_stacks(&self) -> String {
+        let mut output = String::new();
+        for (stack, gas) in &self.stacks {
+            if *gas == 0 {
+                continue;
+            }
+            let frames: Vec<_> = stack.iter().map(ToString::to_string).collect();
+            output += &frames.join(";");
+            output += &format!(" {gas}\n");
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_profile() -> GasProfile {
+        let root = GasProfileFrame::FarCall {
+            address: Address::repeat_byte(1),
+            selector: None,
+        };
+        let callee = GasProfileFrame::FarCall {
+            address: Address::repeat_byte(2),
+            selector: Some([0xde, 0xad, 0xbe, 0xef]),
+        };
+        let near_call = GasProfileFrame::NearCall { pc: 42 };
+
+        let stacks = BTreeMap::from([
+            (vec![root], 100),
+            (vec![root, callee], 50),
+            (vec![root, callee, near_call], 25),
+            (vec![root, near_call], 0),
+        ]);
+        GasProfile {
+            stacks,
+            pcs: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn aggregating_gas_profile() {
+        let profile = test_profile();
+        assert_eq!(profile.total_gas(), 175);
+
+        let by_function = profile.by_function();
+        assert_eq!(by_function.len(), 2);
+        assert_eq!(by_function[&(Address::repeat_byte(1), None)], 100);
+        assert_eq!(
+            by_function[&(Address::repeat_byte(2), Some([0xde, 0xad, 0xbe, 0xef]))],
+            75
+        );
+
+        let by_contract = profile.by_contract();
+        assert_eq!(
+            by_contract,
+            BTreeMap::from([
+                (Address::repeat_byte(1), 100),
+                (Address::repeat_byte(2), 75)
+            ])
+        );
+    }
+
+    #[test]
+    fn gas_profile_as_collapsed_stacks() {
+        let collapsed = test_profile().to_collapsed_stacks();
+        let root = format!("{:?}", Address::repeat_byte(1));
+        let callee = format!("{:?}::0xdeadbeef", Address::repeat_byte(2));
+        let expected = format!("{root} 100\n{root};{callee} 50\n{root};{callee};@0x2a 25\n");
+        assert_eq!(collapsed, expected);
+    }
+}
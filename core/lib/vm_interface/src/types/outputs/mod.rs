@@ -9,6 +9,7 @@ pub use self::{
     },
     execution_state::{BootloaderMemory, CurrentExecutionState},
     finished_l1batch::FinishedL1Batch,
+    gas_profile::{GasProfile, GasProfileFrame},
     l2_block::L2Block,
     statistic::{
         CircuitStatistic, DeduplicatedWritesMetrics, TransactionExecutionMetrics,
@@ -20,6 +21,7 @@ mod bytecode;
 mod execution_result;
 mod execution_state;
 mod finished_l1batch;
+mod gas_profile;
 mod l2_block;
 mod statistic;
 
@@ -592,6 +592,16 @@ impl DivergenceErrors {
         self.divergences.extend(from.divergences);
     }
 
+    /// Checks whether there are no divergences.
+    pub fn is_empty(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    /// Returns human-readable descriptions of the found divergences.
+    pub fn divergences(&self) -> &[String] {
+        &self.divergences
+    }
+
     fn context(mut self, context: String) -> Self {
         self.context = Some(context);
         self
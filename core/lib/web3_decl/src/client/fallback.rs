@@ -0,0 +1,349 @@
+//! Client failing over between multiple upstream nodes.
+
+use std::{
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use jsonrpsee::core::{
+    client::{BatchResponse, ClientT, Error},
+    params::BatchRequestBuilder,
+    traits::ToRpcParams,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::time::Instant;
+
+use super::{
+    boxed::RawParams,
+    metrics::{UpstreamLabels, FALLBACK_METRICS},
+    DynClient, ForWeb3Network, Network, TaggedClient,
+};
+use crate::error::is_retriable;
+
+/// Smoothing factor for the exponential moving average of the upstream health score.
+const HEALTH_SCORE_SMOOTHING: f64 = 0.2;
+
+#[derive(Debug)]
+struct UpstreamState {
+    score: f64,
+    consecutive_failures: u32,
+    backoff_until: Option<Instant>,
+}
+
+impl Default for UpstreamState {
+    fn default() -> Self {
+        Self {
+            score: 1.0,
+            consecutive_failures: 0,
+            backoff_until: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Upstream<Net: Network> {
+    name: Arc<str>,
+    client: Box<DynClient<Net>>,
+    state: Arc<Mutex<UpstreamState>>,
+}
+
+/// Health information about an upstream of a [`FallbackClient`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpstreamHealth {
+    /// Upstream name as specified in [`FallbackClientBuilder::upstream()`].
+    pub name: String,
+    /// Health score in the `[0, 1]` range; an exponential moving average of successful calls.
+    pub score: f64,
+    /// Number of consecutive failed calls to the upstream.
+    pub consecutive_failures: u32,
+    /// Whether the upstream is currently backed off, i.e., calls are routed to other upstreams.
+    pub is_backed_off: bool,
+}
+
+/// JSON-RPC client routing calls to one of several upstreams (e.g., the main node and trusted external nodes).
+///
+/// Upstreams are tried in the order they were added to the builder. If a call to an upstream fails with a transport-level
+/// or otherwise retriable error (e.g., the upstream is down or rate-limits requests), the upstream is backed off
+/// with exponentially growing delay, and the call is retried with the next upstream. Calls that fail with a non-retriable error
+/// (e.g., an RPC error returned by a healthy upstream) are not retried. If all upstreams are backed off, they are tried
+/// in the order of backoff expiration.
+///
+/// Upstream health is shared among all clones of the client.
+#[derive(Debug, Clone)]
+pub struct FallbackClient<Net: Network> {
+    upstreams: Vec<Upstream<Net>>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    component_name: &'static str,
+    network: Net,
+}
+
+impl<Net: Network> FallbackClient<Net> {
+    /// Creates a builder for the client.
+    pub fn builder(network: Net) -> FallbackClientBuilder<Net> {
+        FallbackClientBuilder {
+            upstreams: vec![],
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            network,
+        }
+    }
+
+    /// Iterates over upstream clients together with their names in the order they were added to the builder.
+    /// Calls to the returned clients bypass failover logic and do not influence upstream health.
+    pub fn upstreams(&self) -> impl Iterator<Item = (&str, Box<DynClient<Net>>)> + '_ {
+        self.upstreams
+            .iter()
+            .map(|upstream| (&*upstream.name, upstream.client.clone()))
+    }
+
+    /// Returns health information for all upstreams in the order they were added to the builder.
+    pub fn health(&self) -> Vec<UpstreamHealth> {
+        let now = Instant::now();
+        self.upstreams
+            .iter()
+            .map(|upstream| {
+                let state = upstream.state.lock().unwrap();
+                UpstreamHealth {
+                    name: upstream.name.to_string(),
+                    score: state.score,
+                    consecutive_failures: state.consecutive_failures,
+                    is_backed_off: state.backoff_until.is_some_and(|until| until > now),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns indices of upstreams in the order they should be tried.
+    fn ordered_upstreams(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let mut indices: Vec<_> = self
+            .upstreams
+            .iter()
+            .enumerate()
+            .map(|(i, upstream)| {
+                let backoff_until = upstream.state.lock().unwrap().backoff_until;
+                (backoff_until.filter(|&until| until > now), i)
+            })
+            .collect();
+        // Upstreams that are not backed off (`None`) go first.
+        indices.sort_unstable();
+        indices.into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// Updates the upstream state based on the call result. Returns `true` if the call should be retried
+    /// with the next upstream.
+    fn observe_result<T>(
+        &self,
+        upstream: &Upstream<Net>,
+        method: &str,
+        result: &Result<T, Error>,
+    ) -> bool {
+        let should_fail_over = match result {
+            Ok(_) => false,
+            Err(err) => is_retriable(err) || matches!(err, Error::RestartNeeded(_)),
+        };
+
+        let labels = UpstreamLabels {
+            network: self.network.metric_label(),
+            upstream: upstream.name.to_string(),
+        };
+        let mut state = upstream.state.lock().unwrap();
+        if should_fail_over {
+            state.score *= 1.0 - HEALTH_SCORE_SMOOTHING;
+            state.consecutive_failures += 1;
+            let backoff = self
+                .initial_backoff
+                .saturating_mul(2_u32.saturating_pow(state.consecutive_failures - 1))
+                .min(self.max_backoff);
+            state.backoff_until = Some(Instant::now() + backoff);
+
+            FALLBACK_METRICS.upstream_failures[&labels].inc();
+            if let Err(err) = result {
+                tracing::warn!(
+                    network = labels.network,
+                    component = self.component_name,
+                    upstream = labels.upstream,
+                    "Call `{method}` to upstream `{}` failed ({} consecutive failures); backing off for {backoff:?}: {err}",
+                    upstream.name,
+                    state.consecutive_failures
+                );
+            }
+        } else {
+            state.score = state.score * (1.0 - HEALTH_SCORE_SMOOTHING) + HEALTH_SCORE_SMOOTHING;
+            if state.consecutive_failures > 0 {
+                tracing::info!(
+                    network = labels.network,
+                    component = self.component_name,
+                    upstream = labels.upstream,
+                    "Upstream `{}` has recovered after {} consecutive failures",
+                    upstream.name,
+                    state.consecutive_failures
+                );
+            }
+            state.consecutive_failures = 0;
+            state.backoff_until = None;
+        }
+        FALLBACK_METRICS.upstream_health_score[&labels].set(state.score);
+        should_fail_over
+    }
+
+    /// Calls upstreams in the order returned by [`Self::ordered_upstreams()`] until a call succeeds or fails
+    /// with an error that shouldn't be retried. Returns the call result together with the name of the upstream
+    /// that has produced it, e.g. to cross-check the result with other upstreams.
+    pub async fn call_with_upstream<T, Fut>(
+        &self,
+        method: &str,
+        mut call: impl FnMut(Box<DynClient<Net>>) -> Fut,
+    ) -> (Result<T, Error>, &str)
+    where
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut last_result = None;
+        for (attempt, i) in self.ordered_upstreams().into_iter().enumerate() {
+            let upstream = &self.upstreams[i];
+            if attempt > 0 {
+                FALLBACK_METRICS.failovers[&self.network.metric_label()].inc();
+            }
+            let result = call(upstream.client.clone()).await;
+            if !self.observe_result(upstream, method, &result) {
+                return (result, &upstream.name);
+            }
+            last_result = Some((result, &*upstream.name));
+        }
+        last_result.expect("fallback client has no upstreams")
+    }
+
+    async fn call<T, Fut>(
+        &self,
+        method: &str,
+        call: impl FnMut(Box<DynClient<Net>>) -> Fut,
+    ) -> Result<T, Error>
+    where
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        self.call_with_upstream(method, call).await.0
+    }
+}
+
+impl<Net: Network> ForWeb3Network for FallbackClient<Net> {
+    type Net = Net;
+
+    fn network(&self) -> Self::Net {
+        self.network
+    }
+
+    fn component(&self) -> &'static str {
+        self.component_name
+    }
+}
+
+impl<Net: Network> TaggedClient for FallbackClient<Net> {
+    fn set_component(&mut self, component_name: &'static str) {
+        self.component_name = component_name;
+        for upstream in &mut self.upstreams {
+            upstream.client = upstream.client.clone().for_component(component_name);
+        }
+    }
+}
+
+#[async_trait]
+impl<Net: Network> ClientT for FallbackClient<Net> {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        let params = params.to_rpc_params()?;
+        self.call(method, |client| {
+            let params = RawParams(params.clone());
+            async move { ClientT::notification(&client, method, params).await }
+        })
+        .await
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let params = params.to_rpc_params()?;
+        self.call(method, |client| {
+            let params = RawParams(params.clone());
+            async move { ClientT::request(&client, method, params).await }
+        })
+        .await
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + fmt::Debug + 'a,
+    {
+        self.call("batch", |client| {
+            let batch = batch.clone();
+            async move { ClientT::batch_request(&client, batch).await }
+        })
+        .await
+    }
+}
+
+/// Builder for [`FallbackClient`].
+#[derive(Debug)]
+pub struct FallbackClientBuilder<Net: Network> {
+    upstreams: Vec<Upstream<Net>>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    network: Net,
+}
+
+impl<Net: Network> FallbackClientBuilder<Net> {
+    /// Adds an upstream. The name is used in logs and as a metric label, so it should not contain sensitive info
+    /// (e.g., an API key that may be a part of the upstream URL).
+    pub fn upstream(mut self, name: impl Into<String>, client: Box<DynClient<Net>>) -> Self {
+        let name: String = name.into();
+        let labels = UpstreamLabels {
+            network: self.network.metric_label(),
+            upstream: name.clone(),
+        };
+        FALLBACK_METRICS.upstream_health_score[&labels].set(1.0);
+
+        self.upstreams.push(Upstream {
+            name: name.into(),
+            client,
+            state: Arc::default(),
+        });
+        self
+    }
+
+    /// Sets the backoff applied to a failing upstream. The backoff is doubled after each consecutive failure,
+    /// starting from `initial` and up to `max`.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no upstreams were added.
+    pub fn build(self) -> FallbackClient<Net> {
+        assert!(
+            !self.upstreams.is_empty(),
+            "fallback client must have at least one upstream"
+        );
+        FallbackClient {
+            upstreams: self.upstreams,
+            initial_backoff: self.initial_backoff,
+            max_backoff: self.max_backoff,
+            component_name: "",
+            network: self.network,
+        }
+    }
+}
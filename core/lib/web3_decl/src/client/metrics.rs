@@ -4,8 +4,8 @@ use std::time::Duration;
 
 use jsonrpsee::{core::client, http_client::transport};
 use vise::{
-    Buckets, Counter, DurationAsSecs, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Info,
-    LabeledFamily, Metrics, Unit,
+    Buckets, Counter, DurationAsSecs, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram,
+    Info, LabeledFamily, Metrics, Unit,
 };
 
 use super::{AcquireStats, CallOrigin, SharedRateLimit};
@@ -211,3 +211,24 @@ impl L2ClientMetrics {
 
 #[vise::register]
 pub(super) static METRICS: vise::Global<L2ClientMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct UpstreamLabels {
+    pub network: String,
+    pub upstream: String,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "l2_client_fallback")]
+pub(super) struct FallbackClientMetrics {
+    /// Health score of an upstream in the `[0, 1]` range.
+    pub upstream_health_score: Family<UpstreamLabels, Gauge<f64>>,
+    /// Number of calls to an upstream that failed with a retriable error.
+    pub upstream_failures: Family<UpstreamLabels, Counter>,
+    /// Number of times a call was retried with the next upstream.
+    #[metrics(labels = ["network"])]
+    pub failovers: LabeledFamily<String, Counter>,
+}
+
+#[vise::register]
+pub(super) static FALLBACK_METRICS: vise::Global<FallbackClientMetrics> = vise::Global::new();
@@ -5,6 +5,8 @@
 //!
 //! - [`Client`] is the main client implementation. It's parameterized by the transport (e.g., HTTP or WS),
 //!   with HTTP being the default option.
+//! - [`FallbackClient`] routes calls to one of several upstreams (e.g., the main node and trusted external nodes),
+//!   failing over to the next upstream if a call fails with a retriable error.
 //! - [`MockClient`] is a mock client useful for testing. Bear in mind that because of the client being generic,
 //!   mock tooling is fairly low-level. Prefer defining a domain-specific wrapper trait for the client functionality and mock it
 //!   where it's possible.
@@ -37,12 +39,14 @@ use zksync_types::url::SensitiveUrl;
 use self::metrics::{L2ClientMetrics, METRICS};
 pub use self::{
     boxed::{DynClient, ObjectSafeClient},
+    fallback::{FallbackClient, FallbackClientBuilder, UpstreamHealth},
     mock::{MockClient, MockClientBuilder},
     network::{ForWeb3Network, Network, TaggedClient, L1, L2},
     shared::Shared,
 };
 
 mod boxed;
+mod fallback;
 mod metrics;
 mod mock;
 mod network;
@@ -1,7 +1,10 @@
 //! Tests for `L2Client` focused on rate limiting.
 
 use std::{
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
     time::Duration,
 };
 
@@ -272,3 +275,97 @@ async fn wrapping_mock_client() {
     };
     assert!(metrics.http_errors.contains(&labels), "{metrics:?}");
 }
+
+#[tokio::test]
+async fn fallback_client_fails_over_to_healthy_upstream() {
+    tokio::time::pause();
+
+    let primary_calls = Arc::new(AtomicUsize::new(0));
+    let primary_is_down = Arc::new(AtomicBool::new(true));
+    let primary = MockClient::builder(L2::default())
+        .method("eth_blockNumber", {
+            let primary_calls = primary_calls.clone();
+            let primary_is_down = primary_is_down.clone();
+            move || {
+                primary_calls.fetch_add(1, Ordering::SeqCst);
+                if primary_is_down.load(Ordering::SeqCst) {
+                    let http_err = transport::Error::Rejected { status_code: 429 };
+                    Err(Error::Transport(http_err.into()))
+                } else {
+                    Ok(U64::from(1))
+                }
+            }
+        })
+        .build();
+    let fallback = MockClient::builder(L2::default())
+        .method("eth_blockNumber", || Ok(U64::from(2)))
+        .build();
+    let client = FallbackClient::builder(L2::default())
+        .upstream("primary", Box::new(primary))
+        .upstream("fallback", Box::new(fallback))
+        .with_backoff(Duration::from_secs(1), Duration::from_secs(10))
+        .build();
+
+    let block_number: U64 = client
+        .request("eth_blockNumber", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(block_number, U64::from(2));
+    assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+    let health = client.health();
+    assert_eq!(health[0].consecutive_failures, 1);
+    assert!(health[0].is_backed_off);
+    assert!(health[0].score < health[1].score);
+    assert!(!health[1].is_backed_off);
+
+    // The primary upstream is backed off, so it shouldn't be called.
+    let block_number: U64 = client
+        .request("eth_blockNumber", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(block_number, U64::from(2));
+    assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+
+    // After the backoff expires, the primary upstream is retried; the backoff doubles after another failure.
+    tokio::time::advance(Duration::from_millis(1_100)).await;
+    let block_number: U64 = client
+        .request("eth_blockNumber", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(block_number, U64::from(2));
+    assert_eq!(primary_calls.load(Ordering::SeqCst), 2);
+    assert_eq!(client.health()[0].consecutive_failures, 2);
+    tokio::time::advance(Duration::from_millis(1_100)).await;
+    assert!(client.health()[0].is_backed_off);
+
+    primary_is_down.store(false, Ordering::SeqCst);
+    tokio::time::advance(Duration::from_secs(1)).await;
+    let block_number: U64 = client
+        .request("eth_blockNumber", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(block_number, U64::from(1));
+    assert_eq!(primary_calls.load(Ordering::SeqCst), 3);
+    let health = client.health();
+    assert_eq!(health[0].consecutive_failures, 0);
+    assert!(!health[0].is_backed_off);
+}
+
+#[tokio::test]
+async fn fallback_client_does_not_fail_over_on_rpc_errors() {
+    let primary = MockClient::builder(L2::default()).build();
+    let fallback = MockClient::builder(L2::default())
+        .method("eth_blockNumber", || Ok(U64::from(2)))
+        .build();
+    let client = FallbackClient::builder(L2::default())
+        .upstream("primary", Box::new(primary))
+        .upstream("fallback", Box::new(fallback))
+        .build();
+
+    let err = client
+        .request::<U64, _>("eth_blockNumber", rpc_params![])
+        .await
+        .unwrap_err();
+    assert_matches!(err, Error::Call(_));
+    assert!(client.health().iter().all(|health| !health.is_backed_off));
+}
@@ -14,7 +14,9 @@ use std::{
 use jsonrpsee::{core::ClientError, types::error::ErrorCode};
 use pin_project_lite::pin_project;
 use thiserror::Error;
-use zksync_types::{api::SerializationTransactionError, L1BatchNumber, L2BlockNumber};
+use zksync_types::{
+    api::SerializationTransactionError, pruning::PrunedDataClass, L1BatchNumber, L2BlockNumber,
+};
 
 /// Server-side representation of the RPC error.
 #[derive(Debug, Error)]
@@ -25,6 +27,11 @@ pub enum Web3Error {
     PrunedBlock(L2BlockNumber),
     #[error("L1 batch with such an ID is pruned; the first retained L1 batch is {0}")]
     PrunedL1Batch(L1BatchNumber),
+    /// Block is retained, but the requested data class for it was removed by selective pruning.
+    #[error(
+        "Data ({0}) for block with such an ID is pruned; the first block with retained {0} is {1}"
+    )]
+    PrunedBlockData(PrunedDataClass, L2BlockNumber),
     #[error("{}", _0.as_ref())]
     ProxyError(#[from] EnrichedClientError),
     #[error("{0}")]
@@ -39,6 +46,12 @@ pub enum Web3Error {
     LogsLimitExceeded(usize, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
+    #[error("Unsupported tracer: {0}")]
+    UnsupportedTracer(String),
+    #[error("Simulation session not found")]
+    SimulationSessionNotFound,
+    #[error("Simulation session cannot contain more than {0} transactions")]
+    SimulationSessionFull(usize),
     /// Weaker form of a "method not found" error; the method implementation is technically present,
     /// but the node configuration prevents the method from functioning.
     #[error("Method not implemented")]
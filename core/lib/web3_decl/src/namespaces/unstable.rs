@@ -2,7 +2,12 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
-    api::{ChainAggProof, TeeProof, TransactionExecutionInfo},
+    api::{
+        ChainAggProof, ProtocolUpgradePreflight, ProtocolVersion,
+        SettlementLayerMigrationPreflight, SettlementLayerStatus, TeeProof,
+        TransactionExecutionInfo,
+    },
+    settlement::SettlementMode,
     tee_types::TeeType,
     L1BatchNumber, L2ChainId, H256,
 };
@@ -41,4 +46,28 @@ pub trait UnstableNamespace {
 
     #[method(name = "unconfirmedTxsCount")]
     async fn get_unconfirmed_txs_count(&self) -> RpcResult<usize>;
+
+    #[method(name = "getSettlementLayerStatus")]
+    async fn get_settlement_layer_status(&self) -> RpcResult<SettlementLayerStatus>;
+
+    /// Checks preconditions for switching the node to the specified settlement layer. This is a dry run;
+    /// it doesn't change the node state.
+    #[method(name = "settlementLayerMigrationPreflight")]
+    async fn settlement_layer_migration_preflight(
+        &self,
+        target_layer: SettlementMode,
+    ) -> RpcResult<SettlementLayerMigrationPreflight>;
+
+    /// Returns protocol versions seen on L1 that were not yet applied by the state keeper, ordered by ID.
+    #[method(name = "getUpcomingProtocolUpgrades")]
+    async fn get_upcoming_protocol_upgrades(&self) -> RpcResult<Vec<ProtocolVersion>>;
+
+    /// Executes the upgrade transaction for the specified protocol version on top of the current state
+    /// and checks that the node has everything needed to apply the upgrade. Returns `None` if the version
+    /// is unknown.
+    #[method(name = "protocolUpgradePreflight")]
+    async fn protocol_upgrade_preflight(
+        &self,
+        version_id: u16,
+    ) -> RpcResult<Option<ProtocolUpgradePreflight>>;
 }
@@ -5,8 +5,11 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockDetails, BridgeAddresses, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetailedResult, TransactionDetails,
+        state_override::StateOverride, AddressTransactionsFilter, AddressTransactionsPage,
+        BalanceHistoryFilter, BalanceHistoryPage, BlockDetails, BlockIdVariant, BridgeAddresses,
+        L1BatchDetails, L1BatchSettlementAudit, L2ToL1LogProof, Proof, ProtocolVersion,
+        SimulatedTransaction, SimulationSessionDetails, TokenTransfersFilter, TokenTransfersPage,
+        TransactionDetailedResult, TransactionDetails, TransactionStatusDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -101,16 +104,51 @@ pub trait ZksNamespace {
     #[method(name = "getTransactionDetails")]
     async fn get_transaction_details(&self, hash: H256) -> RpcResult<Option<TransactionDetails>>;
 
+    #[method(name = "getTransactionStatusDetails")]
+    async fn get_transaction_status_details(
+        &self,
+        hash: H256,
+    ) -> RpcResult<Option<TransactionStatusDetails>>;
+
     #[method(name = "getRawBlockTransactions")]
     async fn get_raw_block_transactions(
         &self,
         block_number: L2BlockNumber,
     ) -> RpcResult<Vec<zksync_types::Transaction>>;
 
+    #[method(name = "getTransactionsByAddress")]
+    async fn get_transactions_by_address(
+        &self,
+        address: Address,
+        filter: Option<AddressTransactionsFilter>,
+    ) -> RpcResult<AddressTransactionsPage>;
+
+    #[method(name = "getTokenTransfers")]
+    async fn get_token_transfers(
+        &self,
+        address: Address,
+        filter: Option<TokenTransfersFilter>,
+    ) -> RpcResult<TokenTransfersPage>;
+
+    #[method(name = "getBalanceHistory")]
+    async fn get_balance_history(
+        &self,
+        address: Address,
+        filter: Option<BalanceHistoryFilter>,
+    ) -> RpcResult<BalanceHistoryPage>;
+
     #[method(name = "getL1BatchDetails")]
     async fn get_l1_batch_details(&self, batch: L1BatchNumber)
         -> RpcResult<Option<L1BatchDetails>>;
 
+    /// Returns results of the settlement audit for the specified L1 batch, or `None` if the batch
+    /// wasn't audited yet (or the settlement auditor isn't running).
+    #[method(name = "getL1BatchSettlementAudit")]
+    async fn get_l1_batch_settlement_audit(
+        &self,
+        batch: L1BatchNumber,
+    ) -> RpcResult<Option<L1BatchSettlementAudit>>;
+
     #[method(name = "getBytecodeByHash")]
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>>;
 
@@ -142,4 +180,44 @@ pub trait ZksNamespace {
         &self,
         tx_bytes: Bytes,
     ) -> RpcResult<TransactionDetailedResult>;
+
+    /// Creates a simulation session forked from the specified block (by default, the latest sealed block)
+    /// and returns its ID. Sessions are local to the node they were created at.
+    #[method(name = "createSimulationSession")]
+    async fn create_simulation_session(&self, block: Option<BlockIdVariant>) -> RpcResult<U256>;
+
+    /// Executes a call in a simulation session similarly to `eth_call`. Effects of the call are visible
+    /// to subsequent transactions and state queries in the session; `state_override` only applies to this call.
+    #[method(name = "simulateTransaction")]
+    async fn simulate_transaction(
+        &self,
+        session_id: U256,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<SimulatedTransaction>;
+
+    /// Executes a signed transaction in a simulation session. Unlike `zks_simulateTransaction`, the transaction
+    /// goes through the full validation flow (including custom account and paymaster validation), and pays fees.
+    #[method(name = "simulateRawTransaction")]
+    async fn simulate_raw_transaction(
+        &self,
+        session_id: U256,
+        tx_bytes: Bytes,
+    ) -> RpcResult<SimulatedTransaction>;
+
+    #[method(name = "getSimulationSession")]
+    async fn get_simulation_session(&self, session_id: U256)
+        -> RpcResult<SimulationSessionDetails>;
+
+    #[method(name = "getSimulationStorageAt")]
+    async fn get_simulation_storage_at(
+        &self,
+        session_id: U256,
+        address: Address,
+        key: U256,
+    ) -> RpcResult<H256>;
+
+    /// Discards a simulation session. Returns `false` if the session doesn't exist.
+    #[method(name = "discardSimulationSession")]
+    async fn discard_simulation_session(&self, session_id: U256) -> RpcResult<bool>;
 }
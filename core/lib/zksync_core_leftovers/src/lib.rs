@@ -64,6 +64,8 @@ pub enum Component {
     VmPlayground,
     /// Component for filtering L2 transactions by denylist
     TxSinkDenyList,
+    /// Component auditing settlement transactions for L1 batches against local data.
+    SettlementAuditor,
 }
 
 #[derive(Debug)]
@@ -110,6 +112,7 @@ impl FromStr for Components {
                 Ok(Components(vec![Component::ExternalProofIntegrationApi]))
             }
             "deny_list" => Ok(Components(vec![Component::TxSinkDenyList])),
+            "settlement_auditor" => Ok(Components(vec![Component::SettlementAuditor])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
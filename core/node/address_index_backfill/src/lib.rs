@@ -0,0 +1,398 @@
+use std::{ops, time::Duration};
+
+use anyhow::Context;
+use tokio::sync::watch;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalResult};
+use zksync_types::{pruning::PrunedDataClass, L2BlockNumber};
+
+/// Optional per-address index maintained by the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressIndex {
+    /// Index of transactions by the initiator and recipient address.
+    Transactions,
+    /// Index of token transfers by the sender and recipient address.
+    TokenTransfers,
+}
+
+impl AddressIndex {
+    /// Returns a short name of the index used in task IDs and logs.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Transactions => "address_index",
+            Self::TokenTransfers => "token_transfer_index",
+        }
+    }
+
+    async fn earliest_indexed_l2_block(
+        self,
+        connection: &mut Connection<'_, Core>,
+    ) -> DalResult<Option<L2BlockNumber>> {
+        match self {
+            Self::Transactions => {
+                connection
+                    .address_transactions_dal()
+                    .get_earliest_indexed_l2_block()
+                    .await
+            }
+            Self::TokenTransfers => {
+                connection
+                    .token_transfers_dal()
+                    .get_earliest_indexed_l2_block()
+                    .await
+            }
+        }
+    }
+
+    async fn backfill(
+        self,
+        connection: &mut Connection<'_, Core>,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        match self {
+            Self::Transactions => {
+                connection
+                    .address_transactions_dal()
+                    .backfill_address_transactions(l2_blocks)
+                    .await
+            }
+            Self::TokenTransfers => {
+                connection
+                    .token_transfers_dal()
+                    .backfill_token_transfers(l2_blocks)
+                    .await
+            }
+        }
+    }
+}
+
+/// Backfills an [`AddressIndex`] for L2 blocks sealed before the index was enabled.
+///
+/// The state keeper indexes all L2 blocks sealed after the index is enabled, so this task only needs to process
+/// L2 blocks preceding the earliest indexed one. Blocks are processed in windows from newer to older ones;
+/// each window is indexed atomically, so the task can be safely interrupted and restarted.
+#[derive(Debug)]
+pub struct AddressIndexBackfill {
+    connection_pool: ConnectionPool<Core>,
+    index: AddressIndex,
+}
+
+#[derive(Debug, PartialEq)]
+enum IndexWaitOutcome {
+    Ok(L2BlockNumber),
+    Canceled,
+}
+
+impl AddressIndexBackfill {
+    pub fn new(connection_pool: ConnectionPool<Core>, index: AddressIndex) -> Self {
+        Self {
+            connection_pool,
+            index,
+        }
+    }
+
+    pub fn index(&self) -> AddressIndex {
+        self.index
+    }
+
+    async fn wait_for_indexed_l2_block(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> anyhow::Result<IndexWaitOutcome> {
+        const INTERVAL: Duration = Duration::from_secs(1);
+        tracing::debug!(
+            "waiting for at least one L2 block in DB indexed in {}",
+            self.index.name()
+        );
+
+        loop {
+            if *stop_receiver.borrow() {
+                return Ok(IndexWaitOutcome::Canceled);
+            }
+
+            if let Some(number) = self.index.earliest_indexed_l2_block(connection).await? {
+                return Ok(IndexWaitOutcome::Ok(number));
+            }
+
+            // We don't check the result: if a stop signal is received, we'll return at the start
+            // of the next iteration.
+            tokio::time::timeout(INTERVAL, stop_receiver.changed())
+                .await
+                .ok();
+        }
+    }
+
+    /// Returns the first L2 block with retained transactions, or `None` if there are no L2 blocks in the DB.
+    /// Both indices are derived from data pruned together with transactions (transactions and events, respectively).
+    async fn first_l2_block_with_transactions(
+        connection: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<L2BlockNumber>> {
+        let Some(earliest_l2_block) = connection
+            .blocks_dal()
+            .get_earliest_l2_block_number()
+            .await?
+        else {
+            return Ok(None);
+        };
+        let pruned_data_info = connection.pruning_dal().get_pruned_data_info().await?;
+        let first_retained_l2_block = pruned_data_info
+            .last_soft_pruned(PrunedDataClass::Transactions)
+            .map_or(L2BlockNumber(0), |info| info.l2_block + 1);
+        Ok(Some(earliest_l2_block.max(first_retained_l2_block)))
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let index_name = self.index.name();
+        let mut connection = self
+            .connection_pool
+            .connection_tagged("address_index_backfill")
+            .await?;
+
+        let earliest_indexed_l2_block = match self
+            .wait_for_indexed_l2_block(&mut connection, &mut stop_receiver)
+            .await?
+        {
+            IndexWaitOutcome::Ok(number) => number,
+            IndexWaitOutcome::Canceled => return Ok(()), // Stop signal received
+        };
+        let first_l2_block = Self::first_l2_block_with_transactions(&mut connection)
+            .await?
+            .with_context(|| {
+                format!("{index_name} backfill: missing L2 blocks in DB after waiting for an indexed one")
+            })?;
+        if earliest_indexed_l2_block <= first_l2_block {
+            tracing::info!("{index_name} is already populated, exiting backfill");
+            return Ok(());
+        }
+
+        tracing::info!(
+            "starting {index_name} backfill for L2 blocks {first_l2_block}..{earliest_indexed_l2_block}"
+        );
+        let mut right_bound = earliest_indexed_l2_block.0 - 1;
+        loop {
+            const WINDOW: u32 = 1000;
+
+            if *stop_receiver.borrow_and_update() {
+                tracing::info!("received a stop signal; {index_name} backfill is shut down");
+                return Ok(());
+            }
+
+            let left_bound = right_bound.saturating_sub(WINDOW - 1).max(first_l2_block.0);
+            let indexed_count = self
+                .index
+                .backfill(
+                    &mut connection,
+                    L2BlockNumber(left_bound)..=L2BlockNumber(right_bound),
+                )
+                .await?;
+            tracing::info!(
+                "inserted {indexed_count} {index_name} entries for L2 block range {left_bound}..={right_bound}"
+            );
+
+            if left_bound == first_l2_block.0 {
+                break;
+            } else {
+                right_bound = left_bound - 1;
+            }
+        }
+
+        tracing::info!("{index_name} backfill is finished");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_node_test_utils::{create_l2_block, create_l2_transaction, execute_l2_transaction};
+    use zksync_system_constants::L2_BASE_TOKEN_ADDRESS;
+    use zksync_types::{
+        address_to_h256, api::AddressTransactionDirection, ethabi, l2::L2Tx,
+        tx::IncludedTxLocation, Address, L1BatchNumber, ProtocolVersionId, H256, U256,
+    };
+    use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionResult, VmEvent};
+
+    use super::*;
+
+    async fn create_l2_block_with_tx(
+        conn: &mut Connection<'_, Core>,
+        l2_block_number: u32,
+    ) -> (L2Tx, TransactionExecutionResult) {
+        let tx = create_l2_transaction(10, 100);
+        conn.transactions_dal()
+            .insert_transaction_l2(&tx, Default::default(), ValidationTraces::default())
+            .await
+            .unwrap();
+        let mut l2_block_header = create_l2_block(l2_block_number);
+        l2_block_header.l2_tx_count = 1;
+        conn.blocks_dal()
+            .insert_l2_block(&l2_block_header)
+            .await
+            .unwrap();
+
+        let tx_result = execute_l2_transaction(tx.clone());
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(l2_block_number),
+                &[tx_result.clone()],
+                U256::one(),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+        (tx, tx_result)
+    }
+
+    #[tokio::test]
+    async fn test_address_index_backfill() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut connection = connection_pool.connection().await.unwrap();
+        connection
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&Default::default())
+            .await
+            .unwrap();
+        connection
+            .blocks_dal()
+            .insert_l2_block(&create_l2_block(0))
+            .await
+            .unwrap();
+
+        let blocks_count = 5_u32;
+        let mut txs = vec![];
+        for block_number in 1..=blocks_count {
+            txs.push(create_l2_block_with_tx(&mut connection, block_number).await);
+        }
+        // Emulate the state keeper indexing the last L2 block.
+        let (_, last_tx_result) = txs.last().unwrap();
+        connection
+            .address_transactions_dal()
+            .insert_address_transactions(
+                L2BlockNumber(blocks_count),
+                std::slice::from_ref(last_tx_result),
+            )
+            .await
+            .unwrap();
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        AddressIndexBackfill::new(connection_pool.clone(), AddressIndex::Transactions)
+            .run(stop_receiver)
+            .await
+            .unwrap();
+
+        let earliest_indexed_l2_block = connection
+            .address_transactions_dal()
+            .get_earliest_indexed_l2_block()
+            .await
+            .unwrap();
+        assert_eq!(earliest_indexed_l2_block, Some(L2BlockNumber(1)));
+        for (i, (tx, _)) in txs.iter().enumerate() {
+            let sent_txs = connection
+                .address_transactions_dal()
+                .get_address_transactions(
+                    tx.initiator_account(),
+                    AddressTransactionDirection::Sent,
+                    L2BlockNumber(0)..=L2BlockNumber(blocks_count),
+                    None,
+                    10,
+                )
+                .await
+                .unwrap();
+            assert_eq!(sent_txs.len(), 1);
+            assert_eq!(sent_txs[0].tx_hash, tx.hash());
+            assert_eq!(sent_txs[0].l2_block_number, L2BlockNumber(i as u32 + 1));
+        }
+    }
+
+    fn transfer_event(from: Address, to: Address) -> VmEvent {
+        let signature = ethabi::long_signature(
+            "Transfer",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+            ],
+        );
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: L2_BASE_TOKEN_ADDRESS,
+            indexed_topics: vec![signature, address_to_h256(&from), address_to_h256(&to)],
+            value: H256::from_low_u64_be(1).0.to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_transfer_index_backfill() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut connection = connection_pool.connection().await.unwrap();
+        connection
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&Default::default())
+            .await
+            .unwrap();
+        connection
+            .blocks_dal()
+            .insert_l2_block(&create_l2_block(0))
+            .await
+            .unwrap();
+
+        let blocks_count = 5_u32;
+        let recipient = Address::repeat_byte(0x23);
+        let mut block_events = vec![];
+        for block_number in 1..=blocks_count {
+            let (tx, _) = create_l2_block_with_tx(&mut connection, block_number).await;
+            let location = IncludedTxLocation {
+                tx_hash: tx.hash(),
+                tx_index_in_l2_block: 0,
+                tx_initiator_address: tx.initiator_account(),
+            };
+            let event = transfer_event(tx.initiator_account(), recipient);
+            connection
+                .events_dal()
+                .save_events(L2BlockNumber(block_number), &[(location, vec![&event])])
+                .await
+                .unwrap();
+            block_events.push((location, event));
+        }
+        // Emulate the state keeper indexing the last L2 block.
+        let (location, event) = block_events.last().unwrap();
+        connection
+            .token_transfers_dal()
+            .insert_token_transfers(L2BlockNumber(blocks_count), &[(*location, vec![event])])
+            .await
+            .unwrap();
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        AddressIndexBackfill::new(connection_pool.clone(), AddressIndex::TokenTransfers)
+            .run(stop_receiver)
+            .await
+            .unwrap();
+
+        let earliest_indexed_l2_block = connection
+            .token_transfers_dal()
+            .get_earliest_indexed_l2_block()
+            .await
+            .unwrap();
+        assert_eq!(earliest_indexed_l2_block, Some(L2BlockNumber(1)));
+        let transfers = connection
+            .token_transfers_dal()
+            .get_token_transfers(
+                recipient,
+                Some(L2_BASE_TOKEN_ADDRESS),
+                AddressTransactionDirection::Received,
+                L2BlockNumber(0)..=L2BlockNumber(blocks_count),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        let transfer_blocks: Vec<_> = transfers
+            .iter()
+            .map(|transfer| transfer.block_number.0)
+            .collect();
+        assert_eq!(transfer_blocks, [5, 4, 3, 2, 1]);
+        for (transfer, (location, _)) in transfers.iter().zip(block_events.iter().rev()) {
+            assert_eq!(transfer.transaction_hash, location.tx_hash);
+        }
+    }
+}
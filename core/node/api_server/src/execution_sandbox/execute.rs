@@ -5,17 +5,19 @@ use std::time::{Duration, Instant};
 use anyhow::Context as _;
 use async_trait::async_trait;
 use tokio::runtime::Handle;
+use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{Connection, Core};
 use zksync_multivm::interface::{
     executor::{OneshotExecutor, TransactionValidator},
-    storage::{ReadStorage, StorageWithOverrides},
+    storage::{ReadStorage, StorageDiff, StorageWithOverrides},
     tracer::{TimestampAsserterParams, ValidationError, ValidationParams, ValidationTraces},
-    Call, OneshotEnv, OneshotTracingParams, OneshotTransactionExecutionResult,
+    Call, GasProfile, OneshotEnv, OneshotTracingParams, OneshotTransactionExecutionResult,
     TransactionExecutionMetrics, TxExecutionArgs, VmExecutionResultAndLogs,
 };
 use zksync_state::{PostgresStorage, PostgresStorageCaches};
 use zksync_types::{
-    api::state_override::StateOverride, fee_model::BatchFeeInput, l2::L2Tx, Transaction,
+    api::state_override::StateOverride, fee_model::BatchFeeInput, l2::L2Tx,
+    protocol_upgrade::ProtocolUpgradeTx, ProtocolVersionId, Transaction,
 };
 use zksync_vm_executor::oneshot::{MainOneshotExecutor, MockOneshotExecutor};
 
@@ -28,8 +30,12 @@ use crate::{execution_sandbox::storage::apply_state_override, tx_sender::Sandbox
 /// Action that can be executed by [`SandboxExecutor`].
 #[derive(Debug)]
 pub(crate) enum SandboxAction {
-    /// Execute a transaction.
-    Execution { tx: L2Tx, fee_input: BatchFeeInput },
+    /// Execute a transaction, possibly with tracing.
+    Execution {
+        tx: L2Tx,
+        fee_input: BatchFeeInput,
+        tracing_params: OneshotTracingParams,
+    },
     /// Execute a call, possibly with tracing.
     Call {
         call: L2Tx,
@@ -43,6 +49,14 @@ pub(crate) enum SandboxAction {
         fee_input: BatchFeeInput,
         base_fee: u64,
     },
+    /// Execute a protocol upgrade transaction first in a new L1 batch using base system contracts
+    /// of the upgraded protocol version, similarly to the state keeper.
+    ProtocolUpgrade {
+        tx: ProtocolUpgradeTx,
+        fee_input: BatchFeeInput,
+        protocol_version: ProtocolVersionId,
+        base_system_contracts: BaseSystemContracts,
+    },
 }
 
 impl SandboxAction {
@@ -52,19 +66,23 @@ impl SandboxAction {
                 tx.execute.factory_deps.len()
             }
             Self::GasEstimation { tx, .. } => tx.execute.factory_deps.len(),
+            Self::ProtocolUpgrade { tx, .. } => tx.execute.factory_deps.len(),
         }
     }
 
     fn into_parts(self) -> (TxExecutionArgs, OneshotTracingParams) {
         match self {
-            Self::Execution { tx, .. } => (
-                TxExecutionArgs::for_validation(tx),
-                OneshotTracingParams::default(),
-            ),
+            Self::Execution {
+                tx, tracing_params, ..
+            } => (TxExecutionArgs::for_validation(tx), tracing_params),
             Self::GasEstimation { tx, .. } => (
                 TxExecutionArgs::for_gas_estimate(tx),
                 OneshotTracingParams::default(),
             ),
+            Self::ProtocolUpgrade { tx, .. } => (
+                TxExecutionArgs::for_protocol_upgrade(tx),
+                OneshotTracingParams::default(),
+            ),
             Self::Call {
                 call,
                 tracing_params,
@@ -81,6 +99,8 @@ pub(crate) struct SandboxExecutionOutput {
     pub vm: VmExecutionResultAndLogs,
     /// Traced calls if requested.
     pub call_traces: Vec<Call>,
+    /// Gas profile if requested.
+    pub gas_profile: GasProfile,
     /// Execution metrics.
     pub metrics: TransactionExecutionMetrics,
     /// Were published bytecodes OK?
@@ -150,12 +170,40 @@ impl SandboxExecutor {
         action: SandboxAction,
         block_args: &BlockArgs,
         state_override: Option<StateOverride>,
+    ) -> anyhow::Result<SandboxExecutionOutput> {
+        self.execute_with_storage_diff(
+            vm_permit,
+            connection,
+            action,
+            block_args,
+            None,
+            state_override,
+        )
+        .await
+    }
+
+    /// Same as [`Self::execute_in_sandbox()`], but executes on top of `storage_diff` accumulated
+    /// by previously executed transactions (e.g., in a simulation session). `state_override` is applied
+    /// on top of the diff.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn execute_with_storage_diff(
+        &self,
+        vm_permit: VmPermit,
+        connection: Connection<'static, Core>,
+        action: SandboxAction,
+        block_args: &BlockArgs,
+        storage_diff: Option<&StorageDiff>,
+        state_override: Option<StateOverride>,
     ) -> anyhow::Result<SandboxExecutionOutput> {
         let total_factory_deps = action.factory_deps_count() as u16;
         let (env, storage) = self
             .prepare_env_and_storage(connection, block_args, &action)
             .await?;
 
+        let mut storage = StorageWithOverrides::new(storage);
+        if let Some(diff) = storage_diff {
+            storage.apply_diff(diff);
+        }
         let state_override = state_override.unwrap_or_default();
         let storage = apply_state_override(storage, &state_override);
         let (execution_args, tracing_params) = action.into_parts();
@@ -174,6 +222,7 @@ impl SandboxExecutor {
         Ok(SandboxExecutionOutput {
             vm: *result.tx_result,
             call_traces: result.call_traces,
+            gas_profile: result.gas_profile,
             metrics,
             are_published_bytecodes_ok: result.compression_result.is_ok(),
         })
@@ -195,7 +244,7 @@ impl SandboxExecutor {
         }
 
         let env = match action {
-            SandboxAction::Execution { fee_input, tx } => {
+            SandboxAction::Execution { fee_input, tx, .. } => {
                 self.options
                     .eth_call
                     .to_execute_env(&mut connection, resolved_block_info, *fee_input, tx)
@@ -226,6 +275,23 @@ impl SandboxExecutor {
                     .to_env(&mut connection, resolved_block_info, fee_input, base_fee)
                     .await?
             }
+            SandboxAction::ProtocolUpgrade {
+                fee_input,
+                protocol_version,
+                base_system_contracts,
+                ..
+            } => {
+                self.options
+                    .eth_call
+                    .to_protocol_upgrade_env(
+                        &mut connection,
+                        resolved_block_info,
+                        *fee_input,
+                        *protocol_version,
+                        base_system_contracts.clone(),
+                    )
+                    .await?
+            }
         };
 
         if block_args.resolves_to_latest_sealed_l2_block() {
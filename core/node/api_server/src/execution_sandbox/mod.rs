@@ -5,17 +5,18 @@ use std::{
 
 use anyhow::Context as _;
 use rand::{thread_rng, Rng};
-use zksync_dal::{pruning_dal::PruningInfo, Connection, Core, CoreDal, DalError};
+use zksync_dal::{pruning_dal::PrunedDataInfo, Connection, Core, CoreDal, DalError};
 use zksync_multivm::utils::get_eth_call_gas_limit;
 use zksync_types::{
-    api, fee_model::BatchFeeInput, L1BatchNumber, L2BlockNumber, ProtocolVersionId, U256,
+    api, fee_model::BatchFeeInput, pruning::PrunedDataClass, L1BatchNumber, L2BlockNumber,
+    ProtocolVersionId, U256,
 };
 use zksync_vm_executor::oneshot::{BlockInfo, ResolvedBlockInfo};
 
 use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{SandboxAction, SandboxExecutor},
+    execute::{SandboxAction, SandboxExecutionOutput, SandboxExecutor},
     validate::ValidationError,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
 };
@@ -134,9 +135,9 @@ impl VmConcurrencyLimiter {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct BlockStartInfoInner {
-    info: PruningInfo,
+    info: PrunedDataInfo,
     cached_at: Instant,
 }
 
@@ -169,7 +170,7 @@ impl BlockStartInfo {
         storage: &mut Connection<'_, Core>,
         max_cache_age: Duration,
     ) -> anyhow::Result<Self> {
-        let info = storage.pruning_dal().get_pruning_info().await?;
+        let info = storage.pruning_dal().get_pruned_data_info().await?;
         Ok(Self {
             cached_pruning_info: Arc::new(RwLock::new(BlockStartInfoInner {
                 info,
@@ -179,19 +180,19 @@ impl BlockStartInfo {
         })
     }
 
-    fn copy_inner(&self) -> BlockStartInfoInner {
-        *self
-            .cached_pruning_info
+    fn clone_inner(&self) -> BlockStartInfoInner {
+        self.cached_pruning_info
             .read()
             .expect("BlockStartInfo is poisoned")
+            .clone()
     }
 
     async fn update_cache(
         &self,
         storage: &mut Connection<'_, Core>,
         now: Instant,
-    ) -> anyhow::Result<PruningInfo> {
-        let info = storage.pruning_dal().get_pruning_info().await?;
+    ) -> anyhow::Result<PrunedDataInfo> {
+        let info = storage.pruning_dal().get_pruned_data_info().await?;
 
         let mut new_cached_pruning_info = self
             .cached_pruning_info
@@ -199,21 +200,21 @@ impl BlockStartInfo {
             .map_err(|_| anyhow::anyhow!("BlockStartInfo is poisoned"))?;
         Ok(if new_cached_pruning_info.cached_at < now {
             *new_cached_pruning_info = BlockStartInfoInner {
-                info,
+                info: info.clone(),
                 cached_at: now,
             };
             info
         } else {
             // Got a newer cache already; no need to update it again.
-            new_cached_pruning_info.info
+            new_cached_pruning_info.info.clone()
         })
     }
 
     async fn get_pruning_info(
         &self,
         storage: &mut Connection<'_, Core>,
-    ) -> anyhow::Result<PruningInfo> {
-        let inner = self.copy_inner();
+    ) -> anyhow::Result<PrunedDataInfo> {
+        let inner = self.clone_inner();
         let now = Instant::now();
         if inner.is_expired(now, self.max_cache_age) {
             // Multiple threads may execute this query if we're very unlucky
@@ -223,12 +224,24 @@ impl BlockStartInfo {
         }
     }
 
+    /// Returns the first L2 block that has a retained header.
     pub async fn first_l2_block(
         &self,
         storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L2BlockNumber> {
+        self.first_l2_block_with(PrunedDataClass::Blocks, storage)
+            .await
+    }
+
+    /// Returns the first L2 block for which data of the specified class is retained. Depending on the pruning profile,
+    /// this may be greater than [`Self::first_l2_block()`].
+    pub async fn first_l2_block_with(
+        &self,
+        class: PrunedDataClass,
+        storage: &mut Connection<'_, Core>,
     ) -> anyhow::Result<L2BlockNumber> {
         let cached_pruning_info = self.get_pruning_info(storage).await?;
-        if let Some(pruned) = cached_pruning_info.last_soft_pruned {
+        if let Some(pruned) = cached_pruning_info.last_soft_pruned(class) {
             return Ok(pruned.l2_block + 1);
         }
         Ok(L2BlockNumber(0))
@@ -239,7 +252,7 @@ impl BlockStartInfo {
         storage: &mut Connection<'_, Core>,
     ) -> anyhow::Result<L1BatchNumber> {
         let cached_pruning_info = self.get_pruning_info(storage).await?;
-        if let Some(pruned) = cached_pruning_info.last_soft_pruned {
+        if let Some(pruned) = cached_pruning_info.last_soft_pruned(PrunedDataClass::Blocks) {
             return Ok(pruned.l1_batch + 1);
         }
         Ok(L1BatchNumber(0))
@@ -347,6 +360,11 @@ impl BlockArgs {
         self.inner.block_number()
     }
 
+    /// Returns the number of the L2 block whose state is used for execution.
+    pub fn state_l2_block_number(&self) -> L2BlockNumber {
+        self.resolved.state_l2_block_number()
+    }
+
     fn is_pending(&self) -> bool {
         matches!(
             self.block_id,
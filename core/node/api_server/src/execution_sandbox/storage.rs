@@ -8,12 +8,11 @@ use zksync_types::{
     AccountTreeId, StorageKey, H256,
 };
 
-/// This method is blocking.
+/// Applies `state_override` on top of the provided storage. This method is blocking.
 pub(super) fn apply_state_override<S: ReadStorage>(
-    storage: S,
+    mut storage: StorageWithOverrides<S>,
     state_override: &StateOverride,
 ) -> StorageWithOverrides<S> {
-    let mut storage = StorageWithOverrides::new(storage);
     for (account, overrides) in state_override.iter() {
         if let Some(balance) = overrides.balance {
             let balance_key = storage_key_for_eth_balance(account);
@@ -123,7 +122,7 @@ mod tests {
         storage.set_value(retained_key, H256::repeat_byte(0xfe));
         let erased_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(5)), H256::zero());
         storage.set_value(erased_key, H256::repeat_byte(1));
-        let mut storage = apply_state_override(storage, &overrides);
+        let mut storage = apply_state_override(StorageWithOverrides::new(storage), &overrides);
 
         let balance = storage.read_value(&storage_key_for_eth_balance(&Address::repeat_byte(1)));
         assert_eq!(balance, H256::from_low_u64_be(1));
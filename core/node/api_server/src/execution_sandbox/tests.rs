@@ -4,17 +4,29 @@ use std::collections::HashMap;
 
 use assert_matches::assert_matches;
 use test_casing::test_casing;
+use zksync_contracts::deployer_contract;
 use zksync_dal::ConnectionPool;
-use zksync_multivm::{interface::ExecutionResult, utils::derive_base_fee_and_gas_per_pubdata};
+use zksync_multivm::{
+    interface::{storage::StorageDiff, ExecutionResult, OneshotTracingParams},
+    utils::derive_base_fee_and_gas_per_pubdata,
+};
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l1_batch, create_l2_block, prepare_recovery_snapshot};
 use zksync_state::PostgresStorageCaches;
 use zksync_types::{
     api::state_override::{OverrideAccount, StateOverride},
+    ethabi::Token,
     fee::Fee,
     fee_model::BatchFeeInput,
-    K256PrivateKey, ProtocolVersionId, Transaction, U256,
+    get_code_key, get_nonce_key, h256_to_u256,
+    protocol_upgrade::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData},
+    pruning::PruningProfile,
+    u256_to_h256,
+    utils::storage_key_for_eth_balance,
+    Address, Execute, K256PrivateKey, ProtocolVersionId, Transaction, CONTRACT_DEPLOYER_ADDRESS,
+    CONTRACT_FORCE_DEPLOYER_ADDRESS, H256, REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE, U256,
 };
+use zksync_vm_executor::storage::get_base_system_contracts_by_version_id;
 
 use super::*;
 use crate::{
@@ -191,6 +203,54 @@ async fn creating_block_args_after_snapshot_recovery() {
     }
 }
 
+#[tokio::test]
+async fn block_start_info_with_selective_pruning() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    storage
+        .pruning_dal()
+        .insert_soft_pruning_log(
+            L1BatchNumber(3),
+            L2BlockNumber(5),
+            PruningProfile::KeepTransactions,
+        )
+        .await
+        .unwrap();
+
+    let start_info = BlockStartInfo::new(&mut storage, Duration::MAX)
+        .await
+        .unwrap();
+    assert_eq!(
+        start_info.first_l2_block(&mut storage).await.unwrap(),
+        L2BlockNumber(0)
+    );
+    assert_eq!(
+        start_info.first_l1_batch(&mut storage).await.unwrap(),
+        L1BatchNumber(0)
+    );
+    for (class, expected_first_block) in [
+        (PrunedDataClass::Blocks, 0),
+        (PrunedDataClass::Transactions, 0),
+        (PrunedDataClass::CallTraces, 6),
+        (PrunedDataClass::StateHistory, 6),
+    ] {
+        let first_block = start_info
+            .first_l2_block_with(class, &mut storage)
+            .await
+            .unwrap();
+        assert_eq!(first_block, L2BlockNumber(expected_first_block), "{class}");
+    }
+
+    // Retained blocks must still be accessible.
+    let earliest_block = api::BlockId::Number(api::BlockNumber::Earliest);
+    BlockArgs::new(&mut storage, earliest_block, &start_info)
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn estimating_gas() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -298,7 +358,11 @@ async fn validating_transaction(set_balance: bool) {
         .execute_in_sandbox(
             vm_permit,
             connection,
-            SandboxAction::Execution { tx, fee_input },
+            SandboxAction::Execution {
+                tx,
+                fee_input,
+                tracing_params: OneshotTracingParams::default(),
+            },
             &block_args,
             Some(state_override),
         )
@@ -312,3 +376,174 @@ async fn validating_transaction(set_balance: bool) {
         assert_matches!(result, ExecutionResult::Halt { .. });
     }
 }
+
+#[tokio::test]
+async fn executing_transactions_with_storage_diff() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut connection = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut connection, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let block_args = BlockArgs::pending(&mut connection).await.unwrap();
+    drop(connection);
+
+    let executor = SandboxExecutor::real(
+        SandboxExecutorOptions::mock().await,
+        PostgresStorageCaches::new(1, 1),
+        usize::MAX,
+        None,
+    );
+    let fee_input = BatchFeeInput::l1_pegged(55, 555);
+    let (base_fee, gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+    let fee = Fee {
+        gas_limit: 200_000.into(),
+        max_fee_per_gas: base_fee.into(),
+        max_priority_fee_per_gas: 0.into(),
+        gas_per_pubdata_limit: gas_per_pubdata.into(),
+    };
+    let alice = K256PrivateKey::random();
+    let initiator = alice.address();
+    let initial_balance = U256::from(1) << 128;
+    let balance_key = storage_key_for_eth_balance(&initiator);
+    let mut diff = StorageDiff::default();
+    diff.set_value(balance_key, u256_to_h256(initial_balance));
+
+    let (limiter, _) = VmConcurrencyLimiter::new(1);
+    let tx = alice.create_transfer_with_fee(0.into(), fee.clone());
+    let output = executor
+        .execute_with_storage_diff(
+            limiter.acquire().await.unwrap(),
+            pool.connection().await.unwrap(),
+            SandboxAction::Execution {
+                tx,
+                fee_input,
+                tracing_params: OneshotTracingParams::default(),
+            },
+            &block_args,
+            Some(&diff),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_matches!(output.vm.result, ExecutionResult::Success { .. });
+    diff.merge_execution(&output.vm, &[]);
+
+    let nonce = diff.read_value(&get_nonce_key(&initiator)).unwrap();
+    assert_eq!(h256_to_u256(nonce), 1.into());
+    let balance = h256_to_u256(diff.read_value(&balance_key).unwrap());
+    assert!(balance < initial_balance, "{balance}");
+
+    // The nonce is persisted in the diff, so a transaction with the same nonce must be rejected.
+    let tx = alice.create_transfer_with_fee(0.into(), fee);
+    let output = executor
+        .execute_with_storage_diff(
+            limiter.acquire().await.unwrap(),
+            pool.connection().await.unwrap(),
+            SandboxAction::Execution {
+                tx,
+                fee_input,
+                tracing_params: OneshotTracingParams::default(),
+            },
+            &block_args,
+            Some(&diff),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_matches!(output.vm.result, ExecutionResult::Halt { .. });
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn executing_protocol_upgrade_tx(from_force_deployer: bool) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut connection = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut connection, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let block_args = BlockArgs::pending(&mut connection).await.unwrap();
+    let protocol_version = ProtocolVersionId::latest();
+    let base_system_contracts =
+        get_base_system_contracts_by_version_id(&mut connection, protocol_version)
+            .await
+            .unwrap()
+            .expect("no base system contracts");
+
+    // Force-deploy the default account bytecode (which is known after genesis) to a new address.
+    let bytecode_hash = base_system_contracts.default_aa.hash;
+    let deployed_address = Address::repeat_byte(0x23);
+    let calldata = deployer_contract()
+        .function("forceDeployOnAddresses")
+        .unwrap()
+        .encode_input(&[Token::Array(vec![Token::Tuple(vec![
+            Token::FixedBytes(bytecode_hash.as_bytes().to_vec()),
+            Token::Address(deployed_address),
+            Token::Bool(false),
+            Token::Uint(U256::zero()),
+            Token::Bytes(vec![]),
+        ])])])
+        .unwrap();
+    // Force deployments are only allowed from the force deployer address.
+    let sender = if from_force_deployer {
+        CONTRACT_FORCE_DEPLOYER_ADDRESS
+    } else {
+        Address::repeat_byte(1)
+    };
+    let tx = ProtocolUpgradeTx {
+        execute: Execute {
+            contract_address: Some(CONTRACT_DEPLOYER_ADDRESS),
+            calldata,
+            factory_deps: vec![],
+            value: U256::zero(),
+        },
+        common_data: ProtocolUpgradeTxCommonData {
+            upgrade_id: protocol_version,
+            sender,
+            gas_limit: 200_000_000.into(),
+            gas_per_pubdata_limit: REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE.into(),
+            canonical_tx_hash: H256::repeat_byte(0x42),
+            ..ProtocolUpgradeTxCommonData::default()
+        },
+        received_timestamp_ms: 0,
+    };
+
+    let executor = SandboxExecutor::real(
+        SandboxExecutorOptions::mock().await,
+        PostgresStorageCaches::new(1, 1),
+        usize::MAX,
+        None,
+    );
+    let (limiter, _) = VmConcurrencyLimiter::new(1);
+    let action = SandboxAction::ProtocolUpgrade {
+        tx,
+        fee_input: BatchFeeInput::l1_pegged(55, 555),
+        protocol_version,
+        base_system_contracts,
+    };
+    let output = executor
+        .execute_in_sandbox(
+            limiter.acquire().await.unwrap(),
+            connection,
+            action,
+            &block_args,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let tx_result = output.vm;
+    if from_force_deployer {
+        assert_matches!(tx_result.result, ExecutionResult::Success { .. });
+        let code_key = get_code_key(&deployed_address);
+        let code_write = tx_result
+            .logs
+            .storage_logs
+            .iter()
+            .find(|log| log.log.is_write() && log.log.key == code_key)
+            .expect("no code write");
+        assert_eq!(code_write.log.value, bytecode_hash);
+    } else {
+        assert_matches!(tx_result.result, ExecutionResult::Revert { .. });
+    }
+}
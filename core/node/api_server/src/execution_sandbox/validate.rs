@@ -10,6 +10,7 @@ use zksync_multivm::interface::{
         TimestampAsserterParams, ValidationError as RawValidationError, ValidationParams,
         ValidationTraces,
     },
+    OneshotTracingParams,
 };
 use zksync_types::{
     fee_model::BatchFeeInput, l2::L2Tx, Address, TRUSTED_ADDRESS_SLOTS, TRUSTED_TOKEN_SLOTS,
@@ -53,7 +54,11 @@ impl SandboxExecutor {
         .await
         .context("failed getting validation params")?;
 
-        let action = SandboxAction::Execution { fee_input, tx };
+        let action = SandboxAction::Execution {
+            fee_input,
+            tx,
+            tracing_params: OneshotTracingParams::default(),
+        };
         let (env, storage) = self
             .prepare_env_and_storage(connection, &block_args, &action)
             .await?;
@@ -5,6 +5,7 @@ use std::{collections::HashSet, sync::Arc, time::Duration};
 use anyhow::Context as _;
 use tokio::sync::RwLock;
 use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig, TxSinkConfig};
+use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{
     transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, Core, CoreDal,
 };
@@ -28,6 +29,7 @@ use zksync_types::{
     fee_model::BatchFeeInput,
     get_intrinsic_constants, h256_to_u256,
     l2::{error::TxCheckError::TxDuplication, L2Tx},
+    protocol_upgrade::ProtocolUpgradeTx,
     transaction_request::CallOverrides,
     utils::storage_key_for_eth_balance,
     vm::FastVmMode,
@@ -368,6 +370,7 @@ impl TxSender {
         let action = SandboxAction::Execution {
             fee_input,
             tx: tx.clone(),
+            tracing_params: OneshotTracingParams::default(),
         };
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
         let connection = self.acquire_replica_connection().await?;
@@ -665,6 +668,40 @@ impl TxSender {
         result.vm.into_api_call_result()
     }
 
+    /// Executes a protocol upgrade transaction on top of the pending block using base system contracts
+    /// of the upgraded `protocol_version`, similarly to how the state keeper executes it in the first upgraded L1 batch.
+    pub(crate) async fn execute_protocol_upgrade_tx(
+        &self,
+        block_args: BlockArgs,
+        tx: ProtocolUpgradeTx,
+        protocol_version: ProtocolVersionId,
+        base_system_contracts: BaseSystemContracts,
+    ) -> Result<VmExecutionResultAndLogs, SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let fee_input = self
+            .0
+            .batch_fee_input_provider
+            .get_batch_fee_input()
+            .await?;
+        // It is important to acquire a connection after calling the provider; see the comment in `eth_call()`.
+        let connection = self.acquire_replica_connection().await?;
+
+        let action = SandboxAction::ProtocolUpgrade {
+            tx,
+            fee_input,
+            protocol_version,
+            base_system_contracts,
+        };
+        let result = self
+            .0
+            .executor
+            .execute_in_sandbox(vm_permit, connection, action, &block_args, None)
+            .await?;
+        Ok(result.vm)
+    }
+
     pub async fn gas_price(&self) -> anyhow::Result<u64> {
         let mut connection = self.acquire_replica_connection().await?;
         let protocol_version = connection
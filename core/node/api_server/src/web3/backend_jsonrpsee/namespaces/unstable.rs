@@ -1,5 +1,10 @@
 use zksync_types::{
-    api::{ChainAggProof, TeeProof, TransactionExecutionInfo},
+    api::{
+        ChainAggProof, ProtocolUpgradePreflight, ProtocolVersion,
+        SettlementLayerMigrationPreflight, SettlementLayerStatus, TeeProof,
+        TransactionExecutionInfo,
+    },
+    settlement::SettlementMode,
     tee_types::TeeType,
     L1BatchNumber, L2ChainId, H256,
 };
@@ -46,4 +51,34 @@ impl UnstableNamespaceServer for UnstableNamespace {
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn get_settlement_layer_status(&self) -> RpcResult<SettlementLayerStatus> {
+        self.get_settlement_layer_status_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn settlement_layer_migration_preflight(
+        &self,
+        target_layer: SettlementMode,
+    ) -> RpcResult<SettlementLayerMigrationPreflight> {
+        self.settlement_layer_migration_preflight_impl(target_layer)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_upcoming_protocol_upgrades(&self) -> RpcResult<Vec<ProtocolVersion>> {
+        self.get_upcoming_protocol_upgrades_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn protocol_upgrade_preflight(
+        &self,
+        version_id: u16,
+    ) -> RpcResult<Option<ProtocolUpgradePreflight>> {
+        self.protocol_upgrade_preflight_impl(version_id)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
@@ -3,9 +3,12 @@ use std::collections::HashMap;
 use zksync_multivm::interface::VmEvent;
 use zksync_types::{
     api::{
-        state_override::StateOverride, ApiStorageLog, BlockDetails, BridgeAddresses,
-        L1BatchDetails, L2ToL1LogProof, Log, Proof, ProtocolVersion, TransactionDetailedResult,
-        TransactionDetails,
+        state_override::StateOverride, AddressTransactionsFilter, AddressTransactionsPage,
+        ApiStorageLog, BalanceHistoryFilter, BalanceHistoryPage, BlockDetails, BlockIdVariant,
+        BridgeAddresses, L1BatchDetails, L1BatchSettlementAudit, L2ToL1LogProof, Log, Proof,
+        ProtocolVersion, SimulatedTransaction, SimulationSessionDetails, TokenTransfersFilter,
+        TokenTransfersPage, TransactionDetailedResult, TransactionDetails,
+        TransactionStatusDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -130,6 +133,15 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_transaction_status_details(
+        &self,
+        hash: H256,
+    ) -> RpcResult<Option<TransactionStatusDetails>> {
+        self.get_transaction_status_details_impl(hash)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_raw_block_transactions(
         &self,
         block_number: L2BlockNumber,
@@ -139,6 +151,36 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_transactions_by_address(
+        &self,
+        address: Address,
+        filter: Option<AddressTransactionsFilter>,
+    ) -> RpcResult<AddressTransactionsPage> {
+        self.get_transactions_by_address_impl(address, filter.unwrap_or_default())
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_token_transfers(
+        &self,
+        address: Address,
+        filter: Option<TokenTransfersFilter>,
+    ) -> RpcResult<TokenTransfersPage> {
+        self.get_token_transfers_impl(address, filter.unwrap_or_default())
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_balance_history(
+        &self,
+        address: Address,
+        filter: Option<BalanceHistoryFilter>,
+    ) -> RpcResult<BalanceHistoryPage> {
+        self.get_balance_history_impl(address, filter.unwrap_or_default())
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_l1_batch_details(
         &self,
         batch_number: L1BatchNumber,
@@ -148,6 +190,15 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_l1_batch_settlement_audit(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<L1BatchSettlementAudit>> {
+        self.get_l1_batch_settlement_audit_impl(batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>> {
         self.get_bytecode_by_hash_impl(hash)
             .await
@@ -227,6 +278,59 @@ impl ZksNamespaceServer for ZksNamespace {
             })
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn create_simulation_session(&self, block: Option<BlockIdVariant>) -> RpcResult<U256> {
+        self.create_simulation_session_impl(block.map(Into::into))
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn simulate_transaction(
+        &self,
+        session_id: U256,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<SimulatedTransaction> {
+        self.simulate_transaction_impl(session_id, req, state_override)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn simulate_raw_transaction(
+        &self,
+        session_id: U256,
+        tx_bytes: web3::Bytes,
+    ) -> RpcResult<SimulatedTransaction> {
+        self.simulate_raw_transaction_impl(session_id, tx_bytes)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_simulation_session(
+        &self,
+        session_id: U256,
+    ) -> RpcResult<SimulationSessionDetails> {
+        self.get_simulation_session_impl(session_id)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_simulation_storage_at(
+        &self,
+        session_id: U256,
+        address: Address,
+        key: U256,
+    ) -> RpcResult<H256> {
+        self.get_simulation_storage_at_impl(session_id, address, key)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn discard_simulation_session(&self, session_id: U256) -> RpcResult<bool> {
+        self.discard_simulation_session_impl(session_id)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
 
 fn map_event(vm_event: &VmEvent) -> Log {
@@ -43,7 +43,7 @@ use self::{
         UnstableNamespace, Web3Namespace, ZksNamespace,
     },
     pubsub::{EthSubscribe, EthSubscriptionIdProvider, PubSubEvent},
-    state::{Filters, InternalApiConfig, RpcState, SealedL2BlockNumber},
+    state::{Filters, InternalApiConfig, RpcState, SealedL2BlockNumber, SimulationSessions},
 };
 use crate::{
     execution_sandbox::{BlockStartInfo, VmConcurrencyBarrier},
@@ -377,9 +377,15 @@ impl ApiServer {
                 ))))
             };
 
+        let simulation_sessions = self
+            .config
+            .simulation_sessions_limit
+            .map(|limit| Arc::new(Mutex::new(SimulationSessions::new(limit))));
+
         Ok(RpcState {
             current_method: self.method_tracer,
             installed_filters,
+            simulation_sessions,
             connection_pool: self.pool,
             tx_sender: self.tx_sender,
             sync_state: self.optional.sync_state,
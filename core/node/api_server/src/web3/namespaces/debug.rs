@@ -1,21 +1,29 @@
 use anyhow::Context as _;
 use zksync_dal::{CoreDal, DalError};
-use zksync_multivm::interface::{Call, CallType, ExecutionResult, OneshotTracingParams};
+use zksync_multivm::{
+    interface::{
+        storage::StorageDiff, Call, CallType, ExecutionResult, GasProfile, OneshotTracingParams,
+    },
+    is_supported_by_gas_profiler,
+};
 use zksync_system_constants::MAX_ENCODED_TX_SIZE;
 use zksync_types::{
     api::{
         BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, DebugCall, DebugCallType,
-        ResultDebugCall, SupportedTracers, TracerConfig,
+        DebugGasProfile, DebugGasProfileFunction, DebugGasProfileInstruction, ResultDebugCall,
+        SupportedTracers, TracerConfig,
     },
     debug_flat_call::{Action, CallResult, CallTraceMeta, DebugCallFlat, ResultDebugCallFlat},
+    fee_model::BatchFeeInput,
     l2::L2Tx,
+    pruning::PrunedDataClass,
     transaction_request::CallRequest,
-    web3, H256, U256,
+    web3, L2BlockNumber, Transaction, H256, U256,
 };
 use zksync_web3_decl::error::Web3Error;
 
 use crate::{
-    execution_sandbox::SandboxAction,
+    execution_sandbox::{BlockArgs, SandboxAction, SandboxExecutionOutput},
     web3::{backend_jsonrpsee::MethodTracer, state::RpcState},
 };
 
@@ -52,6 +60,36 @@ impl DebugNamespace {
                 );
                 CallTracerResult::FlatCallTrace(calls)
             }
+            SupportedTracers::GasProfiler => {
+                unreachable!("gas profiles are produced by VM execution, not from call traces")
+            }
+        }
+    }
+
+    fn map_gas_profile(profile: &GasProfile) -> DebugGasProfile {
+        let mut functions: Vec<_> = profile
+            .by_function()
+            .into_iter()
+            .map(|((address, selector), gas)| DebugGasProfileFunction {
+                address,
+                selector: selector.map(|selector| web3::Bytes(selector.to_vec())),
+                gas,
+            })
+            .collect();
+        functions.sort_by(|a, b| b.gas.cmp(&a.gas));
+
+        let mut instructions: Vec<_> = profile
+            .pcs
+            .iter()
+            .map(|(&(address, pc), &gas)| DebugGasProfileInstruction { address, pc, gas })
+            .collect();
+        instructions.sort_by(|a, b| b.gas.cmp(&a.gas));
+
+        DebugGasProfile {
+            total_gas: profile.total_gas(),
+            functions,
+            instructions,
+            collapsed_stacks: profile.to_collapsed_stacks(),
         }
     }
 
@@ -168,13 +206,22 @@ impl DebugNamespace {
         options: Option<TracerConfig>,
     ) -> Result<CallTracerBlockResult, Web3Error> {
         self.current_method().set_block_id(block_id);
+        let options = options.unwrap_or_default();
+        if matches!(options.tracer, SupportedTracers::GasProfiler) {
+            return Err(Web3Error::UnsupportedTracer(
+                "gas profiler is not supported for tracing blocks".to_owned(),
+            ));
+        }
         if matches!(block_id, BlockId::Number(BlockNumber::Pending)) {
             // See `EthNamespace::get_block_impl()` for an explanation why this check is needed.
             return Ok(CallTracerBlockResult::CallTrace(vec![]));
         }
 
         let mut connection = self.state.acquire_connection().await?;
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = self
+            .state
+            .resolve_block_with_data(&mut connection, block_id, PrunedDataClass::CallTraces)
+            .await?;
         // let block_hash = block_hash self.state.
         self.current_method()
             .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
@@ -185,7 +232,6 @@ impl DebugNamespace {
             .await
             .map_err(DalError::generalize)?;
 
-        let options = options.unwrap_or_default();
         let result = match options.tracer {
             SupportedTracers::CallTracer => CallTracerBlockResult::CallTrace(
                 call_traces
@@ -220,6 +266,7 @@ impl DebugNamespace {
                     .collect();
                 CallTracerBlockResult::FlatCallTrace(res)
             }
+            SupportedTracers::GasProfiler => unreachable!("checked above"),
         };
         Ok(result)
     }
@@ -229,15 +276,184 @@ impl DebugNamespace {
         tx_hash: H256,
         options: Option<TracerConfig>,
     ) -> Result<Option<CallTracerResult>, Web3Error> {
+        let options = options.unwrap_or_default();
+        if matches!(options.tracer, SupportedTracers::GasProfiler) {
+            return self.profile_transaction(tx_hash).await;
+        }
+
         let mut connection = self.state.acquire_connection().await?;
         let call_trace = connection
             .transactions_dal()
             .get_call_trace(tx_hash)
             .await
             .map_err(DalError::generalize)?;
-        Ok(call_trace.map(|(call_trace, meta)| {
-            Self::map_call(call_trace, meta, options.unwrap_or_default())
-        }))
+        Ok(call_trace.map(|(call_trace, meta)| Self::map_call(call_trace, meta, options)))
+    }
+
+    /// Profiles a stored L2 transaction by re-executing it on top of the state before its L2 block.
+    /// Transactions preceding the profiled one in the L2 block are replayed first, so that their state changes
+    /// are taken into account.
+    async fn profile_transaction(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<CallTracerResult>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let Some(storage_tx) = connection
+            .transactions_dal()
+            .get_storage_tx_by_hash(tx_hash)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        let (Some(block_number), Some(index_in_block)) =
+            (storage_tx.miniblock_number, storage_tx.index_in_block)
+        else {
+            return Ok(None); // The transaction is not executed yet
+        };
+        let block_number = L2BlockNumber(block_number as u32);
+        let tx = Transaction::from(storage_tx);
+        let tx = L2Tx::try_from(tx).map_err(|_| {
+            Web3Error::UnsupportedTracer("gas profiler only supports L2 transactions".to_owned())
+        })?;
+        let Some(prev_block_number) = block_number.0.checked_sub(1) else {
+            return Ok(None);
+        };
+
+        let block_args = self
+            .state
+            .resolve_block_args(
+                &mut connection,
+                BlockId::Number(BlockNumber::Number(prev_block_number.into())),
+            )
+            .await?;
+        Self::ensure_gas_profiler_support(&block_args)?;
+        let fee_input = connection
+            .blocks_dal()
+            .get_l2_block_header(block_number)
+            .await
+            .map_err(DalError::generalize)?
+            .with_context(|| format!("missing header for L2 block #{block_number}"))?
+            .batch_fee_input;
+        let preceding_txs = connection
+            .transactions_web3_dal()
+            .get_raw_l2_block_transactions(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        drop(connection);
+
+        let preceding_txs = preceding_txs
+            .into_iter()
+            .take(index_in_block as usize)
+            .map(|tx| {
+                L2Tx::try_from(tx).map_err(|_| {
+                    Web3Error::UnsupportedTracer(
+                        "gas profiler cannot replay L1 transactions preceding the profiled transaction"
+                            .to_owned(),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut storage_diff = StorageDiff::default();
+        for preceding_tx in preceding_txs {
+            let factory_deps = preceding_tx.execute.factory_deps.clone();
+            let action = SandboxAction::Execution {
+                tx: preceding_tx,
+                fee_input,
+                tracing_params: OneshotTracingParams::default(),
+            };
+            let output = self
+                .execute_with_storage_diff(action, &block_args, &storage_diff)
+                .await?;
+            storage_diff.merge_execution(&output.vm, &factory_deps);
+        }
+
+        let action = SandboxAction::Execution {
+            tx,
+            fee_input,
+            tracing_params: OneshotTracingParams {
+                profile_gas: true,
+                ..OneshotTracingParams::default()
+            },
+        };
+        let result = self
+            .execute_with_storage_diff(action, &block_args, &storage_diff)
+            .await?;
+        Ok(Some(CallTracerResult::GasProfile(Self::map_gas_profile(
+            &result.gas_profile,
+        ))))
+    }
+
+    async fn execute_with_storage_diff(
+        &self,
+        action: SandboxAction,
+        block_args: &BlockArgs,
+        storage_diff: &StorageDiff,
+    ) -> Result<SandboxExecutionOutput, Web3Error> {
+        let vm_permit = self
+            .state
+            .tx_sender
+            .vm_concurrency_limiter()
+            .acquire()
+            .await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+        let connection = self.state.acquire_connection().await?;
+        let executor = &self.state.tx_sender.0.executor;
+        Ok(executor
+            .execute_with_storage_diff(
+                vm_permit,
+                connection,
+                action,
+                block_args,
+                Some(storage_diff),
+                None,
+            )
+            .await?)
+    }
+
+    fn ensure_gas_profiler_support(block_args: &BlockArgs) -> Result<(), Web3Error> {
+        let protocol_version = block_args.protocol_version();
+        if is_supported_by_gas_profiler(protocol_version) {
+            Ok(())
+        } else {
+            Err(Web3Error::UnsupportedTracer(format!(
+                "gas profiler is unsupported for protocol version {protocol_version:?}"
+            )))
+        }
+    }
+
+    async fn execute_call(
+        &self,
+        call: L2Tx,
+        block_args: &BlockArgs,
+        fee_input: BatchFeeInput,
+        enforced_base_fee: Option<u64>,
+        tracing_params: OneshotTracingParams,
+    ) -> Result<SandboxExecutionOutput, Web3Error> {
+        let vm_permit = self
+            .state
+            .tx_sender
+            .vm_concurrency_limiter()
+            .acquire()
+            .await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+
+        let connection = self.state.acquire_connection().await?;
+        let executor = &self.state.tx_sender.0.executor;
+        Ok(executor
+            .execute_in_sandbox(
+                vm_permit,
+                connection,
+                SandboxAction::Call {
+                    call,
+                    fee_input,
+                    enforced_base_fee,
+                    tracing_params,
+                },
+                block_args,
+                None,
+            )
+            .await?)
     }
 
     pub async fn debug_trace_call_impl(
@@ -283,35 +499,29 @@ impl DebugNamespace {
             block_args.use_evm_emulator(),
         )?;
 
-        let vm_permit = self
-            .state
-            .tx_sender
-            .vm_concurrency_limiter()
-            .acquire()
-            .await;
-        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
-
+        let profile_gas = matches!(options.tracer, SupportedTracers::GasProfiler);
+        if profile_gas {
+            Self::ensure_gas_profiler_support(&block_args)?;
+        }
         // We don't need properly trace if we only need top call
         let tracing_params = OneshotTracingParams {
-            trace_calls: !options.tracer_config.only_top_call,
+            trace_calls: !profile_gas && !options.tracer_config.only_top_call,
+            profile_gas,
         };
-
-        let connection = self.state.acquire_connection().await?;
-        let executor = &self.state.tx_sender.0.executor;
-        let result = executor
-            .execute_in_sandbox(
-                vm_permit,
-                connection,
-                SandboxAction::Call {
-                    call: call.clone(),
-                    fee_input,
-                    enforced_base_fee: call_overrides.enforced_base_fee,
-                    tracing_params,
-                },
+        let result = self
+            .execute_call(
+                call.clone(),
                 &block_args,
-                None,
+                fee_input,
+                call_overrides.enforced_base_fee,
+                tracing_params,
             )
             .await?;
+        if profile_gas {
+            return Ok(CallTracerResult::GasProfile(Self::map_gas_profile(
+                &result.gas_profile,
+            )));
+        }
 
         let (output, revert_reason) = match result.vm.result {
             ExecutionResult::Success { output, .. } => (output, None),
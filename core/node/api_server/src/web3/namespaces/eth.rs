@@ -8,6 +8,7 @@ use zksync_types::{
     },
     bytecode::{trim_padded_evm_bytecode, BytecodeHash, BytecodeMarker},
     l2::{L2Tx, TransactionType},
+    pruning::PrunedDataClass,
     transaction_request::CallRequest,
     u256_to_h256,
     utils::decompose_full_nonce,
@@ -170,7 +171,10 @@ impl EthNamespace {
         self.current_method().set_block_id(block_id);
 
         let mut connection = self.state.acquire_connection().await?;
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = self
+            .state
+            .resolve_block_with_data(&mut connection, block_id, PrunedDataClass::StateHistory)
+            .await?;
 
         let balance = connection
             .storage_web3_dal()
@@ -195,6 +199,20 @@ impl EthNamespace {
         self.state.resolve_filter_block_hash(&mut filter).await?;
         let (from_block, to_block) = self.state.resolve_filter_block_range(&filter).await?;
 
+        // Logs for pruned blocks are silently skipped, but if the node retains blocks without events
+        // (i.e., uses selective pruning), the returned logs would be incomplete; we return an error instead.
+        let mut storage = self.state.acquire_connection().await?;
+        let first_l2_block = self.state.start_info.first_l2_block(&mut storage).await?;
+        self.state
+            .start_info
+            .ensure_data_not_pruned(
+                PrunedDataClass::Transactions,
+                from_block.max(first_l2_block),
+                &mut storage,
+            )
+            .await?;
+        drop(storage);
+
         filter.to_block = Some(BlockNumber::Number(to_block.0.into()));
         let changes = self
             .filter_changes(&mut TypedFilter::Events(filter, from_block))
@@ -269,6 +287,10 @@ impl EthNamespace {
         self.set_block_diff(block_number);
 
         let transactions = if full_transactions {
+            self.state
+                .start_info
+                .ensure_data_not_pruned(PrunedDataClass::Transactions, block_number, &mut storage)
+                .await?;
             let mut transactions = storage
                 .transactions_web3_dal()
                 .get_transactions(&block.transactions, self.state.api_config.l2_chain_id)
@@ -371,6 +393,10 @@ impl EthNamespace {
             return Ok(None);
         };
         self.set_block_diff(block_number); // only report block diff for existing L2 blocks
+        self.state
+            .start_info
+            .ensure_data_not_pruned(PrunedDataClass::Transactions, block_number, &mut storage)
+            .await?;
 
         let mut receipts = storage
             .transactions_web3_dal()
@@ -390,7 +416,10 @@ impl EthNamespace {
         self.current_method().set_block_id(block_id);
 
         let mut connection = self.state.acquire_connection().await?;
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = self
+            .state
+            .resolve_block_with_data(&mut connection, block_id, PrunedDataClass::StateHistory)
+            .await?;
         self.set_block_diff(block_number);
 
         let contract_code = connection
@@ -441,7 +470,10 @@ impl EthNamespace {
 
         let storage_key = StorageKey::new(AccountTreeId::new(address), u256_to_h256(idx));
         let mut connection = self.state.acquire_connection().await?;
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = self
+            .state
+            .resolve_block_with_data(&mut connection, block_id, PrunedDataClass::StateHistory)
+            .await?;
         self.set_block_diff(block_number);
         let value = connection
             .storage_web3_dal()
@@ -462,7 +494,10 @@ impl EthNamespace {
 
         let mut connection = self.state.acquire_connection().await?;
 
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = self
+            .state
+            .resolve_block_with_data(&mut connection, block_id, PrunedDataClass::StateHistory)
+            .await?;
         self.set_block_diff(block_number);
         let full_nonce = connection
             .storage_web3_dal()
@@ -529,6 +564,14 @@ impl EthNamespace {
                 else {
                     return Ok(None);
                 };
+                self.state
+                    .start_info
+                    .ensure_data_not_pruned(
+                        PrunedDataClass::Transactions,
+                        block_number,
+                        &mut storage,
+                    )
+                    .await?;
 
                 storage
                     .transactions_web3_dal()
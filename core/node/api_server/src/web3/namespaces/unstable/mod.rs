@@ -6,14 +6,25 @@ use utils::{
 use zksync_crypto_primitives::hasher::keccak::KeccakHasher;
 use zksync_dal::{CoreDal, DalError};
 use zksync_mini_merkle_tree::MiniMerkleTree;
+use zksync_multivm::interface::ExecutionResult;
 use zksync_types::{
-    api::{ChainAggProof, TeeProof, TransactionExecutionInfo},
+    api::{
+        ChainAggProof, ProtocolUpgradePreflight, ProtocolUpgradeTxExecution, ProtocolVersion,
+        SettlementLayerMigrationCheck, SettlementLayerMigrationCheckResult,
+        SettlementLayerMigrationPreflight, SettlementLayerMigrationStage, SettlementLayerStats,
+        SettlementLayerStatus, TeeProof, TransactionExecutionInfo,
+    },
+    settlement::SettlementMode,
     tee_types::TeeType,
-    L1BatchNumber, L2ChainId,
+    L1BatchNumber, L2ChainId, ProtocolVersionId,
 };
+use zksync_vm_executor::storage::get_base_system_contracts_by_version_id;
 use zksync_web3_decl::{error::Web3Error, types::H256};
 
-use crate::web3::{backend_jsonrpsee::MethodTracer, RpcState};
+use crate::{
+    execution_sandbox::BlockArgs,
+    web3::{backend_jsonrpsee::MethodTracer, RpcState},
+};
 
 mod utils;
 
@@ -151,4 +162,269 @@ impl UnstableNamespace {
 
         Ok(result)
     }
+
+    pub async fn get_settlement_layer_status_impl(
+        &self,
+    ) -> Result<SettlementLayerStatus, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let current_layer = connection
+            .eth_sender_dal()
+            .get_latest_settlement_layer()
+            .await
+            .map_err(DalError::generalize)?;
+        let layers = connection
+            .eth_sender_dal()
+            .get_settlement_layer_stats()
+            .await
+            .map_err(DalError::generalize)?;
+
+        let configured_layer = self.state.api_config.settlement_mode;
+        let migration_stage = configured_layer.map(|configured_layer| {
+            Self::migration_stage(
+                configured_layer,
+                current_layer.map(|(layer, _)| layer),
+                &layers,
+            )
+        });
+        Ok(SettlementLayerStatus {
+            configured_layer,
+            current_layer: current_layer.map(|(layer, _)| layer),
+            current_layer_chain_id: current_layer.and_then(|(_, chain_id)| chain_id),
+            migration_stage,
+            layers,
+        })
+    }
+
+    fn migration_stage(
+        configured_layer: SettlementMode,
+        current_layer: Option<SettlementMode>,
+        layers: &[SettlementLayerStats],
+    ) -> SettlementLayerMigrationStage {
+        let has_old_layer_txs = layers
+            .iter()
+            .any(|stats| stats.layer != configured_layer && stats.inflight_txs > 0);
+        match current_layer {
+            Some(layer) if layer != configured_layer => {
+                SettlementLayerMigrationStage::AwaitingNewLayer
+            }
+            _ if has_old_layer_txs => SettlementLayerMigrationStage::DrainingOldLayer,
+            _ => SettlementLayerMigrationStage::Completed,
+        }
+    }
+
+    /// Checks preconditions for switching the node to the `target_layer` without changing anything.
+    pub async fn settlement_layer_migration_preflight_impl(
+        &self,
+        target_layer: SettlementMode,
+    ) -> Result<SettlementLayerMigrationPreflight, Web3Error> {
+        let status = self.get_settlement_layer_status_impl().await?;
+        let mut checks = vec![];
+
+        let on_target_layer = status.current_layer == Some(target_layer);
+        checks.push(SettlementLayerMigrationCheckResult {
+            check: SettlementLayerMigrationCheck::NotOnTargetLayer,
+            passed: !on_target_layer,
+            details: match status.current_layer {
+                Some(layer) => format!("latest settlement transaction was created for {layer:?}"),
+                None => "no settlement transactions were created yet".to_owned(),
+            },
+        });
+
+        let inflight_txs: u64 = status
+            .layers
+            .iter()
+            .filter(|stats| stats.layer != target_layer)
+            .map(|stats| stats.inflight_txs)
+            .sum();
+        checks.push(SettlementLayerMigrationCheckResult {
+            check: SettlementLayerMigrationCheck::NoInflightTxs,
+            passed: inflight_txs == 0,
+            details: format!(
+                "{inflight_txs} unconfirmed settlement transaction(s) on the old layer"
+            ),
+        });
+
+        let last_committed_batch = status
+            .layers
+            .iter()
+            .filter_map(|stats| stats.last_committed_batch)
+            .max();
+        let last_executed_batch = status
+            .layers
+            .iter()
+            .filter_map(|stats| stats.last_executed_batch)
+            .max();
+        checks.push(SettlementLayerMigrationCheckResult {
+            check: SettlementLayerMigrationCheck::AllBatchesExecuted,
+            passed: last_committed_batch <= last_executed_batch,
+            details: format!(
+                "last committed L1 batch: {last_committed_batch:?}, \
+                 last executed L1 batch: {last_executed_batch:?}"
+            ),
+        });
+
+        let mut connection = self.state.acquire_connection().await?;
+        let latest_version = connection
+            .protocol_versions_dal()
+            .latest_semantic_version()
+            .await
+            .map_err(DalError::generalize)?
+            .map(|version| version.minor);
+        let sealed_l1_batch = connection
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .map_err(DalError::generalize)?;
+        let batch_version = if let Some(number) = sealed_l1_batch {
+            connection
+                .blocks_dal()
+                .get_batch_protocol_version_id(number)
+                .await
+                .map_err(DalError::generalize)?
+        } else {
+            None
+        };
+        let supports_target_layer = !target_layer.is_gateway()
+            || batch_version.is_some_and(|version| version >= ProtocolVersionId::gateway_upgrade());
+        let is_latest_version = batch_version.is_some() && batch_version == latest_version;
+        checks.push(SettlementLayerMigrationCheckResult {
+            check: SettlementLayerMigrationCheck::ProtocolVersion,
+            passed: is_latest_version && supports_target_layer,
+            details: format!(
+                "latest sealed L1 batch uses protocol version {batch_version:?}, \
+                 latest known protocol version: {latest_version:?}"
+            ),
+        });
+
+        Ok(SettlementLayerMigrationPreflight {
+            target_layer,
+            ready: checks.iter().all(|check| check.passed),
+            checks,
+            status,
+        })
+    }
+
+    /// Returns protocol versions that are stored in the node (i.e., were seen on L1), but were not yet picked up
+    /// by the state keeper.
+    pub async fn get_upcoming_protocol_upgrades_impl(
+        &self,
+    ) -> Result<Vec<ProtocolVersion>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let current_version = connection.blocks_dal().pending_protocol_version().await?;
+        let versions = connection
+            .protocol_versions_web3_dal()
+            .get_protocol_versions_after(current_version)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(versions)
+    }
+
+    /// Checks that the state keeper will be able to apply the specified protocol version. Similarly to the state keeper,
+    /// the upgrade transaction is executed on top of the latest sealed state with upgraded base system contracts.
+    pub async fn protocol_upgrade_preflight_impl(
+        &self,
+        version_id: u16,
+    ) -> Result<Option<ProtocolUpgradePreflight>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let Some(version) = connection
+            .protocol_versions_web3_dal()
+            .get_protocol_version_by_id(version_id)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        let upgrade_tx_hash = version.l2_system_upgrade_tx_hash();
+        let mut issues = vec![];
+
+        let current_version = connection.blocks_dal().pending_protocol_version().await? as u16;
+        if version_id <= current_version {
+            issues.push(format!(
+                "protocol version {version_id} is not newer than the current version {current_version}"
+            ));
+        } else if version_id > current_version + 1 {
+            // The state keeper switches to the latest published version, so intermediate upgrades (including
+            // their upgrade transactions) would not be applied.
+            issues.push(format!(
+                "upgrade skips intermediate protocol versions {}..={}",
+                current_version + 1,
+                version_id - 1
+            ));
+        }
+
+        let Ok(protocol_version) = ProtocolVersionId::try_from(version_id) else {
+            issues.push(format!(
+                "protocol version {version_id} is not supported by this node"
+            ));
+            return Ok(Some(ProtocolUpgradePreflight {
+                minor_version: version_id,
+                timestamp: version.timestamp,
+                upgrade_tx_hash,
+                upgrade_tx_execution: None,
+                issues,
+                ready: false,
+            }));
+        };
+
+        let base_system_contracts =
+            get_base_system_contracts_by_version_id(&mut connection, protocol_version).await;
+        let base_system_contracts = match base_system_contracts {
+            Ok(Some(contracts)) => Some(contracts),
+            Ok(None) => {
+                issues.push("base system contracts are not stored for the version".to_owned());
+                None
+            }
+            Err(err) => {
+                issues.push(format!("failed loading base system contracts: {err:#}"));
+                None
+            }
+        };
+        // Same as `load_upgrade_tx()` in the state keeper
+        let upgrade_tx = match connection
+            .protocol_versions_dal()
+            .get_protocol_upgrade_tx(protocol_version)
+            .await
+        {
+            Ok(tx) => tx,
+            Err(err) => {
+                issues.push(format!("failed loading upgrade transaction: {err}"));
+                None
+            }
+        };
+        let block_args = BlockArgs::pending(&mut connection).await?;
+        drop(connection);
+
+        let upgrade_tx_execution = match (upgrade_tx, base_system_contracts) {
+            (Some(tx), Some(contracts)) => {
+                let result = self
+                    .state
+                    .tx_sender
+                    .execute_protocol_upgrade_tx(block_args, tx, protocol_version, contracts)
+                    .await?;
+                let error = match &result.result {
+                    ExecutionResult::Success { .. } => None,
+                    ExecutionResult::Revert { output } => Some(output.to_string()),
+                    ExecutionResult::Halt { reason } => Some(reason.to_string()),
+                };
+                if let Some(error) = &error {
+                    issues.push(format!("upgrade transaction has failed: {error}"));
+                }
+                Some(ProtocolUpgradeTxExecution {
+                    success: error.is_none(),
+                    error,
+                    gas_used: result.statistics.gas_used,
+                })
+            }
+            _ => None,
+        };
+
+        Ok(Some(ProtocolUpgradePreflight {
+            minor_version: version_id,
+            timestamp: version.timestamp,
+            upgrade_tx_hash,
+            upgrade_tx_execution,
+            ready: issues.is_empty(),
+            issues,
+        }))
+    }
 }
@@ -1,17 +1,24 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops, sync::Arc};
 
 use anyhow::Context as _;
+use tokio::sync::Mutex;
 use zksync_crypto_primitives::hasher::{keccak::KeccakHasher, Hasher};
 use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_metadata_calculator::api_server::TreeApiError;
 use zksync_mini_merkle_tree::MiniMerkleTree;
-use zksync_multivm::interface::VmExecutionResultAndLogs;
+use zksync_multivm::interface::{
+    storage::StorageDiff, ExecutionResult, OneshotTracingParams, VmExecutionResultAndLogs,
+};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     address_to_h256,
     api::{
-        state_override::StateOverride, BlockDetails, BridgeAddresses, GetLogsFilter,
-        L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion, StorageProof, TransactionDetails,
+        self, state_override::StateOverride, AddressTransactionsCursor, AddressTransactionsFilter,
+        AddressTransactionsPage, ApiStorageLog, BalanceHistoryFilter, BalanceHistoryPage,
+        BalanceSnapshot, BlockDetails, BlockId, BlockNumber, BridgeAddresses, GetLogsFilter,
+        L1BatchDetails, L1BatchSettlementAudit, L2ToL1LogProof, Proof, ProtocolVersion,
+        SimulatedTransaction, SimulationSessionDetails, StorageProof, TokenTransfersCursor,
+        TokenTransfersFilter, TokenTransfersPage, TransactionDetails, TransactionStatusDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -19,8 +26,10 @@ use zksync_types::{
     l1::L1Tx,
     l2::L2Tx,
     l2_to_l1_log::{l2_to_l1_logs_tree_size, L2ToL1Log, LOG_PROOF_SUPPORTED_METADATA_VERSION},
+    pruning::PrunedDataClass,
     tokens::ETHEREUM_ADDRESS,
     transaction_request::CallRequest,
+    u256_to_h256,
     utils::storage_key_for_standard_token_balance,
     web3::Bytes,
     AccountTreeId, L1BatchNumber, L2BlockNumber, ProtocolVersionId, StorageKey, Transaction,
@@ -33,10 +42,15 @@ use zksync_web3_decl::{
 };
 
 use crate::{
-    execution_sandbox::BlockArgs,
+    execution_sandbox::{BlockArgs, SandboxAction},
     tx_sender::BinarySearchKind,
     utils::open_readonly_transaction,
-    web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, RpcState},
+    web3::{
+        backend_jsonrpsee::MethodTracer,
+        metrics::API_METRICS,
+        state::{SimulationSession, SimulationSessions},
+        RpcState,
+    },
 };
 
 #[derive(Debug)]
@@ -512,6 +526,10 @@ impl ZksNamespace {
             .start_info
             .ensure_not_pruned(block_number, &mut storage)
             .await?;
+        self.state
+            .start_info
+            .ensure_data_not_pruned(PrunedDataClass::Transactions, block_number, &mut storage)
+            .await?;
 
         Ok(storage
             .transactions_web3_dal()
@@ -520,6 +538,286 @@ impl ZksNamespace {
             .map_err(DalError::generalize)?)
     }
 
+    pub async fn get_transactions_by_address_impl(
+        &self,
+        address: Address,
+        filter: AddressTransactionsFilter,
+    ) -> Result<AddressTransactionsPage, Web3Error> {
+        if !self.state.api_config.address_index_enabled {
+            return Err(Web3Error::MethodNotImplemented);
+        }
+        let limit = filter
+            .limit
+            .unwrap_or(usize::MAX)
+            .clamp(1, self.state.api_config.req_entities_limit);
+
+        let mut storage = self.state.acquire_connection().await?;
+        // Open a readonly transaction to have a consistent view of Postgres
+        let mut storage = open_readonly_transaction(&mut storage).await?;
+        let earliest_indexed_l2_block = storage
+            .address_transactions_dal()
+            .get_earliest_indexed_l2_block()
+            .await
+            .map_err(DalError::generalize)?;
+        let l2_blocks = self
+            .resolve_indexed_block_range(
+                &mut storage,
+                filter.from_block,
+                filter.to_block,
+                earliest_indexed_l2_block,
+            )
+            .await?;
+        if l2_blocks.is_empty() {
+            return Ok(AddressTransactionsPage {
+                transactions: vec![],
+                next_cursor: None,
+            });
+        }
+
+        let before = filter
+            .cursor
+            .map(|cursor| (cursor.block_number, cursor.transaction_index));
+        // Fetch an extra entry to check whether there are more entries after the page.
+        let mut tx_refs = storage
+            .address_transactions_dal()
+            .get_address_transactions(address, filter.direction, l2_blocks, before, limit + 1)
+            .await
+            .map_err(DalError::generalize)?;
+        let next_cursor = if tx_refs.len() > limit {
+            tx_refs.truncate(limit);
+            tx_refs.last().map(|tx_ref| AddressTransactionsCursor {
+                block_number: tx_ref.l2_block_number,
+                transaction_index: tx_ref.index_in_block,
+            })
+        } else {
+            None
+        };
+
+        let tx_hashes: Vec<_> = tx_refs.iter().map(|tx_ref| tx_ref.tx_hash).collect();
+        let mut transactions: HashMap<_, _> = storage
+            .transactions_web3_dal()
+            .get_transactions(&tx_hashes, self.state.api_config.l2_chain_id)
+            .await
+            .map_err(DalError::generalize)?
+            .into_iter()
+            .map(|tx| (tx.hash, tx))
+            .collect();
+        let transactions = tx_hashes
+            .iter()
+            .map(|hash| {
+                transactions.remove(hash).with_context(|| {
+                    format!("indexed transaction {hash:?} is missing in the transactions table")
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(AddressTransactionsPage {
+            transactions,
+            next_cursor,
+        })
+    }
+
+    /// Resolves the L2 block range for querying optional per-address indexes. The lower bound defaults to
+    /// the first L2 block with retained transactions, and the upper bound to the latest L2 block.
+    /// The lower bound is clamped to the earliest indexed L2 block since older blocks may be not indexed yet
+    /// (e.g., if the index is being backfilled). The returned range may be empty.
+    async fn resolve_indexed_block_range(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        from_block: Option<BlockNumber>,
+        to_block: Option<BlockNumber>,
+        earliest_indexed_l2_block: Option<L2BlockNumber>,
+    ) -> Result<ops::RangeInclusive<L2BlockNumber>, Web3Error> {
+        let from_block = if let Some(from_block) = from_block {
+            self.state
+                .resolve_block_with_data(
+                    storage,
+                    BlockId::Number(from_block),
+                    PrunedDataClass::Transactions,
+                )
+                .await?
+        } else {
+            self.state
+                .start_info
+                .first_l2_block_with(PrunedDataClass::Transactions, storage)
+                .await?
+        };
+        let to_block = to_block.unwrap_or(BlockNumber::Latest);
+        let to_block = self
+            .state
+            .resolve_block(storage, BlockId::Number(to_block))
+            .await?;
+        let Some(earliest_indexed_l2_block) = earliest_indexed_l2_block else {
+            // Nothing is indexed yet.
+            return Ok(L2BlockNumber(1)..=L2BlockNumber(0));
+        };
+        Ok(from_block.max(earliest_indexed_l2_block)..=to_block)
+    }
+
+    pub async fn get_token_transfers_impl(
+        &self,
+        address: Address,
+        filter: TokenTransfersFilter,
+    ) -> Result<TokenTransfersPage, Web3Error> {
+        if !self.state.api_config.token_transfer_index_enabled {
+            return Err(Web3Error::MethodNotImplemented);
+        }
+        let limit = filter
+            .limit
+            .unwrap_or(usize::MAX)
+            .clamp(1, self.state.api_config.req_entities_limit);
+
+        let mut storage = self.state.acquire_connection().await?;
+        // Open a readonly transaction to have a consistent view of Postgres
+        let mut storage = open_readonly_transaction(&mut storage).await?;
+        let earliest_indexed_l2_block = storage
+            .token_transfers_dal()
+            .get_earliest_indexed_l2_block()
+            .await
+            .map_err(DalError::generalize)?;
+        let l2_blocks = self
+            .resolve_indexed_block_range(
+                &mut storage,
+                filter.from_block,
+                filter.to_block,
+                earliest_indexed_l2_block,
+            )
+            .await?;
+        if l2_blocks.is_empty() {
+            return Ok(TokenTransfersPage {
+                transfers: vec![],
+                next_cursor: None,
+            });
+        }
+
+        let before = filter
+            .cursor
+            .map(|cursor| (cursor.block_number, cursor.log_index));
+        // Fetch an extra entry to check whether there are more entries after the page.
+        let mut transfers = storage
+            .token_transfers_dal()
+            .get_token_transfers(
+                address,
+                filter.token,
+                filter.direction,
+                l2_blocks,
+                before,
+                limit + 1,
+            )
+            .await
+            .map_err(DalError::generalize)?;
+        let next_cursor = if transfers.len() > limit {
+            transfers.truncate(limit);
+            transfers.last().map(|transfer| TokenTransfersCursor {
+                block_number: transfer.block_number,
+                log_index: transfer.log_index,
+            })
+        } else {
+            None
+        };
+        Ok(TokenTransfersPage {
+            transfers,
+            next_cursor,
+        })
+    }
+
+    pub async fn get_balance_history_impl(
+        &self,
+        address: Address,
+        filter: BalanceHistoryFilter,
+    ) -> Result<BalanceHistoryPage, Web3Error> {
+        if !self.state.api_config.token_transfer_index_enabled {
+            return Err(Web3Error::MethodNotImplemented);
+        }
+        let token = filter.token.unwrap_or(L2_BASE_TOKEN_ADDRESS);
+        let limit = filter
+            .limit
+            .unwrap_or(usize::MAX)
+            .clamp(1, self.state.api_config.req_entities_limit);
+
+        let mut storage = self.state.acquire_connection().await?;
+        // Open a readonly transaction to have a consistent view of Postgres
+        let mut storage = open_readonly_transaction(&mut storage).await?;
+        let earliest_indexed_l2_block = storage
+            .token_transfers_dal()
+            .get_earliest_indexed_l2_block()
+            .await
+            .map_err(DalError::generalize)?;
+        let l2_blocks = self
+            .resolve_indexed_block_range(
+                &mut storage,
+                filter.from_block,
+                filter.to_block,
+                earliest_indexed_l2_block,
+            )
+            .await?;
+        let l2_blocks = match filter.cursor {
+            // Only blocks strictly before the cursor should be returned, so there's nothing to return.
+            Some(L2BlockNumber(0)) => L2BlockNumber(1)..=L2BlockNumber(0),
+            Some(cursor) => *l2_blocks.start()..=(*l2_blocks.end()).min(cursor - 1),
+            None => l2_blocks,
+        };
+        if l2_blocks.is_empty() {
+            return Ok(BalanceHistoryPage {
+                snapshots: vec![],
+                next_cursor: None,
+            });
+        }
+
+        // Fetch an extra entry to check whether there are more entries after the page.
+        let mut changes = storage
+            .token_transfers_dal()
+            .get_balance_changes(address, token, l2_blocks, limit + 1)
+            .await
+            .map_err(DalError::generalize)?;
+        let next_cursor = if changes.len() > limit {
+            changes.truncate(limit);
+            changes.last().map(|change| change.l2_block_number)
+        } else {
+            None
+        };
+
+        // The balance storage slot is fixed only for the base token; other tokens may use arbitrary storage layouts,
+        // so historical balances are only provided for the base token.
+        let mut balances = HashMap::new();
+        if token == L2_BASE_TOKEN_ADDRESS {
+            let first_block_with_state = self
+                .state
+                .start_info
+                .first_l2_block_with(PrunedDataClass::StateHistory, &mut storage)
+                .await?;
+            let block_numbers: Vec<_> = changes
+                .iter()
+                .map(|change| change.l2_block_number)
+                .filter(|&number| number >= first_block_with_state)
+                .collect();
+            if !block_numbers.is_empty() {
+                balances = storage
+                    .storage_web3_dal()
+                    .standard_token_historical_balances(
+                        AccountTreeId::new(token),
+                        AccountTreeId::new(address),
+                        &block_numbers,
+                    )
+                    .await
+                    .map_err(DalError::generalize)?;
+            }
+        }
+        let snapshots = changes
+            .into_iter()
+            .map(|change| BalanceSnapshot {
+                block_number: change.l2_block_number,
+                received: change.received,
+                sent: change.sent,
+                balance: balances.get(&change.l2_block_number).copied(),
+            })
+            .collect();
+        Ok(BalanceHistoryPage {
+            snapshots,
+            next_cursor,
+        })
+    }
+
     pub async fn get_transaction_details_impl(
         &self,
         hash: H256,
@@ -543,6 +841,38 @@ impl ZksNamespace {
         Ok(tx_details)
     }
 
+    pub async fn get_transaction_status_details_impl(
+        &self,
+        hash: H256,
+    ) -> Result<Option<TransactionStatusDetails>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        // Open a readonly transaction to have a consistent view of Postgres
+        let mut storage = open_readonly_transaction(&mut storage).await?;
+        let status_details = storage
+            .transactions_web3_dal()
+            .get_transaction_status_details(hash)
+            .await
+            .map_err(DalError::generalize)?;
+        if status_details.is_some() {
+            return Ok(status_details);
+        }
+
+        // The transaction may only be known to the sink (e.g., if it was proxied to the main node).
+        // Pending reasons are not available in this case.
+        let tx_details = self
+            .state
+            .tx_sink()
+            .lookup_tx_details(&mut storage, hash)
+            .await?;
+        Ok(tx_details.map(|details| TransactionStatusDetails {
+            status: details.status,
+            initiator_address: details.initiator_address,
+            received_at: details.received_at,
+            pending_reason: None,
+            error: None,
+        }))
+    }
+
     pub async fn get_l1_batch_details_impl(
         &self,
         batch_number: L1BatchNumber,
@@ -560,6 +890,23 @@ impl ZksNamespace {
             .map_err(DalError::generalize)?)
     }
 
+    pub async fn get_l1_batch_settlement_audit_impl(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> Result<Option<L1BatchSettlementAudit>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(batch_number, &mut storage)
+            .await?;
+
+        Ok(storage
+            .settlement_audits_dal()
+            .get_audit(batch_number)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
     pub async fn get_bytecode_by_hash_impl(
         &self,
         hash: H256,
@@ -702,4 +1049,263 @@ impl ZksNamespace {
             err.into()
         })
     }
+
+    fn simulation_sessions(&self) -> Result<&Mutex<SimulationSessions>, Web3Error> {
+        self.state
+            .simulation_sessions
+            .as_deref()
+            .ok_or(Web3Error::MethodNotImplemented)
+    }
+
+    async fn simulation_session(
+        &self,
+        session_id: U256,
+    ) -> Result<Arc<Mutex<SimulationSession>>, Web3Error> {
+        self.simulation_sessions()?
+            .lock()
+            .await
+            .get(session_id)
+            .ok_or(Web3Error::SimulationSessionNotFound)
+    }
+
+    pub async fn create_simulation_session_impl(
+        &self,
+        block_id: Option<BlockId>,
+    ) -> Result<U256, Web3Error> {
+        let sessions = self.simulation_sessions()?;
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Latest));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+        // Same logic as for `eth_call`: the pending block doesn't have a stored fee input.
+        let fee_input = if block_args.resolves_to_latest_sealed_l2_block() {
+            drop(connection);
+            self.state
+                .tx_sender
+                .0
+                .batch_fee_input_provider
+                .get_batch_fee_input()
+                .await?
+        } else {
+            block_args.historical_fee_input(&mut connection).await?
+        };
+
+        let session = SimulationSession {
+            block_args,
+            fee_input,
+            storage_diff: StorageDiff::default(),
+            transactions: vec![],
+        };
+        Ok(sessions.lock().await.add(session))
+    }
+
+    pub async fn simulate_transaction_impl(
+        &self,
+        session_id: U256,
+        mut request: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> Result<SimulatedTransaction, Web3Error> {
+        let session = self.simulation_session(session_id).await?;
+        // Holding the lock for the entire execution ensures that transactions in the session are executed sequentially.
+        let mut session = session.lock().await;
+        if request.gas.is_none() {
+            let mut connection = self.state.acquire_connection().await?;
+            request.gas = Some(
+                session
+                    .block_args
+                    .default_eth_call_gas(&mut connection)
+                    .await?,
+            );
+        }
+        let call_overrides = request.get_call_overrides()?;
+        let call = L2Tx::from_request(
+            request.into(),
+            self.state.api_config.max_tx_size,
+            session.block_args.use_evm_emulator(),
+        )?;
+        let action = SandboxAction::Call {
+            call,
+            fee_input: session.fee_input,
+            enforced_base_fee: call_overrides.enforced_base_fee,
+            tracing_params: OneshotTracingParams::default(),
+        };
+        self.execute_in_session(&mut session, action, None, state_override)
+            .await
+    }
+
+    pub async fn simulate_raw_transaction_impl(
+        &self,
+        session_id: U256,
+        tx_bytes: Bytes,
+    ) -> Result<SimulatedTransaction, Web3Error> {
+        let session = self.simulation_session(session_id).await?;
+        let mut session = session.lock().await;
+        let (mut tx, hash) = self
+            .state
+            .parse_transaction_bytes(&tx_bytes.0, &session.block_args)?;
+        tx.set_input(tx_bytes.0, hash);
+        let action = SandboxAction::Execution {
+            tx,
+            fee_input: session.fee_input,
+            tracing_params: OneshotTracingParams::default(),
+        };
+        self.execute_in_session(&mut session, action, Some(hash), None)
+            .await
+    }
+
+    async fn execute_in_session(
+        &self,
+        session: &mut SimulationSession,
+        action: SandboxAction,
+        transaction_hash: Option<H256>,
+        state_override: Option<StateOverride>,
+    ) -> Result<SimulatedTransaction, Web3Error> {
+        if session.transactions.len() >= SimulationSession::MAX_TRANSACTIONS {
+            return Err(Web3Error::SimulationSessionFull(
+                SimulationSession::MAX_TRANSACTIONS,
+            ));
+        }
+        let factory_deps = match &action {
+            SandboxAction::Call { call: tx, .. } | SandboxAction::Execution { tx, .. } => {
+                tx.execute.factory_deps.clone()
+            }
+            SandboxAction::GasEstimation { tx, .. } => tx.execute.factory_deps.clone(),
+            SandboxAction::ProtocolUpgrade { tx, .. } => tx.execute.factory_deps.clone(),
+        };
+
+        let vm_permit = self
+            .state
+            .tx_sender
+            .vm_concurrency_limiter()
+            .acquire()
+            .await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+        let connection = self.state.acquire_connection().await?;
+        let executor = &self.state.tx_sender.0.executor;
+        let output = executor
+            .execute_with_storage_diff(
+                vm_permit,
+                connection,
+                action,
+                &session.block_args,
+                Some(&session.storage_diff),
+                state_override,
+            )
+            .await?;
+
+        session
+            .storage_diff
+            .merge_execution(&output.vm, &factory_deps);
+        let index = session.transactions.len();
+        let transaction = Self::map_simulated_transaction(index, transaction_hash, &output.vm);
+        session.transactions.push(transaction.clone());
+        Ok(transaction)
+    }
+
+    fn map_simulated_transaction(
+        index: usize,
+        transaction_hash: Option<H256>,
+        result: &VmExecutionResultAndLogs,
+    ) -> SimulatedTransaction {
+        let (output, revert_reason) = match &result.result {
+            ExecutionResult::Success { output } => (output.clone(), None),
+            ExecutionResult::Revert { output } => (
+                output.encoded_data(),
+                Some(output.to_user_friendly_string()),
+            ),
+            ExecutionResult::Halt { reason } => (vec![], Some(reason.to_string())),
+        };
+        let storage_logs = result
+            .logs
+            .storage_logs
+            .iter()
+            .filter(|log| log.log.is_write())
+            .map(ApiStorageLog::from)
+            .collect();
+        let events = result
+            .logs
+            .events
+            .iter()
+            .enumerate()
+            .map(|(log_index, event)| api::Log {
+                address: event.address,
+                topics: event.indexed_topics.clone(),
+                data: event.value.clone().into(),
+                block_hash: None,
+                block_number: None,
+                l1_batch_number: None,
+                transaction_hash,
+                transaction_index: Some(index.into()),
+                log_index: None,
+                transaction_log_index: Some(log_index.into()),
+                log_type: None,
+                removed: Some(false),
+                block_timestamp: None,
+            })
+            .collect();
+
+        SimulatedTransaction {
+            index: index.into(),
+            transaction_hash,
+            success: !result.result.is_failed(),
+            output: output.into(),
+            revert_reason,
+            gas_used: result.statistics.gas_used.into(),
+            storage_logs,
+            events,
+        }
+    }
+
+    pub async fn get_simulation_session_impl(
+        &self,
+        session_id: U256,
+    ) -> Result<SimulationSessionDetails, Web3Error> {
+        let session = self.simulation_session(session_id).await?;
+        let session = session.lock().await;
+        Ok(SimulationSessionDetails {
+            fork_block_number: session.block_args.state_l2_block_number(),
+            transactions: session.transactions.clone(),
+        })
+    }
+
+    pub async fn get_simulation_storage_at_impl(
+        &self,
+        session_id: U256,
+        address: Address,
+        idx: U256,
+    ) -> Result<H256, Web3Error> {
+        let session = self.simulation_session(session_id).await?;
+        let session = session.lock().await;
+        let storage_key = StorageKey::new(AccountTreeId::new(address), u256_to_h256(idx));
+        if let Some(value) = session.storage_diff.read_value(&storage_key) {
+            return Ok(value);
+        }
+
+        let mut connection = self.state.acquire_connection().await?;
+        let value = connection
+            .storage_web3_dal()
+            .get_historical_value_unchecked(
+                storage_key.hashed_key(),
+                session.block_args.state_l2_block_number(),
+            )
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(value)
+    }
+
+    pub async fn discard_simulation_session_impl(
+        &self,
+        session_id: U256,
+    ) -> Result<bool, Web3Error> {
+        Ok(self.simulation_sessions()?.lock().await.remove(session_id))
+    }
 }
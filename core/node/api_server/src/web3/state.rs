@@ -18,10 +18,12 @@ use zksync_config::{
 };
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
 use zksync_metadata_calculator::api_server::TreeApiClient;
+use zksync_multivm::interface::storage::StorageDiff;
 use zksync_node_sync::SyncState;
 use zksync_types::{
-    api, commitment::L1BatchCommitmentMode, l2::L2Tx, transaction_request::CallRequest, Address,
-    L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId, H256, U256, U64,
+    api, commitment::L1BatchCommitmentMode, fee_model::BatchFeeInput, l2::L2Tx,
+    pruning::PrunedDataClass, settlement::SettlementMode, transaction_request::CallRequest,
+    Address, L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId, H256, U256, U64,
 };
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -91,6 +93,22 @@ impl BlockStartInfo {
             }
         }
     }
+
+    /// Checks whether data of the specified class is retained for the specified L2 block. This check is only meaningful
+    /// for blocks with retained headers (see [`Self::ensure_not_pruned()`]); it can fail for such blocks only
+    /// if the node uses selective pruning.
+    pub(super) async fn ensure_data_not_pruned(
+        &self,
+        class: PrunedDataClass,
+        block_number: L2BlockNumber,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), Web3Error> {
+        let first_l2_block = self.first_l2_block_with(class, storage).await?;
+        if block_number < first_l2_block {
+            return Err(Web3Error::PrunedBlockData(class, first_l2_block));
+        }
+        Ok(())
+    }
 }
 
 /// Configuration values for the API.
@@ -121,6 +139,15 @@ pub struct InternalApiConfig {
     pub dummy_verifier: bool,
     pub l1_batch_commit_data_generator_mode: L1BatchCommitmentMode,
     pub timestamp_asserter_address: Option<Address>,
+    /// Whether the index of transactions by address is maintained by the node.
+    pub address_index_enabled: bool,
+    /// Whether the index of token transfers is maintained by the node.
+    pub token_transfer_index_enabled: bool,
+    /// Max number of simulation sessions kept by the server; if not set, simulation sessions are disabled.
+    pub simulation_sessions_limit: Option<usize>,
+    /// Settlement layer used by the node to send settlement transactions. Not set if the node doesn't send
+    /// settlement transactions.
+    pub settlement_mode: Option<SettlementMode>,
 }
 
 impl InternalApiConfig {
@@ -183,6 +210,12 @@ impl InternalApiConfig {
             dummy_verifier: genesis_config.dummy_verifier,
             l1_batch_commit_data_generator_mode: genesis_config.l1_batch_commit_data_generator_mode,
             timestamp_asserter_address: contracts_config.l2_timestamp_asserter_addr,
+            address_index_enabled: web3_config.address_index_enabled,
+            token_transfer_index_enabled: web3_config.token_transfer_index_enabled,
+            simulation_sessions_limit: web3_config
+                .simulation_sessions_limit
+                .map(|limit| limit as usize),
+            settlement_mode: None,
         }
     }
 }
@@ -256,6 +289,7 @@ impl BridgeAddressesHandle {
 pub(crate) struct RpcState {
     pub(super) current_method: Arc<MethodTracer>,
     pub(super) installed_filters: Option<Arc<Mutex<Filters>>>,
+    pub(super) simulation_sessions: Option<Arc<Mutex<SimulationSessions>>>,
     pub(super) connection_pool: ConnectionPool<Core>,
     pub(super) tree_api: Option<Arc<dyn TreeApiClient>>,
     pub(super) tx_sender: TxSender,
@@ -327,6 +361,21 @@ impl RpcState {
             .ok_or(Web3Error::NoBlock)
     }
 
+    /// Same as [`Self::resolve_block()`], but additionally checks that data of the specified class is retained
+    /// for the resolved block.
+    pub(crate) async fn resolve_block_with_data(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        block: api::BlockId,
+        class: PrunedDataClass,
+    ) -> Result<L2BlockNumber, Web3Error> {
+        let block_number = self.resolve_block(connection, block).await?;
+        self.start_info
+            .ensure_data_not_pruned(class, block_number, connection)
+            .await?;
+        Ok(block_number)
+    }
+
     /// Resolves the specified block ID to a block number, which is **not** guaranteed to be present in the node storage.
     /// Returns `None` if the block is known to not be present in the storage (e.g., it's a "finalized" block ID and no blocks
     /// were finalized yet).
@@ -358,13 +407,22 @@ impl RpcState {
         connection: &mut Connection<'_, Core>,
         block: api::BlockId,
     ) -> Result<BlockArgs, Web3Error> {
-        BlockArgs::new(connection, block, &self.start_info)
+        let block_args = BlockArgs::new(connection, block, &self.start_info)
             .await
             .map_err(|err| match err {
                 BlockArgsError::Pruned(number) => Web3Error::PrunedBlock(number),
                 BlockArgsError::Missing => Web3Error::NoBlock,
                 BlockArgsError::Database(err) => Web3Error::InternalError(err),
-            })
+            })?;
+        // VM execution reads historical storage values, which may be pruned even if the block itself is retained.
+        self.start_info
+            .ensure_data_not_pruned(
+                PrunedDataClass::StateHistory,
+                block_args.resolved_block_number(),
+                connection,
+            )
+            .await?;
+        Ok(block_args)
     }
 
     pub async fn resolve_filter_block_number(
@@ -546,6 +604,57 @@ impl Filters {
     }
 }
 
+/// State of a simulation session created by `zks_createSimulationSession`.
+#[derive(Debug)]
+pub(crate) struct SimulationSession {
+    /// Arguments for the block the session is forked from.
+    pub block_args: BlockArgs,
+    pub fee_input: BatchFeeInput,
+    /// Storage changes produced by transactions executed in the session.
+    pub storage_diff: StorageDiff,
+    pub transactions: Vec<api::SimulatedTransaction>,
+}
+
+impl SimulationSession {
+    /// Max number of transactions that can be executed in a single session.
+    pub const MAX_TRANSACTIONS: usize = 1_000;
+}
+
+/// Contains mapping from index to simulation sessions. Sessions are wrapped in individual mutexes
+/// so that transactions in a session are executed sequentially, without blocking other sessions.
+#[derive(Debug)]
+pub(crate) struct SimulationSessions(LruCache<U256, Arc<Mutex<SimulationSession>>>);
+
+impl SimulationSessions {
+    /// Instantiates `SimulationSessions` with given max capacity.
+    pub fn new(max_cap: usize) -> Self {
+        let max_cap = max_cap
+            .try_into()
+            .expect("Simulation sessions capacity should not be 0");
+        Self(LruCache::new(max_cap))
+    }
+
+    /// Adds a session to the state and returns its key. If the state is full, the least recently used session is evicted.
+    pub fn add(&mut self, session: SimulationSession) -> U256 {
+        let idx = loop {
+            let val = H256::random().to_fixed_bytes().into();
+            if !self.0.contains(&val) {
+                break val;
+            }
+        };
+        self.0.push(idx, Arc::new(Mutex::new(session)));
+        idx
+    }
+
+    pub fn get(&mut self, index: U256) -> Option<Arc<Mutex<SimulationSession>>> {
+        self.0.get(&index).cloned()
+    }
+
+    pub fn remove(&mut self, index: U256) -> bool {
+        self.0.pop(&index).is_some()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::NaiveDateTime;
@@ -1,8 +1,10 @@
 //! Tests for the `debug` Web3 namespace.
 
-use zksync_multivm::interface::{Call, TransactionExecutionResult};
+use std::sync::{Arc, Mutex};
+
+use zksync_multivm::interface::{Call, ExecutionResult, TransactionExecutionResult};
 use zksync_types::{
-    api::{CallTracerConfig, SupportedTracers, TracerConfig},
+    api::{CallTracerConfig, CallTracerResult, SupportedTracers, TracerConfig},
     BOOTLOADER_ADDRESS,
 };
 use zksync_web3_decl::{
@@ -238,6 +240,93 @@ async fn tracing_transaction() {
     test_http_server(TraceTransactionTest).await;
 }
 
+#[derive(Debug)]
+struct TraceBlockWithGasProfilerTest;
+
+#[async_trait]
+impl HttpTest for TraceBlockWithGasProfilerTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [execute_l2_transaction_with_traces(0)];
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let tracer_config = TracerConfig {
+            tracer: SupportedTracers::GasProfiler,
+            tracer_config: CallTracerConfig::default(),
+        };
+        let error = client
+            .trace_block_by_number(api::BlockNumber::from(1), Some(tracer_config))
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+            assert!(error.message().contains("gas profiler"), "{error:?}");
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_block_with_gas_profiler() {
+    test_http_server(TraceBlockWithGasProfilerTest).await;
+}
+
+#[derive(Debug, Default)]
+struct ProfileNonFirstTransactionTest {
+    executed_txs: Arc<Mutex<Vec<H256>>>,
+}
+
+#[async_trait]
+impl HttpTest for ProfileNonFirstTransactionTest {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let executed_txs = self.executed_txs.clone();
+        let mut tx_executor = MockOneshotExecutor::default();
+        tx_executor.set_tx_responses(move |tx, _| {
+            executed_txs.lock().unwrap().push(tx.hash());
+            ExecutionResult::Success { output: vec![] }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [0, 1, 2].map(execute_l2_transaction_with_traces);
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let tracer_config = TracerConfig {
+            tracer: SupportedTracers::GasProfiler,
+            tracer_config: CallTracerConfig::default(),
+        };
+        let result = client
+            .trace_transaction(tx_results[1].hash, Some(tracer_config))
+            .await?
+            .context("no gas profile")?;
+        assert_matches!(result, CallTracerResult::GasProfile(_));
+
+        // The preceding transaction in the block must be replayed before the profiled one.
+        let executed_txs = self.executed_txs.lock().unwrap().clone();
+        assert_eq!(executed_txs, [tx_results[0].hash, tx_results[1].hash]);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn profiling_non_first_transaction_in_block() {
+    test_http_server(ProfileNonFirstTransactionTest::default()).await;
+}
+
 #[derive(Debug)]
 struct TraceBlockTestWithSnapshotRecovery;
 
@@ -28,18 +28,20 @@ use zksync_node_test_utils::{
     l1_batch_metadata_to_commitment_artifacts, prepare_recovery_snapshot,
 };
 use zksync_system_constants::{
-    SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
+    L2_BASE_TOKEN_ADDRESS, SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
 };
 use zksync_types::{
-    api,
+    address_to_h256, api,
     block::{pack_block_info, L2BlockHasher, L2BlockHeader, UnsealedL1BatchHeader},
     bytecode::{
         testonly::{PADDED_EVM_BYTECODE, PROCESSED_EVM_BYTECODE},
         BytecodeHash,
     },
+    ethabi,
     fee_model::{BatchFeeInput, FeeParams},
     get_nonce_key,
     l2::L2Tx,
+    settlement::SettlementMode,
     storage::get_code_key,
     system_contracts::get_system_smart_contracts,
     tokens::{TokenInfo, TokenMetadata},
@@ -164,6 +166,25 @@ trait HttpTest: Send + Sync {
     fn filters_disabled(&self) -> bool {
         false
     }
+
+    /// Overrides the `address_index_enabled` configuration parameter for HTTP server startup
+    fn address_index_enabled(&self) -> bool {
+        false
+    }
+
+    /// Overrides the `token_transfer_index_enabled` configuration parameter for HTTP server startup
+    fn token_transfer_index_enabled(&self) -> bool {
+        false
+    }
+
+    /// Overrides the `simulation_sessions_limit` configuration parameter for HTTP server startup
+    fn simulation_sessions_limit(&self) -> Option<usize> {
+        None
+    }
+    /// Overrides the `settlement_mode` configuration parameter for HTTP server startup
+    fn settlement_mode(&self) -> Option<SettlementMode> {
+        None
+    }
 }
 
 /// Storage initialization strategy.
@@ -286,6 +307,10 @@ async fn test_http_server(test: impl HttpTest) {
     let genesis = GenesisConfig::for_tests();
     let mut api_config = InternalApiConfig::new(&web3_config, &contracts_config, &genesis);
     api_config.filters_disabled = test.filters_disabled();
+    api_config.address_index_enabled = test.address_index_enabled();
+    api_config.token_transfer_index_enabled = test.token_transfer_index_enabled();
+    api_config.simulation_sessions_limit = test.simulation_sessions_limit();
+    api_config.settlement_mode = test.settlement_mode();
     let mut server_builder = TestServerBuilder::new(pool.clone(), api_config)
         .with_tx_executor(test.transaction_executor())
         .with_method_tracer(test.method_tracer());
@@ -838,6 +863,130 @@ async fn getting_transaction_count_for_account() {
     test_http_server(TransactionCountTest).await;
 }
 
+#[derive(Debug)]
+struct TransactionStatusDetailsTest;
+
+#[async_trait]
+impl HttpTest for TransactionStatusDetailsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        let pending_tx = create_l2_transaction(10, 200);
+        let pending_tx_hash = pending_tx.hash();
+        let rejected_tx = create_l2_transaction(10, 200);
+        let rejected_tx_hash = rejected_tx.hash();
+        for tx in [&pending_tx, &rejected_tx] {
+            storage
+                .transactions_dal()
+                .insert_transaction_l2(
+                    tx,
+                    TransactionExecutionMetrics::default(),
+                    ValidationTraces::default(),
+                )
+                .await?;
+        }
+
+        let details = client
+            .get_transaction_status_details(pending_tx_hash)
+            .await?
+            .expect("no transaction details");
+        assert_matches!(details.status, api::TransactionStatus::Pending);
+        assert_eq!(details.initiator_address, pending_tx.initiator_account());
+        assert_eq!(details.pending_reason, None);
+
+        storage
+            .transactions_dal()
+            .set_pending_reason_for_accounts(
+                &[pending_tx.initiator_account()],
+                api::PendingTransactionReason::NonceGap,
+            )
+            .await?;
+        let details = client
+            .get_transaction_status_details(pending_tx_hash)
+            .await?
+            .expect("no transaction details");
+        assert_eq!(
+            details.pending_reason,
+            Some(api::PendingTransactionReason::NonceGap)
+        );
+
+        storage
+            .transactions_dal()
+            .mark_tx_as_rejected(rejected_tx_hash, "rejected: not enough balance")
+            .await?;
+        let details = client
+            .get_transaction_status_details(rejected_tx_hash)
+            .await?
+            .expect("no transaction details");
+        assert_matches!(details.status, api::TransactionStatus::Failed);
+        assert_eq!(details.pending_reason, None);
+        assert_eq!(
+            details.error.as_deref(),
+            Some("rejected: not enough balance")
+        );
+
+        let details = client.get_transaction_status_details(H256::zero()).await?;
+        assert!(details.is_none());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_transaction_status_details() {
+    test_http_server(TransactionStatusDetailsTest).await;
+}
+
+#[derive(Debug)]
+struct L1BatchSettlementAuditTest;
+
+#[async_trait]
+impl HttpTest for L1BatchSettlementAuditTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let audit = client
+            .get_l1_batch_settlement_audit(L1BatchNumber(0))
+            .await?;
+        assert!(audit.is_none());
+
+        use api::SettlementAuditStatus::{Failed, Passed, Pending};
+        pool.connection()
+            .await?
+            .settlement_audits_dal()
+            .save_audit(
+                L1BatchNumber(0),
+                [Passed, Failed, Pending],
+                Some("prove: mismatch"),
+            )
+            .await?;
+        let audit = client
+            .get_l1_batch_settlement_audit(L1BatchNumber(0))
+            .await?
+            .expect("no settlement audit");
+        assert_eq!(audit.number, L1BatchNumber(0));
+        assert_eq!(audit.commit, Passed);
+        assert_eq!(audit.prove, Failed);
+        assert_eq!(audit.execute, Pending);
+        assert_eq!(audit.error.as_deref(), Some("prove: mismatch"));
+
+        let audit = client
+            .get_l1_batch_settlement_audit(L1BatchNumber(1))
+            .await?;
+        assert!(audit.is_none());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_l1_batch_settlement_audit() {
+    test_http_server(L1BatchSettlementAuditTest).await;
+}
+
 #[derive(Debug)]
 struct TransactionCountAfterSnapshotRecoveryTest;
 
@@ -969,6 +1118,361 @@ async fn transaction_receipts() {
     test_http_server(TransactionReceiptsTest).await;
 }
 
+#[derive(Debug)]
+struct TransactionsByAddressTest {
+    address_index_enabled: bool,
+}
+
+#[async_trait]
+impl HttpTest for TransactionsByAddressTest {
+    fn address_index_enabled(&self) -> bool {
+        self.address_index_enabled
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let sender = Address::repeat_byte(0x11);
+        let recipient = Address::repeat_byte(0x22);
+        if !self.address_index_enabled {
+            let err = client
+                .get_transactions_by_address(sender, None)
+                .await
+                .unwrap_err();
+            assert_matches!(err, ClientError::Call(err) if err.code() == ErrorCode::MethodNotFound.code());
+            return Ok(());
+        }
+
+        let mut storage = pool.connection().await?;
+        let mut tx_hashes = vec![];
+        for number in 1..=3 {
+            let mut tx = create_l2_transaction(10, 200);
+            tx.common_data.initiator_address = sender;
+            tx.execute.contract_address = Some(recipient);
+            tx_hashes.push(tx.hash());
+            let tx_results = [execute_l2_transaction(tx)];
+            store_l2_block(&mut storage, L2BlockNumber(number), &tx_results).await?;
+            storage
+                .address_transactions_dal()
+                .insert_address_transactions(L2BlockNumber(number), &tx_results)
+                .await?;
+        }
+        tx_hashes.reverse(); // Transactions are returned from newest to oldest
+
+        let page = client.get_transactions_by_address(sender, None).await?;
+        let returned_hashes: Vec<_> = page.transactions.iter().map(|tx| tx.hash).collect();
+        assert_eq!(returned_hashes, tx_hashes);
+        assert_eq!(page.next_cursor, None);
+
+        // No cursor should be returned if the page is full, but there are no more transactions.
+        let filter = api::AddressTransactionsFilter {
+            limit: Some(3),
+            ..api::AddressTransactionsFilter::default()
+        };
+        let page = client
+            .get_transactions_by_address(sender, Some(filter))
+            .await?;
+        assert_eq!(page.transactions.len(), 3);
+        assert_eq!(page.next_cursor, None);
+
+        let filter = api::AddressTransactionsFilter {
+            direction: api::AddressTransactionDirection::Sent,
+            limit: Some(2),
+            ..api::AddressTransactionsFilter::default()
+        };
+        let page = client
+            .get_transactions_by_address(recipient, Some(filter.clone()))
+            .await?;
+        assert!(page.transactions.is_empty());
+        let page = client
+            .get_transactions_by_address(sender, Some(filter.clone()))
+            .await?;
+        let returned_hashes: Vec<_> = page.transactions.iter().map(|tx| tx.hash).collect();
+        assert_eq!(returned_hashes, tx_hashes[..2]);
+        let next_cursor = page.next_cursor.context("no cursor")?;
+        assert_eq!(next_cursor.block_number, L2BlockNumber(2));
+
+        let filter = api::AddressTransactionsFilter {
+            cursor: Some(next_cursor),
+            ..filter
+        };
+        let page = client
+            .get_transactions_by_address(sender, Some(filter))
+            .await?;
+        let returned_hashes: Vec<_> = page.transactions.iter().map(|tx| tx.hash).collect();
+        assert_eq!(returned_hashes, tx_hashes[2..]);
+        assert_eq!(page.next_cursor, None);
+
+        let filter = api::AddressTransactionsFilter {
+            direction: api::AddressTransactionDirection::Received,
+            from_block: Some(2.into()),
+            to_block: Some(2.into()),
+            ..api::AddressTransactionsFilter::default()
+        };
+        let page = client
+            .get_transactions_by_address(recipient, Some(filter))
+            .await?;
+        let returned_hashes: Vec<_> = page.transactions.iter().map(|tx| tx.hash).collect();
+        assert_eq!(returned_hashes, [tx_hashes[1]]);
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_transactions_by_address() {
+    test_http_server(TransactionsByAddressTest {
+        address_index_enabled: true,
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn getting_transactions_by_address_without_index() {
+    test_http_server(TransactionsByAddressTest {
+        address_index_enabled: false,
+    })
+    .await;
+}
+
+#[derive(Debug)]
+struct TokenTransfersTest {
+    token_transfer_index_enabled: bool,
+}
+
+impl TokenTransfersTest {
+    const ALICE: Address = Address::repeat_byte(0x11);
+    const BOB: Address = Address::repeat_byte(0x22);
+    const TOKEN: Address = Address::repeat_byte(0xfe);
+
+    fn transfer_event(token: Address, from: Address, to: Address, amount: u64) -> VmEvent {
+        let signature = ethabi::long_signature(
+            "Transfer",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+            ],
+        );
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: token,
+            indexed_topics: vec![signature, address_to_h256(&from), address_to_h256(&to)],
+            value: u256_to_h256(amount.into()).0.to_vec(),
+        }
+    }
+
+    fn mint_event(to: Address, amount: u64) -> VmEvent {
+        let signature = ethabi::long_signature(
+            "Mint",
+            &[ethabi::ParamType::Address, ethabi::ParamType::Uint(256)],
+        );
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: L2_BASE_TOKEN_ADDRESS,
+            indexed_topics: vec![signature, address_to_h256(&to)],
+            value: u256_to_h256(amount.into()).0.to_vec(),
+        }
+    }
+
+    async fn store_block_with_transfers(
+        storage: &mut Connection<'_, Core>,
+        number: u32,
+        events: &[VmEvent],
+        alice_balance: u64,
+    ) -> anyhow::Result<()> {
+        let number = L2BlockNumber(number);
+        store_l2_block(storage, number, &[]).await?;
+        let tx_location = IncludedTxLocation {
+            tx_hash: H256::repeat_byte(number.0 as u8),
+            tx_index_in_l2_block: 0,
+            tx_initiator_address: Self::ALICE,
+        };
+        storage
+            .token_transfers_dal()
+            .insert_token_transfers(number, &[(tx_location, events.iter().collect())])
+            .await?;
+        let balance_log = StorageLog::new_write_log(
+            storage_key_for_eth_balance(&Self::ALICE),
+            u256_to_h256(alice_balance.into()),
+        );
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(number, &[balance_log])
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HttpTest for TokenTransfersTest {
+    fn token_transfer_index_enabled(&self) -> bool {
+        self.token_transfer_index_enabled
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        if !self.token_transfer_index_enabled {
+            let err = client
+                .get_token_transfers(Self::ALICE, None)
+                .await
+                .unwrap_err();
+            assert_matches!(err, ClientError::Call(err) if err.code() == ErrorCode::MethodNotFound.code());
+            let err = client
+                .get_balance_history(Self::ALICE, None)
+                .await
+                .unwrap_err();
+            assert_matches!(err, ClientError::Call(err) if err.code() == ErrorCode::MethodNotFound.code());
+            return Ok(());
+        }
+
+        let mut storage = pool.connection().await?;
+        let deposit = Self::mint_event(Self::ALICE, 1_000);
+        Self::store_block_with_transfers(&mut storage, 1, &[deposit], 1_000).await?;
+        let events = [
+            Self::transfer_event(L2_BASE_TOKEN_ADDRESS, Self::ALICE, Self::BOB, 100),
+            Self::transfer_event(Self::TOKEN, Self::BOB, Self::ALICE, 5),
+        ];
+        Self::store_block_with_transfers(&mut storage, 2, &events, 900).await?;
+        let events = [Self::transfer_event(
+            L2_BASE_TOKEN_ADDRESS,
+            Self::ALICE,
+            Self::BOB,
+            50,
+        )];
+        Self::store_block_with_transfers(&mut storage, 3, &events, 850).await?;
+
+        let page = client.get_token_transfers(Self::ALICE, None).await?;
+        let positions: Vec<_> = page
+            .transfers
+            .iter()
+            .map(|transfer| (transfer.block_number.0, transfer.log_index))
+            .collect();
+        assert_eq!(positions, [(3, 0), (2, 1), (2, 0), (1, 0)]);
+        assert_eq!(page.transfers[1].token, Self::TOKEN);
+        assert_eq!(page.transfers[3].from, Address::zero());
+        assert_eq!(page.transfers[3].amount, 1_000.into());
+        assert_eq!(page.next_cursor, None);
+
+        let filter = api::TokenTransfersFilter {
+            token: Some(L2_BASE_TOKEN_ADDRESS),
+            limit: Some(2),
+            ..api::TokenTransfersFilter::default()
+        };
+        let page = client
+            .get_token_transfers(Self::ALICE, Some(filter.clone()))
+            .await?;
+        assert_eq!(page.transfers.len(), 2);
+        let next_cursor = page.next_cursor.context("no cursor")?;
+        assert_eq!(next_cursor.block_number, L2BlockNumber(2));
+        let filter = api::TokenTransfersFilter {
+            cursor: Some(next_cursor),
+            ..filter
+        };
+        let page = client
+            .get_token_transfers(Self::ALICE, Some(filter))
+            .await?;
+        assert_eq!(page.transfers.len(), 1);
+        assert_eq!(page.transfers[0].block_number, L2BlockNumber(1));
+        assert_eq!(page.next_cursor, None);
+
+        let filter = api::TokenTransfersFilter {
+            direction: api::AddressTransactionDirection::Received,
+            ..api::TokenTransfersFilter::default()
+        };
+        let page = client.get_token_transfers(Self::BOB, Some(filter)).await?;
+        let amounts: Vec<_> = page
+            .transfers
+            .iter()
+            .map(|transfer| transfer.amount)
+            .collect();
+        assert_eq!(amounts, [50.into(), 100.into()]);
+
+        let filter = api::BalanceHistoryFilter {
+            limit: Some(2),
+            ..api::BalanceHistoryFilter::default()
+        };
+        let page = client
+            .get_balance_history(Self::ALICE, Some(filter.clone()))
+            .await?;
+        assert_eq!(
+            page.snapshots,
+            [
+                api::BalanceSnapshot {
+                    block_number: L2BlockNumber(3),
+                    received: 0.into(),
+                    sent: 50.into(),
+                    balance: Some(850.into()),
+                },
+                api::BalanceSnapshot {
+                    block_number: L2BlockNumber(2),
+                    received: 0.into(),
+                    sent: 100.into(),
+                    balance: Some(900.into()),
+                },
+            ]
+        );
+        assert_eq!(page.next_cursor, Some(L2BlockNumber(2)));
+        let filter = api::BalanceHistoryFilter {
+            cursor: page.next_cursor,
+            ..filter
+        };
+        let page = client
+            .get_balance_history(Self::ALICE, Some(filter))
+            .await?;
+        assert_eq!(
+            page.snapshots,
+            [api::BalanceSnapshot {
+                block_number: L2BlockNumber(1),
+                received: 1_000.into(),
+                sent: 0.into(),
+                balance: Some(1_000.into()),
+            }]
+        );
+        assert_eq!(page.next_cursor, None);
+
+        // Balances are not provided for tokens other than the base token.
+        let filter = api::BalanceHistoryFilter {
+            token: Some(Self::TOKEN),
+            ..api::BalanceHistoryFilter::default()
+        };
+        let page = client
+            .get_balance_history(Self::ALICE, Some(filter))
+            .await?;
+        assert_eq!(
+            page.snapshots,
+            [api::BalanceSnapshot {
+                block_number: L2BlockNumber(2),
+                received: 5.into(),
+                sent: 0.into(),
+                balance: None,
+            }]
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_token_transfers_and_balance_history() {
+    test_http_server(TokenTransfersTest {
+        token_transfer_index_enabled: true,
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn getting_token_transfers_without_index() {
+    test_http_server(TokenTransfersTest {
+        token_transfer_index_enabled: false,
+    })
+    .await;
+}
+
 #[derive(Debug)]
 struct AllAccountBalancesTest;
 
@@ -1,6 +1,14 @@
 //! Tests for the `unstable` Web3 namespace.
 
-use zksync_types::tee_types::TeeType;
+use zksync_multivm::interface::{ExecutionResult, TxExecutionMode, VmRevertReason};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    protocol_upgrade::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData, ProtocolVersion},
+    protocol_version::ProtocolSemanticVersion,
+    settlement::SettlementMode,
+    tee_types::TeeType,
+    Execute, ExecuteTransactionCommon, SLChainId,
+};
 use zksync_web3_decl::namespaces::UnstableNamespaceClient;
 
 use super::*;
@@ -62,3 +70,283 @@ impl HttpTest for GetTeeProofsTest {
 async fn get_tee_proofs() {
     test_http_server(GetTeeProofsTest::new()).await;
 }
+
+#[derive(Debug)]
+struct SettlementLayerMigrationPreflightTest;
+
+impl SettlementLayerMigrationPreflightTest {
+    fn failed_checks(
+        preflight: &api::SettlementLayerMigrationPreflight,
+    ) -> Vec<api::SettlementLayerMigrationCheck> {
+        preflight
+            .checks
+            .iter()
+            .filter(|check| !check.passed)
+            .map(|check| check.check)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl HttpTest for SettlementLayerMigrationPreflightTest {
+    fn settlement_mode(&self) -> Option<SettlementMode> {
+        Some(SettlementMode::Gateway)
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let status = client.get_settlement_layer_status().await?;
+        assert_eq!(status.configured_layer, Some(SettlementMode::Gateway));
+        assert_eq!(status.current_layer, None);
+        assert_eq!(
+            status.migration_stage,
+            Some(api::SettlementLayerMigrationStage::Completed)
+        );
+        assert!(status.layers.is_empty());
+        let preflight = client
+            .settlement_layer_migration_preflight(SettlementMode::Gateway)
+            .await?;
+        assert!(preflight.ready, "{preflight:?}");
+        assert_eq!(preflight.checks.len(), 4);
+
+        let mut storage = pool.connection().await?;
+        seal_l1_batch(&mut storage, L1BatchNumber(1)).await?;
+        storage
+            .eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(1),
+                AggregatedActionType::Commit,
+                H256::repeat_byte(1),
+                chrono::Utc::now(),
+                Some(SLChainId(9)),
+            )
+            .await?;
+        storage
+            .eth_sender_dal()
+            .save_eth_tx(
+                1,
+                vec![],
+                AggregatedActionType::PublishProofOnchain,
+                Address::default(),
+                None,
+                None,
+                None,
+                false,
+            )
+            .await?;
+
+        let status = client.get_settlement_layer_status().await?;
+        assert_eq!(status.current_layer, Some(SettlementMode::SettlesToL1));
+        assert_eq!(status.current_layer_chain_id, None);
+        assert_eq!(
+            status.migration_stage,
+            Some(api::SettlementLayerMigrationStage::AwaitingNewLayer)
+        );
+        assert_eq!(status.layers.len(), 1);
+        let l1_stats = &status.layers[0];
+        assert_eq!(l1_stats.layer, SettlementMode::SettlesToL1);
+        assert_eq!(l1_stats.last_committed_batch, Some(L1BatchNumber(1)));
+        assert_eq!(l1_stats.last_proven_batch, None);
+        assert_eq!(l1_stats.last_executed_batch, None);
+        assert_eq!(l1_stats.inflight_txs, 1);
+
+        let preflight = client
+            .settlement_layer_migration_preflight(SettlementMode::Gateway)
+            .await?;
+        assert!(!preflight.ready);
+        assert_eq!(preflight.status, status);
+        assert_eq!(
+            Self::failed_checks(&preflight),
+            [
+                api::SettlementLayerMigrationCheck::NoInflightTxs,
+                api::SettlementLayerMigrationCheck::AllBatchesExecuted,
+            ]
+        );
+
+        let preflight = client
+            .settlement_layer_migration_preflight(SettlementMode::SettlesToL1)
+            .await?;
+        assert_eq!(
+            Self::failed_checks(&preflight),
+            [
+                api::SettlementLayerMigrationCheck::NotOnTargetLayer,
+                api::SettlementLayerMigrationCheck::AllBatchesExecuted,
+            ]
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn settlement_layer_migration_preflight() {
+    test_http_server(SettlementLayerMigrationPreflightTest).await;
+}
+
+#[derive(Debug)]
+struct ProtocolUpgradePreflightTest;
+
+impl ProtocolUpgradePreflightTest {
+    const UPGRADE_TX_HASH: H256 = H256::repeat_byte(0x42);
+
+    fn upgrade_tx() -> ProtocolUpgradeTx {
+        ProtocolUpgradeTx {
+            execute: Execute {
+                contract_address: Some(Address::repeat_byte(0x11)),
+                calldata: vec![1, 2, 3],
+                factory_deps: vec![],
+                value: U256::zero(),
+            },
+            common_data: ProtocolUpgradeTxCommonData {
+                upgrade_id: ProtocolVersionId::next(),
+                sender: Address::repeat_byte(1),
+                gas_limit: 1_000_000.into(),
+                gas_per_pubdata_limit: 800.into(),
+                canonical_tx_hash: Self::UPGRADE_TX_HASH,
+                ..ProtocolUpgradeTxCommonData::default()
+            },
+            received_timestamp_ms: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for ProtocolUpgradePreflightTest {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let mut tx_executor = MockOneshotExecutor::default();
+        tx_executor.set_tx_responses(|tx, env| {
+            assert_matches!(
+                &tx.common_data,
+                ExecuteTransactionCommon::ProtocolUpgrade(data)
+                    if data.hash() == Self::UPGRADE_TX_HASH
+            );
+            assert_eq!(env.system.version, ProtocolVersionId::next());
+            // The upgrade transaction must be executed first in a new L1 batch, as in the state keeper.
+            assert_eq!(env.system.execution_mode, TxExecutionMode::VerifyExecute);
+            assert_eq!(env.l1_batch.enforced_base_fee, None);
+            assert!(env.current_block.is_none());
+            ExecutionResult::Revert {
+                output: VmRevertReason::General {
+                    msg: "upgrade failed".to_owned(),
+                    data: vec![],
+                },
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let upgrades = client.get_upcoming_protocol_upgrades().await?;
+        assert_eq!(upgrades, []);
+        let preflight = client
+            .protocol_upgrade_preflight(ProtocolVersionId::next() as u16)
+            .await?;
+        assert_eq!(preflight, None);
+
+        let mut storage = pool.connection().await?;
+        let base_system_contracts_hashes = storage
+            .protocol_versions_dal()
+            .get_base_system_contract_hashes_by_version_id(ProtocolVersionId::latest())
+            .await?
+            .context("no base system contracts for genesis")?;
+        let version = ProtocolVersion {
+            version: ProtocolSemanticVersion {
+                minor: ProtocolVersionId::next(),
+                patch: 0.into(),
+            },
+            timestamp: 1_000,
+            l1_verifier_config: Default::default(),
+            base_system_contracts_hashes,
+            tx: Some(Self::upgrade_tx()),
+        };
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&version)
+            .await?;
+        drop(storage);
+
+        let upgrades = client.get_upcoming_protocol_upgrades().await?;
+        assert_eq!(upgrades.len(), 1);
+        assert_eq!(
+            upgrades[0].minor_version(),
+            Some(ProtocolVersionId::next() as u16)
+        );
+        assert_eq!(upgrades[0].timestamp, 1_000);
+        assert_eq!(
+            upgrades[0].l2_system_upgrade_tx_hash(),
+            Some(Self::UPGRADE_TX_HASH)
+        );
+
+        let preflight = client
+            .protocol_upgrade_preflight(ProtocolVersionId::next() as u16)
+            .await?
+            .context("no preflight for stored version")?;
+        assert_eq!(preflight.minor_version, ProtocolVersionId::next() as u16);
+        assert_eq!(preflight.upgrade_tx_hash, Some(Self::UPGRADE_TX_HASH));
+        assert!(!preflight.ready);
+        let execution = preflight
+            .upgrade_tx_execution
+            .context("upgrade tx was not executed")?;
+        assert!(!execution.success);
+        assert!(
+            execution.error.as_ref().unwrap().contains("upgrade failed"),
+            "{execution:?}"
+        );
+        assert_eq!(preflight.issues.len(), 1);
+
+        // The genesis version is already applied.
+        let preflight = client
+            .protocol_upgrade_preflight(ProtocolVersionId::latest() as u16)
+            .await?
+            .context("no preflight for genesis version")?;
+        assert!(!preflight.ready);
+        assert_eq!(preflight.upgrade_tx_execution, None);
+        assert_eq!(preflight.issues.len(), 1);
+        assert!(
+            preflight.issues[0].contains("not newer than the current version"),
+            "{preflight:?}"
+        );
+
+        // Upgrading to a version after the next one skips the next version.
+        let skipping_version =
+            ProtocolVersionId::try_from(ProtocolVersionId::next() as u16 + 1).unwrap();
+        let mut storage = pool.connection().await?;
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion {
+                version: ProtocolSemanticVersion {
+                    minor: skipping_version,
+                    patch: 0.into(),
+                },
+                timestamp: 2_000,
+                l1_verifier_config: Default::default(),
+                base_system_contracts_hashes: version.base_system_contracts_hashes,
+                tx: None,
+            })
+            .await?;
+        drop(storage);
+
+        let preflight = client
+            .protocol_upgrade_preflight(skipping_version as u16)
+            .await?
+            .context("no preflight for stored version")?;
+        assert!(!preflight.ready);
+        assert_eq!(preflight.issues.len(), 1);
+        assert!(
+            preflight.issues[0].contains("skips intermediate protocol versions"),
+            "{preflight:?}"
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn protocol_upgrade_preflight() {
+    test_http_server(ProtocolUpgradePreflightTest).await;
+}
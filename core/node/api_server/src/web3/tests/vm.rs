@@ -716,6 +716,161 @@ async fn send_raw_transaction_with_detailed_output() {
     test_http_server(SendTransactionWithDetailedOutputTest).await;
 }
 
+#[derive(Debug)]
+struct SimulationSessionTest {
+    sessions_enabled: bool,
+}
+
+impl SimulationSessionTest {
+    const CONTRACT: Address = Address::repeat_byte(2);
+
+    fn slot_key() -> StorageKey {
+        StorageKey::new(AccountTreeId::new(Self::CONTRACT), H256::zero())
+    }
+
+    /// Creates a call writing the specified value to the test slot, or reverting if `value` is `None`.
+    fn call_request(value: Option<u64>) -> CallRequest {
+        let data = match value {
+            Some(value) => u256_to_h256(value.into()).0.to_vec(),
+            None => b"revert".to_vec(),
+        };
+        CallRequest {
+            from: Some(Address::repeat_byte(1)),
+            to: Some(Self::CONTRACT),
+            data: Some(data.into()),
+            gas: Some(123.into()),
+            ..CallRequest::default()
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for SimulationSessionTest {
+    fn simulation_sessions_limit(&self) -> Option<usize> {
+        self.sessions_enabled.then_some(2)
+    }
+
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let mut tx_executor = MockOneshotExecutor::default();
+        tx_executor.set_full_call_responses(|tx, env| {
+            // Sessions are forked from the latest block, and all calls must be executed in the same environment.
+            assert_eq!(env.l1_batch.first_l2_block.number, 1);
+
+            let calldata = tx.execute.calldata();
+            if calldata == b"revert" {
+                return VmExecutionResultAndLogs::mock(ExecutionResult::Revert {
+                    output: VmRevertReason::General {
+                        msg: "oops".to_owned(),
+                        data: vec![],
+                    },
+                });
+            }
+            let write_log = StorageLog::new_write_log(Self::slot_key(), H256::from_slice(calldata));
+            VmExecutionResultAndLogs {
+                logs: VmExecutionLogs {
+                    storage_logs: vec![StorageLogWithPreviousValue {
+                        log: write_log,
+                        previous_value: H256::zero(),
+                    }],
+                    events: vec![VmEvent {
+                        location: (L1BatchNumber(1), 0),
+                        address: Self::CONTRACT,
+                        indexed_topics: vec![H256::repeat_byte(0xaa)],
+                        value: calldata.to_vec(),
+                    }],
+                    ..VmExecutionLogs::default()
+                },
+                ..VmExecutionResultAndLogs::mock_success()
+            }
+        });
+        tx_executor.set_tx_responses(|_, _| ExecutionResult::Success { output: vec![] });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut connection = pool.connection().await?;
+        store_l2_block(&mut connection, L2BlockNumber(1), &[]).await?;
+
+        if !self.sessions_enabled {
+            let err = client.create_simulation_session(None).await.unwrap_err();
+            assert_matches!(err, ClientError::Call(err) if err.code() == ErrorCode::MethodNotFound.code());
+            return Ok(());
+        }
+
+        let session_id = client.create_simulation_session(None).await?;
+        let value = client
+            .get_simulation_storage_at(session_id, Self::CONTRACT, U256::zero())
+            .await?;
+        assert_eq!(value, H256::zero());
+
+        let tx = client
+            .simulate_transaction(session_id, Self::call_request(Some(5)), None)
+            .await?;
+        assert!(tx.success, "{tx:?}");
+        assert_eq!(tx.index, 0.into());
+        assert_eq!(tx.transaction_hash, None);
+        assert_eq!(tx.storage_logs.len(), 1);
+        assert_eq!(tx.events.len(), 1);
+        assert_eq!(tx.events[0].address, Self::CONTRACT);
+        assert_eq!(tx.events[0].transaction_index, Some(0.into()));
+        let value = client
+            .get_simulation_storage_at(session_id, Self::CONTRACT, U256::zero())
+            .await?;
+        assert_eq!(value, u256_to_h256(5.into()));
+
+        let tx = client
+            .simulate_transaction(session_id, Self::call_request(None), None)
+            .await?;
+        assert!(!tx.success);
+        assert_eq!(tx.index, 1.into());
+        assert!(tx.revert_reason.is_some(), "{tx:?}");
+        assert!(tx.storage_logs.is_empty());
+        // The reverted call must not affect the session state.
+        let value = client
+            .get_simulation_storage_at(session_id, Self::CONTRACT, U256::zero())
+            .await?;
+        assert_eq!(value, u256_to_h256(5.into()));
+
+        let (tx_bytes, tx_hash) = SendRawTransactionTest::transaction_bytes_and_hash(true);
+        let tx = client
+            .simulate_raw_transaction(session_id, tx_bytes.into())
+            .await?;
+        assert!(tx.success, "{tx:?}");
+        assert_eq!(tx.index, 2.into());
+        assert_eq!(tx.transaction_hash, Some(tx_hash));
+
+        let session = client.get_simulation_session(session_id).await?;
+        assert_eq!(session.fork_block_number, L2BlockNumber(1));
+        assert_eq!(session.transactions.len(), 3);
+        assert_eq!(session.transactions[0].index, 0.into());
+        assert_eq!(session.transactions[2].transaction_hash, Some(tx_hash));
+
+        // Sessions are independent.
+        let other_session_id = client.create_simulation_session(None).await?;
+        assert_ne!(other_session_id, session_id);
+        let value = client
+            .get_simulation_storage_at(other_session_id, Self::CONTRACT, U256::zero())
+            .await?;
+        assert_eq!(value, H256::zero());
+
+        assert!(client.discard_simulation_session(session_id).await?);
+        assert!(!client.discard_simulation_session(session_id).await?);
+        let err = client.get_simulation_session(session_id).await.unwrap_err();
+        assert_matches!(err, ClientError::Call(err) if err.code() == ErrorCode::InvalidParams.code());
+        Ok(())
+    }
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn simulation_session_basics(sessions_enabled: bool) {
+    test_http_server(SimulationSessionTest { sessions_enabled }).await;
+}
+
 #[derive(Debug, Default)]
 struct TraceCallTest {
     fee_input: ExpectedFeeInput,
@@ -1,4 +1,8 @@
-use std::{path::Path, sync::Arc, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use serde::Serialize;
@@ -9,9 +13,9 @@ use zksync_dal::{ConnectionPool, Core, CoreDal};
 // Public re-export to simplify the API use.
 pub use zksync_eth_client as eth_client;
 use zksync_eth_client::{BoundEthInterface, CallFunctionArgs, EthInterface, Options};
-use zksync_merkle_tree::domain::ZkSyncTree;
+use zksync_merkle_tree::domain::{ZkSyncTree, ZkSyncTreeReader};
 use zksync_object_store::{ObjectStore, ObjectStoreError};
-use zksync_state::RocksdbStorage;
+use zksync_state::{RocksdbStorage, RocksdbStorageBuilder, StateKeeperColumnFamily};
 use zksync_storage::RocksDB;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
@@ -22,7 +26,7 @@ use zksync_types::{
         SnapshotStorageLogsStorageKey,
     },
     web3::BlockNumber,
-    Address, L1BatchNumber, L2ChainId, H160, H256, U256,
+    Address, L1BatchNumber, L2BlockNumber, L2ChainId, H160, H256, U256,
 };
 
 #[cfg(test)]
@@ -153,17 +157,7 @@ impl BlockReverter {
 
     /// Rolls back previously enabled DBs (Postgres + RocksDB) and the snapshot object store to a previous state.
     pub async fn roll_back(&self, last_l1_batch_to_keep: L1BatchNumber) -> anyhow::Result<()> {
-        if !self.allow_rolling_back_executed_batches {
-            let mut storage = self.connection_pool.connection().await?;
-            let last_executed_l1_batch = storage
-                .blocks_dal()
-                .get_number_of_last_l1_batch_executed_on_eth()
-                .await?;
-            anyhow::ensure!(
-                Some(last_l1_batch_to_keep) >= last_executed_l1_batch,
-                "Attempt to roll back already executed L1 batches; the last executed batch is: {last_executed_l1_batch:?}"
-            );
-        }
+        self.check_executed_batches(last_l1_batch_to_keep).await?;
 
         // Tree needs to be rolled back first to keep the state recoverable
         self.roll_back_rocksdb_instances(last_l1_batch_to_keep)
@@ -186,6 +180,265 @@ impl BlockReverter {
         Ok(())
     }
 
+    async fn check_executed_batches(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        if self.allow_rolling_back_executed_batches {
+            return Ok(());
+        }
+        let mut storage = self.connection_pool.connection().await?;
+        let last_executed_l1_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?;
+        anyhow::ensure!(
+            Some(last_l1_batch_to_keep) >= last_executed_l1_batch,
+            "Attempt to roll back already executed L1 batches; the last executed batch is: {last_executed_l1_batch:?}"
+        );
+        Ok(())
+    }
+
+    /// Computes which data would be removed by [`Self::roll_back()`] for the previously enabled DBs and the snapshot
+    /// object store. Doesn't modify any data.
+    pub async fn plan_rollback(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<RollbackPlan> {
+        self.check_executed_batches(last_l1_batch_to_keep).await?;
+
+        let postgres = if self.should_roll_back_postgres {
+            Some(self.plan_postgres_rollback(last_l1_batch_to_keep).await?)
+        } else {
+            None
+        };
+        let merkle_tree = match &self.merkle_tree_path {
+            Some(path) => Self::plan_tree_rollback(last_l1_batch_to_keep, path).await?,
+            None => None,
+        };
+        let mut storage_caches = Vec::with_capacity(self.storage_cache_paths.len());
+        for path in &self.storage_cache_paths {
+            storage_caches
+                .push(Self::plan_storage_cache_rollback(last_l1_batch_to_keep, path).await?);
+        }
+
+        Ok(RollbackPlan {
+            last_l1_batch_to_keep,
+            postgres,
+            merkle_tree,
+            storage_caches,
+        })
+    }
+
+    async fn plan_postgres_rollback(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<PostgresRollbackPlan> {
+        let mut storage = self.connection_pool.connection().await?;
+        let (_, last_l2_block_to_keep) = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(last_l1_batch_to_keep)
+            .await?
+            .with_context(|| {
+                format!("L1 batch #{last_l1_batch_to_keep} doesn't contain L2 blocks")
+            })?;
+        let last_l1_batch = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?
+            .context("no sealed L1 batches in Postgres")?;
+        let last_l2_block = storage
+            .blocks_dal()
+            .get_sealed_l2_block_number()
+            .await?
+            .context("no L2 blocks in Postgres")?;
+
+        let snapshots = storage
+            .snapshots_dal()
+            .get_snapshots_after(last_l1_batch_to_keep)
+            .await?;
+        let snapshot_objects = if self.snapshots_object_store.is_some() {
+            snapshots.iter().map(Self::snapshot_object_count).sum()
+        } else {
+            0
+        };
+
+        Ok(PostgresRollbackPlan {
+            last_l2_block_to_keep,
+            l1_batches: last_l1_batch.0.saturating_sub(last_l1_batch_to_keep.0),
+            l2_blocks: last_l2_block.0.saturating_sub(last_l2_block_to_keep.0),
+            transactions: storage
+                .transactions_dal()
+                .count_transactions_after_l2_block(last_l2_block_to_keep)
+                .await?,
+            events: storage
+                .events_dal()
+                .count_events_after_l2_block(last_l2_block_to_keep)
+                .await?,
+            l2_to_l1_logs: storage
+                .events_dal()
+                .count_l2_to_l1_logs_after_l2_block(last_l2_block_to_keep)
+                .await?,
+            storage_logs: storage
+                .storage_logs_dal()
+                .count_storage_logs_after_l2_block(last_l2_block_to_keep)
+                .await?,
+            factory_deps: storage
+                .factory_deps_dal()
+                .count_factory_deps_after_l2_block(last_l2_block_to_keep)
+                .await?,
+            eth_txs: storage
+                .eth_sender_dal()
+                .count_eth_txs_for_l1_batches_after(last_l1_batch_to_keep)
+                .await?,
+            snapshots: snapshots
+                .iter()
+                .map(|snapshot| snapshot.l1_batch_number)
+                .collect(),
+            snapshot_objects,
+        })
+    }
+
+    /// Returns the number of objects in the object store belonging to a snapshot: factory deps
+    /// and all storage log chunks that were already persisted.
+    fn snapshot_object_count(snapshot: &SnapshotMetadata) -> usize {
+        let storage_log_chunks = snapshot
+            .storage_logs_filepaths
+            .iter()
+            .filter(|path| path.is_some())
+            .count();
+        1 + storage_log_chunks
+    }
+
+    async fn plan_tree_rollback(
+        last_l1_batch_to_keep: L1BatchNumber,
+        merkle_tree_path: &str,
+    ) -> anyhow::Result<Option<MerkleTreeRollbackPlan>> {
+        let path = Path::new(merkle_tree_path);
+        let merkle_tree_exists = fs::try_exists(path).await.with_context(|| {
+            format!(
+                "cannot check whether Merkle tree path `{}` exists",
+                path.display()
+            )
+        })?;
+        if !merkle_tree_exists {
+            tracing::info!("Merkle tree not found at `{}`; skipping", path.display());
+            return Ok(None);
+        }
+
+        let path = path.to_path_buf();
+        let tree = tokio::task::spawn_blocking(move || {
+            // Planning must not modify the DB, so it's opened in the read-only mode.
+            let db =
+                RocksDB::open_read_only(&path).context("failed opening RocksDB for Merkle tree")?;
+            ZkSyncTreeReader::new(db.into()).context("failed initializing Merkle tree reader")
+        })
+        .await
+        .context("opening Merkle tree panicked")??;
+
+        let next_l1_batch_number = tree.next_l1_batch_number();
+        Ok(Some(MerkleTreeRollbackPlan {
+            path: merkle_tree_path.to_owned(),
+            next_l1_batch_number,
+            versions: next_l1_batch_number
+                .0
+                .saturating_sub(last_l1_batch_to_keep.0 + 1),
+            root_hash_to_keep: tree
+                .root_info(last_l1_batch_to_keep)
+                .map(|(root_hash, _)| root_hash),
+        }))
+    }
+
+    async fn plan_storage_cache_rollback(
+        last_l1_batch_to_keep: L1BatchNumber,
+        storage_cache_path: &str,
+    ) -> anyhow::Result<StorageCacheRollbackPlan> {
+        let sk_cache_exists = fs::try_exists(storage_cache_path).await.with_context(|| {
+            format!("cannot check whether storage cache path `{storage_cache_path}` exists")
+        })?;
+        anyhow::ensure!(
+            sk_cache_exists,
+            "Path with storage cache DB doesn't exist at `{storage_cache_path}`"
+        );
+        let path = PathBuf::from(storage_cache_path);
+        let sk_cache = tokio::task::spawn_blocking(move || {
+            RocksDB::<StateKeeperColumnFamily>::open_read_only(&path)
+                .context("failed opening storage cache")
+        })
+        .await
+        .context("opening storage cache panicked")??;
+        let sk_cache = RocksdbStorageBuilder::from_rocksdb(sk_cache);
+        let next_l1_batch_number = sk_cache.l1_batch_number().await;
+        Ok(StorageCacheRollbackPlan {
+            path: storage_cache_path.to_owned(),
+            next_l1_batch_number,
+            l1_batches: next_l1_batch_number.map_or(0, |number| {
+                number.0.saturating_sub(last_l1_batch_to_keep.0 + 1)
+            }),
+        })
+    }
+
+    /// Verifies that all previously enabled DBs are consistent with `last_l1_batch_to_keep` being the last L1 batch,
+    /// e.g. after a [rollback](Self::roll_back()). The Merkle tree and storage caches may lag behind the target L1 batch;
+    /// if the tree contains the target L1 batch, its root hash is checked against Postgres.
+    ///
+    /// Snapshot objects are not checked since their metadata is removed from Postgres by the rollback.
+    pub async fn verify_rollback(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        let plan = self.plan_rollback(last_l1_batch_to_keep).await?;
+        let mut issues = vec![];
+
+        if let Some(postgres) = &plan.postgres {
+            if !postgres.is_empty() {
+                issues.push(format!(
+                    "Postgres contains data after L1 batch #{last_l1_batch_to_keep}: {postgres:?}"
+                ));
+            }
+        }
+        if let Some(tree) = &plan.merkle_tree {
+            if tree.versions > 0 {
+                issues.push(format!(
+                    "Merkle tree at `{}` contains {} versions after L1 batch #{last_l1_batch_to_keep}",
+                    tree.path, tree.versions
+                ));
+            } else if let Some(tree_root_hash) = tree.root_hash_to_keep {
+                let storage_root_hash = self
+                    .connection_pool
+                    .connection()
+                    .await?
+                    .blocks_dal()
+                    .get_l1_batch_state_root(last_l1_batch_to_keep)
+                    .await?;
+                if storage_root_hash != Some(tree_root_hash) {
+                    issues.push(format!(
+                        "Mismatch between the tree root hash {tree_root_hash:?} and storage root hash {storage_root_hash:?} \
+                         for L1 batch #{last_l1_batch_to_keep}"
+                    ));
+                }
+            }
+        }
+        for cache in &plan.storage_caches {
+            if cache.l1_batches > 0 {
+                issues.push(format!(
+                    "Storage cache at `{}` contains {} L1 batches after L1 batch #{last_l1_batch_to_keep}",
+                    cache.path, cache.l1_batches
+                ));
+            }
+        }
+
+        anyhow::ensure!(
+            issues.is_empty(),
+            "Rollback verification failed:\n{}",
+            issues.join("\n")
+        );
+        tracing::info!(
+            "Verified that node state is consistent at L1 batch #{last_l1_batch_to_keep}"
+        );
+        Ok(())
+    }
+
     async fn roll_back_rocksdb_instances(
         &self,
         last_l1_batch_to_keep: L1BatchNumber,
@@ -328,6 +581,16 @@ impl BlockReverter {
             .events_dal()
             .roll_back_l2_to_l1_logs(last_l2_block_to_keep)
             .await?;
+        tracing::info!("Rolling back address transaction index");
+        transaction
+            .address_transactions_dal()
+            .roll_back_address_transactions(last_l2_block_to_keep)
+            .await?;
+        tracing::info!("Rolling back token transfer index");
+        transaction
+            .token_transfers_dal()
+            .roll_back_token_transfers(last_l2_block_to_keep)
+            .await?;
         tracing::info!("Rolling back created tokens");
         transaction
             .tokens_dal()
@@ -640,3 +903,68 @@ pub struct SuggestedRevertValues {
     pub nonce: u64,
     pub priority_fee: u64,
 }
+
+/// Plan of a rollback returned by [`BlockReverter::plan_rollback()`]. Only contains information
+/// on the DBs enabled for the rollback.
+#[derive(Debug, Serialize)]
+pub struct RollbackPlan {
+    pub last_l1_batch_to_keep: L1BatchNumber,
+    /// `None` if rolling back Postgres is disabled.
+    pub postgres: Option<PostgresRollbackPlan>,
+    /// `None` if rolling back the Merkle tree is disabled, or the tree doesn't exist.
+    pub merkle_tree: Option<MerkleTreeRollbackPlan>,
+    pub storage_caches: Vec<StorageCacheRollbackPlan>,
+}
+
+/// Postgres data affected by a rollback.
+#[derive(Debug, Serialize)]
+pub struct PostgresRollbackPlan {
+    pub last_l2_block_to_keep: L2BlockNumber,
+    pub l1_batches: u32,
+    pub l2_blocks: u32,
+    /// Number of transactions that will be reset to the pending state.
+    pub transactions: u64,
+    pub events: u64,
+    pub l2_to_l1_logs: u64,
+    pub storage_logs: u64,
+    pub factory_deps: u64,
+    pub eth_txs: u64,
+    /// L1 batch numbers of removed snapshots.
+    pub snapshots: Vec<L1BatchNumber>,
+    /// Number of objects removed from the snapshot object store. Always 0 if rolling back snapshot objects is disabled.
+    pub snapshot_objects: usize,
+}
+
+impl PostgresRollbackPlan {
+    fn is_empty(&self) -> bool {
+        self.l1_batches == 0
+            && self.l2_blocks == 0
+            && self.transactions == 0
+            && self.events == 0
+            && self.l2_to_l1_logs == 0
+            && self.storage_logs == 0
+            && self.factory_deps == 0
+            && self.eth_txs == 0
+            && self.snapshots.is_empty()
+    }
+}
+
+/// Merkle tree versions removed by a rollback.
+#[derive(Debug, Serialize)]
+pub struct MerkleTreeRollbackPlan {
+    pub path: String,
+    pub next_l1_batch_number: L1BatchNumber,
+    /// Number of removed tree versions (= L1 batches).
+    pub versions: u32,
+    /// Root hash of the tree at the last retained L1 batch, or `None` if the tree lags behind it.
+    pub root_hash_to_keep: Option<H256>,
+}
+
+/// State keeper / VM runner storage cache data removed by a rollback.
+#[derive(Debug, Serialize)]
+pub struct StorageCacheRollbackPlan {
+    pub path: String,
+    /// `None` if the cache is empty.
+    pub next_l1_batch_number: Option<L1BatchNumber>,
+    pub l1_batches: u32,
+}
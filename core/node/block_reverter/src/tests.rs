@@ -148,11 +148,38 @@ async fn block_reverter_basics(sync_merkle_tree: bool) {
         .await
         .unwrap();
 
-    BlockReverter::new(NodeRole::External, pool.clone())
+    let mut block_reverter = BlockReverter::new(NodeRole::External, pool.clone());
+    block_reverter
         .enable_rolling_back_postgres()
         .enable_rolling_back_merkle_tree(merkle_tree_path.to_str().unwrap().to_owned())
-        .add_rocksdb_storage_path_to_rollback(sk_cache_path.to_str().unwrap().to_owned())
-        .roll_back(L1BatchNumber(5))
+        .add_rocksdb_storage_path_to_rollback(sk_cache_path.to_str().unwrap().to_owned());
+
+    let plan = block_reverter
+        .plan_rollback(L1BatchNumber(5))
+        .await
+        .unwrap();
+    let postgres_plan = plan.postgres.unwrap();
+    assert_eq!(postgres_plan.last_l2_block_to_keep, L2BlockNumber(5));
+    assert_eq!(postgres_plan.l1_batches, 4);
+    assert_eq!(postgres_plan.l2_blocks, 4);
+    assert_eq!(postgres_plan.storage_logs, 4);
+    assert_eq!(postgres_plan.transactions, 0);
+    assert_eq!(postgres_plan.snapshots, []);
+    let tree_plan = plan.merkle_tree.unwrap();
+    let expected_tree_versions = if sync_merkle_tree { 4 } else { 1 };
+    assert_eq!(tree_plan.versions, expected_tree_versions);
+    assert_eq!(plan.storage_caches.len(), 1);
+    assert_eq!(plan.storage_caches[0].l1_batches, 4);
+    // The plan must not modify any data.
+    let err = block_reverter
+        .verify_rollback(L1BatchNumber(5))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Postgres"), "{err:#}");
+
+    block_reverter.roll_back(L1BatchNumber(5)).await.unwrap();
+    block_reverter
+        .verify_rollback(L1BatchNumber(5))
         .await
         .unwrap();
 
@@ -283,6 +310,15 @@ async fn reverting_snapshot(remove_objects: bool) {
     if remove_objects {
         block_reverter.enable_rolling_back_snapshot_objects(object_store.clone());
     }
+    let plan = block_reverter
+        .plan_rollback(L1BatchNumber(5))
+        .await
+        .unwrap();
+    let postgres_plan = plan.postgres.unwrap();
+    assert_eq!(postgres_plan.snapshots, [L1BatchNumber(7)]);
+    let expected_snapshot_objects = if remove_objects { 6 } else { 0 };
+    assert_eq!(postgres_plan.snapshot_objects, expected_snapshot_objects);
+
     block_reverter.roll_back(L1BatchNumber(5)).await.unwrap();
 
     // Check that snapshot has been removed.
@@ -6,7 +6,9 @@ use zksync_consensus_executor::{self as executor, attestation};
 use zksync_consensus_roles::{attester, validator};
 use zksync_consensus_storage::{BlockStore, PersistentBlockStore as _};
 use zksync_dal::consensus_dal;
-use zksync_node_sync::{fetcher::FetchedBlock, sync_action::ActionQueueSender, SyncState};
+use zksync_node_sync::{
+    fetcher::FetchedBlock, sync_action::ActionQueueSender, L2BlockHashCrossChecker, SyncState,
+};
 use zksync_types::L2BlockNumber;
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -26,11 +28,18 @@ use crate::{
 /// the fallback fetcher is active.
 pub(crate) const FALLBACK_FETCHER_THRESHOLD: u64 = 10;
 
+/// Maximum number of consecutive attempts to fetch a block whose hash is disputed by main node client upstreams.
+/// Since attempts are spaced by a few seconds, exceeding this number indicates a persistent divergence
+/// (e.g., a misconfigured upstream) that requires manual intervention.
+pub(crate) const MAX_HASH_MISMATCHES: usize = 10;
+
 /// External node.
 pub(super) struct EN {
     pub(super) pool: ConnectionPool,
     pub(super) sync_state: SyncState,
     pub(super) client: Box<DynClient<L2>>,
+    /// If set, hashes of fetched blocks are cross-checked between all main node client upstreams.
+    pub(super) block_hash_cross_checker: Option<L2BlockHashCrossChecker>,
 }
 
 impl EN {
@@ -360,7 +369,12 @@ impl EN {
         .context("deserialize()")?)
     }
 
-    /// Fetches (with retries) the given block from the main node.
+    /// Fetches (with retries) the given block from the main node. If a block hash cross-checker is configured,
+    /// the block is only returned once its hash is not disputed by any upstream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the block hash is disputed on [`MAX_HASH_MISMATCHES`] consecutive attempts.
     async fn fetch_block(
         &self,
         ctx: &ctx::Ctx,
@@ -369,9 +383,37 @@ impl EN {
         const RETRY_INTERVAL: time::Duration = time::Duration::seconds(5);
         let n = L2BlockNumber(n.0.try_into().context("overflow")?);
         METRICS.fetch_block.inc();
+        let mut hash_mismatches = 0;
         loop {
-            match ctx.wait(self.client.sync_l2_block(n, true)).await? {
-                Ok(Some(block)) => return Ok(block.try_into()?),
+            // If the cross-checker is configured, the block is fetched via it in order to know its source upstream.
+            let (result, checker) = match &self.block_hash_cross_checker {
+                Some(checker) => {
+                    let (result, source) = ctx.wait(checker.sync_l2_block(n, true)).await?;
+                    (result, Some((checker, source)))
+                }
+                None => (ctx.wait(self.client.sync_l2_block(n, true)).await?, None),
+            };
+            match result {
+                Ok(Some(block)) => {
+                    let Some((checker, source)) = checker else {
+                        return Ok(block.try_into()?);
+                    };
+                    match ctx.wait(checker.check(&block, &source)).await? {
+                        Ok(()) => return Ok(block.try_into()?),
+                        Err(err) => {
+                            METRICS.block_hash_mismatches.inc();
+                            hash_mismatches += 1;
+                            if hash_mismatches >= MAX_HASH_MISMATCHES {
+                                return Err(err
+                                    .context(format!(
+                                        "hash of L2 block #{n} is disputed after {hash_mismatches} attempts"
+                                    ))
+                                    .into());
+                            }
+                            tracing::warn!("Failed cross-checking L2 block #{n}: {err:#}");
+                        }
+                    }
+                }
                 Ok(None) => {}
                 Err(err) if is_retriable(&err) => {}
                 Err(err) => Err(err).with_context(|| format!("client.sync_l2_block({n})"))?,
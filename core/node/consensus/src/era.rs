@@ -7,7 +7,7 @@
 use zksync_concurrency::ctx;
 use zksync_config::configs::consensus::{ConsensusConfig, ConsensusSecrets};
 use zksync_dal::Core;
-use zksync_node_sync::{sync_action::ActionQueueSender, SyncState};
+use zksync_node_sync::{sync_action::ActionQueueSender, L2BlockHashCrossChecker, SyncState};
 use zksync_web3_decl::client::{DynClient, L2};
 
 use super::{en, mn, storage::ConnectionPool};
@@ -44,6 +44,7 @@ pub async fn run_external_node(
     pool: zksync_dal::ConnectionPool<Core>,
     sync_state: SyncState,
     main_node_client: Box<DynClient<L2>>,
+    block_hash_cross_checker: Option<L2BlockHashCrossChecker>,
     actions: ActionQueueSender,
     build_version: semver::Version,
 ) -> anyhow::Result<()> {
@@ -51,6 +52,7 @@ pub async fn run_external_node(
         pool: ConnectionPool(pool),
         sync_state: sync_state.clone(),
         client: main_node_client.for_component("block_fetcher"),
+        block_hash_cross_checker,
     };
     let res = match cfg {
         Some((cfg, secrets)) => {
@@ -7,6 +7,8 @@ pub(crate) struct Metrics {
     /// It is used only as a fallback when the p2p syncing is disabled or falling behind.
     /// so it shouldn't be increasing under normal circumstances if p2p syncing is enabled.
     pub fetch_block: vise::Counter,
+    /// Number of blocks fetched via JSON-RPC whose hash was disputed by one of the main node client upstreams.
+    pub block_hash_mismatches: vise::Counter,
 }
 
 #[vise::register]
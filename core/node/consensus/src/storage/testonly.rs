@@ -7,8 +7,8 @@ use zksync_dal::CoreDal as _;
 use zksync_node_genesis::{insert_genesis_batch, mock_genesis_config, GenesisParams};
 use zksync_node_test_utils::{recover, snapshot, Snapshot};
 use zksync_types::{
-    protocol_version::ProtocolSemanticVersion, system_contracts::get_system_smart_contracts,
-    L1BatchNumber, L2BlockNumber, ProtocolVersionId,
+    protocol_version::ProtocolSemanticVersion, pruning::PruningProfile,
+    system_contracts::get_system_smart_contracts, L1BatchNumber, L2BlockNumber, ProtocolVersionId,
 };
 
 use super::{Connection, ConnectionPool};
@@ -258,24 +258,25 @@ impl ConnectionPool {
             .context("get_l1_batch_state_root()")?
             .unwrap_or_default();
         let last_block = L2BlockNumber(last_block.0.try_into().context("overflow")?);
-        ctx.wait(
-            conn.0
-                .pruning_dal()
-                .insert_soft_pruning_log(last_batch, last_block),
-        )
+        ctx.wait(conn.0.pruning_dal().insert_soft_pruning_log(
+            last_batch,
+            last_block,
+            PruningProfile::Full,
+        ))
         .await?
         .context("insert_soft_pruning_log()")?;
-        ctx.wait(
-            conn.0
-                .pruning_dal()
-                .hard_prune_batches_range(last_batch, last_block),
-        )
+        ctx.wait(conn.0.pruning_dal().hard_prune_batches_range(
+            last_batch,
+            last_block,
+            PruningProfile::Full,
+        ))
         .await?
         .context("hard_prune_batches_range()")?;
         ctx.wait(conn.0.pruning_dal().insert_hard_pruning_log(
             last_batch,
             last_block,
             last_batch_root_hash,
+            PruningProfile::Full,
         ))
         .await?
         .context("insert_hard_pruning_log()")?;
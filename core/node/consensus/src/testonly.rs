@@ -406,6 +406,7 @@ impl StateKeeper {
         en::EN {
             pool: self.pool,
             client,
+            block_hash_cross_checker: None,
             sync_state: self.sync_state.clone(),
         }
         .run_fetcher(ctx, self.actions_sender)
@@ -422,6 +423,7 @@ impl StateKeeper {
         en::EN {
             pool: self.pool,
             client,
+            block_hash_cross_checker: None,
             sync_state: self.sync_state.clone(),
         }
         .run(
@@ -0,0 +1,151 @@
+//! Archiving of pruned data.
+
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_dal::{pruning_dal::PruningInfo, Connection, ConnectionPool, Core, CoreDal};
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    pruning::{PrunedDataArchive, PrunedDataArchiveKey, PrunedL2Block},
+    L1BatchNumber, L2BlockNumber, L2ChainId, H256,
+};
+
+use crate::metrics::METRICS;
+
+/// Exports data that is about to be hard-pruned into an object store.
+#[derive(Debug)]
+pub(crate) struct PrunedDataArchiver {
+    object_store: Arc<dyn ObjectStore>,
+    l2_chain_id: L2ChainId,
+}
+
+impl PrunedDataArchiver {
+    pub fn new(object_store: Arc<dyn ObjectStore>, l2_chain_id: L2ChainId) -> Self {
+        Self {
+            object_store,
+            l2_chain_id,
+        }
+    }
+
+    /// Archives data in the range starting after the last hard-pruned L1 batch / L2 block (or from the start
+    /// of the stored data if nothing was hard-pruned yet) and ending with the specified L1 batch / L2 block.
+    /// Returns the key of the archive in the object store.
+    ///
+    /// A DB connection is only held while the archived data is loaded; it is released before uploading the archive.
+    pub async fn archive(
+        &self,
+        pool: &ConnectionPool<Core>,
+        pruning_info: &PruningInfo,
+        last_l1_batch: L1BatchNumber,
+        last_l2_block: L2BlockNumber,
+        last_l1_batch_root_hash: H256,
+    ) -> anyhow::Result<String> {
+        let latency = METRICS.archiving_duration.start();
+        let mut storage = pool.connection_tagged("db_pruner").await?;
+        let first_l1_batch = match pruning_info.last_hard_pruned {
+            Some(info) => info.l1_batch + 1,
+            None => storage
+                .blocks_dal()
+                .get_earliest_l1_batch_number()
+                .await?
+                .unwrap_or(last_l1_batch),
+        };
+        let first_l2_block = match pruning_info.last_hard_pruned {
+            Some(info) => info.l2_block + 1,
+            None => storage
+                .blocks_dal()
+                .get_earliest_l2_block_number()
+                .await?
+                .unwrap_or(last_l2_block),
+        };
+
+        let mut l1_batches = Vec::with_capacity((last_l1_batch.0 + 1 - first_l1_batch.0) as usize);
+        for number in first_l1_batch.0..=last_l1_batch.0 {
+            let number = L1BatchNumber(number);
+            let details = storage
+                .blocks_web3_dal()
+                .get_l1_batch_details(number)
+                .await?
+                .with_context(|| format!("L1 batch #{number} to be archived is missing"))?;
+            l1_batches.push(details);
+        }
+
+        let mut l2_blocks = Vec::with_capacity((last_l2_block.0 + 1 - first_l2_block.0) as usize);
+        for number in first_l2_block.0..=last_l2_block.0 {
+            l2_blocks.push(
+                self.export_l2_block(&mut storage, L2BlockNumber(number))
+                    .await?,
+            );
+        }
+        drop(storage);
+
+        let archive = PrunedDataArchive {
+            version: PrunedDataArchive::CURRENT_VERSION,
+            first_l1_batch,
+            last_l1_batch,
+            first_l2_block,
+            last_l2_block,
+            last_l1_batch_root_hash,
+            l1_batches,
+            l2_blocks,
+        };
+        let key = PrunedDataArchiveKey {
+            first_l1_batch,
+            last_l1_batch,
+        };
+        let key = self
+            .object_store
+            .put(key, &archive)
+            .await
+            .context("failed persisting pruned data archive")?;
+
+        let latency = latency.observe();
+        METRICS
+            .archived_l2_blocks
+            .inc_by(archive.l2_blocks.len() as u64);
+        tracing::info!(
+            "Archived L1 batches #{first_l1_batch}..=#{last_l1_batch} (L2 blocks #{first_l2_block}..=#{last_l2_block}) \
+             to `{}/{key}`, operation took {latency:?}",
+            self.object_store.get_storage_prefix::<PrunedDataArchive>()
+        );
+        Ok(key)
+    }
+
+    async fn export_l2_block(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        number: L2BlockNumber,
+    ) -> anyhow::Result<PrunedL2Block> {
+        let block = storage
+            .blocks_web3_dal()
+            .get_api_block(number)
+            .await?
+            .with_context(|| format!("L2 block #{number} to be archived is missing"))?;
+
+        let mut transactions = storage
+            .transactions_web3_dal()
+            .get_transactions(&block.transactions, self.l2_chain_id)
+            .await?;
+        transactions.sort_unstable_by_key(|tx| tx.transaction_index);
+        let mut receipts = storage
+            .transactions_web3_dal()
+            .get_transaction_receipts(&block.transactions)
+            .await?;
+        receipts.sort_unstable_by_key(|receipt| receipt.transaction_index);
+
+        anyhow::ensure!(
+            transactions.len() == block.transactions.len()
+                && receipts.len() == block.transactions.len(),
+            "L2 block #{number} to be archived is inconsistent: it references {} transactions, \
+             but {} transactions and {} receipts were loaded",
+            block.transactions.len(),
+            transactions.len(),
+            receipts.len()
+        );
+        Ok(PrunedL2Block {
+            block,
+            transactions,
+            receipts,
+        })
+    }
+}
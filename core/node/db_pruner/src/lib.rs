@@ -13,9 +13,11 @@ use zksync_dal::{
     Connection, ConnectionPool, Core, CoreDal,
 };
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
-use zksync_types::{L1BatchNumber, L2BlockNumber};
+use zksync_object_store::ObjectStore;
+use zksync_types::{pruning::PruningProfile, L1BatchNumber, L2BlockNumber, L2ChainId};
 
 use self::{
+    archive::PrunedDataArchiver,
     metrics::{ConditionOutcome, PruneType, METRICS},
     prune_conditions::{
         ConsistencyCheckerProcessedBatch, L1BatchExistsCondition, L1BatchOlderThanPruneCondition,
@@ -23,6 +25,7 @@ use self::{
     },
 };
 
+mod archive;
 mod metrics;
 mod prune_conditions;
 #[cfg(test)]
@@ -39,6 +42,8 @@ pub struct DbPrunerConfig {
     /// Minimum age of an L1 batch in order for it to be eligible for pruning. Setting this to zero
     /// will effectively disable this pruning criterion.
     pub minimum_l1_batch_age: Duration,
+    /// Pruning profile determining which data classes are removed.
+    pub profile: PruningProfile,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -82,6 +87,7 @@ pub struct DbPruner {
     connection_pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     prune_conditions: Vec<Arc<dyn PruneCondition>>,
+    archiver: Option<PrunedDataArchiver>,
 }
 
 impl DbPruner {
@@ -121,9 +127,23 @@ impl DbPruner {
             connection_pool,
             health_updater: ReactiveHealthCheck::new("db_pruner").1,
             prune_conditions,
+            archiver: None,
         }
     }
 
+    /// Enables archiving data to the provided object store before it is hard-pruned. Archives are stored
+    /// in the [`PrunedDataArchive`](zksync_types::pruning::PrunedDataArchive) format; their keys are recorded
+    /// in the pruning log. If archiving fails, data is not pruned.
+    #[must_use]
+    pub fn with_archive(
+        mut self,
+        object_store: Arc<dyn ObjectStore>,
+        l2_chain_id: L2ChainId,
+    ) -> Self {
+        self.archiver = Some(PrunedDataArchiver::new(object_store, l2_chain_id));
+        self
+    }
+
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
     }
@@ -190,7 +210,7 @@ impl DbPruner {
         let start = Instant::now();
         let mut transaction = storage.start_transaction().await?;
 
-        let mut current_pruning_info = transaction.pruning_dal().get_pruning_info().await?;
+        let mut current_pruning_info = transaction.pruning_dal().get_pruning_progress().await?;
         let next_l1_batch_to_prune = current_pruning_info
             .last_soft_pruned
             .map_or(L1BatchNumber(0), |info| info.l1_batch)
@@ -207,7 +227,11 @@ impl DbPruner {
             .with_context(|| format!("L1 batch #{next_l1_batch_to_prune} is ready to be pruned, but has no L2 blocks"))?;
         transaction
             .pruning_dal()
-            .insert_soft_pruning_log(next_l1_batch_to_prune, next_l2_block_to_prune)
+            .insert_soft_pruning_log(
+                next_l1_batch_to_prune,
+                next_l2_block_to_prune,
+                self.config.profile,
+            )
             .await?;
 
         transaction.commit().await?;
@@ -228,18 +252,17 @@ impl DbPruner {
 
     async fn hard_prune(
         &self,
-        storage: &mut Connection<'_, Core>,
         stop_receiver: &mut watch::Receiver<bool>,
     ) -> anyhow::Result<PruningIterationOutcome> {
         let latency = METRICS.pruning_chunk_duration[&PruneType::Hard].start();
-        let mut transaction = storage.start_transaction().await?;
+        let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
 
-        let mut current_pruning_info = transaction.pruning_dal().get_pruning_info().await?;
+        let mut current_pruning_info = storage.pruning_dal().get_pruning_progress().await?;
         let soft_pruned = current_pruning_info.last_soft_pruned.with_context(|| {
             format!("bogus pruning info {current_pruning_info:?}: trying to hard-prune data, but there is no soft-pruned data")
         })?;
 
-        let last_pruned_l1_batch_root_hash = transaction
+        let last_pruned_l1_batch_root_hash = storage
             .blocks_dal()
             .get_l1_batch_state_root(soft_pruned.l1_batch)
             .await?
@@ -250,11 +273,34 @@ impl DbPruner {
                 )
             })?;
 
+        drop(storage);
+
+        // The archive is uploaded before the pruning transaction is started, so that a slow or failing upload
+        // doesn't hold locks on the pruned data. Soft-pruned data doesn't change, so the archive cannot get stale.
+        let archive_location = if let Some(archiver) = &self.archiver {
+            let location = archiver
+                .archive(
+                    &self.connection_pool,
+                    &current_pruning_info,
+                    soft_pruned.l1_batch,
+                    soft_pruned.l2_block,
+                    last_pruned_l1_batch_root_hash,
+                )
+                .await
+                .context("failed archiving data before hard pruning")?;
+            Some(location)
+        } else {
+            None
+        };
+
+        let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
+        let mut transaction = storage.start_transaction().await?;
         let mut dal = transaction.pruning_dal();
         let stats = tokio::select! {
             result = dal.hard_prune_batches_range(
                 soft_pruned.l1_batch,
                 soft_pruned.l2_block,
+                self.config.profile,
             ) => result?,
 
             _ = stop_receiver.changed() => {
@@ -271,8 +317,13 @@ impl DbPruner {
             soft_pruned.l1_batch,
             soft_pruned.l2_block,
             last_pruned_l1_batch_root_hash,
+            self.config.profile,
         )
         .await?;
+        if let Some(location) = &archive_location {
+            dal.set_hard_pruning_archive_location(soft_pruned.l1_batch, location)
+                .await?;
+        }
         transaction.commit().await?;
 
         let latency = latency.observe();
@@ -292,7 +343,7 @@ impl DbPruner {
         stop_receiver: &mut watch::Receiver<bool>,
     ) -> anyhow::Result<PruningIterationOutcome> {
         let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
-        let current_pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let current_pruning_info = storage.pruning_dal().get_pruning_progress().await?;
         self.update_health(current_pruning_info);
 
         // If this `if` is not entered, it means that the node has restarted after soft pruning
@@ -311,15 +362,15 @@ impl DbPruner {
             return Ok(PruningIterationOutcome::Interrupted);
         }
 
-        let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
-        self.hard_prune(&mut storage, stop_receiver).await
+        self.hard_prune(stop_receiver).await
     }
 
     pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let next_iteration_delay = self.config.removal_delay / 2;
         tracing::info!(
-            "Starting Postgres pruning with configuration {:?}, prune conditions {:?}",
+            "Starting Postgres pruning with configuration {:?}, archiver {:?}, prune conditions {:?}",
             self.config,
+            self.archiver,
             self.prune_conditions
                 .iter()
                 .map(ToString::to_string)
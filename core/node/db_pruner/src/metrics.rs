@@ -58,6 +58,11 @@ pub(super) struct DbPrunerMetrics {
     deleted_entities: Family<PrunedEntityType, Histogram<u64>>,
     /// Number of times a certain condition has resulted in a specific outcome (succeeded, failed, or errored).
     condition_outcomes: Family<ConditionOutcomeLabels, Counter>,
+    /// Latency of archiving data before a single hard pruning iteration.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub archiving_duration: Histogram<Duration>,
+    /// Total number of L2 blocks archived before hard pruning.
+    pub archived_l2_blocks: Counter,
 }
 
 impl DbPrunerMetrics {
@@ -11,8 +11,11 @@ use zksync_node_test_utils::{
     create_l1_batch, create_l1_batch_metadata, create_l2_block,
     l1_batch_metadata_to_commitment_artifacts,
 };
+use zksync_object_store::MockObjectStore;
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, L2BlockNumber, ProtocolVersion, H256,
+    aggregated_operations::AggregatedActionType,
+    pruning::{PrunedDataArchive, PrunedDataArchiveKey, PruningProfile},
+    L2BlockNumber, ProtocolVersion, H256,
 };
 
 use super::*;
@@ -78,6 +81,7 @@ async fn is_l1_batch_prunable_works() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 1,
             minimum_l1_batch_age: Duration::ZERO,
+            profile: PruningProfile::Full,
         },
         ConnectionPool::test_pool().await,
         vec![failing_check, other_failing_check],
@@ -138,7 +142,7 @@ async fn hard_pruning_ignores_conditions_checks() {
 
     insert_l2_blocks(&mut conn, 10, 2).await;
     conn.pruning_dal()
-        .insert_soft_pruning_log(L1BatchNumber(2), L2BlockNumber(5))
+        .insert_soft_pruning_log(L1BatchNumber(2), L2BlockNumber(5), PruningProfile::Full)
         .await
         .unwrap();
 
@@ -148,6 +152,7 @@ async fn hard_pruning_ignores_conditions_checks() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            profile: PruningProfile::Full,
         },
         pool.clone(),
         vec![nothing_prunable_check],
@@ -188,7 +193,7 @@ async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignorin
     let mut conn = pool.connection().await.unwrap();
     insert_l2_blocks(&mut conn, 10, 2).await;
     conn.pruning_dal()
-        .insert_soft_pruning_log(L1BatchNumber(2), L2BlockNumber(5))
+        .insert_soft_pruning_log(L1BatchNumber(2), L2BlockNumber(5), PruningProfile::Full)
         .await
         .unwrap();
 
@@ -197,6 +202,7 @@ async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignorin
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            profile: PruningProfile::Full,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -235,6 +241,7 @@ async fn unconstrained_pruner_with_fresh_database() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            profile: PruningProfile::Full,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -261,6 +268,70 @@ async fn unconstrained_pruner_with_fresh_database() {
     );
 }
 
+#[test(tokio::test)]
+async fn pruner_archives_data_before_hard_pruning() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+
+    let object_store = MockObjectStore::arc();
+    let pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 3,
+            minimum_l1_batch_age: Duration::ZERO,
+            profile: PruningProfile::Full,
+        },
+        pool.clone(),
+        vec![], //No checks, so every batch is prunable
+    )
+    .with_archive(object_store.clone(), L2ChainId::default());
+
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    for _ in 0..2 {
+        pruner
+            .run_single_iteration(&mut stop_receiver)
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        test_pruning_info(6, 13),
+        conn.pruning_dal().get_pruning_info().await.unwrap()
+    );
+
+    let archives = conn.pruning_dal().get_pruned_data_archives().await.unwrap();
+    let locations: Vec<_> = archives.iter().map(|info| info.location.as_str()).collect();
+    assert_eq!(
+        locations,
+        [
+            "pruned_l1_batches_0_3.json.gzip",
+            "pruned_l1_batches_4_6.json.gzip"
+        ]
+    );
+
+    let key = PrunedDataArchiveKey {
+        first_l1_batch: L1BatchNumber(4),
+        last_l1_batch: L1BatchNumber(6),
+    };
+    let archive: PrunedDataArchive = object_store.get(key).await.unwrap();
+    assert_eq!(archive.version, PrunedDataArchive::CURRENT_VERSION);
+    assert_eq!(archive.first_l2_block, L2BlockNumber(8));
+    assert_eq!(archive.last_l2_block, L2BlockNumber(13));
+    assert_eq!(archive.last_l1_batch_root_hash, H256::from_low_u64_be(6));
+    let l1_batch_numbers: Vec<_> = archive
+        .l1_batches
+        .iter()
+        .map(|batch| batch.number.0)
+        .collect();
+    assert_eq!(l1_batch_numbers, [4, 5, 6]);
+    let l2_block_numbers: Vec<_> = archive
+        .l2_blocks
+        .iter()
+        .map(|block| block.block.number.as_u32())
+        .collect();
+    assert_eq!(l2_block_numbers, [8, 9, 10, 11, 12, 13]);
+}
+
 #[test(tokio::test)]
 async fn pruning_blocked_after_first_chunk() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -275,6 +346,7 @@ async fn pruning_blocked_after_first_chunk() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            profile: PruningProfile::Full,
         },
         pool.clone(),
         vec![first_chunk_prunable_check],
@@ -315,6 +387,7 @@ async fn pruner_is_resistant_to_errors() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            profile: PruningProfile::Full,
         },
         pool.clone(),
         vec![erroneous_condition],
@@ -485,6 +558,7 @@ async fn pruner_with_real_conditions() {
         removal_delay: Duration::from_millis(10), // non-zero to not have a tight loop in `DbPruner::run()`
         pruned_batch_chunk_size: 1,
         minimum_l1_batch_age: Duration::ZERO,
+        profile: PruningProfile::Full,
     };
     let pruner = DbPruner::new(config, pool.clone());
     let mut health_check = pruner.health_check();
@@ -541,6 +615,7 @@ async fn pruning_iteration_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            profile: PruningProfile::Full,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -570,6 +645,7 @@ async fn pruner_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            profile: PruningProfile::Full,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
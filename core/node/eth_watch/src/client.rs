@@ -39,12 +39,27 @@ pub trait EthClient: 'static + fmt::Debug + Send + Sync {
         retries_left: usize,
     ) -> EnrichedClientResult<Vec<Log>>;
 
+    /// Returns events emitted by the specified `contract` in a given block range. Unlike [`Self::get_events()`],
+    /// the contract doesn't need to be one of the core contracts known to the client.
+    async fn get_contract_events(
+        &self,
+        contract: Address,
+        from: BlockNumber,
+        to: BlockNumber,
+        topic1: H256,
+        topic2: Option<H256>,
+        retries_left: usize,
+    ) -> EnrichedClientResult<Vec<Log>>;
+
     /// Returns either finalized L1 block number or block number that satisfies `self.confirmations_for_eth_event` if it's set.
     async fn confirmed_block_number(&self) -> EnrichedClientResult<u64>;
 
     /// Returns finalized L1 block number.
     async fn finalized_block_number(&self) -> EnrichedClientResult<u64>;
 
+    /// Returns the hash of the block with the specified number, or `None` if there is no such block.
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>>;
+
     async fn get_total_priority_txs(&self) -> Result<u64, ContractCallError>;
     /// Returns scheduler verification key hash by verifier address.
     async fn scheduler_vk_hash(&self, verifier_address: Address)
@@ -356,6 +371,26 @@ where
         .await
     }
 
+    async fn get_contract_events(
+        &self,
+        contract: Address,
+        from: BlockNumber,
+        to: BlockNumber,
+        topic1: H256,
+        topic2: Option<H256>,
+        retries_left: usize,
+    ) -> EnrichedClientResult<Vec<Log>> {
+        self.get_events_inner(
+            from,
+            to,
+            Some(vec![topic1]),
+            topic2.map(|topic2| vec![topic2]),
+            Some(vec![contract]),
+            retries_left,
+        )
+        .await
+    }
+
     async fn confirmed_block_number(&self) -> EnrichedClientResult<u64> {
         if let Some(confirmations) = self.confirmations_for_eth_event {
             let latest_block_number = self.client.block_number().await?.as_u64();
@@ -381,6 +416,14 @@ where
         Ok(block_number.as_u64())
     }
 
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>> {
+        let block = self
+            .client
+            .block(BlockId::Number(BlockNumber::Number(number.into())))
+            .await?;
+        Ok(block.and_then(|block| block.hash))
+    }
+
     async fn get_total_priority_txs(&self) -> Result<u64, ContractCallError> {
         CallFunctionArgs::new("getTotalPriorityTxs", ())
             .for_contract(self.diamond_proxy_addr, &self.getters_facet_contract_abi)
@@ -634,6 +677,20 @@ impl EthClient for L2EthClientW {
             .await
     }
 
+    async fn get_contract_events(
+        &self,
+        contract: Address,
+        from: BlockNumber,
+        to: BlockNumber,
+        topic1: H256,
+        topic2: Option<H256>,
+        retries_left: usize,
+    ) -> EnrichedClientResult<Vec<Log>> {
+        self.0
+            .get_contract_events(contract, from, to, topic1, topic2, retries_left)
+            .await
+    }
+
     async fn confirmed_block_number(&self) -> EnrichedClientResult<u64> {
         self.0.confirmed_block_number().await
     }
@@ -642,6 +699,10 @@ impl EthClient for L2EthClientW {
         self.0.finalized_block_number().await
     }
 
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>> {
+        self.0.block_hash(number).await
+    }
+
     async fn get_total_priority_txs(&self) -> Result<u64, ContractCallError> {
         self.0.get_total_priority_txs().await
     }
@@ -15,7 +15,7 @@ use zksync_types::{
 
 use crate::{
     client::L2EthClient,
-    event_processors::{EventProcessor, EventProcessorError, EventsSource},
+    event_processors::{CheckpointKey, EventProcessor, EventProcessorError, EventsSource},
 };
 
 /// Listens to `AppendedChainBatchRoot` events and saves `BatchAndChainMerklePath` for batches.
@@ -193,8 +193,8 @@ impl EventProcessor for BatchRootProcessor {
         EventsSource::SL
     }
 
-    fn event_type(&self) -> EventType {
-        EventType::ChainBatchRoot
+    fn checkpoint_key(&self) -> CheckpointKey<'_> {
+        CheckpointKey::Builtin(EventType::ChainBatchRoot)
     }
 
     fn only_finalized_block(&self) -> bool {
@@ -0,0 +1,133 @@
+use std::{fmt, sync::Mutex};
+
+use zksync_dal::{Connection, Core};
+use zksync_types::{api::Log, Address, H256};
+
+use crate::event_processors::{CheckpointKey, EventProcessor, EventProcessorError, EventsSource};
+
+/// Processor of events emitted by an arbitrary L1 contract (e.g., governance or allowlist changes) that can be plugged
+/// into [`EthWatch`](crate::EthWatch) via [`CustomEventProcessors`].
+///
+/// Custom processors are run after the built-in ones. Processing progress is persisted in Postgres
+/// keyed by the [processor name](Self::name()), so that events are not processed again after a node restart.
+#[async_trait::async_trait]
+pub trait CustomEventProcessor: 'static + fmt::Debug + Send + Sync {
+    /// Unique name of the processor. Must not change between node restarts, since it's used as a key
+    /// to persist processing progress.
+    fn name(&self) -> &str;
+
+    /// Address of the L1 contract emitting events.
+    fn contract_address(&self) -> Address;
+
+    /// Relevant topic1 (i.e., the event signature) which defines what events to be processed.
+    fn topic1(&self) -> H256;
+
+    /// Relevant topic2 which defines what events to be processed.
+    fn topic2(&self) -> Option<H256> {
+        None
+    }
+
+    /// Whether processor expects events only from finalized blocks. Otherwise, events are processed
+    /// once they have the number of confirmations configured for the watcher.
+    fn only_finalized_block(&self) -> bool {
+        false
+    }
+
+    /// Processes given events ordered by their position on L1. Returns the number of processed events;
+    /// the remaining events will be provided again on the next watcher iteration. Since progress is tracked
+    /// with the block granularity, events from the block of the last processed event may be provided again as well,
+    /// so processing must be idempotent.
+    ///
+    /// Errors are logged and the events are retried on the next iteration. A failing processor doesn't block
+    /// other processors.
+    async fn process_events(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        events: Vec<Log>,
+    ) -> anyhow::Result<usize>;
+}
+
+/// Collection of [`CustomEventProcessor`]s to be run by [`EthWatch`](crate::EthWatch).
+///
+/// Processors can be inserted at any time; they are picked up by the watcher on its next iteration.
+#[derive(Debug, Default)]
+pub struct CustomEventProcessors(Mutex<Vec<Box<dyn CustomEventProcessor>>>);
+
+impl CustomEventProcessors {
+    /// Registers a new processor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a processor with the same name is already registered.
+    pub fn insert(&self, processor: Box<dyn CustomEventProcessor>) -> anyhow::Result<()> {
+        let mut guard = self.0.lock().expect("custom event processors are poisoned");
+        anyhow::ensure!(
+            guard
+                .iter()
+                .all(|existing| existing.name() != processor.name()),
+            "custom event processor `{}` is already registered",
+            processor.name()
+        );
+        guard.push(processor);
+        Ok(())
+    }
+
+    pub(crate) fn take(&self) -> Vec<Box<dyn CustomEventProcessor>> {
+        let mut guard = self.0.lock().expect("custom event processors are poisoned");
+        std::mem::take(&mut *guard)
+    }
+}
+
+/// Adapter of a [`CustomEventProcessor`] to the internal [`EventProcessor`] interface.
+#[derive(Debug)]
+pub(crate) struct CustomEventProcessorAdapter(pub Box<dyn CustomEventProcessor>);
+
+#[async_trait::async_trait]
+impl EventProcessor for CustomEventProcessorAdapter {
+    async fn process_events(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        events: Vec<Log>,
+    ) -> Result<usize, EventProcessorError> {
+        let events_count = events.len();
+        let result = self
+            .0
+            .process_events(storage, events)
+            .await
+            .and_then(|processed_count| {
+                anyhow::ensure!(
+                    processed_count <= events_count,
+                    "processor reported {processed_count} processed events, while only {events_count} were provided"
+                );
+                Ok(processed_count)
+            });
+        result.map_err(|source| EventProcessorError::Custom {
+            name: self.0.name().to_owned(),
+            source,
+        })
+    }
+
+    fn topic1(&self) -> H256 {
+        self.0.topic1()
+    }
+
+    fn topic2(&self) -> Option<H256> {
+        self.0.topic2()
+    }
+
+    fn contract_address(&self) -> Option<Address> {
+        Some(self.0.contract_address())
+    }
+
+    fn event_source(&self) -> EventsSource {
+        EventsSource::L1
+    }
+
+    fn checkpoint_key(&self) -> CheckpointKey<'_> {
+        CheckpointKey::Custom(self.0.name())
+    }
+
+    fn only_finalized_block(&self) -> bool {
+        self.0.only_finalized_block()
+    }
+}
@@ -9,7 +9,7 @@ use zksync_types::{
 
 use crate::{
     client::EthClient,
-    event_processors::{EventProcessor, EventProcessorError, EventsSource},
+    event_processors::{CheckpointKey, EventProcessor, EventProcessorError, EventsSource},
     metrics::{PollStage, METRICS},
 };
 
@@ -185,7 +185,7 @@ impl EventProcessor for DecentralizedUpgradesEventProcessor {
         EventsSource::SL
     }
 
-    fn event_type(&self) -> EventType {
-        EventType::ProtocolUpgrades
+    fn checkpoint_key(&self) -> CheckpointKey<'_> {
+        CheckpointKey::Builtin(EventType::ProtocolUpgrades)
     }
 }
@@ -2,15 +2,17 @@ use std::fmt;
 
 use zksync_dal::{eth_watcher_dal::EventType, Connection, Core};
 use zksync_eth_client::{ContractCallError, EnrichedClientError};
-use zksync_types::{api::Log, H256};
+use zksync_types::{api::Log, Address, SLChainId, H256};
 
+pub use self::custom::{CustomEventProcessor, CustomEventProcessors};
 pub(crate) use self::{
-    appended_chain_batch_root::BatchRootProcessor,
+    appended_chain_batch_root::BatchRootProcessor, custom::CustomEventProcessorAdapter,
     decentralized_upgrades::DecentralizedUpgradesEventProcessor,
     priority_ops::PriorityOpsEventProcessor,
 };
 
 mod appended_chain_batch_root;
+mod custom;
 mod decentralized_upgrades;
 mod priority_ops;
 
@@ -27,6 +29,18 @@ pub(super) enum EventProcessorError {
     Client(#[from] EnrichedClientError),
     #[error("Contract call error: {0}")]
     ContractCall(#[from] ContractCallError),
+    /// Errors returned by [`CustomEventProcessor`]s. Unlike internal errors, they are not fatal; processing
+    /// is retried on the next iteration.
+    #[error("custom event processor `{name}` failed: {source:?}")]
+    Custom {
+        name: String,
+        #[source]
+        source: anyhow::Error,
+    },
+    /// A block is missing on the settlement layer, e.g. because the queried node lags behind or is in the middle
+    /// of a reorg. Not fatal; processing is retried on the next iteration.
+    #[error("block #{number} is missing on chain {chain_id}")]
+    MissingBlock { chain_id: SLChainId, number: u64 },
     /// Internal errors are considered fatal (i.e., they bubble up and lead to the watcher termination).
     #[error("internal processing error: {0:?}")]
     Internal(#[from] anyhow::Error),
@@ -38,6 +52,14 @@ pub(super) enum EventsSource {
     SL,
 }
 
+/// Key used to persist the processing progress of an [`EventProcessor`].
+#[derive(Debug, Clone, Copy)]
+pub(super) enum CheckpointKey<'a> {
+    Builtin(EventType),
+    /// Custom processor identified by its name.
+    Custom(&'a str),
+}
+
 impl EventProcessorError {
     pub fn log_parse(source: impl Into<anyhow::Error>, log_kind: &'static str) -> Self {
         Self::LogParse {
@@ -67,9 +89,15 @@ pub(super) trait EventProcessor: 'static + fmt::Debug + Send + Sync {
         None
     }
 
+    /// Address of the contract emitting events. If not specified, events are filtered
+    /// by the core contracts known to the client.
+    fn contract_address(&self) -> Option<Address> {
+        None
+    }
+
     fn event_source(&self) -> EventsSource;
 
-    fn event_type(&self) -> EventType;
+    fn checkpoint_key(&self) -> CheckpointKey<'_>;
 
     /// Whether processor expect events only from finalized blocks.
     fn only_finalized_block(&self) -> bool {
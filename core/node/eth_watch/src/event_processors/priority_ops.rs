@@ -8,7 +8,7 @@ use zksync_types::{api::Log, l1::L1Tx, PriorityOpId, H256};
 
 use crate::{
     client::EthClient,
-    event_processors::{EventProcessor, EventProcessorError, EventsSource},
+    event_processors::{CheckpointKey, EventProcessor, EventProcessorError, EventsSource},
     metrics::{PollStage, METRICS},
 };
 
@@ -117,7 +117,7 @@ impl EventProcessor for PriorityOpsEventProcessor {
         EventsSource::L1
     }
 
-    fn event_type(&self) -> EventType {
-        EventType::PriorityTransactions
+    fn checkpoint_key(&self) -> CheckpointKey<'_> {
+        CheckpointKey::Builtin(EventType::PriorityTransactions)
     }
 }
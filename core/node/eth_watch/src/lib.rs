@@ -1,8 +1,10 @@
 //! Ethereum watcher polls the Ethereum node for the relevant events, such as priority operations (aka L1 transactions),
 //! protocol upgrades etc.
 //! New events are accepted to the ZKsync network once they have the sufficient amount of L1 confirmations.
+//! Hashes of processed blocks are recorded in order to detect reorgs, which may happen if events are processed
+//! based on the number of confirmations rather than on block finality.
 
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use tokio::sync::watch;
@@ -14,19 +16,25 @@ use zksync_types::{
     web3::BlockNumber as Web3BlockNumber, L1BatchNumber, L2ChainId, PriorityOpId,
 };
 
-pub use self::client::{EthClient, EthHttpQueryClient, L2EthClient};
+pub use self::{
+    client::{EthClient, EthHttpQueryClient, L2EthClient},
+    event_processors::{CustomEventProcessor, CustomEventProcessors},
+};
 use self::{
     client::{L2EthClientW, RETRY_LIMIT},
     event_processors::{EventProcessor, EventProcessorError, PriorityOpsEventProcessor},
     metrics::METRICS,
+    reorg::BlockHashes,
 };
 use crate::event_processors::{
-    BatchRootProcessor, DecentralizedUpgradesEventProcessor, EventsSource,
+    BatchRootProcessor, CheckpointKey, CustomEventProcessorAdapter,
+    DecentralizedUpgradesEventProcessor, EventsSource,
 };
 
 mod client;
 mod event_processors;
 mod metrics;
+mod reorg;
 #[cfg(test)]
 mod tests;
 
@@ -45,6 +53,7 @@ pub struct EthWatch {
     sl_client: Arc<dyn EthClient>,
     poll_interval: Duration,
     event_processors: Vec<Box<dyn EventProcessor>>,
+    custom_event_processors: Arc<CustomEventProcessors>,
     pool: ConnectionPool<Core>,
 }
 
@@ -97,10 +106,18 @@ impl EthWatch {
             sl_client,
             poll_interval,
             event_processors,
+            custom_event_processors: Arc::default(),
             pool,
         })
     }
 
+    /// Sets custom event processors to run in addition to the built-in ones. Processors are taken
+    /// from the collection on each watcher iteration, so they can be inserted after the watcher is created.
+    pub fn with_custom_event_processors(mut self, processors: Arc<CustomEventProcessors>) -> Self {
+        self.custom_event_processors = processors;
+        self
+    }
+
     #[tracing::instrument(name = "EthWatch::initialize_state", skip_all)]
     async fn initialize_state(
         storage: &mut Connection<'_, Core>,
@@ -178,6 +195,26 @@ impl EthWatch {
         &mut self,
         storage: &mut Connection<'_, Core>,
     ) -> Result<(), EventProcessorError> {
+        for processor in self.custom_event_processors.take() {
+            tracing::info!(
+                "Registered custom event processor `{}` for contract {:?}",
+                processor.name(),
+                processor.contract_address()
+            );
+            self.event_processors
+                .push(Box::new(CustomEventProcessorAdapter(processor)));
+        }
+
+        let mut checked_chains = HashSet::new();
+        for client in [self.l1_client.as_ref(), self.sl_client.as_ref()] {
+            let chain_id = client.chain_id().await?;
+            if checked_chains.insert(chain_id) {
+                reorg::detect_reorg(storage, client, chain_id).await?;
+            }
+        }
+
+        let mut block_hashes = BlockHashes::default();
+
         for processor in &mut self.event_processors {
             let client = match processor.event_source() {
                 EventsSource::L1 => self.l1_client.as_ref(),
@@ -190,33 +227,76 @@ impl EthWatch {
                 client.confirmed_block_number().await?
             };
 
-            let from_block = storage
-                .eth_watcher_dal()
-                .get_or_set_next_block_to_process(
-                    processor.event_type(),
-                    chain_id,
-                    to_block.saturating_sub(PRIORITY_EXPIRATION),
-                )
-                .await
-                .map_err(DalError::generalize)?;
+            let default_from_block = to_block.saturating_sub(PRIORITY_EXPIRATION);
+            let mut eth_watcher_dal = storage.eth_watcher_dal();
+            let from_block = match processor.checkpoint_key() {
+                CheckpointKey::Builtin(event_type) => {
+                    eth_watcher_dal
+                        .get_or_set_next_block_to_process(event_type, chain_id, default_from_block)
+                        .await
+                }
+                CheckpointKey::Custom(name) => {
+                    eth_watcher_dal
+                        .get_or_set_next_block_to_process_for_custom_events(
+                            name,
+                            chain_id,
+                            default_from_block,
+                        )
+                        .await
+                }
+            }
+            .map_err(DalError::generalize)?;
 
             // There are no new blocks so there is nothing to be done
             if from_block > to_block {
                 continue;
             }
 
-            let processor_events = client
-                .get_events(
-                    Web3BlockNumber::Number(from_block.into()),
-                    Web3BlockNumber::Number(to_block.into()),
-                    processor.topic1(),
-                    processor.topic2(),
-                    RETRY_LIMIT,
-                )
-                .await?;
-            let processed_events_count = processor
+            let to_block_hash = block_hashes.get(client, chain_id, to_block).await?;
+            let from = Web3BlockNumber::Number(from_block.into());
+            let to = Web3BlockNumber::Number(to_block.into());
+            let processor_events = if let Some(contract) = processor.contract_address() {
+                client
+                    .get_contract_events(
+                        contract,
+                        from,
+                        to,
+                        processor.topic1(),
+                        processor.topic2(),
+                        RETRY_LIMIT,
+                    )
+                    .await?
+            } else {
+                client
+                    .get_events(
+                        from,
+                        to,
+                        processor.topic1(),
+                        processor.topic2(),
+                        RETRY_LIMIT,
+                    )
+                    .await?
+            };
+            let processed_events_count = match processor
                 .process_events(storage, processor_events.clone())
-                .await?;
+                .await
+            {
+                Ok(count) => count,
+                Err(EventProcessorError::Custom { name, source }) => {
+                    // Custom processors are independent, so a failing one must not block the following ones.
+                    tracing::warn!("Custom event processor `{name}` failed: {source:#}");
+                    METRICS.custom_processor_errors[&name].inc();
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            reorg::save_processed_blocks(
+                storage,
+                chain_id,
+                (to_block, to_block_hash),
+                &processor_events[..processed_events_count],
+            )
+            .await?;
 
             let next_block_to_process = if processed_events_count == processor_events.len() {
                 to_block + 1
@@ -231,15 +311,24 @@ impl EthWatch {
                     .unwrap()
             };
 
-            storage
-                .eth_watcher_dal()
-                .update_next_block_to_process(
-                    processor.event_type(),
-                    chain_id,
-                    next_block_to_process,
-                )
-                .await
-                .map_err(DalError::generalize)?;
+            let mut eth_watcher_dal = storage.eth_watcher_dal();
+            match processor.checkpoint_key() {
+                CheckpointKey::Builtin(event_type) => {
+                    eth_watcher_dal
+                        .update_next_block_to_process(event_type, chain_id, next_block_to_process)
+                        .await
+                }
+                CheckpointKey::Custom(name) => {
+                    eth_watcher_dal
+                        .update_next_block_to_process_for_custom_events(
+                            name,
+                            chain_id,
+                            next_block_to_process,
+                        )
+                        .await
+                }
+            }
+            .map_err(DalError::generalize)?;
         }
         Ok(())
     }
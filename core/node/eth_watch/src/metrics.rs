@@ -2,7 +2,9 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, LabeledFamily, Metrics,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -11,6 +13,16 @@ pub(super) enum PollStage {
     PersistUpgrades,
 }
 
+/// Outcome of a detected L1 / settlement layer reorg.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(super) enum L1ReorgOutcome {
+    /// Orphaned blocks didn't contain processed events, so the watcher state was reverted to the fork point.
+    Reverted,
+    /// Orphaned blocks contained processed events; the watcher was stopped.
+    Unrecoverable,
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_eth_watch")]
 pub(super) struct EthWatcherMetrics {
@@ -19,6 +31,11 @@ pub(super) struct EthWatcherMetrics {
     /// Latency of polling and processing events split by stage.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub poll_eth_node: Family<PollStage, Histogram<Duration>>,
+    /// Number of detected L1 / settlement layer reorgs.
+    pub l1_reorgs: Family<L1ReorgOutcome, Counter>,
+    /// Number of errors returned by custom event processors.
+    #[metrics(labels = ["processor"])]
+    pub custom_processor_errors: LabeledFamily<String, Counter>,
 }
 
 #[vise::register]
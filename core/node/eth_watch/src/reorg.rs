@@ -0,0 +1,207 @@
+//! L1 / settlement layer reorg detection.
+//!
+//! If the watcher processes events after a certain number of confirmations rather than after finalization,
+//! blocks with processed events may be orphaned by a reorg. To detect this, the watcher records hashes
+//! of the processed blocks and checks them against the chain on each iteration. If the reorg only affects blocks
+//! without processed events, processing progress is rewound to the fork point, and affected events are processed again.
+//! Otherwise, the reorg is persisted (so that it's picked up by the corresponding circuit breaker), and the watcher
+//! is stopped.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Context as _;
+use zksync_dal::{eth_watcher_dal::EthWatcherBlockHash, Connection, Core, CoreDal, DalError};
+use zksync_types::{api::Log, SLChainId, H256};
+
+use crate::{
+    client::EthClient,
+    event_processors::EventProcessorError,
+    metrics::{L1ReorgOutcome, METRICS},
+};
+
+/// Number of blocks before the latest processed block for which hashes are retained. Should be much larger than
+/// the depth of any realistic reorg.
+const BLOCK_HASHES_RETENTION: u64 = 1_024;
+
+/// Cache of block hashes fetched during a single watcher iteration.
+#[derive(Debug, Default)]
+pub(crate) struct BlockHashes(HashMap<(SLChainId, u64), H256>);
+
+impl BlockHashes {
+    pub async fn get(
+        &mut self,
+        client: &dyn EthClient,
+        chain_id: SLChainId,
+        number: u64,
+    ) -> Result<H256, EventProcessorError> {
+        if let Some(&hash) = self.0.get(&(chain_id, number)) {
+            return Ok(hash);
+        }
+        let hash = fetch_block_hash(client, chain_id, number).await?;
+        self.0.insert((chain_id, number), hash);
+        Ok(hash)
+    }
+}
+
+/// Fetches the hash of the specified block. A missing block is not treated as evidence of a reorg since
+/// it may be caused by a lagging or load-balanced node; instead, a transient error is returned.
+async fn fetch_block_hash(
+    client: &dyn EthClient,
+    chain_id: SLChainId,
+    number: u64,
+) -> Result<H256, EventProcessorError> {
+    client
+        .block_hash(number)
+        .await?
+        .ok_or(EventProcessorError::MissingBlock { chain_id, number })
+}
+
+/// Records hashes of blocks processed by an event processor: the block that events were fetched up to,
+/// and blocks of the consumed `events`. The hash of the former should be fetched *before* fetching events,
+/// so that events from a chain that has reorged in the meantime are caught on the next iteration.
+pub(crate) async fn save_processed_blocks(
+    storage: &mut Connection<'_, Core>,
+    chain_id: SLChainId,
+    (to_block, to_block_hash): (u64, H256),
+    events: &[Log],
+) -> Result<(), EventProcessorError> {
+    let mut blocks = BTreeMap::from([(
+        to_block,
+        EthWatcherBlockHash {
+            number: to_block,
+            hash: to_block_hash,
+            has_processed_events: false,
+        },
+    )]);
+    for event in events {
+        let (Some(number), Some(hash)) = (event.block_number, event.block_hash) else {
+            continue;
+        };
+        let number = number.as_u64();
+        blocks.insert(
+            number,
+            EthWatcherBlockHash {
+                number,
+                hash,
+                has_processed_events: true,
+            },
+        );
+    }
+    let blocks: Vec<_> = blocks.into_values().collect();
+
+    let mut dal = storage.eth_watcher_dal();
+    dal.insert_block_hashes(chain_id, &blocks)
+        .await
+        .map_err(DalError::generalize)?;
+    dal.prune_block_hashes(chain_id, to_block.saturating_sub(BLOCK_HASHES_RETENTION))
+        .await
+        .map_err(DalError::generalize)?;
+    Ok(())
+}
+
+/// Checks recorded block hashes for the chain with the specified ID served by `client` against the chain itself.
+/// If a reorg is detected and can be recovered from, reverts the watcher state to the fork point. If the reorg
+/// orphaned blocks with processed events, persists it and returns a fatal error.
+pub(crate) async fn detect_reorg(
+    storage: &mut Connection<'_, Core>,
+    client: &dyn EthClient,
+    chain_id: SLChainId,
+) -> Result<(), EventProcessorError> {
+    let recorded_blocks = storage
+        .eth_watcher_dal()
+        .get_block_hashes(chain_id)
+        .await
+        .map_err(DalError::generalize)?;
+    let Some(latest_block) = recorded_blocks.first() else {
+        return Ok(());
+    };
+
+    // Since each block hash commits to the parent block, it's usually sufficient to check the latest block.
+    // We also check the latest block with processed events in case the chain has changed between recording
+    // the latest block hash and fetching events.
+    if fetch_block_hash(client, chain_id, latest_block.number).await? == latest_block.hash {
+        let latest_block_with_events = recorded_blocks
+            .iter()
+            .find(|block| block.has_processed_events && block.number != latest_block.number);
+        if let Some(block) = latest_block_with_events {
+            let actual_hash = fetch_block_hash(client, chain_id, block.number).await?;
+            if actual_hash != block.hash {
+                return persist_unrecoverable_reorg(storage, chain_id, block, actual_hash).await;
+            }
+        }
+        return Ok(());
+    }
+
+    let mut fork_point = None;
+    let mut orphaned_blocks = vec![];
+    for block in &recorded_blocks {
+        let actual_hash = fetch_block_hash(client, chain_id, block.number).await?;
+        if actual_hash == block.hash {
+            fork_point = Some(block.number);
+            break;
+        }
+        tracing::info!(
+            "Block #{} on chain {chain_id} is orphaned: expected hash {:?}, actual {actual_hash:?}",
+            block.number,
+            block.hash
+        );
+        orphaned_blocks.push((block, actual_hash));
+    }
+
+    let orphaned_block_with_events = orphaned_blocks
+        .iter()
+        .rev()
+        .find(|(block, _)| block.has_processed_events);
+    let fork_point = match (fork_point, orphaned_block_with_events) {
+        (Some(fork_point), None) => fork_point,
+        (_, Some(&(block, actual_hash))) => {
+            return persist_unrecoverable_reorg(storage, chain_id, block, actual_hash).await;
+        }
+        (None, None) => {
+            // The reorg is deeper than all recorded blocks, so we cannot check whether it has affected
+            // processed events.
+            let &(block, actual_hash) = orphaned_blocks.last().context("no orphaned blocks")?;
+            return persist_unrecoverable_reorg(storage, chain_id, block, actual_hash).await;
+        }
+    };
+
+    tracing::warn!(
+        "Detected reorg on chain {chain_id} with the fork point at block #{fork_point}; orphaned blocks don't contain \
+         processed events, so watcher state will be reverted to the fork point"
+    );
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(DalError::generalize)?;
+    transaction
+        .eth_watcher_dal()
+        .revert_to_block(chain_id, fork_point)
+        .await
+        .map_err(DalError::generalize)?;
+    transaction.commit().await.map_err(DalError::generalize)?;
+    METRICS.l1_reorgs[&L1ReorgOutcome::Reverted].inc();
+    Ok(())
+}
+
+/// Persists a reorg that cannot be recovered from and returns a fatal error.
+async fn persist_unrecoverable_reorg(
+    storage: &mut Connection<'_, Core>,
+    chain_id: SLChainId,
+    block: &EthWatcherBlockHash,
+    actual_hash: H256,
+) -> Result<(), EventProcessorError> {
+    storage
+        .eth_watcher_dal()
+        .insert_detected_reorg(chain_id, block.number, block.hash, Some(actual_hash))
+        .await
+        .map_err(DalError::generalize)?;
+    METRICS.l1_reorgs[&L1ReorgOutcome::Unrecoverable].inc();
+
+    let err = anyhow::anyhow!(
+        "reorg on chain {chain_id} orphaned block #{} (expected hash {:?}, actual {actual_hash:?}) \
+         that may contain processed events; manual intervention is required",
+        block.number,
+        block.hash
+    );
+    Err(EventProcessorError::Internal(err))
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, convert::TryInto, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+    sync::Arc,
+};
 
 use tokio::sync::RwLock;
 use zksync_contracts::{
@@ -32,6 +36,9 @@ pub struct FakeEthClientData {
     batch_roots: HashMap<u64, Vec<Log>>,
     chain_roots: HashMap<u64, H256>,
     bytecode_preimages: HashMap<H256, Vec<u8>>,
+    custom_events: HashMap<u64, Vec<Log>>,
+    block_hashes: HashMap<u64, H256>,
+    missing_blocks: HashSet<u64>,
 }
 
 impl FakeEthClientData {
@@ -47,9 +54,21 @@ impl FakeEthClientData {
             batch_roots: Default::default(),
             chain_roots: Default::default(),
             bytecode_preimages: Default::default(),
+            custom_events: Default::default(),
+            block_hashes: Default::default(),
+            missing_blocks: Default::default(),
         }
     }
 
+    /// Returns the hash of the specified block. Blocks have the same hash as set in the mock logs unless overridden
+    /// (e.g., to emulate an L1 reorg).
+    fn block_hash(&self, number: u64) -> H256 {
+        self.block_hashes
+            .get(&number)
+            .copied()
+            .unwrap_or(H256::repeat_byte(0x11))
+    }
+
     fn add_transactions(&mut self, transactions: &[L1Tx]) {
         for transaction in transactions {
             let eth_block = transaction.eth_block();
@@ -148,6 +167,20 @@ impl MockEthClient {
             .set_last_finalized_block_number(number);
     }
 
+    /// Overrides hashes of the specified blocks. Logs returned by the client for these blocks will have updated hashes as well.
+    pub async fn set_block_hashes(&mut self, hashes: &[(u64, H256)]) {
+        self.inner
+            .write()
+            .await
+            .block_hashes
+            .extend(hashes.iter().copied());
+    }
+
+    /// Makes the client return no hash for the specified blocks (e.g., to emulate a lagging node).
+    pub async fn set_missing_blocks(&mut self, numbers: &[u64]) {
+        self.inner.write().await.missing_blocks = numbers.iter().copied().collect();
+    }
+
     pub async fn set_processed_priority_transactions_count(&mut self, number: u64) {
         self.inner
             .write()
@@ -166,6 +199,18 @@ impl MockEthClient {
         }
     }
 
+    pub async fn add_custom_events(&mut self, events: &[Log]) {
+        let mut inner = self.inner.write().await;
+        for event in events {
+            let block_number = event.block_number.expect("no block number").as_u64();
+            inner
+                .custom_events
+                .entry(block_number)
+                .or_default()
+                .push(event.clone());
+        }
+    }
+
     pub async fn add_batch_roots(&mut self, batch_roots: &[(u64, u64, H256)]) {
         self.inner.write().await.add_batch_roots(batch_roots);
     }
@@ -199,6 +244,7 @@ impl EthClient for MockEthClient {
         let to = self.block_to_number(to).await;
         let mut logs = vec![];
         for number in from..=to {
+            let block_logs_start = logs.len();
             if let Some(ops) = self.inner.read().await.transactions.get(&number) {
                 logs.extend_from_slice(ops);
             }
@@ -211,6 +257,10 @@ impl EthClient for MockEthClient {
             if let Some(ops) = self.inner.read().await.batch_roots.get(&number) {
                 logs.extend_from_slice(ops);
             }
+            let block_hash = self.inner.read().await.block_hash(number);
+            for log in &mut logs[block_logs_start..] {
+                log.block_hash = Some(block_hash);
+            }
         }
         Ok(logs
             .into_iter()
@@ -221,6 +271,36 @@ impl EthClient for MockEthClient {
             .collect())
     }
 
+    async fn get_contract_events(
+        &self,
+        contract: Address,
+        from: BlockNumber,
+        to: BlockNumber,
+        topic1: H256,
+        topic2: Option<H256>,
+        _retries_left: usize,
+    ) -> EnrichedClientResult<Vec<Log>> {
+        let from = self.block_to_number(from).await;
+        let to = self.block_to_number(to).await;
+        let inner = self.inner.read().await;
+        let logs = (from..=to).filter_map(|number| {
+            let block_hash = inner.block_hash(number);
+            let logs = inner.custom_events.get(&number)?;
+            Some(logs.iter().map(move |log| Log {
+                block_hash: Some(block_hash),
+                ..log.clone()
+            }))
+        });
+        Ok(logs
+            .flatten()
+            .filter(|log| {
+                log.address == contract
+                    && log.topics.first() == Some(&topic1)
+                    && (topic2.is_none() || log.topics.get(1) == topic2.as_ref())
+            })
+            .collect())
+    }
+
     async fn scheduler_vk_hash(
         &self,
         _verifier_address: Address,
@@ -236,6 +316,11 @@ impl EthClient for MockEthClient {
         Ok(self.inner.read().await.last_finalized_block_number)
     }
 
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>> {
+        let inner = self.inner.read().await;
+        Ok((!inner.missing_blocks.contains(&number)).then(|| inner.block_hash(number)))
+    }
+
     async fn diamond_cut_by_version(
         &self,
         packed_version: H256,
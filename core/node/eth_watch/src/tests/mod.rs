@@ -1,22 +1,28 @@
-use std::convert::TryInto;
+use std::{
+    convert::TryInto,
+    sync::{Arc, Mutex},
+};
 
 use zksync_contracts::chain_admin_contract;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_types::{
     abi,
     aggregated_operations::AggregatedActionType,
-    api::ChainAggProof,
+    api::{ChainAggProof, Log},
     block::L1BatchHeader,
     commitment::L1BatchCommitmentArtifacts,
     l1::{L1Tx, OpProcessingType, PriorityQueueType},
     l2_to_l1_log::BatchAndChainMerklePath,
     protocol_upgrade::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData},
     protocol_version::ProtocolSemanticVersion,
-    Address, Execute, L1BatchNumber, L1TxCommonData, L2ChainId, PriorityOpId, ProtocolUpgrade,
-    ProtocolVersion, ProtocolVersionId, SLChainId, Transaction, H256, U256,
+    Address, Execute, L1BatchNumber, L1BlockNumber, L1TxCommonData, L2ChainId, PriorityOpId,
+    ProtocolUpgrade, ProtocolVersion, ProtocolVersionId, SLChainId, Transaction, H256, U256, U64,
 };
 
-use crate::{tests::client::MockEthClient, EthWatch, L2EthClient};
+use crate::{
+    event_processors::EventProcessorError, tests::client::MockEthClient, CustomEventProcessor,
+    CustomEventProcessors, EthWatch, L2EthClient,
+};
 
 mod client;
 
@@ -169,6 +175,333 @@ async fn test_normal_operation_l1_txs() {
     assert_eq!(db_tx.common_data.serial_id.0, 2);
 }
 
+#[test_log::test(tokio::test)]
+async fn test_l1_reorg_without_processed_events() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client.set_last_finalized_block_number(12).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+
+    // Emulate a reorg orphaning blocks 13..=15, which don't contain processed events. The new chain contains a priority op
+    // in one of the reorged blocks.
+    let new_hashes: Vec<_> = (13..=15)
+        .map(|number| (number, H256::repeat_byte(0x22)))
+        .collect();
+    client.set_block_hashes(&new_hashes).await;
+    client.add_transactions(&[build_l1_tx(1, 14)]).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let db_txs = get_all_db_txs(&mut storage).await;
+    let mut db_txs: Vec<L1Tx> = db_txs
+        .into_iter()
+        .map(|tx| tx.try_into().unwrap())
+        .collect();
+    db_txs.sort_by_key(|tx| tx.common_data.serial_id);
+    assert_eq!(db_txs.len(), 2);
+    assert_eq!(db_txs[1].common_data.serial_id.0, 1);
+    assert_eq!(db_txs[1].eth_block(), L1BlockNumber(14));
+
+    let recorded_blocks = storage
+        .eth_watcher_dal()
+        .get_block_hashes(SLChainId(42))
+        .await
+        .unwrap();
+    let latest_block = recorded_blocks[0];
+    assert_eq!(latest_block.number, 15);
+    assert_eq!(latest_block.hash, H256::repeat_byte(0x22));
+    assert_eq!(
+        storage
+            .eth_watcher_dal()
+            .get_detected_reorg()
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_l1_reorg_with_processed_events() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client.set_last_finalized_block_number(12).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+
+    // Emulate a reorg orphaning the block with the processed priority op.
+    let new_hashes: Vec<_> = (9..=12)
+        .map(|number| (number, H256::repeat_byte(0x22)))
+        .collect();
+    client.set_block_hashes(&new_hashes).await;
+    let err = watcher.loop_iteration(&mut storage).await.unwrap_err();
+    assert!(matches!(err, EventProcessorError::Internal(_)), "{err:?}");
+
+    let reorg = storage
+        .eth_watcher_dal()
+        .get_detected_reorg()
+        .await
+        .unwrap()
+        .expect("reorg not persisted");
+    assert_eq!(reorg.chain_id, SLChainId(42));
+    assert_eq!(reorg.block_number, 10);
+    assert_eq!(reorg.expected_block_hash, H256::repeat_byte(0x11));
+    assert_eq!(reorg.actual_block_hash, Some(H256::repeat_byte(0x22)));
+    // Priority ops must not be processed after the reorg.
+    watcher.loop_iteration(&mut storage).await.unwrap_err();
+}
+
+#[test_log::test(tokio::test)]
+async fn missing_l1_block_is_not_a_reorg() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client.set_last_finalized_block_number(12).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+
+    // Emulate a lagging node that doesn't know about recorded blocks.
+    client.set_missing_blocks(&[10, 11, 12]).await;
+    let err = watcher.loop_iteration(&mut storage).await.unwrap_err();
+    assert!(
+        matches!(err, EventProcessorError::MissingBlock { number: 12, .. }),
+        "{err:?}"
+    );
+    assert_eq!(
+        storage
+            .eth_watcher_dal()
+            .get_detected_reorg()
+            .await
+            .unwrap(),
+        None
+    );
+
+    // Processing should resume once the node catches up.
+    client.set_missing_blocks(&[]).await;
+    client.add_transactions(&[build_l1_tx(1, 14)]).await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 2);
+}
+
+const CUSTOM_CONTRACT: Address = Address::repeat_byte(0x42);
+const CUSTOM_TOPIC: H256 = H256::repeat_byte(0x42);
+
+#[derive(Debug)]
+struct TestCustomEventProcessor {
+    max_events_per_iteration: usize,
+    processed_events: Arc<Mutex<Vec<Log>>>,
+}
+
+#[async_trait::async_trait]
+impl CustomEventProcessor for TestCustomEventProcessor {
+    fn name(&self) -> &str {
+        "test"
+    }
+
+    fn contract_address(&self) -> Address {
+        CUSTOM_CONTRACT
+    }
+
+    fn topic1(&self) -> H256 {
+        CUSTOM_TOPIC
+    }
+
+    async fn process_events(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        events: Vec<Log>,
+    ) -> anyhow::Result<usize> {
+        let processed_count = events.len().min(self.max_events_per_iteration);
+        let mut processed_events = self.processed_events.lock().unwrap();
+        for event in events.into_iter().take(processed_count) {
+            // Events from the last processed block may be provided again.
+            if !processed_events.contains(&event) {
+                processed_events.push(event);
+            }
+        }
+        Ok(processed_count)
+    }
+}
+
+fn custom_log(address: Address, topic: H256, eth_block: u64) -> Log {
+    Log {
+        address,
+        topics: vec![topic],
+        data: vec![].into(),
+        block_hash: Some(H256::repeat_byte(0x11)),
+        block_number: Some(U64::from(eth_block)),
+        l1_batch_number: None,
+        transaction_hash: Some(H256::from_low_u64_be(eth_block)),
+        transaction_index: Some(0.into()),
+        log_index: Some(0.into()),
+        transaction_log_index: Some(0.into()),
+        log_type: None,
+        removed: None,
+        block_timestamp: None,
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_custom_event_processor() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+    let custom_processors = Arc::new(CustomEventProcessors::default());
+    let mut watcher = watcher.with_custom_event_processors(custom_processors.clone());
+    let processed_events = Arc::default();
+    custom_processors
+        .insert(Box::new(TestCustomEventProcessor {
+            max_events_per_iteration: 2,
+            processed_events: Arc::clone(&processed_events),
+        }))
+        .unwrap();
+    // Processors with duplicate names are rejected.
+    custom_processors
+        .insert(Box::new(TestCustomEventProcessor {
+            max_events_per_iteration: 1,
+            processed_events: Arc::default(),
+        }))
+        .unwrap_err();
+
+    client
+        .add_custom_events(&[
+            custom_log(CUSTOM_CONTRACT, CUSTOM_TOPIC, 10),
+            custom_log(Address::repeat_byte(1), CUSTOM_TOPIC, 11),
+            custom_log(CUSTOM_CONTRACT, H256::repeat_byte(1), 12),
+            custom_log(CUSTOM_CONTRACT, CUSTOM_TOPIC, 13),
+            custom_log(CUSTOM_CONTRACT, CUSTOM_TOPIC, 14),
+            custom_log(CUSTOM_CONTRACT, CUSTOM_TOPIC, 20),
+        ])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    let processed_blocks: Vec<_> = processed_events
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|log| log.block_number.unwrap().as_u64())
+        .collect();
+    // Only 2 events should be processed per iteration; events from other contracts / with other topics are ignored.
+    assert_eq!(processed_blocks, [10, 13]);
+
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    let processed_blocks: Vec<_> = processed_events
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|log| log.block_number.unwrap().as_u64())
+        .collect();
+    assert_eq!(processed_blocks, [10, 13, 14]);
+
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    let processed_blocks: Vec<_> = processed_events
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|log| log.block_number.unwrap().as_u64())
+        .collect();
+    assert_eq!(processed_blocks, [10, 13, 14, 20]);
+
+    let next_block = storage
+        .eth_watcher_dal()
+        .get_or_set_next_block_to_process_for_custom_events("test", SLChainId(42), 0)
+        .await
+        .unwrap();
+    assert_eq!(next_block, 21);
+}
+
+#[derive(Debug)]
+struct FailingCustomEventProcessor;
+
+#[async_trait::async_trait]
+impl CustomEventProcessor for FailingCustomEventProcessor {
+    fn name(&self) -> &str {
+        "failing"
+    }
+
+    fn contract_address(&self) -> Address {
+        CUSTOM_CONTRACT
+    }
+
+    fn topic1(&self) -> H256 {
+        CUSTOM_TOPIC
+    }
+
+    async fn process_events(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        _events: Vec<Log>,
+    ) -> anyhow::Result<usize> {
+        anyhow::bail!("oops")
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn failing_custom_event_processor_does_not_block_others() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+    let custom_processors = Arc::new(CustomEventProcessors::default());
+    let mut watcher = watcher.with_custom_event_processors(custom_processors.clone());
+    custom_processors
+        .insert(Box::new(FailingCustomEventProcessor))
+        .unwrap();
+    let processed_events = Arc::default();
+    custom_processors
+        .insert(Box::new(TestCustomEventProcessor {
+            max_events_per_iteration: 10,
+            processed_events: Arc::clone(&processed_events),
+        }))
+        .unwrap();
+
+    client
+        .add_custom_events(&[
+            custom_log(CUSTOM_CONTRACT, CUSTOM_TOPIC, 10),
+            custom_log(CUSTOM_CONTRACT, CUSTOM_TOPIC, 13),
+        ])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    let processed_blocks: Vec<_> = processed_events
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|log| log.block_number.unwrap().as_u64())
+        .collect();
+    assert_eq!(processed_blocks, [10, 13]);
+
+    let mut dal = storage.eth_watcher_dal();
+    let next_block = dal
+        .get_or_set_next_block_to_process_for_custom_events("test", SLChainId(42), 0)
+        .await
+        .unwrap();
+    assert_eq!(next_block, 16);
+    // Progress of the failing processor must not be advanced.
+    let next_block = dal
+        .get_or_set_next_block_to_process_for_custom_events("failing", SLChainId(42), 0)
+        .await
+        .unwrap();
+    assert!(next_block <= 10, "{next_block}");
+}
+
 #[test_log::test(tokio::test)]
 async fn test_gap_in_upgrade_timestamp() {
     let connection_pool = ConnectionPool::<Core>::test_pool().await;
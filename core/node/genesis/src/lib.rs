@@ -36,6 +36,7 @@ use crate::utils::{
     insert_factory_deps, insert_storage_logs, save_genesis_l1_batch_metadata,
 };
 
+pub mod manifest;
 #[cfg(test)]
 mod tests;
 pub mod utils;
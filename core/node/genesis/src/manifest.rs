@@ -0,0 +1,504 @@
+//! Declarative genesis allocations.
+//!
+//! A [`GenesisManifest`] describes accounts that should exist in the genesis state on top of system contracts:
+//! base token balances, nonces, EraVM bytecodes and arbitrary storage slots. It can be converted into a [`GenesisState`]
+//! that is consumed by the node via the `custom_genesis_state_path` genesis config param.
+//!
+//! # Formats
+//!
+//! **JSON** manifests are maps from an address to its allocation. All numeric values can be specified either as decimal
+//! strings or as `0x`-prefixed hex strings; all fields are optional.
+//!
+//! ```json
+//! {
+//!   "0x36615cf349d7f6344891b1e7ca7c72883f5dc049": {
+//!     "balance": "1000000000000000000",
+//!     "nonce": "0",
+//!     "deploymentNonce": "1",
+//!     "code": "0x...",
+//!     "storage": { "0x0": "0x1" }
+//!   }
+//! }
+//! ```
+//!
+//! **CSV** manifests contain `address,field,key,value` rows, where `field` is one of `balance`, `nonce`,
+//! `deploymentNonce`, `code` or `storage`. `key` must be empty for all fields except `storage`. An optional header row,
+//! empty lines and lines starting with `#` are skipped.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::Context as _;
+use serde::{de, Deserialize, Deserializer};
+use zksync_dal::custom_genesis_export_dal::{FactoryDepRow, GenesisState, StorageLogRow};
+use zksync_types::{
+    address_to_u256,
+    block::DeployedContract,
+    bytecode::{validate_bytecode, BytecodeHash, InvalidBytecodeError},
+    get_code_key, get_known_code_key, get_nonce_key, u256_to_h256,
+    utils::{nonces_to_full_nonce, storage_key_for_eth_balance},
+    web3::Bytes,
+    AccountTreeId, Address, StorageKey, StorageLog, H256, L2_BASE_TOKEN_ADDRESS, U256,
+};
+
+use crate::utils::get_storage_logs;
+
+/// Slot of `totalSupply` in the `L2BaseToken` system contract.
+const BASE_TOKEN_TOTAL_SUPPLY_SLOT: u64 = 1;
+/// Addresses below this bound are reserved for kernel-space system contracts.
+const MAX_RESERVED_ADDRESS: u64 = 0xffff;
+
+/// Errors produced when a [`GenesisManifest`] cannot be converted into a genesis state.
+#[derive(Debug, thiserror::Error)]
+pub enum ManifestError {
+    #[error("address {0:?} is reserved for system contracts")]
+    ReservedAddress(Address),
+    #[error("invalid bytecode for {address:?}: {source}")]
+    InvalidBytecode {
+        address: Address,
+        #[source]
+        source: InvalidBytecodeError,
+    },
+    #[error("nonce for {0:?} does not fit into 128 bits")]
+    NonceOutOfRange(Address),
+    #[error("total base token supply overflows")]
+    TotalSupplyOverflow,
+}
+
+/// Genesis allocation for a single account.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GenesisAllocation {
+    /// Base token balance.
+    pub balance: U256,
+    /// Transaction nonce.
+    pub nonce: U256,
+    /// Deployment nonce (i.e., the number of contracts deployed by the account via `CREATE`).
+    pub deployment_nonce: U256,
+    /// EraVM bytecode deployed at the account.
+    pub code: Option<Vec<u8>>,
+    /// Storage slots of the account.
+    pub storage: BTreeMap<H256, H256>,
+}
+
+/// Declarative description of accounts added to the genesis state.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GenesisManifest {
+    pub allocations: BTreeMap<Address, GenesisAllocation>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct AllocationRepr {
+    #[serde(default, deserialize_with = "deserialize_quantity")]
+    balance: U256,
+    #[serde(default, deserialize_with = "deserialize_quantity")]
+    nonce: U256,
+    #[serde(default, deserialize_with = "deserialize_quantity")]
+    deployment_nonce: U256,
+    #[serde(default)]
+    code: Option<Bytes>,
+    #[serde(default)]
+    storage: BTreeMap<String, String>,
+}
+
+fn deserialize_quantity<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse_quantity(&s).map_err(de::Error::custom)
+}
+
+fn parse_quantity(s: &str) -> anyhow::Result<U256> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix("0x") {
+        U256::from_str_radix(hex, 16).with_context(|| format!("invalid hex quantity `{s}`"))
+    } else {
+        U256::from_dec_str(s).with_context(|| format!("invalid decimal quantity `{s}`"))
+    }
+}
+
+fn parse_word(s: &str) -> anyhow::Result<H256> {
+    parse_quantity(s).map(u256_to_h256)
+}
+
+fn parse_bytes(s: &str) -> anyhow::Result<Vec<u8>> {
+    let hex = s
+        .trim()
+        .strip_prefix("0x")
+        .context("bytecode must be 0x-prefixed")?;
+    hex::decode(hex).context("invalid bytecode hex")
+}
+
+impl GenesisManifest {
+    /// Reads a manifest from a file. The format is determined by the file extension (`.json` or `.csv`).
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed reading manifest `{}`", path.display()))?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            Some("csv") => Self::from_csv(&contents),
+            _ => anyhow::bail!(
+                "unsupported manifest format for `{}`; expected a .json or .csv file",
+                path.display()
+            ),
+        }
+    }
+
+    /// Parses a JSON manifest.
+    pub fn from_json(s: &str) -> anyhow::Result<Self> {
+        let raw: BTreeMap<Address, AllocationRepr> =
+            serde_json::from_str(s).context("invalid JSON manifest")?;
+        let allocations = raw
+            .into_iter()
+            .map(|(address, repr)| {
+                let storage = repr
+                    .storage
+                    .iter()
+                    .map(|(key, value)| Ok((parse_word(key)?, parse_word(value)?)))
+                    .collect::<anyhow::Result<_>>()
+                    .with_context(|| format!("invalid storage for {address:?}"))?;
+                let allocation = GenesisAllocation {
+                    balance: repr.balance,
+                    nonce: repr.nonce,
+                    deployment_nonce: repr.deployment_nonce,
+                    code: repr.code.map(|code| code.0),
+                    storage,
+                };
+                Ok((address, allocation))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { allocations })
+    }
+
+    /// Parses a CSV manifest.
+    pub fn from_csv(s: &str) -> anyhow::Result<Self> {
+        let mut this = Self::default();
+        let mut seen_fields = HashSet::new();
+        for (i, line) in s.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let cells: Vec<_> = line.split(',').map(str::trim).collect();
+            if i == 0 && cells.first() == Some(&"address") {
+                continue;
+            }
+            let [address, field, key, value] = cells[..] else {
+                anyhow::bail!("line {line_number}: expected 4 cells, got {}", cells.len());
+            };
+
+            let address = Address::from_str(address)
+                .with_context(|| format!("line {line_number}: invalid address `{address}`"))?;
+            if field != "storage" {
+                if !key.is_empty() {
+                    anyhow::bail!("line {line_number}: key is only allowed for `storage` entries");
+                }
+                if !seen_fields.insert((address, field.to_owned())) {
+                    anyhow::bail!("line {line_number}: duplicate `{field}` entry for {address:?}");
+                }
+            }
+
+            let allocation = this.allocations.entry(address).or_default();
+            let parsed = match field {
+                "balance" => parse_quantity(value).map(|value| allocation.balance = value),
+                "nonce" => parse_quantity(value).map(|value| allocation.nonce = value),
+                "deploymentNonce" => {
+                    parse_quantity(value).map(|value| allocation.deployment_nonce = value)
+                }
+                "code" => parse_bytes(value).map(|code| allocation.code = Some(code)),
+                "storage" => parse_word(key).and_then(|key| {
+                    let prev_value = allocation.storage.insert(key, parse_word(value)?);
+                    anyhow::ensure!(
+                        prev_value.is_none(),
+                        "duplicate `storage` entry for {key:?}"
+                    );
+                    Ok(())
+                }),
+                _ => Err(anyhow::anyhow!("unknown field `{field}`")),
+            };
+            parsed.with_context(|| format!("line {line_number}"))?;
+        }
+        Ok(this)
+    }
+
+    /// Converts this manifest into a genesis state containing the provided system contracts and all allocations
+    /// from the manifest. Besides allocation data, the state updates the base token total supply so that
+    /// it matches the sum of allocated balances.
+    pub fn to_genesis_state(
+        &self,
+        system_contracts: &[DeployedContract],
+    ) -> Result<GenesisState, ManifestError> {
+        let system_addresses: HashSet<_> = system_contracts
+            .iter()
+            .map(|contract| *contract.account_id.address())
+            .collect();
+
+        let mut storage_logs = get_storage_logs(system_contracts);
+        let mut factory_deps: BTreeMap<H256, Vec<u8>> = system_contracts
+            .iter()
+            .map(|contract| {
+                let hash = BytecodeHash::for_bytecode(&contract.bytecode).value();
+                (hash, contract.bytecode.clone())
+            })
+            .collect();
+        let mut total_supply = U256::zero();
+
+        for (address, allocation) in &self.allocations {
+            if address_to_u256(address) <= MAX_RESERVED_ADDRESS.into()
+                || system_addresses.contains(address)
+            {
+                return Err(ManifestError::ReservedAddress(*address));
+            }
+
+            if !allocation.balance.is_zero() {
+                total_supply = total_supply
+                    .checked_add(allocation.balance)
+                    .ok_or(ManifestError::TotalSupplyOverflow)?;
+                storage_logs.push(StorageLog::new_write_log(
+                    storage_key_for_eth_balance(address),
+                    u256_to_h256(allocation.balance),
+                ));
+            }
+
+            let max_nonce = U256::one() << 128;
+            if allocation.nonce >= max_nonce || allocation.deployment_nonce >= max_nonce {
+                return Err(ManifestError::NonceOutOfRange(*address));
+            }
+            let full_nonce = nonces_to_full_nonce(allocation.nonce, allocation.deployment_nonce);
+            if !full_nonce.is_zero() {
+                storage_logs.push(StorageLog::new_write_log(
+                    get_nonce_key(address),
+                    u256_to_h256(full_nonce),
+                ));
+            }
+
+            if let Some(code) = &allocation.code {
+                validate_bytecode(code).map_err(|source| ManifestError::InvalidBytecode {
+                    address: *address,
+                    source,
+                })?;
+                let hash = BytecodeHash::for_bytecode(code).value();
+                storage_logs.push(StorageLog::new_write_log(get_code_key(address), hash));
+                if !factory_deps.contains_key(&hash) {
+                    storage_logs.push(StorageLog::new_write_log(
+                        get_known_code_key(&hash),
+                        H256::from_low_u64_be(1),
+                    ));
+                    factory_deps.insert(hash, code.clone());
+                }
+            }
+
+            let account = AccountTreeId::new(*address);
+            for (key, value) in &allocation.storage {
+                if !value.is_zero() {
+                    let key = StorageKey::new(account, *key);
+                    storage_logs.push(StorageLog::new_write_log(key, *value));
+                }
+            }
+        }
+
+        if !total_supply.is_zero() {
+            let total_supply_key = StorageKey::new(
+                AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
+                H256::from_low_u64_be(BASE_TOKEN_TOTAL_SUPPLY_SLOT),
+            );
+            storage_logs.push(StorageLog::new_write_log(
+                total_supply_key,
+                u256_to_h256(total_supply),
+            ));
+        }
+
+        Ok(GenesisState {
+            storage_logs: storage_logs
+                .iter()
+                .map(|log| StorageLogRow {
+                    address: log.key.address().0,
+                    key: log.key.key().0,
+                    value: log.value.0,
+                })
+                .collect(),
+            factory_deps: factory_deps
+                .into_iter()
+                .map(|(hash, bytecode)| FactoryDepRow {
+                    bytecode_hash: hash.0,
+                    bytecode,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::system_contracts::get_system_smart_contracts;
+
+    use super::*;
+
+    const ACCOUNT: Address = Address::repeat_byte(0x11);
+    const CONTRACT: Address = Address::repeat_byte(0x22);
+
+    fn test_bytecode() -> Vec<u8> {
+        vec![1; 96]
+    }
+
+    fn test_manifest() -> GenesisManifest {
+        let account = GenesisAllocation {
+            balance: 1_000_000.into(),
+            nonce: 3.into(),
+            ..GenesisAllocation::default()
+        };
+        let contract = GenesisAllocation {
+            balance: 500.into(),
+            deployment_nonce: 1.into(),
+            code: Some(test_bytecode()),
+            storage: BTreeMap::from([(H256::zero(), H256::from_low_u64_be(42))]),
+            ..GenesisAllocation::default()
+        };
+        GenesisManifest {
+            allocations: BTreeMap::from([(ACCOUNT, account), (CONTRACT, contract)]),
+        }
+    }
+
+    #[test]
+    fn parsing_json_manifest() {
+        let json = serde_json::json!({
+            format!("{ACCOUNT:?}"): { "balance": "1000000", "nonce": "0x3" },
+            format!("{CONTRACT:?}"): {
+                "balance": "0x1f4",
+                "deploymentNonce": "1",
+                "code": format!("0x{}", hex::encode(test_bytecode())),
+                "storage": { "0x0": "42" },
+            },
+        });
+        let manifest = GenesisManifest::from_json(&json.to_string()).unwrap();
+        assert_eq!(manifest, test_manifest());
+
+        let json = serde_json::json!({ format!("{ACCOUNT:?}"): { "balance": "1", "unknown": 1 } });
+        GenesisManifest::from_json(&json.to_string()).unwrap_err();
+    }
+
+    #[test]
+    fn parsing_csv_manifest() {
+        let csv = format!(
+            "address,field,key,value\n\
+             # comment\n\
+             {ACCOUNT:?},balance,,1000000\n\
+             {ACCOUNT:?},nonce,,0x3\n\
+             \n\
+             {CONTRACT:?},balance,,0x1f4\n\
+             {CONTRACT:?},deploymentNonce,,1\n\
+             {CONTRACT:?},code,,0x{}\n\
+             {CONTRACT:?},storage,0x0,42\n",
+            hex::encode(test_bytecode())
+        );
+        let manifest = GenesisManifest::from_csv(&csv).unwrap();
+        assert_eq!(manifest, test_manifest());
+
+        let csv = format!("{ACCOUNT:?},balance,,1\n{ACCOUNT:?},balance,,2\n");
+        let err = GenesisManifest::from_csv(&csv).unwrap_err();
+        assert!(format!("{err:#}").contains("duplicate"), "{err:#}");
+
+        let csv = format!("{ACCOUNT:?},storage,0x1,1\n{ACCOUNT:?},storage,1,2\n");
+        let err = GenesisManifest::from_csv(&csv).unwrap_err();
+        assert!(format!("{err:#}").contains("duplicate"), "{err:#}");
+
+        let csv = format!("{ACCOUNT:?},balance,0x1,1\n");
+        let err = GenesisManifest::from_csv(&csv).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("key is only allowed"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn converting_manifest_to_genesis_state() {
+        let system_contracts = get_system_smart_contracts(false);
+        let state = test_manifest().to_genesis_state(&system_contracts).unwrap();
+
+        let logs: Vec<_> = state.storage_logs.iter().map(StorageLog::from).collect();
+        let value_of = |key: StorageKey| {
+            let mut matching = logs.iter().filter(|log| log.key == key);
+            let log = matching
+                .next()
+                .unwrap_or_else(|| panic!("no log for {key:?}"));
+            assert!(matching.next().is_none(), "duplicate log for {key:?}");
+            log.value
+        };
+
+        let bytecode_hash = BytecodeHash::for_bytecode(&test_bytecode()).value();
+        assert_eq!(
+            value_of(storage_key_for_eth_balance(&ACCOUNT)),
+            u256_to_h256(1_000_000.into())
+        );
+        assert_eq!(value_of(get_nonce_key(&ACCOUNT)), H256::from_low_u64_be(3));
+        assert_eq!(
+            value_of(get_nonce_key(&CONTRACT)),
+            u256_to_h256(nonces_to_full_nonce(0.into(), 1.into()))
+        );
+        assert_eq!(value_of(get_code_key(&CONTRACT)), bytecode_hash);
+        assert_eq!(
+            value_of(get_known_code_key(&bytecode_hash)),
+            H256::from_low_u64_be(1)
+        );
+        assert_eq!(
+            value_of(StorageKey::new(AccountTreeId::new(CONTRACT), H256::zero())),
+            H256::from_low_u64_be(42)
+        );
+        let total_supply_key = StorageKey::new(
+            AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
+            H256::from_low_u64_be(BASE_TOKEN_TOTAL_SUPPLY_SLOT),
+        );
+        assert_eq!(value_of(total_supply_key), u256_to_h256(1_000_500.into()));
+
+        assert!(state
+            .factory_deps
+            .iter()
+            .any(|dep| dep.bytecode_hash == bytecode_hash.0 && dep.bytecode == test_bytecode()));
+        for contract in &system_contracts {
+            assert_eq!(
+                value_of(get_code_key(contract.account_id.address())),
+                BytecodeHash::for_bytecode(&contract.bytecode).value()
+            );
+        }
+    }
+
+    #[test]
+    fn validating_manifest() {
+        let system_contracts = get_system_smart_contracts(false);
+
+        let mut manifest = test_manifest();
+        manifest.allocations.insert(
+            Address::from_low_u64_be(0x8001),
+            GenesisAllocation::default(),
+        );
+        let err = manifest.to_genesis_state(&system_contracts).unwrap_err();
+        assert!(matches!(err, ManifestError::ReservedAddress(_)), "{err}");
+
+        let mut manifest = test_manifest();
+        manifest.allocations.get_mut(&CONTRACT).unwrap().code = Some(vec![1; 64]);
+        let err = manifest.to_genesis_state(&system_contracts).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ManifestError::InvalidBytecode {
+                    source: InvalidBytecodeError::BytecodeLengthInWordsIsEven,
+                    ..
+                }
+            ),
+            "{err}"
+        );
+
+        let mut manifest = test_manifest();
+        manifest.allocations.get_mut(&ACCOUNT).unwrap().nonce = U256::one() << 128;
+        let err = manifest.to_genesis_state(&system_contracts).unwrap_err();
+        assert!(matches!(err, ManifestError::NonceOutOfRange(_)), "{err}");
+
+        let mut manifest = test_manifest();
+        manifest.allocations.get_mut(&ACCOUNT).unwrap().balance = U256::MAX;
+        let err = manifest.to_genesis_state(&system_contracts).unwrap_err();
+        assert!(matches!(err, ManifestError::TotalSupplyOverflow), "{err}");
+    }
+}
@@ -64,3 +64,49 @@ async fn running_genesis_with_non_latest_protocol_version() {
     insert_genesis_batch(&mut conn, &params).await.unwrap();
     assert!(!conn.blocks_dal().is_genesis_needed().await.unwrap());
 }
+
+#[tokio::test]
+async fn running_genesis_with_manifest_state() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    conn.blocks_dal().delete_genesis().await.unwrap();
+
+    let account = Address::repeat_byte(0x11);
+    let manifest = manifest::GenesisManifest {
+        allocations: [(
+            account,
+            manifest::GenesisAllocation {
+                balance: 1_000.into(),
+                ..Default::default()
+            },
+        )]
+        .into(),
+    };
+    let mut params = GenesisParams::mock();
+    let state = manifest
+        .to_genesis_state(params.system_contracts())
+        .unwrap();
+
+    // Compute expected genesis params the same way as `custom_genesis_export` does.
+    let storage_logs: Vec<_> = state.storage_logs.iter().map(StorageLog::from).collect();
+    let (expected_params, _) = make_genesis_batch_params(
+        get_deduped_log_queries(&storage_logs),
+        params.base_system_contracts().hashes(),
+        params.minor_protocol_version(),
+    );
+    params.config.genesis_root_hash = Some(expected_params.root_hash);
+    params.config.genesis_commitment = Some(expected_params.commitment);
+    params.config.rollup_last_leaf_index = Some(expected_params.rollup_last_leaf_index);
+
+    let root_hash = ensure_genesis_state(&mut conn, &params, Some(state))
+        .await
+        .unwrap();
+    assert_eq!(root_hash, expected_params.root_hash);
+
+    let balance = conn
+        .storage_web3_dal()
+        .get_value(&zksync_types::utils::storage_key_for_eth_balance(&account))
+        .await
+        .unwrap();
+    assert_eq!(balance, u256_to_h256(1_000.into()));
+}
@@ -1,3 +1,4 @@
 pub mod blocks_state_reporter;
 mod metrics;
 pub mod periodic_job;
+pub mod settlement_sla;
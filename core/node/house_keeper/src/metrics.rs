@@ -1,4 +1,8 @@
-use vise::{Gauge, Metrics};
+use std::time::Duration;
+
+use vise::{Family, Gauge, Metrics, Unit};
+
+use crate::settlement_sla::SettlementStage;
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "fri_prover")]
@@ -9,3 +13,18 @@ pub(crate) struct FriProverMetrics {
 
 #[vise::register]
 pub(crate) static FRI_PROVER_METRICS: vise::Global<FriProverMetrics> = vise::Global::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "house_keeper_settlement")]
+pub(crate) struct SettlementSlaMetrics {
+    /// Configured percentile of L1 batch latencies for each tracked settlement stage.
+    #[metrics(unit = Unit::Seconds)]
+    pub stage_latency: Family<SettlementStage, Gauge<Duration>>,
+    /// Number of L1 batches used to compute the latency percentile for each tracked settlement stage.
+    pub stage_samples: Family<SettlementStage, Gauge<usize>>,
+    /// Whether the SLA for a settlement stage is breached (1) or not (0).
+    pub sla_breached: Family<SettlementStage, Gauge<u64>>,
+}
+
+#[vise::register]
+pub(crate) static SETTLEMENT_SLA_METRICS: vise::Global<SettlementSlaMetrics> = vise::Global::new();
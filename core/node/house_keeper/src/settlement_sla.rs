@@ -0,0 +1,385 @@
+//! Tracking of L1 batch settlement latencies against configured SLAs.
+
+use std::{collections::BTreeMap, fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use vise::{EncodeLabelSet, EncodeLabelValue};
+use zksync_config::configs::house_keeper::HouseKeeperConfig;
+use zksync_dal::{blocks_dal::L1BatchStageTimestamps, ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+
+use crate::{metrics::SETTLEMENT_SLA_METRICS, periodic_job::PeriodicJob};
+
+/// Settlement stage of an L1 batch, for which latency is tracked.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    EncodeLabelValue,
+    EncodeLabelSet,
+)]
+#[serde(rename_all = "snake_case")]
+#[metrics(label = "stage", rename_all = "snake_case")]
+pub enum SettlementStage {
+    /// From sealing an L1 batch to confirming its commit transaction.
+    Commit,
+    /// From confirming the commit transaction to confirming the prove transaction.
+    Prove,
+    /// From confirming the prove transaction to confirming the execute transaction.
+    Execute,
+    /// From sealing an L1 batch to dispatching its pubdata to the DA layer.
+    DaDispatch,
+    /// From dispatching pubdata to the DA layer to its inclusion.
+    DaInclusion,
+}
+
+impl SettlementStage {
+    const ALL: [Self; 5] = [
+        Self::Commit,
+        Self::Prove,
+        Self::Execute,
+        Self::DaDispatch,
+        Self::DaInclusion,
+    ];
+
+    /// Returns start and end timestamps of this stage for the specified batch.
+    fn bounds(
+        self,
+        timestamps: &L1BatchStageTimestamps,
+    ) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        match self {
+            Self::Commit => (timestamps.sealed_at, timestamps.committed_at),
+            Self::Prove => (timestamps.committed_at, timestamps.proven_at),
+            Self::Execute => (timestamps.proven_at, timestamps.executed_at),
+            Self::DaDispatch => (timestamps.sealed_at, timestamps.da_dispatched_at),
+            Self::DaInclusion => (timestamps.da_dispatched_at, timestamps.da_included_at),
+        }
+    }
+
+    fn is_da_stage(self) -> bool {
+        matches!(self, Self::DaDispatch | Self::DaInclusion)
+    }
+
+    /// Returns the stage latency for the specified batch. If the stage is started, but not finished yet,
+    /// the latency is measured until `now`, so that stuck batches are accounted for. Returns `None` if the stage
+    /// isn't started or isn't applicable to the batch (DA stages for batches without a DA requirement).
+    fn latency(self, timestamps: &L1BatchStageTimestamps, now: DateTime<Utc>) -> Option<Duration> {
+        if self.is_da_stage() && !timestamps.da_required {
+            return None;
+        }
+        let (start, end) = self.bounds(timestamps);
+        let start = start?;
+        let end = end.unwrap_or(now);
+        // Latency can be negative if clocks of the involved components are out of sync.
+        Some((end - start).to_std().unwrap_or_default())
+    }
+
+    fn sla(self, config: &HouseKeeperConfig) -> Option<Duration> {
+        let sla_ms = match self {
+            Self::Commit => config.settlement_sla_commit_latency_ms,
+            Self::Prove => config.settlement_sla_prove_latency_ms,
+            Self::Execute => config.settlement_sla_execute_latency_ms,
+            Self::DaDispatch => config.settlement_sla_da_dispatch_latency_ms,
+            Self::DaInclusion => config.settlement_sla_da_inclusion_latency_ms,
+        };
+        sla_ms.map(Duration::from_millis)
+    }
+}
+
+impl fmt::Display for SettlementStage {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::Commit => "commit",
+            Self::Prove => "prove",
+            Self::Execute => "execute",
+            Self::DaDispatch => "da_dispatch",
+            Self::DaInclusion => "da_inclusion",
+        })
+    }
+}
+
+/// Computes the percentile of `values` using the nearest-rank method. Returns `None` if `values` are empty.
+fn nearest_rank_percentile(values: &mut [Duration], percentile: f64) -> Option<Duration> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * values.len() as f64).ceil() as usize;
+    Some(values[rank.clamp(1, values.len()) - 1])
+}
+
+#[derive(Debug, Serialize)]
+struct StageSlaDetails {
+    latency_ms: u64,
+    sla_ms: u64,
+    samples: usize,
+}
+
+/// Health details reported by [`SettlementSlaTracker`].
+#[derive(Debug, Serialize)]
+struct SettlementSlaDetails {
+    percentile: f64,
+    stages: BTreeMap<SettlementStage, StageSlaDetails>,
+    breached_stages: Vec<SettlementStage>,
+}
+
+impl SettlementSlaDetails {
+    /// Computes stage latencies for the specified L1 batches and compares them against `slas`.
+    /// Also reports the corresponding metrics.
+    fn new(
+        percentile: f64,
+        slas: &[(SettlementStage, Duration)],
+        timestamps: &[L1BatchStageTimestamps],
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut details = Self {
+            percentile,
+            stages: BTreeMap::new(),
+            breached_stages: vec![],
+        };
+        for &(stage, sla) in slas {
+            let mut latencies: Vec<_> = timestamps
+                .iter()
+                .filter_map(|batch| stage.latency(batch, now))
+                .collect();
+            let samples = latencies.len();
+            SETTLEMENT_SLA_METRICS.stage_samples[&stage].set(samples);
+            let Some(latency) = nearest_rank_percentile(&mut latencies, percentile) else {
+                SETTLEMENT_SLA_METRICS.sla_breached[&stage].set(0);
+                continue;
+            };
+
+            SETTLEMENT_SLA_METRICS.stage_latency[&stage].set(latency);
+            let is_breached = latency > sla;
+            SETTLEMENT_SLA_METRICS.sla_breached[&stage].set(is_breached.into());
+            if is_breached {
+                tracing::warn!(
+                    "Settlement SLA for stage `{stage}` is breached: p{percentile} latency over {samples} latest \
+                     L1 batches is {latency:?}, SLA is {sla:?}"
+                );
+                details.breached_stages.push(stage);
+            }
+            details.stages.insert(
+                stage,
+                StageSlaDetails {
+                    latency_ms: latency.as_millis() as u64,
+                    sla_ms: sla.as_millis() as u64,
+                    samples,
+                },
+            );
+        }
+        details
+    }
+
+    fn health(&self) -> Health {
+        let status = if self.breached_stages.is_empty() {
+            HealthStatus::Ready
+        } else {
+            HealthStatus::Affected
+        };
+        Health::from(status).with_details(self)
+    }
+}
+
+/// Periodically computes rolling percentiles of settlement stage latencies for the latest sealed L1 batches
+/// and compares them against configured SLAs. Only stages with a configured SLA are tracked. If an SLA is breached,
+/// the tracker health check is switched to [`HealthStatus::Affected`].
+///
+/// Percentiles are recomputed from Postgres on each check, so the tracker doesn't need to persist any state.
+#[derive(Debug, Clone)]
+pub struct SettlementSlaTracker {
+    check_interval: Duration,
+    window_size: usize,
+    percentile: f64,
+    slas: Vec<(SettlementStage, Duration)>,
+    connection_pool: ConnectionPool<Core>,
+    health_updater: Arc<HealthUpdater>,
+}
+
+impl SettlementSlaTracker {
+    /// Creates a new tracker. Returns `None` if settlement SLA tracking is disabled in the `config`.
+    pub fn new(config: &HouseKeeperConfig, connection_pool: ConnectionPool<Core>) -> Option<Self> {
+        let check_interval = config.settlement_sla_check_interval()?;
+        let slas = SettlementStage::ALL
+            .into_iter()
+            .filter_map(|stage| Some((stage, stage.sla(config)?)))
+            .collect();
+        Some(Self {
+            check_interval,
+            window_size: config.settlement_sla_window_size.max(1) as usize,
+            percentile: config.settlement_sla_percentile,
+            slas,
+            connection_pool,
+            health_updater: Arc::new(ReactiveHealthCheck::new("settlement_sla").1),
+        })
+    }
+
+    /// Returns the health check for this tracker.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    async fn check_slas(&self) -> anyhow::Result<()> {
+        let mut conn = self
+            .connection_pool
+            .connection_tagged("house_keeper")
+            .await?;
+        let timestamps = conn
+            .blocks_dal()
+            .get_l1_batch_stage_timestamps(self.window_size)
+            .await?;
+        drop(conn);
+
+        let details =
+            SettlementSlaDetails::new(self.percentile, &self.slas, &timestamps, Utc::now());
+        self.health_updater.update(details.health());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PeriodicJob for SettlementSlaTracker {
+    const SERVICE_NAME: &'static str = "SettlementSlaTracker";
+
+    async fn run_routine_task(&mut self) -> anyhow::Result<()> {
+        self.check_slas().await
+    }
+
+    fn polling_interval_ms(&self) -> u64 {
+        self.check_interval.as_millis() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::L1BatchNumber;
+
+    use super::*;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    fn batch_timestamps(
+        number: u32,
+        sealed_at: DateTime<Utc>,
+        committed_at: Option<DateTime<Utc>>,
+    ) -> L1BatchStageTimestamps {
+        L1BatchStageTimestamps {
+            number: L1BatchNumber(number),
+            sealed_at: Some(sealed_at),
+            committed_at,
+            proven_at: None,
+            executed_at: None,
+            da_dispatched_at: None,
+            da_included_at: None,
+            da_required: false,
+        }
+    }
+
+    #[test]
+    fn computing_nearest_rank_percentile() {
+        assert_eq!(nearest_rank_percentile(&mut [], 50.0), None);
+        assert_eq!(nearest_rank_percentile(&mut [secs(5)], 0.0), Some(secs(5)));
+        assert_eq!(nearest_rank_percentile(&mut [secs(5)], 99.0), Some(secs(5)));
+
+        let mut values: Vec<_> = (1..=10).rev().map(secs).collect();
+        assert_eq!(nearest_rank_percentile(&mut values, 0.0), Some(secs(1)));
+        assert_eq!(nearest_rank_percentile(&mut values, 10.0), Some(secs(1)));
+        assert_eq!(nearest_rank_percentile(&mut values, 11.0), Some(secs(2)));
+        assert_eq!(nearest_rank_percentile(&mut values, 50.0), Some(secs(5)));
+        assert_eq!(nearest_rank_percentile(&mut values, 95.0), Some(secs(10)));
+        assert_eq!(nearest_rank_percentile(&mut values, 100.0), Some(secs(10)));
+        // Out-of-range percentiles are clamped.
+        assert_eq!(nearest_rank_percentile(&mut values, 150.0), Some(secs(10)));
+    }
+
+    #[test]
+    fn computing_stage_latency() {
+        let now = Utc::now();
+        let sealed_at = now - chrono::Duration::seconds(100);
+        let committed_at = now - chrono::Duration::seconds(40);
+
+        let finished = batch_timestamps(1, sealed_at, Some(committed_at));
+        assert_eq!(
+            SettlementStage::Commit.latency(&finished, now),
+            Some(secs(60))
+        );
+        // The prove stage is started, but not finished, so its latency is measured until now.
+        assert_eq!(
+            SettlementStage::Prove.latency(&finished, now),
+            Some(secs(40))
+        );
+        // The execute stage is not started.
+        assert_eq!(SettlementStage::Execute.latency(&finished, now), None);
+
+        let in_progress = batch_timestamps(2, sealed_at, None);
+        assert_eq!(
+            SettlementStage::Commit.latency(&in_progress, now),
+            Some(secs(100))
+        );
+        assert_eq!(SettlementStage::Prove.latency(&in_progress, now), None);
+
+        // DA stages are not applicable to batches without a DA requirement.
+        assert_eq!(SettlementStage::DaDispatch.latency(&in_progress, now), None);
+        let with_da = L1BatchStageTimestamps {
+            da_required: true,
+            ..in_progress
+        };
+        assert_eq!(
+            SettlementStage::DaDispatch.latency(&with_da, now),
+            Some(secs(100))
+        );
+
+        // Clock skew must not lead to a panic.
+        let skewed = batch_timestamps(3, now, Some(sealed_at));
+        assert_eq!(
+            SettlementStage::Commit.latency(&skewed, now),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn health_is_affected_on_sla_breach() {
+        let now = Utc::now();
+        let timestamps: Vec<_> = (0..10)
+            .map(|i| {
+                let sealed_at = now - chrono::Duration::seconds(100);
+                let committed_at = sealed_at + chrono::Duration::seconds(i + 1);
+                batch_timestamps(i as u32, sealed_at, Some(committed_at))
+            })
+            .collect();
+        let slas = [(SettlementStage::Commit, secs(10))];
+
+        let details = SettlementSlaDetails::new(90.0, &slas, &timestamps, now);
+        assert_eq!(details.breached_stages, []);
+        assert_eq!(details.stages[&SettlementStage::Commit].latency_ms, 9_000);
+        assert_eq!(details.stages[&SettlementStage::Commit].samples, 10);
+        assert_eq!(details.health().status(), HealthStatus::Ready);
+
+        // Add a stuck batch, which should be accounted for by the percentile.
+        let mut timestamps = timestamps;
+        timestamps.push(batch_timestamps(
+            10,
+            now - chrono::Duration::seconds(60),
+            None,
+        ));
+        timestamps.push(batch_timestamps(
+            11,
+            now - chrono::Duration::seconds(50),
+            None,
+        ));
+        let details = SettlementSlaDetails::new(90.0, &slas, &timestamps, now);
+        assert_eq!(details.breached_stages, [SettlementStage::Commit]);
+        assert_eq!(details.stages[&SettlementStage::Commit].latency_ms, 50_000);
+        assert_eq!(details.health().status(), HealthStatus::Affected);
+    }
+}
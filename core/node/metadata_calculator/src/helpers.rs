@@ -453,7 +453,7 @@ impl WeakAsyncTreeReader {
 }
 
 /// Lazily initialized [`AsyncTreeReader`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LazyAsyncTreeReader(pub(super) watch::Receiver<Option<AsyncTreeReader>>);
 
 impl LazyAsyncTreeReader {
@@ -148,7 +148,7 @@ mod tests {
     use test_casing::test_casing;
     use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
     use zksync_node_test_utils::prepare_recovery_snapshot;
-    use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
+    use zksync_types::{pruning::PruningProfile, L1BatchNumber, L2BlockNumber, H256};
 
     use super::*;
     use crate::{
@@ -191,12 +191,17 @@ mod tests {
         // Add a pruning log to force pruning.
         storage
             .pruning_dal()
-            .hard_prune_batches_range(L1BatchNumber(3), L2BlockNumber(3))
+            .hard_prune_batches_range(L1BatchNumber(3), L2BlockNumber(3), PruningProfile::Full)
             .await
             .unwrap();
         storage
             .pruning_dal()
-            .insert_hard_pruning_log(L1BatchNumber(3), L2BlockNumber(3), H256::zero())
+            .insert_hard_pruning_log(
+                L1BatchNumber(3),
+                L2BlockNumber(3),
+                H256::zero(),
+                PruningProfile::Full,
+            )
             .await
             .unwrap();
 
@@ -331,6 +336,7 @@ mod tests {
                 snapshot_recovery.l1_batch_number + 3,
                 snapshot_recovery.l2_block_number + 3,
                 H256::zero(), // not used
+                PruningProfile::Full,
             )
             .await
             .unwrap();
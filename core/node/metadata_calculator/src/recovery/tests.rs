@@ -16,7 +16,7 @@ use zksync_merkle_tree::{domain::ZkSyncTree, recovery::PersistenceThreadHandle,
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::prepare_recovery_snapshot;
 use zksync_storage::RocksDB;
-use zksync_types::L1BatchNumber;
+use zksync_types::{pruning::PruningProfile, L1BatchNumber};
 
 use super::*;
 use crate::{
@@ -127,12 +127,12 @@ async fn prune_storage(pool: &ConnectionPool<Core>, pruned_l1_batch: L1BatchNumb
 
     storage
         .pruning_dal()
-        .insert_soft_pruning_log(pruned_l1_batch, pruned_l2_block)
+        .insert_soft_pruning_log(pruned_l1_batch, pruned_l2_block, PruningProfile::Full)
         .await
         .unwrap();
     let pruning_stats = storage
         .pruning_dal()
-        .hard_prune_batches_range(pruned_l1_batch, pruned_l2_block)
+        .hard_prune_batches_range(pruned_l1_batch, pruned_l2_block, PruningProfile::Full)
         .await
         .unwrap();
     assert!(
@@ -141,7 +141,12 @@ async fn prune_storage(pool: &ConnectionPool<Core>, pruned_l1_batch: L1BatchNumb
     );
     storage
         .pruning_dal()
-        .insert_hard_pruning_log(pruned_l1_batch, pruned_l2_block, root_hash)
+        .insert_hard_pruning_log(
+            pruned_l1_batch,
+            pruned_l2_block,
+            root_hash,
+            PruningProfile::Full,
+        )
         .await
         .unwrap();
 }
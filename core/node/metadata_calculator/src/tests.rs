@@ -21,6 +21,7 @@ use zksync_prover_interface::inputs::WitnessInputMerklePaths;
 use zksync_storage::RocksDB;
 use zksync_types::{
     block::{L1BatchHeader, L1BatchTreeData},
+    pruning::PruningProfile,
     AccountTreeId, Address, L1BatchNumber, L2BlockNumber, StorageKey, StorageLog, H256,
 };
 
@@ -402,17 +403,22 @@ async fn error_on_pruned_next_l1_batch(sealed_protective_reads: bool) {
     extend_db_state(&mut storage, new_logs).await;
     storage
         .pruning_dal()
-        .insert_soft_pruning_log(L1BatchNumber(5), L2BlockNumber(5))
+        .insert_soft_pruning_log(L1BatchNumber(5), L2BlockNumber(5), PruningProfile::Full)
         .await
         .unwrap();
     storage
         .pruning_dal()
-        .hard_prune_batches_range(L1BatchNumber(5), L2BlockNumber(5))
+        .hard_prune_batches_range(L1BatchNumber(5), L2BlockNumber(5), PruningProfile::Full)
         .await
         .unwrap();
     storage
         .pruning_dal()
-        .insert_hard_pruning_log(L1BatchNumber(5), L2BlockNumber(5), H256::zero())
+        .insert_hard_pruning_log(
+            L1BatchNumber(5),
+            L2BlockNumber(5),
+            H256::zero(),
+            PruningProfile::Full,
+        )
         .await
         .unwrap();
     // Sanity check: there should be no pruned batch headers.
@@ -0,0 +1,75 @@
+pub use zksync_address_index_backfill::AddressIndex;
+use zksync_address_index_backfill::AddressIndexBackfill;
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::StopReceiver,
+    task::{Task, TaskId, TaskKind},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for an address index backfill.
+///
+/// Responsible for initializing and running of [`AddressIndexBackfill`] task, that populates the specified
+/// [`AddressIndex`] for blocks sealed before the index was enabled.
+#[derive(Debug)]
+pub struct AddressIndexBackfillLayer {
+    index: AddressIndex,
+}
+
+impl AddressIndexBackfillLayer {
+    pub fn new(index: AddressIndex) -> Self {
+        Self { index }
+    }
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub address_index_backfill: AddressIndexBackfill,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for AddressIndexBackfillLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "address_index_backfill_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = input.master_pool.get_singleton().await?;
+        let address_index_backfill = AddressIndexBackfill::new(pool, self.index);
+        Ok(Output {
+            address_index_backfill,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for AddressIndexBackfill {
+    fn kind(&self) -> TaskKind {
+        TaskKind::OneshotTask
+    }
+
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::OneshotTask)
+    }
+
+    fn id(&self) -> TaskId {
+        format!("{}_backfill", self.index().name()).into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -10,12 +10,13 @@ use zksync_types::L1ChainId;
 use crate::{
     implementations::resources::{
         eth_interface::EthInterfaceResource,
+        healthcheck::AppHealthCheckResource,
         l1_tx_params::TxParamsResource,
         pools::{MasterPool, PoolResource},
         price_api_client::PriceAPIClientResource,
     },
     service::StopReceiver,
-    task::{Task, TaskId},
+    task::{RestartPolicy, SupervisedTask, Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
@@ -24,12 +25,16 @@ use crate::{
 ///
 /// Responsible for orchestrating communications with external API feeds to get ETH<->BaseToken
 /// conversion ratios and persisting them both in the DB and in the L1.
+///
+/// The persister task is restarted on failure (e.g., if the price API is unavailable) according to
+/// the [`RestartPolicy`].
 #[derive(Debug)]
 pub struct BaseTokenRatioPersisterLayer {
     config: BaseTokenAdjusterConfig,
     contracts_config: ContractsConfig,
     wallets_config: Wallets,
     l1_chain_id: L1ChainId,
+    restart_policy: RestartPolicy,
 }
 
 #[derive(Debug, FromContext)]
@@ -40,13 +45,15 @@ pub struct Input {
     pub price_api_client: PriceAPIClientResource,
     pub eth_client: EthInterfaceResource,
     pub tx_params: TxParamsResource,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
 }
 
 #[derive(Debug, IntoContext)]
 #[context(crate = crate)]
 pub struct Output {
     #[context(task)]
-    pub persister: BaseTokenRatioPersister,
+    pub persister: SupervisedTask,
 }
 
 impl BaseTokenRatioPersisterLayer {
@@ -61,8 +68,14 @@ impl BaseTokenRatioPersisterLayer {
             contracts_config,
             wallets_config,
             l1_chain_id,
+            restart_policy: RestartPolicy::default(),
         }
     }
+
+    pub fn with_restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.restart_policy = restart_policy;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -122,6 +135,17 @@ impl WiringLayer for BaseTokenRatioPersisterLayer {
             price_api_client.0,
             l1_behaviour,
         );
+        // Each restart uses a fresh clone of the persister, so the L1 ratio is re-fetched after a restart.
+        let persister = SupervisedTask::from_cloneable(
+            "base_token_ratio_persister",
+            self.restart_policy,
+            persister,
+        );
+        input
+            .app_health
+            .0
+            .insert_component(persister.health_check())
+            .map_err(WiringError::internal)?;
 
         Ok(Output { persister })
     }
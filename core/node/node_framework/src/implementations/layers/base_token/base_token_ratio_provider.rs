@@ -6,10 +6,11 @@ use zksync_config::BaseTokenAdjusterConfig;
 use crate::{
     implementations::resources::{
         base_token_ratio_provider::BaseTokenRatioProviderResource,
+        healthcheck::AppHealthCheckResource,
         pools::{PoolResource, ReplicaPool},
     },
     service::StopReceiver,
-    task::{Task, TaskId},
+    task::{RestartPolicy, SupervisedTask, Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
@@ -22,14 +23,26 @@ use crate::{
 ///
 /// If the base token is ETH, a default, no-op impl of the BaseTokenRatioProviderResource is used by other
 /// layers to always return a conversion ratio of 1.
+///
+/// The polling task is restarted on failure according to the [`RestartPolicy`]; in the meantime, the resource
+/// keeps serving the last cached ratio.
 #[derive(Debug)]
 pub struct BaseTokenRatioProviderLayer {
     config: BaseTokenAdjusterConfig,
+    restart_policy: RestartPolicy,
 }
 
 impl BaseTokenRatioProviderLayer {
     pub fn new(config: BaseTokenAdjusterConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            restart_policy: RestartPolicy::default(),
+        }
+    }
+
+    pub fn with_restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.restart_policy = restart_policy;
+        self
     }
 }
 
@@ -37,6 +50,8 @@ impl BaseTokenRatioProviderLayer {
 #[context(crate = crate)]
 pub struct Input {
     pub replica_pool: PoolResource<ReplicaPool>,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
 }
 
 #[derive(Debug, IntoContext)]
@@ -44,7 +59,7 @@ pub struct Input {
 pub struct Output {
     pub ratio_provider: BaseTokenRatioProviderResource,
     #[context(task)]
-    pub ratio_provider_task: DBBaseTokenRatioProvider,
+    pub ratio_provider_task: SupervisedTask,
 }
 
 #[async_trait::async_trait]
@@ -61,9 +76,19 @@ impl WiringLayer for BaseTokenRatioProviderLayer {
 
         let ratio_provider = DBBaseTokenRatioProvider::new(replica_pool, self.config).await?;
         // Cloning the provided preserves the internal state.
+        let ratio_provider_task = SupervisedTask::from_cloneable(
+            "base_token_ratio_provider",
+            self.restart_policy,
+            ratio_provider.clone(),
+        );
+        input
+            .app_health
+            .0
+            .insert_component(ratio_provider_task.health_check())
+            .map_err(WiringError::internal)?;
         Ok(Output {
-            ratio_provider: Arc::new(ratio_provider.clone()).into(),
-            ratio_provider_task: ratio_provider,
+            ratio_provider: Arc::new(ratio_provider).into(),
+            ratio_provider_task,
         })
     }
 }
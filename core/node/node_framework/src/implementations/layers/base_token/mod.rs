@@ -2,13 +2,20 @@ use std::{str::FromStr, sync::Arc};
 
 use zksync_config::configs::ExternalPriceApiClientConfig;
 use zksync_external_price_api::{
-    cmc_api::CmcPriceApiClient, coingecko_api::CoinGeckoPriceAPIClient,
-    forced_price_client::ForcedPriceClient, NoOpPriceAPIClient,
+    cmc_api::CmcPriceApiClient,
+    coingecko_api::CoinGeckoPriceAPIClient,
+    composite_client::{CompositePriceClient, PriceSource},
+    forced_price_client::ForcedPriceClient,
+    onchain_oracle::{ChainlinkPriceClient, UniswapV3TwapPriceClient},
+    NoOpPriceAPIClient, PriceAPIClient,
 };
+use zksync_web3_decl::client::{DynClient, L1};
 
 use crate::{
-    implementations::resources::price_api_client::PriceAPIClientResource, IntoContext, WiringError,
-    WiringLayer,
+    implementations::resources::{
+        eth_interface::EthInterfaceResource, price_api_client::PriceAPIClientResource,
+    },
+    FromContext, IntoContext, WiringError, WiringLayer,
 };
 
 pub mod base_token_ratio_persister;
@@ -21,12 +28,29 @@ enum ExternalPriceApiKind {
     Forced,
     CoinGecko,
     CoinMarketCap,
+    Chainlink,
+    UniswapV3Twap,
+    Composite,
 }
 
 #[derive(Debug, thiserror::Error)]
 #[error("Unknown external price API client source: \"{0}\"")]
 pub struct UnknownExternalPriceApiClientSourceError(String);
 
+#[derive(Debug, thiserror::Error)]
+pub enum ExternalPriceApiLayerError {
+    #[error(transparent)]
+    UnknownSource(#[from] UnknownExternalPriceApiClientSourceError),
+    #[error("Composite external price API client requires at least one source to be configured")]
+    NoCompositeSources,
+    #[error("Composite external price API client sources cannot be composite themselves")]
+    NestedCompositeSource,
+    #[error("External price API client source \"{0}\" requires on-chain oracle config")]
+    NoOnchainOracleConfig(String),
+    #[error("Uniswap v3 TWAP window must be positive")]
+    ZeroTwapWindow,
+}
+
 impl FromStr for ExternalPriceApiKind {
     type Err = UnknownExternalPriceApiClientSourceError;
 
@@ -36,19 +60,80 @@ impl FromStr for ExternalPriceApiKind {
             "forced" => Self::Forced,
             "coingecko" => Self::CoinGecko,
             "coinmarketcap" => Self::CoinMarketCap,
+            "chainlink" => Self::Chainlink,
+            "uniswap_v3_twap" => Self::UniswapV3Twap,
+            "composite" => Self::Composite,
             _ => return Err(UnknownExternalPriceApiClientSourceError(s.to_owned())),
         })
     }
 }
 
 impl ExternalPriceApiKind {
-    fn instantiate(&self, config: ExternalPriceApiClientConfig) -> PriceAPIClientResource {
-        PriceAPIClientResource(match self {
+    fn requires_l1_client(&self) -> bool {
+        matches!(self, Self::Chainlink | Self::UniswapV3Twap)
+    }
+
+    /// Checks source-specific config so that instantiating the client cannot fail.
+    fn validate(
+        &self,
+        config: &ExternalPriceApiClientConfig,
+    ) -> Result<(), ExternalPriceApiLayerError> {
+        if !self.requires_l1_client() {
+            return Ok(());
+        }
+        let oracle_config = config.onchain_oracle.as_ref().ok_or_else(|| {
+            ExternalPriceApiLayerError::NoOnchainOracleConfig(config.source.clone())
+        })?;
+        if *self == Self::UniswapV3Twap && oracle_config.twap_window_sec == 0 {
+            return Err(ExternalPriceApiLayerError::ZeroTwapWindow);
+        }
+        Ok(())
+    }
+
+    fn instantiate(
+        &self,
+        config: ExternalPriceApiClientConfig,
+        l1_client: Option<&DynClient<L1>>,
+    ) -> Arc<dyn PriceAPIClient> {
+        match self {
             Self::NoOp => Arc::new(NoOpPriceAPIClient {}),
             Self::Forced => Arc::new(ForcedPriceClient::new(config)),
             Self::CoinGecko => Arc::new(CoinGeckoPriceAPIClient::new(config)),
             Self::CoinMarketCap => Arc::new(CmcPriceApiClient::new(config)),
-        })
+            Self::Chainlink | Self::UniswapV3Twap => {
+                // On-chain oracle config is validated when the layer is created.
+                let oracle_config = config
+                    .onchain_oracle
+                    .expect("on-chain oracle price client started with no config");
+                // L1 client presence is checked when the layer is wired.
+                let l1_client = l1_client
+                    .expect("on-chain oracle price client started with no L1 client")
+                    .clone_boxed();
+                if *self == Self::Chainlink {
+                    Arc::new(ChainlinkPriceClient::new(oracle_config, l1_client))
+                } else {
+                    Arc::new(UniswapV3TwapPriceClient::new(oracle_config, l1_client))
+                }
+            }
+            Self::Composite => {
+                let composite_config = config
+                    .composite
+                    .expect("composite price client started with no config");
+                let sources = composite_config
+                    .sources
+                    .iter()
+                    .map(|source_config| {
+                        // Source kinds are validated when the layer is created.
+                        let kind: Self = source_config.source.parse().unwrap();
+                        PriceSource::new(
+                            source_config.source.clone(),
+                            kind.instantiate(source_config.clone(), l1_client),
+                        )
+                    })
+                    .collect();
+                Arc::new(CompositePriceClient::new(sources, &composite_config))
+            }
+        }
     }
 }
 
@@ -58,17 +143,63 @@ pub struct ExternalPriceApiLayer {
     config: ExternalPriceApiClientConfig,
 }
 
+impl ExternalPriceApiLayer {
+    fn requires_l1_client(&self) -> bool {
+        if self.kind.requires_l1_client() {
+            return true;
+        }
+        let composite_sources = self
+            .config
+            .composite
+            .as_ref()
+            .map(|composite| composite.sources.as_slice())
+            .unwrap_or_default();
+        self.kind == ExternalPriceApiKind::Composite
+            && composite_sources.iter().any(|source| {
+                // Source kinds are validated when the layer is created.
+                source
+                    .source
+                    .parse::<ExternalPriceApiKind>()
+                    .unwrap()
+                    .requires_l1_client()
+            })
+    }
+}
+
 impl TryFrom<ExternalPriceApiClientConfig> for ExternalPriceApiLayer {
-    type Error = UnknownExternalPriceApiClientSourceError;
+    type Error = ExternalPriceApiLayerError;
 
     fn try_from(config: ExternalPriceApiClientConfig) -> Result<Self, Self::Error> {
-        Ok(Self {
-            kind: config.source.parse()?,
-            config,
-        })
+        let kind: ExternalPriceApiKind = config.source.parse()?;
+        kind.validate(&config)?;
+        if kind == ExternalPriceApiKind::Composite {
+            let sources = config
+                .composite
+                .as_ref()
+                .map(|composite| composite.sources.as_slice())
+                .unwrap_or_default();
+            if sources.is_empty() {
+                return Err(ExternalPriceApiLayerError::NoCompositeSources);
+            }
+            for source in sources {
+                let source_kind: ExternalPriceApiKind = source.source.parse()?;
+                if source_kind == ExternalPriceApiKind::Composite {
+                    return Err(ExternalPriceApiLayerError::NestedCompositeSource);
+                }
+                source_kind.validate(source)?;
+            }
+        }
+        Ok(Self { kind, config })
     }
 }
 
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    /// L1 client used by on-chain oracle price sources.
+    pub eth_client: Option<EthInterfaceResource>,
+}
+
 #[derive(Debug, IntoContext)]
 #[context(crate = crate)]
 pub struct Output {
@@ -77,16 +208,25 @@ pub struct Output {
 
 #[async_trait::async_trait]
 impl WiringLayer for ExternalPriceApiLayer {
-    type Input = ();
+    type Input = Input;
     type Output = Output;
 
     fn layer_name(&self) -> &'static str {
         "external_price_api"
     }
 
-    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let l1_client = input.eth_client.map(|resource| resource.0);
+        if self.requires_l1_client() && l1_client.is_none() {
+            return Err(WiringError::Configuration(
+                "on-chain oracle price sources require an L1 client".into(),
+            ));
+        }
+
         Ok(Output {
-            price_api_client: self.kind.instantiate(self.config),
+            price_api_client: PriceAPIClientResource(
+                self.kind.instantiate(self.config, l1_client.as_deref()),
+            ),
         })
     }
 }
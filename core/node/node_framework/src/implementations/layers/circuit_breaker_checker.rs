@@ -56,6 +56,10 @@ impl Task for CircuitBreakerChecker {
         TaskKind::UnconstrainedTask
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::UnconstrainedTask)
+    }
+
     fn id(&self) -> TaskId {
         "circuit_breaker_checker".into()
     }
@@ -4,13 +4,13 @@ use zksync_config::configs::consensus::{ConsensusConfig, ConsensusSecrets};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_node_consensus as consensus;
 use zksync_node_framework_derive::IntoContext;
-use zksync_node_sync::{ActionQueueSender, SyncState};
+use zksync_node_sync::{ActionQueueSender, L2BlockHashCrossChecker, SyncState};
 use zksync_web3_decl::client::{DynClient, L2};
 
 use crate::{
     implementations::resources::{
         action_queue::ActionQueueSenderResource,
-        main_node_client::MainNodeClientResource,
+        main_node_client::{L2BlockHashCrossCheckerResource, MainNodeClientResource},
         pools::{MasterPool, PoolResource},
         sync_state::SyncStateResource,
     },
@@ -33,6 +33,7 @@ pub struct ExternalNodeConsensusLayer {
 pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
     pub main_node_client: MainNodeClientResource,
+    pub block_hash_cross_checker: Option<L2BlockHashCrossCheckerResource>,
     pub sync_state: SyncStateResource,
     pub action_queue_sender: ActionQueueSenderResource,
 }
@@ -57,6 +58,7 @@ impl WiringLayer for ExternalNodeConsensusLayer {
         let pool = input.master_pool.get().await?;
 
         let main_node_client = input.main_node_client.0;
+        let block_hash_cross_checker = input.block_hash_cross_checker.map(|res| res.0);
         let sync_state = input.sync_state.0;
         let action_queue_sender = input.action_queue_sender.0.take().ok_or_else(|| {
             WiringError::Configuration(
@@ -83,6 +85,7 @@ impl WiringLayer for ExternalNodeConsensusLayer {
             config,
             pool,
             main_node_client,
+            block_hash_cross_checker,
             sync_state,
             action_queue_sender,
         };
@@ -96,6 +99,7 @@ pub struct ExternalNodeTask {
     config: Option<(ConsensusConfig, ConsensusSecrets)>,
     pool: ConnectionPool<Core>,
     main_node_client: Box<DynClient<L2>>,
+    block_hash_cross_checker: Option<L2BlockHashCrossChecker>,
     sync_state: SyncState,
     action_queue_sender: ActionQueueSender,
 }
@@ -120,6 +124,7 @@ impl Task for ExternalNodeTask {
                 self.pool,
                 self.sync_state,
                 self.main_node_client,
+                self.block_hash_cross_checker,
                 self.action_queue_sender,
                 self.build_version,
             ));
@@ -4,19 +4,24 @@ use zksync_da_dispatcher::DataAvailabilityDispatcher;
 use crate::{
     implementations::resources::{
         da_client::DAClientResource,
+        healthcheck::AppHealthCheckResource,
         pools::{MasterPool, PoolResource},
     },
     service::StopReceiver,
-    task::{Task, TaskId},
+    task::{RestartPolicy, SupervisedTask, Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
 
 /// A layer that wires the data availability dispatcher task.
+///
+/// The dispatcher resumes dispatching and inclusion polling from the state persisted in Postgres, so it is
+/// restarted on transient failures (e.g., DA layer outages) according to the [`RestartPolicy`].
 #[derive(Debug)]
 pub struct DataAvailabilityDispatcherLayer {
     state_keeper_config: StateKeeperConfig,
     da_config: DADispatcherConfig,
+    restart_policy: RestartPolicy,
 }
 
 #[derive(Debug, FromContext)]
@@ -24,13 +29,15 @@ pub struct DataAvailabilityDispatcherLayer {
 pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
     pub da_client: DAClientResource,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
 }
 
 #[derive(Debug, IntoContext)]
 #[context(crate = crate)]
 pub struct Output {
     #[context(task)]
-    pub da_dispatcher_task: DataAvailabilityDispatcher,
+    pub da_dispatcher_task: SupervisedTask,
 }
 
 impl DataAvailabilityDispatcherLayer {
@@ -38,8 +45,14 @@ impl DataAvailabilityDispatcherLayer {
         Self {
             state_keeper_config,
             da_config,
+            restart_policy: RestartPolicy::default(),
         }
     }
+
+    pub fn with_restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.restart_policy = restart_policy;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -65,8 +78,14 @@ impl WiringLayer for DataAvailabilityDispatcherLayer {
             }
         }
 
+        let da_dispatcher = DataAvailabilityDispatcher::new(master_pool, self.da_config, da_client);
         let da_dispatcher_task =
-            DataAvailabilityDispatcher::new(master_pool, self.da_config, da_client);
+            SupervisedTask::from_cloneable("da_dispatcher", self.restart_policy, da_dispatcher);
+        input
+            .app_health
+            .0
+            .insert_component(da_dispatcher_task.health_check())
+            .map_err(WiringError::internal)?;
 
         Ok(Output { da_dispatcher_task })
     }
@@ -1,12 +1,15 @@
 use anyhow::Context;
+use zksync_circuit_breaker::l1_reorg::L1ReorgChecker;
 use zksync_config::{configs::gateway::GatewayChainConfig, ContractsConfig, EthWatchConfig};
 use zksync_contracts::chain_admin_contract;
-use zksync_eth_watch::{EthHttpQueryClient, EthWatch, L2EthClient};
+use zksync_eth_watch::{CustomEventProcessor, EthHttpQueryClient, EthWatch, L2EthClient};
 use zksync_types::{settlement::SettlementMode, L2ChainId};
 
 use crate::{
     implementations::resources::{
+        circuit_breakers::CircuitBreakersResource,
         eth_interface::{EthInterfaceResource, L2InterfaceResource},
+        eth_watch::CustomEventProcessorsResource,
         pools::{MasterPool, PoolResource},
     },
     service::StopReceiver,
@@ -19,6 +22,19 @@ use crate::{
 ///
 /// Responsible for initializing and running of [`EthWatch`] component, that polls the Ethereum node for the relevant events,
 /// such as priority operations (aka L1 transactions), protocol upgrades etc.
+/// Custom event processors registered with [`CustomEventProcessorLayer`] are run in addition to the built-in ones.
+///
+/// ## Requests resources
+///
+/// - `PoolResource<MasterPool>`
+/// - `EthInterfaceResource`
+/// - `L2InterfaceResource` (optional)
+/// - `CustomEventProcessorsResource`
+/// - `CircuitBreakersResource` (adds a circuit breaker)
+///
+/// ## Adds tasks
+///
+/// - `EthWatch`
 #[derive(Debug)]
 pub struct EthWatchLayer {
     eth_watch_config: EthWatchConfig,
@@ -34,6 +50,10 @@ pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
     pub eth_client: EthInterfaceResource,
     pub gateway_client: Option<L2InterfaceResource>,
+    #[context(default)]
+    pub custom_event_processors: CustomEventProcessorsResource,
+    #[context(default)]
+    pub circuit_breakers: CircuitBreakersResource,
 }
 
 #[derive(Debug, IntoContext)]
@@ -135,6 +155,14 @@ impl WiringLayer for EthWatchLayer {
                 None
             };
 
+        input
+            .circuit_breakers
+            .breakers
+            .insert(Box::new(L1ReorgChecker {
+                pool: main_pool.clone(),
+            }))
+            .await;
+
         let eth_watch = EthWatch::new(
             &chain_admin_contract(),
             Box::new(l1_client),
@@ -143,12 +171,58 @@ impl WiringLayer for EthWatchLayer {
             self.eth_watch_config.poll_interval(),
             self.chain_id,
         )
-        .await?;
+        .await?
+        .with_custom_event_processors(input.custom_event_processors.0);
 
         Ok(Output { eth_watch })
     }
 }
 
+/// Wiring layer registering a [`CustomEventProcessor`] to be run by [`EthWatch`]. Allows reacting to events
+/// emitted by arbitrary L1 contracts (e.g., governance or allowlist changes) without modifying the watcher.
+///
+/// ## Requests resources
+///
+/// - `CustomEventProcessorsResource` (adds a processor)
+#[derive(Debug)]
+pub struct CustomEventProcessorLayer {
+    processor: Box<dyn CustomEventProcessor>,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct CustomEventProcessorInput {
+    #[context(default)]
+    pub custom_event_processors: CustomEventProcessorsResource,
+}
+
+impl CustomEventProcessorLayer {
+    pub fn new(processor: impl CustomEventProcessor) -> Self {
+        Self {
+            processor: Box::new(processor),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for CustomEventProcessorLayer {
+    type Input = CustomEventProcessorInput;
+    type Output = ();
+
+    fn layer_name(&self) -> &'static str {
+        "eth_watch_custom_event_processor_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        input
+            .custom_event_processors
+            .0
+            .insert(self.processor)
+            .map_err(|err| WiringError::Configuration(format!("{err:#}")))?;
+        Ok(())
+    }
+}
+
 #[async_trait::async_trait]
 impl Task for EthWatch {
     fn id(&self) -> TaskId {
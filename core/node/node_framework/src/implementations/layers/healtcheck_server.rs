@@ -77,6 +77,10 @@ impl Task for HealthCheckTask {
         TaskKind::UnconstrainedTask
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::UnconstrainedTask)
+    }
+
     fn id(&self) -> TaskId {
         "healthcheck_server".into()
     }
@@ -1,42 +1,60 @@
 use zksync_config::configs::house_keeper::HouseKeeperConfig;
 use zksync_house_keeper::{
     blocks_state_reporter::L1BatchMetricsReporter, periodic_job::PeriodicJob,
+    settlement_sla::SettlementSlaTracker,
 };
 
 use crate::{
-    implementations::resources::pools::{PoolResource, ReplicaPool},
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        pools::{PoolResource, ReplicaPool},
+    },
     service::StopReceiver,
-    task::{Task, TaskId},
+    task::{RestartPolicy, SupervisedTask, Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
 
 /// Wiring layer for `HouseKeeper` - a component responsible for managing prover jobs
 /// and auxiliary server activities.
+///
+/// House keeper jobs are non-critical, so they are restarted on failure according to the [`RestartPolicy`].
 #[derive(Debug)]
 pub struct HouseKeeperLayer {
     house_keeper_config: HouseKeeperConfig,
+    restart_policy: RestartPolicy,
 }
 
 #[derive(Debug, FromContext)]
 #[context(crate = crate)]
 pub struct Input {
     pub replica_pool: PoolResource<ReplicaPool>,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
 }
 
 #[derive(Debug, IntoContext)]
 #[context(crate = crate)]
 pub struct Output {
     #[context(task)]
-    pub l1_batch_metrics_reporter: L1BatchMetricsReporter,
+    pub l1_batch_metrics_reporter: SupervisedTask,
+    /// Only present if settlement SLA tracking is enabled in the config.
+    #[context(task)]
+    pub settlement_sla_tracker: Option<SupervisedTask>,
 }
 
 impl HouseKeeperLayer {
     pub fn new(house_keeper_config: HouseKeeperConfig) -> Self {
         Self {
             house_keeper_config,
+            restart_policy: RestartPolicy::default(),
         }
     }
+
+    pub fn with_restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.restart_policy = restart_policy;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -53,14 +71,46 @@ impl WiringLayer for HouseKeeperLayer {
         let replica_pool = input.replica_pool.get().await?;
 
         // Initialize and add tasks
-        let l1_batch_metrics_reporter = L1BatchMetricsReporter::new(
-            self.house_keeper_config
-                .l1_batch_metrics_reporting_interval_ms,
-            replica_pool,
+        let reporting_interval_ms = self
+            .house_keeper_config
+            .l1_batch_metrics_reporting_interval_ms;
+        let settlement_sla_tracker =
+            SettlementSlaTracker::new(&self.house_keeper_config, replica_pool.clone());
+        let l1_batch_metrics_reporter = SupervisedTask::new(
+            "l1_batch_metrics_reporter",
+            self.restart_policy,
+            move || L1BatchMetricsReporter::new(reporting_interval_ms, replica_pool.clone()),
         );
+        input
+            .app_health
+            .0
+            .insert_component(l1_batch_metrics_reporter.health_check())
+            .map_err(WiringError::internal)?;
+
+        let settlement_sla_tracker = if let Some(tracker) = settlement_sla_tracker {
+            input
+                .app_health
+                .0
+                .insert_component(tracker.health_check())
+                .map_err(WiringError::internal)?;
+            let task = SupervisedTask::from_cloneable(
+                "settlement_sla_tracker",
+                self.restart_policy,
+                tracker,
+            );
+            input
+                .app_health
+                .0
+                .insert_component(task.health_check())
+                .map_err(WiringError::internal)?;
+            Some(task)
+        } else {
+            None
+        };
 
         Ok(Output {
             l1_batch_metrics_reporter,
+            settlement_sla_tracker,
         })
     }
 }
@@ -75,3 +125,14 @@ impl Task for L1BatchMetricsReporter {
         (*self).run(stop_receiver.0).await
     }
 }
+
+#[async_trait::async_trait]
+impl Task for SettlementSlaTracker {
+    fn id(&self) -> TaskId {
+        "settlement_sla_tracker".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
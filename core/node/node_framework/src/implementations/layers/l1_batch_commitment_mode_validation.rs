@@ -69,6 +69,10 @@ impl Task for L1BatchCommitmentModeValidationTask {
         TaskKind::Precondition
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::Precondition)
+    }
+
     fn id(&self) -> TaskId {
         "l1_batch_commitment_mode_validation".into()
     }
@@ -51,6 +51,10 @@ impl Task for LogsBloomBackfill {
         TaskKind::OneshotTask
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::OneshotTask)
+    }
+
     fn id(&self) -> TaskId {
         "logs_bloom_backfill".into()
     }
@@ -1,22 +1,29 @@
 use std::{num::NonZeroUsize, sync::Arc};
 
 use anyhow::Context;
-use zksync_node_sync::MainNodeHealthCheck;
+use zksync_node_sync::{
+    L2BlockHashCrossChecker, MainNodeHealthCheck, MainNodeUpstreamsHealthCheck,
+};
 use zksync_types::{url::SensitiveUrl, L2ChainId};
-use zksync_web3_decl::client::{Client, DynClient, L2};
+use zksync_web3_decl::client::{Client, DynClient, FallbackClient, L2};
 
 use crate::{
     implementations::resources::{
-        healthcheck::AppHealthCheckResource, main_node_client::MainNodeClientResource,
+        healthcheck::AppHealthCheckResource,
+        main_node_client::{L2BlockHashCrossCheckerResource, MainNodeClientResource},
     },
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
 
 /// Wiring layer for main node client.
+///
+/// If fallback URLs are specified, the client fails over between the main node and fallback upstreams
+/// (e.g., trusted external nodes), and block hashes are cross-checked between all upstreams.
 #[derive(Debug)]
 pub struct MainNodeClientLayer {
     url: SensitiveUrl,
+    fallback_urls: Vec<SensitiveUrl>,
     rate_limit_rps: NonZeroUsize,
     l2_chain_id: L2ChainId,
 }
@@ -32,16 +39,32 @@ pub struct Input {
 #[context(crate = crate)]
 pub struct Output {
     pub main_node_client: MainNodeClientResource,
+    pub block_hash_cross_checker: Option<L2BlockHashCrossCheckerResource>,
 }
 
 impl MainNodeClientLayer {
     pub fn new(url: SensitiveUrl, rate_limit_rps: NonZeroUsize, l2_chain_id: L2ChainId) -> Self {
         Self {
             url,
+            fallback_urls: vec![],
             rate_limit_rps,
             l2_chain_id,
         }
     }
+
+    /// Adds fallback upstreams to the client. Upstreams are tried in the specified order after the main node.
+    pub fn with_fallback_urls(mut self, fallback_urls: Vec<SensitiveUrl>) -> Self {
+        self.fallback_urls = fallback_urls;
+        self
+    }
+
+    fn build_client(&self, url: SensitiveUrl) -> anyhow::Result<Box<DynClient<L2>>> {
+        let client = Client::http(url)?
+            .for_network(self.l2_chain_id.into())
+            .with_allowed_requests_per_second(self.rate_limit_rps)
+            .build();
+        Ok(Box::new(client))
+    }
 }
 
 #[async_trait::async_trait]
@@ -54,23 +77,43 @@ impl WiringLayer for MainNodeClientLayer {
     }
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
-        let main_node_client = Client::http(self.url)
-            .context("failed creating JSON-RPC client for main node")?
-            .for_network(self.l2_chain_id.into())
-            .with_allowed_requests_per_second(self.rate_limit_rps)
-            .build();
+        let main_node_client = self
+            .build_client(self.url.clone())
+            .context("failed creating JSON-RPC client for main node")?;
+        let app_health = &input.app_health.0;
+
+        let (client, block_hash_cross_checker) = if self.fallback_urls.is_empty() {
+            (main_node_client, None)
+        } else {
+            let mut builder = FallbackClient::builder(self.l2_chain_id.into())
+                .upstream("main_node", main_node_client);
+            for (i, url) in self.fallback_urls.iter().enumerate() {
+                tracing::info!("Using fallback upstream #{i} for main node client: {url:?}");
+                let client = self.build_client(url.clone()).with_context(|| {
+                    format!("failed creating JSON-RPC client for fallback upstream #{i}")
+                })?;
+                builder = builder.upstream(format!("fallback_{i}"), client);
+            }
+            let client = builder.build();
 
-        let client = Box::new(main_node_client) as Box<DynClient<L2>>;
+            let cross_checker = L2BlockHashCrossChecker::new(&client);
+            app_health
+                .insert_custom_component(Arc::new(MainNodeUpstreamsHealthCheck::from(
+                    client.clone(),
+                )))
+                .map_err(WiringError::internal)?;
+            let client = Box::new(client) as Box<DynClient<L2>>;
+            (client, Some(L2BlockHashCrossCheckerResource(cross_checker)))
+        };
 
         // Insert healthcheck
-        input
-            .app_health
-            .0
+        app_health
             .insert_custom_component(Arc::new(MainNodeHealthCheck::from(client.clone())))
             .map_err(WiringError::internal)?;
 
         Ok(Output {
             main_node_client: client.into(),
+            block_hash_cross_checker,
         })
     }
 }
@@ -20,16 +20,19 @@ use crate::{
         web3_api::TreeApiClientResource,
     },
     service::{ShutdownHook, StopReceiver},
-    task::{Task, TaskId, TaskKind},
+    task::{RestartPolicy, SupervisedTask, Task, TaskId, TaskKind},
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
 
 /// Wiring layer for Metadata calculator and Tree API.
+///
+/// The tree API server is non-critical, so it is restarted on failure according to the [`RestartPolicy`].
 #[derive(Debug)]
 pub struct MetadataCalculatorLayer {
     config: MetadataCalculatorConfig,
     tree_api_config: Option<MerkleTreeApiConfig>,
+    tree_api_restart_policy: RestartPolicy,
     pruning_config: Option<Duration>,
     stale_keys_repair_enabled: bool,
 }
@@ -53,7 +56,7 @@ pub struct Output {
     pub tree_api_client: TreeApiClientResource,
     /// Only provided if configuration is provided.
     #[context(task)]
-    pub tree_api_task: Option<TreeApiTask>,
+    pub tree_api_task: Option<SupervisedTask>,
     /// Only provided if configuration is provided.
     #[context(task)]
     pub pruning_task: Option<MerkleTreePruningTask>,
@@ -68,6 +71,7 @@ impl MetadataCalculatorLayer {
         Self {
             config,
             tree_api_config: None,
+            tree_api_restart_policy: RestartPolicy::default(),
             pruning_config: None,
             stale_keys_repair_enabled: false,
         }
@@ -78,6 +82,11 @@ impl MetadataCalculatorLayer {
         self
     }
 
+    pub fn with_tree_api_restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.tree_api_restart_policy = restart_policy;
+        self
+    }
+
     pub fn with_pruning_config(mut self, pruning_config: Duration) -> Self {
         self.pruning_config = Some(pruning_config);
         self
@@ -129,14 +138,23 @@ impl WiringLayer for MetadataCalculatorLayer {
             .insert_custom_component(Arc::new(metadata_calculator.tree_health_check()))
             .map_err(WiringError::internal)?;
 
-        let tree_api_task = self.tree_api_config.map(|tree_api_config| {
-            let bind_addr = (Ipv4Addr::UNSPECIFIED, tree_api_config.port).into();
-            let tree_reader = metadata_calculator.tree_reader();
-            TreeApiTask {
-                bind_addr,
-                tree_reader,
-            }
-        });
+        let tree_api_task = self
+            .tree_api_config
+            .map(|tree_api_config| -> Result<SupervisedTask, WiringError> {
+                let bind_addr = (Ipv4Addr::UNSPECIFIED, tree_api_config.port).into();
+                let tree_reader = metadata_calculator.tree_reader();
+                let task = TreeApiTask {
+                    bind_addr,
+                    tree_reader,
+                };
+                let task =
+                    SupervisedTask::from_cloneable("tree_api", self.tree_api_restart_policy, task);
+                app_health
+                    .insert_component(task.health_check())
+                    .map_err(WiringError::internal)?;
+                Ok(task)
+            })
+            .transpose()?;
 
         let pruning_task = self
             .pruning_config
@@ -188,7 +206,7 @@ impl Task for MetadataCalculator {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TreeApiTask {
     bind_addr: SocketAddr,
     tree_reader: LazyAsyncTreeReader,
@@ -240,14 +258,31 @@ impl Task for MerkleTreePruningTask {
 pub struct TreeApiServerLayer {
     config: MerkleTreeReaderConfig,
     api_config: MerkleTreeApiConfig,
+    restart_policy: RestartPolicy,
 }
 
 impl TreeApiServerLayer {
     pub fn new(config: MerkleTreeReaderConfig, api_config: MerkleTreeApiConfig) -> Self {
-        Self { config, api_config }
+        Self {
+            config,
+            api_config,
+            restart_policy: RestartPolicy::default(),
+        }
+    }
+
+    pub fn with_restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.restart_policy = restart_policy;
+        self
     }
 }
 
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct TreeApiServerInput {
+    #[context(default)]
+    app_health: AppHealthCheckResource,
+}
+
 #[derive(Debug, IntoContext)]
 #[context(crate = crate)]
 pub struct TreeApiServerOutput {
@@ -255,25 +290,32 @@ pub struct TreeApiServerOutput {
     #[context(task)]
     tree_reader_task: TreeReaderTask,
     #[context(task)]
-    tree_api_task: TreeApiTask,
+    tree_api_task: SupervisedTask,
 }
 
 #[async_trait::async_trait]
 impl WiringLayer for TreeApiServerLayer {
-    type Input = ();
+    type Input = TreeApiServerInput;
     type Output = TreeApiServerOutput;
 
     fn layer_name(&self) -> &'static str {
         "tree_api_server"
     }
 
-    async fn wire(self, (): Self::Input) -> Result<Self::Output, WiringError> {
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let tree_reader_task = TreeReaderTask::new(self.config);
         let bind_addr = (Ipv4Addr::UNSPECIFIED, self.api_config.port).into();
         let tree_api_task = TreeApiTask {
             bind_addr,
             tree_reader: tree_reader_task.tree_reader(),
         };
+        let tree_api_task =
+            SupervisedTask::from_cloneable("tree_api", self.restart_policy, tree_api_task);
+        input
+            .app_health
+            .0
+            .insert_component(tree_api_task.health_check())
+            .map_err(WiringError::internal)?;
         Ok(TreeApiServerOutput {
             tree_api_client: TreeApiClientResource(Arc::new(tree_reader_task.tree_reader())),
             tree_api_task,
@@ -288,6 +330,10 @@ impl Task for TreeReaderTask {
         TaskKind::OneshotTask
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::OneshotTask)
+    }
+
     fn id(&self) -> TaskId {
         "merkle_tree_reader_task".into()
     }
@@ -1,3 +1,4 @@
+pub mod address_index_backfill;
 pub mod base_token;
 pub mod batch_status_updater;
 pub mod block_reverter;
@@ -30,6 +31,7 @@ pub mod proof_data_handler;
 pub mod pruning;
 pub mod query_eth_client;
 pub mod reorg_detector;
+pub mod settlement_auditor;
 pub mod sigint;
 pub mod state_keeper;
 pub mod sync_state_updater;
@@ -1,4 +1,7 @@
-use std::{num::NonZeroUsize, sync::Arc};
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    sync::Arc,
+};
 
 // Re-export to initialize the layer without having to depend on the crate directly.
 pub use zksync_node_storage_init::SnapshotRecoveryConfig;
@@ -26,6 +29,9 @@ pub struct ExternalNodeInitStrategyLayer {
     pub l2_chain_id: L2ChainId,
     pub max_postgres_concurrency: NonZeroUsize,
     pub snapshot_recovery_config: Option<SnapshotRecoveryConfig>,
+    /// Maximum number of L1 batches that can be rolled back when recovering from a reorg. If not set,
+    /// rollbacks are not limited.
+    pub max_reorg_rollback_depth: Option<NonZeroU32>,
 }
 
 #[derive(Debug, FromContext)]
@@ -97,6 +103,7 @@ impl WiringLayer for ExternalNodeInitStrategyLayer {
             client,
             pool: pool.clone(),
             reverter: block_reverter,
+            max_rollback_depth: self.max_reorg_rollback_depth,
         }) as Arc<dyn RevertStorage>);
         let strategy = NodeInitializationStrategy {
             genesis,
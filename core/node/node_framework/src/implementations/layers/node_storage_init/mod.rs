@@ -106,6 +106,10 @@ impl Task for NodeStorageInitializer {
         TaskKind::UnconstrainedOneshotTask
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::UnconstrainedOneshotTask)
+    }
+
     fn id(&self) -> TaskId {
         "node_storage_initializer".into()
     }
@@ -129,6 +133,10 @@ impl Task for NodeStorageInitializerPrecondition {
         TaskKind::Precondition
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::Precondition)
+    }
+
     fn id(&self) -> TaskId {
         "node_storage_initializer_precondition".into()
     }
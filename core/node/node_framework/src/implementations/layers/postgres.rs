@@ -83,6 +83,10 @@ impl Task for PostgresMetricsScrapingTask {
         TaskKind::UnconstrainedTask
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::UnconstrainedTask)
+    }
+
     fn id(&self) -> TaskId {
         "postgres_metrics_scraping".into()
     }
@@ -67,6 +67,10 @@ impl Task for PrometheusExporterTask {
         TaskKind::UnconstrainedTask
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::UnconstrainedTask)
+    }
+
     fn id(&self) -> TaskId {
         "prometheus_exporter".into()
     }
@@ -1,6 +1,9 @@
 use std::time::Duration;
 
+use zksync_config::ObjectStoreConfig;
 use zksync_node_db_pruner::{DbPruner, DbPrunerConfig};
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::{pruning::PruningProfile, L2ChainId};
 
 use crate::{
     implementations::resources::{
@@ -19,6 +22,8 @@ pub struct PruningLayer {
     pruning_removal_delay: Duration,
     pruning_chunk_size: u32,
     minimum_l1_batch_age: Duration,
+    archive: Option<(ObjectStoreConfig, L2ChainId)>,
+    profile: PruningProfile,
 }
 
 #[derive(Debug, FromContext)]
@@ -46,8 +51,28 @@ impl PruningLayer {
             pruning_removal_delay,
             pruning_chunk_size,
             minimum_l1_batch_age,
+            archive: None,
+            profile: PruningProfile::default(),
         }
     }
+
+    /// Sets the profile determining which data classes are pruned.
+    #[must_use]
+    pub fn with_profile(mut self, profile: PruningProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Enables archiving pruned data to the object store with the specified config.
+    #[must_use]
+    pub fn with_archive(
+        mut self,
+        object_store_config: ObjectStoreConfig,
+        l2_chain_id: L2ChainId,
+    ) -> Self {
+        self.archive = Some((object_store_config, l2_chain_id));
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -62,14 +87,21 @@ impl WiringLayer for PruningLayer {
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let main_pool = input.master_pool.get().await?;
 
-        let db_pruner = DbPruner::new(
+        let mut db_pruner = DbPruner::new(
             DbPrunerConfig {
                 removal_delay: self.pruning_removal_delay,
                 pruned_batch_chunk_size: self.pruning_chunk_size,
                 minimum_l1_batch_age: self.minimum_l1_batch_age,
+                profile: self.profile,
             },
             main_pool,
         );
+        if let Some((object_store_config, l2_chain_id)) = self.archive {
+            let object_store = ObjectStoreFactory::new(object_store_config)
+                .create_store()
+                .await?;
+            db_pruner = db_pruner.with_archive(object_store, l2_chain_id);
+        }
 
         input
             .app_health
@@ -0,0 +1,110 @@
+use zksync_settlement_auditor::SettlementAuditor;
+use zksync_types::{Address, L2ChainId};
+
+use crate::{
+    implementations::resources::{
+        eth_interface::{EthInterfaceResource, GatewayEthInterfaceResource},
+        healthcheck::AppHealthCheckResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the [`SettlementAuditor`].
+///
+/// ## Requests resources
+///
+/// - `EthInterfaceResource`
+/// - `GatewayEthInterfaceResource` (optional)
+/// - `PoolResource<MasterPool>`
+/// - `AppHealthCheckResource` (adds a health check)
+///
+/// ## Adds tasks
+///
+/// - `SettlementAuditor`
+#[derive(Debug)]
+pub struct SettlementAuditorLayer {
+    l1_diamond_proxy_addr: Address,
+    max_batches_to_recheck: u32,
+    l2_chain_id: L2ChainId,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub l1_client: EthInterfaceResource,
+    pub gateway_client: Option<GatewayEthInterfaceResource>,
+    pub master_pool: PoolResource<MasterPool>,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub settlement_auditor: SettlementAuditor,
+}
+
+impl SettlementAuditorLayer {
+    pub fn new(
+        l1_diamond_proxy_addr: Address,
+        max_batches_to_recheck: u32,
+        l2_chain_id: L2ChainId,
+    ) -> Self {
+        Self {
+            l1_diamond_proxy_addr,
+            max_batches_to_recheck,
+            l2_chain_id,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for SettlementAuditorLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "settlement_auditor_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let l1_client = input.l1_client.0;
+        let gateway_client = input.gateway_client.map(|c| c.0);
+        let singleton_pool = input.master_pool.get_singleton().await?;
+
+        let settlement_auditor = SettlementAuditor::new(
+            l1_client,
+            self.l1_diamond_proxy_addr,
+            gateway_client,
+            self.max_batches_to_recheck,
+            singleton_pool,
+            self.l2_chain_id,
+        )
+        .await
+        .map_err(WiringError::Internal)?;
+
+        input
+            .app_health
+            .0
+            .insert_component(settlement_auditor.health_check())
+            .map_err(WiringError::internal)?;
+
+        Ok(Output { settlement_auditor })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for SettlementAuditor {
+    fn id(&self) -> TaskId {
+        "settlement_auditor".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
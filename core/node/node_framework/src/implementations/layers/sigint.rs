@@ -45,6 +45,10 @@ impl Task for SigintHandlerTask {
         TaskKind::UnconstrainedTask
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::UnconstrainedTask)
+    }
+
     fn id(&self) -> TaskId {
         "sigint_handler".into()
     }
@@ -3,7 +3,9 @@ use zksync_config::configs::{
     chain::{MempoolConfig, StateKeeperConfig},
     wallets,
 };
-use zksync_state_keeper::{MempoolFetcher, MempoolGuard, MempoolIO, SequencerSealer};
+use zksync_state_keeper::{
+    MempoolFetcher, MempoolGuard, MempoolIO, PendingReasonsUpdater, SequencerSealer,
+};
 use zksync_types::{commitment::PubdataType, Address, L2ChainId};
 
 use crate::{
@@ -33,6 +35,7 @@ use crate::{
 /// ## Adds tasks
 ///
 /// - `MempoolFetcherTask`
+/// - `PendingReasonsUpdater`
 #[derive(Debug)]
 pub struct MempoolIOLayer {
     zksync_network_id: L2ChainId,
@@ -57,6 +60,8 @@ pub struct Output {
     pub conditional_sealer: ConditionalSealerResource,
     #[context(task)]
     pub mempool_fetcher: MempoolFetcher,
+    #[context(task)]
+    pub pending_reasons_updater: PendingReasonsUpdater,
 }
 
 impl MempoolIOLayer {
@@ -121,6 +126,15 @@ impl WiringLayer for MempoolIOLayer {
             &self.mempool_config,
             mempool_fetcher_pool,
         );
+        let pending_reasons_pool = master_pool
+            .get_singleton()
+            .await
+            .context("Get master pool")?;
+        let pending_reasons_updater = PendingReasonsUpdater::new(
+            mempool_guard.clone(),
+            batch_fee_input_provider.clone(),
+            pending_reasons_pool,
+        );
 
         // Create mempool IO resource.
         let mempool_db_pool = master_pool
@@ -146,6 +160,7 @@ impl WiringLayer for MempoolIOLayer {
             state_keeper_io: io.into(),
             conditional_sealer: sealer.into(),
             mempool_fetcher,
+            pending_reasons_updater,
         })
     }
 }
@@ -160,3 +175,14 @@ impl Task for MempoolFetcher {
         (*self).run(stop_receiver.0).await
     }
 }
+
+#[async_trait::async_trait]
+impl Task for PendingReasonsUpdater {
+    fn id(&self) -> TaskId {
+        "state_keeper/pending_reasons_updater".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -158,6 +158,10 @@ impl Task for AsyncCatchupTask {
         TaskKind::OneshotTask
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::OneshotTask)
+    }
+
     fn id(&self) -> TaskId {
         "state_keeper/rocksdb_catchup_task".into()
     }
@@ -45,6 +45,10 @@ pub struct OutputHandlerLayer {
     /// May be set to `false` for nodes that do not participate in the sequencing process (e.g. external nodes)
     /// or run `vm_runner_protective_reads` component.
     protective_reads_persistence_enabled: bool,
+    /// Whether executed transactions should be added to the index of transactions by address.
+    address_index_enabled: bool,
+    /// Whether token transfers should be added to the token transfer index.
+    token_transfer_index_enabled: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -72,6 +76,8 @@ impl OutputHandlerLayer {
             l2_block_seal_queue_capacity,
             pre_insert_txs: false,
             protective_reads_persistence_enabled: false,
+            address_index_enabled: false,
+            token_transfer_index_enabled: false,
         }
     }
 
@@ -87,6 +93,16 @@ impl OutputHandlerLayer {
         self.protective_reads_persistence_enabled = protective_reads_persistence_enabled;
         self
     }
+
+    pub fn with_address_index_enabled(mut self, address_index_enabled: bool) -> Self {
+        self.address_index_enabled = address_index_enabled;
+        self
+    }
+
+    pub fn with_token_transfer_index_enabled(mut self, token_transfer_index_enabled: bool) -> Self {
+        self.token_transfer_index_enabled = token_transfer_index_enabled;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -119,6 +135,12 @@ impl WiringLayer for OutputHandlerLayer {
         if !self.protective_reads_persistence_enabled {
             persistence = persistence.without_protective_reads();
         }
+        if self.address_index_enabled {
+            persistence = persistence.with_address_index();
+        }
+        if self.token_transfer_index_enabled {
+            persistence = persistence.with_token_transfer_index();
+        }
 
         let tree_writes_persistence = TreeWritesPersistence::new(persistence_pool);
         let mut output_handler = OutputHandler::new(Box::new(persistence))
@@ -92,6 +92,10 @@ impl Task for ValidateChainIdsTask {
         TaskKind::Precondition
     }
 
+    fn static_kind() -> Option<TaskKind> {
+        Some(TaskKind::Precondition)
+    }
+
     fn id(&self) -> TaskId {
         "validate_chain_ids".into()
     }
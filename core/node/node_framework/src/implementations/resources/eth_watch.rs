@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use zksync_eth_watch::CustomEventProcessors;
+
+use crate::resource::Resource;
+
+/// A resource that provides [`CustomEventProcessors`] run by the Ethereum watcher.
+#[derive(Debug, Clone, Default)]
+pub struct CustomEventProcessorsResource(pub Arc<CustomEventProcessors>);
+
+impl Resource for CustomEventProcessorsResource {
+    fn name() -> String {
+        "eth_watch/custom_event_processors".into()
+    }
+}
@@ -1,3 +1,4 @@
+use zksync_node_sync::L2BlockHashCrossChecker;
 use zksync_web3_decl::client::{DynClient, L2};
 
 use crate::resource::Resource;
@@ -17,3 +18,14 @@ impl<T: Into<Box<DynClient<L2>>>> From<T> for MainNodeClientResource {
         Self(client.into())
     }
 }
+
+/// A resource that provides [`L2BlockHashCrossChecker`] for the main node client. Only present if the client
+/// is configured with multiple upstreams.
+#[derive(Debug, Clone)]
+pub struct L2BlockHashCrossCheckerResource(pub L2BlockHashCrossChecker);
+
+impl Resource for L2BlockHashCrossCheckerResource {
+    fn name() -> String {
+        "external_node/l2_block_hash_cross_checker".into()
+    }
+}
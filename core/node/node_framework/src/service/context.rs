@@ -53,6 +53,15 @@ impl<'a> ServiceContext<'a> {
     /// are met.
     pub fn add_task<T: Task>(&mut self, task: T) -> &mut Self {
         tracing::info!("Layer {} has added a new task: {}", self.layer, task.id());
+        if let Some(static_kind) = T::static_kind() {
+            if static_kind != task.kind() {
+                tracing::warn!(
+                    "Task {} has kind {} inconsistent with its static kind {static_kind}; the wiring graph will be inaccurate",
+                    task.id(),
+                    task.kind()
+                );
+            }
+        }
         self.service.runnables.tasks.push(Box::new(task));
         self
     }
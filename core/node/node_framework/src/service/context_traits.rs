@@ -1,4 +1,11 @@
-use crate::{resource::Resource, service::context::ServiceContext, wiring_layer::WiringError};
+use crate::{
+    resource::Resource,
+    service::{
+        context::ServiceContext,
+        wiring_graph::{ProvidedItem, RequestMode, ResourceRequest},
+    },
+    wiring_layer::WiringError,
+};
 
 /// Trait used as input for wiring layers, aiming to provide all the resources the layer needs for wiring.
 ///
@@ -33,12 +40,20 @@ use crate::{resource::Resource, service::context::ServiceContext, wiring_layer::
 /// ```
 pub trait FromContext: Sized {
     fn from_context(context: &mut ServiceContext<'_>) -> Result<Self, WiringError>;
+
+    /// Describes resources requested by [`Self::from_context()`] without accessing the context.
+    /// Used to build the [`WiringGraph`](crate::service::WiringGraph); implemented automatically by the derive macro.
+    fn describe_inputs(_requests: &mut Vec<ResourceRequest>) {}
 }
 
 impl<T: Resource + Clone> FromContext for T {
     fn from_context(context: &mut ServiceContext<'_>) -> Result<Self, WiringError> {
         context.get_resource::<T>()
     }
+
+    fn describe_inputs(requests: &mut Vec<ResourceRequest>) {
+        requests.push(ResourceRequest::new::<T>(RequestMode::Required));
+    }
 }
 
 impl FromContext for () {
@@ -55,6 +70,16 @@ impl<T: FromContext> FromContext for Option<T> {
             Err(err) => Err(err),
         }
     }
+
+    fn describe_inputs(requests: &mut Vec<ResourceRequest>) {
+        let start = requests.len();
+        T::describe_inputs(requests);
+        for request in &mut requests[start..] {
+            if request.mode == RequestMode::Required {
+                request.mode = RequestMode::Optional;
+            }
+        }
+    }
 }
 
 /// Trait used as output for wiring layers, aiming to provide all the resources and tasks the layer creates.
@@ -106,6 +131,10 @@ impl<T: FromContext> FromContext for Option<T> {
 /// ```
 pub trait IntoContext {
     fn into_context(self, context: &mut ServiceContext<'_>) -> Result<(), WiringError>;
+
+    /// Describes resources, tasks and shutdown hooks provided by [`Self::into_context()`] without accessing the context.
+    /// Used to build the [`WiringGraph`](crate::service::WiringGraph); implemented automatically by the derive macro.
+    fn describe_outputs(_items: &mut Vec<ProvidedItem>) {}
 }
 
 // Unfortunately, without specialization we cannot provide a blanket implementation for `T: Task`
@@ -114,6 +143,10 @@ impl<T: Resource> IntoContext for T {
     fn into_context(self, context: &mut ServiceContext<'_>) -> Result<(), WiringError> {
         context.insert_resource(self)
     }
+
+    fn describe_outputs(items: &mut Vec<ProvidedItem>) {
+        items.push(ProvidedItem::resource::<T>());
+    }
 }
 
 impl IntoContext for () {
@@ -130,4 +163,10 @@ impl<T: IntoContext> IntoContext for Option<T> {
             Ok(())
         }
     }
+
+    fn describe_outputs(items: &mut Vec<ProvidedItem>) {
+        let start = items.len();
+        T::describe_outputs(items);
+        ProvidedItem::mark_conditional_tail(items, start);
+    }
 }
@@ -1,6 +1,5 @@
 use std::{collections::HashMap, time::Duration};
 
-use error::TaskError;
 use futures::future::Fuse;
 use tokio::{runtime::Runtime, sync::watch, task::JoinHandle};
 use zksync_utils::panic_extractor::try_extract_panic_message;
@@ -9,9 +8,12 @@ use zksync_vlog::ObservabilityGuard;
 pub use self::{
     context::ServiceContext,
     context_traits::{FromContext, IntoContext},
-    error::ZkStackServiceError,
+    error::{TaskError, TaskErrors, ZkStackServiceError},
     shutdown_hook::ShutdownHook,
     stop_receiver::StopReceiver,
+    wiring_graph::{
+        LayerNode, ProvidedItem, RequestMode, ResourceRequest, WiringGraph, WiringGraphError,
+    },
 };
 use crate::{
     resource::{ResourceId, StoredResource},
@@ -32,6 +34,7 @@ mod shutdown_hook;
 mod stop_receiver;
 #[cfg(test)]
 mod tests;
+mod wiring_graph;
 
 // A reasonable amount of time for any task to finish the shutdown process
 const TASK_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
@@ -43,6 +46,8 @@ pub struct ZkStackServiceBuilder {
     // Note: It has to be a `Vec` and not e.g. `HashMap` because the order in which we
     // iterate through it matters.
     layers: Vec<(&'static str, WireFn)>,
+    /// Static description of the added layers.
+    wiring_graph: WiringGraph,
     /// Tokio runtime used to spawn tasks.
     runtime: Runtime,
 }
@@ -70,6 +75,7 @@ impl ZkStackServiceBuilder {
     pub fn on_runtime(runtime: Runtime) -> Self {
         Self {
             layers: Vec::new(),
+            wiring_graph: WiringGraph::default(),
             runtime,
         }
     }
@@ -95,17 +101,25 @@ impl ZkStackServiceBuilder {
             .iter()
             .any(|(existing_name, _)| name == *existing_name)
         {
+            self.wiring_graph.layers.push(LayerNode::new::<T>(name));
             self.layers.push((name, layer.into_wire_fn()));
         }
         self
     }
 
+    /// Returns the dependency graph of the added layers. The graph is built without running the layers,
+    /// so it can be used to inspect and [validate](WiringGraph::validate()) the node composition offline.
+    pub fn wiring_graph(&self) -> &WiringGraph {
+        &self.wiring_graph
+    }
+
     /// Builds the service.
     pub fn build(self) -> ZkStackService {
         let (stop_sender, _stop_receiver) = watch::channel(false);
 
         ZkStackService {
             layers: self.layers,
+            wiring_graph: self.wiring_graph,
             resources: Default::default(),
             runnables: Default::default(),
             stop_sender,
@@ -123,6 +137,8 @@ pub struct ZkStackService {
     resources: HashMap<ResourceId, Box<dyn StoredResource>>,
     /// List of wiring layers.
     layers: Vec<(&'static str, WireFn)>,
+    /// Static description of the wiring layers.
+    wiring_graph: WiringGraph,
     /// Different kinds of tasks for the service.
     runnables: Runnables,
 
@@ -138,6 +154,11 @@ pub struct ZkStackService {
 type TaskFuture = NamedFuture<Fuse<JoinHandle<anyhow::Result<()>>>>;
 
 impl ZkStackService {
+    /// Returns the dependency graph of the wiring layers. See [`ZkStackServiceBuilder::wiring_graph()`].
+    pub fn wiring_graph(&self) -> &WiringGraph {
+        &self.wiring_graph
+    }
+
     /// Runs the system.
     ///
     /// In case of errors during wiring phase, will return the list of all the errors that happened, in the order
@@ -2,7 +2,7 @@ use std::{fmt, future::Future};
 
 use futures::{future::BoxFuture, FutureExt};
 
-use crate::{IntoContext, TaskId};
+use crate::{service::ProvidedItem, IntoContext, TaskId};
 
 /// A named future that will be invoked after all the tasks are stopped.
 /// The future is expected to perform a cleanup or a shutdown of the service.
@@ -44,4 +44,8 @@ impl IntoContext for ShutdownHook {
         context.add_shutdown_hook(self);
         Ok(())
     }
+
+    fn describe_outputs(items: &mut Vec<ProvidedItem>) {
+        items.push(ProvidedItem::ShutdownHook { conditional: false });
+    }
 }
@@ -1,13 +1,27 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
 use anyhow::anyhow;
 use assert_matches::assert_matches;
-use tokio::{runtime::Runtime, sync::Barrier};
+use tokio::{
+    runtime::Runtime,
+    sync::{watch, Barrier},
+};
+use zksync_health_check::{CheckHealth, HealthStatus};
 
 use crate::{
-    service::{StopReceiver, WiringError, WiringLayer, ZkStackServiceBuilder, ZkStackServiceError},
-    task::{Task, TaskId},
-    IntoContext,
+    resource::Resource,
+    service::{
+        ProvidedItem, RequestMode, StopReceiver, WiringError, WiringGraphError, WiringLayer,
+        ZkStackServiceBuilder, ZkStackServiceError,
+    },
+    task::{RestartPolicy, SupervisedTask, Task, TaskId, TaskKind},
+    FromContext, IntoContext,
 };
 
 // `ZkStack` Service's `new()` method has to have a check for nested runtime.
@@ -252,3 +266,347 @@ fn test_task_run() {
     let res2 = *remaining_task_was_run.lock().unwrap();
     assert!(res2, "Incorrect resource value");
 }
+
+#[derive(Debug, Clone, Copy)]
+enum FailureMode {
+    Error,
+    Panic,
+}
+
+/// Task failing (or panicking) during the first `failures` runs and succeeding afterwards.
+#[derive(Debug, Clone)]
+struct FlakyTask {
+    runs: Arc<AtomicUsize>,
+    failures: usize,
+    mode: FailureMode,
+    wait_for_stop: bool,
+}
+
+#[async_trait::async_trait]
+impl Task for FlakyTask {
+    fn id(&self) -> TaskId {
+        "flaky_task".into()
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let run = self.runs.fetch_add(1, Ordering::SeqCst);
+        if run < self.failures {
+            match self.mode {
+                FailureMode::Error => anyhow::bail!("flaky task failure #{run}"),
+                FailureMode::Panic => panic!("flaky task panic #{run}"),
+            }
+        }
+        if self.wait_for_stop {
+            stop_receiver.0.changed().await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct SupervisedTaskLayer {
+    task: FlakyTask,
+    policy: RestartPolicy,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+struct SupervisedTaskLayerOutput {
+    #[context(task)]
+    task: SupervisedTask,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for SupervisedTaskLayer {
+    type Input = ();
+    type Output = SupervisedTaskLayerOutput;
+
+    fn layer_name(&self) -> &'static str {
+        "supervised_task_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let task = SupervisedTask::from_cloneable("supervised_flaky_task", self.policy, self.task);
+        Ok(SupervisedTaskLayerOutput { task })
+    }
+}
+
+fn test_restart_policy(max_restarts: u32) -> RestartPolicy {
+    RestartPolicy::new(max_restarts)
+        .with_backoff(Duration::from_millis(1), Duration::from_millis(5))
+}
+
+fn run_supervised_task(
+    failures: usize,
+    mode: FailureMode,
+    policy: RestartPolicy,
+) -> (Result<(), ZkStackServiceError>, usize) {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let task = FlakyTask {
+        runs: runs.clone(),
+        failures,
+        mode,
+        wait_for_stop: false,
+    };
+    let mut zk_stack_service = ZkStackServiceBuilder::new().unwrap();
+    zk_stack_service.add_layer(SupervisedTaskLayer { task, policy });
+    let result = zk_stack_service.build().run(None);
+    (result, runs.load(Ordering::SeqCst))
+}
+
+// Supervised tasks should be restarted after errors while the restart policy permits it.
+#[test]
+fn test_supervised_task_restarts_after_errors() {
+    let (result, runs) = run_supervised_task(2, FailureMode::Error, test_restart_policy(3));
+    result.unwrap();
+    assert_eq!(runs, 3);
+}
+
+// Supervised tasks should be restarted after panics as well.
+#[test]
+fn test_supervised_task_restarts_after_panics() {
+    let (result, runs) = run_supervised_task(1, FailureMode::Panic, test_restart_policy(1));
+    result.unwrap();
+    assert_eq!(runs, 2);
+}
+
+// Once the restarts are exhausted, the failure should be escalated to the service.
+#[test]
+fn test_supervised_task_escalates_after_exhausting_restarts() {
+    let (result, runs) =
+        run_supervised_task(usize::MAX, FailureMode::Error, test_restart_policy(2));
+    assert_matches!(result.unwrap_err(), ZkStackServiceError::Task(_));
+    assert_eq!(runs, 3);
+
+    let (result, runs) = run_supervised_task(1, FailureMode::Error, RestartPolicy::never());
+    assert_matches!(result.unwrap_err(), ZkStackServiceError::Task(_));
+    assert_eq!(runs, 1);
+}
+
+// Restarts should be reflected in the health check of the supervised task.
+#[test]
+fn test_supervised_task_health() {
+    let runtime = Runtime::new().unwrap();
+    runtime.block_on(async {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let task = FlakyTask {
+            runs: runs.clone(),
+            failures: 2,
+            mode: FailureMode::Error,
+            wait_for_stop: true,
+        };
+        let policy = test_restart_policy(5).with_stable_after(Duration::from_secs(3_600));
+        let task = SupervisedTask::from_cloneable("supervised_flaky_task", policy, task);
+        let mut health_check = task.health_check();
+        assert_eq!(health_check.name(), "supervised_flaky_task");
+
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let task_handle = tokio::spawn(Box::new(task).run(StopReceiver(stop_receiver)));
+        let health = health_check
+            .wait_for(|health| {
+                health.status() == HealthStatus::Affected
+                    && health.details().unwrap()["total_restarts"] == 2
+            })
+            .await;
+        let details = health.details().unwrap();
+        assert_eq!(details["consecutive_restarts"], 2);
+        assert_eq!(details["last_error"], "flaky task failure #1");
+
+        stop_sender.send_replace(true);
+        task_handle.await.unwrap().unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    });
+}
+
+#[derive(Debug, Clone, Default)]
+struct TestResource;
+
+impl Resource for TestResource {
+    fn name() -> String {
+        "test/resource".into()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct OtherTestResource;
+
+impl Resource for OtherTestResource {
+    fn name() -> String {
+        "test/other_resource".into()
+    }
+}
+
+#[derive(Debug)]
+struct ProviderLayer(&'static str);
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+struct ProviderLayerOutput {
+    resource: TestResource,
+    other_resource: Option<OtherTestResource>,
+    #[context(task)]
+    task: SuccessfulTask,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ProviderLayer {
+    type Input = ();
+    type Output = ProviderLayerOutput;
+
+    fn layer_name(&self) -> &'static str {
+        self.0
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        unreachable!("layer must not be wired when building the wiring graph")
+    }
+}
+
+#[derive(Debug)]
+struct ConsumerLayer;
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+struct ConsumerLayerInput {
+    resource: TestResource,
+    other_resource: Option<OtherTestResource>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+struct ConsumerLayerOutput {
+    #[context(task)]
+    maybe_task: Option<SupervisedTask>,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ConsumerLayer {
+    type Input = ConsumerLayerInput;
+    type Output = ConsumerLayerOutput;
+
+    fn layer_name(&self) -> &'static str {
+        "consumer_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        unreachable!("layer must not be wired when building the wiring graph")
+    }
+}
+
+#[derive(Debug)]
+struct DefaultResourceLayer;
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+struct DefaultResourceLayerInput {
+    #[context(default)]
+    resource: TestResource,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for DefaultResourceLayer {
+    type Input = DefaultResourceLayerInput;
+    type Output = ();
+
+    fn layer_name(&self) -> &'static str {
+        "default_resource_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        unreachable!("layer must not be wired when building the wiring graph")
+    }
+}
+
+// The wiring graph should describe layers without wiring them.
+#[test]
+fn test_wiring_graph() {
+    let mut zk_stack_service = ZkStackServiceBuilder::new().unwrap();
+    zk_stack_service
+        .add_layer(ProviderLayer("provider_layer"))
+        .add_layer(ConsumerLayer);
+    let graph = zk_stack_service.wiring_graph();
+    graph.validate().unwrap();
+
+    let [provider, consumer] = graph.layers.as_slice() else {
+        panic!("Unexpected layers: {:?}", graph.layers);
+    };
+    assert_eq!(provider.name, "provider_layer");
+    assert!(provider.requests.is_empty());
+    assert_matches!(
+        provider.provides.as_slice(),
+        [
+            ProvidedItem::Resource { name: resource, conditional: false, .. },
+            ProvidedItem::Resource { name: other_resource, conditional: true, .. },
+            ProvidedItem::Task { field: "task", kind: Some(TaskKind::Task), conditional: false, .. },
+        ] if resource == "test/resource" && other_resource == "test/other_resource"
+    );
+
+    assert_eq!(consumer.name, "consumer_layer");
+    let request_modes: Vec<_> = consumer
+        .requests
+        .iter()
+        .map(|request| (request.name.as_str(), request.mode))
+        .collect();
+    assert_eq!(
+        request_modes,
+        [
+            ("test/resource", RequestMode::Required),
+            ("test/other_resource", RequestMode::Optional)
+        ]
+    );
+    assert_matches!(
+        consumer.provides.as_slice(),
+        [ProvidedItem::Task {
+            field: "maybe_task",
+            kind: None,
+            conditional: true,
+            ..
+        }]
+    );
+
+    let json = serde_json::to_value(graph).unwrap();
+    assert_eq!(json["layers"][0]["provides"][2]["item"], "task");
+    assert_eq!(json["layers"][1]["requests"][1]["mode"], "optional");
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph wiring {"), "{dot}");
+    assert!(dot.contains("label=\"provider_layer\""), "{dot}");
+    assert!(dot.contains("label=\"test/resource\""), "{dot}");
+    assert!(dot.contains("label=\"task\\n(task)\""), "{dot}");
+}
+
+// Validation of the wiring graph should report all missing and duplicate resources.
+#[test]
+fn test_wiring_graph_validation_errors() {
+    let mut zk_stack_service = ZkStackServiceBuilder::new().unwrap();
+    zk_stack_service
+        .add_layer(ConsumerLayer)
+        .add_layer(DefaultResourceLayer)
+        .add_layer(ProviderLayer("provider_layer"))
+        .add_layer(ProviderLayer("other_provider_layer"));
+    let errors = zk_stack_service.wiring_graph().validate().unwrap_err();
+
+    assert_eq!(errors.len(), 3, "{errors:?}");
+    assert_matches!(
+        &errors[0],
+        WiringGraphError::ResourceLacking { layer: "consumer_layer", resource }
+            if resource == "test/resource"
+    );
+    // The resource is inserted by the `default_resource_layer`, so providing it afterwards is an error.
+    assert_matches!(
+        &errors[1],
+        WiringGraphError::ResourceAlreadyProvided {
+            layer: "provider_layer",
+            provided_by: "default_resource_layer",
+            ..
+        }
+    );
+    assert_matches!(
+        &errors[2],
+        WiringGraphError::ResourceAlreadyProvided {
+            layer: "other_provider_layer",
+            provided_by: "default_resource_layer",
+            ..
+        }
+    );
+}
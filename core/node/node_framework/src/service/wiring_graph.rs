@@ -0,0 +1,342 @@
+//! Static description of the wiring layers added to the service, which can be inspected and validated
+//! without running the layers.
+
+use std::{
+    any::type_name,
+    collections::HashMap,
+    fmt::{self, Write as _},
+};
+
+use serde::Serialize;
+
+use crate::{
+    resource::{Resource, ResourceId},
+    service::{FromContext, IntoContext},
+    task::{Task, TaskKind},
+    wiring_layer::WiringLayer,
+};
+
+/// How a wiring layer requests a resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestMode {
+    /// Resource must be provided by one of the preceding layers.
+    Required,
+    /// Resource is used if it's provided by one of the preceding layers.
+    Optional,
+    /// Resource is inserted with the default value if it's not provided by one of the preceding layers.
+    Default,
+}
+
+/// Resource requested by a wiring layer.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceRequest {
+    #[serde(skip)]
+    pub id: ResourceId,
+    pub name: String,
+    pub type_name: &'static str,
+    pub mode: RequestMode,
+}
+
+impl ResourceRequest {
+    pub fn new<T: Resource>(mode: RequestMode) -> Self {
+        Self {
+            id: ResourceId::of::<T>(),
+            name: T::name(),
+            type_name: type_name::<T>(),
+            mode,
+        }
+    }
+}
+
+/// Item provided by a wiring layer.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "item", rename_all = "snake_case")]
+pub enum ProvidedItem {
+    Resource {
+        #[serde(skip)]
+        id: ResourceId,
+        name: String,
+        type_name: &'static str,
+        /// Whether the resource is only provided under certain conditions (i.e., it's wrapped in an `Option`).
+        conditional: bool,
+    },
+    Task {
+        /// Name of the output field holding the task. Task IDs are only known after wiring.
+        field: &'static str,
+        type_name: &'static str,
+        /// Kind of the task, or `None` if it's only known after wiring.
+        kind: Option<TaskKind>,
+        conditional: bool,
+    },
+    ShutdownHook {
+        conditional: bool,
+    },
+}
+
+impl ProvidedItem {
+    pub fn resource<T: Resource>() -> Self {
+        Self::Resource {
+            id: ResourceId::of::<T>(),
+            name: T::name(),
+            type_name: type_name::<T>(),
+            conditional: false,
+        }
+    }
+
+    pub fn task<T: Task>(field: &'static str) -> Self {
+        Self::Task {
+            field,
+            type_name: type_name::<T>(),
+            kind: T::static_kind(),
+            conditional: false,
+        }
+    }
+
+    /// Marks this item as conditionally provided.
+    pub fn conditional(mut self) -> Self {
+        self.mark_conditional();
+        self
+    }
+
+    fn mark_conditional(&mut self) {
+        match self {
+            Self::Resource { conditional, .. }
+            | Self::Task { conditional, .. }
+            | Self::ShutdownHook { conditional } => *conditional = true,
+        }
+    }
+
+    /// Marks all items starting from `start` as conditional. Used for `Option`al outputs.
+    pub(crate) fn mark_conditional_tail(items: &mut [Self], start: usize) {
+        for item in &mut items[start..] {
+            item.mark_conditional();
+        }
+    }
+}
+
+/// Description of a single wiring layer.
+#[derive(Debug, Clone, Serialize)]
+pub struct LayerNode {
+    pub name: &'static str,
+    pub requests: Vec<ResourceRequest>,
+    pub provides: Vec<ProvidedItem>,
+}
+
+impl LayerNode {
+    pub(crate) fn new<T: WiringLayer>(name: &'static str) -> Self {
+        let mut requests = vec![];
+        T::Input::describe_inputs(&mut requests);
+        let mut provides = vec![];
+        T::Output::describe_outputs(&mut provides);
+        Self {
+            name,
+            requests,
+            provides,
+        }
+    }
+}
+
+/// Problem found during validation of the [`WiringGraph`].
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum WiringGraphError {
+    #[error("Layer `{layer}` requires resource {resource}, but it is not provided by any of the preceding layers")]
+    ResourceLacking {
+        layer: &'static str,
+        resource: String,
+    },
+    #[error(
+        "Layer `{layer}` provides resource {resource}, but it is already provided by layer `{provided_by}`"
+    )]
+    ResourceAlreadyProvided {
+        layer: &'static str,
+        resource: String,
+        provided_by: &'static str,
+    },
+}
+
+/// Dependency graph of the wiring layers added to the service, in the order the layers will be wired.
+///
+/// The graph is built from the `FromContext` / `IntoContext` implementations of layer inputs and outputs,
+/// so it can be obtained without running wiring layers (and thus without access to Postgres, L1 etc.).
+/// The graph is serializable to JSON and can be rendered in the DOT format via [`Self::to_dot()`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WiringGraph {
+    pub layers: Vec<LayerNode>,
+}
+
+impl WiringGraph {
+    /// Checks that all the required resources are provided by the preceding layers, and that no resource is provided
+    /// twice. Returns all the found problems in the order of the layers.
+    ///
+    /// Conditionally provided resources are considered to be present, since their presence can only be determined
+    /// during wiring.
+    pub fn validate(&self) -> Result<(), Vec<WiringGraphError>> {
+        let mut errors = vec![];
+        // Resource ID -> (providing layer, is conditional).
+        let mut available = HashMap::<&ResourceId, (&'static str, bool)>::new();
+
+        for layer in &self.layers {
+            for request in &layer.requests {
+                match request.mode {
+                    RequestMode::Required if !available.contains_key(&request.id) => {
+                        errors.push(WiringGraphError::ResourceLacking {
+                            layer: layer.name,
+                            resource: request.name.clone(),
+                        });
+                    }
+                    RequestMode::Default => {
+                        available.entry(&request.id).or_insert((layer.name, false));
+                    }
+                    _ => { /* no changes */ }
+                }
+            }
+
+            for item in &layer.provides {
+                let ProvidedItem::Resource {
+                    id,
+                    name,
+                    conditional,
+                    ..
+                } = item
+                else {
+                    continue;
+                };
+                match available.get(id) {
+                    Some(&(provided_by, false)) if !conditional => {
+                        errors.push(WiringGraphError::ResourceAlreadyProvided {
+                            layer: layer.name,
+                            resource: name.clone(),
+                            provided_by,
+                        });
+                    }
+                    Some(_) => { /* at least one of the providers is conditional */ }
+                    None => {
+                        available.insert(id, (layer.name, *conditional));
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Renders the graph in the DOT format. Layers and tasks are represented as boxes, resources as ellipses,
+    /// and optional / conditional edges are dashed.
+    pub fn to_dot(&self) -> String {
+        DotRenderer::default().render(self)
+    }
+}
+
+#[derive(Debug, Default)]
+struct DotRenderer {
+    output: String,
+    resource_nodes: HashMap<ResourceId, usize>,
+}
+
+impl DotRenderer {
+    fn render(mut self, graph: &WiringGraph) -> String {
+        self.output.push_str("digraph wiring {\n  rankdir=LR;\n");
+        for (layer_idx, layer) in graph.layers.iter().enumerate() {
+            let layer_node = format!("layer_{layer_idx}");
+            self.line(format_args!(
+                "{layer_node} [shape=box, style=bold, label=\"{}\"];",
+                escape(layer.name)
+            ));
+
+            for request in &layer.requests {
+                let resource_node = self.resource_node(&request.id, &request.name);
+                let style = match request.mode {
+                    RequestMode::Required => "solid",
+                    RequestMode::Optional => "dashed",
+                    RequestMode::Default => "dotted",
+                };
+                self.line(format_args!(
+                    "{resource_node} -> {layer_node} [style={style}];"
+                ));
+            }
+
+            for (item_idx, item) in layer.provides.iter().enumerate() {
+                match item {
+                    ProvidedItem::Resource {
+                        id,
+                        name,
+                        conditional,
+                        ..
+                    } => {
+                        let resource_node = self.resource_node(id, name);
+                        let style = edge_style(*conditional);
+                        self.line(format_args!(
+                            "{layer_node} -> {resource_node} [style={style}];"
+                        ));
+                    }
+                    ProvidedItem::Task {
+                        field,
+                        kind,
+                        conditional,
+                        ..
+                    } => {
+                        let task_node = format!("task_{layer_idx}_{item_idx}");
+                        let kind = kind.map_or_else(|| "?".to_owned(), |kind| kind.to_string());
+                        self.line(format_args!(
+                            "{task_node} [shape=box, style=rounded, label=\"{}\\n({kind})\"];",
+                            escape(field)
+                        ));
+                        let style = edge_style(*conditional);
+                        self.line(format_args!("{layer_node} -> {task_node} [style={style}];"));
+                    }
+                    ProvidedItem::ShutdownHook { conditional } => {
+                        let hook_node = format!("hook_{layer_idx}_{item_idx}");
+                        self.line(format_args!(
+                            "{hook_node} [shape=note, label=\"shutdown hook\"];"
+                        ));
+                        let style = edge_style(*conditional);
+                        self.line(format_args!("{layer_node} -> {hook_node} [style={style}];"));
+                    }
+                }
+            }
+        }
+        self.output.push_str("}\n");
+        self.output
+    }
+
+    fn line(&mut self, line: fmt::Arguments<'_>) {
+        writeln!(self.output, "  {line}").unwrap();
+    }
+
+    fn resource_node(&mut self, id: &ResourceId, name: &str) -> String {
+        let next_idx = self.resource_nodes.len();
+        let (idx, is_new) = match self.resource_nodes.get(id) {
+            Some(&idx) => (idx, false),
+            None => {
+                self.resource_nodes.insert(id.clone(), next_idx);
+                (next_idx, true)
+            }
+        };
+        let node = format!("resource_{idx}");
+        if is_new {
+            self.line(format_args!(
+                "{node} [shape=ellipse, label=\"{}\"];",
+                escape(name)
+            ));
+        }
+        node
+    }
+}
+
+fn edge_style(conditional: bool) -> &'static str {
+    if conditional {
+        "dashed"
+    } else {
+        "solid"
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,20 @@
+//! Metrics for supervised tasks.
+
+use vise::{Counter, Gauge, Global, LabeledFamily, Metrics};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "node_framework_supervised_task")]
+pub(super) struct SupervisedTaskMetrics {
+    /// Number of times a supervised task was restarted after a failure.
+    #[metrics(labels = ["task"])]
+    pub restarts: LabeledFamily<&'static str, Counter>,
+    /// Number of consecutive restarts of a supervised task since it was last considered stable.
+    #[metrics(labels = ["task"])]
+    pub consecutive_restarts: LabeledFamily<&'static str, Gauge<u64>>,
+    /// Number of times a supervised task has exhausted its restarts and escalated the failure to the node.
+    #[metrics(labels = ["task"])]
+    pub escalations: LabeledFamily<&'static str, Counter>,
+}
+
+#[vise::register]
+pub(super) static METRICS: Global<SupervisedTaskMetrics> = Global::new();
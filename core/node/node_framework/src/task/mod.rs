@@ -7,9 +7,14 @@ use std::{
 
 use tokio::sync::Barrier;
 
-pub use self::types::{TaskId, TaskKind};
+pub use self::{
+    supervised::{RestartPolicy, SupervisedTask},
+    types::{TaskId, TaskKind},
+};
 use crate::service::StopReceiver;
 
+mod metrics;
+mod supervised;
 mod types;
 
 /// A task implementation.
@@ -56,6 +61,12 @@ mod types;
 /// A task that can run without waiting for preconditions and can exit without stopping the service.
 /// Usually such tasks may be used for satisfying a precondition, for example, they can perform the database
 /// setup.
+///
+/// ## Restarts
+///
+/// By default, a failing task is never restarted. Non-critical tasks can be wrapped into a [`SupervisedTask`]
+/// in the wiring layer, which restarts the task according to a [`RestartPolicy`] and only escalates the failure
+/// to the service once the restarts are exhausted.
 #[async_trait::async_trait]
 pub trait Task: 'static + Send {
     /// Returns the kind of the task.
@@ -65,6 +76,16 @@ pub trait Task: 'static + Send {
         TaskKind::Task
     }
 
+    /// Returns the kind of the task without instantiating it, or `None` if the kind is only known at runtime.
+    /// Used to build the [`WiringGraph`](crate::service::WiringGraph) without running wiring layers, so
+    /// if [`Task::kind`] is overridden, this method should be overridden accordingly.
+    fn static_kind() -> Option<TaskKind>
+    where
+        Self: Sized,
+    {
+        Some(TaskKind::Task)
+    }
+
     /// Unique name of the task.
     fn id(&self) -> TaskId;
 
@@ -0,0 +1,280 @@
+use std::{any::Any, fmt, panic::AssertUnwindSafe, time::Duration};
+
+use futures::FutureExt as _;
+use serde::Serialize;
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+
+use super::{metrics::METRICS, Task, TaskId, TaskKind};
+use crate::service::StopReceiver;
+
+/// Restart policy for a [`SupervisedTask`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// Maximum number of consecutive restarts. If the task fails once more after this number of restarts,
+    /// the error is propagated to the service, which leads to the node shutdown.
+    pub max_restarts: u32,
+    /// Delay before the first restart.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between restarts. The delay is doubled after each consecutive restart.
+    pub max_backoff: Duration,
+    /// If a restarted task runs without failing for this long, it's considered stable again,
+    /// and the restart counter and backoff are reset.
+    pub stable_after: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self::new(5)
+    }
+}
+
+impl RestartPolicy {
+    const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+    const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+    const DEFAULT_STABLE_AFTER: Duration = Duration::from_secs(300);
+
+    /// Creates a policy with the specified max number of consecutive restarts and default backoff settings.
+    pub const fn new(max_restarts: u32) -> Self {
+        Self {
+            max_restarts,
+            initial_backoff: Self::DEFAULT_INITIAL_BACKOFF,
+            max_backoff: Self::DEFAULT_MAX_BACKOFF,
+            stable_after: Self::DEFAULT_STABLE_AFTER,
+        }
+    }
+
+    /// Policy that never restarts the task, i.e. the first failure is escalated to the service.
+    pub const fn never() -> Self {
+        Self::new(0)
+    }
+
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff.max(initial_backoff);
+        self
+    }
+
+    pub fn with_stable_after(mut self, stable_after: Duration) -> Self {
+        self.stable_after = stable_after;
+        self
+    }
+
+    fn next_backoff(&self, backoff: Duration) -> Duration {
+        backoff.saturating_mul(2).min(self.max_backoff)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SupervisedTaskDetails<'a> {
+    consecutive_restarts: u32,
+    total_restarts: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<&'a str>,
+}
+
+/// Task wrapper restarting the wrapped task according to a [`RestartPolicy`] when it fails or panics.
+///
+/// Since [`Task::run()`] consumes the task, the wrapper is constructed from a factory producing task instances;
+/// the first instance is created eagerly to determine the [`TaskKind`]. The wrapper is intended for non-critical
+/// components that are able to resume their work from the persisted state (e.g., periodic jobs or pollers).
+/// If the task fails after exhausting the policy, the error is propagated to the service as usual.
+///
+/// The wrapper reports its state as a health check component with the same name as the task ID. After a restart,
+/// the component is [`Affected`](HealthStatus::Affected) until the task is considered stable again.
+pub struct SupervisedTask {
+    name: &'static str,
+    kind: TaskKind,
+    policy: RestartPolicy,
+    factory: Box<dyn FnMut() -> Box<dyn Task> + Send>,
+    next_task: Option<Box<dyn Task>>,
+    health_updater: HealthUpdater,
+}
+
+impl fmt::Debug for SupervisedTask {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("SupervisedTask")
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .field("policy", &self.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SupervisedTask {
+    /// Creates a supervised task. `name` is used both as the task ID and as the health check component name;
+    /// it overrides the ID of the task instances produced by `factory`.
+    pub fn new<T: Task>(
+        name: &'static str,
+        policy: RestartPolicy,
+        mut factory: impl FnMut() -> T + Send + 'static,
+    ) -> Self {
+        let first_task: Box<dyn Task> = Box::new(factory());
+        let (_, health_updater) = ReactiveHealthCheck::new(name);
+        Self {
+            name,
+            kind: first_task.kind(),
+            policy,
+            factory: Box::new(move || -> Box<dyn Task> { Box::new(factory()) }),
+            next_task: Some(first_task),
+            health_updater,
+        }
+    }
+
+    /// Creates a supervised task from a cloneable task. Each restart uses a fresh clone of the provided task.
+    pub fn from_cloneable<T: Task + Clone>(
+        name: &'static str,
+        policy: RestartPolicy,
+        task: T,
+    ) -> Self {
+        Self::new(name, policy, move || task.clone())
+    }
+
+    pub fn policy(&self) -> RestartPolicy {
+        self.policy
+    }
+
+    /// Returns the health check for this task. It should be inserted into the app health check by the wiring layer.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+}
+
+fn update_health(
+    health_updater: &HealthUpdater,
+    status: HealthStatus,
+    consecutive_restarts: u32,
+    total_restarts: u64,
+    last_error: Option<&str>,
+) {
+    let details = SupervisedTaskDetails {
+        consecutive_restarts,
+        total_restarts,
+        last_error,
+    };
+    health_updater.update(Health::from(status).with_details(details));
+}
+
+fn extract_panic_message(panic: Box<dyn Any + Send>) -> String {
+    if let Some(message) = panic.downcast_ref::<&'static str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for SupervisedTask {
+    fn kind(&self) -> TaskKind {
+        self.kind
+    }
+
+    fn static_kind() -> Option<TaskKind> {
+        // Inherited from the supervised task.
+        None
+    }
+
+    fn id(&self) -> TaskId {
+        self.name.into()
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let Self {
+            name,
+            policy,
+            mut factory,
+            mut next_task,
+            health_updater,
+            ..
+        } = *self;
+        let mut consecutive_restarts = 0_u32;
+        let mut total_restarts = 0_u64;
+        let mut backoff = policy.initial_backoff;
+        let mut last_error: Option<String> = None;
+        METRICS.consecutive_restarts[&name].set(0);
+        update_health(&health_updater, HealthStatus::Ready, 0, 0, None);
+
+        loop {
+            let task = match next_task.take() {
+                Some(task) => task,
+                None => factory(),
+            };
+            let run_future = AssertUnwindSafe(task.run(stop_receiver.clone())).catch_unwind();
+            tokio::pin!(run_future);
+            let stability_timer = tokio::time::sleep(policy.stable_after);
+            tokio::pin!(stability_timer);
+            let mut is_stable = consecutive_restarts == 0;
+
+            let result = loop {
+                tokio::select! {
+                    result = &mut run_future => break result,
+                    () = &mut stability_timer, if !is_stable => {
+                        tracing::info!(
+                            "Supervised task `{name}` is stable after {consecutive_restarts} restart(s); resetting restart counter"
+                        );
+                        is_stable = true;
+                        consecutive_restarts = 0;
+                        backoff = policy.initial_backoff;
+                        METRICS.consecutive_restarts[&name].set(0);
+                        update_health(
+                            &health_updater,
+                            HealthStatus::Ready,
+                            0,
+                            total_restarts,
+                            last_error.as_deref(),
+                        );
+                    }
+                }
+            };
+
+            let err = match result {
+                Ok(Ok(())) => return Ok(()),
+                Ok(Err(err)) => err,
+                Err(panic) => anyhow::anyhow!("task panicked: {}", extract_panic_message(panic)),
+            };
+            if *stop_receiver.0.borrow() {
+                // Errors during the node shutdown are not worth restarting the task for.
+                return Err(err);
+            }
+            if consecutive_restarts >= policy.max_restarts {
+                tracing::error!(
+                    "Supervised task `{name}` failed after {consecutive_restarts} consecutive restart(s); escalating: {err:#}"
+                );
+                METRICS.escalations[&name].inc();
+                return Err(err.context(format!(
+                    "supervised task `{name}` exhausted its {} restart(s)",
+                    policy.max_restarts
+                )));
+            }
+
+            consecutive_restarts += 1;
+            total_restarts += 1;
+            tracing::warn!(
+                "Supervised task `{name}` failed, restarting in {backoff:?} (restart {consecutive_restarts}/{}): {err:#}",
+                policy.max_restarts
+            );
+            METRICS.restarts[&name].inc();
+            METRICS.consecutive_restarts[&name].set(consecutive_restarts.into());
+            let error_message = format!("{err:#}");
+            update_health(
+                &health_updater,
+                HealthStatus::Affected,
+                consecutive_restarts,
+                total_restarts,
+                Some(&error_message),
+            );
+            last_error = Some(error_message);
+
+            if tokio::time::timeout(backoff, stop_receiver.0.changed())
+                .await
+                .is_ok()
+            {
+                tracing::info!("Stop signal received while waiting to restart `{name}`, exiting");
+                return Ok(());
+            }
+            backoff = policy.next_backoff(backoff);
+        }
+    }
+}
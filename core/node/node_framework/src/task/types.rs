@@ -3,9 +3,12 @@ use std::{
     ops::Deref,
 };
 
+use serde::Serialize;
+
 /// Task kind.
 /// See [`Task`](super::Task) documentation for more details.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum TaskKind {
     Task,
@@ -24,6 +27,18 @@ impl TaskKind {
     }
 }
 
+impl Display for TaskKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Task => "task",
+            Self::OneshotTask => "oneshot_task",
+            Self::UnconstrainedTask => "unconstrained_task",
+            Self::UnconstrainedOneshotTask => "unconstrained_oneshot_task",
+            Self::Precondition => "precondition",
+        })
+    }
+}
+
 /// A unique human-readable identifier of a task.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TaskId(String);
@@ -1,8 +1,11 @@
+use std::num::NonZeroU32;
+
 use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_block_reverter::BlockReverter;
-use zksync_dal::{ConnectionPool, Core};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_reorg_detector::ReorgDetector;
+use zksync_shared_metrics::EN_METRICS;
 use zksync_types::L1BatchNumber;
 use zksync_web3_decl::client::{DynClient, L2};
 
@@ -13,6 +16,37 @@ pub struct ExternalNodeReverter {
     pub client: Box<DynClient<L2>>,
     pub pool: ConnectionPool<Core>,
     pub reverter: Option<BlockReverter>,
+    /// Maximum number of L1 batches that can be rolled back. If not set, rollbacks are not limited.
+    pub max_rollback_depth: Option<NonZeroU32>,
+}
+
+impl ExternalNodeReverter {
+    async fn check_rollback_depth(&self, to_batch: L1BatchNumber) -> anyhow::Result<()> {
+        let mut storage = self.pool.connection_tagged("node_init").await?;
+        let last_batch = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?
+            .context("storage contains no L1 batches")?;
+        drop(storage);
+
+        let depth = last_batch.0.saturating_sub(to_batch.0);
+        EN_METRICS.reorg_rollback_depth.set(depth.into());
+        if let Some(max_depth) = self.max_rollback_depth {
+            if depth > max_depth.get() {
+                EN_METRICS.reorg_rollback_limit_exceeded.set(1);
+                tracing::error!(
+                    "Recovering from reorg requires rolling back {depth} L1 batches (from #{last_batch} to #{to_batch}), \
+                     which exceeds the configured limit of {max_depth} L1 batches"
+                );
+                anyhow::bail!(
+                    "Rollback to L1 batch #{to_batch} exceeds the limit of {max_depth} L1 batches; \
+                     it must be performed manually using the block reverter"
+                );
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -28,9 +62,13 @@ impl RevertStorage for ExternalNodeReverter {
             );
         };
 
+        self.check_rollback_depth(to_batch).await?;
+
         tracing::info!("Reverting to l1 batch number {to_batch}");
         block_reverter.roll_back(to_batch).await?;
         tracing::info!("Revert successfully completed");
+        EN_METRICS.reorg_rollbacks.inc();
+        EN_METRICS.reorg_rollback_limit_exceeded.set(0);
         Ok(())
     }
 
@@ -60,3 +98,51 @@ impl RevertStorage for ExternalNodeReverter {
         Ok(batch)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_block_reverter::NodeRole;
+    use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+    use zksync_node_test_utils::create_l1_batch;
+    use zksync_web3_decl::client::MockClient;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn rollback_exceeding_depth_limit_is_rejected() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        for number in 1..=5 {
+            storage
+                .blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch(number))
+                .await
+                .unwrap();
+        }
+        drop(storage);
+
+        let reverter = ExternalNodeReverter {
+            client: Box::new(MockClient::builder(L2::default()).build()),
+            pool: pool.clone(),
+            reverter: Some(BlockReverter::new(NodeRole::External, pool.clone())),
+            max_rollback_depth: NonZeroU32::new(3),
+        };
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let err = reverter
+            .revert_storage(L1BatchNumber(1), stop_receiver)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("manually"), "{err:#}");
+        assert_eq!(EN_METRICS.reorg_rollback_limit_exceeded.get(), 1);
+
+        // Rollbacks within the limit are not rejected by the depth check.
+        reverter
+            .check_rollback_depth(L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(EN_METRICS.reorg_rollback_depth.get(), 3);
+    }
+}
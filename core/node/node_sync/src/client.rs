@@ -3,6 +3,7 @@
 use std::fmt;
 
 use async_trait::async_trait;
+use futures::future;
 use zksync_config::GenesisConfig;
 use zksync_health_check::{CheckHealth, Health, HealthStatus};
 use zksync_system_constants::ACCOUNT_CODE_STORAGE_ADDRESS;
@@ -12,8 +13,9 @@ use zksync_types::{
     get_code_key, h256_to_u256, Address, L2BlockNumber, ProtocolVersionId, H256, U64,
 };
 use zksync_web3_decl::{
-    client::{DynClient, L2},
+    client::{DynClient, FallbackClient, L2},
     error::{ClientRpcContext, EnrichedClientError, EnrichedClientResult},
+    jsonrpsee::core::ClientError,
     namespaces::{EnNamespaceClient, EthNamespaceClient, ZksNamespaceClient},
 };
 
@@ -158,3 +160,127 @@ impl CheckHealth for MainNodeHealthCheck {
         HealthStatus::Ready.into()
     }
 }
+
+/// Health check for upstreams of the main node client if it's configured with fallback upstreams.
+/// Unlike [`MainNodeHealthCheck`], this check is passive; it reports the health of upstreams
+/// as observed by the client.
+#[derive(Debug)]
+pub struct MainNodeUpstreamsHealthCheck(FallbackClient<L2>);
+
+impl From<FallbackClient<L2>> for MainNodeUpstreamsHealthCheck {
+    fn from(client: FallbackClient<L2>) -> Self {
+        Self(client)
+    }
+}
+
+#[async_trait]
+impl CheckHealth for MainNodeUpstreamsHealthCheck {
+    fn name(&self) -> &'static str {
+        "main_node_upstreams"
+    }
+
+    async fn check_health(&self) -> Health {
+        let upstreams = self.0.health();
+        let backed_off_count = upstreams
+            .iter()
+            .filter(|health| health.is_backed_off)
+            .count();
+        let status = if backed_off_count == upstreams.len() {
+            HealthStatus::NotReady
+        } else if backed_off_count > 0 {
+            HealthStatus::Affected
+        } else {
+            HealthStatus::Ready
+        };
+        Health::from(status).with_details(serde_json::json!({
+            "upstreams": upstreams,
+        }))
+    }
+}
+
+/// Cross-checks L2 block hashes returned by the main node client against all upstreams of the client.
+#[derive(Debug, Clone)]
+pub struct L2BlockHashCrossChecker {
+    client: FallbackClient<L2>,
+    upstreams: Vec<(String, Box<DynClient<L2>>)>,
+}
+
+impl L2BlockHashCrossChecker {
+    pub fn new(client: &FallbackClient<L2>) -> Self {
+        let upstreams = client
+            .upstreams()
+            .map(|(name, client)| {
+                (
+                    name.to_owned(),
+                    client.for_component("block_hash_cross_checker"),
+                )
+            })
+            .collect();
+        Self {
+            client: client.clone(),
+            upstreams,
+        }
+    }
+
+    /// Fetches an L2 block using the main node client. Returns the block together with the name of the upstream
+    /// that has served it, which should be passed to [`Self::check()`].
+    pub async fn sync_l2_block(
+        &self,
+        number: L2BlockNumber,
+        with_transactions: bool,
+    ) -> (Result<Option<en::SyncBlock>, ClientError>, String) {
+        let (result, source) = self
+            .client
+            .call_with_upstream("en_syncL2Block", |client| async move {
+                client.sync_l2_block(number, with_transactions).await
+            })
+            .await;
+        (result, source.to_owned())
+    }
+
+    /// Checks that no upstream reports a hash for the `block` differing from the one in the block.
+    /// The `source` upstream that has served the block, and upstreams that are unreachable or do not have the block yet
+    /// are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on hash mismatch.
+    pub async fn check(&self, block: &en::SyncBlock, source: &str) -> anyhow::Result<()> {
+        let Some(expected_hash) = block.hash else {
+            return Ok(()); // Nothing to compare with
+        };
+        let number = block.number;
+        let upstreams = self.upstreams.iter().filter(|(name, _)| name != source);
+        let fetches = upstreams.map(|(name, client)| async move {
+            let result = client
+                .sync_l2_block(number, false)
+                .rpc_context("sync_l2_block")
+                .with_arg("number", &number)
+                .await;
+            (name, result)
+        });
+
+        for (name, result) in future::join_all(fetches).await {
+            let upstream_block = match result {
+                Ok(Some(upstream_block)) => upstream_block,
+                Ok(None) => continue, // The upstream is lagging behind
+                Err(err) => {
+                    tracing::info!(
+                        "Cannot cross-check hash of L2 block #{number} with upstream `{name}`: {err}"
+                    );
+                    continue;
+                }
+            };
+            let Some(upstream_hash) = upstream_block.hash else {
+                continue;
+            };
+            if upstream_hash != expected_hash {
+                anyhow::bail!(
+                    "L2 block #{number} hash mismatch: {expected_hash:?} was returned by upstream `{source}`, \
+                     but upstream `{name}` reports {upstream_hash:?}"
+                );
+            }
+        }
+        Ok(())
+    }
+}
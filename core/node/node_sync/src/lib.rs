@@ -13,7 +13,9 @@ pub mod tree_data_fetcher;
 pub mod validate_chain_ids_task;
 
 pub use self::{
-    client::{MainNodeClient, MainNodeHealthCheck},
+    client::{
+        L2BlockHashCrossChecker, MainNodeClient, MainNodeHealthCheck, MainNodeUpstreamsHealthCheck,
+    },
     external_io::ExternalIO,
     sync_action::{ActionQueue, ActionQueueSender},
     sync_state::SyncState,
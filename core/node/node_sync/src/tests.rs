@@ -1,6 +1,13 @@
 //! High-level sync layer tests.
 
-use std::{iter, sync::Arc, time::Duration};
+use std::{
+    iter,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use backon::{ConstantBuilder, Retryable};
 use test_casing::test_casing;
@@ -24,6 +31,7 @@ use zksync_types::{
     snapshots::SnapshotRecoveryStatus,
     Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersionId, Transaction, H256,
 };
+use zksync_web3_decl::client::{DynClient, FallbackClient, MockClient, L2};
 
 use super::{
     fetcher::FetchedTransaction, sync_action::SyncAction, testonly::MockMainNodeClient, *,
@@ -754,3 +762,74 @@ async fn external_io_empty_unsealed_batch() {
     state_keeper.wait_for_local_block(L2BlockNumber(4)).await;
     hash_task.await.unwrap();
 }
+
+fn mock_upstream(hash: Option<H256>, calls: Arc<AtomicUsize>) -> Box<DynClient<L2>> {
+    let client = MockClient::builder(L2::default())
+        .method(
+            "en_syncL2Block",
+            move |number: L2BlockNumber, _with_transactions: bool| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                let block = hash.map(|hash| api::en::SyncBlock {
+                    number,
+                    l1_batch_number: L1BatchNumber(1),
+                    last_in_batch: false,
+                    timestamp: number.0.into(),
+                    l1_gas_price: 2,
+                    l2_fair_gas_price: 3,
+                    fair_pubdata_price: Some(24),
+                    base_system_contracts_hashes: BaseSystemContractsHashes::default(),
+                    operator_address: OPERATOR_ADDRESS,
+                    transactions: None,
+                    virtual_blocks: Some(1),
+                    hash: Some(hash),
+                    protocol_version: ProtocolVersionId::latest(),
+                    pubdata_params: Default::default(),
+                });
+                Ok(block)
+            },
+        )
+        .build();
+    Box::new(client)
+}
+
+#[tokio::test]
+async fn cross_checking_l2_block_hashes() {
+    let main_node_calls = Arc::<AtomicUsize>::default();
+    let client = FallbackClient::builder(L2::default())
+        .upstream(
+            "main_node",
+            mock_upstream(Some(H256::repeat_byte(1)), main_node_calls.clone()),
+        )
+        .upstream("lagging", mock_upstream(None, Arc::default()))
+        .upstream(
+            "other",
+            mock_upstream(Some(H256::repeat_byte(1)), Arc::default()),
+        )
+        .build();
+    let checker = L2BlockHashCrossChecker::new(&client);
+    let (block, source) = checker.sync_l2_block(L2BlockNumber(1), false).await;
+    let block = block.unwrap().unwrap();
+    assert_eq!(source, "main_node");
+    checker.check(&block, &source).await.unwrap();
+    // The upstream that has served the block must not be queried again.
+    assert_eq!(main_node_calls.load(Ordering::Relaxed), 1);
+
+    let client = FallbackClient::builder(L2::default())
+        .upstream(
+            "main_node",
+            mock_upstream(Some(H256::repeat_byte(1)), Arc::default()),
+        )
+        .upstream(
+            "diverging",
+            mock_upstream(Some(H256::repeat_byte(2)), Arc::default()),
+        )
+        .build();
+    let checker = L2BlockHashCrossChecker::new(&client);
+    let err = checker
+        .check(&block, "main_node")
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("hash mismatch"), "{err}");
+    assert!(err.contains("diverging"), "{err}");
+}
@@ -0,0 +1,378 @@
+//! Intel DCAP attestation collateral: CRLs, TCB info and QE identity. The collateral is used to check that
+//! certificates in the PCK chain are not revoked, and to evaluate the TCB status of the attested platform
+//! and its quoting enclave.
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use serde::{de, de::DeserializeOwned, Deserialize, Deserializer};
+use serde_json::value::RawValue;
+use webpki::CertRevocationList;
+use zksync_types::tee_types::TeeType;
+
+use super::{
+    quote::QeReport,
+    x509::{self, Certificate, SgxExtensions},
+    AttestationError,
+};
+
+fn collateral_error(message: String) -> AttestationError {
+    AttestationError::Collateral(message)
+}
+
+fn deserialize_hex<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error> {
+    let hex_string = String::deserialize(deserializer)?;
+    let bytes = hex::decode(&hex_string).map_err(de::Error::custom)?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| de::Error::invalid_length(bytes.len(), &"fixed-size hex string"))
+}
+
+/// Collateral bundle as stored in the file specified by `tee_attestation_collateral_path`. Mirrors collateral
+/// served by the Intel Provisioning Certification Service (PCS). TCB infos and QE identities must be stored
+/// as verbatim PCS response bodies, since their signatures cover the exact JSON bytes.
+#[derive(Debug, Deserialize)]
+struct CollateralBundle {
+    /// PEM-encoded CRL issued by the root CA.
+    root_ca_crl: String,
+    /// PEM-encoded CRLs issued by the PCK CAs (Intel has separate Platform and Processor CAs).
+    pck_crls: Vec<String>,
+    /// PEM-encoded certificate chain of the key signing TCB infos and QE identities.
+    tcb_signing_chain: String,
+    /// TCB infos for all supported platforms (FMSPCs) and TEE types.
+    tcb_infos: Vec<String>,
+    /// Identities of SGX (`QE`) and TDX (`TD_QE`) quoting enclaves.
+    qe_identities: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignedTcbInfo {
+    tcb_info: Box<RawValue>,
+    signature: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignedQeIdentity {
+    enclave_identity: Box<RawValue>,
+    signature: String,
+}
+
+/// Collateral item signed by the TCB signing key.
+#[derive(Debug)]
+struct Signed<T> {
+    body: Box<RawValue>,
+    signature: Signature,
+    value: T,
+}
+
+impl<T: DeserializeOwned + CollateralItem> Signed<T> {
+    fn new(body: Box<RawValue>, signature: &str) -> anyhow::Result<Self> {
+        let signature = hex::decode(signature).context("signature is not hex-encoded")?;
+        let signature = Signature::from_slice(&signature).context("invalid signature")?;
+        let value = serde_json::from_str(body.get())?;
+        Ok(Self {
+            body,
+            signature,
+            value,
+        })
+    }
+
+    /// Verifies the signature and freshness of this item, returning the verified item.
+    fn verify(
+        &self,
+        signing_key: &VerifyingKey,
+        now: DateTime<Utc>,
+    ) -> Result<&T, AttestationError> {
+        let id = self.value.id();
+        signing_key
+            .verify(self.body.get().as_bytes(), &self.signature)
+            .map_err(|_| collateral_error(format!("invalid signature of {id}")))?;
+        let (issue_date, next_update) = self.value.validity();
+        if now < issue_date || now > next_update {
+            return Err(collateral_error(format!(
+                "{id} is not valid at {now}: validity period is {issue_date} to {next_update}"
+            )));
+        }
+        Ok(&self.value)
+    }
+}
+
+trait CollateralItem {
+    /// Human-readable ID of the item used in errors.
+    fn id(&self) -> String;
+
+    fn validity(&self) -> (DateTime<Utc>, DateTime<Utc>);
+}
+
+#[derive(Debug, Deserialize)]
+struct TcbComponent {
+    svn: u8,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlatformTcb {
+    sgxtcbcomponents: Vec<TcbComponent>,
+    pcesvn: u16,
+    /// Only present in TDX TCB infos.
+    #[serde(default)]
+    tdxtcbcomponents: Vec<TcbComponent>,
+}
+
+impl PlatformTcb {
+    fn is_satisfied_by(&self, platform: &SgxExtensions, tee_tcb_svn: Option<&[u8; 16]>) -> bool {
+        let components_satisfied = |components: &[TcbComponent], svns: &[u8; 16]| {
+            components.len() == svns.len()
+                && components
+                    .iter()
+                    .zip(svns)
+                    .all(|(component, &svn)| svn >= component.svn)
+        };
+
+        let tdx_satisfied = match tee_tcb_svn {
+            Some(tee_tcb_svn) => components_satisfied(&self.tdxtcbcomponents, tee_tcb_svn),
+            None => true,
+        };
+        components_satisfied(&self.sgxtcbcomponents, &platform.tcb_components)
+            && platform.pce_svn >= self.pcesvn
+            && tdx_satisfied
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TcbLevel<T> {
+    tcb: T,
+    tcb_status: String,
+}
+
+/// TCB info for a specific platform (FMSPC) and TEE type.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TcbInfo {
+    /// `SGX` or `TDX`.
+    id: String,
+    issue_date: DateTime<Utc>,
+    next_update: DateTime<Utc>,
+    #[serde(deserialize_with = "deserialize_hex")]
+    fmspc: [u8; 6],
+    #[serde(deserialize_with = "deserialize_hex")]
+    pce_id: [u8; 2],
+    /// TCB levels ordered from the highest to the lowest one.
+    tcb_levels: Vec<TcbLevel<PlatformTcb>>,
+}
+
+impl CollateralItem for TcbInfo {
+    fn id(&self) -> String {
+        format!("{} TCB info for FMSPC {}", self.id, hex::encode(self.fmspc))
+    }
+
+    fn validity(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        (self.issue_date, self.next_update)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct QeTcb {
+    isvsvn: u16,
+}
+
+/// Identity of the quoting enclave.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QeIdentity {
+    /// `QE` for SGX or `TD_QE` for TDX.
+    id: String,
+    issue_date: DateTime<Utc>,
+    next_update: DateTime<Utc>,
+    #[serde(deserialize_with = "deserialize_hex")]
+    miscselect: [u8; 4],
+    #[serde(deserialize_with = "deserialize_hex")]
+    miscselect_mask: [u8; 4],
+    #[serde(deserialize_with = "deserialize_hex")]
+    attributes: [u8; 16],
+    #[serde(deserialize_with = "deserialize_hex")]
+    attributes_mask: [u8; 16],
+    #[serde(deserialize_with = "deserialize_hex")]
+    mrsigner: [u8; 32],
+    isvprodid: u16,
+    /// TCB levels ordered from the highest to the lowest one.
+    tcb_levels: Vec<TcbLevel<QeTcb>>,
+}
+
+impl CollateralItem for QeIdentity {
+    fn id(&self) -> String {
+        format!("{} identity", self.id)
+    }
+
+    fn validity(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        (self.issue_date, self.next_update)
+    }
+}
+
+fn masked_eq(lhs: &[u8], rhs: &[u8], mask: &[u8]) -> bool {
+    lhs.iter()
+        .zip(rhs)
+        .zip(mask)
+        .all(|((&lhs, &rhs), &mask)| lhs & mask == rhs & mask)
+}
+
+/// Parsed attestation collateral. Signatures and freshness of collateral items are checked on each use,
+/// since collateral expires over time.
+#[derive(Debug)]
+pub(super) struct Collateral {
+    crls: Vec<CertRevocationList<'static>>,
+    tcb_signing_chain: Vec<Certificate>,
+    tcb_infos: Vec<Signed<TcbInfo>>,
+    qe_identities: Vec<Signed<QeIdentity>>,
+}
+
+impl Collateral {
+    pub fn from_json(raw: &[u8]) -> anyhow::Result<Self> {
+        let bundle: CollateralBundle =
+            serde_json::from_slice(raw).context("invalid collateral bundle")?;
+
+        let crls = std::iter::once(&bundle.root_ca_crl)
+            .chain(&bundle.pck_crls)
+            .enumerate()
+            .map(|(i, crl)| x509::parse_crl(crl.as_bytes()).with_context(|| format!("CRL #{i}")))
+            .collect::<anyhow::Result<_>>()?;
+        let tcb_signing_chain = x509::parse_pem_chain(bundle.tcb_signing_chain.as_bytes())
+            .context("tcb_signing_chain")?;
+        anyhow::ensure!(!tcb_signing_chain.is_empty(), "tcb_signing_chain is empty");
+        let tcb_infos = bundle
+            .tcb_infos
+            .iter()
+            .enumerate()
+            .map(|(i, raw)| {
+                let signed: SignedTcbInfo = serde_json::from_str(raw)?;
+                Signed::new(signed.tcb_info, &signed.signature).context(i)
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("tcb_infos")?;
+        let qe_identities = bundle
+            .qe_identities
+            .iter()
+            .enumerate()
+            .map(|(i, raw)| {
+                let signed: SignedQeIdentity = serde_json::from_str(raw)?;
+                Signed::new(signed.enclave_identity, &signed.signature).context(i)
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("qe_identities")?;
+
+        Ok(Self {
+            crls,
+            tcb_signing_chain,
+            tcb_infos,
+            qe_identities,
+        })
+    }
+
+    /// Verifies the certificate `chain` against `trusted_root` and checks that no certificate in it is revoked.
+    pub fn verify_chain(
+        &self,
+        chain: &[Certificate],
+        trusted_root: &Certificate,
+        now: DateTime<Utc>,
+    ) -> Result<(), AttestationError> {
+        x509::verify_chain(chain, trusted_root, &self.crls, now)
+    }
+
+    fn tcb_signing_key(
+        &self,
+        trusted_root: &Certificate,
+        now: DateTime<Utc>,
+    ) -> Result<&VerifyingKey, AttestationError> {
+        self.verify_chain(&self.tcb_signing_chain, trusted_root, now)
+            .map_err(|err| collateral_error(format!("TCB signing chain: {err}")))?;
+        Ok(self.tcb_signing_chain[0].public_key())
+    }
+
+    /// Evaluates the TCB status of the attested platform. `tee_tcb_svn` must be specified for TDX.
+    pub fn platform_tcb_status(
+        &self,
+        trusted_root: &Certificate,
+        now: DateTime<Utc>,
+        tee_type: TeeType,
+        platform: &SgxExtensions,
+        tee_tcb_svn: Option<&[u8; 16]>,
+    ) -> Result<&str, AttestationError> {
+        let id = match tee_type {
+            TeeType::Sgx => "SGX",
+            TeeType::Tdx => "TDX",
+            _ => return Err(AttestationError::UnsupportedTeeType(tee_type)),
+        };
+        let signed_tcb_info = self
+            .tcb_infos
+            .iter()
+            .find(|tcb_info| tcb_info.value.id == id && tcb_info.value.fmspc == platform.fmspc)
+            .ok_or_else(|| {
+                collateral_error(format!(
+                    "no {id} TCB info for FMSPC {}",
+                    hex::encode(platform.fmspc)
+                ))
+            })?;
+        let tcb_info = signed_tcb_info.verify(self.tcb_signing_key(trusted_root, now)?, now)?;
+        if tcb_info.pce_id != platform.pce_id {
+            return Err(collateral_error(format!(
+                "PCE ID in {} does not match the PCK certificate",
+                tcb_info.id()
+            )));
+        }
+
+        tcb_info
+            .tcb_levels
+            .iter()
+            .find(|level| level.tcb.is_satisfied_by(platform, tee_tcb_svn))
+            .map(|level| level.tcb_status.as_str())
+            .ok_or(AttestationError::TcbLevelNotFound("platform"))
+    }
+
+    /// Checks the quoting enclave against its identity and evaluates its TCB status.
+    pub fn qe_tcb_status(
+        &self,
+        trusted_root: &Certificate,
+        now: DateTime<Utc>,
+        tee_type: TeeType,
+        qe_report: &QeReport<'_>,
+    ) -> Result<&str, AttestationError> {
+        let id = match tee_type {
+            TeeType::Sgx => "QE",
+            TeeType::Tdx => "TD_QE",
+            _ => return Err(AttestationError::UnsupportedTeeType(tee_type)),
+        };
+        let signed_identity = self
+            .qe_identities
+            .iter()
+            .find(|identity| identity.value.id == id)
+            .ok_or_else(|| collateral_error(format!("no {id} identity")))?;
+        let identity = signed_identity.verify(self.tcb_signing_key(trusted_root, now)?, now)?;
+
+        if qe_report.mr_signer != identity.mrsigner
+            || qe_report.isv_prod_id != identity.isvprodid
+            || !masked_eq(
+                &qe_report.misc_select,
+                &identity.miscselect,
+                &identity.miscselect_mask,
+            )
+            || !masked_eq(
+                &qe_report.attributes,
+                &identity.attributes,
+                &identity.attributes_mask,
+            )
+        {
+            return Err(AttestationError::QeIdentityMismatch);
+        }
+
+        identity
+            .tcb_levels
+            .iter()
+            .find(|level| qe_report.isv_svn >= level.tcb.isvsvn)
+            .map(|level| level.tcb_status.as_str())
+            .ok_or(AttestationError::TcbLevelNotFound("quoting enclave"))
+    }
+}
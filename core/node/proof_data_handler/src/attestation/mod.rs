@@ -0,0 +1,277 @@
+//! Server-side verification of TEE attestation quotes.
+//!
+//! Supported are Intel DCAP ECDSA quotes: v3 quotes produced by SGX enclaves and v4 quotes produced by SGX enclaves
+//! or TDX trust domains. Verification checks that:
+//!
+//! - The PCK certificate chain embedded into the quote chains to the configured root CA, all certificates in it
+//!   are valid at the time of verification, and none of them is revoked according to the collateral CRLs.
+//! - The quoting enclave report is signed by the PCK key and commits to the attestation key.
+//! - The quote header and report body are signed by the attestation key.
+//! - The quoting enclave matches its identity from the collateral, and its TCB status is allowed.
+//! - The TCB status of the platform evaluated against the TCB info from the collateral is allowed.
+//! - The attested enclave / trust domain is not in debug mode and its measurements are allowlisted.
+//! - The report data is bound to the public key the TEE prover will use to sign proofs.
+//!
+//! Collateral (CRLs, TCB infos and QE identities) is bundled with the node configuration rather than fetched
+//! from Intel PCS, so it must be refreshed before it expires. For TDX, TDX module identities are not evaluated.
+
+use std::{collections::HashSet, path::Path};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use zksync_config::configs::TeeConfig;
+use zksync_types::{tee_types::TeeType, H256};
+
+use self::{
+    collateral::Collateral,
+    quote::{Measurements, Quote},
+    x509::Certificate,
+};
+
+mod collateral;
+mod quote;
+#[cfg(test)]
+mod tests;
+mod x509;
+
+/// Errors that can occur during attestation verification.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum AttestationError {
+    #[error("malformed attestation quote: {0}")]
+    MalformedQuote(String),
+    #[error("unsupported attestation quote: {0}")]
+    UnsupportedQuote(String),
+    #[error("unsupported TEE type: {0}")]
+    UnsupportedTeeType(TeeType),
+    #[error("PCK certificate chain verification failed: {0}")]
+    CertificateChain(String),
+    #[error("attestation collateral verification failed: {0}")]
+    Collateral(String),
+    #[error("quoting enclave does not match its identity from the collateral")]
+    QeIdentityMismatch,
+    #[error("{0} TCB level is not supported by the collateral")]
+    TcbLevelNotFound(&'static str),
+    #[error("{component} TCB status `{status}` is not allowed")]
+    TcbStatusNotAllowed {
+        component: &'static str,
+        status: String,
+    },
+    #[error("invalid {0} signature")]
+    InvalidSignature(&'static str),
+    #[error("quoting enclave report data does not commit to the attestation key")]
+    AttestationKeyMismatch,
+    #[error("attested {0} TEE runs in debug mode")]
+    DebugMode(TeeType),
+    #[error("measurements of the attested {0} TEE are not allowlisted")]
+    MeasurementsNotAllowed(TeeType),
+    #[error("report data is not bound to the submitted public key")]
+    PubkeyMismatch,
+}
+
+/// TCB status of an up-to-date platform / quoting enclave, which is always allowed.
+const TCB_STATUS_UP_TO_DATE: &str = "UpToDate";
+
+/// Verifier of attestation quotes, configured with the trusted root CA, collateral and allowlisted measurements.
+#[derive(Debug)]
+pub(crate) struct AttestationVerifier {
+    trusted_root: Certificate,
+    collateral: Collateral,
+    allowed_tcb_statuses: HashSet<String>,
+    sgx_mrenclave_allowlist: HashSet<H256>,
+    sgx_mrsigner_allowlist: HashSet<H256>,
+    tdx_mrtd_allowlist: HashSet<[u8; 48]>,
+}
+
+impl AttestationVerifier {
+    pub fn new(config: &TeeConfig) -> anyhow::Result<Self> {
+        let root_ca_path = config.tee_attestation_root_ca_path.as_deref().context(
+            "`tee_attestation_root_ca_path` must be set if attestation verification is enabled",
+        )?;
+        let root_ca_pem = std::fs::read(Path::new(root_ca_path))
+            .with_context(|| format!("failed reading root CA certificate from `{root_ca_path}`"))?;
+        let collateral_path = config.tee_attestation_collateral_path.as_deref().context(
+            "`tee_attestation_collateral_path` must be set if attestation verification is enabled",
+        )?;
+        let collateral = std::fs::read(Path::new(collateral_path)).with_context(|| {
+            format!("failed reading attestation collateral from `{collateral_path}`")
+        })?;
+        Self::from_raw(&root_ca_pem, &collateral, config)
+    }
+
+    fn from_raw(root_ca_pem: &[u8], collateral: &[u8], config: &TeeConfig) -> anyhow::Result<Self> {
+        let trusted_root = x509::parse_pem_chain(root_ca_pem)
+            .context("failed parsing root CA certificate")?
+            .into_iter()
+            .next()
+            .context("no certificates in root CA PEM")?;
+        let collateral =
+            Collateral::from_json(collateral).context("failed parsing attestation collateral")?;
+        let allowed_tcb_statuses = config
+            .tee_attestation_allowed_tcb_statuses
+            .iter()
+            .cloned()
+            .chain([TCB_STATUS_UP_TO_DATE.to_owned()])
+            .collect();
+        let tdx_mrtd_allowlist = config
+            .tee_tdx_mrtd_allowlist
+            .iter()
+            .enumerate()
+            .map(|(i, mr_td)| {
+                let mr_td = hex::decode(mr_td.strip_prefix("0x").unwrap_or(mr_td))?;
+                <[u8; 48]>::try_from(mr_td)
+                    .map_err(|mr_td| {
+                        anyhow::anyhow!("MRTD has length {}, expected 48", mr_td.len())
+                    })
+                    .context(i)
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("tee_tdx_mrtd_allowlist")?;
+
+        let this = Self {
+            trusted_root,
+            collateral,
+            allowed_tcb_statuses,
+            sgx_mrenclave_allowlist: config.tee_sgx_mrenclave_allowlist.iter().copied().collect(),
+            sgx_mrsigner_allowlist: config.tee_sgx_mrsigner_allowlist.iter().copied().collect(),
+            tdx_mrtd_allowlist,
+        };
+        if this.sgx_mrenclave_allowlist.is_empty()
+            && this.sgx_mrsigner_allowlist.is_empty()
+            && this.tdx_mrtd_allowlist.is_empty()
+        {
+            tracing::warn!(
+                "TEE attestation verification is enabled, but no measurements are allowlisted; all attestations will be rejected"
+            );
+        }
+        Ok(this)
+    }
+
+    /// Verifies the attestation `quote` and checks that it is bound to `pubkey`. Returns the type of the attested TEE.
+    pub fn verify(
+        &self,
+        quote: &[u8],
+        pubkey: &[u8],
+        now: DateTime<Utc>,
+    ) -> Result<TeeType, AttestationError> {
+        let quote = Quote::parse(quote)?;
+
+        let pck_chain = x509::parse_pem_chain(quote.pck_chain)?;
+        self.collateral
+            .verify_chain(&pck_chain, &self.trusted_root, now)?;
+        let platform = pck_chain[0].sgx_extensions().ok_or_else(|| {
+            AttestationError::CertificateChain("PCK certificate has no SGX extensions".into())
+        })?;
+        let pck_key = pck_chain[0].public_key();
+        verify_signature(
+            pck_key,
+            quote.qe_report.raw,
+            quote.qe_report_signature,
+            "QE report",
+        )?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(quote.attestation_key);
+        hasher.update(quote.qe_auth_data);
+        let expected_qe_report_data = hasher.finalize();
+        let (qe_key_hash, qe_padding) = quote.qe_report.report_data.split_at(32);
+        if qe_key_hash != &expected_qe_report_data[..] || !is_zero(qe_padding) {
+            return Err(AttestationError::AttestationKeyMismatch);
+        }
+
+        let mut sec1_attestation_key = [0_u8; 65];
+        sec1_attestation_key[0] = 0x04; // uncompressed point
+        sec1_attestation_key[1..].copy_from_slice(quote.attestation_key);
+        let attestation_key = VerifyingKey::from_sec1_bytes(&sec1_attestation_key)
+            .map_err(|_| AttestationError::MalformedQuote("invalid attestation key".into()))?;
+        verify_signature(
+            &attestation_key,
+            quote.signed_data,
+            quote.signature,
+            "quote",
+        )?;
+
+        let tee_type = quote.measurements.tee_type();
+        let qe_tcb_status =
+            self.collateral
+                .qe_tcb_status(&self.trusted_root, now, tee_type, &quote.qe_report)?;
+        self.check_tcb_status("quoting enclave", qe_tcb_status)?;
+        let tee_tcb_svn = match &quote.measurements {
+            Measurements::Sgx { .. } => None,
+            Measurements::Tdx { tee_tcb_svn, .. } => Some(tee_tcb_svn),
+        };
+        let platform_tcb_status = self.collateral.platform_tcb_status(
+            &self.trusted_root,
+            now,
+            tee_type,
+            platform,
+            tee_tcb_svn,
+        )?;
+        self.check_tcb_status("platform", platform_tcb_status)?;
+
+        if quote.measurements.is_debug() {
+            return Err(AttestationError::DebugMode(tee_type));
+        }
+        if !self.is_allowed(&quote.measurements) {
+            return Err(AttestationError::MeasurementsNotAllowed(tee_type));
+        }
+        if !is_bound_to_pubkey(quote.report_data, pubkey) {
+            return Err(AttestationError::PubkeyMismatch);
+        }
+        Ok(tee_type)
+    }
+
+    fn check_tcb_status(
+        &self,
+        component: &'static str,
+        status: &str,
+    ) -> Result<(), AttestationError> {
+        if self.allowed_tcb_statuses.contains(status) {
+            Ok(())
+        } else {
+            Err(AttestationError::TcbStatusNotAllowed {
+                component,
+                status: status.to_owned(),
+            })
+        }
+    }
+
+    fn is_allowed(&self, measurements: &Measurements) -> bool {
+        match measurements {
+            Measurements::Sgx {
+                mr_enclave,
+                mr_signer,
+                ..
+            } => {
+                self.sgx_mrenclave_allowlist.contains(&H256(*mr_enclave))
+                    || self.sgx_mrsigner_allowlist.contains(&H256(*mr_signer))
+            }
+            Measurements::Tdx { mr_td, .. } => self.tdx_mrtd_allowlist.contains(mr_td),
+        }
+    }
+}
+
+fn verify_signature(
+    key: &VerifyingKey,
+    message: &[u8],
+    signature: &[u8],
+    name: &'static str,
+) -> Result<(), AttestationError> {
+    let signature =
+        Signature::from_slice(signature).map_err(|_| AttestationError::InvalidSignature(name))?;
+    key.verify(message, &signature)
+        .map_err(|_| AttestationError::InvalidSignature(name))
+}
+
+fn is_zero(bytes: &[u8]) -> bool {
+    bytes.iter().all(|&byte| byte == 0)
+}
+
+/// Report data is bound to a public key if it starts with the key bytes and is zero-padded after them.
+fn is_bound_to_pubkey(report_data: &[u8], pubkey: &[u8]) -> bool {
+    !pubkey.is_empty()
+        && report_data.len() >= pubkey.len()
+        && report_data.starts_with(pubkey)
+        && is_zero(&report_data[pubkey.len()..])
+}
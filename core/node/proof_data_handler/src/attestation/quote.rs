@@ -0,0 +1,240 @@
+//! Parsing of Intel DCAP ECDSA quotes (v3 for SGX, v4 for SGX and TDX).
+
+use zksync_types::tee_types::TeeType;
+
+use super::AttestationError;
+
+const HEADER_LEN: usize = 48;
+const SGX_REPORT_BODY_LEN: usize = 384;
+const TDX_REPORT_BODY_LEN: usize = 584;
+const ECDSA_SIGNATURE_LEN: usize = 64;
+const ECDSA_PUBLIC_KEY_LEN: usize = 64;
+
+/// Attestation key type for ECDSA-256-with-P-256 curve.
+const ATTESTATION_KEY_TYPE_ECDSA_P256: u16 = 2;
+const TEE_TYPE_SGX: u32 = 0;
+const TEE_TYPE_TDX: u32 = 0x81;
+/// Certification data containing a concatenated PEM-encoded PCK certificate chain.
+const CERT_DATA_TYPE_PCK_CHAIN: u16 = 5;
+/// Certification data containing the QE report, its signature and nested certification data (v4 quotes).
+const CERT_DATA_TYPE_QE_REPORT: u16 = 6;
+
+/// `DEBUG` bit in SGX enclave attributes.
+const SGX_ATTRIBUTE_DEBUG: u64 = 1 << 1;
+/// `DEBUG` bit in TDX TD attributes.
+const TDX_ATTRIBUTE_DEBUG: u64 = 1;
+
+fn malformed(message: impl Into<String>) -> AttestationError {
+    AttestationError::MalformedQuote(message.into())
+}
+
+/// Cursor over little-endian quote data.
+#[derive(Debug)]
+struct QuoteReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> QuoteReader<'a> {
+    fn take(&mut self, len: usize, field: &str) -> Result<&'a [u8], AttestationError> {
+        if self.data.len() < len {
+            return Err(malformed(format!("quote is truncated at `{field}`")));
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn take_array<const N: usize>(&mut self, field: &str) -> Result<[u8; N], AttestationError> {
+        Ok(self.take(N, field)?.try_into().unwrap())
+    }
+
+    fn read_u16(&mut self, field: &str) -> Result<u16, AttestationError> {
+        Ok(u16::from_le_bytes(self.take_array(field)?))
+    }
+
+    fn read_u32(&mut self, field: &str) -> Result<u32, AttestationError> {
+        Ok(u32::from_le_bytes(self.take_array(field)?))
+    }
+
+    fn read_sized(&mut self, field: &str) -> Result<&'a [u8], AttestationError> {
+        let len = self.read_u32(field)? as usize;
+        self.take(len, field)
+    }
+}
+
+/// Measurements of the attested enclave / trust domain.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Measurements {
+    Sgx {
+        mr_enclave: [u8; 32],
+        mr_signer: [u8; 32],
+        debug: bool,
+    },
+    Tdx {
+        mr_td: [u8; 48],
+        /// SVNs of the TDX TCB components.
+        tee_tcb_svn: [u8; 16],
+        debug: bool,
+    },
+}
+
+impl Measurements {
+    pub fn tee_type(&self) -> TeeType {
+        match self {
+            Self::Sgx { .. } => TeeType::Sgx,
+            Self::Tdx { .. } => TeeType::Tdx,
+        }
+    }
+
+    pub fn is_debug(&self) -> bool {
+        match self {
+            Self::Sgx { debug, .. } | Self::Tdx { debug, .. } => *debug,
+        }
+    }
+}
+
+/// Parsed SGX report body of the quoting enclave.
+#[derive(Debug, Clone)]
+pub(super) struct QeReport<'a> {
+    /// Raw report body; signed by the PCK key.
+    pub raw: &'a [u8],
+    pub misc_select: [u8; 4],
+    pub attributes: [u8; 16],
+    pub mr_signer: [u8; 32],
+    pub isv_prod_id: u16,
+    pub isv_svn: u16,
+    pub report_data: &'a [u8],
+}
+
+/// Parsed DCAP quote. Fields borrow from the raw quote bytes.
+#[derive(Debug, Clone)]
+pub(super) struct Quote<'a> {
+    /// Header and report body; signed by the attestation key.
+    pub signed_data: &'a [u8],
+    pub measurements: Measurements,
+    pub report_data: &'a [u8],
+    pub signature: &'a [u8],
+    /// Raw public attestation key (`x || y` coordinates).
+    pub attestation_key: &'a [u8],
+    pub qe_report: QeReport<'a>,
+    pub qe_report_signature: &'a [u8],
+    pub qe_auth_data: &'a [u8],
+    /// PEM-encoded PCK certificate chain.
+    pub pck_chain: &'a [u8],
+}
+
+impl<'a> Quote<'a> {
+    pub fn parse(raw: &'a [u8]) -> Result<Self, AttestationError> {
+        let mut reader = QuoteReader { data: raw };
+        let version = reader.read_u16("version")?;
+        let attestation_key_type = reader.read_u16("attestation_key_type")?;
+        let tee_type = reader.read_u32("tee_type")?;
+        reader.take(HEADER_LEN - 8, "header")?;
+
+        if attestation_key_type != ATTESTATION_KEY_TYPE_ECDSA_P256 {
+            return Err(AttestationError::UnsupportedQuote(format!(
+                "attestation key type {attestation_key_type}"
+            )));
+        }
+        let (measurements, report_data) = match (version, tee_type) {
+            (3 | 4, TEE_TYPE_SGX) => {
+                parse_sgx_report_body(reader.take(SGX_REPORT_BODY_LEN, "report_body")?)
+            }
+            (4, TEE_TYPE_TDX) => {
+                parse_tdx_report_body(reader.take(TDX_REPORT_BODY_LEN, "report_body")?)
+            }
+            _ => {
+                return Err(AttestationError::UnsupportedQuote(format!(
+                    "version {version} with TEE type {tee_type:#x}"
+                )));
+            }
+        };
+        let signed_data = &raw[..raw.len() - reader.data.len()];
+
+        let mut signature_data = QuoteReader {
+            data: reader.read_sized("signature_data")?,
+        };
+        let signature = signature_data.take(ECDSA_SIGNATURE_LEN, "signature")?;
+        let attestation_key = signature_data.take(ECDSA_PUBLIC_KEY_LEN, "attestation_key")?;
+
+        let (qe_report, qe_report_signature, qe_auth_data, pck_chain) = if version == 3 {
+            parse_qe_certification_data(&mut signature_data)?
+        } else {
+            let cert_data_type = signature_data.read_u16("certification_data_type")?;
+            if cert_data_type != CERT_DATA_TYPE_QE_REPORT {
+                return Err(AttestationError::UnsupportedQuote(format!(
+                    "certification data type {cert_data_type}"
+                )));
+            }
+            let mut qe_cert_data = QuoteReader {
+                data: signature_data.read_sized("certification_data")?,
+            };
+            parse_qe_certification_data(&mut qe_cert_data)?
+        };
+
+        Ok(Self {
+            signed_data,
+            measurements,
+            report_data,
+            signature,
+            attestation_key,
+            qe_report,
+            qe_report_signature,
+            qe_auth_data,
+            pck_chain,
+        })
+    }
+}
+
+type QeCertificationData<'a> = (QeReport<'a>, &'a [u8], &'a [u8], &'a [u8]);
+
+fn parse_qe_certification_data<'a>(
+    reader: &mut QuoteReader<'a>,
+) -> Result<QeCertificationData<'a>, AttestationError> {
+    let qe_report = reader.take(SGX_REPORT_BODY_LEN, "qe_report")?;
+    let qe_report_signature = reader.take(ECDSA_SIGNATURE_LEN, "qe_report_signature")?;
+    let qe_auth_data_len = reader.read_u16("qe_auth_data")?;
+    let qe_auth_data = reader.take(qe_auth_data_len.into(), "qe_auth_data")?;
+
+    let cert_data_type = reader.read_u16("certification_data_type")?;
+    if cert_data_type != CERT_DATA_TYPE_PCK_CHAIN {
+        return Err(AttestationError::UnsupportedQuote(format!(
+            "certification data type {cert_data_type}"
+        )));
+    }
+    let pck_chain = reader.read_sized("certification_data")?;
+
+    let (_, report_data) = parse_sgx_report_body(qe_report);
+    let qe_report = QeReport {
+        raw: qe_report,
+        misc_select: qe_report[16..20].try_into().unwrap(),
+        attributes: qe_report[48..64].try_into().unwrap(),
+        mr_signer: qe_report[128..160].try_into().unwrap(),
+        isv_prod_id: u16::from_le_bytes(qe_report[256..258].try_into().unwrap()),
+        isv_svn: u16::from_le_bytes(qe_report[258..260].try_into().unwrap()),
+        report_data,
+    };
+    Ok((qe_report, qe_report_signature, qe_auth_data, pck_chain))
+}
+
+/// Parses an SGX report body. The caller must ensure that `body` has the correct length.
+fn parse_sgx_report_body(body: &[u8]) -> (Measurements, &[u8]) {
+    let attributes = u64::from_le_bytes(body[48..56].try_into().unwrap());
+    let measurements = Measurements::Sgx {
+        mr_enclave: body[64..96].try_into().unwrap(),
+        mr_signer: body[128..160].try_into().unwrap(),
+        debug: attributes & SGX_ATTRIBUTE_DEBUG != 0,
+    };
+    (measurements, &body[320..384])
+}
+
+/// Parses a TDX 1.0 report body. The caller must ensure that `body` has the correct length.
+fn parse_tdx_report_body(body: &[u8]) -> (Measurements, &[u8]) {
+    let td_attributes = u64::from_le_bytes(body[120..128].try_into().unwrap());
+    let measurements = Measurements::Tdx {
+        mr_td: body[136..184].try_into().unwrap(),
+        tee_tcb_svn: body[..16].try_into().unwrap(),
+        debug: td_attributes & TDX_ATTRIBUTE_DEBUG != 0,
+    };
+    (measurements, &body[520..584])
+}
@@ -0,0 +1,252 @@
+//! Tests for attestation verification. Test collateral (root CA, PCK certificate chain, CRLs, TCB info,
+//! QE identity and quotes) is synthetic; the quotes are bound to the public key of the secp256k1 secret key
+//! `[0x42; 32]`.
+
+use assert_matches::assert_matches;
+use chrono::TimeZone;
+
+use super::*;
+
+const ROOT_CA_PEM: &[u8] = include_bytes!("testdata/root_ca.pem");
+const COLLATERAL: &[u8] = include_bytes!("testdata/collateral.json");
+/// Same as `COLLATERAL`, but the platform matches only an `OutOfDate` TCB level.
+const OUTDATED_COLLATERAL: &[u8] = include_bytes!("testdata/outdated_collateral.json");
+/// Same as `COLLATERAL`, but the PCK certificate from quotes is revoked.
+const REVOKED_COLLATERAL: &[u8] = include_bytes!("testdata/revoked_collateral.json");
+const SGX_QUOTE: &[u8] = include_bytes!("testdata/sgx_quote.bin");
+const SGX_DEBUG_QUOTE: &[u8] = include_bytes!("testdata/sgx_debug_quote.bin");
+const TDX_QUOTE: &[u8] = include_bytes!("testdata/tdx_quote.bin");
+/// SGX quote with the PCK certificate issued by a non-CA certificate.
+const SGX_QUOTE_WITH_NON_CA_ISSUER: &[u8] = include_bytes!("testdata/sgx_quote_non_ca_issuer.bin");
+
+const SGX_MRENCLAVE: H256 = H256::repeat_byte(0xaa);
+const SGX_MRSIGNER: H256 = H256::repeat_byte(0xbb);
+
+fn pubkey() -> Vec<u8> {
+    hex::decode("0324653eac434488002cc06bbfb7f10fe18991e35f9fe4302dbea6d2353dc0ab1c").unwrap()
+}
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+}
+
+fn config() -> TeeConfig {
+    TeeConfig {
+        tee_verify_attestations: true,
+        tee_sgx_mrenclave_allowlist: vec![SGX_MRENCLAVE],
+        tee_tdx_mrtd_allowlist: vec!["cc".repeat(48)],
+        ..TeeConfig::default()
+    }
+}
+
+fn verifier(config: &TeeConfig) -> AttestationVerifier {
+    AttestationVerifier::from_raw(ROOT_CA_PEM, COLLATERAL, config).unwrap()
+}
+
+#[test]
+fn verifying_sgx_quote() {
+    let tee_type = verifier(&config())
+        .verify(SGX_QUOTE, &pubkey(), now())
+        .unwrap();
+    assert_eq!(tee_type, TeeType::Sgx);
+
+    let config = TeeConfig {
+        tee_sgx_mrenclave_allowlist: vec![],
+        tee_sgx_mrsigner_allowlist: vec![SGX_MRSIGNER],
+        ..config()
+    };
+    let tee_type = verifier(&config)
+        .verify(SGX_QUOTE, &pubkey(), now())
+        .unwrap();
+    assert_eq!(tee_type, TeeType::Sgx);
+}
+
+#[test]
+fn verifying_tdx_quote() {
+    let tee_type = verifier(&config())
+        .verify(TDX_QUOTE, &pubkey(), now())
+        .unwrap();
+    assert_eq!(tee_type, TeeType::Tdx);
+}
+
+#[test]
+fn quote_with_disallowed_measurements_is_rejected() {
+    let config = TeeConfig {
+        tee_sgx_mrenclave_allowlist: vec![H256::repeat_byte(1)],
+        tee_tdx_mrtd_allowlist: vec![],
+        ..config()
+    };
+    let verifier = verifier(&config);
+    let err = verifier.verify(SGX_QUOTE, &pubkey(), now()).unwrap_err();
+    assert_matches!(err, AttestationError::MeasurementsNotAllowed(TeeType::Sgx));
+    let err = verifier.verify(TDX_QUOTE, &pubkey(), now()).unwrap_err();
+    assert_matches!(err, AttestationError::MeasurementsNotAllowed(TeeType::Tdx));
+}
+
+#[test]
+fn debug_enclave_is_rejected() {
+    let err = verifier(&config())
+        .verify(SGX_DEBUG_QUOTE, &pubkey(), now())
+        .unwrap_err();
+    assert_matches!(err, AttestationError::DebugMode(TeeType::Sgx));
+}
+
+#[test]
+fn quote_bound_to_other_pubkey_is_rejected() {
+    let mut other_pubkey = pubkey();
+    other_pubkey[1] ^= 1;
+    let verifier = verifier(&config());
+    let err = verifier
+        .verify(SGX_QUOTE, &other_pubkey, now())
+        .unwrap_err();
+    assert_matches!(err, AttestationError::PubkeyMismatch);
+    let err = verifier
+        .verify(SGX_QUOTE, &pubkey()[..32], now())
+        .unwrap_err();
+    assert_matches!(err, AttestationError::PubkeyMismatch);
+}
+
+#[test]
+fn tampered_quote_is_rejected() {
+    let verifier = verifier(&config());
+
+    // Change MRENCLAVE, which is covered by the quote signature.
+    let mut quote = SGX_QUOTE.to_vec();
+    quote[48 + 64] ^= 1;
+    let err = verifier.verify(&quote, &pubkey(), now()).unwrap_err();
+    assert_matches!(err, AttestationError::InvalidSignature("quote"));
+
+    // Change the attestation key, which the QE report commits to.
+    let mut quote = SGX_QUOTE.to_vec();
+    quote[48 + 384 + 4 + 64] ^= 1;
+    let err = verifier.verify(&quote, &pubkey(), now()).unwrap_err();
+    assert_matches!(err, AttestationError::AttestationKeyMismatch);
+
+    let err = verifier
+        .verify(&SGX_QUOTE[..SGX_QUOTE.len() - 100], &pubkey(), now())
+        .unwrap_err();
+    assert_matches!(err, AttestationError::MalformedQuote(_));
+}
+
+#[test]
+fn quote_with_untrusted_or_expired_chain_is_rejected() {
+    let err = verifier(&config())
+        .verify(
+            SGX_QUOTE,
+            &pubkey(),
+            Utc.with_ymd_and_hms(2060, 1, 1, 0, 0, 0).unwrap(),
+        )
+        .unwrap_err();
+    assert_matches!(err, AttestationError::CertificateChain(_));
+
+    // Use the PCK certificate from the quote as the trusted root.
+    let quote = Quote::parse(SGX_QUOTE).unwrap();
+    let pck_chain = x509::parse_pem_chain(quote.pck_chain).unwrap();
+    let other_root = pem::encode(&pem::Pem::new("CERTIFICATE", pck_chain[0].der()));
+    let verifier =
+        AttestationVerifier::from_raw(other_root.as_bytes(), COLLATERAL, &config()).unwrap();
+    let err = verifier.verify(SGX_QUOTE, &pubkey(), now()).unwrap_err();
+    assert_matches!(err, AttestationError::CertificateChain(_));
+}
+
+#[test]
+fn quote_with_non_ca_issuer_is_rejected() {
+    let err = verifier(&config())
+        .verify(SGX_QUOTE_WITH_NON_CA_ISSUER, &pubkey(), now())
+        .unwrap_err();
+    assert_matches!(err, AttestationError::CertificateChain(msg) if msg.contains("not a CA"));
+}
+
+#[test]
+fn quote_with_revoked_pck_certificate_is_rejected() {
+    let verifier =
+        AttestationVerifier::from_raw(ROOT_CA_PEM, REVOKED_COLLATERAL, &config()).unwrap();
+    let err = verifier.verify(SGX_QUOTE, &pubkey(), now()).unwrap_err();
+    assert_matches!(err, AttestationError::CertificateChain(msg) if msg.contains("revoked"));
+}
+
+#[test]
+fn quote_with_outdated_tcb_is_rejected() {
+    let verifier =
+        AttestationVerifier::from_raw(ROOT_CA_PEM, OUTDATED_COLLATERAL, &config()).unwrap();
+    for quote in [SGX_QUOTE, TDX_QUOTE] {
+        let err = verifier.verify(quote, &pubkey(), now()).unwrap_err();
+        assert_matches!(
+            err,
+            AttestationError::TcbStatusNotAllowed { component: "platform", status }
+                if status == "OutOfDate"
+        );
+    }
+
+    let config = TeeConfig {
+        tee_attestation_allowed_tcb_statuses: vec!["OutOfDate".to_owned()],
+        ..config()
+    };
+    let verifier =
+        AttestationVerifier::from_raw(ROOT_CA_PEM, OUTDATED_COLLATERAL, &config).unwrap();
+    let tee_type = verifier.verify(SGX_QUOTE, &pubkey(), now()).unwrap();
+    assert_eq!(tee_type, TeeType::Sgx);
+}
+
+#[test]
+fn quote_is_rejected_with_expired_or_tampered_collateral() {
+    // Certificates are valid until the end of 2049, while collateral expires at 2049-12-31T23:59:59.
+    let err = verifier(&config())
+        .verify(
+            SGX_QUOTE,
+            &pubkey(),
+            Utc.with_ymd_and_hms(2050, 1, 1, 0, 0, 0).unwrap(),
+        )
+        .unwrap_err();
+    assert_matches!(err, AttestationError::CertificateChain(_));
+
+    let collateral = std::str::from_utf8(COLLATERAL).unwrap();
+    let tampered_collateral = collateral.replace(r#"\"pcesvn\":11"#, r#"\"pcesvn\":1"#);
+    assert_ne!(tampered_collateral, collateral);
+    let verifier =
+        AttestationVerifier::from_raw(ROOT_CA_PEM, tampered_collateral.as_bytes(), &config())
+            .unwrap();
+    let err = verifier.verify(SGX_QUOTE, &pubkey(), now()).unwrap_err();
+    assert_matches!(err, AttestationError::Collateral(msg) if msg.contains("invalid signature"));
+}
+
+#[test]
+fn quote_from_unknown_quoting_enclave_is_rejected() {
+    let collateral = std::str::from_utf8(COLLATERAL).unwrap();
+    let other_collateral = collateral.replace(r#"\"id\":\"QE\""#, r#"\"id\":\"QE2\""#);
+    assert_ne!(other_collateral, collateral);
+    let verifier =
+        AttestationVerifier::from_raw(ROOT_CA_PEM, other_collateral.as_bytes(), &config()).unwrap();
+    let err = verifier.verify(SGX_QUOTE, &pubkey(), now()).unwrap_err();
+    assert_matches!(err, AttestationError::Collateral(msg) if msg.contains("no QE identity"));
+}
+
+#[test]
+fn certificate_with_malformed_time_is_rejected() {
+    let quote = Quote::parse(SGX_QUOTE).unwrap();
+    let pck_chain = x509::parse_pem_chain(quote.pck_chain).unwrap();
+    let mut der = pck_chain[0].der().to_vec();
+    let not_before = b"\x17\x0d240101000000Z";
+    let pos = der
+        .windows(not_before.len())
+        .position(|window| window == not_before)
+        .unwrap();
+    // Replace some digits with a 2-byte UTF-8 char keeping the value length.
+    der[pos + 11..pos + 14].copy_from_slice("\u{e9}Z".as_bytes());
+
+    let err = x509::Certificate::from_der(&der).unwrap_err();
+    assert_matches!(err, AttestationError::CertificateChain(msg) if msg.contains("malformed"));
+}
+
+#[test]
+fn invalid_mrtd_allowlist_is_rejected() {
+    let config = TeeConfig {
+        tee_tdx_mrtd_allowlist: vec!["cc".repeat(32)],
+        ..config()
+    };
+    let err = AttestationVerifier::from_raw(ROOT_CA_PEM, COLLATERAL, &config).unwrap_err();
+    assert!(
+        format!("{err:#}").contains("tee_tdx_mrtd_allowlist"),
+        "{err:#}"
+    );
+}
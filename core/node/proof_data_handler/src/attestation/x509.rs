@@ -0,0 +1,251 @@
+//! X.509 support required to verify PCK certificate chains embedded into DCAP quotes against the certificate
+//! revocation lists (CRLs) from attestation collateral. Certificates are parsed with `x509-cert`; chains
+//! and their revocation status are verified with `webpki`.
+//!
+//! Only certificates and CRLs signed with `ecdsa-with-SHA256` and certificates carrying P-256 public keys
+//! are supported, which is the case for all certificates and CRLs in Intel's PCK certificate hierarchy.
+
+use std::{fmt, time::Duration};
+
+use chrono::{DateTime, Utc};
+use p256::ecdsa::VerifyingKey;
+use rustls_pki_types::{CertificateDer, UnixTime};
+use webpki::{
+    CertRevocationList, EndEntityCert, ExpirationPolicy, KeyUsage, OwnedCertRevocationList,
+    RevocationCheckDepth, RevocationOptionsBuilder, UnknownStatusPolicy,
+};
+use x509_cert::{
+    attr::AttributeTypeAndValue,
+    der::{
+        asn1::{Any, ObjectIdentifier, OctetString},
+        Decode, DecodeOwned, Encode,
+    },
+};
+
+use super::AttestationError;
+
+/// Intel SGX extensions OID (1.2.840.113741.1.13.1) present in PCK certificates.
+const SGX_EXTENSIONS_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1");
+const SGX_TCB_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.2");
+const SGX_PCE_ID_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.3");
+const SGX_FMSPC_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.4");
+/// Last arc of the PCESVN OID in the SGX TCB extension; arcs 1..=16 correspond to the TCB component SVNs.
+const SGX_TCB_PCESVN_ARC: u32 = 17;
+
+fn malformed(err: impl fmt::Display) -> AttestationError {
+    AttestationError::CertificateChain(format!("malformed certificate: {err}"))
+}
+
+fn verification_error(err: webpki::Error) -> AttestationError {
+    let message = match err {
+        webpki::Error::CertRevoked => "certificate is revoked".to_owned(),
+        webpki::Error::EndEntityUsedAsCa => "issuer certificate is not a CA certificate".to_owned(),
+        webpki::Error::UnknownIssuer => {
+            "certificate chain is not issued by the trusted root".to_owned()
+        }
+        err => err.to_string(),
+    };
+    AttestationError::CertificateChain(message)
+}
+
+/// Decodes a value of an SGX extension entry.
+fn decode_value<T: DecodeOwned>(value: &Any) -> Result<T, AttestationError> {
+    value
+        .to_der()
+        .and_then(|der| T::from_der(&der))
+        .map_err(malformed)
+}
+
+fn decode_octet_string_array<const N: usize>(value: &Any) -> Result<[u8; N], AttestationError> {
+    decode_value::<OctetString>(value)?
+        .as_bytes()
+        .try_into()
+        .map_err(|_| malformed("unexpected octet string length"))
+}
+
+/// Platform data from the SGX extensions of a PCK certificate.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SgxExtensions {
+    pub fmspc: [u8; 6],
+    pub pce_id: [u8; 2],
+    /// SVNs of the 16 SGX TCB components.
+    pub tcb_components: [u8; 16],
+    pub pce_svn: u16,
+}
+
+impl SgxExtensions {
+    /// Parses SGX extensions. Entries of the extension (including nested TCB entries) are `SEQUENCE { OID, ANY }`,
+    /// i.e. have the same structure as X.501 attributes.
+    fn parse(value: &[u8]) -> Result<Self, AttestationError> {
+        let (mut fmspc, mut pce_id, mut tcb) = (None, None, None);
+        for entry in Vec::<AttributeTypeAndValue>::from_der(value).map_err(malformed)? {
+            if entry.oid == SGX_TCB_OID {
+                tcb = Some(Self::parse_tcb(&entry.value)?);
+            } else if entry.oid == SGX_PCE_ID_OID {
+                pce_id = Some(decode_octet_string_array(&entry.value)?);
+            } else if entry.oid == SGX_FMSPC_OID {
+                fmspc = Some(decode_octet_string_array(&entry.value)?);
+            }
+            // Other entries (e.g., PPID) are not used.
+        }
+
+        let missing = || malformed("incomplete SGX extensions");
+        let (tcb_components, pce_svn) = tcb.ok_or_else(missing)?;
+        Ok(Self {
+            fmspc: fmspc.ok_or_else(missing)?,
+            pce_id: pce_id.ok_or_else(missing)?,
+            tcb_components,
+            pce_svn,
+        })
+    }
+
+    fn parse_tcb(value: &Any) -> Result<([u8; 16], u16), AttestationError> {
+        let mut components = [0_u8; 16];
+        let mut parsed_components = 0_u16;
+        let mut pce_svn = None;
+        for entry in decode_value::<Vec<AttributeTypeAndValue>>(value)? {
+            if entry.oid.parent() != Some(SGX_TCB_OID) {
+                continue;
+            }
+            match entry.oid.arcs().last() {
+                Some(arc @ 1..=16) => {
+                    let idx = arc as usize - 1;
+                    components[idx] = decode_value(&entry.value)?;
+                    parsed_components |= 1 << idx;
+                }
+                Some(SGX_TCB_PCESVN_ARC) => pce_svn = Some(decode_value(&entry.value)?),
+                _ => { /* CPUSVN duplicates the component SVNs */ }
+            }
+        }
+
+        match pce_svn {
+            Some(pce_svn) if parsed_components == u16::MAX => Ok((components, pce_svn)),
+            _ => Err(malformed("incomplete SGX TCB extension")),
+        }
+    }
+}
+
+/// Parsed X.509 certificate.
+#[derive(Debug, Clone)]
+pub(super) struct Certificate {
+    der: Vec<u8>,
+    public_key: VerifyingKey,
+    sgx_extensions: Option<SgxExtensions>,
+}
+
+impl Certificate {
+    pub fn from_der(der: &[u8]) -> Result<Self, AttestationError> {
+        let certificate = x509_cert::Certificate::from_der(der).map_err(malformed)?;
+        let tbs = &certificate.tbs_certificate;
+        let public_key = VerifyingKey::from_sec1_bytes(
+            tbs.subject_public_key_info.subject_public_key.raw_bytes(),
+        )
+        .map_err(|_| malformed("public key is not a valid P-256 point"))?;
+        let sgx_extensions = tbs
+            .extensions
+            .iter()
+            .flatten()
+            .find(|extension| extension.extn_id == SGX_EXTENSIONS_OID)
+            .map(|extension| SgxExtensions::parse(extension.extn_value.as_bytes()))
+            .transpose()?;
+
+        Ok(Self {
+            der: der.to_vec(),
+            public_key,
+            sgx_extensions,
+        })
+    }
+
+    pub fn der(&self) -> &[u8] {
+        &self.der
+    }
+
+    pub fn public_key(&self) -> &VerifyingKey {
+        &self.public_key
+    }
+
+    /// Returns platform data from SGX extensions, which are present in PCK certificates.
+    pub fn sgx_extensions(&self) -> Option<&SgxExtensions> {
+        self.sgx_extensions.as_ref()
+    }
+}
+
+/// Parses a PEM-encoded certificate chain. Trailing NUL bytes (which are present in certification data
+/// of some quotes) are ignored.
+pub(super) fn parse_pem_chain(pem_data: &[u8]) -> Result<Vec<Certificate>, AttestationError> {
+    let end = pem_data
+        .iter()
+        .rposition(|&byte| byte != 0)
+        .map_or(0, |pos| pos + 1);
+    let pems = pem::parse_many(&pem_data[..end])
+        .map_err(|err| AttestationError::CertificateChain(format!("invalid PEM data: {err}")))?;
+    pems.iter()
+        .filter(|pem| pem.tag() == "CERTIFICATE")
+        .map(|pem| Certificate::from_der(pem.contents()))
+        .collect()
+}
+
+/// Verifies a certificate chain (ordered from the leaf certificate to the root) against the trusted root
+/// certificate. The chain may either include the trusted root as its last element, or end with a certificate
+/// issued by it. All certificates in the chain except for the trusted root must be covered by one of `crls`.
+pub(super) fn verify_chain(
+    chain: &[Certificate],
+    trusted_root: &Certificate,
+    crls: &[CertRevocationList<'_>],
+    now: DateTime<Utc>,
+) -> Result<(), AttestationError> {
+    let Some((leaf, intermediates)) = chain.split_first() else {
+        return Err(AttestationError::CertificateChain(
+            "certificate chain is empty".into(),
+        ));
+    };
+    let leaf = CertificateDer::from(leaf.der());
+    let leaf = EndEntityCert::try_from(&leaf).map_err(verification_error)?;
+    let intermediates: Vec<_> = intermediates
+        .iter()
+        .map(|certificate| CertificateDer::from(certificate.der()))
+        .collect();
+    let trusted_root = CertificateDer::from(trusted_root.der());
+    let trust_anchor =
+        webpki::anchor_from_trusted_cert(&trusted_root).map_err(verification_error)?;
+
+    let crls: Vec<_> = crls.iter().collect();
+    let revocation = RevocationOptionsBuilder::new(&crls)
+        .map_err(|_| AttestationError::CertificateChain("no CRLs provided".into()))?
+        .with_depth(RevocationCheckDepth::Chain)
+        .with_status_policy(UnknownStatusPolicy::Deny)
+        .with_expiration_policy(ExpirationPolicy::Enforce)
+        .build();
+    let now = UnixTime::since_unix_epoch(Duration::from_secs(
+        u64::try_from(now.timestamp()).unwrap_or(0),
+    ));
+
+    // Certificates in Intel's PCK hierarchy don't restrict extended key usage; `client_auth()` only checks
+    // the usage if the extension is present.
+    leaf.verify_for_usage(
+        &[webpki::ring::ECDSA_P256_SHA256],
+        &[trust_anchor],
+        &intermediates,
+        now,
+        KeyUsage::client_auth(),
+        Some(revocation),
+        None,
+    )
+    .map_err(verification_error)?;
+    Ok(())
+}
+
+/// Parses a CRL, which may be either PEM- or DER-encoded.
+pub(super) fn parse_crl(data: &[u8]) -> Result<CertRevocationList<'static>, AttestationError> {
+    let crl = if data.starts_with(b"-----BEGIN") {
+        let pem = pem::parse(data).map_err(|err| {
+            AttestationError::CertificateChain(format!("invalid PEM data: {err}"))
+        })?;
+        OwnedCertRevocationList::from_der(pem.contents())
+    } else {
+        OwnedCertRevocationList::from_der(data)
+    };
+    let crl =
+        crl.map_err(|err| AttestationError::CertificateChain(format!("malformed CRL: {err}")))?;
+    Ok(crl.into())
+}
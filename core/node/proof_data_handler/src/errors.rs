@@ -5,10 +5,15 @@ use axum::{
 use zksync_dal::DalError;
 use zksync_object_store::ObjectStoreError;
 
+use crate::attestation::AttestationError;
+
 pub(crate) enum RequestProcessorError {
     GeneralError(String),
     ObjectStore(ObjectStoreError),
     Dal(DalError),
+    Attestation(AttestationError),
+    /// TEE proof is not signed by a key bound to a verified attestation.
+    UnattestedTeeProof(String),
 }
 
 impl From<DalError> for RequestProcessorError {
@@ -41,6 +46,17 @@ impl IntoResponse for RequestProcessorError {
                     "Failed fetching/saving from db".to_owned(),
                 )
             }
+            Self::Attestation(err) => {
+                tracing::warn!("Rejected TEE attestation: {err}");
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Attestation verification failed: {err}"),
+                )
+            }
+            Self::UnattestedTeeProof(err) => {
+                tracing::warn!("Rejected TEE proof: {err}");
+                (StatusCode::FORBIDDEN, format!("TEE proof rejected: {err}"))
+            }
         };
         (status_code, message).into_response()
     }
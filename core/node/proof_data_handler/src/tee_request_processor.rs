@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
+use anyhow::Context as _;
 use axum::{extract::Path, Json};
 use chrono::{Duration as ChronoDuration, Utc};
+use secp256k1::{ecdsa::Signature, Message, PublicKey, SECP256K1};
 use zksync_config::configs::ProofDataHandlerConfig;
 use zksync_dal::{
     tee_proof_generation_dal::{LockedBatch, TeeProofGenerationJobStatus},
@@ -20,7 +22,7 @@ use zksync_prover_interface::{
 use zksync_types::{tee_types::TeeType, L1BatchNumber, L2ChainId};
 use zksync_vm_executor::storage::L1BatchParamsProvider;
 
-use crate::{errors::RequestProcessorError, metrics::METRICS};
+use crate::{attestation::AttestationVerifier, errors::RequestProcessorError, metrics::METRICS};
 
 #[derive(Clone)]
 pub(crate) struct TeeRequestProcessor {
@@ -28,6 +30,8 @@ pub(crate) struct TeeRequestProcessor {
     pool: ConnectionPool<Core>,
     config: ProofDataHandlerConfig,
     l2_chain_id: L2ChainId,
+    /// Set if attestation verification is enabled in the config.
+    attestation_verifier: Option<Arc<AttestationVerifier>>,
 }
 
 impl TeeRequestProcessor {
@@ -36,13 +40,22 @@ impl TeeRequestProcessor {
         pool: ConnectionPool<Core>,
         config: ProofDataHandlerConfig,
         l2_chain_id: L2ChainId,
-    ) -> Self {
-        Self {
+    ) -> anyhow::Result<Self> {
+        let attestation_verifier = if config.tee_config.tee_verify_attestations {
+            let verifier = AttestationVerifier::new(&config.tee_config)
+                .context("failed initializing TEE attestation verifier")?;
+            Some(Arc::new(verifier))
+        } else {
+            None
+        };
+
+        Ok(Self {
             blob_store,
             pool,
             config,
             l2_chain_id,
-        }
+            attestation_verifier,
+        })
     }
 
     pub(crate) async fn get_proof_generation_data(
@@ -212,6 +225,20 @@ impl TeeRequestProcessor {
         let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
         let mut dal = connection.tee_proof_generation_dal();
 
+        if self.attestation_verifier.is_some() {
+            let is_attested = dal
+                .is_attestation_verified(&proof.0.pubkey, proof.0.tee_type)
+                .await?;
+            if !is_attested {
+                return Err(RequestProcessorError::UnattestedTeeProof(format!(
+                    "public key is not bound to a verified {} attestation",
+                    proof.0.tee_type
+                )));
+            }
+            verify_proof_signature(&proof.0.pubkey, &proof.0.signature, &proof.0.proof)
+                .map_err(|err| RequestProcessorError::UnattestedTeeProof(format!("{err:#}")))?;
+        }
+
         dal.save_proof_artifacts_metadata(
             l1_batch_number,
             proof.0.tee_type,
@@ -254,9 +281,32 @@ impl TeeRequestProcessor {
         let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
         let mut dal = connection.tee_proof_generation_dal();
 
-        dal.save_attestation(&payload.pubkey, &payload.attestation)
-            .await?;
+        if let Some(verifier) = &self.attestation_verifier {
+            let tee_type = verifier
+                .verify(&payload.attestation, &payload.pubkey, Utc::now())
+                .map_err(RequestProcessorError::Attestation)?;
+            tracing::info!(
+                "Verified {tee_type} attestation for public key {:?}",
+                payload.pubkey
+            );
+            dal.save_verified_attestation(&payload.pubkey, &payload.attestation, tee_type)
+                .await?;
+        } else {
+            dal.save_attestation(&payload.pubkey, &payload.attestation)
+                .await?;
+        }
 
         Ok(Json(RegisterTeeAttestationResponse::Success))
     }
 }
+
+/// Checks that `signature` is a valid secp256k1 signature of the `proof` (i.e., the batch root hash) by `pubkey`,
+/// in the format produced by the TEE prover.
+fn verify_proof_signature(pubkey: &[u8], signature: &[u8], proof: &[u8]) -> anyhow::Result<()> {
+    let pubkey = PublicKey::from_slice(pubkey).context("invalid public key")?;
+    let signature = Signature::from_compact(signature).context("invalid signature encoding")?;
+    let message = Message::from_slice(proof).context("proof is not a 32-byte root hash")?;
+    SECP256K1
+        .verify_ecdsa(&message, &signature, &pubkey)
+        .context("invalid proof signature")
+}
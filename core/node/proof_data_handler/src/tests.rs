@@ -4,14 +4,18 @@ use axum::{
     response::Response,
     Router,
 };
+use secp256k1::{Message, SecretKey, SECP256K1};
 use serde_json::json;
 use tower::ServiceExt;
 use zksync_config::configs::{ProofDataHandlerConfig, TeeConfig};
 use zksync_dal::{ConnectionPool, CoreDal};
 use zksync_object_store::MockObjectStore;
-use zksync_prover_interface::api::SubmitTeeProofRequest;
+use zksync_prover_interface::{
+    api::{RegisterTeeAttestationRequest, SubmitTeeProofRequest},
+    outputs::L1BatchTeeProofForL1,
+};
 use zksync_types::{
-    commitment::L1BatchCommitmentMode, tee_types::TeeType, L1BatchNumber, L2ChainId,
+    commitment::L1BatchCommitmentMode, tee_types::TeeType, L1BatchNumber, L2ChainId, H256,
 };
 
 use crate::create_proof_processing_router;
@@ -31,11 +35,13 @@ async fn request_tee_proof_inputs() {
                 first_tee_processed_batch: L1BatchNumber(0),
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 10 * 24,
+                ..TeeConfig::default()
             },
         },
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
-    );
+    )
+    .unwrap();
     let test_cases = vec![
         (json!({ "tee_type": "sgx" }), StatusCode::NO_CONTENT),
         (
@@ -91,11 +97,13 @@ async fn submit_tee_proof() {
                 first_tee_processed_batch: L1BatchNumber(0),
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 10 * 24,
+                ..TeeConfig::default()
             },
         },
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
-    );
+    )
+    .unwrap();
 
     // this should fail because we haven't saved the attestation for the pubkey yet
 
@@ -149,6 +157,104 @@ async fn submit_tee_proof() {
     assert_eq!(proof.pubkey.as_ref().unwrap(), &tee_proof_request.0.pubkey);
 }
 
+// Test that with attestation verification enabled, only proofs signed by keys with verified attestations are accepted
+#[tokio::test]
+async fn submit_tee_proof_with_attestation_verification() {
+    let batch_number = L1BatchNumber::from(1);
+    let db_conn_pool = ConnectionPool::test_pool().await;
+
+    mock_tee_batch_status(db_conn_pool.clone(), batch_number).await;
+
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool.clone(),
+        ProofDataHandlerConfig {
+            http_port: 1337,
+            proof_generation_timeout_in_secs: 10,
+            tee_config: TeeConfig {
+                tee_support: true,
+                tee_verify_attestations: true,
+                tee_attestation_root_ca_path: Some(
+                    concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/src/attestation/testdata/root_ca.pem"
+                    )
+                    .to_owned(),
+                ),
+                tee_attestation_collateral_path: Some(
+                    concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/src/attestation/testdata/collateral.json"
+                    )
+                    .to_owned(),
+                ),
+                tee_sgx_mrenclave_allowlist: vec![H256::repeat_byte(0xaa)],
+                ..TeeConfig::default()
+            },
+        },
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+    )
+    .unwrap();
+
+    // The test quote is bound to the public key of this secret key.
+    let secret_key = SecretKey::from_slice(&[0x42; 32]).unwrap();
+    let pubkey = secret_key.public_key(SECP256K1).serialize().to_vec();
+    let root_hash = H256::repeat_byte(0x23);
+    let signature = SECP256K1
+        .sign_ecdsa(
+            &Message::from_slice(root_hash.as_bytes()).unwrap(),
+            &secret_key,
+        )
+        .serialize_compact();
+    let tee_proof_request = SubmitTeeProofRequest(Box::new(L1BatchTeeProofForL1 {
+        signature: signature.to_vec(),
+        pubkey: pubkey.clone(),
+        proof: root_hash.as_bytes().to_vec(),
+        tee_type: TeeType::Sgx,
+    }));
+    let uri = format!("/tee/submit_proofs/{}", batch_number.0);
+
+    // the key has no attestation yet
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // an attestation that fails verification is rejected and not saved
+    let quote = include_bytes!("attestation/testdata/sgx_debug_quote.bin").to_vec();
+    let response = send_register_attestation_request(&app, &quote, &pubkey).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let quote = include_bytes!("attestation/testdata/sgx_quote.bin").to_vec();
+    let response = send_register_attestation_request(&app, &quote, &pubkey).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // a proof with a signature not matching the attested key is rejected
+    let mut invalid_request = SubmitTeeProofRequest(tee_proof_request.0.clone());
+    invalid_request.0.proof = H256::repeat_byte(0x24).as_bytes().to_vec();
+    let response = send_submit_tee_proof_request(&app, &uri, &invalid_request).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // the key is attested for SGX, not TDX
+    let mut invalid_request = SubmitTeeProofRequest(tee_proof_request.0.clone());
+    invalid_request.0.tee_type = TeeType::Tdx;
+    let response = send_submit_tee_proof_request(&app, &uri, &invalid_request).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut proof_db_conn = db_conn_pool.connection().await.unwrap();
+    let proofs = proof_db_conn
+        .tee_proof_generation_dal()
+        .get_tee_proofs(batch_number, Some(TeeType::Sgx))
+        .await
+        .unwrap();
+    assert_eq!(proofs.len(), 1);
+    assert_eq!(proofs[0].attestation.as_ref().unwrap(), &quote);
+}
+
 // Mock SQL db with information about the status of the TEE proof generation
 async fn mock_tee_batch_status(
     db_conn_pool: ConnectionPool<zksync_dal::Core>,
@@ -197,3 +303,26 @@ async fn send_submit_tee_proof_request(
         .await
         .unwrap()
 }
+
+async fn send_register_attestation_request(
+    app: &Router,
+    attestation: &[u8],
+    pubkey: &[u8],
+) -> Response {
+    let request = RegisterTeeAttestationRequest {
+        attestation: attestation.to_vec(),
+        pubkey: pubkey.to_vec(),
+    };
+    let req_body = Body::from(serde_json::to_vec(&request).unwrap());
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/tee/register_attestation")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(req_body)
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
@@ -0,0 +1,342 @@
+//! Checks of settlement layer data against local data. All returned errors signal a divergence between
+//! the settlement layer and local data, except for [`UnsupportedSelector`].
+
+use anyhow::Context as _;
+use zksync_contracts::{POST_SHARED_BRIDGE_EXECUTE_FUNCTION, POST_SHARED_BRIDGE_PROVE_FUNCTION};
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_l1_contract_interface::{
+    i_executor::structures::{StoredBatchInfo, SUPPORTED_ENCODING_VERSION},
+    Tokenizable,
+};
+use zksync_types::{
+    commitment::L1BatchWithMetadata,
+    ethabi::{self, ParamType, Token},
+    web3::{self, keccak256},
+    L1BatchNumber, H256, U256,
+};
+
+/// Shift applied to the hash of batch commitments to get the proof public input; see `Executor.sol`.
+const PUBLIC_INPUT_SHIFT: usize = 32;
+
+/// Settlement transaction calls a function not supported by the auditor (e.g., introduced by a newer version
+/// of the ZKsync contracts). This doesn't signal a divergence; the auditor needs to be updated instead.
+#[derive(Debug, thiserror::Error)]
+#[error("unexpected function selector: {0:?}")]
+pub(crate) struct UnsupportedSelector(pub Vec<u8>);
+
+/// Local data for an audited L1 batch.
+#[derive(Debug)]
+pub(crate) struct LocalL1BatchData {
+    pub l1_batch: L1BatchWithMetadata,
+    /// Previous L1 batch; its commitment is used in the proof public input.
+    pub prev_l1_batch: L1BatchWithMetadata,
+}
+
+impl LocalL1BatchData {
+    /// Returns `Ok(None)` if the batch or the previous batch doesn't have metadata yet.
+    pub async fn load(
+        storage: &mut Connection<'_, Core>,
+        number: L1BatchNumber,
+    ) -> anyhow::Result<Option<Self>> {
+        let Some(l1_batch) = storage.blocks_dal().get_l1_batch_metadata(number).await? else {
+            return Ok(None);
+        };
+        let prev_number = number
+            .0
+            .checked_sub(1)
+            .context("genesis L1 batch is not settled")?;
+        let Some(prev_l1_batch) = storage
+            .blocks_dal()
+            .get_l1_batch_metadata(L1BatchNumber(prev_number))
+            .await?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            l1_batch,
+            prev_l1_batch,
+        }))
+    }
+
+    fn number(&self) -> L1BatchNumber {
+        self.l1_batch.header.number
+    }
+
+    fn stored_batch_info(&self) -> StoredBatchInfo {
+        StoredBatchInfo::from(&self.l1_batch)
+    }
+
+    pub fn stored_batch_hash(&self) -> H256 {
+        self.stored_batch_info().hash()
+    }
+
+    fn public_input(&self) -> U256 {
+        batch_proof_public_input(
+            self.prev_l1_batch.metadata.commitment,
+            self.l1_batch.metadata.commitment,
+        )
+    }
+}
+
+/// Computes the batch proof public input in the same way as `Executor.sol`.
+pub(crate) fn batch_proof_public_input(prev_commitment: H256, commitment: H256) -> U256 {
+    let hash = keccak256(&[prev_commitment.as_bytes(), commitment.as_bytes()].concat());
+    U256::from_big_endian(&hash) >> PUBLIC_INPUT_SHIFT
+}
+
+/// Parses logs emitted by the diamond proxy for the specified event, skipping logs that cannot be parsed.
+fn parse_logs<'a>(
+    event: &'a ethabi::Event,
+    logs: &'a [web3::Log],
+) -> impl Iterator<Item = ethabi::Log> + 'a {
+    logs.iter().filter_map(|log| {
+        event
+            .parse_log_whole(ethabi::RawLog {
+                topics: log.topics.clone(),
+                data: log.data.0.clone(),
+            })
+            .ok()
+    })
+}
+
+fn log_param<'a>(log: &'a ethabi::Log, name: &str) -> Option<&'a Token> {
+    log.params
+        .iter()
+        .find_map(|param| (param.name == name).then_some(&param.value))
+}
+
+fn log_uint_param(log: &ethabi::Log, name: &str) -> Option<U256> {
+    log_param(log, name)?.clone().into_uint()
+}
+
+fn log_hash_param(log: &ethabi::Log, name: &str) -> Option<H256> {
+    let bytes = log_param(log, name)?.clone().into_fixed_bytes()?;
+    (bytes.len() == 32).then(|| H256::from_slice(&bytes))
+}
+
+/// Checks that `logs` contain an event with the specified name for the batch with the local batch hash and commitment.
+/// This works for both `BlockCommit` and `BlockExecution` events.
+fn check_batch_event(
+    contract: &ethabi::Contract,
+    event_name: &str,
+    logs: &[web3::Log],
+    local: &LocalL1BatchData,
+) -> anyhow::Result<()> {
+    let event = contract
+        .event(event_name)
+        .with_context(|| format!("`{event_name}` event not found for ZKsync L1 contract"))?;
+    let batch_number = U256::from(local.number().0);
+    let log = parse_logs(event, logs)
+        .find(|log| log_uint_param(log, "batchNumber") == Some(batch_number))
+        .with_context(|| format!("no `{event_name}` event for the batch"))?;
+
+    let batch_hash = log_hash_param(&log, "batchHash");
+    let local_batch_hash = local.l1_batch.metadata.root_hash;
+    anyhow::ensure!(
+        batch_hash == Some(local_batch_hash),
+        "batch hash in `{event_name}` event differs from the local one: {batch_hash:?} vs {local_batch_hash:?}"
+    );
+    let commitment = log_hash_param(&log, "commitment");
+    let local_commitment = local.l1_batch.metadata.commitment;
+    anyhow::ensure!(
+        commitment == Some(local_commitment),
+        "commitment in `{event_name}` event differs from the local one: {commitment:?} vs {local_commitment:?}"
+    );
+    Ok(())
+}
+
+/// Checks the commit transaction given its logs.
+pub(crate) fn check_commit(
+    contract: &ethabi::Contract,
+    logs: &[web3::Log],
+    local: &LocalL1BatchData,
+) -> anyhow::Result<()> {
+    check_batch_event(contract, "BlockCommit", logs, local)
+}
+
+/// Checks the prove transaction given its calldata and logs.
+pub(crate) fn check_prove(
+    contract: &ethabi::Contract,
+    calldata: &[u8],
+    logs: &[web3::Log],
+    local: &LocalL1BatchData,
+) -> anyhow::Result<()> {
+    let event = contract
+        .event("BlocksVerification")
+        .context("`BlocksVerification` event not found for ZKsync L1 contract")?;
+    let batch_number = U256::from(local.number().0);
+    let is_verified = parse_logs(event, logs).any(|log| {
+        let prev = log_uint_param(&log, "previousLastVerifiedBatch");
+        let current = log_uint_param(&log, "currentLastVerifiedBatch");
+        matches!((prev, current), (Some(prev), Some(current)) if prev < batch_number && batch_number <= current)
+    });
+    anyhow::ensure!(
+        is_verified,
+        "no `BlocksVerification` event covering the batch"
+    );
+
+    let (prev_batch, batches) = decode_prove_calldata(contract, calldata)?;
+    let idx = find_batch(&batches, local.number())?;
+    let prev_batch = if idx == 0 {
+        &prev_batch
+    } else {
+        &batches[idx - 1]
+    };
+    let batch = &batches[idx];
+
+    let public_input = batch_proof_public_input(prev_batch.commitment, batch.commitment);
+    let local_public_input = local.public_input();
+    anyhow::ensure!(
+        public_input == local_public_input,
+        "proof public input differs from the local one: {public_input:#x} vs {local_public_input:#x}"
+    );
+    let local_batch = local.stored_batch_info();
+    anyhow::ensure!(
+        *batch == local_batch,
+        "proved batch info differs from the local one: {batch:?} vs {local_batch:?}"
+    );
+    Ok(())
+}
+
+/// Checks the execute transaction given its calldata and logs.
+pub(crate) fn check_execute(
+    contract: &ethabi::Contract,
+    calldata: &[u8],
+    logs: &[web3::Log],
+    local: &LocalL1BatchData,
+) -> anyhow::Result<()> {
+    check_batch_event(contract, "BlockExecution", logs, local)?;
+
+    let batches = decode_execute_calldata(contract, calldata)?;
+    let batch = &batches[find_batch(&batches, local.number())?];
+    let local_batch = local.stored_batch_info();
+    anyhow::ensure!(
+        batch.priority_operations_hash == local_batch.priority_operations_hash,
+        "priority operations hash differs from the local one: {:?} vs {:?}",
+        batch.priority_operations_hash,
+        local_batch.priority_operations_hash
+    );
+    anyhow::ensure!(
+        *batch == local_batch,
+        "executed batch info differs from the local one: {batch:?} vs {local_batch:?}"
+    );
+    Ok(())
+}
+
+fn find_batch(batches: &[StoredBatchInfo], number: L1BatchNumber) -> anyhow::Result<usize> {
+    batches
+        .iter()
+        .position(|batch| batch.batch_number == u64::from(number.0))
+        .with_context(|| {
+            let numbers: Vec<_> = batches.iter().map(|batch| batch.batch_number).collect();
+            format!("calldata doesn't contain the batch; it contains batches {numbers:?}")
+        })
+}
+
+fn split_selector(calldata: &[u8]) -> anyhow::Result<(&[u8], &[u8])> {
+    anyhow::ensure!(
+        calldata.len() >= 4,
+        "calldata is too short: {} bytes",
+        calldata.len()
+    );
+    Ok(calldata.split_at(4))
+}
+
+fn stored_batch_infos_schema() -> ParamType {
+    ParamType::Array(Box::new(StoredBatchInfo::schema()))
+}
+
+/// Decodes the `bytes` payload of post-gateway executor functions, which have `(chainId, processFrom, processTo, data)`
+/// signature. `data` is prefixed with the encoding version.
+fn decode_versioned_payload(data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let schema = [
+        ParamType::Uint(256),
+        ParamType::Uint(256),
+        ParamType::Uint(256),
+        ParamType::Bytes,
+    ];
+    let tokens = ethabi::decode(&schema, data).context("failed decoding calldata")?;
+    let payload = tokens
+        .into_iter()
+        .nth(3)
+        .and_then(Token::into_bytes)
+        .context("missing payload")?;
+    let (&version, encoded) = payload.split_first().context("empty payload")?;
+    anyhow::ensure!(
+        version == SUPPORTED_ENCODING_VERSION,
+        "unsupported payload encoding version: {version}"
+    );
+    Ok(encoded.to_vec())
+}
+
+fn decode_stored_batch_infos(token: Token) -> anyhow::Result<Vec<StoredBatchInfo>> {
+    token
+        .into_array()
+        .context("batches are not an array")?
+        .into_iter()
+        .map(|token| StoredBatchInfo::from_token(token).context("malformed batch info"))
+        .collect()
+}
+
+/// Decodes the previous batch and proved batches from `proveBatchesSharedBridge` calldata.
+pub(crate) fn decode_prove_calldata(
+    contract: &ethabi::Contract,
+    calldata: &[u8],
+) -> anyhow::Result<(StoredBatchInfo, Vec<StoredBatchInfo>)> {
+    let (selector, data) = split_selector(calldata)?;
+    let post_gateway_function = contract
+        .function("proveBatchesSharedBridge")
+        .context("L1 contract does not have `proveBatchesSharedBridge` function")?;
+    let batches_schema = [StoredBatchInfo::schema(), stored_batch_infos_schema()];
+
+    let tokens = if selector == POST_SHARED_BRIDGE_PROVE_FUNCTION.short_signature() {
+        // `(chainId, prevBatch, committedBatches, proof)`; we don't need the proof, so we only decode the leading params.
+        let schema = [
+            ParamType::Uint(256),
+            batches_schema[0].clone(),
+            batches_schema[1].clone(),
+        ];
+        let mut tokens = ethabi::decode(&schema, data).context("failed decoding calldata")?;
+        tokens.split_off(1)
+    } else if selector == post_gateway_function.short_signature() {
+        let payload = decode_versioned_payload(data)?;
+        ethabi::decode(&batches_schema, &payload).context("failed decoding payload")?
+    } else {
+        return Err(UnsupportedSelector(selector.to_vec()).into());
+    };
+
+    let [prev_batch, batches]: [Token; 2] = tokens
+        .try_into()
+        .ok()
+        .context("unexpected number of decoded tokens")?;
+    let prev_batch =
+        StoredBatchInfo::from_token(prev_batch).context("malformed previous batch info")?;
+    Ok((prev_batch, decode_stored_batch_infos(batches)?))
+}
+
+/// Decodes executed batches from `executeBatchesSharedBridge` calldata.
+pub(crate) fn decode_execute_calldata(
+    contract: &ethabi::Contract,
+    calldata: &[u8],
+) -> anyhow::Result<Vec<StoredBatchInfo>> {
+    let (selector, data) = split_selector(calldata)?;
+    let post_gateway_function = contract
+        .function("executeBatchesSharedBridge")
+        .context("L1 contract does not have `executeBatchesSharedBridge` function")?;
+
+    let batches = if selector == POST_SHARED_BRIDGE_EXECUTE_FUNCTION.short_signature() {
+        // `(chainId, batches)`
+        let schema = [ParamType::Uint(256), stored_batch_infos_schema()];
+        let tokens = ethabi::decode(&schema, data).context("failed decoding calldata")?;
+        tokens.into_iter().nth(1)
+    } else if selector == post_gateway_function.short_signature() {
+        // `(batches, priorityOpsProofs)`; we only decode batches.
+        let payload = decode_versioned_payload(data)?;
+        let tokens = ethabi::decode(&[stored_batch_infos_schema()], &payload)
+            .context("failed decoding payload")?;
+        tokens.into_iter().next()
+    } else {
+        return Err(UnsupportedSelector(selector.to_vec()).into());
+    };
+    decode_stored_batch_infos(batches.context("missing batches")?)
+}
@@ -0,0 +1,484 @@
+//! Independent auditor of L1 batch settlement on L1 or the Gateway settlement layer.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_contracts::bridgehub_contract;
+use zksync_dal::{
+    settlement_audits_dal::{L1BatchSettlementTxs, SettlementTx},
+    ConnectionPool, Core, CoreDal,
+};
+use zksync_eth_client::{
+    clients::{DynClient, L1},
+    CallFunctionArgs, ContractCallError, EnrichedClientError, EthInterface,
+};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_types::{
+    api::SettlementAuditStatus,
+    ethabi::{self, Token},
+    web3, Address, L1BatchNumber, L2ChainId, SLChainId, H256, L2_BRIDGEHUB_ADDRESS, U256,
+};
+
+use self::{
+    checks::LocalL1BatchData,
+    metrics::{SettlementStage, METRICS},
+};
+
+mod checks;
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, thiserror::Error)]
+enum AuditError {
+    #[error("Web3 error communicating with settlement layer")]
+    Web3(#[from] EnrichedClientError),
+    #[error("error calling settlement layer contract")]
+    ContractCall(#[from] ContractCallError),
+    /// Settlement layer data diverges from local data.
+    #[error("settlement layer data diverges from local data: {0:#}")]
+    Divergence(anyhow::Error),
+    /// Error that should resolve on retry (e.g., the settlement layer node lagging behind).
+    #[error("transient error: {0:#}")]
+    Transient(anyhow::Error),
+    /// Error that is caused by violating invariants internal to this node (e.g., not having expected data in Postgres).
+    #[error("internal error: {0}")]
+    Internal(anyhow::Error),
+}
+
+impl AuditError {
+    fn is_retriable(&self) -> bool {
+        match self {
+            Self::Web3(err) | Self::ContractCall(ContractCallError::EthereumGateway(err)) => {
+                err.is_retriable()
+            }
+            Self::Transient(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Classifies an error returned by one of [`checks`].
+    fn from_check(err: anyhow::Error) -> Self {
+        if err.is::<checks::UnsupportedSelector>() {
+            Self::Internal(err)
+        } else {
+            Self::Divergence(err)
+        }
+    }
+}
+
+/// Audit status of the L1 batch currently processed by [`SettlementAuditor`].
+#[derive(Debug, Clone, Copy, Serialize)]
+struct CurrentBatchAudit {
+    number: L1BatchNumber,
+    commit: SettlementAuditStatus,
+    prove: SettlementAuditStatus,
+    execute: SettlementAuditStatus,
+}
+
+/// Health details reported by [`SettlementAuditor`].
+#[derive(Debug, Default, Serialize)]
+struct SettlementAuditorDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_audited_batch: Option<L1BatchNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_batch: Option<CurrentBatchAudit>,
+    /// Latest L1 batches with failed audits in the ascending order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failed_batches: Vec<L1BatchNumber>,
+}
+
+impl SettlementAuditorDetails {
+    /// Maximum number of failed batches reported in health details.
+    const MAX_REPORTED_FAILED_BATCHES: usize = 100;
+
+    fn health(&self) -> Health {
+        let status = if self.failed_batches.is_empty() {
+            HealthStatus::Ready
+        } else {
+            HealthStatus::Affected
+        };
+        Health::from(status).with_details(self)
+    }
+
+    fn report_failed_batch(&mut self, number: L1BatchNumber) {
+        if self.failed_batches.last() != Some(&number) {
+            self.failed_batches.push(number);
+        }
+        if self.failed_batches.len() > Self::MAX_REPORTED_FAILED_BATCHES {
+            self.failed_batches.remove(0);
+        }
+    }
+}
+
+/// Settlement layer accessed by the auditor.
+#[derive(Debug)]
+struct SettlementLayer {
+    client: Box<DynClient<L1>>,
+    chain_id: SLChainId,
+    diamond_proxy_addr: Address,
+}
+
+/// Component auditing commit, prove and execute transactions for each L1 batch on the settlement layer
+/// against local data.
+#[derive(Debug)]
+pub struct SettlementAuditor {
+    /// ABI of the ZKsync contract.
+    contract: ethabi::Contract,
+    /// How many past batches to audit when starting without previous audit results.
+    max_batches_to_recheck: u32,
+    sleep_interval: Duration,
+    l1: SettlementLayer,
+    gateway: Option<SettlementLayer>,
+    pool: ConnectionPool<Core>,
+    health_updater: HealthUpdater,
+    details: SettlementAuditorDetails,
+}
+
+impl SettlementAuditor {
+    const DEFAULT_SLEEP_INTERVAL: Duration = Duration::from_secs(10);
+
+    pub async fn new(
+        l1_client: Box<DynClient<L1>>,
+        l1_diamond_proxy_addr: Address,
+        gateway_client: Option<Box<DynClient<L1>>>,
+        max_batches_to_recheck: u32,
+        pool: ConnectionPool<Core>,
+        l2_chain_id: L2ChainId,
+    ) -> anyhow::Result<Self> {
+        let l1 = SettlementLayer {
+            chain_id: l1_client.fetch_chain_id().await?,
+            client: l1_client.for_component("settlement_auditor"),
+            diamond_proxy_addr: l1_diamond_proxy_addr,
+        };
+        let gateway = if let Some(client) = gateway_client {
+            let diamond_proxy_addr =
+                CallFunctionArgs::new("getZKChain", Token::Uint(l2_chain_id.as_u64().into()))
+                    .for_contract(L2_BRIDGEHUB_ADDRESS, &bridgehub_contract())
+                    .call(&client)
+                    .await?;
+            Some(SettlementLayer {
+                chain_id: client.fetch_chain_id().await?,
+                client: client.for_component("settlement_auditor"),
+                diamond_proxy_addr,
+            })
+        } else {
+            None
+        };
+
+        Ok(Self {
+            contract: zksync_contracts::hyperchain_contract(),
+            max_batches_to_recheck,
+            sleep_interval: Self::DEFAULT_SLEEP_INTERVAL,
+            l1,
+            gateway,
+            pool,
+            health_updater: ReactiveHealthCheck::new("settlement_auditor").1,
+            details: SettlementAuditorDetails::default(),
+        })
+    }
+
+    /// Returns health check associated with this auditor.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    fn settlement_layer(
+        &self,
+        chain_id: Option<SLChainId>,
+    ) -> Result<&SettlementLayer, AuditError> {
+        let Some(chain_id) = chain_id else {
+            // Transactions without a recorded chain ID are always sent to L1.
+            return Ok(&self.l1);
+        };
+        std::iter::once(&self.l1)
+            .chain(&self.gateway)
+            .find(|layer| layer.chain_id == chain_id)
+            .with_context(|| format!("no client for settlement layer with chain ID {chain_id}"))
+            .map_err(AuditError::Internal) // the node is misconfigured (e.g., has no Gateway client)
+    }
+
+    /// Audits a single settlement stage for an L1 batch.
+    async fn audit_stage(
+        &self,
+        stage: SettlementStage,
+        tx: SettlementTx,
+        local: &LocalL1BatchData,
+    ) -> Result<(), AuditError> {
+        let layer = self.settlement_layer(tx.chain_id)?;
+        let tx_hash = tx.hash;
+        tracing::debug!(
+            "Auditing {stage} tx {tx_hash:?} for L1 batch #{} on chain {}",
+            local.l1_batch.header.number,
+            layer.chain_id
+        );
+
+        let status = layer
+            .client
+            .get_tx_status(tx_hash)
+            .await?
+            .with_context(|| format!("receipt for {stage} tx {tx_hash:?} not found"))
+            .map_err(AuditError::Transient)?; // the settlement layer node may lag behind the one used by the node
+        if !status.success {
+            let err = anyhow::anyhow!("{stage} tx {tx_hash:?} has failed");
+            return Err(AuditError::Divergence(err));
+        }
+        let logs: Vec<web3::Log> = status
+            .receipt
+            .logs
+            .into_iter()
+            .filter(|log| log.address == layer.diamond_proxy_addr)
+            .collect();
+
+        match stage {
+            SettlementStage::Commit => {
+                checks::check_commit(&self.contract, &logs, local).map_err(AuditError::from_check)
+            }
+            SettlementStage::Prove => {
+                let calldata = Self::get_calldata(layer, tx_hash).await?;
+                checks::check_prove(&self.contract, &calldata, &logs, local)
+                    .map_err(AuditError::from_check)
+            }
+            SettlementStage::Execute => {
+                let calldata = Self::get_calldata(layer, tx_hash).await?;
+                checks::check_execute(&self.contract, &calldata, &logs, local)
+                    .map_err(AuditError::from_check)?;
+                self.check_stored_batch_hash(layer, local).await
+            }
+        }
+    }
+
+    async fn get_calldata(layer: &SettlementLayer, tx_hash: H256) -> Result<Vec<u8>, AuditError> {
+        let tx = layer
+            .client
+            .get_tx(tx_hash)
+            .await?
+            .with_context(|| format!("transaction {tx_hash:?} not found"))
+            .map_err(AuditError::Internal)?; // we've got a transaction receipt previously, thus an internal error
+        Ok(tx.input.0)
+    }
+
+    /// Checks that the batch hash stored in the diamond proxy for an executed batch matches the local one.
+    async fn check_stored_batch_hash(
+        &self,
+        layer: &SettlementLayer,
+        local: &LocalL1BatchData,
+    ) -> Result<(), AuditError> {
+        let number = local.l1_batch.header.number;
+        let stored_hash: H256 =
+            CallFunctionArgs::new("storedBatchHash", Token::Uint(U256::from(number.0)))
+                .for_contract(layer.diamond_proxy_addr, &self.contract)
+                .call(&layer.client)
+                .await?;
+        let local_hash = local.stored_batch_hash();
+        if stored_hash != local_hash {
+            let err = anyhow::anyhow!(
+                "executed batch hash stored on the settlement layer differs from the local one: \
+                 {stored_hash:?} vs {local_hash:?}"
+            );
+            return Err(AuditError::Divergence(err));
+        }
+        Ok(())
+    }
+
+    async fn first_batch_to_audit(
+        &self,
+        earliest_l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<L1BatchNumber> {
+        let mut storage = self.pool.connection_tagged("settlement_auditor").await?;
+        // The genesis batch (or the snapshot batch) is not settled, and we need the previous batch for audits.
+        let first_possible_batch = earliest_l1_batch_number + 1;
+        let last_audited_batch = storage
+            .settlement_audits_dal()
+            .get_last_completed_audit_batch()
+            .await?;
+        if let Some(last_audited_batch) = last_audited_batch {
+            return Ok((last_audited_batch + 1).max(first_possible_batch));
+        }
+
+        let last_committed_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_committed_on_eth()
+            .await?
+            .unwrap_or(earliest_l1_batch_number);
+        let first_batch = last_committed_batch
+            .0
+            .saturating_sub(self.max_batches_to_recheck);
+        Ok(L1BatchNumber(first_batch).max(first_possible_batch))
+    }
+
+    async fn load_failed_batches(&mut self) -> anyhow::Result<()> {
+        let mut failed_batches = self
+            .pool
+            .connection_tagged("settlement_auditor")
+            .await?
+            .settlement_audits_dal()
+            .get_failed_audit_batches(SettlementAuditorDetails::MAX_REPORTED_FAILED_BATCHES)
+            .await?;
+        failed_batches.reverse();
+        self.details.failed_batches = failed_batches;
+        Ok(())
+    }
+
+    /// Audits all settlement stages for the L1 batch that have confirmed transactions and weren't audited previously.
+    /// Returns `Ok(None)` if local data for the batch is not available yet, or the audit was interrupted
+    /// by a transient error.
+    async fn audit_batch(
+        &mut self,
+        number: L1BatchNumber,
+    ) -> anyhow::Result<Option<[SettlementAuditStatus; 3]>> {
+        let mut storage = self.pool.connection_tagged("settlement_auditor").await?;
+        let Some(local) = LocalL1BatchData::load(&mut storage, number).await? else {
+            return Ok(None);
+        };
+        let txs = storage
+            .settlement_audits_dal()
+            .get_settlement_txs(number)
+            .await?
+            .with_context(|| format!("L1 batch #{number} disappeared from storage"))?;
+        let prev_audit = storage.settlement_audits_dal().get_audit(number).await?;
+        drop(storage);
+
+        let (mut statuses, mut errors) = match prev_audit {
+            Some(audit) => ([audit.commit, audit.prove, audit.execute], audit.error),
+            None => ([SettlementAuditStatus::Pending; 3], None),
+        };
+        let L1BatchSettlementTxs {
+            commit,
+            prove,
+            execute,
+        } = txs;
+
+        let mut has_changes = false;
+        for ((stage, tx), status) in SettlementStage::ALL
+            .into_iter()
+            .zip([commit, prove, execute])
+            .zip(&mut statuses)
+        {
+            let (SettlementAuditStatus::Pending, Some(tx)) = (*status, tx) else {
+                continue;
+            };
+
+            match self.audit_stage(stage, tx, &local).await {
+                Ok(()) => {
+                    tracing::info!(
+                        "Audit of {stage} tx {:?} for L1 batch #{number} passed",
+                        tx.hash
+                    );
+                    METRICS.passed_audits[&stage].inc();
+                    *status = SettlementAuditStatus::Passed;
+                }
+                Err(AuditError::Divergence(err)) => {
+                    tracing::error!(
+                        "Audit of {stage} tx {:?} for L1 batch #{number} failed: {err:#}",
+                        tx.hash
+                    );
+                    METRICS.failed_audits[&stage].inc();
+                    *status = SettlementAuditStatus::Failed;
+                    let err = format!("{stage}: {err:#}");
+                    errors = Some(match errors {
+                        Some(prev_errors) => format!("{prev_errors}; {err}"),
+                        None => err,
+                    });
+                    self.details.report_failed_batch(number);
+                }
+                Err(err) if err.is_retriable() => {
+                    tracing::warn!(
+                        "Transient error while auditing {stage} tx for L1 batch #{number}; \
+                         will retry after a delay: {:#}",
+                        anyhow::Error::from(err)
+                    );
+                    break;
+                }
+                Err(err) => {
+                    let context = format!("failed auditing {stage} tx for L1 batch #{number}");
+                    return Err(anyhow::Error::from(err).context(context));
+                }
+            }
+            has_changes = true;
+        }
+
+        if has_changes {
+            self.pool
+                .connection_tagged("settlement_auditor")
+                .await?
+                .settlement_audits_dal()
+                .save_audit(number, statuses, errors.as_deref())
+                .await?;
+        }
+        Ok(Some(statuses))
+    }
+
+    fn update_health(&mut self, number: L1BatchNumber, statuses: [SettlementAuditStatus; 3]) {
+        if !statuses.contains(&SettlementAuditStatus::Pending) {
+            self.details.last_audited_batch = Some(number);
+            self.details.current_batch = None;
+            METRICS.last_audited_batch.set(number.0.into());
+        } else {
+            let [commit, prove, execute] = statuses;
+            self.details.current_batch = Some(CurrentBatchAudit {
+                number,
+                commit,
+                prove,
+                execute,
+            });
+        }
+        self.health_updater.update(self.details.health());
+    }
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting settlement auditor with L1 diamond proxy contract: {:?}, gateway diamond proxy contract: {:?}, \
+             sleep interval: {:?}",
+            self.l1.diamond_proxy_addr,
+            self.gateway.as_ref().map(|layer| layer.diamond_proxy_addr),
+            self.sleep_interval
+        );
+        self.load_failed_batches().await?;
+        self.health_updater.update(self.details.health());
+
+        let earliest_l1_batch_number = loop {
+            let earliest_l1_batch_number = self
+                .pool
+                .connection_tagged("settlement_auditor")
+                .await?
+                .blocks_dal()
+                .get_earliest_l1_batch_number_with_metadata()
+                .await?;
+            if let Some(number) = earliest_l1_batch_number {
+                break number;
+            }
+            if tokio::time::timeout(self.sleep_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                tracing::info!("Stop signal received, settlement auditor is shutting down");
+                return Ok(());
+            }
+        };
+
+        let mut batch_number = self.first_batch_to_audit(earliest_l1_batch_number).await?;
+        tracing::info!("Starting settlement audits from L1 batch #{batch_number}");
+        while !*stop_receiver.borrow_and_update() {
+            if let Some(statuses) = self.audit_batch(batch_number).await? {
+                self.update_health(batch_number, statuses);
+                if !statuses.contains(&SettlementAuditStatus::Pending) {
+                    batch_number += 1;
+                    continue;
+                }
+            }
+
+            if tokio::time::timeout(self.sleep_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        tracing::info!("Stop signal received, settlement auditor is shutting down");
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+//! Metrics for the settlement auditor.
+
+use std::fmt;
+
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Metrics};
+
+/// Stage of L1 batch settlement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "stage", rename_all = "snake_case")]
+pub(crate) enum SettlementStage {
+    Commit,
+    Prove,
+    Execute,
+}
+
+impl SettlementStage {
+    pub const ALL: [Self; 3] = [Self::Commit, Self::Prove, Self::Execute];
+}
+
+impl fmt::Display for SettlementStage {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::Commit => "commit",
+            Self::Prove => "prove",
+            Self::Execute => "execute",
+        })
+    }
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "settlement_auditor")]
+pub(crate) struct SettlementAuditorMetrics {
+    /// Number of the latest L1 batch with all settlement stages audited.
+    pub last_audited_batch: Gauge<u64>,
+    /// Number of passed audits split by the settlement stage.
+    pub passed_audits: Family<SettlementStage, Counter>,
+    /// Number of failed audits split by the settlement stage.
+    pub failed_audits: Family<SettlementStage, Counter>,
+}
+
+#[vise::register]
+pub(crate) static METRICS: vise::Global<SettlementAuditorMetrics> = vise::Global::new();
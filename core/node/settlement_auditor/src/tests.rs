@@ -0,0 +1,504 @@
+//! Tests for the settlement auditor.
+
+use assert_matches::assert_matches;
+use once_cell::sync::Lazy;
+use zksync_dal::Connection;
+use zksync_eth_client::{clients::MockSettlementLayer, Options};
+use zksync_l1_contract_interface::{
+    i_executor::{
+        methods::{ExecuteBatches, ProveBatches},
+        structures::StoredBatchInfo,
+    },
+    Tokenizable,
+};
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_test_utils::{
+    create_l1_batch, create_l1_batch_metadata, l1_batch_metadata_to_commitment_artifacts,
+};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, commitment::L1BatchWithMetadata,
+    priority_op_onchain_data::PriorityOpOnchainData, ProtocolVersionId,
+};
+
+use super::*;
+use crate::checks::{decode_execute_calldata, decode_prove_calldata};
+
+const L1_DIAMOND_PROXY_ADDR: Address = Address::repeat_byte(1);
+const VALIDATOR_TIMELOCK_ADDR: Address = Address::repeat_byte(23);
+const ERA_CHAIN_ID: u64 = 270;
+const L1_CHAIN_ID: u64 = 9;
+
+static CONTRACT: Lazy<ethabi::Contract> = Lazy::new(zksync_contracts::hyperchain_contract);
+
+fn create_l1_batch_with_metadata(number: u32) -> L1BatchWithMetadata {
+    L1BatchWithMetadata {
+        header: create_l1_batch(number),
+        metadata: create_l1_batch_metadata(number),
+        raw_published_factory_deps: vec![],
+    }
+}
+
+fn encode_with_chain_id(function: &ethabi::Function, tokens: Vec<Token>) -> Vec<u8> {
+    let tokens: Vec<_> = [Token::Uint(ERA_CHAIN_ID.into())]
+        .into_iter()
+        .chain(tokens)
+        .collect();
+    function.encode_input(&tokens).unwrap()
+}
+
+fn build_prove_tx_input_data(
+    prev_l1_batch: &L1BatchWithMetadata,
+    l1_batches: &[L1BatchWithMetadata],
+) -> Vec<u8> {
+    let tokens = ProveBatches {
+        prev_l1_batch: prev_l1_batch.clone(),
+        l1_batches: l1_batches.to_vec(),
+        proofs: vec![],
+        should_verify: false,
+    }
+    .conditional_into_tokens(false);
+    let function = CONTRACT.function("proveBatchesSharedBridge").unwrap();
+    encode_with_chain_id(function, tokens)
+}
+
+fn build_execute_tx_input_data(l1_batches: &[L1BatchWithMetadata]) -> Vec<u8> {
+    let tokens = ExecuteBatches {
+        l1_batches: l1_batches.to_vec(),
+        priority_ops_proofs: vec![],
+    }
+    .encode_for_eth_tx(ProtocolVersionId::latest());
+    let function = CONTRACT.function("executeBatchesSharedBridge").unwrap();
+    encode_with_chain_id(function, tokens)
+}
+
+fn mock_log(topics: Vec<H256>) -> web3::Log {
+    web3::Log {
+        address: L1_DIAMOND_PROXY_ADDR,
+        topics,
+        data: vec![].into(),
+        block_hash: None,
+        block_number: None,
+        transaction_hash: None,
+        transaction_index: None,
+        log_index: None,
+        transaction_log_index: None,
+        log_type: Some("mined".into()),
+        removed: None,
+        block_timestamp: None,
+    }
+}
+
+fn batch_event_log(event_name: &str, l1_batch: &L1BatchWithMetadata) -> web3::Log {
+    mock_log(vec![
+        CONTRACT.event(event_name).unwrap().signature(),
+        H256::from_low_u64_be(l1_batch.header.number.0.into()),
+        l1_batch.metadata.root_hash,
+        l1_batch.metadata.commitment,
+    ])
+}
+
+fn verification_log(prev: u32, current: u32) -> web3::Log {
+    mock_log(vec![
+        CONTRACT.event("BlocksVerification").unwrap().signature(),
+        H256::from_low_u64_be(prev.into()),
+        H256::from_low_u64_be(current.into()),
+    ])
+}
+
+/// Creates a mock L1 returning `stored_batch_hash` for `storedBatchHash` calls.
+fn create_mock_l1(stored_batch_hash: fn(u32) -> H256) -> MockSettlementLayer {
+    MockSettlementLayer::builder()
+        .with_call_handler(move |call, _block_id| {
+            assert_eq!(call.to, Some(L1_DIAMOND_PROXY_ADDR));
+            let data = &call.data.as_ref().unwrap().0;
+            let selector = CONTRACT
+                .function("storedBatchHash")
+                .unwrap()
+                .short_signature();
+            assert_eq!(data[..4], selector);
+            let number = U256::from_big_endian(&data[4..36]).as_u32();
+            Token::FixedBytes(stored_batch_hash(number).0.to_vec())
+        })
+        .with_chain_id(L1_CHAIN_ID)
+        .build()
+}
+
+async fn send_tx(
+    client: &MockSettlementLayer,
+    nonce: usize,
+    input_data: Vec<u8>,
+    logs: Vec<web3::Log>,
+) -> H256 {
+    let signed_tx = client
+        .sign_prepared_tx(
+            input_data,
+            VALIDATOR_TIMELOCK_ADDR,
+            Options {
+                nonce: Some(nonce.into()),
+                ..Options::default()
+            },
+        )
+        .unwrap();
+    client.as_ref().send_raw_tx(signed_tx.raw_tx).await.unwrap();
+    client.execute_tx(signed_tx.hash, true, 1).with_logs(logs);
+    signed_tx.hash
+}
+
+async fn save_l1_batch(storage: &mut Connection<'_, Core>, l1_batch: &L1BatchWithMetadata) {
+    let number = l1_batch.header.number;
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&l1_batch.header)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .save_l1_batch_tree_data(number, &l1_batch.metadata.tree_data())
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .save_l1_batch_commitment_artifacts(
+            number,
+            &l1_batch_metadata_to_commitment_artifacts(&l1_batch.metadata),
+        )
+        .await
+        .unwrap();
+}
+
+async fn save_settlement_tx(
+    storage: &mut Connection<'_, Core>,
+    number: L1BatchNumber,
+    action: AggregatedActionType,
+    tx_hash: H256,
+) {
+    storage
+        .eth_sender_dal()
+        .insert_bogus_confirmed_eth_tx(
+            number,
+            action,
+            tx_hash,
+            chrono::Utc::now(),
+            Some(SLChainId(L1_CHAIN_ID)),
+        )
+        .await
+        .unwrap();
+}
+
+fn create_auditor(client: MockSettlementLayer, pool: ConnectionPool<Core>) -> SettlementAuditor {
+    SettlementAuditor {
+        contract: zksync_contracts::hyperchain_contract(),
+        max_batches_to_recheck: 10,
+        sleep_interval: Duration::from_millis(10),
+        l1: SettlementLayer {
+            client: Box::new(client.into_client()),
+            chain_id: SLChainId(L1_CHAIN_ID),
+            diamond_proxy_addr: L1_DIAMOND_PROXY_ADDR,
+        },
+        gateway: None,
+        pool,
+        health_updater: ReactiveHealthCheck::new("settlement_auditor").1,
+        details: SettlementAuditorDetails::default(),
+    }
+}
+
+/// Prepares 2 L1 batches settled in a single commit, prove and execute transactions each.
+/// The execute transaction contains `execute_tx_batches` instead of the local batches.
+async fn prepare_settled_batches(
+    pool: &ConnectionPool<Core>,
+    client: &MockSettlementLayer,
+    execute_tx_batches: &[L1BatchWithMetadata],
+) {
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let genesis_batch = storage
+        .blocks_dal()
+        .get_l1_batch_metadata(L1BatchNumber(0))
+        .await
+        .unwrap()
+        .unwrap();
+    let l1_batches: Vec<_> = (1..=2).map(create_l1_batch_with_metadata).collect();
+    for l1_batch in &l1_batches {
+        save_l1_batch(&mut storage, l1_batch).await;
+    }
+
+    let commit_logs = l1_batches
+        .iter()
+        .map(|batch| batch_event_log("BlockCommit", batch))
+        .collect();
+    // Commit calldata isn't checked by the auditor.
+    let commit_tx_hash = send_tx(client, 0, vec![0; 4], commit_logs).await;
+    let prove_tx_hash = send_tx(
+        client,
+        1,
+        build_prove_tx_input_data(&genesis_batch, &l1_batches),
+        vec![verification_log(0, 2)],
+    )
+    .await;
+    let execute_logs = execute_tx_batches
+        .iter()
+        .map(|batch| batch_event_log("BlockExecution", batch))
+        .collect();
+    let execute_tx_hash = send_tx(
+        client,
+        2,
+        build_execute_tx_input_data(execute_tx_batches),
+        execute_logs,
+    )
+    .await;
+
+    for l1_batch in &l1_batches {
+        let number = l1_batch.header.number;
+        save_settlement_tx(
+            &mut storage,
+            number,
+            AggregatedActionType::Commit,
+            commit_tx_hash,
+        )
+        .await;
+        save_settlement_tx(
+            &mut storage,
+            number,
+            AggregatedActionType::PublishProofOnchain,
+            prove_tx_hash,
+        )
+        .await;
+        save_settlement_tx(
+            &mut storage,
+            number,
+            AggregatedActionType::Execute,
+            execute_tx_hash,
+        )
+        .await;
+    }
+}
+
+fn stored_batch_hash(number: u32) -> H256 {
+    StoredBatchInfo::from(&create_l1_batch_with_metadata(number)).hash()
+}
+
+#[test]
+fn decoding_prove_calldata() {
+    let prev_l1_batch = create_l1_batch_with_metadata(1);
+    let l1_batches: Vec<_> = (2..=4).map(create_l1_batch_with_metadata).collect();
+    let calldata = build_prove_tx_input_data(&prev_l1_batch, &l1_batches);
+
+    let (prev_batch, batches) = decode_prove_calldata(&CONTRACT, &calldata).unwrap();
+    assert_eq!(prev_batch, StoredBatchInfo::from(&prev_l1_batch));
+    let expected_batches: Vec<_> = l1_batches.iter().map(StoredBatchInfo::from).collect();
+    assert_eq!(batches, expected_batches);
+}
+
+#[test]
+fn decoding_pre_gateway_prove_calldata() {
+    let prev_l1_batch = create_l1_batch_with_metadata(1);
+    let l1_batch = create_l1_batch_with_metadata(2);
+    let tokens = vec![
+        StoredBatchInfo::from(&prev_l1_batch).into_token(),
+        Token::Array(vec![StoredBatchInfo::from(&l1_batch).into_token()]),
+        Token::Tuple(vec![Token::Array(vec![]), Token::Array(vec![])]),
+    ];
+    let calldata =
+        encode_with_chain_id(&zksync_contracts::POST_SHARED_BRIDGE_PROVE_FUNCTION, tokens);
+
+    let (prev_batch, batches) = decode_prove_calldata(&CONTRACT, &calldata).unwrap();
+    assert_eq!(prev_batch, StoredBatchInfo::from(&prev_l1_batch));
+    assert_eq!(batches, [StoredBatchInfo::from(&l1_batch)]);
+}
+
+#[test]
+fn decoding_execute_calldata() {
+    let l1_batches: Vec<_> = (1..=3).map(create_l1_batch_with_metadata).collect();
+    let expected_batches: Vec<_> = l1_batches.iter().map(StoredBatchInfo::from).collect();
+
+    let calldata = build_execute_tx_input_data(&l1_batches);
+    let batches = decode_execute_calldata(&CONTRACT, &calldata).unwrap();
+    assert_eq!(batches, expected_batches);
+
+    let tokens = vec![Token::Array(
+        expected_batches
+            .iter()
+            .map(|batch| batch.clone().into_token())
+            .collect(),
+    )];
+    let calldata = encode_with_chain_id(
+        &zksync_contracts::POST_SHARED_BRIDGE_EXECUTE_FUNCTION,
+        tokens,
+    );
+    let batches = decode_execute_calldata(&CONTRACT, &calldata).unwrap();
+    assert_eq!(batches, expected_batches);
+}
+
+#[test]
+fn decoding_calldata_with_unknown_selector() {
+    let err = decode_execute_calldata(&CONTRACT, &[1, 2, 3, 4, 5])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("unexpected function selector"), "{err}");
+    let err = decode_prove_calldata(&CONTRACT, &[1])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("too short"), "{err}");
+}
+
+async fn wait_for_completed_audit(pool: &ConnectionPool<Core>, number: L1BatchNumber) {
+    loop {
+        let last_audited_batch = pool
+            .connection()
+            .await
+            .unwrap()
+            .settlement_audits_dal()
+            .get_last_completed_audit_batch()
+            .await
+            .unwrap();
+        if last_audited_batch >= Some(number) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test]
+async fn auditor_passes_valid_settlement() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let client = create_mock_l1(stored_batch_hash);
+    let l1_batches: Vec<_> = (1..=2).map(create_l1_batch_with_metadata).collect();
+    prepare_settled_batches(&pool, &client, &l1_batches).await;
+
+    let auditor = create_auditor(client, pool.clone());
+    let mut health_check = auditor.health_check();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let auditor_task = tokio::spawn(auditor.run(stop_receiver));
+    wait_for_completed_audit(&pool, L1BatchNumber(2)).await;
+
+    let mut storage = pool.connection().await.unwrap();
+    for number in [1, 2] {
+        let audit = storage
+            .settlement_audits_dal()
+            .get_audit(L1BatchNumber(number))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(audit.commit, SettlementAuditStatus::Passed);
+        assert_eq!(audit.prove, SettlementAuditStatus::Passed);
+        assert_eq!(audit.execute, SettlementAuditStatus::Passed);
+        assert_eq!(audit.error, None);
+    }
+
+    let health = health_check
+        .wait_for(|health| {
+            health
+                .details()
+                .is_some_and(|details| details["last_audited_batch"] == 2)
+        })
+        .await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+
+    stop_sender.send_replace(true);
+    auditor_task.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn auditor_detects_diverging_execution() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    // The stored batch hash is always wrong.
+    let client = create_mock_l1(|_| H256::repeat_byte(0xff));
+    // Execute tx contains a different priority ops hash for the batch #2.
+    let mut l1_batches: Vec<_> = (1..=2).map(create_l1_batch_with_metadata).collect();
+    l1_batches[1]
+        .header
+        .priority_ops_onchain_data
+        .push(PriorityOpOnchainData {
+            layer_2_tip_fee: U256::zero(),
+            onchain_data_hash: H256::repeat_byte(1),
+        });
+    prepare_settled_batches(&pool, &client, &l1_batches).await;
+
+    let auditor = create_auditor(client, pool.clone());
+    let mut health_check = auditor.health_check();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let auditor_task = tokio::spawn(auditor.run(stop_receiver));
+    wait_for_completed_audit(&pool, L1BatchNumber(2)).await;
+
+    let mut storage = pool.connection().await.unwrap();
+    let audit = storage
+        .settlement_audits_dal()
+        .get_audit(L1BatchNumber(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(audit.commit, SettlementAuditStatus::Passed);
+    assert_eq!(audit.prove, SettlementAuditStatus::Passed);
+    assert_eq!(audit.execute, SettlementAuditStatus::Failed);
+    let err = audit.error.unwrap();
+    assert!(err.starts_with("execute:"), "{err}");
+    assert!(err.contains("stored on the settlement layer"), "{err}");
+
+    let audit = storage
+        .settlement_audits_dal()
+        .get_audit(L1BatchNumber(2))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(audit.execute, SettlementAuditStatus::Failed);
+    let err = audit.error.unwrap();
+    assert!(err.contains("priority operations hash"), "{err}");
+
+    let health = health_check
+        .wait_for(|health| {
+            health
+                .details()
+                .is_some_and(|details| details["failed_batches"] == serde_json::json!([1, 2]))
+        })
+        .await;
+    assert_matches!(health.status(), HealthStatus::Affected);
+
+    stop_sender.send_replace(true);
+    auditor_task.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn auditor_retries_on_missing_receipt() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let l1_batch = create_l1_batch_with_metadata(1);
+    save_l1_batch(&mut storage, &l1_batch).await;
+    // The transaction is not known to the settlement layer client.
+    save_settlement_tx(
+        &mut storage,
+        L1BatchNumber(1),
+        AggregatedActionType::Commit,
+        H256::repeat_byte(1),
+    )
+    .await;
+    drop(storage);
+
+    let client = create_mock_l1(stored_batch_hash);
+    let mut auditor = create_auditor(client, pool.clone());
+    let statuses = auditor.audit_batch(L1BatchNumber(1)).await.unwrap();
+    assert_eq!(statuses, Some([SettlementAuditStatus::Pending; 3]));
+    assert!(auditor.details.failed_batches.is_empty());
+
+    let audit = pool
+        .connection()
+        .await
+        .unwrap()
+        .settlement_audits_dal()
+        .get_audit(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert!(audit.is_none(), "{audit:?}");
+}
+
+#[test]
+fn unsupported_selector_is_not_a_divergence() {
+    let err = decode_execute_calldata(&CONTRACT, &[1, 2, 3, 4, 5]).unwrap_err();
+    assert_matches!(AuditError::from_check(err), AuditError::Internal(_));
+    let err = anyhow::anyhow!("no `BlockExecution` event for the batch");
+    assert_matches!(AuditError::from_check(err), AuditError::Divergence(_));
+}
@@ -192,6 +192,13 @@ pub struct ExternalNodeMetrics {
     pub last_correct_batch: Family<CheckerComponent, Gauge<u64>>,
     /// Number of the last L2 block checked by the re-org detector.
     pub last_correct_l2_block: Family<CheckerComponent, Gauge<u64>>,
+    /// Number of rollbacks performed by the external node after detecting a reorg.
+    pub reorg_rollbacks: Counter,
+    /// Number of L1 batches that need to be rolled back to recover from the last detected reorg.
+    pub reorg_rollback_depth: Gauge<u64>,
+    /// Set to 1 if the rollback required to recover from a reorg exceeds the configured limit
+    /// and must be performed manually.
+    pub reorg_rollback_limit_exceeded: Gauge<u64>,
 }
 
 #[vise::register]
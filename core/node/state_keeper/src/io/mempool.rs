@@ -31,7 +31,8 @@ use crate::{
     mempool_actor::l2_tx_filter,
     metrics::{L2BlockSealReason, AGGREGATION_METRICS, KEEPER_METRICS},
     seal_criteria::{
-        IoSealCriteria, L2BlockMaxPayloadSizeSealer, TimeoutSealer, UnexecutableReason,
+        FeeAwareResolution, FeeAwareSealer, IoSealCriteria, L2BlockMaxPayloadSizeSealer,
+        TimeoutSealer, TransactionQuotas, UnexecutableReason,
     },
     updates::UpdatesManager,
     utils::millis_since_epoch,
@@ -47,8 +48,10 @@ pub struct MempoolIO {
     mempool: MempoolGuard,
     pool: ConnectionPool<Core>,
     timeout_sealer: TimeoutSealer,
+    fee_aware_sealer: Option<FeeAwareSealer>,
     l2_block_max_payload_size_sealer: L2BlockMaxPayloadSizeSealer,
     filter: L2TxFilter,
+    transaction_quotas: Option<Arc<TransactionQuotas>>,
     l1_batch_params_provider: L1BatchParamsProvider,
     fee_account: Address,
     validation_computational_gas_limit: u32,
@@ -63,6 +66,13 @@ pub struct MempoolIO {
 
 impl IoSealCriteria for MempoolIO {
     fn should_seal_l1_batch_unconditionally(&mut self, manager: &UpdatesManager) -> bool {
+        if let Some(fee_aware_sealer) = &self.fee_aware_sealer {
+            match fee_aware_sealer.resolve(manager) {
+                FeeAwareResolution::Seal => return true,
+                FeeAwareResolution::Hold => return false,
+                FeeAwareResolution::Defer => { /* fall through to the timeout sealer */ }
+            }
+        }
         self.timeout_sealer
             .should_seal_l1_batch_unconditionally(manager)
     }
@@ -293,12 +303,27 @@ impl StateKeeperIO for MempoolIO {
     async fn wait_for_next_tx(
         &mut self,
         max_wait: Duration,
-        l2_block_timestamp: u64,
+        manager: &UpdatesManager,
     ) -> anyhow::Result<Option<Transaction>> {
+        let l2_block_timestamp = manager.l2_block.timestamp;
+        // Transactions in classes that have exhausted their quota in the pending batch are deferred,
+        // and transactions in classes with reserved resources are included first.
+        let transaction_quotas = self.transaction_quotas.clone();
+        let quota_priorities = transaction_quotas
+            .as_deref()
+            .map(|quotas| quotas.mempool_priorities(manager.pending_usage_by_quota()));
         let started_at = Instant::now();
         while started_at.elapsed() <= max_wait {
             let get_latency = KEEPER_METRICS.get_tx_from_mempool.start();
-            let maybe_tx = self.mempool.next_transaction(&self.filter);
+            let maybe_tx = if let Some(priorities) = &quota_priorities {
+                self.mempool.next_transaction_with_priority(
+                    &self.filter,
+                    |tx| priorities.priority(tx),
+                    priorities.max_priority(),
+                )
+            } else {
+                self.mempool.next_transaction(&self.filter)
+            };
             get_latency.observe();
 
             if let Some((tx, constraint)) = maybe_tx {
@@ -500,9 +525,13 @@ impl MempoolIO {
             mempool,
             pool,
             timeout_sealer: TimeoutSealer::new(config),
+            fee_aware_sealer: FeeAwareSealer::new(config)?,
             l2_block_max_payload_size_sealer: L2BlockMaxPayloadSizeSealer::new(config),
             filter: L2TxFilter::default(),
             // ^ Will be initialized properly on the first newly opened batch
+            transaction_quotas: Some(TransactionQuotas::new(config))
+                .filter(|quotas| !quotas.is_empty())
+                .map(Arc::new),
             l1_batch_params_provider: L1BatchParamsProvider::uninitialized(),
             fee_account,
             validation_computational_gas_limit: config.validation_computational_gas_limit,
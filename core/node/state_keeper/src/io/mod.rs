@@ -15,7 +15,10 @@ pub use self::{
     output_handler::{OutputHandler, StateKeeperOutputHandler},
     persistence::{L2BlockSealerTask, StateKeeperPersistence, TreeWritesPersistence},
 };
-use super::seal_criteria::{IoSealCriteria, UnexecutableReason};
+use super::{
+    seal_criteria::{IoSealCriteria, UnexecutableReason},
+    updates::UpdatesManager,
+};
 
 pub mod common;
 pub(crate) mod mempool;
@@ -137,10 +140,12 @@ pub trait StateKeeperIO: 'static + Send + Sync + fmt::Debug + IoSealCriteria {
 
     /// Blocks for up to `max_wait` until the next transaction is available for execution.
     /// Returns `None` if no transaction became available until the timeout.
+    ///
+    /// `manager` provides the state of the pending L2 block and L1 batch, which can be used to select the transaction.
     async fn wait_for_next_tx(
         &mut self,
         max_wait: Duration,
-        l2_block_timestamp: u64,
+        manager: &UpdatesManager,
     ) -> anyhow::Result<Option<Transaction>>;
     /// Marks the transaction as "not executed", so it can be retrieved from the IO again.
     async fn rollback(&mut self, tx: Transaction) -> anyhow::Result<()>;
@@ -31,6 +31,8 @@ pub struct StateKeeperPersistence {
     l2_legacy_shared_bridge_addr: Option<Address>,
     pre_insert_txs: bool,
     insert_protective_reads: bool,
+    insert_address_index: bool,
+    insert_token_transfers: bool,
     commands_sender: mpsc::Sender<Completable<L2BlockSealCommand>>,
     latest_completion_receiver: Option<oneshot::Receiver<()>>,
     // If true, `submit_l2_block()` will wait for the operation to complete.
@@ -94,6 +96,8 @@ impl StateKeeperPersistence {
             l2_legacy_shared_bridge_addr,
             pre_insert_txs: false,
             insert_protective_reads: true,
+            insert_address_index: false,
+            insert_token_transfers: false,
             commands_sender,
             latest_completion_receiver: None,
             is_sync,
@@ -113,6 +117,18 @@ impl StateKeeperPersistence {
         self
     }
 
+    /// Enables maintaining the index of transactions by address when persisting L2 blocks.
+    pub fn with_address_index(mut self) -> Self {
+        self.insert_address_index = true;
+        self
+    }
+
+    /// Enables maintaining the token transfer index when persisting L2 blocks.
+    pub fn with_token_transfer_index(mut self) -> Self {
+        self.insert_token_transfers = true;
+        self
+    }
+
     /// Submits a new sealing `command` to the sealer that this handle is attached to.
     ///
     /// If there are currently too many unprocessed commands, this method will wait until
@@ -188,8 +204,12 @@ impl StateKeeperOutputHandler for StateKeeperPersistence {
     }
 
     async fn handle_l2_block(&mut self, updates_manager: &UpdatesManager) -> anyhow::Result<()> {
-        let command = updates_manager
-            .seal_l2_block_command(self.l2_legacy_shared_bridge_addr, self.pre_insert_txs);
+        let command = updates_manager.seal_l2_block_command(
+            self.l2_legacy_shared_bridge_addr,
+            self.pre_insert_txs,
+            self.insert_address_index,
+            self.insert_token_transfers,
+        );
         self.submit_l2_block(command).await;
         Ok(())
     }
@@ -207,6 +227,7 @@ impl StateKeeperOutputHandler for StateKeeperPersistence {
                 self.pool.clone(),
                 self.l2_legacy_shared_bridge_addr,
                 self.insert_protective_reads,
+                self.insert_token_transfers,
             )
             .await
             .with_context(|| format!("cannot persist L1 batch #{batch_number}"))?;
@@ -619,7 +640,8 @@ mod tests {
 
         // The first command should be successfully submitted immediately.
         let mut updates_manager = create_updates_manager();
-        let seal_command = updates_manager.seal_l2_block_command(Some(Address::default()), false);
+        let seal_command =
+            updates_manager.seal_l2_block_command(Some(Address::default()), false, false, false);
         persistence.submit_l2_block(seal_command).await;
 
         // The second command should lead to blocking
@@ -627,7 +649,8 @@ mod tests {
             timestamp: 2,
             virtual_blocks: 1,
         });
-        let seal_command = updates_manager.seal_l2_block_command(Some(Address::default()), false);
+        let seal_command =
+            updates_manager.seal_l2_block_command(Some(Address::default()), false, false, false);
         {
             let submit_future = persistence.submit_l2_block(seal_command);
             futures::pin_mut!(submit_future);
@@ -655,7 +678,8 @@ mod tests {
             timestamp: 3,
             virtual_blocks: 1,
         });
-        let seal_command = updates_manager.seal_l2_block_command(Some(Address::default()), false);
+        let seal_command =
+            updates_manager.seal_l2_block_command(Some(Address::default()), false, false, false);
         persistence.submit_l2_block(seal_command).await;
         let command = sealer.commands_receiver.recv().await.unwrap();
         command.completion_sender.send(()).unwrap();
@@ -673,8 +697,12 @@ mod tests {
         // 5 L2 block sealing commands can be submitted without blocking.
         let mut updates_manager = create_updates_manager();
         for i in 1..=5 {
-            let seal_command =
-                updates_manager.seal_l2_block_command(Some(Address::default()), false);
+            let seal_command = updates_manager.seal_l2_block_command(
+                Some(Address::default()),
+                false,
+                false,
+                false,
+            );
             updates_manager.push_l2_block(L2BlockParams {
                 timestamp: i,
                 virtual_blocks: 1,
@@ -110,6 +110,8 @@ impl L2BlockSealProcess {
             Box::new(InsertTokensSubtask),
             Box::new(InsertEventsSubtask),
             Box::new(InsertL2ToL1LogsSubtask),
+            Box::new(InsertAddressTransactionsSubtask),
+            Box::new(InsertTokenTransfersSubtask),
         ]
     }
 
@@ -453,6 +455,96 @@ impl L2BlockSealSubtask for InsertL2ToL1LogsSubtask {
     }
 }
 
+#[derive(Debug)]
+pub(super) struct InsertAddressTransactionsSubtask;
+
+#[async_trait]
+impl L2BlockSealSubtask for InsertAddressTransactionsSubtask {
+    fn name(&self) -> &'static str {
+        "insert_address_transactions"
+    }
+
+    async fn run(
+        self: Box<Self>,
+        command: &L2BlockSealCommand,
+        connection: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<()> {
+        let executed_transactions = &command.l2_block.executed_transactions;
+        if !command.insert_address_index || executed_transactions.is_empty() {
+            return Ok(());
+        }
+
+        let progress = L2_BLOCK_METRICS.start(
+            L2BlockSealStage::InsertAddressTransactions,
+            command.is_l2_block_fictive(),
+        );
+        connection
+            .address_transactions_dal()
+            .insert_address_transactions(command.l2_block.number, executed_transactions)
+            .await?;
+        progress.observe(executed_transactions.len());
+        Ok(())
+    }
+
+    // Rollback is performed regardless of whether the index is enabled, so that the index remains consistent
+    // if it is toggled between restarts.
+    async fn rollback(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        last_sealed_l2_block: L2BlockNumber,
+    ) -> anyhow::Result<()> {
+        storage
+            .address_transactions_dal()
+            .roll_back_address_transactions(last_sealed_l2_block)
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct InsertTokenTransfersSubtask;
+
+#[async_trait]
+impl L2BlockSealSubtask for InsertTokenTransfersSubtask {
+    fn name(&self) -> &'static str {
+        "insert_token_transfers"
+    }
+
+    async fn run(
+        self: Box<Self>,
+        command: &L2BlockSealCommand,
+        connection: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<()> {
+        if !command.insert_token_transfers || command.l2_block.events.is_empty() {
+            return Ok(());
+        }
+
+        let is_fictive = command.is_l2_block_fictive();
+        let progress = L2_BLOCK_METRICS.start(L2BlockSealStage::InsertTokenTransfers, is_fictive);
+        let l2_block_events = command.extract_events(is_fictive);
+        connection
+            .token_transfers_dal()
+            .insert_token_transfers(command.l2_block.number, &l2_block_events)
+            .await?;
+        progress.observe(command.l2_block.events.len());
+        Ok(())
+    }
+
+    // Rollback is performed regardless of whether the index is enabled, so that the index remains consistent
+    // if it is toggled between restarts.
+    async fn rollback(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        last_sealed_l2_block: L2BlockNumber,
+    ) -> anyhow::Result<()> {
+        storage
+            .token_transfers_dal()
+            .roll_back_token_transfers(last_sealed_l2_block)
+            .await?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_dal::{ConnectionPool, Core};
@@ -464,9 +556,11 @@ mod tests {
     };
     use zksync_node_test_utils::create_l2_transaction;
     use zksync_types::{
+        address_to_h256,
+        api::AddressTransactionDirection,
         block::L2BlockHeader,
         commitment::PubdataParams,
-        h256_to_u256,
+        ethabi, h256_to_u256,
         l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
         AccountTreeId, Address, L1BatchNumber, ProtocolVersionId, StorageKey, StorageLog,
         StorageLogKind, StorageLogWithPreviousValue,
@@ -490,6 +584,7 @@ mod tests {
             .await
             .unwrap();
         let tx_hash = tx.hash();
+        let tx_initiator = tx.initiator_account();
         let executed_transactions = vec![TransactionExecutionResult {
             transaction: tx.into(),
             hash: tx_hash,
@@ -501,11 +596,24 @@ mod tests {
             call_traces: Vec::new(),
             revert_reason: None,
         }];
+        let token_address = Address::repeat_byte(0x10);
+        let transfer_signature = ethabi::long_signature(
+            "Transfer",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+            ],
+        );
         let events = vec![VmEvent {
             location: (L1BatchNumber(1), 0),
-            address: Address::zero(),
-            indexed_topics: Vec::new(),
-            value: Vec::new(),
+            address: token_address,
+            indexed_topics: vec![
+                transfer_signature,
+                address_to_h256(&tx_initiator),
+                H256::repeat_byte(2),
+            ],
+            value: H256::from_low_u64_be(100).0.to_vec(),
         }];
         let storage_key = StorageKey::new(AccountTreeId::new(Address::zero()), H256::zero());
         let storage_value = H256::from_low_u64_be(1);
@@ -556,6 +664,8 @@ mod tests {
             protocol_version: Some(ProtocolVersionId::latest()),
             l2_legacy_shared_bridge_addr: Default::default(),
             pre_insert_txs: false,
+            insert_address_index: true,
+            insert_token_transfers: true,
             pubdata_params: PubdataParams::default(),
         };
 
@@ -572,6 +682,36 @@ mod tests {
             .get_factory_deps(&vec![bytecode_hash].into_iter().collect())
             .await;
         assert!(factory_deps.contains_key(&h256_to_u256(bytecode_hash)));
+        // Check the transaction is indexed.
+        let indexed_txs = connection
+            .address_transactions_dal()
+            .get_address_transactions(
+                tx_initiator,
+                AddressTransactionDirection::Sent,
+                L2BlockNumber(0)..=L2BlockNumber(1),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(indexed_txs.len(), 1);
+        assert_eq!(indexed_txs[0].tx_hash, tx_hash);
+        // Check the transfer is indexed.
+        let transfers = connection
+            .token_transfers_dal()
+            .get_token_transfers(
+                tx_initiator,
+                Some(token_address),
+                AddressTransactionDirection::Sent,
+                L2BlockNumber(0)..=L2BlockNumber(1),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].transaction_hash, tx_hash);
+        assert_eq!(transfers[0].amount, 100.into());
 
         // Rollback.
         L2BlockSealProcess::clear_pending_l2_block(&mut connection, L2BlockNumber(0))
@@ -584,6 +724,32 @@ mod tests {
             .get_factory_deps(&vec![bytecode_hash].into_iter().collect())
             .await;
         assert!(factory_deps.is_empty());
+        // Check the index entry was removed.
+        let indexed_txs = connection
+            .address_transactions_dal()
+            .get_address_transactions(
+                tx_initiator,
+                AddressTransactionDirection::All,
+                L2BlockNumber(0)..=L2BlockNumber(1),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert!(indexed_txs.is_empty());
+        let transfers = connection
+            .token_transfers_dal()
+            .get_token_transfers(
+                tx_initiator,
+                None,
+                AddressTransactionDirection::All,
+                L2BlockNumber(0)..=L2BlockNumber(1),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert!(transfers.is_empty());
         drop(connection);
 
         // Run again.
@@ -48,6 +48,7 @@ impl UpdatesManager {
         pool: ConnectionPool<Core>,
         l2_legacy_shared_bridge_addr: Option<Address>,
         insert_protective_reads: bool,
+        insert_token_transfers: bool,
     ) -> anyhow::Result<()> {
         let started_at = Instant::now();
         let finished_batch = self
@@ -60,7 +61,11 @@ impl UpdatesManager {
         // Seal fictive L2 block with last events and storage logs.
         let l2_block_command = self.seal_l2_block_command(
             l2_legacy_shared_bridge_addr,
-            false, // fictive L2 blocks don't have txs, so it's fine to pass `false` here.
+            // Fictive L2 blocks don't have txs, so it's fine to pass `false` here.
+            false,
+            false,
+            // Fictive L2 blocks contain events (e.g., fee transfers to the operator), so they are indexed as usual.
+            insert_token_transfers,
         );
 
         let mut connection = pool.connection_tagged("state_keeper").await?;
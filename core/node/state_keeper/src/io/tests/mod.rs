@@ -29,7 +29,10 @@ use crate::{
     io::{seal_logic::l2_block_seal_subtasks::L2BlockSealProcess, StateKeeperIO},
     mempool_actor::l2_tx_filter,
     testonly::BASE_SYSTEM_CONTRACTS,
-    tests::{create_execution_result, create_transaction, seconds_since_epoch, Query},
+    tests::{
+        create_execution_result, create_transaction, create_updates_manager, seconds_since_epoch,
+        Query,
+    },
     updates::{L2BlockSealCommand, L2BlockUpdates, UpdatesManager},
     StateKeeperOutputHandler, StateKeeperPersistence,
 };
@@ -248,6 +251,8 @@ fn create_block_seal_command(
         protocol_version: Some(ProtocolVersionId::latest()),
         l2_legacy_shared_bridge_addr: Some(Address::default()),
         pre_insert_txs: false,
+        insert_address_index: false,
+        insert_token_transfers: false,
         pubdata_params: PubdataParams::default(),
     }
 }
@@ -782,6 +787,8 @@ async fn test_mempool_with_timestamp_assertion() {
         .duration_since(UNIX_EPOCH)
         .expect("Time went backwards")
         .as_secs();
+    let mut updates_manager = create_updates_manager();
+    updates_manager.l2_block.timestamp = system_time;
 
     // inserting 3 transactions - a good one, sandwiched in between two bad ones. The good one should
     // be returned by wait_for_next_tx, while two bad ones should be rejected.
@@ -814,14 +821,14 @@ async fn test_mempool_with_timestamp_assertion() {
     insert_l2_transaction(&mut storage, &rejected_tx_2).await;
 
     let tx = mempool
-        .wait_for_next_tx(Duration::from_secs(2), system_time)
+        .wait_for_next_tx(Duration::from_secs(2), &updates_manager)
         .await
         .unwrap()
         .expect("No expected transaction in the mempool");
     assert_eq!(expected_tx.hash(), tx.hash());
 
     let next_tx = mempool
-        .wait_for_next_tx(Duration::from_secs(2), system_time)
+        .wait_for_next_tx(Duration::from_secs(2), &updates_manager)
         .await
         .expect("Should be no more transactions in the mempool");
     assert!(next_tx.is_none());
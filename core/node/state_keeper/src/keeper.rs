@@ -153,7 +153,8 @@ impl ZkSyncStateKeeper {
         };
 
         let protocol_version = system_env.version;
-        let mut updates_manager = UpdatesManager::new(&l1_batch_env, &system_env, pubdata_params);
+        let mut updates_manager = UpdatesManager::new(&l1_batch_env, &system_env, pubdata_params)
+            .with_transaction_quotas(self.sealer.transaction_quotas());
         let mut protocol_upgrade_tx: Option<ProtocolUpgradeTx> = self
             .load_protocol_upgrade_tx(&pending_l2_blocks, protocol_version, l1_batch_env.number)
             .await?;
@@ -220,7 +221,8 @@ impl ZkSyncStateKeeper {
             (system_env, l1_batch_env, pubdata_params) = self
                 .wait_for_new_batch_env(&next_cursor, &mut stop_receiver)
                 .await?;
-            updates_manager = UpdatesManager::new(&l1_batch_env, &system_env, pubdata_params);
+            updates_manager = UpdatesManager::new(&l1_batch_env, &system_env, pubdata_params)
+                .with_transaction_quotas(self.sealer.transaction_quotas());
             batch_executor = self
                 .create_batch_executor(
                     l1_batch_env.clone(),
@@ -609,7 +611,7 @@ impl ZkSyncStateKeeper {
             let waiting_latency = KEEPER_METRICS.waiting_for_tx.start();
             let Some(tx) = self
                 .io
-                .wait_for_next_tx(POLL_WAIT_DURATION, updates_manager.l2_block.timestamp)
+                .wait_for_next_tx(POLL_WAIT_DURATION, updates_manager)
                 .instrument(info_span!("wait_for_next_tx"))
                 .await
                 .context("error waiting for next transaction")?
@@ -834,11 +836,17 @@ impl ZkSyncStateKeeper {
                 let tx_writes_metrics =
                     StorageWritesDeduplicator::apply_on_empty_state(logs_to_apply_iter);
 
+                let tx_usage_by_quota =
+                    updates_manager.transaction_usage_by_quota(&tx, tx_execution_metrics);
+                let mut block_usage_by_quota = updates_manager.pending_usage_by_quota().clone();
+                block_usage_by_quota += &tx_usage_by_quota;
+
                 let tx_data = SealData {
                     execution_metrics: **tx_execution_metrics,
                     cumulative_size: encoding_len,
                     writes_metrics: tx_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    usage_by_quota: tx_usage_by_quota,
                 };
                 let block_data = SealData {
                     execution_metrics: tx_data.execution_metrics
@@ -847,6 +855,7 @@ impl ZkSyncStateKeeper {
                         + updates_manager.pending_txs_encoding_size(),
                     writes_metrics: block_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    usage_by_quota: block_usage_by_quota,
                 };
                 let is_tx_l1 = tx.is_l1() as usize;
 
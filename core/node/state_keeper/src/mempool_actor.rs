@@ -1,4 +1,8 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Context as _;
 #[cfg(test)]
@@ -6,15 +10,20 @@ use tokio::sync::mpsc;
 use tokio::sync::watch;
 use zksync_config::configs::chain::MempoolConfig;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
-use zksync_mempool::L2TxFilter;
+use zksync_mempool::{L2TxFilter, MempoolInfo};
 use zksync_multivm::utils::derive_base_fee_and_gas_per_pubdata;
 use zksync_node_fee_model::BatchFeeModelInputProvider;
 #[cfg(test)]
 use zksync_types::H256;
-use zksync_types::{get_nonce_key, vm::VmVersion, Address, Nonce, Transaction};
+use zksync_types::{
+    api::PendingTransactionReason, get_nonce_key, vm::VmVersion, Address, Nonce, Transaction,
+};
 
 use super::{metrics::KEEPER_METRICS, types::MempoolGuard};
 
+/// Interval between updates of pending transaction reasons that require scanning pending transactions.
+const PENDING_REASONS_UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Creates a mempool filter for L2 transactions based on the current L1 gas price.
 /// The filter is used to filter out transactions from the mempool that do not cover expenses
 /// to process them.
@@ -97,27 +106,12 @@ impl MempoolFetcher {
                 .await
                 .context("failed getting pending protocol version")?;
 
-            let (fee_per_gas, gas_per_pubdata) = if let Some(unsealed_batch) = storage
-                .blocks_dal()
-                .get_unsealed_l1_batch()
-                .await
-                .context("failed getting unsealed batch")?
-            {
-                let (fee_per_gas, gas_per_pubdata) = derive_base_fee_and_gas_per_pubdata(
-                    unsealed_batch.fee_input,
-                    protocol_version.into(),
-                );
-                (fee_per_gas, gas_per_pubdata as u32)
-            } else {
-                let filter = l2_tx_filter(
-                    self.batch_fee_input_provider.as_ref(),
-                    protocol_version.into(),
-                )
-                .await
-                .context("failed creating L2 transaction filter")?;
-
-                (filter.fee_per_gas, filter.gas_per_pubdata)
-            };
+            let (fee_per_gas, gas_per_pubdata) = get_fee_params(
+                &mut storage,
+                self.batch_fee_input_provider.as_ref(),
+                protocol_version.into(),
+            )
+            .await?;
 
             let transactions_with_constraints = storage
                 .transactions_dal()
@@ -135,15 +129,22 @@ impl MempoolFetcher {
                 .iter()
                 .map(|(t, _c)| t)
                 .collect();
+            let transaction_hashes: Vec<_> = transactions.iter().map(|tx| tx.hash()).collect();
 
             let nonces = get_transaction_nonces(&mut storage, &transactions).await?;
+            record_evicted_accounts(&mut storage, &mempool_info).await?;
+            if !transaction_hashes.is_empty() {
+                // Reasons for transactions that were just loaded into the mempool are no longer relevant.
+                storage
+                    .transactions_dal()
+                    .clear_pending_reasons(&transaction_hashes)
+                    .await
+                    .context("failed clearing pending reasons")?;
+            }
             drop(storage);
 
             #[cfg(test)]
-            {
-                let transaction_hashes = transactions.iter().map(|x| x.hash()).collect();
-                self.transaction_hashes_sender.send(transaction_hashes).ok();
-            }
+            self.transaction_hashes_sender.send(transaction_hashes).ok();
             let all_transactions_loaded = transactions.len() < self.sync_batch_size;
             self.mempool.insert(transactions_with_constraints, nonces);
             latency.observe();
@@ -156,6 +157,145 @@ impl MempoolFetcher {
     }
 }
 
+/// Periodically records reasons why pending transactions are not executed, so that they can be surfaced via the API.
+/// This is separate from [`MempoolFetcher`] since the updates require scanning all pending transactions.
+#[derive(Debug)]
+pub struct PendingReasonsUpdater {
+    mempool: MempoolGuard,
+    pool: ConnectionPool<Core>,
+    batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
+    update_interval: Duration,
+    /// Accounts that had a nonce gap during the last update.
+    nonce_gap_accounts: HashSet<Address>,
+}
+
+impl PendingReasonsUpdater {
+    pub fn new(
+        mempool: MempoolGuard,
+        batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
+        pool: ConnectionPool<Core>,
+    ) -> Self {
+        Self {
+            mempool,
+            pool,
+            batch_fee_input_provider,
+            update_interval: PENDING_REASONS_UPDATE_INTERVAL,
+            nonce_gap_accounts: HashSet::new(),
+        }
+    }
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            self.update().await?;
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(self.update_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, pending reasons updater is shutting down");
+        Ok(())
+    }
+
+    async fn update(&mut self) -> anyhow::Result<()> {
+        let nonce_gap_accounts: HashSet<_> =
+            self.mempool.nonce_gap_accounts().into_iter().collect();
+
+        let mut storage = self.pool.connection_tagged("state_keeper").await?;
+        let protocol_version = storage
+            .blocks_dal()
+            .pending_protocol_version()
+            .await
+            .context("failed getting pending protocol version")?;
+        let (fee_per_gas, gas_per_pubdata) = get_fee_params(
+            &mut storage,
+            self.batch_fee_input_provider.as_ref(),
+            protocol_version.into(),
+        )
+        .await?;
+
+        let resolved_accounts: Vec<_> = self
+            .nonce_gap_accounts
+            .difference(&nonce_gap_accounts)
+            .copied()
+            .collect();
+        let mut transactions_dal = storage.transactions_dal();
+        if !resolved_accounts.is_empty() {
+            transactions_dal
+                .clear_pending_reason_for_accounts(
+                    &resolved_accounts,
+                    PendingTransactionReason::NonceGap,
+                )
+                .await
+                .context("failed clearing nonce gap reasons")?;
+        }
+        if !nonce_gap_accounts.is_empty() {
+            let accounts: Vec<_> = nonce_gap_accounts.iter().copied().collect();
+            transactions_dal
+                .set_pending_reason_for_accounts(&accounts, PendingTransactionReason::NonceGap)
+                .await
+                .context("failed setting nonce gap reasons")?;
+        }
+        self.nonce_gap_accounts = nonce_gap_accounts;
+
+        transactions_dal
+            .mark_underpriced_pending_transactions(fee_per_gas, gas_per_pubdata)
+            .await
+            .context("failed marking underpriced transactions")?;
+        Ok(())
+    }
+}
+
+/// Records pending reasons for transactions of accounts stashed or purged by the mempool. Reasons for transactions
+/// loaded back into the mempool are cleared afterwards.
+async fn record_evicted_accounts(
+    storage: &mut Connection<'_, Core>,
+    mempool_info: &MempoolInfo,
+) -> anyhow::Result<()> {
+    let mut transactions_dal = storage.transactions_dal();
+    if !mempool_info.stashed_accounts.is_empty() {
+        transactions_dal
+            .set_pending_reason_for_accounts(
+                &mempool_info.stashed_accounts,
+                PendingTransactionReason::AccountStashed,
+            )
+            .await
+            .context("failed setting reasons for stashed accounts")?;
+    }
+    if !mempool_info.purged_accounts.is_empty() {
+        transactions_dal
+            .set_pending_reason_for_accounts(
+                &mempool_info.purged_accounts,
+                PendingTransactionReason::AccountPurged,
+            )
+            .await
+            .context("failed setting reasons for purged accounts")?;
+    }
+    Ok(())
+}
+
+/// Returns the fee per gas and gas per pubdata that transactions must cover to be loaded into the mempool.
+async fn get_fee_params(
+    storage: &mut Connection<'_, Core>,
+    batch_fee_input_provider: &dyn BatchFeeModelInputProvider,
+    vm_version: VmVersion,
+) -> anyhow::Result<(u64, u32)> {
+    let unsealed_batch = storage
+        .blocks_dal()
+        .get_unsealed_l1_batch()
+        .await
+        .context("failed getting unsealed batch")?;
+    Ok(if let Some(unsealed_batch) = unsealed_batch {
+        let (fee_per_gas, gas_per_pubdata) =
+            derive_base_fee_and_gas_per_pubdata(unsealed_batch.fee_input, vm_version);
+        (fee_per_gas, gas_per_pubdata as u32)
+    } else {
+        let filter = l2_tx_filter(batch_fee_input_provider, vm_version)
+            .await
+            .context("failed creating L2 transaction filter")?;
+        (filter.fee_per_gas, filter.gas_per_pubdata)
+    })
+}
+
 /// Loads nonces for all distinct `transactions` initiators from the storage.
 async fn get_transaction_nonces(
     storage: &mut Connection<'_, Core>,
@@ -189,6 +329,8 @@ async fn get_transaction_nonces(
 
 #[cfg(test)]
 mod tests {
+    use std::time::Instant;
+
     use zksync_multivm::interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
     use zksync_node_fee_model::MockBatchFeeParamsProvider;
     use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
@@ -245,6 +387,52 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn recording_evicted_accounts() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+
+        let stashed_tx = create_l2_transaction(10, 100);
+        let purged_tx = create_l2_transaction(10, 100);
+        let other_tx = create_l2_transaction(10, 100);
+        for tx in [&stashed_tx, &purged_tx, &other_tx] {
+            storage
+                .transactions_dal()
+                .insert_transaction_l2(
+                    tx,
+                    TransactionExecutionMetrics::default(),
+                    ValidationTraces::default(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let mempool_info = MempoolInfo {
+            stashed_accounts: vec![stashed_tx.initiator_account()],
+            purged_accounts: vec![purged_tx.initiator_account()],
+        };
+        record_evicted_accounts(&mut storage, &mempool_info)
+            .await
+            .unwrap();
+
+        for (tx, expected_reason) in [
+            (&stashed_tx, Some(PendingTransactionReason::AccountStashed)),
+            (&purged_tx, Some(PendingTransactionReason::AccountPurged)),
+            (&other_tx, None),
+        ] {
+            let details = storage
+                .transactions_web3_dal()
+                .get_transaction_status_details(tx.hash())
+                .await
+                .unwrap()
+                .expect("no transaction");
+            assert_eq!(details.pending_reason, expected_reason);
+        }
+    }
+
     #[tokio::test]
     async fn syncing_mempool_basics() {
         let pool = ConnectionPool::constrained_test_pool(1).await;
@@ -326,12 +514,16 @@ mod tests {
 
         let fetcher = MempoolFetcher::new(
             mempool.clone(),
-            fee_params_provider,
+            fee_params_provider.clone(),
             &TEST_MEMPOOL_CONFIG,
             pool.clone(),
         );
+        let mut reasons_updater =
+            PendingReasonsUpdater::new(mempool.clone(), fee_params_provider, pool.clone());
+        reasons_updater.update_interval = TEST_MEMPOOL_CONFIG.sync_interval();
         let (stop_sender, stop_receiver) = watch::channel(false);
-        let fetcher_task = tokio::spawn(fetcher.run(stop_receiver));
+        let fetcher_task = tokio::spawn(fetcher.run(stop_receiver.clone()));
+        let reasons_updater_task = tokio::spawn(reasons_updater.run(stop_receiver));
 
         // Add a transaction with insufficient fee to the storage.
         let transaction = create_l2_transaction(base_fee / 2, gas_per_pubdata / 2);
@@ -350,8 +542,33 @@ mod tests {
         tokio::time::sleep(TEST_MEMPOOL_CONFIG.sync_interval() * 5).await;
         assert_eq!(mempool.stats().l2_transaction_count, 0);
 
+        // The reason should be recorded for the transaction once pending reasons are updated.
+        let started_at = Instant::now();
+        loop {
+            assert!(
+                started_at.elapsed() < Duration::from_secs(10),
+                "pending reason was not recorded"
+            );
+            let mut storage = pool.connection().await.unwrap();
+            let details = storage
+                .transactions_web3_dal()
+                .get_transaction_status_details(transaction.hash())
+                .await
+                .unwrap()
+                .expect("no transaction");
+            drop(storage);
+            if details.pending_reason == Some(PendingTransactionReason::FeeTooLow) {
+                break;
+            }
+            tokio::time::sleep(TEST_MEMPOOL_CONFIG.sync_interval()).await;
+        }
+
         stop_sender.send_replace(true);
         fetcher_task.await.unwrap().expect("fetcher errored");
+        reasons_updater_task
+            .await
+            .unwrap()
+            .expect("pending reasons updater errored");
     }
 
     #[tokio::test]
@@ -341,6 +341,8 @@ pub(super) enum L2BlockSealStage {
     InsertEvents,
     ExtractL2ToL1Logs,
     InsertL2ToL1Logs,
+    InsertAddressTransactions,
+    InsertTokenTransfers,
     ReportTxMetrics,
     CalculateLogsBloom,
 }
@@ -4,12 +4,14 @@
 //! The conditional sealer abstraction allows to implement different sealing strategies, e.g. the actual
 //! sealing strategy for the main node or noop sealer for the external node.
 
-use std::fmt;
+use std::{fmt, sync::Arc};
 
 use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_types::ProtocolVersionId;
 
-use super::{criteria, SealCriterion, SealData, SealResolution, AGGREGATION_METRICS};
+use super::{
+    criteria, SealCriterion, SealData, SealResolution, TransactionQuotas, AGGREGATION_METRICS,
+};
 
 /// Checks if an L1 batch should be sealed after executing a transaction.
 pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
@@ -34,6 +36,12 @@ pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
         tx_data: &SealData,
         protocol_version: ProtocolVersionId,
     ) -> SealResolution;
+
+    /// Returns transaction quotas enforced by this sealer, if any. Resource usage of transactions is only attributed
+    /// to quotas (which requires classifying each executed transaction) if this returns `Some(_)`.
+    fn transaction_quotas(&self) -> Option<Arc<TransactionQuotas>> {
+        None
+    }
 }
 
 /// Implementation of [`ConditionalSealer`] used by the main node.
@@ -45,6 +53,7 @@ pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
 pub struct SequencerSealer {
     config: StateKeeperConfig,
     sealers: Vec<Box<dyn SealCriterion>>,
+    transaction_quotas: Option<Arc<TransactionQuotas>>,
 }
 
 impl ConditionalSealer for SequencerSealer {
@@ -118,12 +127,23 @@ impl ConditionalSealer for SequencerSealer {
         }
         final_seal_resolution
     }
+
+    fn transaction_quotas(&self) -> Option<Arc<TransactionQuotas>> {
+        self.transaction_quotas.clone()
+    }
 }
 
 impl SequencerSealer {
     pub fn new(config: StateKeeperConfig) -> Self {
-        let sealers = Self::default_sealers(&config);
-        Self { config, sealers }
+        let transaction_quotas = Some(TransactionQuotas::new(&config))
+            .filter(|quotas| !quotas.is_empty())
+            .map(Arc::new);
+        let sealers = Self::default_sealers(&config, transaction_quotas.clone());
+        Self {
+            config,
+            sealers,
+            transaction_quotas,
+        }
     }
 
     #[cfg(test)]
@@ -131,11 +151,18 @@ impl SequencerSealer {
         config: StateKeeperConfig,
         sealers: Vec<Box<dyn SealCriterion>>,
     ) -> Self {
-        Self { config, sealers }
+        Self {
+            config,
+            sealers,
+            transaction_quotas: None,
+        }
     }
 
-    fn default_sealers(config: &StateKeeperConfig) -> Vec<Box<dyn SealCriterion>> {
-        vec![
+    fn default_sealers(
+        config: &StateKeeperConfig,
+        transaction_quotas: Option<Arc<TransactionQuotas>>,
+    ) -> Vec<Box<dyn SealCriterion>> {
+        let mut sealers: Vec<Box<dyn SealCriterion>> = vec![
             Box::new(criteria::SlotsCriterion),
             Box::new(criteria::PubDataBytesCriterion {
                 max_pubdata_per_batch: config.max_pubdata_per_batch,
@@ -145,7 +172,11 @@ impl SequencerSealer {
             Box::new(criteria::GasForBatchTipCriterion),
             Box::new(criteria::L1L2TxsCriterion),
             Box::new(criteria::L2L1LogsCriterion),
-        ]
+        ];
+        if let Some(quotas) = transaction_quotas {
+            sealers.push(Box::new(criteria::TransactionQuotasCriterion::new(quotas)));
+        }
+        sealers
     }
 }
 
@@ -5,10 +5,12 @@ mod l2_l1_logs;
 mod pubdata_bytes;
 mod slots;
 mod tx_encoding_size;
+mod tx_quotas;
 
+pub use self::tx_quotas::TransactionQuotas;
 pub(crate) use self::{
     gas_for_batch_tip::GasForBatchTipCriterion, geometry_seal_criteria::CircuitsCriterion,
     l1_l2_txs::L1L2TxsCriterion, l2_l1_logs::L2L1LogsCriterion,
     pubdata_bytes::PubDataBytesCriterion, slots::SlotsCriterion,
-    tx_encoding_size::TxEncodingSizeCriterion,
+    tx_encoding_size::TxEncodingSizeCriterion, tx_quotas::TransactionQuotasCriterion,
 };
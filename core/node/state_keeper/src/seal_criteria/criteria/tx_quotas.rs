@@ -0,0 +1,607 @@
+use std::{collections::HashSet, sync::Arc};
+
+use zksync_config::configs::chain::TransactionQuotaConfig;
+use zksync_mempool::TransactionPriority;
+use zksync_types::{l2::L2Tx, Address, ProtocolVersionId, Transaction};
+
+use crate::seal_criteria::{
+    QuotaUsage, ResourceUsage, SealCriterion, SealData, SealResolution, StateKeeperConfig,
+    TransactionTarget, UsageByQuota,
+};
+
+#[derive(Debug)]
+struct TransactionQuota {
+    name: String,
+    l1_transactions: bool,
+    contracts: HashSet<Address>,
+    max_gas_share: Option<f64>,
+    max_pubdata_share: Option<f64>,
+    reserved_gas_share: Option<f64>,
+    reserved_pubdata_share: Option<f64>,
+}
+
+impl TransactionQuota {
+    fn new(config: &TransactionQuotaConfig) -> Self {
+        Self {
+            name: config.name.clone(),
+            l1_transactions: config.l1_transactions,
+            contracts: config.contracts.iter().copied().collect(),
+            max_gas_share: config.max_gas_share,
+            max_pubdata_share: config.max_pubdata_share,
+            reserved_gas_share: config.reserved_gas_share,
+            reserved_pubdata_share: config.reserved_pubdata_share,
+        }
+    }
+
+    fn other_gas_share(&self) -> Option<f64> {
+        self.reserved_gas_share.map(|share| 1.0 - share)
+    }
+
+    fn other_pubdata_share(&self) -> Option<f64> {
+        self.reserved_pubdata_share.map(|share| 1.0 - share)
+    }
+}
+
+/// Per-batch quotas for classes of transactions (e.g., L1 -> L2 transactions or transactions routed through
+/// a specific set of contracts).
+///
+/// An L2 transaction belongs to a class if its recipient, its initiator account (e.g., a custom account), its paymaster
+/// or, for transactions sent to one of the trusted multicall contracts, one of the aggregated call targets
+/// is among the class contracts.
+#[derive(Debug)]
+pub struct TransactionQuotas {
+    quotas: Vec<TransactionQuota>,
+    multicall_contracts: HashSet<Address>,
+    max_gas_per_batch: u64,
+    max_pubdata_per_batch: u64,
+}
+
+impl TransactionQuotas {
+    pub fn new(config: &StateKeeperConfig) -> Self {
+        Self {
+            quotas: config
+                .transaction_quotas
+                .iter()
+                .map(TransactionQuota::new)
+                .collect(),
+            multicall_contracts: config
+                .transaction_quota_multicall_contracts
+                .iter()
+                .copied()
+                .collect(),
+            max_gas_per_batch: config.max_gas_per_batch,
+            max_pubdata_per_batch: config.max_pubdata_per_batch,
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.quotas.is_empty()
+    }
+
+    fn matches(&self, quota: &TransactionQuota, target: &TransactionTarget) -> bool {
+        match target {
+            TransactionTarget::L1 => quota.l1_transactions,
+            TransactionTarget::L2(route) => route
+                .addresses(&self.multicall_contracts)
+                .any(|address| quota.contracts.contains(&address)),
+        }
+    }
+
+    /// Attributes resource usage of a transaction with the specified target to each quota.
+    fn attribute_usage(&self, target: &TransactionTarget, usage: ResourceUsage) -> UsageByQuota {
+        self.quotas
+            .iter()
+            .map(|quota| {
+                if self.matches(quota, target) {
+                    QuotaUsage {
+                        class: usage,
+                        other: ResourceUsage::default(),
+                    }
+                } else {
+                    QuotaUsage {
+                        class: ResourceUsage::default(),
+                        other: usage,
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Attributes resource usage of an executed transaction to each quota.
+    pub(crate) fn transaction_usage(
+        &self,
+        transaction: &Transaction,
+        usage: ResourceUsage,
+    ) -> UsageByQuota {
+        self.attribute_usage(&TransactionTarget::new(transaction), usage)
+    }
+
+    /// Checks whether `usage` exceeds the bound defined by `share` of the batch limits.
+    fn exceeds_share(
+        &self,
+        usage: ResourceUsage,
+        gas_share: Option<f64>,
+        pubdata_share: Option<f64>,
+    ) -> bool {
+        let exceeds = |value: u64, share: Option<f64>, limit: u64| {
+            share.is_some_and(|share| value as f64 > (limit as f64 * share).round())
+        };
+        exceeds(usage.gas, gas_share, self.max_gas_per_batch)
+            || exceeds(usage.pubdata, pubdata_share, self.max_pubdata_per_batch)
+    }
+
+    /// Checks whether `usage` reaches the bound defined by `share` of the batch limits, i.e., no more transactions
+    /// subject to the bound can be included into the batch.
+    fn reaches_share(
+        &self,
+        usage: ResourceUsage,
+        gas_share: Option<f64>,
+        pubdata_share: Option<f64>,
+    ) -> bool {
+        let reaches = |value: u64, share: Option<f64>, limit: u64| {
+            share.is_some_and(|share| value as f64 >= (limit as f64 * share).round())
+        };
+        reaches(usage.gas, gas_share, self.max_gas_per_batch)
+            || reaches(usage.pubdata, pubdata_share, self.max_pubdata_per_batch)
+    }
+
+    /// Returns priorities for L2 transactions in the mempool given the resource usage of the pending batch.
+    pub(crate) fn mempool_priorities(&self, batch_usage: &UsageByQuota) -> MempoolPriorities<'_> {
+        let states = self
+            .quotas
+            .iter()
+            .enumerate()
+            .map(|(i, quota)| {
+                let QuotaUsage {
+                    class: class_usage,
+                    other: other_usage,
+                } = batch_usage.get(i);
+                let has_unfilled_reserved_gas = quota.reserved_gas_share.is_some()
+                    && !self.reaches_share(class_usage, quota.reserved_gas_share, None);
+                let has_unfilled_reserved_pubdata = quota.reserved_pubdata_share.is_some()
+                    && !self.reaches_share(class_usage, None, quota.reserved_pubdata_share);
+                QuotaState {
+                    is_class_exhausted: self.reaches_share(
+                        class_usage,
+                        quota.max_gas_share,
+                        quota.max_pubdata_share,
+                    ),
+                    is_reserved_for_class: self.reaches_share(
+                        other_usage,
+                        quota.other_gas_share(),
+                        quota.other_pubdata_share(),
+                    ),
+                    has_unfilled_reservation: has_unfilled_reserved_gas
+                        || has_unfilled_reserved_pubdata,
+                }
+            })
+            .collect();
+        MempoolPriorities {
+            quotas: self,
+            states,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct QuotaState {
+    /// The class has reached its max share; other class transactions should be deferred to the next batch.
+    is_class_exhausted: bool,
+    /// Transactions outside the class have used up all non-reserved resources.
+    is_reserved_for_class: bool,
+    /// The class hasn't used up its reserved share yet, so its transactions should be included first.
+    has_unfilled_reservation: bool,
+}
+
+/// Priorities of L2 transactions in the mempool according to [`TransactionQuotas`].
+#[derive(Debug)]
+pub(crate) struct MempoolPriorities<'a> {
+    quotas: &'a TransactionQuotas,
+    states: Vec<QuotaState>,
+}
+
+impl MempoolPriorities<'_> {
+    /// Returns the maximum priority that can be returned by [`Self::priority()`].
+    pub fn max_priority(&self) -> TransactionPriority {
+        if self
+            .states
+            .iter()
+            .any(|state| state.has_unfilled_reservation)
+        {
+            TransactionPriority::High
+        } else {
+            TransactionPriority::Normal
+        }
+    }
+
+    pub fn priority(&self, transaction: &L2Tx) -> TransactionPriority {
+        let target = TransactionTarget::for_l2_transaction(transaction);
+        let mut priority = TransactionPriority::Normal;
+        for (quota, state) in self.quotas.quotas.iter().zip(&self.states) {
+            if self.quotas.matches(quota, &target) {
+                if state.is_class_exhausted {
+                    return TransactionPriority::Deferred;
+                }
+                if state.has_unfilled_reservation {
+                    priority = TransactionPriority::High;
+                }
+            } else if state.is_reserved_for_class {
+                return TransactionPriority::Deferred;
+            }
+        }
+        priority
+    }
+}
+
+/// Enforces [`TransactionQuotas`] when sealing batches.
+///
+/// - If a class has a max share of gas / pubdata and a transaction in the class exceeds it, the batch is sealed
+///   and the transaction is moved to the next batch.
+/// - If a class has a reserved share of gas / pubdata and a transaction outside the class uses it, the batch is sealed
+///   and the transaction is moved to the next batch.
+///
+/// The first transaction of the corresponding kind in a batch is always included, so that quotas cannot make
+/// a transaction unexecutable. The mempool side of quotas (deferring transactions in exhausted classes and
+/// including transactions in classes with reserved resources first) is implemented by [`MempoolPriorities`].
+#[derive(Debug)]
+pub(crate) struct TransactionQuotasCriterion {
+    quotas: Arc<TransactionQuotas>,
+}
+
+impl TransactionQuotasCriterion {
+    pub fn new(quotas: Arc<TransactionQuotas>) -> Self {
+        Self { quotas }
+    }
+}
+
+impl SealCriterion for TransactionQuotasCriterion {
+    fn should_seal(
+        &self,
+        _config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        _tx_count: usize,
+        _l1_tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        _protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        for (i, quota) in self.quotas.quotas.iter().enumerate() {
+            let tx_usage = tx_data.usage_by_quota.get(i);
+            let block_usage = block_data.usage_by_quota.get(i);
+            let bounds = [
+                (
+                    tx_usage.class,
+                    block_usage.class,
+                    quota.max_gas_share,
+                    quota.max_pubdata_share,
+                ),
+                (
+                    tx_usage.other,
+                    block_usage.other,
+                    quota.other_gas_share(),
+                    quota.other_pubdata_share(),
+                ),
+            ];
+
+            for (tx_usage, usage, gas_share, pubdata_share) in bounds {
+                // Only bounds the transaction contributes to are checked. The first transaction of the corresponding kind
+                // is always included.
+                let is_affected = tx_usage != ResourceUsage::default() && usage != tx_usage;
+                if is_affected && self.quotas.exceeds_share(usage, gas_share, pubdata_share) {
+                    tracing::debug!(
+                        "Transaction quota `{}` is exhausted by a transaction with usage {tx_usage:?}; \
+                         batch usage: {block_usage:?}",
+                        quota.name
+                    );
+                    return SealResolution::ExcludeAndSeal;
+                }
+            }
+        }
+        SealResolution::NoSeal
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "transaction_quotas"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use zksync_types::{
+        ethabi, fee::Fee, transaction_request::PaymasterParams, K256PrivateKey, L2ChainId, Nonce,
+        U256,
+    };
+
+    use super::*;
+    use crate::seal_criteria::L2TransactionRoute;
+
+    const BRIDGE: Address = Address::repeat_byte(1);
+    const NOISY_DAPP: Address = Address::repeat_byte(2);
+    const OTHER: Address = Address::repeat_byte(3);
+    const MULTICALL: Address = Address::repeat_byte(4);
+    const SENDER: Address = Address::repeat_byte(0xee);
+
+    fn config(quotas: Vec<TransactionQuotaConfig>) -> StateKeeperConfig {
+        StateKeeperConfig {
+            max_gas_per_batch: 1_000,
+            max_pubdata_per_batch: 100,
+            transaction_quotas: quotas,
+            transaction_quota_multicall_contracts: vec![MULTICALL],
+            ..StateKeeperConfig::default()
+        }
+    }
+
+    fn usage(gas: u64, pubdata: u64) -> ResourceUsage {
+        ResourceUsage { gas, pubdata }
+    }
+
+    fn target(recipient: Address) -> TransactionTarget {
+        TransactionTarget::L2(L2TransactionRoute {
+            recipient: Some(recipient),
+            initiator: SENDER,
+            paymaster: None,
+            multicall_targets: vec![],
+        })
+    }
+
+    fn criterion(quotas: Vec<TransactionQuotaConfig>) -> TransactionQuotasCriterion {
+        TransactionQuotasCriterion::new(Arc::new(TransactionQuotas::new(&config(quotas))))
+    }
+
+    fn usage_by_quota(
+        quotas: &TransactionQuotas,
+        usage: HashMap<TransactionTarget, ResourceUsage>,
+    ) -> UsageByQuota {
+        let mut usage_by_quota = UsageByQuota::default();
+        for (target, usage) in &usage {
+            usage_by_quota += &quotas.attribute_usage(target, *usage);
+        }
+        usage_by_quota
+    }
+
+    fn check(
+        criterion: &TransactionQuotasCriterion,
+        block_usage: HashMap<TransactionTarget, ResourceUsage>,
+        tx: (TransactionTarget, ResourceUsage),
+    ) -> SealResolution {
+        let block_data = SealData {
+            usage_by_quota: usage_by_quota(&criterion.quotas, block_usage),
+            ..SealData::default()
+        };
+        let tx_data = SealData {
+            usage_by_quota: criterion.quotas.attribute_usage(&tx.0, tx.1),
+            ..SealData::default()
+        };
+        criterion.should_seal(
+            &config(vec![]),
+            0,
+            0,
+            0,
+            &block_data,
+            &tx_data,
+            ProtocolVersionId::latest(),
+        )
+    }
+
+    fn l2_tx(recipient: Address, calldata: Vec<u8>, paymaster_params: PaymasterParams) -> L2Tx {
+        L2Tx::new_signed(
+            Some(recipient),
+            calldata,
+            Nonce(0),
+            Fee {
+                gas_limit: U256::from(1_000_000),
+                max_fee_per_gas: U256::from(1),
+                max_priority_fee_per_gas: U256::zero(),
+                gas_per_pubdata_limit: U256::from(50_000),
+            },
+            U256::zero(),
+            L2ChainId::default(),
+            &K256PrivateKey::random(),
+            vec![],
+            paymaster_params,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn capping_class_usage() {
+        let criterion = criterion(vec![TransactionQuotaConfig {
+            name: "noisy".to_owned(),
+            contracts: vec![NOISY_DAPP],
+            max_gas_share: Some(0.5),
+            ..TransactionQuotaConfig::default()
+        }]);
+        let noisy = target(NOISY_DAPP);
+        let other = target(OTHER);
+
+        let resolution = check(
+            &criterion,
+            HashMap::from([(noisy.clone(), usage(400, 0))]),
+            (noisy.clone(), usage(100, 0)),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+
+        let resolution = check(
+            &criterion,
+            HashMap::from([(noisy.clone(), usage(600, 0))]),
+            (noisy.clone(), usage(200, 0)),
+        );
+        assert_eq!(resolution, SealResolution::ExcludeAndSeal);
+
+        // The first class transaction is always included.
+        let resolution = check(
+            &criterion,
+            HashMap::from([
+                (noisy.clone(), usage(800, 0)),
+                (other.clone(), usage(100, 0)),
+            ]),
+            (noisy.clone(), usage(800, 0)),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+
+        // Transactions outside the class are not affected.
+        let resolution = check(
+            &criterion,
+            HashMap::from([(noisy, usage(600, 0)), (other.clone(), usage(300, 0))]),
+            (other, usage(100, 0)),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+    }
+
+    #[test]
+    fn reserving_blockspace() {
+        let criterion = criterion(vec![TransactionQuotaConfig {
+            name: "bridge".to_owned(),
+            l1_transactions: true,
+            contracts: vec![BRIDGE],
+            reserved_pubdata_share: Some(0.3),
+            ..TransactionQuotaConfig::default()
+        }]);
+        let l1 = TransactionTarget::L1;
+        let bridge = target(BRIDGE);
+        let other = target(OTHER);
+        let deployment = TransactionTarget::L2(L2TransactionRoute {
+            recipient: None,
+            initiator: SENDER,
+            paymaster: None,
+            multicall_targets: vec![],
+        });
+
+        let resolution = check(
+            &criterion,
+            HashMap::from([(other.clone(), usage(0, 70))]),
+            (other.clone(), usage(0, 10)),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+
+        let resolution = check(
+            &criterion,
+            HashMap::from([(other.clone(), usage(0, 60)), (deployment, usage(0, 20))]),
+            (other.clone(), usage(0, 10)),
+        );
+        assert_eq!(resolution, SealResolution::ExcludeAndSeal);
+
+        // Transactions in the class can use the reserved space.
+        for target in [l1, bridge] {
+            let resolution = check(
+                &criterion,
+                HashMap::from([
+                    (other.clone(), usage(0, 70)),
+                    (target.clone(), usage(0, 25)),
+                ]),
+                (target, usage(0, 20)),
+            );
+            assert_eq!(resolution, SealResolution::NoSeal);
+        }
+    }
+
+    #[test]
+    fn classifying_transaction_routes() {
+        let quotas = TransactionQuotas::new(&config(vec![TransactionQuotaConfig {
+            name: "noisy".to_owned(),
+            contracts: vec![NOISY_DAPP],
+            ..TransactionQuotaConfig::default()
+        }]));
+        let quota = &quotas.quotas[0];
+        let matches = |tx: &L2Tx| quotas.matches(quota, &TransactionTarget::for_l2_transaction(tx));
+
+        assert!(matches(&l2_tx(
+            NOISY_DAPP,
+            vec![],
+            PaymasterParams::default()
+        )));
+        assert!(!matches(&l2_tx(OTHER, vec![], PaymasterParams::default())));
+
+        let paymaster_params = PaymasterParams {
+            paymaster: NOISY_DAPP,
+            paymaster_input: vec![],
+        };
+        assert!(matches(&l2_tx(OTHER, vec![], paymaster_params)));
+
+        // Custom account calling the dApp on behalf of its owner.
+        let mut aa_tx = l2_tx(OTHER, vec![], PaymasterParams::default());
+        aa_tx.common_data.initiator_address = NOISY_DAPP;
+        assert!(matches(&aa_tx));
+
+        // `aggregate3((address,bool,bytes)[])`
+        let mut multicall_calldata = vec![0x82, 0xad, 0x56, 0xcb];
+        let call = |target: Address| {
+            ethabi::Token::Tuple(vec![
+                ethabi::Token::Address(target),
+                ethabi::Token::Bool(false),
+                ethabi::Token::Bytes(vec![1, 2, 3]),
+            ])
+        };
+        multicall_calldata.extend(ethabi::encode(&[ethabi::Token::Array(vec![
+            call(OTHER),
+            call(NOISY_DAPP),
+        ])]));
+        let multicall_tx = l2_tx(
+            MULTICALL,
+            multicall_calldata.clone(),
+            PaymasterParams::default(),
+        );
+        assert!(matches(&multicall_tx));
+        // Aggregated calls via an untrusted contract are not attributed to the transaction.
+        let spoofed_tx = l2_tx(OTHER, multicall_calldata, PaymasterParams::default());
+        assert!(!matches(&spoofed_tx));
+    }
+
+    #[test]
+    fn prioritizing_mempool_transactions() {
+        let quotas = TransactionQuotas::new(&config(vec![
+            TransactionQuotaConfig {
+                name: "noisy".to_owned(),
+                contracts: vec![NOISY_DAPP],
+                max_gas_share: Some(0.5),
+                ..TransactionQuotaConfig::default()
+            },
+            TransactionQuotaConfig {
+                name: "bridge".to_owned(),
+                contracts: vec![BRIDGE],
+                reserved_gas_share: Some(0.2),
+                ..TransactionQuotaConfig::default()
+            },
+        ]));
+        let noisy_tx = l2_tx(NOISY_DAPP, vec![], PaymasterParams::default());
+        let bridge_tx = l2_tx(BRIDGE, vec![], PaymasterParams::default());
+        let other_tx = l2_tx(OTHER, vec![], PaymasterParams::default());
+
+        let priorities = quotas.mempool_priorities(&UsageByQuota::default());
+        assert_eq!(priorities.max_priority(), TransactionPriority::High);
+        assert_eq!(priorities.priority(&noisy_tx), TransactionPriority::Normal);
+        assert_eq!(priorities.priority(&bridge_tx), TransactionPriority::High);
+        assert_eq!(priorities.priority(&other_tx), TransactionPriority::Normal);
+
+        let batch_usage = usage_by_quota(
+            &quotas,
+            HashMap::from([
+                (target(NOISY_DAPP), usage(500, 0)),
+                (target(BRIDGE), usage(200, 0)),
+            ]),
+        );
+        let priorities = quotas.mempool_priorities(&batch_usage);
+        assert_eq!(priorities.max_priority(), TransactionPriority::Normal);
+        assert_eq!(
+            priorities.priority(&noisy_tx),
+            TransactionPriority::Deferred
+        );
+        assert_eq!(priorities.priority(&bridge_tx), TransactionPriority::Normal);
+        assert_eq!(priorities.priority(&other_tx), TransactionPriority::Normal);
+
+        // Non-reserved gas is used up.
+        let batch_usage = usage_by_quota(&quotas, HashMap::from([(target(OTHER), usage(800, 0))]));
+        let priorities = quotas.mempool_priorities(&batch_usage);
+        assert_eq!(
+            priorities.priority(&noisy_tx),
+            TransactionPriority::Deferred
+        );
+        assert_eq!(priorities.priority(&bridge_tx), TransactionPriority::High);
+        assert_eq!(
+            priorities.priority(&other_tx),
+            TransactionPriority::Deferred
+        );
+    }
+}
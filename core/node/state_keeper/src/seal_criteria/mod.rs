@@ -10,16 +10,22 @@
 //! Maintaining all the criteria in one place has proven itself to be very error-prone,
 //! thus now every criterion is independent of the others.
 
-use std::fmt;
+use std::{collections::HashSet, fmt, ops};
 
 use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_multivm::{
     interface::{DeduplicatedWritesMetrics, Halt, TransactionExecutionMetrics, VmExecutionMetrics},
     vm_latest::TransactionVmExt,
 };
-use zksync_types::{utils::display_timestamp, ProtocolVersionId, Transaction};
+use zksync_types::{
+    ethabi, l2::L2Tx, utils::display_timestamp, Address, Execute, ExecuteTransactionCommon,
+    L2TxCommonData, ProtocolVersionId, Transaction,
+};
 
-pub use self::conditional_sealer::{ConditionalSealer, NoopSealer, SequencerSealer};
+pub use self::{
+    conditional_sealer::{ConditionalSealer, NoopSealer, SequencerSealer},
+    criteria::TransactionQuotas,
+};
 use crate::{metrics::AGGREGATION_METRICS, updates::UpdatesManager, utils::millis_since};
 
 mod conditional_sealer;
@@ -151,6 +157,202 @@ impl SealResolution {
     }
 }
 
+/// Target of a transaction used to attribute resource usage to transaction quotas.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum TransactionTarget {
+    /// L1 -> L2 or protocol upgrade transaction.
+    L1,
+    /// L2 transaction.
+    L2(L2TransactionRoute),
+}
+
+impl TransactionTarget {
+    pub fn new(transaction: &Transaction) -> Self {
+        match &transaction.common_data {
+            ExecuteTransactionCommon::L2(common_data) => {
+                Self::L2(L2TransactionRoute::new(&transaction.execute, common_data))
+            }
+            ExecuteTransactionCommon::L1(_) | ExecuteTransactionCommon::ProtocolUpgrade(_) => {
+                Self::L1
+            }
+        }
+    }
+
+    pub fn for_l2_transaction(transaction: &L2Tx) -> Self {
+        Self::L2(L2TransactionRoute::new(
+            &transaction.execute,
+            &transaction.common_data,
+        ))
+    }
+}
+
+/// Accounts and contracts an L2 transaction is routed through.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct L2TransactionRoute {
+    /// Contract called by the transaction; `None` for deployments without a recipient.
+    pub recipient: Option<Address>,
+    /// Account initiating the transaction, which may be a custom (AA) account.
+    pub initiator: Address,
+    /// Paymaster covering the transaction fee, if any.
+    pub paymaster: Option<Address>,
+    /// Contracts called via `recipient` if the transaction calldata is a Multicall3 aggregation call.
+    /// Whether these calls are attributed to the transaction depends on whether `recipient` is a trusted multicall contract.
+    pub multicall_targets: Vec<Address>,
+}
+
+impl L2TransactionRoute {
+    fn new(execute: &Execute, common_data: &L2TxCommonData) -> Self {
+        let paymaster = common_data.paymaster_params.paymaster;
+        Self {
+            recipient: execute.contract_address,
+            initiator: common_data.initiator_address,
+            paymaster: (paymaster != Address::zero()).then_some(paymaster),
+            multicall_targets: decode_multicall_targets(&execute.calldata),
+        }
+    }
+
+    /// Iterates over all addresses the transaction is routed through. Multicall targets are only included
+    /// if the recipient is one of `multicall_contracts`.
+    pub fn addresses<'a>(
+        &'a self,
+        multicall_contracts: &HashSet<Address>,
+    ) -> impl Iterator<Item = Address> + 'a {
+        let is_multicall = self
+            .recipient
+            .is_some_and(|recipient| multicall_contracts.contains(&recipient));
+        let multicall_targets = if is_multicall {
+            self.multicall_targets.as_slice()
+        } else {
+            &[]
+        };
+        self.recipient
+            .into_iter()
+            .chain([self.initiator])
+            .chain(self.paymaster)
+            .chain(multicall_targets.iter().copied())
+    }
+}
+
+/// Decodes targets of calls aggregated by a Multicall3 contract. Returns an empty list if `calldata`
+/// is not a Multicall3 aggregation call.
+fn decode_multicall_targets(calldata: &[u8]) -> Vec<Address> {
+    use ethabi::{ParamType, Token};
+
+    const AGGREGATE: [u8; 4] = [0x25, 0x2d, 0xba, 0x42];
+    const BLOCK_AND_AGGREGATE: [u8; 4] = [0xc3, 0x07, 0x7f, 0xa9];
+    const TRY_AGGREGATE: [u8; 4] = [0xbc, 0xe3, 0x8b, 0xd7];
+    const TRY_BLOCK_AND_AGGREGATE: [u8; 4] = [0x39, 0x95, 0x42, 0xe9];
+    const AGGREGATE3: [u8; 4] = [0x82, 0xad, 0x56, 0xcb];
+    const AGGREGATE3_VALUE: [u8; 4] = [0x17, 0x4d, 0xea, 0x71];
+
+    let calls = |fields: Vec<ParamType>| ParamType::Array(Box::new(ParamType::Tuple(fields)));
+    let Some((selector, data)) = calldata.split_at_checked(4) else {
+        return vec![];
+    };
+    let params = match <[u8; 4]>::try_from(selector).unwrap() {
+        AGGREGATE | BLOCK_AND_AGGREGATE => {
+            vec![calls(vec![ParamType::Address, ParamType::Bytes])]
+        }
+        TRY_AGGREGATE | TRY_BLOCK_AND_AGGREGATE => vec![
+            ParamType::Bool,
+            calls(vec![ParamType::Address, ParamType::Bytes]),
+        ],
+        AGGREGATE3 => vec![calls(vec![
+            ParamType::Address,
+            ParamType::Bool,
+            ParamType::Bytes,
+        ])],
+        AGGREGATE3_VALUE => vec![calls(vec![
+            ParamType::Address,
+            ParamType::Bool,
+            ParamType::Uint(256),
+            ParamType::Bytes,
+        ])],
+        _ => return vec![],
+    };
+    let Ok(mut tokens) = ethabi::decode(&params, data) else {
+        return vec![];
+    };
+    let Some(Token::Array(calls)) = tokens.pop() else {
+        return vec![];
+    };
+    calls
+        .into_iter()
+        .filter_map(|call| match call {
+            Token::Tuple(fields) => fields.into_iter().next()?.into_address(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Resources used by transactions that are subject to transaction quotas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ResourceUsage {
+    pub gas: u64,
+    pub pubdata: u64,
+}
+
+impl ResourceUsage {
+    pub fn new(execution_metrics: &VmExecutionMetrics) -> Self {
+        Self {
+            gas: execution_metrics.gas_used as u64,
+            pubdata: execution_metrics.pubdata_published.into(),
+        }
+    }
+}
+
+impl ops::AddAssign for ResourceUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.gas += other.gas;
+        self.pubdata += other.pubdata;
+    }
+}
+
+/// Resource usage attributed to a single transaction quota.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct QuotaUsage {
+    /// Usage by transactions in the quota class.
+    pub class: ResourceUsage,
+    /// Usage by all other transactions.
+    pub other: ResourceUsage,
+}
+
+impl ops::AddAssign for QuotaUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.class += other.class;
+        self.other += other.other;
+    }
+}
+
+/// Resource usage attributed to each of the configured transaction quotas, in the order of quotas in the config.
+/// Empty if no quotas are configured.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct UsageByQuota(Vec<QuotaUsage>);
+
+impl UsageByQuota {
+    /// Returns usage attributed to the quota with the specified index.
+    pub fn get(&self, quota_idx: usize) -> QuotaUsage {
+        self.0.get(quota_idx).copied().unwrap_or_default()
+    }
+}
+
+impl FromIterator<QuotaUsage> for UsageByQuota {
+    fn from_iter<I: IntoIterator<Item = QuotaUsage>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl ops::AddAssign<&Self> for UsageByQuota {
+    fn add_assign(&mut self, other: &Self) {
+        if self.0.len() < other.0.len() {
+            self.0.resize(other.0.len(), QuotaUsage::default());
+        }
+        for (total, &usage) in self.0.iter_mut().zip(&other.0) {
+            *total += usage;
+        }
+    }
+}
+
 /// Information about transaction or block applicable either to a single transaction, or
 /// to the entire L2 block / L1 batch.
 #[derive(Debug, Default)]
@@ -159,6 +361,7 @@ pub struct SealData {
     pub(super) cumulative_size: usize,
     pub(super) writes_metrics: DeduplicatedWritesMetrics,
     pub(super) gas_remaining: u32,
+    pub(super) usage_by_quota: UsageByQuota,
 }
 
 impl SealData {
@@ -175,6 +378,8 @@ impl SealData {
             cumulative_size: transaction.bootloader_encoding_size(),
             writes_metrics,
             gas_remaining: tx_metrics.gas_remaining,
+            // Transaction quotas never make a transaction unexecutable, so usage is not attributed to them.
+            usage_by_quota: UsageByQuota::default(),
         }
     }
 }
@@ -252,6 +457,121 @@ impl IoSealCriteria for TimeoutSealer {
     }
 }
 
+/// Decision of the [`FeeAwareSealer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FeeAwareResolution {
+    /// L1 batch should be sealed.
+    Seal,
+    /// L1 batch should be kept open even if it has reached the commit deadline.
+    Hold,
+    /// Decision is delegated to other I/O sealers.
+    Defer,
+}
+
+/// Economic L1 batch sealer. Estimates the L1 overhead of committing, proving and executing the batch
+/// from `batch_overhead_l1_gas` and the L1 gas price in the batch fee input (which is derived from the gas adjuster),
+/// and adjusts batch latency within `[fee_aware_seal_min_latency_ms, fee_aware_seal_max_latency_ms]`:
+///
+/// - If L1 is cheap, the batch is sealed as soon as it reaches the min latency.
+/// - If the overhead per transaction is high, the batch is held open past `block_commit_deadline_ms`
+///   so that the overhead is amortized over more transactions.
+/// - The batch is always sealed once it reaches the max latency.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct FeeAwareSealer {
+    batch_overhead_l1_gas: u64,
+    min_latency_ms: u64,
+    max_latency_ms: u64,
+    cheap_l1_gas_price: Option<u64>,
+    max_overhead_per_tx: Option<u64>,
+}
+
+impl FeeAwareSealer {
+    /// Returns `Ok(None)` if fee-aware sealing is disabled in the provided config, or an error
+    /// if the configured min latency exceeds the max one.
+    pub fn new(config: &StateKeeperConfig) -> anyhow::Result<Option<Self>> {
+        let Some(max_latency_ms) = config.fee_aware_seal_max_latency_ms else {
+            return Ok(None);
+        };
+        let min_latency_ms = config.fee_aware_seal_min_latency_ms;
+        anyhow::ensure!(
+            min_latency_ms <= max_latency_ms,
+            "invalid fee-aware sealing config: `fee_aware_seal_min_latency_ms` ({min_latency_ms}) \
+             exceeds `fee_aware_seal_max_latency_ms` ({max_latency_ms})"
+        );
+        Ok(Some(Self {
+            batch_overhead_l1_gas: config.batch_overhead_l1_gas,
+            min_latency_ms,
+            max_latency_ms,
+            cheap_l1_gas_price: config.fee_aware_seal_cheap_l1_gas_price,
+            max_overhead_per_tx: config.fee_aware_seal_max_overhead_per_tx,
+        }))
+    }
+
+    /// Estimated L1 overhead of the batch per transaction, in wei.
+    fn overhead_per_tx(&self, l1_gas_price: u64, tx_count: usize) -> u128 {
+        let batch_overhead = u128::from(self.batch_overhead_l1_gas) * u128::from(l1_gas_price);
+        batch_overhead / tx_count as u128
+    }
+
+    fn resolve_inner(
+        &self,
+        batch_age_ms: u64,
+        l1_gas_price: u64,
+        tx_count: usize,
+    ) -> (FeeAwareResolution, &'static str) {
+        if tx_count == 0 {
+            // Regardless of which sealers are provided, we never want to seal an empty batch.
+            return (FeeAwareResolution::Defer, "");
+        }
+        if batch_age_ms >= self.max_latency_ms {
+            return (FeeAwareResolution::Seal, "fee_aware_max_latency");
+        }
+
+        let is_l1_cheap = self
+            .cheap_l1_gas_price
+            .is_some_and(|cheap_price| l1_gas_price <= cheap_price);
+        if is_l1_cheap && batch_age_ms >= self.min_latency_ms {
+            return (FeeAwareResolution::Seal, "fee_aware_cheap_l1");
+        }
+
+        let overhead_per_tx = self.overhead_per_tx(l1_gas_price, tx_count);
+        let is_overhead_high = self
+            .max_overhead_per_tx
+            .is_some_and(|max_overhead| overhead_per_tx > u128::from(max_overhead));
+        if is_overhead_high {
+            return (FeeAwareResolution::Hold, "fee_aware_high_overhead");
+        }
+        (FeeAwareResolution::Defer, "")
+    }
+
+    pub fn resolve(&self, manager: &UpdatesManager) -> FeeAwareResolution {
+        let batch_age_ms = millis_since(manager.batch_timestamp());
+        let l1_gas_price = manager.batch_fee_input.l1_gas_price();
+        let tx_count = manager.pending_executed_transactions_len();
+        let (resolution, rule_name) = self.resolve_inner(batch_age_ms, l1_gas_price, tx_count);
+
+        match resolution {
+            FeeAwareResolution::Seal => {
+                AGGREGATION_METRICS.l1_batch_reason_inc_criterion(rule_name);
+                tracing::debug!(
+                    "Decided to seal L1 batch using rule `{rule_name}`; batch timestamp: {}, \
+                     L1 gas price: {l1_gas_price}, tx count: {tx_count}",
+                    display_timestamp(manager.batch_timestamp())
+                );
+            }
+            FeeAwareResolution::Hold => {
+                tracing::trace!(
+                    "Holding L1 batch open using rule `{rule_name}`; batch timestamp: {}, \
+                     L1 gas price: {l1_gas_price}, tx count: {tx_count}",
+                    display_timestamp(manager.batch_timestamp())
+                );
+            }
+            FeeAwareResolution::Defer => { /* do nothing */ }
+        }
+        resolution
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(super) struct L2BlockMaxPayloadSizeSealer {
     max_payload_size: usize,
@@ -319,6 +639,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fee_aware_sealer() {
+        let sealer = FeeAwareSealer {
+            batch_overhead_l1_gas: 1_000_000,
+            min_latency_ms: 1_000,
+            max_latency_ms: 10_000,
+            cheap_l1_gas_price: Some(1_000_000_000),
+            max_overhead_per_tx: Some(100_000_000_000_000), // 10^-4 ETH
+        };
+
+        // Empty batches are never sealed.
+        assert_eq!(
+            sealer.resolve_inner(20_000, 1, 0).0,
+            FeeAwareResolution::Defer
+        );
+        // Max latency is always respected.
+        assert_eq!(
+            sealer.resolve_inner(10_000, 100_000_000_000, 1).0,
+            FeeAwareResolution::Seal
+        );
+
+        // Cheap L1: seal once the batch has reached the min latency.
+        assert_eq!(
+            sealer.resolve_inner(500, 1_000_000_000, 1).0,
+            FeeAwareResolution::Defer
+        );
+        assert_eq!(
+            sealer.resolve_inner(1_000, 1_000_000_000, 1).0,
+            FeeAwareResolution::Seal
+        );
+
+        // Expensive L1: overhead per tx is `10^6 * 10^10 / tx_count` wei.
+        let l1_gas_price = 10_000_000_000;
+        assert_eq!(
+            sealer.resolve_inner(5_000, l1_gas_price, 10).0,
+            FeeAwareResolution::Hold
+        );
+        assert_eq!(
+            sealer.resolve_inner(5_000, l1_gas_price, 100).0,
+            FeeAwareResolution::Defer
+        );
+    }
+
+    #[test]
+    fn fee_aware_sealer_is_disabled_by_default() {
+        assert!(FeeAwareSealer::new(&StateKeeperConfig::for_tests())
+            .unwrap()
+            .is_none());
+
+        let config = StateKeeperConfig {
+            fee_aware_seal_max_latency_ms: Some(10_000),
+            ..StateKeeperConfig::for_tests()
+        };
+        let sealer = FeeAwareSealer::new(&config).unwrap().unwrap();
+        // Without thresholds, the sealer only enforces the max latency.
+        assert_eq!(
+            sealer.resolve_inner(5_000, 1, 1).0,
+            FeeAwareResolution::Defer
+        );
+        assert_eq!(
+            sealer.resolve_inner(5_000, u64::MAX, 1).0,
+            FeeAwareResolution::Defer
+        );
+        assert_eq!(
+            sealer.resolve_inner(10_000, 1, 1).0,
+            FeeAwareResolution::Seal
+        );
+    }
+
+    #[test]
+    fn fee_aware_sealer_with_invalid_latencies() {
+        let config = StateKeeperConfig {
+            fee_aware_seal_min_latency_ms: 20_000,
+            fee_aware_seal_max_latency_ms: Some(10_000),
+            ..StateKeeperConfig::for_tests()
+        };
+        let err = FeeAwareSealer::new(&config).unwrap_err().to_string();
+        assert!(err.contains("fee_aware_seal_min_latency_ms"), "{err}");
+    }
+
     #[test]
     fn max_size_l2_block_sealer() {
         let tx = create_transaction(10, 100);
@@ -4,8 +4,10 @@ use std::{
 };
 
 use zksync_dal::{Connection, Core, CoreDal};
-use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolStore};
-use zksync_types::{Address, Nonce, PriorityOpId, Transaction, TransactionTimeRangeConstraint};
+use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolStore, TransactionPriority};
+use zksync_types::{
+    l2::L2Tx, Address, Nonce, PriorityOpId, Transaction, TransactionTimeRangeConstraint,
+};
 
 use super::metrics::StateKeeperGauges;
 
@@ -69,6 +71,18 @@ impl MempoolGuard {
             .next_transaction(filter)
     }
 
+    pub fn next_transaction_with_priority(
+        &mut self,
+        filter: &L2TxFilter,
+        priority: impl Fn(&L2Tx) -> TransactionPriority,
+        max_priority: TransactionPriority,
+    ) -> Option<(Transaction, TransactionTimeRangeConstraint)> {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .next_transaction_with_priority(filter, priority, max_priority)
+    }
+
     pub fn rollback(&mut self, rejected: &Transaction) -> TransactionTimeRangeConstraint {
         self.0
             .lock()
@@ -83,6 +97,13 @@ impl MempoolGuard {
             .get_mempool_info()
     }
 
+    pub fn nonce_gap_accounts(&self) -> Vec<Address> {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .nonce_gap_accounts()
+    }
+
     #[cfg(test)]
     pub fn stats(&self) -> zksync_mempool::MempoolStats {
         self.0
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_multivm::{
     interface::{
@@ -15,6 +17,7 @@ pub(crate) use self::{l1_batch_updates::L1BatchUpdates, l2_block_updates::L2Bloc
 use super::{
     io::{IoCursor, L2BlockParams},
     metrics::{BATCH_TIP_METRICS, UPDATES_MANAGER_METRICS},
+    seal_criteria::{ResourceUsage, TransactionQuotas, UsageByQuota},
 };
 
 pub mod l1_batch_updates;
@@ -39,6 +42,8 @@ pub struct UpdatesManager {
     pub l2_block: L2BlockUpdates,
     pub storage_writes_deduplicator: StorageWritesDeduplicator,
     pubdata_params: PubdataParams,
+    transaction_quotas: Option<Arc<TransactionQuotas>>,
+    usage_by_quota: UsageByQuota,
 }
 
 impl UpdatesManager {
@@ -66,9 +71,20 @@ impl UpdatesManager {
             storage_writes_deduplicator: StorageWritesDeduplicator::new(),
             storage_view_cache: None,
             pubdata_params,
+            transaction_quotas: None,
+            usage_by_quota: UsageByQuota::default(),
         }
     }
 
+    /// Enables attributing resource usage of executed transactions to the specified transaction quotas.
+    pub(crate) fn with_transaction_quotas(
+        mut self,
+        transaction_quotas: Option<Arc<TransactionQuotas>>,
+    ) -> Self {
+        self.transaction_quotas = transaction_quotas;
+        self
+    }
+
     pub(crate) fn batch_timestamp(&self) -> u64 {
         self.batch_timestamp
     }
@@ -90,6 +106,8 @@ impl UpdatesManager {
         &self,
         l2_legacy_shared_bridge_addr: Option<Address>,
         pre_insert_txs: bool,
+        insert_address_index: bool,
+        insert_token_transfers: bool,
     ) -> L2BlockSealCommand {
         L2BlockSealCommand {
             l1_batch_number: self.l1_batch.number,
@@ -102,6 +120,8 @@ impl UpdatesManager {
             protocol_version: Some(self.protocol_version),
             l2_legacy_shared_bridge_addr,
             pre_insert_txs,
+            insert_address_index,
+            insert_token_transfers,
             pubdata_params: self.pubdata_params,
         }
     }
@@ -124,6 +144,7 @@ impl UpdatesManager {
             .start();
         self.storage_writes_deduplicator
             .apply(&tx_execution_result.logs.storage_logs);
+        self.usage_by_quota += &self.transaction_usage_by_quota(&tx, &execution_metrics);
         self.l2_block.extend_from_executed_transaction(
             tx,
             tx_execution_result,
@@ -192,6 +213,24 @@ impl UpdatesManager {
         self.l1_batch.block_execution_metrics + self.l2_block.block_execution_metrics
     }
 
+    /// Returns resource usage of the pending L1 batch attributed to transaction quotas.
+    pub(crate) fn pending_usage_by_quota(&self) -> &UsageByQuota {
+        &self.usage_by_quota
+    }
+
+    /// Attributes resource usage of a transaction to transaction quotas. Returns empty usage without classifying
+    /// the transaction if no quotas are configured.
+    pub(crate) fn transaction_usage_by_quota(
+        &self,
+        tx: &Transaction,
+        execution_metrics: &VmExecutionMetrics,
+    ) -> UsageByQuota {
+        match &self.transaction_quotas {
+            Some(quotas) => quotas.transaction_usage(tx, ResourceUsage::new(execution_metrics)),
+            None => UsageByQuota::default(),
+        }
+    }
+
     pub(crate) fn pending_txs_encoding_size(&self) -> usize {
         self.l1_batch.txs_encoding_size + self.l2_block.txs_encoding_size
     }
@@ -213,6 +252,10 @@ pub struct L2BlockSealCommand {
     /// Should be set to `true` for EN's IO as EN doesn't store transactions in DB
     /// before they are included into L2 blocks.
     pub pre_insert_txs: bool,
+    /// Whether executed transactions should be added to the index of transactions by address.
+    pub insert_address_index: bool,
+    /// Whether token transfers among emitted events should be added to the token transfer index.
+    pub insert_token_transfers: bool,
     pub pubdata_params: PubdataParams,
 }
 
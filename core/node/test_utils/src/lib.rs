@@ -17,6 +17,7 @@ use zksync_types::{
     l2::L2Tx,
     l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
     protocol_version::ProtocolSemanticVersion,
+    pruning::PruningProfile,
     snapshots::{SnapshotRecoveryStatus, SnapshotStorageLog},
     transaction_request::PaymasterParams,
     Address, K256PrivateKey, L1BatchNumber, L2BlockNumber, L2ChainId, Nonce, ProtocolVersion,
@@ -382,7 +383,11 @@ pub async fn recover(
 
     storage
         .pruning_dal()
-        .insert_soft_pruning_log(snapshot.l1_batch.number, snapshot.l2_block.number)
+        .insert_soft_pruning_log(
+            snapshot.l1_batch.number,
+            snapshot.l2_block.number,
+            PruningProfile::Full,
+        )
         .await
         .unwrap();
     storage
@@ -391,6 +396,7 @@ pub async fn recover(
             snapshot.l1_batch.number,
             snapshot.l2_block.number,
             snapshot_recovery.l1_batch_root_hash,
+            PruningProfile::Full,
         )
         .await
         .unwrap();